@@ -1,5 +1,8 @@
 pub use crate::data::dataset::{Dataset, DatasetIterator};
+use burn_tensor::backend::Backend;
 use core::iter::Iterator;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// A progress struct that can be used to track the progress of a data loader.
 #[derive(new, Clone, Debug)]
@@ -11,34 +14,65 @@ pub struct Progress {
     pub items_total: usize,
 }
 
+/// The position of a [data loader iterator](DataLoaderIterator), which can be used to resume the
+/// iteration exactly where it stopped.
+#[derive(new, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataLoaderState {
+    /// The number of iterators created by the data loader before the current one.
+    ///
+    /// Each new iterator advances the shuffling random number generator, so this is required to
+    /// reproduce the same order of items.
+    pub iteration: usize,
+
+    /// The number of items already processed by each worker of the data loader.
+    pub items_processed: Vec<usize>,
+}
+
 /// A data loader iterator that can be used to iterate over a data loader.
 pub trait DataLoaderIterator<O>: Iterator<Item = O> {
     /// Returns the progress of the data loader.
     fn progress(&self) -> Progress;
+
+    /// Returns the current state of the iterator, which can be given to
+    /// [iter_from](DataLoader::iter_from) to resume the iteration.
+    ///
+    /// The default implementation only tracks the number of processed items.
+    fn state(&self) -> DataLoaderState {
+        DataLoaderState::new(0, vec![self.progress().items_processed])
+    }
 }
 
 /// A data loader that can be used to iterate over a dataset.
-pub trait DataLoader<O>: Send {
+///
+/// The batches are created on the device of the data loader, which can be changed with
+/// [to_device](DataLoader::to_device).
+pub trait DataLoader<B: Backend, O>: Send + Sync {
     /// Returns a boxed [iterator](DataLoaderIterator) to iterate over the data loader.
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a>;
-    /// The number of items (not the number of batches nor the number of iterations),
-    /// corresponding to the items_total of the progress returned by the iterator.
-    fn num_items(&self) -> usize;
-}
+    /// Returns a boxed [iterator](DataLoaderIterator) resuming from the given
+    /// [state](DataLoaderState).
+    ///
+    /// The default implementation creates a new iterator and skips batches until the number of
+    /// processed items is reached, which ignores the shuffling order. Data loaders should override
+    /// it to provide an exact resumption.
+    fn iter_from<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        let items_processed = state.items_processed.iter().sum::<usize>();
+        let mut iterator = self.iter();
 
-/// A super trait for [dataloader](DataLoader) that allows it to be cloned dynamically.
-///
-/// Any dataloader that implements [Clone] should also implement this automatically.
-pub trait DynDataLoader<O>: DataLoader<O> {
-    /// Clone the dataloader and returns a new one.
-    fn clone_dyn(&self) -> Box<dyn DynDataLoader<O>>;
-}
+        while iterator.progress().items_processed < items_processed {
+            if iterator.next().is_none() {
+                break;
+            }
+        }
 
-impl<D, O> DynDataLoader<O> for D
-where
-    D: DataLoader<O> + Clone + 'static,
-{
-    fn clone_dyn(&self) -> Box<dyn DynDataLoader<O>> {
-        Box::new(self.clone())
+        iterator
     }
+    /// The number of items (not the number of batches nor the number of iterations),
+    /// corresponding to the items_total of the progress returned by the iterator.
+    fn num_items(&self) -> usize;
+    /// Returns a new data loader creating the batches on the given device.
+    fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>>;
+    /// Splits the data loader into `num` data loaders iterating over distinct parts of the items,
+    /// e.g. to give each device its own data loader.
+    fn split(&self, num: usize) -> Vec<Arc<dyn DataLoader<B, O>>>;
 }
@@ -1,34 +1,53 @@
 use super::{
-    batcher::DynBatcher, BatchStrategy, DataLoader, DataLoaderIterator, DynDataLoader,
+    batcher::DynBatcher, BatchStrategy, DataLoader, DataLoaderIterator, DataLoaderState,
     MultiThreadDataLoader, Progress,
 };
 use burn_dataset::{
-    transform::{PartialDataset, ShuffledDataset},
+    transform::{PartialDataset, ShardRemainder, ShardedDataset, ShuffledDataset},
     Dataset,
 };
-use rand::{distributions::Standard, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+use burn_tensor::backend::Backend;
+use rand::{distributions::Standard, Rng};
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// A data loader that can be used to iterate over a dataset in batches.
-pub struct BatchDataLoader<I, O> {
+pub struct BatchDataLoader<B: Backend, I, O> {
     strategy: Box<dyn BatchStrategy<I>>,
     dataset: Arc<dyn Dataset<I>>,
-    batcher: Box<dyn DynBatcher<I, O>>,
-    rng: Option<Arc<spin::Mutex<rand::rngs::StdRng>>>,
+    batcher: Box<dyn DynBatcher<B, I, O>>,
+    device: B::Device,
+    rng: Option<rand::rngs::StdRng>,
+    shard: Option<Shard>,
+    range: Option<Range<usize>>,
+    iteration: Arc<AtomicUsize>,
 }
 
-impl<I, O> Clone for BatchDataLoader<I, O> {
+/// The shard of the dataset used by a data loader.
+#[derive(Clone, Debug)]
+struct Shard {
+    rank: usize,
+    world_size: usize,
+    remainder: ShardRemainder,
+}
+
+impl<B: Backend, I, O> Clone for BatchDataLoader<B, I, O> {
     fn clone(&self) -> Self {
         Self {
             strategy: self.strategy.clone_dyn(),
             dataset: self.dataset.clone(),
             batcher: self.batcher.clone_dyn(),
+            device: self.device.clone(),
             rng: self.rng.clone(),
+            shard: self.shard.clone(),
+            range: self.range.clone(),
+            iteration: self.iteration.clone(),
         }
     }
 }
 
-impl<I, O> BatchDataLoader<I, O> {
+impl<B: Backend, I, O> BatchDataLoader<B, I, O> {
     /// Creates a new batch data loader.
     ///
     /// # Arguments
@@ -36,6 +55,7 @@ impl<I, O> BatchDataLoader<I, O> {
     /// * `strategy` - The batch strategy.
     /// * `dataset` - The dataset.
     /// * `batcher` - The batcher.
+    /// * `device`  - The device on which the batches are created.
     /// * `rng`     - The rng determining if the dataset is shuffled each time a dataloader
     ///               iterator is created.
     ///
@@ -45,30 +65,124 @@ impl<I, O> BatchDataLoader<I, O> {
     pub fn new(
         strategy: Box<dyn BatchStrategy<I>>,
         dataset: Arc<dyn Dataset<I>>,
-        batcher: Box<dyn DynBatcher<I, O>>,
+        batcher: Box<dyn DynBatcher<B, I, O>>,
+        device: B::Device,
         rng: Option<rand::rngs::StdRng>,
     ) -> Self {
         Self {
             strategy,
             dataset,
             batcher,
-            rng: rng.map(|rng| Arc::new(spin::Mutex::new(rng))),
+            device,
+            rng,
+            shard: None,
+            range: None,
+            iteration: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Only iterate over one shard of the dataset, e.g. when each process of a distributed
+    /// training has its own data loader.
+    ///
+    /// The dataset is shuffled before being sharded, so every rank must use the same rng for the
+    /// shards not to overlap. Each iteration reshuffles the whole dataset with a seed derived from
+    /// the rng and the number of iterations, so the items move between the shards at every epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - The index of the shard.
+    /// * `world_size` - The number of shards.
+    /// * `remainder` - How the items that can't be evenly divided between the shards are handled.
+    ///
+    /// # Returns
+    ///
+    /// The batch data loader.
+    pub fn with_shard(mut self, rank: usize, world_size: usize, remainder: ShardRemainder) -> Self {
+        assert!(
+            rank < world_size,
+            "The rank {rank} should be smaller than the world size {world_size}"
+        );
+        self.shard = Some(Shard {
+            rank,
+            world_size,
+            remainder,
+        });
+        self
+    }
+
+    /// Returns a copy of the data loader with its own iterations, creating the batches on the
+    /// given device.
+    fn copy(&self, device: &B::Device, range: Option<Range<usize>>) -> Self {
+        Self {
+            device: device.clone(),
+            range,
+            iteration: Arc::new(AtomicUsize::new(0)),
+            ..self.clone()
+        }
+    }
+}
+
+impl<B, I, O> BatchDataLoader<B, I, O>
+where
+    B: Backend,
+    I: Send + Sync + Clone + 'static,
+{
+    /// Returns the items of the given iteration: the dataset is shuffled, then sharded, then
+    /// restricted to the range of the data loader.
+    fn dataset(&self, iteration: usize) -> Arc<dyn Dataset<I>> {
+        let dataset = match &self.rng {
+            // The rng is advanced once for each iterator created before this one, so that each
+            // iteration shuffles the dataset differently while being reproducible.
+            Some(rng) => {
+                let mut rng = rng.clone();
+                for _ in 0..iteration {
+                    let _seed: u64 = rng.sample(Standard);
+                }
+
+                Arc::new(ShuffledDataset::with_seed(
+                    self.dataset.clone(),
+                    rng.sample(Standard),
+                ))
+            }
+            None => self.dataset.clone(),
+        };
+
+        self.partition(dataset)
+    }
+
+    fn partition(&self, dataset: Arc<dyn Dataset<I>>) -> Arc<dyn Dataset<I>> {
+        let dataset: Arc<dyn Dataset<I>> = match &self.shard {
+            Some(shard) => Arc::new(ShardedDataset::new(
+                dataset,
+                shard.rank,
+                shard.world_size,
+                shard.remainder,
+            )),
+            None => dataset,
+        };
+
+        match &self.range {
+            Some(range) => Arc::new(PartialDataset::new(dataset, range.start, range.end)),
+            None => dataset,
         }
     }
 }
 
 /// A data loader iterator that can be used to iterate over a data loader.
-struct BatchDataloaderIterator<I, O> {
+struct BatchDataloaderIterator<B: Backend, I, O> {
+    iteration: usize,
     current_index: usize,
     strategy: Box<dyn BatchStrategy<I>>,
     dataset: Arc<dyn Dataset<I>>,
-    batcher: Box<dyn DynBatcher<I, O>>,
+    batcher: Box<dyn DynBatcher<B, I, O>>,
+    device: B::Device,
 }
 
-impl<I, O> BatchDataLoader<I, O>
+impl<B, I, O> BatchDataLoader<B, I, O>
 where
+    B: Backend,
     I: Send + Sync + Clone + 'static,
-    O: Send + Clone + 'static,
+    O: Send + 'static,
 {
     /// Creates a new multi-threaded batch data loader.
     ///
@@ -78,6 +192,7 @@ where
     /// * `dataset` - The dataset.
     /// * `batcher` - The batcher.
     /// * `num_threads` - The number of threads.
+    /// * `device` - The device on which the batches are created.
     ///
     /// # Returns
     ///
@@ -85,64 +200,109 @@ where
     pub fn multi_thread(
         strategy: Box<dyn BatchStrategy<I>>,
         dataset: Arc<dyn Dataset<I>>,
-        batcher: Box<dyn DynBatcher<I, O>>,
+        batcher: Box<dyn DynBatcher<B, I, O>>,
         num_threads: usize,
-        mut rng: Option<rand::rngs::StdRng>,
-    ) -> MultiThreadDataLoader<O> {
-        let datasets = PartialDataset::split(dataset, num_threads);
-
-        let mut dataloaders = Vec::with_capacity(num_threads);
-
-        // Create more rngs from the first one, one for each new dataloader.
-        let rngs = (0..num_threads).map(|_| {
-            rng.as_mut()
-                .map(|rng| StdRng::seed_from_u64(Distribution::sample(&Standard, rng)))
-        });
+        device: B::Device,
+        rng: Option<rand::rngs::StdRng>,
+    ) -> MultiThreadDataLoader<B, O> {
+        BatchDataLoader::new(strategy, dataset, batcher, device, rng).into_multi_thread(num_threads)
+    }
 
-        for (dataset, rng) in datasets.into_iter().zip(rngs) {
-            let strategy = strategy.clone_dyn();
-            let dataloader =
-                BatchDataLoader::new(strategy, Arc::new(dataset), batcher.clone_dyn(), rng);
-            let dataloader: Box<dyn DynDataLoader<_>> = Box::new(dataloader);
-            dataloaders.push(dataloader);
-        }
-        MultiThreadDataLoader::new(dataloaders)
+    /// Splits the data loader between multiple threads.
+    ///
+    /// The items are shuffled and sharded before being split, so each thread processes a distinct
+    /// part of the items of the data loader.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_threads` - The number of threads.
+    ///
+    /// # Returns
+    ///
+    /// The multi-threaded batch data loader.
+    pub fn into_multi_thread(self, num_threads: usize) -> MultiThreadDataLoader<B, O> {
+        MultiThreadDataLoader::new(self.split(num_threads))
     }
 }
 
-impl<I, O> DataLoader<O> for BatchDataLoader<I, O>
+impl<B, I, O> DataLoader<B, O> for BatchDataLoader<B, I, O>
 where
+    B: Backend,
     I: Send + Sync + Clone + 'static,
     O: Send + 'static,
 {
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
-        // When starting a new iteration, we first check if the dataloader was created with an rng,
-        // implying that we should shuffle the dataset beforehand, while advancing the current
-        // rng to ensure that each new iteration shuffles the dataset differently.
-        let dataset = match &self.rng {
-            Some(rng) => {
-                let mut rng = rng.lock();
+        let iteration = self.iteration.fetch_add(1, Ordering::Relaxed);
 
-                Arc::new(ShuffledDataset::with_seed(
-                    self.dataset.clone(),
-                    rng.sample(Standard),
-                ))
+        Box::new(BatchDataloaderIterator::new(
+            self.strategy.clone_iteration(iteration),
+            self.dataset(iteration),
+            self.batcher.clone_dyn(),
+            self.device.clone(),
+            iteration,
+            0,
+        ))
+    }
+
+    fn iter_from<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        self.iteration.store(state.iteration + 1, Ordering::Relaxed);
+
+        let current_index = state.items_processed.first().copied().unwrap_or(0);
+        let mut strategy = self.strategy.clone_iteration(state.iteration);
+        let dataset = self.dataset(state.iteration);
+
+        // The items already processed are added again, the same way as the iterator does, to
+        // restore the items waiting in the strategy and the random numbers it drew.
+        if strategy.is_stateful() {
+            for index in 0..current_index {
+                if let Some(item) = dataset.get_for_epoch(index, state.iteration) {
+                    strategy.add(item);
+                    let _batch = strategy.batch(false);
+                }
             }
-            None => self.dataset.clone(),
-        };
+        }
+
         Box::new(BatchDataloaderIterator::new(
-            self.strategy.clone_dyn(),
+            strategy,
             dataset,
             self.batcher.clone_dyn(),
+            self.device.clone(),
+            state.iteration,
+            current_index,
         ))
     }
 
     fn num_items(&self) -> usize {
-        self.dataset.len()
+        self.partition(self.dataset.clone()).len()
+    }
+
+    fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>> {
+        Arc::new(self.copy(device, self.range.clone()))
+    }
+
+    fn split(&self, num: usize) -> Vec<Arc<dyn DataLoader<B, O>>> {
+        // The ranges are relative to the current one, which always has the same length since it
+        // only depends on the length of the dataset.
+        let offset = self.range.as_ref().map(|range| range.start).unwrap_or(0);
+        let num_items = self.num_items();
+        let size = num_items / num;
+
+        (0..num)
+            .map(|index| {
+                let start = offset + index * size;
+                let end = match index == num - 1 {
+                    true => offset + num_items,
+                    false => start + size,
+                };
+                let dataloader: Arc<dyn DataLoader<B, O>> =
+                    Arc::new(self.copy(&self.device, Some(start..end)));
+                dataloader
+            })
+            .collect()
     }
 }
 
-impl<I, O> BatchDataloaderIterator<I, O> {
+impl<B: Backend, I, O> BatchDataloaderIterator<B, I, O> {
     /// Creates a new batch data loader iterator.
     ///
     /// # Arguments
@@ -150,6 +310,10 @@ impl<I, O> BatchDataloaderIterator<I, O> {
     /// * `strategy` - The batch strategy.
     /// * `dataset` - The dataset.
     /// * `batcher` - The batcher.
+    /// * `device` - The device on which the batches are created.
+    /// * `iteration` - The number of iterators created by the data loader before this one, which
+    ///   is the [epoch](Dataset::get_for_epoch) the items are read for.
+    /// * `current_index` - The index of the first item to process.
     ///
     /// # Returns
     ///
@@ -157,42 +321,54 @@ impl<I, O> BatchDataloaderIterator<I, O> {
     pub fn new(
         strategy: Box<dyn BatchStrategy<I>>,
         dataset: Arc<dyn Dataset<I>>,
-        batcher: Box<dyn DynBatcher<I, O>>,
+        batcher: Box<dyn DynBatcher<B, I, O>>,
+        device: B::Device,
+        iteration: usize,
+        current_index: usize,
     ) -> Self {
         BatchDataloaderIterator {
-            current_index: 0,
+            iteration,
+            current_index,
             strategy,
             dataset,
             batcher,
+            device,
         }
     }
 }
 
-impl<I, O> Iterator for BatchDataloaderIterator<I, O> {
+impl<B: Backend, I, O> Iterator for BatchDataloaderIterator<B, I, O> {
     type Item = O;
 
     fn next(&mut self) -> Option<O> {
-        while let Some(item) = self.dataset.get(self.current_index) {
+        while let Some(item) = self
+            .dataset
+            .get_for_epoch(self.current_index, self.iteration)
+        {
             self.current_index += 1;
             self.strategy.add(item);
 
             if let Some(items) = self.strategy.batch(false) {
-                return Some(self.batcher.batch(items));
+                return Some(self.batcher.batch(items, &self.device));
             }
         }
 
         if let Some(items) = self.strategy.batch(true) {
-            return Some(self.batcher.batch(items));
+            return Some(self.batcher.batch(items, &self.device));
         }
 
         None
     }
 }
 
-impl<I, O> DataLoaderIterator<O> for BatchDataloaderIterator<I, O> {
+impl<B: Backend, I, O> DataLoaderIterator<O> for BatchDataloaderIterator<B, I, O> {
     fn progress(&self) -> Progress {
         Progress::new(self.current_index, self.dataset.len())
     }
+
+    fn state(&self) -> DataLoaderState {
+        DataLoaderState::new(self.iteration, vec![self.current_index])
+    }
 }
 
 #[cfg(test)]
@@ -201,17 +377,20 @@ mod tests {
 
     use super::*;
     use crate::data::dataloader::batcher::TestBatcher;
-    use crate::data::dataloader::FixBatchStrategy;
-    use crate::data::dataset::FakeDataset;
+    use crate::data::dataloader::{BucketBatchStrategy, FixBatchStrategy};
+    use crate::data::dataset::{FakeDataset, InMemDataset};
+    use crate::TestBackend;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_batch_dataloader() {
         let batcher = Box::new(TestBatcher::new());
         let dataset = Arc::new(FakeDataset::<String>::new(27));
-        let dataloader = BatchDataLoader::new(
+        let dataloader = BatchDataLoader::<TestBackend, _, _>::new(
             Box::new(FixBatchStrategy::new(5)),
             dataset.clone(),
             batcher,
+            Default::default(),
             None,
         );
 
@@ -235,17 +414,19 @@ mod tests {
     fn test_multi_thread_batch_dataloader() {
         let batcher = Box::new(TestBatcher::new());
         let dataset = Arc::new(FakeDataset::<String>::new(27));
-        let dataloader_single_thread = BatchDataLoader::new(
+        let dataloader_single_thread = BatchDataLoader::<TestBackend, _, _>::new(
             Box::new(FixBatchStrategy::new(5)),
             dataset.clone(),
-            batcher.clone_dyn(),
+            batcher.clone(),
+            Default::default(),
             None,
         );
-        let dataloader_multi_thread = BatchDataLoader::multi_thread(
+        let dataloader_multi_thread = BatchDataLoader::<TestBackend, _, _>::multi_thread(
             Box::new(FixBatchStrategy::new(5)),
             dataset,
             batcher,
             4,
+            Default::default(),
             None,
         );
 
@@ -266,4 +447,273 @@ mod tests {
 
         assert_eq!(items_single_thread, items_multi_thread);
     }
+
+    #[test]
+    fn test_batch_dataloader_resume_from_state() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let new_dataloader = || {
+            BatchDataLoader::<TestBackend, _, _>::new(
+                Box::new(FixBatchStrategy::new(5)),
+                dataset.clone(),
+                Box::new(TestBatcher::new()),
+                Default::default(),
+                Some(StdRng::seed_from_u64(42)),
+            )
+        };
+
+        let dataloader = new_dataloader();
+        let _first_epoch = dataloader.iter().collect::<Vec<_>>();
+        let mut iterator = dataloader.iter();
+        iterator.next();
+        iterator.next();
+        let state = iterator.state();
+        let expected = iterator.collect::<Vec<_>>();
+
+        let dataloader = new_dataloader();
+        let actual = dataloader.iter_from(&state).collect::<Vec<_>>();
+
+        assert_eq!(state, DataLoaderState::new(1, vec![10]));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_multi_thread_batch_dataloader_resume_from_state() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let new_dataloader = || {
+            BatchDataLoader::<TestBackend, _, _>::multi_thread(
+                Box::new(FixBatchStrategy::new(5)),
+                dataset.clone(),
+                Box::new(TestBatcher::new()),
+                2,
+                Default::default(),
+                Some(StdRng::seed_from_u64(42)),
+            )
+        };
+
+        let dataloader = new_dataloader();
+        let mut iterator = dataloader.iter();
+        let mut items_processed = HashSet::new();
+        for item in iterator.next().unwrap() {
+            items_processed.insert(item);
+        }
+        let state = iterator.state();
+        let mut items_expected = HashSet::new();
+        for items in iterator {
+            items_expected.extend(items);
+        }
+
+        let dataloader = new_dataloader();
+        let mut items_resumed = HashSet::new();
+        for items in dataloader.iter_from(&state) {
+            items_resumed.extend(items);
+        }
+
+        assert_eq!(items_expected, items_resumed);
+        assert!(items_processed.is_disjoint(&items_resumed));
+    }
+
+    fn bucket_dataloader() -> BatchDataLoader<TestBackend, String, Vec<String>> {
+        let items = (0..64).map(|i| "a".repeat(i % 7 + 1)).collect();
+
+        BatchDataLoader::new(
+            Box::new(
+                BucketBatchStrategy::new(|item: &String| item.len(), vec![2, 4, 6], 3)
+                    .with_shuffle(7),
+            ),
+            Arc::new(InMemDataset::new(items)),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        )
+    }
+
+    #[test]
+    fn test_bucket_batch_dataloader_should_not_depend_on_threads() {
+        let epochs = |dataloader: Arc<dyn DataLoader<TestBackend, Vec<String>>>| {
+            (0..3)
+                .map(|_| dataloader.iter().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        let expected = bucket_dataloader()
+            .split(4)
+            .into_iter()
+            .map(epochs)
+            .collect::<Vec<_>>();
+        let actual = std::thread::scope(|scope| {
+            let handles = bucket_dataloader()
+                .split(4)
+                .into_iter()
+                .map(|dataloader| scope.spawn(move || epochs(dataloader)))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(expected, actual);
+        assert_ne!(expected[0][0], expected[0][1]);
+    }
+
+    #[test]
+    fn test_bucket_batch_dataloader_resume_from_state() {
+        let dataloader = bucket_dataloader();
+        let _first_epoch = dataloader.iter().collect::<Vec<_>>();
+        let mut iterator = dataloader.iter();
+        for _ in 0..5 {
+            iterator.next();
+        }
+        let state = iterator.state();
+        let expected = iterator.collect::<Vec<_>>();
+
+        let actual = bucket_dataloader().iter_from(&state).collect::<Vec<_>>();
+
+        assert_eq!(state.iteration, 1);
+        assert!(!expected.is_empty());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_split_batch_dataloader() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let dataloader = BatchDataLoader::<TestBackend, _, _>::new(
+            Box::new(FixBatchStrategy::new(5)),
+            dataset.clone(),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        );
+
+        let dataloaders = dataloader.split(3);
+        let mut items_split = HashSet::new();
+        for dataloader in dataloaders.iter() {
+            assert_eq!(dataloader.num_items(), 9);
+            for items in dataloader.iter() {
+                items_split.extend(items);
+            }
+        }
+
+        assert_eq!(items_split, dataset.iter().collect::<HashSet<_>>());
+    }
+
+    fn sharded_dataloader(
+        dataset: &Arc<FakeDataset<String>>,
+        rank: usize,
+        remainder: ShardRemainder,
+    ) -> BatchDataLoader<TestBackend, String, Vec<String>> {
+        BatchDataLoader::new(
+            Box::new(FixBatchStrategy::new(5)),
+            dataset.clone(),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        )
+        .with_shard(rank, 3, remainder)
+    }
+
+    #[test]
+    fn test_sharded_batch_dataloader_should_not_overlap() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let dataloaders = (0..3)
+            .map(|rank| sharded_dataloader(&dataset, rank, ShardRemainder::Drop))
+            .collect::<Vec<_>>();
+
+        for _epoch in 0..2 {
+            let mut items = Vec::new();
+            for dataloader in dataloaders.iter() {
+                assert_eq!(dataloader.num_items(), 9);
+                items.extend(dataloader.iter().flatten());
+            }
+
+            assert_eq!(items.len(), 27);
+            assert_eq!(
+                items.into_iter().collect::<HashSet<_>>(),
+                dataset.iter().collect::<HashSet<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sharded_batch_dataloader_should_reshuffle_each_epoch() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let dataloader = sharded_dataloader(&dataset, 1, ShardRemainder::Drop);
+
+        let first_epoch = dataloader.iter().flatten().collect::<HashSet<_>>();
+        let second_epoch = dataloader.iter().flatten().collect::<HashSet<_>>();
+        let resumed = dataloader
+            .iter_from(&DataLoaderState::new(1, vec![0]))
+            .flatten()
+            .collect::<HashSet<_>>();
+
+        assert_ne!(first_epoch, second_epoch);
+        assert_eq!(second_epoch, resumed);
+    }
+
+    #[test]
+    fn test_sharded_multi_thread_batch_dataloader_should_pad_remainder() {
+        let dataset = Arc::new(FakeDataset::<String>::new(28));
+        let mut items = Vec::new();
+
+        for rank in 0..3 {
+            let dataloader =
+                sharded_dataloader(&dataset, rank, ShardRemainder::Pad).into_multi_thread(2);
+            assert_eq!(dataloader.num_items(), 10);
+            items.extend(dataloader.iter().flatten());
+        }
+
+        assert_eq!(items.len(), 30);
+        assert_eq!(
+            items.into_iter().collect::<HashSet<_>>(),
+            dataset.iter().collect::<HashSet<_>>()
+        );
+    }
+
+    /// The items are their index and the epoch they are read for.
+    struct EpochDataset;
+
+    impl Dataset<(usize, usize)> for EpochDataset {
+        fn get(&self, index: usize) -> Option<(usize, usize)> {
+            self.get_for_epoch(index, 0)
+        }
+
+        fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<(usize, usize)> {
+            (index < self.len()).then_some((index, epoch))
+        }
+
+        fn len(&self) -> usize {
+            6
+        }
+    }
+
+    #[test]
+    fn test_batch_dataloader_should_read_the_items_for_the_epoch() {
+        let dataloader = BatchDataLoader::<TestBackend, _, _>::new(
+            Box::new(FixBatchStrategy::new(4)),
+            Arc::new(EpochDataset),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        )
+        .into_multi_thread(2);
+        let epochs = |items: Vec<(usize, usize)>| {
+            items
+                .into_iter()
+                .map(|(_, epoch)| epoch)
+                .collect::<HashSet<_>>()
+        };
+
+        let first_epoch = dataloader.iter().flatten().collect::<Vec<_>>();
+        let second_epoch = dataloader.iter().flatten().collect::<Vec<_>>();
+        let resumed = dataloader
+            .iter_from(&DataLoaderState::new(1, vec![0, 0]))
+            .flatten()
+            .collect::<Vec<_>>();
+
+        assert_eq!(first_epoch.len(), 6);
+        assert_eq!(epochs(first_epoch), HashSet::from([0]));
+        assert_eq!(epochs(second_epoch), HashSet::from([1]));
+        assert_eq!(epochs(resumed), HashSet::from([1]));
+    }
 }
@@ -1,41 +1,89 @@
+use burn_tensor::backend::Backend;
+
 /// A trait for batching items of type `I` into items of type `O`.
-pub trait Batcher<I, O>: Send {
-    /// Batches the given items.
+///
+/// The device on which the batch is created is chosen by the [data loader](super::DataLoader),
+/// so the same batcher can be used for any device.
+pub trait Batcher<B: Backend, I, O>: Send + Sync {
+    /// Batches the given items on the given device.
     ///
     /// # Arguments
     ///
     /// * `items` - The items to batch.
+    /// * `device` - The device on which the batch is created.
     ///
     /// # Returns
     ///
     /// The batched items.
-    fn batch(&self, items: Vec<I>) -> O;
+    fn batch(&self, items: Vec<I>, device: &B::Device) -> O;
 }
 
 /// A super trait for [batcher](Batcher) that allows it to be cloned dynamically.
 ///
 /// Any batcher that implements [Clone] should also implement this automatically.
-pub trait DynBatcher<I, O>: Send + Batcher<I, O> {
+pub trait DynBatcher<B: Backend, I, O>: Send + Sync + Batcher<B, I, O> {
     /// Clone the batcher and returns a new one.
-    fn clone_dyn(&self) -> Box<dyn DynBatcher<I, O>>;
+    fn clone_dyn(&self) -> Box<dyn DynBatcher<B, I, O>>;
 }
 
-impl<B, I, O> DynBatcher<I, O> for B
+impl<B, Bt, I, O> DynBatcher<B, I, O> for Bt
 where
-    B: Batcher<I, O> + Clone + 'static,
+    B: Backend,
+    Bt: Batcher<B, I, O> + Clone + 'static,
 {
-    fn clone_dyn(&self) -> Box<dyn DynBatcher<I, O>> {
+    fn clone_dyn(&self) -> Box<dyn DynBatcher<B, I, O>> {
         Box::new(self.clone())
     }
 }
 
+#[cfg(feature = "text")]
+impl<B: Backend, S: AsRef<str>> Batcher<B, S, burn_dataset::text::TextBatch<B>>
+    for burn_dataset::text::TextBatcher
+{
+    fn batch(&self, items: Vec<S>, device: &B::Device) -> burn_dataset::text::TextBatch<B> {
+        self.batch_texts(&items, device)
+    }
+}
+
 #[cfg(test)]
 #[derive(new, Clone)]
 pub struct TestBatcher;
 
 #[cfg(test)]
-impl<I> Batcher<I, Vec<I>> for TestBatcher {
-    fn batch(&self, items: Vec<I>) -> Vec<I> {
+impl<B: Backend, I> Batcher<B, I, Vec<I>> for TestBatcher {
+    fn batch(&self, items: Vec<I>, _device: &B::Device) -> Vec<I> {
         items
     }
 }
+
+#[cfg(all(test, feature = "text"))]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_dataset::text::{TextBatch, TextBatcher, Tokenizer};
+    use std::sync::Arc;
+
+    #[test]
+    fn text_batcher_should_pad_tokens_and_mask() {
+        let tokenizer = Tokenizer::from_json(
+            r#"{
+                "model": {"type": "WordLevel", "unk_token": "<unk>", "vocab": {"<pad>": 0, "<unk>": 1, "hello": 2, "world": 3}},
+                "pre_tokenizer": {"type": "WhitespaceSplit"}
+            }"#,
+        )
+        .unwrap();
+        let batcher = TextBatcher::new(Arc::new(tokenizer));
+
+        let batch: TextBatch<TestBackend> =
+            batcher.batch(vec!["hello world", "foo"], &Default::default());
+
+        batch
+            .tokens
+            .into_data()
+            .assert_eq(&burn_tensor::TensorData::from([[2, 3], [1, 0]]), false);
+        batch.mask_pad.into_data().assert_eq(
+            &burn_tensor::TensorData::from([[false, false], [false, true]]),
+            false,
+        );
+    }
+}
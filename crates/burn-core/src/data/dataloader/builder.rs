@@ -1,18 +1,26 @@
-use super::{batcher::DynBatcher, BatchDataLoader, BatchStrategy, DataLoader, FixBatchStrategy};
-use burn_dataset::Dataset;
+use super::{
+    batcher::DynBatcher, BatchDataLoader, BatchStrategy, DataLoader, FixBatchStrategy,
+    IterableDataLoader, MultiThreadDataLoader, PrefetchDataLoader,
+};
+use burn_dataset::{transform::ShardRemainder, Dataset, IterableDataset};
+use burn_tensor::backend::Backend;
 use rand::{rngs::StdRng, SeedableRng};
 use std::sync::Arc;
 
 /// A builder for data loaders.
-pub struct DataLoaderBuilder<I, O> {
+pub struct DataLoaderBuilder<B: Backend, I, O> {
     strategy: Option<Box<dyn BatchStrategy<I>>>,
-    batcher: Box<dyn DynBatcher<I, O>>,
+    batcher: Box<dyn DynBatcher<B, I, O>>,
     num_threads: Option<usize>,
     shuffle: Option<u64>,
+    device: Option<B::Device>,
+    prefetch: Option<usize>,
+    shard: Option<(usize, usize, ShardRemainder)>,
 }
 
-impl<I, O> DataLoaderBuilder<I, O>
+impl<B, I, O> DataLoaderBuilder<B, I, O>
 where
+    B: Backend,
     I: Send + Sync + Clone + std::fmt::Debug + 'static,
     O: Send + Clone + std::fmt::Debug + 'static,
 {
@@ -25,15 +33,18 @@ where
     /// # Returns
     ///
     /// The data loader builder.
-    pub fn new<B>(batcher: B) -> Self
+    pub fn new<Bt>(batcher: Bt) -> Self
     where
-        B: DynBatcher<I, O> + 'static,
+        Bt: DynBatcher<B, I, O> + 'static,
     {
         Self {
             batcher: Box::new(batcher),
             strategy: None,
             num_threads: None,
             shuffle: None,
+            device: None,
+            prefetch: None,
+            shard: None,
         }
     }
 
@@ -52,6 +63,25 @@ where
         self
     }
 
+    /// Sets the strategy used to batch items, e.g. a
+    /// [bucket batch strategy](super::BucketBatchStrategy) to batch sequences of similar length
+    /// together.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The batch strategy.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn batch_strategy<S>(mut self, strategy: S) -> Self
+    where
+        S: BatchStrategy<I> + 'static,
+    {
+        self.strategy = Some(Box::new(strategy));
+        self
+    }
+
     /// Sets the seed for shuffling.
     ///
     /// Each time the dataloader starts a new iteration, the dataset will be shuffled.
@@ -68,6 +98,27 @@ where
         self
     }
 
+    /// Only iterate over one shard of the dataset, so that each replica of a distributed training
+    /// sees different items.
+    ///
+    /// The dataset is shuffled before being sharded, so every replica should use the same
+    /// [shuffle](Self::shuffle) seed. The items are then reshuffled at each epoch, while the shards
+    /// never overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - The index of the replica.
+    /// * `world_size` - The number of replicas.
+    /// * `remainder` - How the items that can't be evenly divided between the replicas are handled.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn shard(mut self, rank: usize, world_size: usize, remainder: ShardRemainder) -> Self {
+        self.shard = Some((rank, world_size, remainder));
+        self
+    }
+
     /// Sets the number of workers.
     ///
     /// # Arguments
@@ -82,6 +133,38 @@ where
         self
     }
 
+    /// Sets the device on which the batches are created, the default device being used otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn set_device(mut self, device: B::Device) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    /// Creates the next batches in advance while the current one is being used, so that loading
+    /// the items and uploading the batches to the device overlaps with the training step.
+    ///
+    /// With multiple workers, the batches are already created in the worker threads, and the
+    /// number of prefetched batches bounds how many of them are created in advance.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_batches` - The number of batches created in advance.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn prefetch(mut self, num_batches: usize) -> Self {
+        self.prefetch = Some(num_batches);
+        self
+    }
+
     /// Builds the data loader.
     ///
     /// # Arguments
@@ -91,27 +174,92 @@ where
     /// # Returns
     ///
     /// The data loader.
-    pub fn build<D>(self, dataset: D) -> Arc<dyn DataLoader<O>>
+    pub fn build<D>(self, dataset: D) -> Arc<dyn DataLoader<B, O>>
     where
         D: Dataset<I> + 'static,
     {
         let dataset = Arc::new(dataset);
 
         let rng = self.shuffle.map(StdRng::seed_from_u64);
+        let device = self.device.unwrap_or_default();
+        let strategy = match self.strategy {
+            Some(strategy) => strategy,
+            None => Box::new(FixBatchStrategy::new(1)),
+        };
+        let mut dataloader = BatchDataLoader::new(strategy, dataset, self.batcher, device, rng);
+        if let Some((rank, world_size, remainder)) = self.shard {
+            dataloader = dataloader.with_shard(rank, world_size, remainder);
+        }
+
+        if let Some(num_threads) = self.num_threads {
+            let dataloader = dataloader.into_multi_thread(num_threads);
+            return Self::prefetch_multi_thread(dataloader, self.prefetch);
+        }
+
+        Self::prefetch_single_thread(Arc::new(dataloader), self.prefetch)
+    }
+
+    /// Builds the data loader for an [iterable dataset](IterableDataset).
+    ///
+    /// When multiple workers are used, the shards of the dataset are distributed across them.
+    /// The items of an iterable dataset can't be shuffled nor sharded by the data loader, the
+    /// [shuffle buffer dataset](burn_dataset::transform::ShuffleBufferDataset) should be used
+    /// instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The iterable dataset.
+    ///
+    /// # Returns
+    ///
+    /// The data loader.
+    pub fn build_iterable<D>(self, dataset: D) -> Arc<dyn DataLoader<B, O>>
+    where
+        D: IterableDataset<I> + 'static,
+    {
+        assert!(
+            self.shard.is_none(),
+            "Iterable datasets can't be sharded by the data loader"
+        );
+        let dataset = Arc::new(dataset);
+
+        let device = self.device.unwrap_or_default();
         let strategy = match self.strategy {
             Some(strategy) => strategy,
             None => Box::new(FixBatchStrategy::new(1)),
         };
         if let Some(num_threads) = self.num_threads {
-            return Arc::new(BatchDataLoader::multi_thread(
+            let dataloader = IterableDataLoader::multi_thread(
                 strategy,
                 dataset,
                 self.batcher,
                 num_threads,
-                rng,
-            ));
+                device,
+            );
+            return Self::prefetch_multi_thread(dataloader, self.prefetch);
         }
 
-        Arc::new(BatchDataLoader::new(strategy, dataset, self.batcher, rng))
+        let dataloader = IterableDataLoader::new(strategy, dataset, self.batcher, device);
+        Self::prefetch_single_thread(Arc::new(dataloader), self.prefetch)
+    }
+
+    fn prefetch_single_thread(
+        dataloader: Arc<dyn DataLoader<B, O>>,
+        prefetch: Option<usize>,
+    ) -> Arc<dyn DataLoader<B, O>> {
+        match prefetch {
+            Some(num_batches) => Arc::new(PrefetchDataLoader::new(dataloader, num_batches)),
+            None => dataloader,
+        }
+    }
+
+    fn prefetch_multi_thread(
+        dataloader: MultiThreadDataLoader<B, O>,
+        prefetch: Option<usize>,
+    ) -> Arc<dyn DataLoader<B, O>> {
+        match prefetch {
+            Some(num_batches) => Arc::new(dataloader.with_max_queued(num_batches)),
+            None => Arc::new(dataloader),
+        }
     }
 }
@@ -0,0 +1,304 @@
+use super::{
+    batcher::DynBatcher, BatchStrategy, DataLoader, DataLoaderIterator, MultiThreadDataLoader,
+    Progress,
+};
+use burn_dataset::{IterableDataset, IterableDatasetIterator};
+use burn_tensor::backend::Backend;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A data loader that can be used to iterate over an [iterable dataset](IterableDataset) in
+/// batches.
+pub struct IterableDataLoader<B: Backend, I, O> {
+    strategy: Box<dyn BatchStrategy<I>>,
+    dataset: Arc<dyn IterableDataset<I>>,
+    batcher: Box<dyn DynBatcher<B, I, O>>,
+    device: B::Device,
+    shards: Vec<usize>,
+    iteration: Arc<AtomicUsize>,
+}
+
+impl<B: Backend, I, O> Clone for IterableDataLoader<B, I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            strategy: self.strategy.clone_dyn(),
+            dataset: self.dataset.clone(),
+            batcher: self.batcher.clone_dyn(),
+            device: self.device.clone(),
+            shards: self.shards.clone(),
+            iteration: self.iteration.clone(),
+        }
+    }
+}
+
+impl<B: Backend, I, O> IterableDataLoader<B, I, O> {
+    /// Creates a new iterable data loader iterating over all the shards of the dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The batch strategy.
+    /// * `dataset` - The iterable dataset.
+    /// * `batcher` - The batcher.
+    /// * `device` - The device on which the batches are created.
+    ///
+    /// # Returns
+    ///
+    /// The iterable data loader.
+    pub fn new(
+        strategy: Box<dyn BatchStrategy<I>>,
+        dataset: Arc<dyn IterableDataset<I>>,
+        batcher: Box<dyn DynBatcher<B, I, O>>,
+        device: B::Device,
+    ) -> Self {
+        let shards = (0..dataset.num_shards()).collect();
+
+        Self {
+            strategy,
+            dataset,
+            batcher,
+            device,
+            shards,
+            iteration: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Only iterate over the given shards of the dataset.
+    pub fn with_shards(mut self, shards: Vec<usize>) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Returns a copy of the data loader with its own iterations.
+    fn copy(&self) -> Self {
+        Self {
+            iteration: Arc::new(AtomicUsize::new(0)),
+            ..self.clone()
+        }
+    }
+}
+
+impl<B, I, O> IterableDataLoader<B, I, O>
+where
+    B: Backend,
+    I: Send + Sync + 'static,
+    O: Send + 'static,
+{
+    /// Creates a new multi-threaded iterable data loader.
+    ///
+    /// The shards of the dataset are distributed across the workers, so each item is loaded by
+    /// exactly one worker. When there are fewer shards than threads, only one worker is created
+    /// per shard.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The batch strategy.
+    /// * `dataset` - The iterable dataset.
+    /// * `batcher` - The batcher.
+    /// * `num_threads` - The number of threads.
+    /// * `device` - The device on which the batches are created.
+    ///
+    /// # Returns
+    ///
+    /// The multi-threaded iterable data loader.
+    pub fn multi_thread(
+        strategy: Box<dyn BatchStrategy<I>>,
+        dataset: Arc<dyn IterableDataset<I>>,
+        batcher: Box<dyn DynBatcher<B, I, O>>,
+        num_threads: usize,
+        device: B::Device,
+    ) -> MultiThreadDataLoader<B, O> {
+        let num_shards = dataset.num_shards();
+        let num_workers = num_threads.min(num_shards).max(1);
+
+        let dataloaders = (0..num_workers)
+            .map(|worker| {
+                let shards = (worker..num_shards).step_by(num_workers).collect();
+                let dataloader = IterableDataLoader::new(
+                    strategy.clone_dyn(),
+                    dataset.clone(),
+                    batcher.clone_dyn(),
+                    device.clone(),
+                )
+                .with_shards(shards);
+                let dataloader: Arc<dyn DataLoader<B, O>> = Arc::new(dataloader);
+                dataloader
+            })
+            .collect();
+
+        MultiThreadDataLoader::new(dataloaders)
+    }
+}
+
+/// A data loader iterator that can be used to iterate over an iterable data loader.
+struct IterableDataloaderIterator<'a, B: Backend, I, O> {
+    items: IterableDatasetIterator<'a, I>,
+    items_processed: usize,
+    items_total: Option<usize>,
+    strategy: Box<dyn BatchStrategy<I>>,
+    batcher: Box<dyn DynBatcher<B, I, O>>,
+    device: B::Device,
+}
+
+impl<B, I, O> DataLoader<B, O> for IterableDataLoader<B, I, O>
+where
+    B: Backend,
+    I: Send + Sync + 'static,
+    O: Send + 'static,
+{
+    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        let iteration = self.iteration.fetch_add(1, Ordering::Relaxed);
+
+        Box::new(IterableDataloaderIterator {
+            items: self.dataset.iter_shards(self.shards.clone()),
+            items_processed: 0,
+            items_total: self.dataset.len_hint().map(|len| self.shard_len(len)),
+            strategy: self.strategy.clone_iteration(iteration),
+            batcher: self.batcher.clone_dyn(),
+            device: self.device.clone(),
+        })
+    }
+
+    fn num_items(&self) -> usize {
+        self.dataset
+            .len_hint()
+            .map(|len| self.shard_len(len))
+            .unwrap_or(0)
+    }
+
+    fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>> {
+        let mut dataloader = self.copy();
+        dataloader.device = device.clone();
+
+        Arc::new(dataloader)
+    }
+
+    fn split(&self, num: usize) -> Vec<Arc<dyn DataLoader<B, O>>> {
+        // The shards are distributed across the new data loaders, some of them being empty when
+        // there are fewer shards than data loaders.
+        (0..num)
+            .map(|index| {
+                let shards = self
+                    .shards
+                    .iter()
+                    .skip(index)
+                    .step_by(num)
+                    .copied()
+                    .collect();
+                let dataloader: Arc<dyn DataLoader<B, O>> =
+                    Arc::new(self.copy().with_shards(shards));
+                dataloader
+            })
+            .collect()
+    }
+}
+
+impl<B: Backend, I, O> IterableDataLoader<B, I, O> {
+    /// The approximate number of items of the shards of the data loader, assuming that the items
+    /// are evenly split across the shards.
+    fn shard_len(&self, len: usize) -> usize {
+        let num_shards = self.dataset.num_shards().max(1);
+        len * self.shards.len() / num_shards
+    }
+}
+
+impl<B: Backend, I, O> Iterator for IterableDataloaderIterator<'_, B, I, O> {
+    type Item = O;
+
+    fn next(&mut self) -> Option<O> {
+        for item in self.items.by_ref() {
+            self.items_processed += 1;
+            self.strategy.add(item);
+
+            if let Some(items) = self.strategy.batch(false) {
+                return Some(self.batcher.batch(items, &self.device));
+            }
+        }
+
+        if let Some(items) = self.strategy.batch(true) {
+            return Some(self.batcher.batch(items, &self.device));
+        }
+
+        None
+    }
+}
+
+impl<B: Backend, I, O> DataLoaderIterator<O> for IterableDataloaderIterator<'_, B, I, O> {
+    fn progress(&self) -> Progress {
+        // When the number of items is unknown, only the processed items are known to exist.
+        let items_total = self
+            .items_total
+            .unwrap_or(self.items_processed)
+            .max(self.items_processed);
+
+        Progress::new(self.items_processed, items_total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::dataloader::batcher::TestBatcher;
+    use crate::data::dataloader::FixBatchStrategy;
+    use crate::TestBackend;
+    use burn_dataset::GeneratorDataset;
+
+    fn dataset() -> Arc<dyn IterableDataset<String>> {
+        Arc::new(
+            GeneratorDataset::new(5, |shard| {
+                (0..7).map(move |i| format!("shard {shard} item {i}"))
+            })
+            .with_len(35),
+        )
+    }
+
+    #[test]
+    fn test_iterable_dataloader() {
+        let dataloader = IterableDataLoader::<TestBackend, _, _>::new(
+            Box::new(FixBatchStrategy::new(4)),
+            dataset(),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+        );
+
+        let items = dataloader.iter().flatten().collect::<Vec<_>>();
+
+        assert_eq!(items, dataset().iter().collect::<Vec<_>>());
+        assert_eq!(dataloader.num_items(), 35);
+    }
+
+    #[test]
+    fn test_multi_thread_iterable_dataloader_should_split_shards() {
+        let dataloader = IterableDataLoader::<TestBackend, _, _>::multi_thread(
+            Box::new(FixBatchStrategy::new(4)),
+            dataset(),
+            Box::new(TestBatcher::new()),
+            3,
+            Default::default(),
+        );
+
+        let mut items = dataloader.iter().flatten().collect::<Vec<_>>();
+        let mut expected = dataset().iter().collect::<Vec<_>>();
+        items.sort();
+        expected.sort();
+
+        // Each item is loaded exactly once.
+        assert_eq!(items, expected);
+    }
+
+    #[test]
+    fn test_multi_thread_iterable_dataloader_with_more_threads_than_shards() {
+        let dataloader = IterableDataLoader::<TestBackend, _, _>::multi_thread(
+            Box::new(FixBatchStrategy::new(4)),
+            dataset(),
+            Box::new(TestBatcher::new()),
+            8,
+            Default::default(),
+        );
+
+        let mut iterator = dataloader.iter();
+        let num_items = iterator.by_ref().flatten().count();
+
+        assert_eq!(num_items, 35);
+        assert_eq!(iterator.progress().items_processed, 35);
+    }
+}
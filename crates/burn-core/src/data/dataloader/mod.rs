@@ -1,7 +1,9 @@
 mod base;
 mod batch;
 mod builder;
+mod iterable;
 mod multithread;
+mod prefetch;
 mod strategy;
 
 /// Module for batching items.
@@ -10,5 +12,7 @@ pub mod batcher;
 pub use base::*;
 pub use batch::*;
 pub use builder::*;
+pub use iterable::*;
 pub use multithread::*;
+pub use prefetch::*;
 pub use strategy::*;
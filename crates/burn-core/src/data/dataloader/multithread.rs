@@ -1,12 +1,19 @@
-use super::{DataLoader, DataLoaderIterator, DynDataLoader, Progress};
-use std::sync::mpsc;
+use super::{DataLoader, DataLoaderIterator, DataLoaderState, Progress};
+use burn_tensor::backend::Backend;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 
 const MAX_QUEUED_ITEMS: usize = 100;
 
 /// A multi-threaded data loader that can be used to iterate over a dataset.
-pub struct MultiThreadDataLoader<O> {
-    dataloaders: Vec<Box<dyn DynDataLoader<O>>>,
+///
+/// Each data loader is iterated in its own thread, so the batches are created on the device while
+/// the previous ones are being used.
+pub struct MultiThreadDataLoader<B: Backend, O> {
+    dataloaders: Vec<Arc<dyn DataLoader<B, O>>>,
+    max_queued: usize,
+    iteration: AtomicUsize,
 }
 
 /// A message that can be sent between threads.
@@ -20,13 +27,14 @@ pub enum Message<O> {
 }
 
 struct MultiThreadsDataloaderIterator<O> {
+    iteration: usize,
     num_done: usize,
     workers: Vec<thread::JoinHandle<()>>,
     receiver: mpsc::Receiver<Message<O>>,
     progresses: Vec<Progress>,
 }
 
-impl<O> MultiThreadDataLoader<O> {
+impl<B: Backend, O> MultiThreadDataLoader<B, O> {
     /// Creates a new multi-threaded data loader.
     ///
     /// # Arguments
@@ -36,17 +44,32 @@ impl<O> MultiThreadDataLoader<O> {
     /// # Returns
     ///
     /// The multi-threaded data loader.
-    pub fn new(dataloaders: Vec<Box<dyn DynDataLoader<O>>>) -> Self {
-        Self { dataloaders }
+    pub fn new(dataloaders: Vec<Arc<dyn DataLoader<B, O>>>) -> Self {
+        Self {
+            dataloaders,
+            max_queued: MAX_QUEUED_ITEMS,
+            iteration: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the maximum number of batches created in advance by the workers.
+    pub fn with_max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
     }
 }
 
-impl<O> DataLoader<O> for MultiThreadDataLoader<O>
+impl<B, O> MultiThreadDataLoader<B, O>
 where
+    B: Backend,
     O: Send + 'static + std::fmt::Debug,
 {
-    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
-        let (sender, receiver) = mpsc::sync_channel::<Message<O>>(MAX_QUEUED_ITEMS);
+    fn spawn_workers(
+        &self,
+        iteration: usize,
+        state: Option<&DataLoaderState>,
+    ) -> MultiThreadsDataloaderIterator<O> {
+        let (sender, receiver) = mpsc::sync_channel::<Message<O>>(self.max_queued);
 
         let mut progresses = Vec::with_capacity(self.dataloaders.len());
 
@@ -55,12 +78,27 @@ where
             .iter()
             .enumerate()
             .map(|(index, dataloader)| {
-                let dataloader_cloned = dataloader.clone_dyn();
+                let dataloader_cloned = dataloader.clone();
                 let sender_cloned = sender.clone();
-                progresses.push(Progress::new(0, dataloader_cloned.num_items()));
+                // Each worker resumes from its own number of processed items.
+                let state = state.map(|state| {
+                    let items_processed = state.items_processed.get(index).copied().unwrap_or(0);
+                    DataLoaderState::new(state.iteration, vec![items_processed])
+                });
+                let items_processed = state
+                    .as_ref()
+                    .map(|state| state.items_processed[0])
+                    .unwrap_or(0);
+                progresses.push(Progress::new(
+                    items_processed,
+                    dataloader_cloned.num_items(),
+                ));
 
                 thread::spawn(move || {
-                    let mut iterator = dataloader_cloned.iter();
+                    let mut iterator = match &state {
+                        Some(state) => dataloader_cloned.iter_from(state),
+                        None => dataloader_cloned.iter(),
+                    };
                     while let Some(item) = iterator.next() {
                         let progress = iterator.progress();
 
@@ -77,23 +115,72 @@ where
             })
             .collect();
 
-        Box::new(MultiThreadsDataloaderIterator::new(
-            receiver, handlers, progresses,
-        ))
+        MultiThreadsDataloaderIterator::new(iteration, receiver, handlers, progresses)
+    }
+}
+
+impl<B, O> DataLoader<B, O> for MultiThreadDataLoader<B, O>
+where
+    B: Backend,
+    O: Send + 'static + std::fmt::Debug,
+{
+    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        let iteration = self.iteration.fetch_add(1, Ordering::Relaxed);
+
+        Box::new(self.spawn_workers(iteration, None))
+    }
+
+    fn iter_from<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        self.iteration.store(state.iteration + 1, Ordering::Relaxed);
+
+        Box::new(self.spawn_workers(state.iteration, Some(state)))
     }
 
     fn num_items(&self) -> usize {
         self.dataloaders.iter().map(|dl| dl.num_items()).sum()
     }
+
+    fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>> {
+        let dataloaders = self
+            .dataloaders
+            .iter()
+            .map(|dataloader| dataloader.to_device(device))
+            .collect();
+
+        Arc::new(MultiThreadDataLoader::new(dataloaders).with_max_queued(self.max_queued))
+    }
+
+    fn split(&self, num: usize) -> Vec<Arc<dyn DataLoader<B, O>>> {
+        // Each worker is split, so every new data loader keeps the same number of workers.
+        let mut dataloaders = (0..num).map(|_| Vec::new()).collect::<Vec<_>>();
+
+        for dataloader in self.dataloaders.iter() {
+            for (index, split) in dataloader.split(num).into_iter().enumerate() {
+                dataloaders[index].push(split);
+            }
+        }
+
+        dataloaders
+            .into_iter()
+            .map(|dataloaders| {
+                let dataloader: Arc<dyn DataLoader<B, O>> = Arc::new(
+                    MultiThreadDataLoader::new(dataloaders).with_max_queued(self.max_queued),
+                );
+                dataloader
+            })
+            .collect()
+    }
 }
 
 impl<O> MultiThreadsDataloaderIterator<O> {
     pub fn new(
+        iteration: usize,
         receiver: mpsc::Receiver<Message<O>>,
         workers: Vec<thread::JoinHandle<()>>,
         progresses: Vec<Progress>,
     ) -> Self {
         MultiThreadsDataloaderIterator {
+            iteration,
             num_done: 0,
             workers,
             receiver,
@@ -113,6 +200,16 @@ impl<O: std::fmt::Debug> DataLoaderIterator<O> for MultiThreadsDataloaderIterato
 
         Progress::new(items_processed, items_total)
     }
+
+    fn state(&self) -> DataLoaderState {
+        let items_processed = self
+            .progresses
+            .iter()
+            .map(|progress| progress.items_processed)
+            .collect();
+
+        DataLoaderState::new(self.iteration, items_processed)
+    }
 }
 
 impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
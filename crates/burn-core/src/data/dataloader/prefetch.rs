@@ -0,0 +1,204 @@
+use super::{DataLoader, DataLoaderIterator, DataLoaderState, Progress};
+use burn_tensor::backend::Backend;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// A data loader creating the next batches in a background thread, so that loading the items and
+/// uploading the batches to the device happens while the current batch is being used.
+pub struct PrefetchDataLoader<B: Backend, O> {
+    dataloader: Arc<dyn DataLoader<B, O>>,
+    num_batches: usize,
+}
+
+impl<B: Backend, O> PrefetchDataLoader<B, O> {
+    /// Creates a new prefetch data loader.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataloader` - The data loader creating the batches.
+    /// * `num_batches` - The number of batches created in advance.
+    ///
+    /// # Returns
+    ///
+    /// The prefetch data loader.
+    pub fn new(dataloader: Arc<dyn DataLoader<B, O>>, num_batches: usize) -> Self {
+        Self {
+            dataloader,
+            num_batches,
+        }
+    }
+}
+
+/// A batch with the progress and the state of the iterator after it was created.
+type Prefetched<O> = (O, Progress, DataLoaderState);
+
+struct PrefetchDataLoaderIterator<O> {
+    receiver: mpsc::Receiver<Prefetched<O>>,
+    worker: Option<thread::JoinHandle<()>>,
+    progress: Progress,
+    state: DataLoaderState,
+}
+
+impl<B, O> PrefetchDataLoader<B, O>
+where
+    B: Backend,
+    O: Send + 'static,
+{
+    fn spawn_worker(&self, state: Option<DataLoaderState>) -> PrefetchDataLoaderIterator<O> {
+        // The worker blocks once the channel is full, and one more batch is created while waiting.
+        let (sender, receiver) =
+            mpsc::sync_channel::<Prefetched<O>>(self.num_batches.saturating_sub(1));
+        let dataloader = self.dataloader.clone();
+
+        let progress = Progress::new(
+            state
+                .as_ref()
+                .map(|state| state.items_processed.iter().sum())
+                .unwrap_or(0),
+            dataloader.num_items(),
+        );
+        let state_initial = state.clone().unwrap_or_default();
+
+        let worker = thread::spawn(move || {
+            let mut iterator = match &state {
+                Some(state) => dataloader.iter_from(state),
+                None => dataloader.iter(),
+            };
+
+            while let Some(item) = iterator.next() {
+                let prefetched = (item, iterator.progress(), iterator.state());
+
+                // The receiver is gone when the iterator is dropped, so the worker just stops.
+                if sender.send(prefetched).is_err() {
+                    return;
+                }
+            }
+        });
+
+        PrefetchDataLoaderIterator {
+            receiver,
+            worker: Some(worker),
+            progress,
+            state: state_initial,
+        }
+    }
+}
+
+impl<B, O> DataLoader<B, O> for PrefetchDataLoader<B, O>
+where
+    B: Backend,
+    O: Send + 'static,
+{
+    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        Box::new(self.spawn_worker(None))
+    }
+
+    fn iter_from<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        Box::new(self.spawn_worker(Some(state.clone())))
+    }
+
+    fn num_items(&self) -> usize {
+        self.dataloader.num_items()
+    }
+
+    fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>> {
+        Arc::new(PrefetchDataLoader::new(
+            self.dataloader.to_device(device),
+            self.num_batches,
+        ))
+    }
+
+    fn split(&self, num: usize) -> Vec<Arc<dyn DataLoader<B, O>>> {
+        self.dataloader
+            .split(num)
+            .into_iter()
+            .map(|dataloader| {
+                let dataloader: Arc<dyn DataLoader<B, O>> =
+                    Arc::new(PrefetchDataLoader::new(dataloader, self.num_batches));
+                dataloader
+            })
+            .collect()
+    }
+}
+
+impl<O> Iterator for PrefetchDataLoaderIterator<O> {
+    type Item = O;
+
+    fn next(&mut self) -> Option<O> {
+        match self.receiver.recv() {
+            Ok((item, progress, state)) => {
+                self.progress = progress;
+                self.state = state;
+                Some(item)
+            }
+            Err(_) => {
+                // The worker is done, propagating its panic if any.
+                if let Some(worker) = self.worker.take() {
+                    worker.join().unwrap();
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<O> DataLoaderIterator<O> for PrefetchDataLoaderIterator<O> {
+    fn progress(&self) -> Progress {
+        self.progress.clone()
+    }
+
+    fn state(&self) -> DataLoaderState {
+        self.state.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::dataloader::batcher::TestBatcher;
+    use crate::data::dataloader::{BatchDataLoader, FixBatchStrategy};
+    use crate::data::dataset::FakeDataset;
+    use crate::TestBackend;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn dataloader(
+        dataset: &Arc<FakeDataset<String>>,
+    ) -> Arc<dyn DataLoader<TestBackend, Vec<String>>> {
+        Arc::new(BatchDataLoader::<TestBackend, _, _>::new(
+            Box::new(FixBatchStrategy::new(5)),
+            dataset.clone(),
+            Box::new(TestBatcher::new()),
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        ))
+    }
+
+    #[test]
+    fn test_prefetch_dataloader_should_keep_batches_order() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let expected = dataloader(&dataset).iter().collect::<Vec<_>>();
+        let prefetch = PrefetchDataLoader::new(dataloader(&dataset), 2);
+
+        let mut iterator = prefetch.iter();
+        let actual = iterator.by_ref().collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(iterator.progress().items_processed, 27);
+    }
+
+    #[test]
+    fn test_prefetch_dataloader_resume_from_state() {
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let prefetch = PrefetchDataLoader::new(dataloader(&dataset), 3);
+        let mut iterator = prefetch.iter();
+        iterator.next();
+        let state = iterator.state();
+        let expected = iterator.collect::<Vec<_>>();
+
+        let prefetch = PrefetchDataLoader::new(dataloader(&dataset), 3);
+        let actual = prefetch.iter_from(&state).collect::<Vec<_>>();
+
+        assert_eq!(state, DataLoaderState::new(0, vec![5]));
+        assert_eq!(actual, expected);
+    }
+}
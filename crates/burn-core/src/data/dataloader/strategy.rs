@@ -1,5 +1,8 @@
+use rand::{distributions::Standard, rngs::StdRng, Rng, SeedableRng};
+use std::{collections::VecDeque, sync::Arc};
+
 /// A strategy to batch items.
-pub trait BatchStrategy<I>: Send {
+pub trait BatchStrategy<I>: Send + Sync {
     /// Adds an item to the strategy.
     ///
     /// # Arguments
@@ -24,6 +27,33 @@ pub trait BatchStrategy<I>: Send {
     ///
     /// The new strategy.
     fn clone_dyn(&self) -> Box<dyn BatchStrategy<I>>;
+
+    /// Creates a new strategy of the same type for the given iteration of a data loader, i.e. the
+    /// number of iterators created by the data loader before this one.
+    ///
+    /// Strategies drawing random numbers derive them from the iteration, so that each iteration
+    /// is reproducible, even when it is split between threads or resumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `iteration` - The iteration of the data loader.
+    ///
+    /// # Returns
+    ///
+    /// The new strategy.
+    fn clone_iteration(&self, iteration: usize) -> Box<dyn BatchStrategy<I>> {
+        let _ = iteration;
+        self.clone_dyn()
+    }
+
+    /// Whether the batches depend on the items added before the previous batch, e.g. items
+    /// waiting in buckets or random numbers already drawn.
+    ///
+    /// Resuming an iteration of a data loader then adds the items processed before the resume
+    /// point again, discarding their batches, to restore the state of the strategy.
+    fn is_stateful(&self) -> bool {
+        false
+    }
 }
 
 /// A strategy to batch items with a fixed batch size.
@@ -50,7 +80,7 @@ impl<I> FixBatchStrategy<I> {
     }
 }
 
-impl<I: Send + 'static> BatchStrategy<I> for FixBatchStrategy<I> {
+impl<I: Send + Sync + 'static> BatchStrategy<I> for FixBatchStrategy<I> {
     fn add(&mut self, item: I) {
         self.items.push(item);
     }
@@ -74,3 +104,318 @@ impl<I: Send + 'static> BatchStrategy<I> for FixBatchStrategy<I> {
         Box::new(Self::new(self.batch_size))
     }
 }
+
+/// A strategy to batch items of similar length together, so that little compute is wasted on
+/// padding.
+///
+/// Items are assigned to buckets according to their length and the bucket boundaries. A bucket is
+/// batched when it reaches the batch size, or when adding an item would exceed the token budget,
+/// i.e. the number of items times the longest length of the batch.
+///
+/// When shuffling is enabled, the order in which full buckets are batched is randomized with a
+/// generator derived from the seed and the [iteration](BatchStrategy::clone_iteration), so it
+/// changes between epochs while being reproducible.
+pub struct BucketBatchStrategy<I> {
+    length: Arc<dyn Fn(&I) -> usize + Send + Sync>,
+    boundaries: Vec<usize>,
+    batch_size: Option<usize>,
+    max_tokens: Option<usize>,
+    seed: Option<u64>,
+    rng: Option<StdRng>,
+    buckets: Vec<Bucket<I>>,
+    ready: VecDeque<Vec<I>>,
+}
+
+struct Bucket<I> {
+    items: Vec<I>,
+    max_length: usize,
+}
+
+impl<I> Bucket<I> {
+    fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            max_length: 0,
+        }
+    }
+
+    fn take(&mut self) -> Vec<I> {
+        self.max_length = 0;
+        std::mem::take(&mut self.items)
+    }
+}
+
+impl<I> BucketBatchStrategy<I> {
+    /// Creates a new strategy to batch items of similar length.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The function returning the length of an item, e.g. its number of tokens.
+    /// * `boundaries` - The lengths at which a new bucket starts. Items shorter than the first
+    ///   boundary go in the first bucket, and items at least as long as the last boundary go in
+    ///   the last one.
+    /// * `batch_size` - The maximum number of items in a batch.
+    ///
+    /// # Returns
+    ///
+    /// The strategy.
+    pub fn new<F>(length: F, mut boundaries: Vec<usize>, batch_size: usize) -> Self
+    where
+        F: Fn(&I) -> usize + Send + Sync + 'static,
+    {
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        Self::from_parts(
+            Arc::new(length),
+            boundaries,
+            Some(batch_size),
+            None,
+            None,
+            0,
+        )
+    }
+
+    /// Creates a new strategy to batch items in arrival order, with the number of items of each
+    /// batch only limited by a token budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The function returning the length of an item, e.g. its number of tokens.
+    /// * `max_tokens` - The maximum number of tokens in a padded batch.
+    ///
+    /// # Returns
+    ///
+    /// The strategy.
+    pub fn token_budget<F>(length: F, max_tokens: usize) -> Self
+    where
+        F: Fn(&I) -> usize + Send + Sync + 'static,
+    {
+        Self::from_parts(
+            Arc::new(length),
+            Vec::new(),
+            None,
+            Some(max_tokens),
+            None,
+            0,
+        )
+    }
+
+    /// Limits the number of tokens in a padded batch, in addition to the number of items.
+    ///
+    /// A single item longer than the budget is still batched on its own.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Shuffles the order in which buckets are batched, using the given seed.
+    pub fn with_shuffle(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = Some(Self::rng(seed, 0));
+        self
+    }
+
+    /// The generator of an iteration, advanced once for each iteration before it like the
+    /// shuffling of the data loader.
+    fn rng(seed: u64, iteration: usize) -> StdRng {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..iteration {
+            let _seed: u64 = rng.sample(Standard);
+        }
+
+        StdRng::seed_from_u64(rng.sample(Standard))
+    }
+
+    fn from_parts(
+        length: Arc<dyn Fn(&I) -> usize + Send + Sync>,
+        boundaries: Vec<usize>,
+        batch_size: Option<usize>,
+        max_tokens: Option<usize>,
+        seed: Option<u64>,
+        iteration: usize,
+    ) -> Self {
+        let buckets = (0..boundaries.len() + 1).map(|_| Bucket::new()).collect();
+
+        Self {
+            length,
+            boundaries,
+            batch_size,
+            max_tokens,
+            seed,
+            rng: seed.map(|seed| Self::rng(seed, iteration)),
+            buckets,
+            ready: VecDeque::new(),
+        }
+    }
+
+    fn exceeds_budget(&self, bucket: &Bucket<I>, length: usize) -> bool {
+        match self.max_tokens {
+            Some(max_tokens) => {
+                let max_length = usize::max(bucket.max_length, length);
+                !bucket.items.is_empty() && (bucket.items.len() + 1) * max_length > max_tokens
+            }
+            None => false,
+        }
+    }
+
+    fn is_full(&self, bucket: &Bucket<I>) -> bool {
+        match self.batch_size {
+            Some(batch_size) => bucket.items.len() >= batch_size,
+            None => false,
+        }
+    }
+
+    fn next_ready(&mut self) -> Option<Vec<I>> {
+        match &mut self.rng {
+            Some(rng) if !self.ready.is_empty() => {
+                let index = rng.gen_range(0..self.ready.len());
+                self.ready.swap_remove_back(index)
+            }
+            _ => self.ready.pop_front(),
+        }
+    }
+}
+
+impl<I: Send + Sync + 'static> BatchStrategy<I> for BucketBatchStrategy<I> {
+    fn add(&mut self, item: I) {
+        let length = (self.length)(&item);
+        let index = self
+            .boundaries
+            .partition_point(|boundary| *boundary <= length);
+
+        if self.exceeds_budget(&self.buckets[index], length) {
+            let items = self.buckets[index].take();
+            self.ready.push_back(items);
+        }
+
+        let bucket = &mut self.buckets[index];
+        bucket.items.push(item);
+        bucket.max_length = usize::max(bucket.max_length, length);
+
+        if self.is_full(&self.buckets[index]) {
+            let items = self.buckets[index].take();
+            self.ready.push_back(items);
+        }
+    }
+
+    fn batch(&mut self, force: bool) -> Option<Vec<I>> {
+        if force {
+            for bucket in self.buckets.iter_mut() {
+                if !bucket.items.is_empty() {
+                    self.ready.push_back(bucket.take());
+                }
+            }
+        }
+
+        // When shuffling, full batches wait until there is at least one per bucket to choose from.
+        let num_waiting = match self.rng {
+            Some(_) if !force => self.buckets.len(),
+            _ => 1,
+        };
+
+        if self.ready.len() < num_waiting {
+            return None;
+        }
+
+        self.next_ready()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn BatchStrategy<I>> {
+        self.clone_iteration(0)
+    }
+
+    fn clone_iteration(&self, iteration: usize) -> Box<dyn BatchStrategy<I>> {
+        Box::new(Self::from_parts(
+            self.length.clone(),
+            self.boundaries.clone(),
+            self.batch_size,
+            self.max_tokens,
+            self.seed,
+            iteration,
+        ))
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batches(strategy: &mut dyn BatchStrategy<String>, items: &[&str]) -> Vec<Vec<String>> {
+        let mut batches = Vec::new();
+
+        for item in items {
+            strategy.add(item.to_string());
+            if let Some(batch) = strategy.batch(false) {
+                batches.push(batch);
+            }
+        }
+        while let Some(batch) = strategy.batch(true) {
+            batches.push(batch);
+        }
+
+        batches
+    }
+
+    #[test]
+    fn bucket_strategy_should_group_items_of_similar_length() {
+        let mut strategy = BucketBatchStrategy::new(|item: &String| item.len(), vec![3], 2);
+
+        let batches = batches(&mut strategy, &["a", "aaaa", "b", "bbbb", "c", "cccc", "d"]);
+
+        assert_eq!(
+            batches,
+            vec![
+                vec!["a", "b"],
+                vec!["aaaa", "bbbb"],
+                vec!["c", "d"],
+                vec!["cccc"],
+            ]
+        );
+    }
+
+    #[test]
+    fn token_budget_strategy_should_limit_padded_tokens() {
+        let mut strategy = BucketBatchStrategy::token_budget(|item: &String| item.len(), 6);
+
+        let batches = batches(&mut strategy, &["a", "b", "ccc", "d", "eeeeeeee", "f"]);
+
+        assert_eq!(
+            batches,
+            vec![
+                vec!["a", "b"],
+                vec!["ccc", "d"],
+                vec!["eeeeeeee"],
+                vec!["f"],
+            ]
+        );
+    }
+
+    #[test]
+    fn bucket_strategy_should_shuffle_buckets_between_epochs() {
+        let strategy =
+            BucketBatchStrategy::new(|item: &String| item.len(), vec![2, 3, 4], 1).with_shuffle(42);
+        let items = ["a", "aa", "aaa", "aaaa", "b", "bb", "bbb", "bbbb"];
+
+        let epochs = (0..4)
+            .map(|epoch| batches(strategy.clone_iteration(epoch).as_mut(), &items))
+            .collect::<Vec<_>>();
+
+        for epoch in epochs.iter() {
+            let mut items_epoch = epoch.iter().flatten().cloned().collect::<Vec<_>>();
+            items_epoch.sort();
+            let mut items_expected = items.map(String::from).to_vec();
+            items_expected.sort();
+            assert_eq!(items_epoch, items_expected);
+        }
+        assert!(epochs.iter().any(|epoch| *epoch != epochs[0]));
+        assert_eq!(
+            batches(strategy.clone_iteration(2).as_mut(), &items),
+            epochs[2]
+        );
+    }
+}
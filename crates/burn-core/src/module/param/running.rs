@@ -99,14 +99,12 @@ impl<const D: usize, B: Backend> Module<B> for RunningState<Tensor<B, D>> {
         Param::initialized(self.id, tensor.clone())
     }
 
-    fn load_record(mut self, record: Self::Record) -> Self {
-        let mut tensor = self.value.lock().unwrap();
-        *tensor = record.val().to_device(&tensor.device());
-        self.id = record.id;
-
-        core::mem::drop(tensor);
+    fn load_record(self, record: Self::Record) -> Self {
+        // The loaded state isn't shared with the clones of the current one, so that loading a
+        // record into a clone of a module leaves the original module unchanged.
+        let device = self.value.lock().unwrap().device();
 
-        self
+        Self::with_id(record.id, record.val().to_device(&device))
     }
 
     fn to_device(self, device: &<B as Backend>::Device) -> Self {
@@ -105,30 +105,92 @@ impl<B: Backend> Gru<B> {
     /// # Shapes
     /// - batched_input: `[batch_size, sequence_length, input_size]`.
     /// - state: An optional tensor representing an initial cell state with the same dimensions
-    ///          as batched_input. If none is provided, one will be generated.
+    ///   as the output, of which the first timestep is used. If none is provided, zeros are used.
     /// - output: `[batch_size, sequence_length, hidden_size]`.
     pub fn forward(
         &self,
         batched_input: Tensor<B, 3>,
         state: Option<Tensor<B, 3>>,
     ) -> Tensor<B, 3> {
+        let state = state.map(|state| {
+            let [batch_size, _, d_hidden] = state.dims();
+            state.slice([0..batch_size, 0..1, 0..d_hidden]).squeeze(1)
+        });
+
+        self.forward_with_state(batched_input, state)
+    }
+
+    /// Applies the forward pass on the input tensor, starting from the given hidden state. The
+    /// hidden state of each timestep is returned, the last one being the final state.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state with dimensions
+    ///   `[batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size]`.
+    pub fn forward_with_state(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 3> {
+        self.forward_sequence(batched_input, state, false)
+    }
+
+    /// Applies the forward pass on the input tensor like [forward_with_state](Gru::forward_with_state),
+    /// but with the reset gate applied after the hidden transformation of the new gate instead of
+    /// before it, as in PyTorch, cuDNN and ONNX models with `linear_before_reset`.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state with dimensions
+    ///   `[batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size]`.
+    pub fn forward_reset_after(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 3> {
+        self.forward_sequence(batched_input, state, true)
+    }
+
+    fn forward_sequence(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+        reset_after: bool,
+    ) -> Tensor<B, 3> {
+        let device = batched_input.device();
         let [batch_size, seq_length, _] = batched_input.shape().dims();
 
-        let mut hidden_state = match state {
+        self.forward_iter(
+            batched_input.iter_dim(1).zip(0..seq_length),
+            state,
+            batch_size,
+            seq_length,
+            reset_after,
+            &device,
+        )
+    }
+
+    fn forward_iter<I: Iterator<Item = (Tensor<B, 3>, usize)>>(
+        &self,
+        input_timestep_iter: I,
+        state: Option<Tensor<B, 2>>,
+        batch_size: usize,
+        seq_length: usize,
+        reset_after: bool,
+        device: &B::Device,
+    ) -> Tensor<B, 3> {
+        let mut batched_hidden_state =
+            Tensor::empty([batch_size, seq_length, self.d_hidden], device);
+
+        let mut hidden_t = match state {
             Some(state) => state,
-            None => Tensor::zeros(
-                [batch_size, seq_length, self.d_hidden],
-                &batched_input.device(),
-            ),
+            None => Tensor::zeros([batch_size, self.d_hidden], device),
         };
 
-        for (t, (input_t, hidden_t)) in batched_input
-            .iter_dim(1)
-            .zip(hidden_state.clone().iter_dim(1))
-            .enumerate()
-        {
+        for (input_t, t) in input_timestep_iter {
             let input_t = input_t.squeeze(1);
-            let hidden_t = hidden_t.squeeze(1);
             // u(pdate)g(ate) tensors
             let biased_ug_input_sum = self.gate_product(&input_t, &hidden_t, &self.update_gate);
             let update_values = activation::sigmoid(biased_ug_input_sum); // Colloquially referred to as z(t)
@@ -136,29 +198,34 @@ impl<B: Backend> Gru<B> {
             // r(eset)g(ate) tensors
             let biased_rg_input_sum = self.gate_product(&input_t, &hidden_t, &self.reset_gate);
             let reset_values = activation::sigmoid(biased_rg_input_sum); // Colloquially referred to as r(t)
-            let reset_t = hidden_t.clone().mul(reset_values); // Passed as input to new_gate
 
             // n(ew)g(ate) tensor
-            let biased_ng_input_sum = self.gate_product(&input_t, &reset_t, &self.new_gate);
+            let biased_ng_input_sum = match reset_after {
+                true => {
+                    self.new_gate.input_transform.forward(input_t)
+                        + reset_values.mul(self.new_gate.hidden_transform.forward(hidden_t.clone()))
+                }
+                false => {
+                    let reset_t = hidden_t.clone().mul(reset_values); // Passed as input to new_gate
+                    self.gate_product(&input_t, &reset_t, &self.new_gate)
+                }
+            };
             let candidate_state = biased_ng_input_sum.tanh(); // Colloquially referred to as g(t)
 
             // calculate linear interpolation between previous hidden state and candidate state:
             // g(t) * (1 - z(t)) + z(t) * hidden_t
-            let state_vector = candidate_state
+            hidden_t = candidate_state
                 .clone()
                 .mul(update_values.clone().sub_scalar(1).mul_scalar(-1)) // (1 - z(t)) = -(z(t) - 1)
                 + update_values.clone().mul(hidden_t);
 
-            let current_shape = state_vector.shape().dims;
-            let unsqueezed_shape = [current_shape[0], 1, current_shape[1]];
-            let reshaped_state_vector = state_vector.reshape(unsqueezed_shape);
-            hidden_state = hidden_state.slice_assign(
+            batched_hidden_state = batched_hidden_state.slice_assign(
                 [0..batch_size, t..(t + 1), 0..self.d_hidden],
-                reshaped_state_vector,
+                hidden_t.clone().unsqueeze_dim(1),
             );
         }
 
-        hidden_state
+        batched_hidden_state
     }
 
     /// Helper function for performing weighted matrix product for a gate and adds
@@ -201,12 +268,220 @@ impl<B: Backend> Gru<B> {
     }
 }
 
+/// Configuration to create a [BiGru](BiGru) module using the [init function](BiGruConfig::init).
+#[derive(Config)]
+pub struct BiGruConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// If a bias should be applied during the BiGru transformation.
+    pub bias: bool,
+    /// BiGru initializer
+    #[config(default = "Initializer::XavierNormal{gain:1.0}")]
+    pub initializer: Initializer,
+}
+
+/// The BiGru module. This implementation is for Bidirectional GRU.
+///
+/// Should be created with [BiGruConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct BiGru<B: Backend> {
+    /// GRU for the forward direction.
+    pub forward: Gru<B>,
+    /// GRU for the reverse direction.
+    pub reverse: Gru<B>,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+}
+
+impl<B: Backend> ModuleDisplay for BiGru<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_input, _] = self
+            .forward
+            .update_gate
+            .input_transform
+            .weight
+            .shape()
+            .dims();
+        let bias = self.forward.update_gate.input_transform.bias.is_some();
+
+        content
+            .add("d_input", &d_input)
+            .add("d_hidden", &self.d_hidden)
+            .add("bias", &bias)
+            .optional()
+    }
+}
+
+impl BiGruConfig {
+    /// Initialize a new [Bidirectional GRU](BiGru) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> BiGru<B> {
+        let config = GruConfig::new(self.d_input, self.d_hidden, self.bias)
+            .with_initializer(self.initializer.clone());
+
+        BiGru {
+            forward: config.init(device),
+            reverse: config.init(device),
+            d_hidden: self.d_hidden,
+        }
+    }
+}
+
+impl<B: Backend> BiGru<B> {
+    /// Applies the forward pass on the input tensor. This Bidirectional GRU implementation
+    /// returns the hidden states of both directions concatenated for each timestep.
+    ///
+    /// The final state of the forward direction is at the last timestep, while the final state
+    /// of the reverse direction is at the first one.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state of both directions with
+    ///   dimensions `[2, batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size * 2]`.
+    pub fn forward(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+    ) -> Tensor<B, 3> {
+        self.forward_directions(batched_input, state, false)
+    }
+
+    /// Applies the forward pass on the input tensor like [forward](BiGru::forward), but with the
+    /// reset gate of both directions applied after the hidden transformation of the new gate, as
+    /// with [Gru::forward_reset_after].
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state of both directions with
+    ///   dimensions `[2, batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size * 2]`.
+    pub fn forward_reset_after(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+    ) -> Tensor<B, 3> {
+        self.forward_directions(batched_input, state, true)
+    }
+
+    fn forward_directions(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+        reset_after: bool,
+    ) -> Tensor<B, 3> {
+        let device = batched_input.device();
+        let [batch_size, seq_length, _] = batched_input.shape().dims();
+
+        let [state_forward, state_reverse] = match state {
+            Some(state) => {
+                let state_forward = state
+                    .clone()
+                    .slice([0..1, 0..batch_size, 0..self.d_hidden])
+                    .squeeze(0);
+                let state_reverse = state
+                    .slice([1..2, 0..batch_size, 0..self.d_hidden])
+                    .squeeze(0);
+
+                [Some(state_forward), Some(state_reverse)]
+            }
+            None => [None, None],
+        };
+
+        let output_forward =
+            self.forward
+                .forward_sequence(batched_input.clone(), state_forward, reset_after);
+        let output_reverse = self.reverse.forward_iter(
+            batched_input.iter_dim(1).rev().zip((0..seq_length).rev()),
+            state_reverse,
+            batch_size,
+            seq_length,
+            reset_after,
+            &device,
+        );
+
+        Tensor::cat([output_forward, output_reverse].to_vec(), 2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tensor::{Distribution, TensorData};
     use crate::{module::Param, nn::LinearRecord, TestBackend};
 
+    fn create_gate_controller(
+        weights: f32,
+        biases: f32,
+        d_input: usize,
+        d_output: usize,
+        bias: bool,
+        initializer: Initializer,
+        device: &<TestBackend as Backend>::Device,
+    ) -> GateController<TestBackend> {
+        let record_1 = LinearRecord {
+            weight: Param::from_data(TensorData::from([[weights]]), device),
+            bias: Some(Param::from_data(TensorData::from([biases]), device)),
+        };
+        let record_2 = LinearRecord {
+            weight: Param::from_data(TensorData::from([[weights]]), device),
+            bias: Some(Param::from_data(TensorData::from([biases]), device)),
+        };
+        gate_controller::GateController::create_with_weights(
+            d_input,
+            d_output,
+            bias,
+            initializer,
+            record_1,
+            record_2,
+        )
+    }
+
+    fn init_gru(new_gate_bias: f32) -> Gru<TestBackend> {
+        TestBackend::seed(0);
+        let config = GruConfig::new(1, 1, false);
+        let device = Default::default();
+        let mut gru = config.init::<TestBackend>(&device);
+
+        gru.update_gate = create_gate_controller(
+            0.5,
+            0.0,
+            1,
+            1,
+            false,
+            Initializer::XavierNormal { gain: 1.0 },
+            &device,
+        );
+        gru.reset_gate = create_gate_controller(
+            0.6,
+            0.0,
+            1,
+            1,
+            false,
+            Initializer::XavierNormal { gain: 1.0 },
+            &device,
+        );
+        gru.new_gate = create_gate_controller(
+            0.7,
+            new_gate_bias,
+            1,
+            1,
+            true,
+            Initializer::XavierNormal { gain: 1.0 },
+            &device,
+        );
+
+        gru
+    }
+
     /// Test forward pass with simple input vector.
     ///
     /// z_t = sigmoid(0.5*0.1 + 0.5*0) = 0.5125
@@ -288,6 +563,61 @@ mod tests {
         output.to_data().assert_approx_eq(&expected, 3);
     }
 
+    /// The second timestep uses the hidden state of the first one.
+    #[test]
+    fn test_forward_multiple_timesteps() {
+        let gru = init_gru(0.0);
+        let device = Default::default();
+
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1], [0.1]]]), &device);
+
+        let output = gru.forward(input, None);
+
+        let expected = TensorData::from([[[0.0341], [0.0573]]]);
+        output.to_data().assert_approx_eq(&expected, 3);
+    }
+
+    #[test]
+    fn test_forward_reset_after() {
+        let device = Default::default();
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1], [0.1]]]), &device);
+
+        let gru = init_gru(0.1);
+
+        let output = gru.forward_with_state(input.clone(), None);
+        let output_reset_after = gru.forward_reset_after(input, None);
+
+        output
+            .to_data()
+            .assert_approx_eq(&TensorData::from([[[0.1285], [0.2130]]]), 3);
+        output_reset_after
+            .to_data()
+            .assert_approx_eq(&TensorData::from([[[0.1062], [0.1776]]]), 3);
+    }
+
+    #[test]
+    fn test_forward_initial_state() {
+        let gru = init_gru(0.0);
+        let device = Default::default();
+
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1], [0.1]]]), &device);
+        let state = Tensor::<TestBackend, 2>::from_data(TensorData::from([[0.0341]]), &device);
+
+        let output = gru.forward_with_state(input.clone(), Some(state.clone()));
+        let output_3d = gru.forward(input, Some(state.unsqueeze_dim(1).repeat_dim(1, 2)));
+
+        let expected = TensorData::from([[[0.0573]]]);
+        output
+            .clone()
+            .slice([0..1, 0..1, 0..1])
+            .to_data()
+            .assert_approx_eq(&expected, 3);
+        output_3d.to_data().assert_approx_eq(&output.to_data(), 3);
+    }
+
     #[test]
     fn test_batched_forward_pass() {
         let device = Default::default();
@@ -311,4 +641,59 @@ mod tests {
             "Gru {d_input: 2, d_hidden: 8, bias: true, params: 288}"
         );
     }
+
+    #[test]
+    fn test_bigru_batched_forward_pass() {
+        let device = Default::default();
+        let bigru = BiGruConfig::new(64, 32, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([8, 10, 64], Distribution::Default, &device);
+
+        let output = bigru.forward(batched_input, None);
+
+        assert_eq!(output.shape().dims, [8, 10, 64]);
+    }
+
+    /// The reverse direction of a bidirectional GRU is the same as a GRU over the reversed sequence.
+    #[test]
+    fn test_bigru_reverse_direction() {
+        let device = Default::default();
+        let bigru = BiGruConfig::new(4, 3, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+
+        let output = bigru.forward(batched_input.clone(), None);
+        let expected = bigru
+            .reverse
+            .forward(batched_input.clone().flip([1]), None)
+            .flip([1]);
+
+        output
+            .slice([0..2, 0..5, 3..6])
+            .to_data()
+            .assert_approx_eq(&expected.to_data(), 3);
+
+        let output = bigru.forward_reset_after(batched_input.clone(), None);
+        let expected = bigru
+            .reverse
+            .forward_reset_after(batched_input.flip([1]), None)
+            .flip([1]);
+
+        output
+            .slice([0..2, 0..5, 3..6])
+            .to_data()
+            .assert_approx_eq(&expected.to_data(), 3);
+    }
+
+    #[test]
+    fn bigru_display() {
+        let config = BiGruConfig::new(2, 8, true);
+
+        let layer = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{}", layer),
+            "BiGru {d_input: 2, d_hidden: 8, bias: true, params: 576}"
+        );
+    }
 }
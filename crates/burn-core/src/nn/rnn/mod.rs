@@ -6,5 +6,10 @@ pub mod gru;
 /// Long Short-Term Memory module.
 pub mod lstm;
 
+/// Elman Recurrent Neural Network module.
+pub mod simple_rnn;
+
 pub use gate_controller::*;
+pub use gru::*;
 pub use lstm::*;
+pub use simple_rnn::*;
@@ -0,0 +1,326 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::Module;
+use crate::module::{Content, DisplaySettings, ModuleDisplay};
+use crate::nn::rnn::gate_controller::GateController;
+use crate::nn::Initializer;
+use crate::tensor::backend::Backend;
+use crate::tensor::Tensor;
+
+/// Configuration to create a [Rnn](Rnn) module using the [init function](RnnConfig::init).
+#[derive(Config)]
+pub struct RnnConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// If a bias should be applied during the Rnn transformation.
+    pub bias: bool,
+    /// Rnn initializer
+    #[config(default = "Initializer::XavierNormal{gain:1.0}")]
+    pub initializer: Initializer,
+}
+
+/// The Rnn module. This implementation is for a unidirectional, stateless, Elman recurrent
+/// neural network with a tanh activation.
+///
+/// `h_t = tanh(W_x * x_t + b_x + W_h * h_(t-1) + b_h)`
+///
+/// Should be created with [RnnConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct Rnn<B: Backend> {
+    /// The gate computing the new hidden state from the input and the previous hidden state.
+    pub hidden_gate: GateController<B>,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+}
+
+impl<B: Backend> ModuleDisplay for Rnn<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_input, _] = self.hidden_gate.input_transform.weight.shape().dims();
+        let bias = self.hidden_gate.input_transform.bias.is_some();
+
+        content
+            .add("d_input", &d_input)
+            .add("d_hidden", &self.d_hidden)
+            .add("bias", &bias)
+            .optional()
+    }
+}
+
+impl RnnConfig {
+    /// Initialize a new [rnn](Rnn) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Rnn<B> {
+        let hidden_gate = GateController::new(
+            self.d_input,
+            self.d_hidden,
+            self.bias,
+            self.initializer.clone(),
+            device,
+        );
+
+        Rnn {
+            hidden_gate,
+            d_hidden: self.d_hidden,
+        }
+    }
+}
+
+impl<B: Backend> Rnn<B> {
+    /// Applies the forward pass on the input tensor. This RNN implementation
+    /// returns the hidden state of each timestep, the last one being the final state.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state with dimensions
+    ///   `[batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size]`.
+    pub fn forward(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 3> {
+        let device = batched_input.device();
+        let [batch_size, seq_length, _] = batched_input.shape().dims();
+
+        self.forward_iter(
+            batched_input.iter_dim(1).zip(0..seq_length),
+            state,
+            batch_size,
+            seq_length,
+            &device,
+        )
+    }
+
+    fn forward_iter<I: Iterator<Item = (Tensor<B, 3>, usize)>>(
+        &self,
+        input_timestep_iter: I,
+        state: Option<Tensor<B, 2>>,
+        batch_size: usize,
+        seq_length: usize,
+        device: &B::Device,
+    ) -> Tensor<B, 3> {
+        let mut batched_hidden_state =
+            Tensor::empty([batch_size, seq_length, self.d_hidden], device);
+
+        let mut hidden_t = match state {
+            Some(state) => state,
+            None => Tensor::zeros([batch_size, self.d_hidden], device),
+        };
+
+        for (input_t, t) in input_timestep_iter {
+            let input_t = input_t.squeeze(1);
+            hidden_t = self.hidden_gate.gate_product(input_t, hidden_t).tanh();
+
+            batched_hidden_state = batched_hidden_state.slice_assign(
+                [0..batch_size, t..(t + 1), 0..self.d_hidden],
+                hidden_t.clone().unsqueeze_dim(1),
+            );
+        }
+
+        batched_hidden_state
+    }
+}
+
+/// Configuration to create a [BiRnn](BiRnn) module using the [init function](BiRnnConfig::init).
+#[derive(Config)]
+pub struct BiRnnConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// If a bias should be applied during the BiRnn transformation.
+    pub bias: bool,
+    /// BiRnn initializer
+    #[config(default = "Initializer::XavierNormal{gain:1.0}")]
+    pub initializer: Initializer,
+}
+
+/// The BiRnn module. This implementation is for Bidirectional RNN.
+///
+/// Should be created with [BiRnnConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct BiRnn<B: Backend> {
+    /// RNN for the forward direction.
+    pub forward: Rnn<B>,
+    /// RNN for the reverse direction.
+    pub reverse: Rnn<B>,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+}
+
+impl<B: Backend> ModuleDisplay for BiRnn<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_input, _] = self
+            .forward
+            .hidden_gate
+            .input_transform
+            .weight
+            .shape()
+            .dims();
+        let bias = self.forward.hidden_gate.input_transform.bias.is_some();
+
+        content
+            .add("d_input", &d_input)
+            .add("d_hidden", &self.d_hidden)
+            .add("bias", &bias)
+            .optional()
+    }
+}
+
+impl BiRnnConfig {
+    /// Initialize a new [Bidirectional RNN](BiRnn) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> BiRnn<B> {
+        let config = RnnConfig::new(self.d_input, self.d_hidden, self.bias)
+            .with_initializer(self.initializer.clone());
+
+        BiRnn {
+            forward: config.init(device),
+            reverse: config.init(device),
+            d_hidden: self.d_hidden,
+        }
+    }
+}
+
+impl<B: Backend> BiRnn<B> {
+    /// Applies the forward pass on the input tensor. This Bidirectional RNN implementation
+    /// returns the hidden states of both directions concatenated for each timestep.
+    ///
+    /// The final state of the forward direction is at the last timestep, while the final state
+    /// of the reverse direction is at the first one.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state of both directions with
+    ///   dimensions `[2, batch_size, hidden_size]`. If none is provided, zeros are used.
+    /// - output: `[batch_size, sequence_length, hidden_size * 2]`.
+    pub fn forward(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+    ) -> Tensor<B, 3> {
+        let device = batched_input.device();
+        let [batch_size, seq_length, _] = batched_input.shape().dims();
+
+        let [state_forward, state_reverse] = match state {
+            Some(state) => {
+                let state_forward = state
+                    .clone()
+                    .slice([0..1, 0..batch_size, 0..self.d_hidden])
+                    .squeeze(0);
+                let state_reverse = state
+                    .slice([1..2, 0..batch_size, 0..self.d_hidden])
+                    .squeeze(0);
+
+                [Some(state_forward), Some(state_reverse)]
+            }
+            None => [None, None],
+        };
+
+        let output_forward = self.forward.forward(batched_input.clone(), state_forward);
+        let output_reverse = self.reverse.forward_iter(
+            batched_input.iter_dim(1).rev().zip((0..seq_length).rev()),
+            state_reverse,
+            batch_size,
+            seq_length,
+            &device,
+        );
+
+        Tensor::cat([output_forward, output_reverse].to_vec(), 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Distribution, TensorData};
+    use crate::{module::Param, nn::LinearRecord, TestBackend};
+
+    /// h_1 = tanh(0.5*0.1 + 0.1 + 0.3*0 + 0.1) = 0.2449
+    /// h_2 = tanh(0.5*0.2 + 0.1 + 0.3*0.2449 + 0.1) = 0.3570
+    #[test]
+    fn test_forward_multiple_timesteps() {
+        let device = Default::default();
+        let mut rnn = RnnConfig::new(1, 1, true).init::<TestBackend>(&device);
+        let record = |weight: f32, bias: f32| LinearRecord {
+            weight: Param::from_data(TensorData::from([[weight]]), &device),
+            bias: Some(Param::from_data(TensorData::from([bias]), &device)),
+        };
+        rnn.hidden_gate = GateController::create_with_weights(
+            1,
+            1,
+            true,
+            Initializer::XavierNormal { gain: 1.0 },
+            record(0.5, 0.1),
+            record(0.3, 0.1),
+        );
+
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1], [0.2]]]), &device);
+
+        let output = rnn.forward(input, None);
+
+        let expected = TensorData::from([[[0.2449], [0.3570]]]);
+        output.to_data().assert_approx_eq(&expected, 3);
+    }
+
+    #[test]
+    fn test_batched_forward_pass() {
+        let device = Default::default();
+        let rnn = RnnConfig::new(64, 32, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([8, 10, 64], Distribution::Default, &device);
+
+        let output = rnn.forward(batched_input, None);
+
+        assert_eq!(output.shape().dims, [8, 10, 32]);
+    }
+
+    /// The reverse direction of a bidirectional RNN is the same as a RNN over the reversed sequence.
+    #[test]
+    fn test_birnn_reverse_direction() {
+        let device = Default::default();
+        let birnn = BiRnnConfig::new(4, 3, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+
+        let output = birnn.forward(batched_input.clone(), None);
+        let expected = birnn
+            .reverse
+            .forward(batched_input.flip([1]), None)
+            .flip([1]);
+
+        assert_eq!(output.shape().dims, [2, 5, 6]);
+        output
+            .slice([0..2, 0..5, 3..6])
+            .to_data()
+            .assert_approx_eq(&expected.to_data(), 3);
+    }
+
+    #[test]
+    fn display() {
+        let config = RnnConfig::new(2, 8, true);
+
+        let layer = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{}", layer),
+            "Rnn {d_input: 2, d_hidden: 8, bias: true, params: 96}"
+        );
+    }
+}
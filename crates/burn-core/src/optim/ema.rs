@@ -0,0 +1,289 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::{Module, ModuleMapper, ModuleVisitor, ParamId};
+use burn_tensor::{backend::Backend, container::TensorContainer, Tensor};
+
+/// Configuration to create an [exponential moving average](ModuleEma) of a module's weights.
+#[derive(Config)]
+pub struct ModuleEmaConfig {
+    /// The decay applied to the averaged weights at each update.
+    #[config(default = 0.9999)]
+    pub decay: f64,
+    /// The number of updates during which the averaged weights are simply replaced by the
+    /// current weights.
+    #[config(default = 0)]
+    pub update_after_step: usize,
+    /// Whether the decay should be warmed up, following `1 - (1 + step / gamma) ^ -power`.
+    #[config(default = false)]
+    pub warmup: bool,
+    /// The multiplicative factor of the warmup.
+    #[config(default = 1.0)]
+    pub warmup_gamma: f64,
+    /// The exponent of the warmup.
+    #[config(default = 0.75)]
+    pub warmup_power: f64,
+}
+
+/// The [record](crate::record::Record) of a [module ema](ModuleEma): the averaged module record
+/// and the number of updates.
+pub type ModuleEmaRecord<B, M> = (<M as Module<B>>::Record, usize);
+
+/// Keeps an exponential moving average of the weights of a module.
+///
+/// The averaged weights are stored in a shadow copy of the module, which is updated with
+/// `shadow = decay * shadow + (1 - decay) * weights` each time [update](ModuleEma::update) is
+/// called, usually after each optimizer step.
+#[derive(Clone)]
+pub struct ModuleEma<B: Backend, M: Module<B>> {
+    module: M,
+    step: usize,
+    config: ModuleEmaConfig,
+    _backend: core::marker::PhantomData<B>,
+}
+
+impl ModuleEmaConfig {
+    /// Initialize the moving average from the current weights of the given module.
+    ///
+    /// The shadow module is loaded from a record of the module instead of being cloned, so that
+    /// its running states (e.g. batch norm statistics) aren't shared with the module.
+    pub fn init<B: Backend, M: Module<B>>(&self, module: &M) -> ModuleEma<B, M> {
+        let record = module.clone().into_record();
+
+        ModuleEma {
+            module: module.clone().load_record(record),
+            step: 0,
+            config: self.clone(),
+            _backend: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<B: Backend, M: Module<B>> ModuleEma<B, M> {
+    /// Update the averaged weights with the current weights of the given module.
+    pub fn update(&mut self, module: &M) {
+        self.step += 1;
+
+        let mut collector = ParamsCollector {
+            tensors: TensorContainer::new(),
+        };
+        module.visit(&mut collector);
+
+        let mut mapper = EmaMapper {
+            tensors: collector.tensors,
+            decay: self.decay(),
+        };
+        self.module = self.module.clone().map(&mut mapper);
+    }
+
+    /// The decay used for the last update.
+    pub fn decay(&self) -> f64 {
+        if self.step <= self.config.update_after_step {
+            return 0.0;
+        }
+
+        if !self.config.warmup {
+            return self.config.decay;
+        }
+
+        let step = (self.step - self.config.update_after_step) as f64;
+        let decay = 1.0 - (1.0 + step / self.config.warmup_gamma).powf(-self.config.warmup_power);
+
+        decay.clamp(0.0, self.config.decay)
+    }
+
+    /// The number of updates done so far.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The module holding the averaged weights.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Consume the moving average and return the module holding the averaged weights.
+    pub fn into_module(self) -> M {
+        self.module
+    }
+
+    /// Get the current state of the moving average as a [record](ModuleEmaRecord).
+    pub fn to_record(&self) -> ModuleEmaRecord<B, M> {
+        (self.module.clone().into_record(), self.step)
+    }
+
+    /// Load the state of the moving average from a [record](ModuleEmaRecord).
+    pub fn load_record(mut self, record: ModuleEmaRecord<B, M>) -> Self {
+        let (module, step) = record;
+        self.module = self.module.load_record(module);
+        self.step = step;
+        self
+    }
+}
+
+struct ParamsCollector {
+    tensors: TensorContainer<ParamId>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for ParamsCollector {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        // Detaching a tensor keeps its gradient requirement, which would make the averaged
+        // tensors part of a graph.
+        self.tensors
+            .register::<B>(id, tensor.clone().set_require_grad(false).into_primitive());
+    }
+}
+
+struct EmaMapper {
+    tensors: TensorContainer<ParamId>,
+    decay: f64,
+}
+
+impl<B: Backend> ModuleMapper<B> for EmaMapper {
+    fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let Some(current) = self.tensors.remove::<B>(&id) else {
+            return tensor;
+        };
+        let current = Tensor::<B, D>::from_primitive(current);
+        let is_require_grad = tensor.is_require_grad();
+
+        let mut tensor = tensor
+            .set_require_grad(false)
+            .mul_scalar(self.decay)
+            .add(current.mul_scalar(1.0 - self.decay));
+
+        if is_require_grad {
+            tensor = tensor.require_grad();
+        }
+
+        tensor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{BatchNormConfig, Linear};
+    use crate::{module::Param, TestAutodiffBackend, TestBackend};
+    use burn_tensor::TensorData;
+
+    #[test]
+    fn update_should_average_weights() {
+        let device = Default::default();
+        let layer_1 = layer(&device, 1.0);
+        let layer_2 = layer(&device, 3.0);
+        let mut ema = ModuleEmaConfig::new().with_decay(0.5).init(&layer_1);
+
+        ema.update(&layer_2);
+
+        ema.module()
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&TensorData::from([[2.0f32, 2.0], [2.0, 2.0]]), false);
+        assert_eq!(ema.step(), 1);
+    }
+
+    #[test]
+    fn update_should_copy_weights_before_update_after_step() {
+        let device = Default::default();
+        let layer_1 = layer(&device, 1.0);
+        let layer_2 = layer(&device, 3.0);
+        let mut ema = ModuleEmaConfig::new()
+            .with_decay(0.5)
+            .with_update_after_step(1)
+            .init(&layer_1);
+
+        ema.update(&layer_2);
+
+        ema.module()
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&TensorData::from([[3.0f32, 3.0], [3.0, 3.0]]), false);
+    }
+
+    #[test]
+    fn warmup_should_increase_decay() {
+        let device = Default::default();
+        let layer = layer(&device, 1.0);
+        let mut ema = ModuleEmaConfig::new().with_warmup(true).init(&layer);
+        let mut decay = 0.0;
+
+        for _ in 0..10 {
+            ema.update(&layer);
+            assert!(ema.decay() > decay);
+            decay = ema.decay();
+        }
+
+        assert!(decay < 0.9999);
+    }
+
+    #[test]
+    fn should_load_record() {
+        let device = Default::default();
+        let layer_1 = layer(&device, 1.0);
+        let layer_2 = layer(&device, 3.0);
+        let mut ema = ModuleEmaConfig::new().with_decay(0.5).init(&layer_1);
+        ema.update(&layer_2);
+
+        let loaded = ModuleEmaConfig::new()
+            .with_decay(0.5)
+            .init(&layer_1)
+            .load_record(ema.to_record());
+
+        assert_eq!(loaded.step(), 1);
+        loaded
+            .module()
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&ema.module().weight.val().into_data(), true);
+    }
+
+    #[test]
+    fn running_states_should_be_independent() {
+        let device = Default::default();
+        let module = BatchNormConfig::new(2).init::<TestAutodiffBackend, 1>(&device);
+        let mut ema = ModuleEmaConfig::new().with_decay(0.5).init(&module);
+
+        // The running mean of the module moves to 0.9 * 0.0 + 0.1 * 1.0.
+        module.forward(Tensor::<TestAutodiffBackend, 3>::ones([4, 2, 3], &device));
+        let expected = TensorData::from([0.1f32, 0.1]);
+        module
+            .running_mean
+            .value_sync()
+            .into_data()
+            .assert_approx_eq(&expected, 3);
+        ema.module()
+            .running_mean
+            .value_sync()
+            .into_data()
+            .assert_approx_eq(&TensorData::from([0.0f32, 0.0]), 3);
+
+        ema.update(&module);
+
+        ema.module()
+            .running_mean
+            .value_sync()
+            .into_data()
+            .assert_approx_eq(&TensorData::from([0.05f32, 0.05]), 3);
+        assert!(ema.module().gamma.val().is_require_grad());
+        module
+            .running_mean
+            .value_sync()
+            .into_data()
+            .assert_approx_eq(&expected, 3);
+    }
+
+    fn layer(device: &<TestBackend as Backend>::Device, value: f32) -> Linear<TestBackend> {
+        let weight = Tensor::full([2, 2], value, device);
+
+        // Both layers share the same parameter id, as a module does before and after an
+        // optimizer step.
+        Linear {
+            weight: Param::initialized(ParamId::from(0), weight),
+            bias: None,
+        }
+    }
+}
@@ -8,6 +8,7 @@ mod adagrad;
 mod adam;
 mod adamw;
 mod base;
+mod ema;
 mod grad_accum;
 mod grads;
 mod rmsprop;
@@ -19,6 +20,7 @@ pub use adagrad::*;
 pub use adam::*;
 pub use adamw::*;
 pub use base::*;
+pub use ema::*;
 pub use grad_accum::*;
 pub use grads::*;
 pub use rmsprop::*;
@@ -0,0 +1,799 @@
+use super::{SpeechCommandClass, SpeechItem};
+use crate::transform::Mapper;
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Window function applied to the frames before the Fourier transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Window {
+    /// Hann window.
+    #[default]
+    Hann,
+    /// Hamming window.
+    Hamming,
+    /// Rectangular window, keeping the frames unchanged.
+    Rectangular,
+}
+
+impl Window {
+    /// Returns the coefficients of the periodic window of the given size.
+    pub fn coefficients(&self, size: usize) -> Vec<f32> {
+        (0..size)
+            .map(|n| {
+                let phase = 2.0 * PI * n as f64 / size as f64;
+                let coefficient = match self {
+                    Window::Hann => 0.5 - 0.5 * phase.cos(),
+                    Window::Hamming => 0.54 - 0.46 * phase.cos(),
+                    Window::Rectangular => 1.0,
+                };
+                coefficient as f32
+            })
+            .collect()
+    }
+}
+
+/// Width of the windowed sinc filter used to resample, in zero crossings on each side.
+const RESAMPLE_ZERO_CROSSINGS: f64 = 6.0;
+
+/// Cutoff of the resampling filter relative to the Nyquist frequency, reducing aliasing.
+const RESAMPLE_ROLLOFF: f64 = 0.99;
+
+/// Kernel resampling a signal with band-limited (Hann windowed sinc) interpolation.
+///
+/// Each output sample is the weighted sum of the input samples at `offset..offset + weights.len()`,
+/// the input samples outside of the signal being zeros.
+struct ResampleKernel {
+    /// First input sample of each output sample.
+    offsets: Vec<isize>,
+    /// Weights of each output sample, all of the same length.
+    weights: Vec<Vec<f32>>,
+}
+
+impl ResampleKernel {
+    fn new(num_samples: usize, orig_rate: usize, new_rate: usize) -> Self {
+        let num_outputs = (num_samples * new_rate).div_ceil(orig_rate);
+        // Lowpass below the lowest of the two Nyquist frequencies, in cycles per input sample.
+        let cutoff = RESAMPLE_ROLLOFF * (new_rate as f64 / orig_rate as f64).min(1.0);
+        let half_width = (RESAMPLE_ZERO_CROSSINGS / cutoff).ceil() as isize;
+
+        let mut offsets = Vec::with_capacity(num_outputs);
+        let mut weights = Vec::with_capacity(num_outputs);
+
+        for k in 0..num_outputs {
+            let time = (k * orig_rate) as f64 / new_rate as f64;
+            let offset = time.floor() as isize - half_width;
+
+            offsets.push(offset);
+            weights.push(
+                (0..2 * half_width + 1)
+                    .map(|j| {
+                        let distance = (time - (offset + j) as f64) * cutoff;
+                        if distance.abs() > RESAMPLE_ZERO_CROSSINGS {
+                            return 0.0;
+                        }
+                        let window = (PI * distance / (2.0 * RESAMPLE_ZERO_CROSSINGS)).cos();
+                        let sinc = match distance == 0.0 {
+                            true => 1.0,
+                            false => (PI * distance).sin() / (PI * distance),
+                        };
+                        (cutoff * sinc * window * window) as f32
+                    })
+                    .collect(),
+            );
+        }
+
+        Self { offsets, weights }
+    }
+}
+
+/// Resamples audio samples to another sample rate with band-limited interpolation.
+///
+/// # Arguments
+///
+/// * `samples` - The audio samples.
+/// * `orig_rate` - The sample rate of the samples, in Hz.
+/// * `new_rate` - The sample rate of the resampled samples, in Hz.
+///
+/// # Returns
+///
+/// The `ceil(samples.len() * new_rate / orig_rate)` resampled samples.
+pub fn resample(samples: &[f32], orig_rate: usize, new_rate: usize) -> Vec<f32> {
+    if orig_rate == new_rate {
+        return samples.to_vec();
+    }
+
+    let kernel = ResampleKernel::new(samples.len(), orig_rate, new_rate);
+
+    kernel
+        .offsets
+        .iter()
+        .zip(kernel.weights.iter())
+        .map(|(offset, weights)| {
+            weights
+                .iter()
+                .enumerate()
+                .filter_map(|(j, weight)| {
+                    let index = usize::try_from(offset + j as isize).ok()?;
+                    samples.get(index).map(|sample| sample * weight)
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Index of the sample at the given position of a signal reflected at its boundaries.
+fn reflect(position: isize, len: usize) -> usize {
+    if len == 1 {
+        return 0;
+    }
+
+    let period = 2 * (len as isize - 1);
+    let position = position.rem_euclid(period);
+
+    match position < len as isize {
+        true => position as usize,
+        false => (period - position) as usize,
+    }
+}
+
+/// Splits audio samples into overlapping frames.
+///
+/// # Arguments
+///
+/// * `samples` - The audio samples.
+/// * `frame_length` - The number of samples of each frame.
+/// * `hop_length` - The number of samples between the starts of consecutive frames.
+/// * `center` - Whether to pad the samples by reflection with `frame_length / 2` samples on both
+///   sides, so that the frame `t` is centered on the sample `t * hop_length`.
+///
+/// # Returns
+///
+/// The frames, none if the samples are shorter than a frame without centering.
+pub fn frame(
+    samples: &[f32],
+    frame_length: usize,
+    hop_length: usize,
+    center: bool,
+) -> Vec<Vec<f32>> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let pad = match center {
+        true => frame_length / 2,
+        false => 0,
+    };
+    let padded_len = samples.len() + 2 * pad;
+    if padded_len < frame_length {
+        return Vec::new();
+    }
+    let num_frames = 1 + (padded_len - frame_length) / hop_length;
+
+    (0..num_frames)
+        .map(|t| {
+            (0..frame_length)
+                .map(|n| {
+                    let position = (t * hop_length + n) as isize - pad as isize;
+                    samples[reflect(position, samples.len())]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Short-time Fourier transform of audio samples.
+///
+/// # Arguments
+///
+/// * `samples` - The audio samples.
+/// * `n_fft` - The size of the Fourier transform, which is also the length of the frames.
+/// * `hop_length` - The number of samples between the starts of consecutive frames.
+/// * `window` - The window function applied to the frames.
+/// * `center` - Whether to center the frames on the samples `t * hop_length` (see [frame]).
+///
+/// # Returns
+///
+/// The `n_fft / 2 + 1` frequency bins of each frame, as `(real, imaginary)` pairs.
+pub fn stft(
+    samples: &[f32],
+    n_fft: usize,
+    hop_length: usize,
+    window: Window,
+    center: bool,
+) -> Vec<Vec<(f32, f32)>> {
+    let window = window.coefficients(n_fft);
+
+    frame(samples, n_fft, hop_length, center)
+        .into_iter()
+        .map(|frame| {
+            let mut bins = frame
+                .iter()
+                .zip(window.iter())
+                .map(|(sample, coefficient)| ((sample * coefficient) as f64, 0.0))
+                .collect::<Vec<_>>();
+            fft(&mut bins);
+
+            bins.into_iter()
+                .take(n_fft / 2 + 1)
+                .map(|(real, imaginary)| (real as f32, imaginary as f32))
+                .collect()
+        })
+        .collect()
+}
+
+/// In-place discrete Fourier transform, with a radix-2 FFT when the size is a power of two.
+fn fft(values: &mut [(f64, f64)]) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+
+    if !n.is_power_of_two() {
+        let input = values.to_vec();
+        for (k, value) in values.iter_mut().enumerate() {
+            *value = input.iter().enumerate().fold((0.0, 0.0), |sum, (j, x)| {
+                let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                let (sin, cos) = angle.sin_cos();
+                (sum.0 + x.0 * cos - x.1 * sin, sum.1 + x.0 * sin + x.1 * cos)
+            });
+        }
+        return;
+    }
+
+    // Bit reversal permutation.
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= n {
+        let angle = -2.0 * PI / size as f64;
+        for start in (0..n).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let even = values[start + k];
+                let odd = values[start + k + size / 2];
+                let odd = (odd.0 * cos - odd.1 * sin, odd.0 * sin + odd.1 * cos);
+
+                values[start + k] = (even.0 + odd.0, even.1 + odd.1);
+                values[start + k + size / 2] = (even.0 - odd.0, even.1 - odd.1);
+            }
+        }
+        size *= 2;
+    }
+}
+
+/// Power spectrogram of audio samples, the squared magnitude of their [STFT](stft).
+///
+/// # Returns
+///
+/// The `n_fft / 2 + 1` frequency bins of each frame.
+pub fn spectrogram(
+    samples: &[f32],
+    n_fft: usize,
+    hop_length: usize,
+    window: Window,
+    center: bool,
+) -> Vec<Vec<f32>> {
+    stft(samples, n_fft, hop_length, window, center)
+        .into_iter()
+        .map(|bins| {
+            bins.into_iter()
+                .map(|(real, imaginary)| real * real + imaginary * imaginary)
+                .collect()
+        })
+        .collect()
+}
+
+fn hz_to_mel(frequency: f64) -> f64 {
+    2595.0 * (1.0 + frequency / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    700.0 * (10f64.powf(mel / 2595.0) - 1.0)
+}
+
+/// Triangular mel filterbank, with filters equally spaced on the HTK mel scale.
+///
+/// # Arguments
+///
+/// * `n_freqs` - The number of frequency bins of the spectrogram, `n_fft / 2 + 1`.
+/// * `sample_rate` - The sample rate of the audio, in Hz.
+/// * `n_mels` - The number of mel filters.
+/// * `f_min` - The lowest frequency, in Hz.
+/// * `f_max` - The highest frequency, in Hz.
+///
+/// # Returns
+///
+/// The weight of each frequency bin for each filter, of shape `[n_freqs][n_mels]`.
+pub fn mel_filterbank(
+    n_freqs: usize,
+    sample_rate: usize,
+    n_mels: usize,
+    f_min: f32,
+    f_max: f32,
+) -> Vec<Vec<f32>> {
+    let nyquist = sample_rate as f64 / 2.0;
+    let (mel_min, mel_max) = (hz_to_mel(f_min as f64), hz_to_mel(f_max as f64));
+    let points = (0..n_mels + 2)
+        .map(|i| mel_to_hz(mel_min + (mel_max - mel_min) * i as f64 / (n_mels + 1) as f64))
+        .collect::<Vec<_>>();
+
+    (0..n_freqs)
+        .map(|bin| {
+            let frequency = match n_freqs {
+                1 => 0.0,
+                _ => nyquist * bin as f64 / (n_freqs - 1) as f64,
+            };
+
+            (0..n_mels)
+                .map(|m| {
+                    let rising = (frequency - points[m]) / (points[m + 1] - points[m]);
+                    let falling = (points[m + 2] - frequency) / (points[m + 2] - points[m + 1]);
+                    rising.min(falling).max(0.0) as f32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Converts power features to decibels, `10 * log10(max(x, 1e-10))`.
+///
+/// # Arguments
+///
+/// * `features` - The power features of each frame.
+/// * `top_db` - The maximum range of the decibels, the lower values being clamped to
+///   `max - top_db`.
+pub fn power_to_db(features: &[Vec<f32>], top_db: Option<f32>) -> Vec<Vec<f32>> {
+    let mut decibels = features
+        .iter()
+        .map(|frame| {
+            frame
+                .iter()
+                .map(|value| 10.0 * value.max(1e-10).log10())
+                .collect::<Vec<f32>>()
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(top_db) = top_db {
+        let max = decibels
+            .iter()
+            .flatten()
+            .fold(f32::NEG_INFINITY, |max, value| max.max(*value));
+
+        decibels
+            .iter_mut()
+            .flatten()
+            .for_each(|value| *value = value.max(max - top_db));
+    }
+
+    decibels
+}
+
+/// Orthonormal DCT-II matrix used to compute the MFCC, of shape `[n_mels][n_mfcc]`.
+fn dct_matrix(n_mels: usize, n_mfcc: usize) -> Vec<Vec<f32>> {
+    (0..n_mels)
+        .map(|n| {
+            (0..n_mfcc)
+                .map(|k| {
+                    let scale = match k {
+                        0 => (1.0 / n_mels as f64).sqrt(),
+                        _ => (2.0 / n_mels as f64).sqrt(),
+                    };
+                    let angle = PI / n_mels as f64 * (n as f64 + 0.5) * k as f64;
+                    (scale * angle.cos()) as f32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Mel-frequency cepstral coefficients of log-mel features, their orthonormal DCT-II.
+///
+/// # Arguments
+///
+/// * `log_mel` - The log-mel features of each frame.
+/// * `n_mfcc` - The number of coefficients to keep.
+pub fn mfcc(log_mel: &[Vec<f32>], n_mfcc: usize) -> Vec<Vec<f32>> {
+    let n_mels = log_mel.first().map(Vec::len).unwrap_or(0);
+    let dct = dct_matrix(n_mels, n_mfcc);
+
+    log_mel.iter().map(|frame| matmul(frame, &dct)).collect()
+}
+
+/// Multiplies a row vector by a matrix of shape `[row.len()][n]`.
+fn matmul(row: &[f32], matrix: &[Vec<f32>]) -> Vec<f32> {
+    let n = matrix.first().map(Vec::len).unwrap_or(0);
+
+    row.iter()
+        .zip(matrix.iter())
+        .fold(vec![0.0; n], |mut output, (value, weights)| {
+            output
+                .iter_mut()
+                .zip(weights.iter())
+                .for_each(|(output, weight)| *output += value * weight);
+            output
+        })
+}
+
+/// Speech item with the audio features of its samples.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeechFeatureItem {
+    /// Audio features of shape `[num_frames, num_features]`, stored frame after frame.
+    pub features: Vec<f32>,
+
+    /// The number of frames.
+    pub num_frames: usize,
+
+    /// The number of features of each frame.
+    pub num_features: usize,
+
+    /// The label of the audio.
+    pub label: SpeechCommandClass,
+}
+
+impl SpeechFeatureItem {
+    fn new(frames: Vec<Vec<f32>>, num_features: usize, label: SpeechCommandClass) -> Self {
+        Self {
+            num_frames: frames.len(),
+            features: frames.into_iter().flatten().collect(),
+            num_features,
+            label,
+        }
+    }
+}
+
+/// Resamples the audio samples of speech items to a fixed sample rate.
+#[derive(new, Debug, Clone, Copy)]
+pub struct Resample {
+    sample_rate: usize,
+}
+
+impl Mapper<SpeechItem, SpeechItem> for Resample {
+    fn map(&self, item: &SpeechItem) -> SpeechItem {
+        SpeechItem {
+            audio_samples: resample(&item.audio_samples, item.sample_rate, self.sample_rate),
+            sample_rate: self.sample_rate,
+            label: item.label,
+        }
+    }
+}
+
+/// Computes the power [spectrogram](spectrogram) of speech items.
+#[derive(Debug, Clone, Copy)]
+pub struct Spectrogram {
+    n_fft: usize,
+    hop_length: usize,
+    window: Window,
+    center: bool,
+}
+
+impl Spectrogram {
+    /// Creates a spectrogram with a Hann window and centered frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_fft` - The size of the Fourier transform, which is also the length of the frames.
+    /// * `hop_length` - The number of samples between the starts of consecutive frames.
+    pub fn new(n_fft: usize, hop_length: usize) -> Self {
+        Self {
+            n_fft,
+            hop_length,
+            window: Window::Hann,
+            center: true,
+        }
+    }
+
+    /// Sets the window function applied to the frames.
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets whether to center the frames on the samples `t * hop_length`.
+    pub fn with_center(mut self, center: bool) -> Self {
+        self.center = center;
+        self
+    }
+
+    fn compute(&self, samples: &[f32]) -> Vec<Vec<f32>> {
+        spectrogram(
+            samples,
+            self.n_fft,
+            self.hop_length,
+            self.window,
+            self.center,
+        )
+    }
+}
+
+impl Mapper<SpeechItem, SpeechFeatureItem> for Spectrogram {
+    fn map(&self, item: &SpeechItem) -> SpeechFeatureItem {
+        let frames = self.compute(&item.audio_samples);
+
+        SpeechFeatureItem::new(frames, self.n_fft / 2 + 1, item.label)
+    }
+}
+
+/// Computes the mel spectrogram of speech items, the power spectrogram filtered by a
+/// [mel filterbank](mel_filterbank) for the sample rate of each item.
+#[derive(Debug, Clone, Copy)]
+pub struct MelSpectrogram {
+    spectrogram: Spectrogram,
+    n_mels: usize,
+    f_min: f32,
+    f_max: Option<f32>,
+}
+
+impl MelSpectrogram {
+    /// Creates a mel spectrogram with a Hann window, centered frames and filters from 0 Hz to the
+    /// Nyquist frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_fft` - The size of the Fourier transform, which is also the length of the frames.
+    /// * `hop_length` - The number of samples between the starts of consecutive frames.
+    /// * `n_mels` - The number of mel filters.
+    pub fn new(n_fft: usize, hop_length: usize, n_mels: usize) -> Self {
+        Self {
+            spectrogram: Spectrogram::new(n_fft, hop_length),
+            n_mels,
+            f_min: 0.0,
+            f_max: None,
+        }
+    }
+
+    /// Sets the window function applied to the frames.
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.spectrogram = self.spectrogram.with_window(window);
+        self
+    }
+
+    /// Sets whether to center the frames on the samples `t * hop_length`.
+    pub fn with_center(mut self, center: bool) -> Self {
+        self.spectrogram = self.spectrogram.with_center(center);
+        self
+    }
+
+    /// Sets the lowest frequency of the filters, in Hz.
+    pub fn with_f_min(mut self, f_min: f32) -> Self {
+        self.f_min = f_min;
+        self
+    }
+
+    /// Sets the highest frequency of the filters, in Hz.
+    pub fn with_f_max(mut self, f_max: f32) -> Self {
+        self.f_max = Some(f_max);
+        self
+    }
+
+    fn compute(&self, samples: &[f32], sample_rate: usize) -> Vec<Vec<f32>> {
+        let filterbank = mel_filterbank(
+            self.spectrogram.n_fft / 2 + 1,
+            sample_rate,
+            self.n_mels,
+            self.f_min,
+            self.f_max.unwrap_or(sample_rate as f32 / 2.0),
+        );
+
+        self.spectrogram
+            .compute(samples)
+            .iter()
+            .map(|frame| matmul(frame, &filterbank))
+            .collect()
+    }
+}
+
+impl Mapper<SpeechItem, SpeechFeatureItem> for MelSpectrogram {
+    fn map(&self, item: &SpeechItem) -> SpeechFeatureItem {
+        let frames = self.compute(&item.audio_samples, item.sample_rate);
+
+        SpeechFeatureItem::new(frames, self.n_mels, item.label)
+    }
+}
+
+/// Computes the log-mel spectrogram of speech items, the [mel spectrogram](MelSpectrogram) in
+/// decibels.
+#[derive(Debug, Clone, Copy)]
+pub struct LogMelSpectrogram {
+    mel: MelSpectrogram,
+    top_db: Option<f32>,
+}
+
+impl LogMelSpectrogram {
+    /// Creates a log-mel spectrogram without limiting the range of the decibels.
+    pub fn new(mel: MelSpectrogram) -> Self {
+        Self { mel, top_db: None }
+    }
+
+    /// Sets the maximum range of the decibels of each item, e.g. 80 dB.
+    pub fn with_top_db(mut self, top_db: f32) -> Self {
+        self.top_db = Some(top_db);
+        self
+    }
+
+    fn compute(&self, samples: &[f32], sample_rate: usize) -> Vec<Vec<f32>> {
+        power_to_db(&self.mel.compute(samples, sample_rate), self.top_db)
+    }
+}
+
+impl Mapper<SpeechItem, SpeechFeatureItem> for LogMelSpectrogram {
+    fn map(&self, item: &SpeechItem) -> SpeechFeatureItem {
+        let frames = self.compute(&item.audio_samples, item.sample_rate);
+
+        SpeechFeatureItem::new(frames, self.mel.n_mels, item.label)
+    }
+}
+
+/// Computes the [mel-frequency cepstral coefficients](mfcc) of speech items from their
+/// [log-mel spectrogram](LogMelSpectrogram).
+#[derive(new, Debug, Clone, Copy)]
+pub struct Mfcc {
+    log_mel: LogMelSpectrogram,
+    n_mfcc: usize,
+}
+
+impl Mapper<SpeechItem, SpeechFeatureItem> for Mfcc {
+    fn map(&self, item: &SpeechItem) -> SpeechFeatureItem {
+        let log_mel = self.log_mel.compute(&item.audio_samples, item.sample_rate);
+
+        SpeechFeatureItem::new(mfcc(&log_mel, self.n_mfcc), self.n_mfcc, item.label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f64, sample_rate: usize, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| (2.0 * PI * frequency * n as f64 / sample_rate as f64).sin() as f32)
+            .collect()
+    }
+
+    fn argmax(values: &[f32]) -> usize {
+        values
+            .iter()
+            .enumerate()
+            .fold((0, f32::NEG_INFINITY), |best, (index, value)| {
+                match *value > best.1 {
+                    true => (index, *value),
+                    false => best,
+                }
+            })
+            .0
+    }
+
+    #[test]
+    fn frame_should_pad_by_reflection() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let frames = frame(&samples, 4, 2, true);
+
+        assert_eq!(
+            frames,
+            vec![
+                vec![3.0, 2.0, 1.0, 2.0],
+                vec![1.0, 2.0, 3.0, 4.0],
+                vec![3.0, 4.0, 5.0, 4.0],
+            ]
+        );
+        assert_eq!(frame(&samples, 4, 2, false).len(), 1);
+    }
+
+    #[test]
+    fn fft_should_match_dft() {
+        let samples = sine(3.0, 16, 16);
+        let mut fast = samples.iter().map(|x| (*x as f64, 0.0)).collect::<Vec<_>>();
+        // Dropping a sample switches to the direct DFT, the peak staying on the same bin.
+        let mut direct = fast.clone();
+        direct.truncate(15);
+
+        fft(&mut fast);
+        fft(&mut direct);
+
+        assert!((fast[3].1 + 8.0).abs() < 1e-5);
+        assert!(fast[2].0.abs() < 1e-5 && fast[2].1.abs() < 1e-5);
+        let magnitude = |(re, im): (f64, f64)| (re * re + im * im).sqrt();
+        let peak = (0..8).max_by(|a, b| magnitude(direct[*a]).total_cmp(&magnitude(direct[*b])));
+        assert_eq!(peak, Some(3));
+    }
+
+    #[test]
+    fn spectrogram_should_peak_at_signal_frequency() {
+        // 1 kHz at 16 kHz falls on the bin 32 of a 512 points FFT.
+        let samples = sine(1000.0, 16000, 4000);
+
+        let frames = spectrogram(&samples, 512, 256, Window::Hann, true);
+
+        assert_eq!(frames.len(), 1 + 4000 / 256);
+        assert_eq!(frames[0].len(), 257);
+        // Half of the first frame is made of reflected samples.
+        assert!(frames[1..].iter().all(|frame| argmax(frame) == 32));
+    }
+
+    #[test]
+    fn mel_filterbank_should_have_triangular_filters() {
+        let filterbank = mel_filterbank(257, 16000, 40, 0.0, 8000.0);
+
+        assert_eq!(filterbank.len(), 257);
+        assert!(filterbank.iter().all(|weights| weights.len() == 40));
+        assert!(filterbank
+            .iter()
+            .flatten()
+            .all(|weight| (0.0..=1.0).contains(weight)));
+        // Each filter has a single peak, moving up with the filters.
+        let peaks = (0..40)
+            .map(|m| argmax(&filterbank.iter().map(|w| w[m]).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        assert!(peaks.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(filterbank[0].iter().all(|weight| *weight == 0.0));
+    }
+
+    #[test]
+    fn resample_should_keep_frequency() {
+        let samples = sine(440.0, 16000, 16000);
+
+        let resampled = resample(&samples, 16000, 8000);
+        let expected = sine(440.0, 8000, 8000);
+
+        assert_eq!(resampled.len(), 8000);
+        // Away from the boundaries, the resampled signal matches the signal sampled at 8 kHz.
+        let error = resampled[100..7900]
+            .iter()
+            .zip(expected[100..7900].iter())
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max);
+        assert!(error < 0.01, "Error {error}");
+
+        let upsampled = resample(&expected, 8000, 16000);
+        assert_eq!(upsampled.len(), 16000);
+        let error = upsampled[200..15800]
+            .iter()
+            .zip(samples[200..15800].iter())
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max);
+        assert!(error < 0.01, "Error {error}");
+    }
+
+    #[test]
+    fn mfcc_of_constant_log_mel_should_only_have_first_coefficient() {
+        let log_mel = vec![vec![2.0; 16]; 3];
+
+        let coefficients = mfcc(&log_mel, 5);
+
+        assert_eq!(coefficients.len(), 3);
+        assert!((coefficients[0][0] - 8.0).abs() < 1e-5);
+        assert!(coefficients[0][1..].iter().all(|c| c.abs() < 1e-5));
+    }
+
+    #[test]
+    fn mappers_should_compute_features() {
+        let item = SpeechItem {
+            audio_samples: sine(1000.0, 16000, 16000),
+            sample_rate: 16000,
+            label: SpeechCommandClass::Yes,
+        };
+
+        let resampled = Resample::new(8000).map(&item);
+        assert_eq!(resampled.sample_rate, 8000);
+        assert_eq!(resampled.audio_samples.len(), 8000);
+
+        let mel = MelSpectrogram::new(400, 160, 40);
+        let features = LogMelSpectrogram::new(mel).with_top_db(80.0).map(&item);
+        assert_eq!(features.num_frames, 101);
+        assert_eq!(features.num_features, 40);
+        assert_eq!(features.features.len(), 101 * 40);
+        let max = features.features.iter().fold(f32::MIN, |a, b| a.max(*b));
+        assert!(features.features.iter().all(|x| *x >= max - 80.0));
+
+        let features = Mfcc::new(LogMelSpectrogram::new(mel), 13).map(&resampled);
+        assert_eq!(features.num_features, 13);
+        assert_eq!(features.num_frames, 1 + 8000 / 160);
+    }
+}
@@ -1,3 +1,5 @@
+mod features;
 mod speech_commands;
 
+pub use features::*;
 pub use speech_commands::*;
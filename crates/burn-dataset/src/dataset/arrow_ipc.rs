@@ -0,0 +1,284 @@
+use std::{
+    fs::File,
+    io::Read,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::Dataset;
+
+use super::record_batch::{deserialize_row, locate_row, ArrowDatasetError, RecordBatchCache};
+use arrow_array::RecordBatch;
+use arrow_ipc::reader::{FileReader, StreamReader};
+use arrow_schema::SchemaRef;
+use serde::de::DeserializeOwned;
+
+/// The magic bytes at the start of Arrow IPC files.
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+
+/// The number of decoded record batches kept in memory by default.
+const DEFAULT_CACHE_SIZE: usize = 2;
+
+/// Dataset where all items are stored in an [Arrow IPC](https://arrow.apache.org/docs/format/Columnar.html#serialization-and-interprocess-communication-ipc)
+/// file, also known as Feather (version 2).
+///
+/// Both the IPC file format and the IPC stream format (e.g. the `.arrow` files of the HuggingFace
+/// datasets cache) are supported:
+///
+/// - Files provide random access by record batch: reading an item decodes the record batch
+///   containing it, which is kept in a small cache so that reading the items in order is
+///   efficient.
+/// - Streams can only be read sequentially, so all their record batches are loaded in memory.
+///
+/// Each row is deserialized with serde, the same way as the [parquet dataset](super::ParquetDataset).
+#[derive(Debug)]
+pub struct ArrowIpcDataset<I> {
+    path: PathBuf,
+    source: IpcSource,
+    schema: SchemaRef,
+    offsets: Vec<usize>,
+    projection: Option<Vec<usize>>,
+    cache: RecordBatchCache,
+    phantom: PhantomData<I>,
+}
+
+#[derive(Debug)]
+enum IpcSource {
+    File,
+    Stream(Vec<RecordBatch>),
+}
+
+impl<I> ArrowIpcDataset<I> {
+    /// Initializes an `ArrowIpcDataset` from an Arrow IPC file or stream.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ArrowDatasetError> {
+        Self::open(path.as_ref(), None)
+    }
+
+    /// Initializes an `ArrowIpcDataset` only reading the given columns, the other columns of the
+    /// file aren't decoded.
+    pub fn from_file_with_columns<P: AsRef<Path>>(
+        path: P,
+        columns: &[&str],
+    ) -> Result<Self, ArrowDatasetError> {
+        let schema = if is_ipc_file(path.as_ref())? {
+            FileReader::try_new_buffered(File::open(path.as_ref())?, None)?.schema()
+        } else {
+            StreamReader::try_new_buffered(File::open(path.as_ref())?, None)?.schema()
+        };
+        let projection = columns
+            .iter()
+            .map(|column| {
+                schema
+                    .index_of(column)
+                    .map_err(|_| ArrowDatasetError::ColumnNotFound(column.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::open(path.as_ref(), Some(projection))
+    }
+
+    /// Sets the number of decoded record batches kept in memory.
+    ///
+    /// It has no effect on streams, which are always entirely loaded in memory.
+    pub fn with_cache_size(mut self, size: usize) -> Self {
+        self.cache = RecordBatchCache::new(size);
+        self
+    }
+
+    /// The names of the columns read by the dataset.
+    pub fn columns(&self) -> Vec<String> {
+        match &self.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|index| self.schema.field(*index).name().clone())
+                .collect(),
+            None => self
+                .schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+        }
+    }
+
+    /// The number of record batches of the file.
+    pub fn num_batches(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn open(path: &Path, projection: Option<Vec<usize>>) -> Result<Self, ArrowDatasetError> {
+        let mut offsets = vec![0];
+
+        let (source, schema) = if is_ipc_file(path)? {
+            // Only the number of rows of each batch is needed, so no column is decoded.
+            let reader = FileReader::try_new_buffered(File::open(path)?, Some(Vec::new()))?;
+            let schema = reader.schema();
+
+            for batch in reader {
+                offsets.push(offsets.last().unwrap() + batch?.num_rows());
+            }
+
+            (IpcSource::File, schema)
+        } else {
+            let reader = StreamReader::try_new_buffered(File::open(path)?, projection.clone())?;
+            let schema = reader.schema();
+            let batches = reader.collect::<Result<Vec<_>, _>>()?;
+
+            for batch in batches.iter() {
+                offsets.push(offsets.last().unwrap() + batch.num_rows());
+            }
+
+            (IpcSource::Stream(batches), schema)
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            source,
+            schema,
+            offsets,
+            projection,
+            cache: RecordBatchCache::new(DEFAULT_CACHE_SIZE),
+            phantom: PhantomData,
+        })
+    }
+
+    fn read_batch(&self, index: usize) -> Result<RecordBatch, ArrowDatasetError> {
+        let mut reader =
+            FileReader::try_new_buffered(File::open(&self.path)?, self.projection.clone())?;
+        reader.set_index(index)?;
+
+        match reader.next() {
+            Some(batch) => Ok(batch?),
+            None => Err(ArrowDatasetError::Other(format!(
+                "Record batch {index} of {} not found",
+                self.path.display()
+            ))),
+        }
+    }
+}
+
+/// Checks whether the file uses the IPC file format rather than the IPC stream format.
+fn is_ipc_file(path: &Path) -> Result<bool, ArrowDatasetError> {
+    let mut magic = [0; 6];
+    let is_file = File::open(path)?.read_exact(&mut magic).is_ok() && &magic == ARROW_MAGIC;
+
+    Ok(is_file)
+}
+
+impl<I> Dataset<I> for ArrowIpcDataset<I>
+where
+    I: DeserializeOwned + Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let (batch, row) = locate_row(&self.offsets, index)?;
+
+        let batch = match &self.source {
+            IpcSource::File => self
+                .cache
+                .get_or_load(batch, || self.read_batch(batch))
+                .expect("Can read the arrow record batch"),
+            IpcSource::Stream(batches) => Arc::new(batches[batch].clone()),
+        };
+
+        Some(deserialize_row(&batch, row).expect("Can deserialize the arrow row"))
+    }
+
+    fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int64Array, StringArray};
+    use arrow_ipc::writer::{FileWriter, StreamWriter};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: i64,
+        text: String,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Text {
+        text: String,
+    }
+
+    fn batch(ids: std::ops::Range<i64>) -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(ids.clone())) as _,
+            ),
+            (
+                "text",
+                Arc::new(StringArray::from_iter_values(
+                    ids.map(|id| format!("item {id}")),
+                )) as _,
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn item(id: i64) -> Item {
+        Item {
+            id,
+            text: format!("item {id}"),
+        }
+    }
+
+    #[test]
+    fn given_ipc_file_when_get_should_read_items_across_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.feather");
+        let mut writer =
+            FileWriter::try_new(File::create(&path).unwrap(), &batch(0..0).schema()).unwrap();
+        writer.write(&batch(0..3)).unwrap();
+        writer.write(&batch(3..5)).unwrap();
+        writer.finish().unwrap();
+
+        let dataset = ArrowIpcDataset::<Item>::from_file(&path).unwrap();
+
+        assert_eq!(dataset.len(), 5);
+        assert_eq!(dataset.num_batches(), 2);
+        assert_eq!(dataset.get(4), Some(item(4)));
+        assert_eq!(dataset.get(1), Some(item(1)));
+        assert_eq!(dataset.get(5), None);
+
+        let dataset = ArrowIpcDataset::<Text>::from_file_with_columns(&path, &["text"]).unwrap();
+
+        assert_eq!(dataset.columns(), vec!["text"]);
+        assert_eq!(
+            dataset.get(3),
+            Some(Text {
+                text: "item 3".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn given_ipc_stream_when_get_should_read_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.arrow");
+        let mut writer =
+            StreamWriter::try_new(File::create(&path).unwrap(), &batch(0..0).schema()).unwrap();
+        writer.write(&batch(0..2)).unwrap();
+        writer.write(&batch(2..4)).unwrap();
+        writer.finish().unwrap();
+
+        let dataset = ArrowIpcDataset::<Item>::from_file(&path).unwrap();
+
+        assert_eq!(dataset.len(), 4);
+        assert_eq!(
+            dataset.iter().collect::<Vec<_>>(),
+            (0..4).map(item).collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            ArrowIpcDataset::<Item>::from_file_with_columns(&path, &["label"]),
+            Err(ArrowDatasetError::ColumnNotFound(_))
+        ));
+    }
+}
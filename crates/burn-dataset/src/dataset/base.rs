@@ -7,6 +7,17 @@ pub trait Dataset<I>: Send + Sync {
     /// Gets the item at the given index.
     fn get(&self, index: usize) -> Option<I>;
 
+    /// Gets the item at the given index for the given epoch, counted from zero.
+    ///
+    /// Data loaders read their items with the number of iterations they created before, so that
+    /// datasets whose items change at every epoch, like random augmentations, can derive them
+    /// from the epoch rather than from the items read before. Datasets that don't depend on the
+    /// epoch return the same item as [get](Dataset::get).
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        let _ = epoch;
+        self.get(index)
+    }
+
     /// Gets the number of items in the dataset.
     fn len(&self) -> usize;
 
@@ -32,6 +43,10 @@ where
         self.as_ref().get(index)
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.as_ref().get_for_epoch(index, epoch)
+    }
+
     fn len(&self) -> usize {
         self.as_ref().len()
     }
@@ -42,6 +57,10 @@ impl<I> Dataset<I> for Arc<dyn Dataset<I>> {
         self.as_ref().get(index)
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.as_ref().get_for_epoch(index, epoch)
+    }
+
     fn len(&self) -> usize {
         self.as_ref().len()
     }
@@ -55,6 +74,10 @@ where
         self.as_ref().get(index)
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.as_ref().get_for_epoch(index, epoch)
+    }
+
     fn len(&self) -> usize {
         self.as_ref().len()
     }
@@ -65,6 +88,10 @@ impl<I> Dataset<I> for Box<dyn Dataset<I>> {
         self.as_ref().get(index)
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.as_ref().get_for_epoch(index, epoch)
+    }
+
     fn len(&self) -> usize {
         self.as_ref().len()
     }
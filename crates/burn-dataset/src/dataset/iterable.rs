@@ -0,0 +1,180 @@
+use std::{marker::PhantomData, sync::Arc};
+
+/// An iterator over the items of an [iterable dataset](IterableDataset).
+pub type IterableDatasetIterator<'a, I> = Box<dyn Iterator<Item = I> + Send + 'a>;
+
+/// The iterable dataset trait defines a stream of items that can only be iterated over, for data
+/// that doesn't provide random access or whose size isn't known, such as very large corpora or
+/// data generated on the fly.
+///
+/// The items are split into shards that can be iterated over independently, which allows the
+/// shards to be distributed across multiple workers without duplicating any item.
+pub trait IterableDataset<I>: Send + Sync {
+    /// The number of shards of the dataset.
+    fn num_shards(&self) -> usize {
+        1
+    }
+
+    /// Returns an iterator over the items of the given shard.
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I>;
+
+    /// The number of items of the dataset, if known.
+    fn len_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns an iterator over the items of all the shards, one shard after the other.
+    fn iter(&self) -> IterableDatasetIterator<'_, I>
+    where
+        I: 'static,
+    {
+        self.iter_shards((0..self.num_shards()).collect())
+    }
+
+    /// Returns an iterator over the items of the given shards, one shard after the other.
+    fn iter_shards(&self, shards: Vec<usize>) -> IterableDatasetIterator<'_, I>
+    where
+        I: 'static,
+    {
+        Box::new(shards.into_iter().flat_map(|shard| self.iter_shard(shard)))
+    }
+}
+
+impl<D, I> IterableDataset<I> for Arc<D>
+where
+    D: IterableDataset<I>,
+{
+    fn num_shards(&self) -> usize {
+        self.as_ref().num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        self.as_ref().iter_shard(shard)
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.as_ref().len_hint()
+    }
+}
+
+impl<I> IterableDataset<I> for Arc<dyn IterableDataset<I>> {
+    fn num_shards(&self) -> usize {
+        self.as_ref().num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        self.as_ref().iter_shard(shard)
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.as_ref().len_hint()
+    }
+}
+
+impl<D, I> IterableDataset<I> for Box<D>
+where
+    D: IterableDataset<I>,
+{
+    fn num_shards(&self) -> usize {
+        self.as_ref().num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        self.as_ref().iter_shard(shard)
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.as_ref().len_hint()
+    }
+}
+
+impl<I> IterableDataset<I> for Box<dyn IterableDataset<I>> {
+    fn num_shards(&self) -> usize {
+        self.as_ref().num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        self.as_ref().iter_shard(shard)
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.as_ref().len_hint()
+    }
+}
+
+/// Iterable dataset whose shards are generated by a function, e.g. reading one file per shard or
+/// producing synthetic items.
+pub struct GeneratorDataset<F, It, I> {
+    generator: F,
+    num_shards: usize,
+    len: Option<usize>,
+    _types: PhantomData<fn() -> (It, I)>,
+}
+
+impl<F, It, I> GeneratorDataset<F, It, I>
+where
+    F: Fn(usize) -> It + Send + Sync,
+    It: Iterator<Item = I> + Send,
+{
+    /// Creates a new generator dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_shards` - The number of shards.
+    /// * `generator` - The function returning an iterator over the items of a shard.
+    ///
+    /// # Returns
+    ///
+    /// The generator dataset.
+    pub fn new(num_shards: usize, generator: F) -> Self {
+        Self {
+            generator,
+            num_shards,
+            len: None,
+            _types: PhantomData,
+        }
+    }
+
+    /// Sets the number of items of the dataset, when known.
+    pub fn with_len(mut self, len: usize) -> Self {
+        self.len = Some(len);
+        self
+    }
+}
+
+impl<F, It, I> IterableDataset<I> for GeneratorDataset<F, It, I>
+where
+    F: Fn(usize) -> It + Send + Sync,
+    It: Iterator<Item = I> + Send,
+{
+    fn num_shards(&self) -> usize {
+        self.num_shards
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        Box::new((self.generator)(shard))
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_generator_dataset_when_iterate_should_chain_shards() {
+        let dataset = GeneratorDataset::new(3, |shard| (0..2).map(move |i| shard * 10 + i));
+
+        let items: Vec<usize> = dataset.iter().collect();
+
+        assert_eq!(dataset.num_shards(), 3);
+        assert_eq!(items, vec![0, 1, 10, 11, 20, 21]);
+        assert_eq!(
+            dataset.iter_shards(vec![2, 0]).collect::<Vec<_>>(),
+            vec![20, 21, 0, 1]
+        );
+    }
+}
@@ -1,9 +1,11 @@
 mod base;
 mod in_memory;
+mod iterable;
 mod iterator;
 
 pub use base::*;
 pub use in_memory::*;
+pub use iterable::*;
 pub use iterator::*;
 
 #[cfg(any(test, feature = "fake"))]
@@ -23,3 +25,25 @@ pub use sqlite::*;
 
 #[cfg(any(feature = "sqlite", feature = "sqlite-bundled"))]
 mod sqlite;
+
+#[cfg(feature = "parquet")]
+mod arrow_ipc;
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "parquet")]
+mod record_batch;
+
+#[cfg(feature = "parquet")]
+pub use self::{arrow_ipc::*, parquet::*, record_batch::ArrowDatasetError};
+
+#[cfg(feature = "webdataset")]
+mod webdataset;
+
+#[cfg(feature = "webdataset")]
+pub use webdataset::*;
+
+#[cfg(feature = "npz")]
+mod npz;
+
+#[cfg(feature = "npz")]
+pub use npz::*;
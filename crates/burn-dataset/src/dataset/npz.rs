@@ -0,0 +1,412 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    marker::PhantomData,
+    path::Path,
+};
+
+use crate::Dataset;
+
+use burn_tensor::{NpyError, NpyHeader, TensorData};
+use memmap2::Mmap;
+use zip::{result::ZipError, CompressionMethod, ZipArchive};
+
+/// NumPy archive dataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum NpzDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Zip archive related error.
+    #[error("Zip error: {0}")]
+    Zip(#[from] ZipError),
+
+    /// Error when reading an array.
+    #[error("Npy error: {0}")]
+    Npy(#[from] NpyError),
+
+    /// The array doesn't exist in the archive.
+    #[error("Array not found: {0}")]
+    ArrayNotFound(String),
+
+    /// Any other error.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// An item of a [NumPy archive dataset](NpzDataset), built from the slices of the arrays at the
+/// same index of their first axis.
+pub trait NpzItem: Sized {
+    /// The number of arrays needed to build an item, if fixed.
+    fn num_arrays() -> Option<usize>;
+
+    /// Builds an item from the slices of the arrays, in the order of the arrays of the dataset.
+    fn from_slices(slices: Vec<TensorData>) -> Self;
+}
+
+impl NpzItem for Vec<TensorData> {
+    fn num_arrays() -> Option<usize> {
+        None
+    }
+
+    fn from_slices(slices: Vec<TensorData>) -> Self {
+        slices
+    }
+}
+
+impl NpzItem for TensorData {
+    fn num_arrays() -> Option<usize> {
+        Some(1)
+    }
+
+    fn from_slices(slices: Vec<TensorData>) -> Self {
+        let [slice] = <[TensorData; 1]>::try_from(slices).unwrap();
+        slice
+    }
+}
+
+impl NpzItem for (TensorData, TensorData) {
+    fn num_arrays() -> Option<usize> {
+        Some(2)
+    }
+
+    fn from_slices(slices: Vec<TensorData>) -> Self {
+        let [a, b] = <[TensorData; 2]>::try_from(slices).unwrap();
+        (a, b)
+    }
+}
+
+impl NpzItem for (TensorData, TensorData, TensorData) {
+    fn num_arrays() -> Option<usize> {
+        Some(3)
+    }
+
+    fn from_slices(slices: Vec<TensorData>) -> Self {
+        let [a, b, c] = <[TensorData; 3]>::try_from(slices).unwrap();
+        (a, b, c)
+    }
+}
+
+/// Dataset indexing the first axis of the arrays of a [NumPy](https://numpy.org/) `.npz` archive,
+/// e.g. the features and the labels saved with `np.savez("data.npz", x=x, y=y)`.
+///
+/// The item at index `i` is built from the slices `array[i]` of each array, as a tuple of
+/// [TensorData] (or a vector for any number of arrays). All the arrays must have the same size
+/// along their first axis.
+///
+/// Arrays stored without compression (`np.savez`) are memory-mapped, so only the slices read are
+/// loaded in memory. Compressed arrays (`np.savez_compressed`) are decompressed in memory when the
+/// dataset is created.
+///
+/// The archive must not be modified while the dataset is used.
+pub struct NpzDataset<I> {
+    arrays: Vec<NpzArray>,
+    mmap: Mmap,
+    len: usize,
+    phantom: PhantomData<I>,
+}
+
+struct NpzArray {
+    header: NpyHeader,
+    data: NpzArrayData,
+}
+
+enum NpzArrayData {
+    /// Offset of the values in the memory-mapped archive.
+    Mapped(usize),
+    /// Decompressed values.
+    Loaded(Vec<u8>),
+}
+
+impl<I: NpzItem> NpzDataset<I> {
+    /// Creates a dataset from all the arrays of a `.npz` archive, in the order of the archive.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, NpzDatasetError> {
+        Self::open(path.as_ref(), None)
+    }
+
+    /// Creates a dataset from the given arrays of a `.npz` archive, in the given order.
+    pub fn with_arrays<P: AsRef<Path>>(path: P, names: &[&str]) -> Result<Self, NpzDatasetError> {
+        Self::open(path.as_ref(), Some(names))
+    }
+
+    fn open(path: &Path, names: Option<&[&str]>) -> Result<Self, NpzDatasetError> {
+        let file = File::open(path)?;
+        // SAFETY: The archive must not be modified while the dataset is used, as documented.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let mut archive = ZipArchive::new(file)?;
+
+        let names = match names {
+            Some(names) => names.iter().map(|name| name.to_string()).collect(),
+            None => archive
+                .file_names()
+                .filter_map(|name| name.strip_suffix(".npy"))
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+        };
+
+        if let Some(num_arrays) = I::num_arrays() {
+            if names.len() != num_arrays {
+                return Err(NpzDatasetError::Other(format!(
+                    "The item needs {num_arrays} arrays, got {}",
+                    names.len()
+                )));
+            }
+        }
+
+        let mut arrays = Vec::with_capacity(names.len());
+        let mut len = None;
+
+        for name in names {
+            let array = match archive.by_name(&format!("{name}.npy")) {
+                Ok(file) => read_array(file, &mmap)?,
+                Err(ZipError::FileNotFound) => return Err(NpzDatasetError::ArrayNotFound(name)),
+                Err(err) => return Err(err.into()),
+            };
+
+            let size = *array.header.shape.first().ok_or_else(|| {
+                NpzDatasetError::Other(format!("The array {name} has no axis to index"))
+            })?;
+
+            match len {
+                Some(len) if len != size => {
+                    return Err(NpzDatasetError::Other(format!(
+                        "The array {name} has {size} items instead of {len}"
+                    )))
+                }
+                _ => len = Some(size),
+            }
+
+            arrays.push(array);
+        }
+
+        Ok(Self {
+            arrays,
+            mmap,
+            len: len.unwrap_or(0),
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// Reads the header of an array of the archive, and its values when they are compressed.
+fn read_array(mut file: zip::read::ZipFile, mmap: &Mmap) -> Result<NpzArray, NpzDatasetError> {
+    if file.compression() == CompressionMethod::Stored {
+        let offset = file.data_start() as usize;
+        let bytes = mmap
+            .get(offset..offset + file.size() as usize)
+            .ok_or_else(|| {
+                NpzDatasetError::Other(format!("The array {} is truncated", file.name()))
+            })?;
+        let (header, header_len) = NpyHeader::parse(bytes)?;
+
+        if header.data_size() > bytes.len() - header_len {
+            return Err(NpzDatasetError::Other(format!(
+                "The array {} is truncated",
+                file.name()
+            )));
+        }
+
+        Ok(NpzArray {
+            header,
+            data: NpzArrayData::Mapped(offset + header_len),
+        })
+    } else {
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)?;
+        let (header, header_len) = NpyHeader::parse(&bytes)?;
+        bytes.drain(..header_len);
+
+        if bytes.len() != header.data_size() {
+            return Err(NpyError::InvalidSize {
+                expected: header.data_size(),
+                actual: bytes.len(),
+            }
+            .into());
+        }
+
+        Ok(NpzArray {
+            header,
+            data: NpzArrayData::Loaded(bytes),
+        })
+    }
+}
+
+impl NpzArray {
+    /// Reads the slice `array[index]`.
+    fn slice(&self, mmap: &Mmap, index: usize) -> Result<TensorData, NpyError> {
+        let values = match &self.data {
+            NpzArrayData::Mapped(offset) => &mmap[*offset..*offset + self.header.data_size()],
+            NpzArrayData::Loaded(values) => values.as_slice(),
+        };
+
+        let len = self.header.shape[0];
+        let header = NpyHeader {
+            shape: self.header.shape[1..].to_vec(),
+            ..self.header.clone()
+        };
+        let elem_size = header.elem_size();
+        let slice_size = header.data_size();
+
+        if !self.header.fortran_order {
+            return header.decode(&values[index * slice_size..(index + 1) * slice_size]);
+        }
+
+        // The values of the slice are strided by the size of the first axis.
+        let bytes = (0..slice_size / elem_size)
+            .flat_map(|i| {
+                let offset = (index + i * len) * elem_size;
+                values[offset..offset + elem_size].iter().copied()
+            })
+            .collect::<Vec<_>>();
+
+        header.decode(&bytes)
+    }
+}
+
+impl<I> Dataset<I> for NpzDataset<I>
+where
+    I: NpzItem + Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        if index >= self.len {
+            return None;
+        }
+
+        let slices = self
+            .arrays
+            .iter()
+            .map(|array| {
+                array
+                    .slice(&self.mmap, index)
+                    .expect("Can read the slice of the array")
+            })
+            .collect();
+
+        Some(I::from_slices(slices))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn write_npz(path: &Path, compression: CompressionMethod, arrays: &[(&str, Vec<u8>)]) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(compression);
+
+        for (name, npy) in arrays {
+            writer.start_file(format!("{name}.npy"), options).unwrap();
+            writer.write_all(npy).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    /// A column-major `.npy` array with the values of `[[0, 1, 2], [3, 4, 5]]`.
+    fn fortran_npy() -> Vec<u8> {
+        let header = NpyHeader {
+            dtype: burn_tensor::DType::I64,
+            shape: vec![2, 3],
+            fortran_order: true,
+            big_endian: cfg!(target_endian = "big"),
+        };
+        let mut npy = header.encode().unwrap();
+        for value in [0i64, 3, 1, 4, 2, 5] {
+            npy.extend_from_slice(&value.to_ne_bytes());
+        }
+        npy
+    }
+
+    #[test]
+    fn given_stored_and_compressed_arrays_when_get_should_slice_first_axis() {
+        for compression in [CompressionMethod::Stored, CompressionMethod::Deflated] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("data.npz");
+            let x = TensorData::new(vec![0f32, 1., 2., 3., 4., 5.], [3, 2]);
+            let y = TensorData::new(vec![7u8, 8, 9], [3]);
+            write_npz(
+                &path,
+                compression,
+                &[("x", x.to_npy().unwrap()), ("y", y.to_npy().unwrap())],
+            );
+
+            let dataset = NpzDataset::<(TensorData, TensorData)>::new(&path).unwrap();
+
+            assert_eq!(dataset.len(), 3);
+            assert_eq!(
+                dataset.get(1),
+                Some((
+                    TensorData::new(vec![2f32, 3.], [2]),
+                    TensorData::new(vec![8u8], [])
+                ))
+            );
+            assert_eq!(dataset.get(3), None);
+
+            let dataset = NpzDataset::<TensorData>::with_arrays(&path, &["y"]).unwrap();
+            assert_eq!(dataset.get(2), Some(TensorData::new(vec![9u8], [])));
+        }
+    }
+
+    #[test]
+    fn given_fortran_order_array_when_get_should_slice_first_axis() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.npz");
+        write_npz(&path, CompressionMethod::Stored, &[("x", fortran_npy())]);
+
+        let dataset = NpzDataset::<Vec<TensorData>>::new(&path).unwrap();
+
+        assert_eq!(
+            dataset.get(1),
+            Some(vec![TensorData::new(vec![3i64, 4, 5], [3])])
+        );
+    }
+
+    #[test]
+    fn given_invalid_arrays_when_new_should_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.npz");
+        let x = TensorData::new(vec![0f32; 4], [4]);
+        let y = TensorData::new(vec![0f32; 3], [3]);
+        write_npz(
+            &path,
+            CompressionMethod::Stored,
+            &[("x", x.to_npy().unwrap()), ("y", y.to_npy().unwrap())],
+        );
+
+        assert!(matches!(
+            NpzDataset::<Vec<TensorData>>::new(&path),
+            Err(NpzDatasetError::Other(_))
+        ));
+        assert!(matches!(
+            NpzDataset::<TensorData>::with_arrays(&path, &["z"]),
+            Err(NpzDatasetError::ArrayNotFound(_))
+        ));
+        assert!(matches!(
+            NpzDataset::<(TensorData, TensorData)>::with_arrays(&path, &["x"]),
+            Err(NpzDatasetError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn given_truncated_compressed_array_when_new_should_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.npz");
+        let mut npy = TensorData::new(vec![0f32; 4], [4]).to_npy().unwrap();
+        npy.truncate(npy.len() - 4);
+        write_npz(&path, CompressionMethod::Deflated, &[("x", npy)]);
+
+        assert!(matches!(
+            NpzDataset::<TensorData>::new(&path),
+            Err(NpzDatasetError::Npy(NpyError::InvalidSize {
+                expected: 16,
+                actual: 12
+            }))
+        ));
+    }
+}
@@ -0,0 +1,501 @@
+use std::{
+    fs::{self, File},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use crate::Dataset;
+
+use super::record_batch::{deserialize_row, locate_row, ArrowDatasetError, RecordBatchCache};
+use parquet::arrow::{
+    arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder},
+    ProjectionMask,
+};
+use serde::de::DeserializeOwned;
+
+/// The number of decoded row groups kept in memory by default.
+const DEFAULT_CACHE_SIZE: usize = 2;
+
+/// Dataset where all items are stored in [parquet](https://parquet.apache.org/) files.
+///
+/// Rows are read by row group: reading an item decodes the row group containing it, which is kept
+/// in a small cache so that reading the items in order is efficient. Random access is supported,
+/// although shuffling the items of large row groups may decode the same row group many times.
+///
+/// Each row is deserialized with serde, the column names matching the field names of the `I`
+/// struct. Nested columns are supported: lists are deserialized as sequences, structs and maps as
+/// structs or maps, and binary columns as sequences of bytes (e.g. `Vec<u8>`).
+///
+/// Only the columns needed can be read with [with_columns](ParquetDataset::with_columns), which
+/// can greatly reduce the amount of data decoded.
+#[derive(Debug)]
+pub struct ParquetDataset<I> {
+    files: Vec<ParquetFile>,
+    row_groups: Vec<(usize, usize)>,
+    offsets: Vec<usize>,
+    columns: Option<Vec<String>>,
+    cache: RecordBatchCache,
+    phantom: PhantomData<I>,
+}
+
+#[derive(Debug)]
+struct ParquetFile {
+    path: PathBuf,
+    metadata: ArrowReaderMetadata,
+}
+
+impl<I> ParquetDataset<I> {
+    /// Initializes a `ParquetDataset` from a single parquet file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ArrowDatasetError> {
+        Self::from_files([path])
+    }
+
+    /// Initializes a `ParquetDataset` from multiple parquet files, with the items of each file
+    /// following the items of the previous one.
+    ///
+    /// All the files must contain the columns read by the dataset.
+    pub fn from_files<P, It>(paths: It) -> Result<Self, ArrowDatasetError>
+    where
+        P: AsRef<Path>,
+        It: IntoIterator<Item = P>,
+    {
+        let mut files = Vec::new();
+        let mut row_groups = Vec::new();
+        let mut offsets = vec![0];
+
+        for path in paths {
+            let path = path.as_ref().to_path_buf();
+            let metadata =
+                ArrowReaderMetadata::load(&File::open(&path)?, ArrowReaderOptions::default())?;
+
+            for (row_group, meta) in metadata.metadata().row_groups().iter().enumerate() {
+                let len = offsets.last().unwrap() + meta.num_rows() as usize;
+                row_groups.push((files.len(), row_group));
+                offsets.push(len);
+            }
+
+            files.push(ParquetFile { path, metadata });
+        }
+
+        Ok(Self {
+            files,
+            row_groups,
+            offsets,
+            columns: None,
+            cache: RecordBatchCache::new(DEFAULT_CACHE_SIZE),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Initializes a `ParquetDataset` from all the parquet files in a directory and its
+    /// subdirectories, sorted by path.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, ArrowDatasetError> {
+        let files = find_parquet_files(dir.as_ref())?;
+
+        if files.is_empty() {
+            return Err(ArrowDatasetError::Other(format!(
+                "No parquet file found in {}",
+                dir.as_ref().display()
+            )));
+        }
+
+        Self::from_files(files)
+    }
+
+    /// Initializes a `ParquetDataset` from the parquet files of a HuggingFace dataset that were
+    /// already downloaded in the local [HuggingFace cache](huggingface_hub_cache_dir).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dataset, e.g. `ylecun/mnist`.
+    /// * `subset` - The subset (configuration) of the dataset, if it has many.
+    /// * `split` - The split of the dataset, e.g. `train`.
+    pub fn from_huggingface_cache(
+        name: &str,
+        subset: Option<&str>,
+        split: &str,
+    ) -> Result<Self, ArrowDatasetError> {
+        let cache_dir = huggingface_hub_cache_dir().ok_or_else(|| {
+            ArrowDatasetError::Other("Could not find the HuggingFace cache directory".to_string())
+        })?;
+        let files = huggingface_parquet_files(cache_dir, name, subset, split)?;
+
+        Self::from_files(files)
+    }
+
+    /// Only read the given columns, the other columns of the files aren't decoded.
+    pub fn with_columns(mut self, columns: &[&str]) -> Result<Self, ArrowDatasetError> {
+        for file in self.files.iter() {
+            let schema = file.metadata.schema();
+
+            for column in columns {
+                if schema.column_with_name(column).is_none() {
+                    return Err(ArrowDatasetError::ColumnNotFound(column.to_string()));
+                }
+            }
+        }
+
+        self.columns = Some(columns.iter().map(|column| column.to_string()).collect());
+        self.cache.clear();
+
+        Ok(self)
+    }
+
+    /// Sets the number of decoded row groups kept in memory.
+    ///
+    /// Multiple row groups should be cached when the items are read from many threads or in an
+    /// order interleaving row groups.
+    pub fn with_cache_size(mut self, size: usize) -> Self {
+        self.cache = RecordBatchCache::new(size);
+        self
+    }
+
+    /// The names of the columns read by the dataset.
+    pub fn columns(&self) -> Vec<String> {
+        match (&self.columns, self.files.first()) {
+            (Some(columns), _) => columns.clone(),
+            (None, Some(file)) => file
+                .metadata
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// The number of row groups of all the files.
+    pub fn num_row_groups(&self) -> usize {
+        self.row_groups.len()
+    }
+
+    fn read_row_group(
+        &self,
+        row_group: usize,
+    ) -> Result<arrow_array::RecordBatch, ArrowDatasetError> {
+        let (file, index) = self.row_groups[row_group];
+        let file = &self.files[file];
+        let num_rows = self.offsets[row_group + 1] - self.offsets[row_group];
+
+        let mut builder = ParquetRecordBatchReaderBuilder::new_with_metadata(
+            File::open(&file.path)?,
+            file.metadata.clone(),
+        )
+        .with_row_groups(vec![index])
+        .with_batch_size(num_rows.max(1));
+
+        if let Some(columns) = &self.columns {
+            let schema = file.metadata.schema();
+            let indices = columns
+                .iter()
+                .map(|column| {
+                    schema
+                        .index_of(column)
+                        .map_err(|_| ArrowDatasetError::ColumnNotFound(column.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mask = ProjectionMask::roots(file.metadata.parquet_schema(), indices);
+            builder = builder.with_projection(mask);
+        }
+
+        let mut reader = builder.build()?;
+
+        // The batch size is the number of rows of the row group, so it is read as a single batch.
+        match reader.next() {
+            Some(batch) => Ok(batch?),
+            None => Err(ArrowDatasetError::Other(format!(
+                "Row group {index} of {} is empty",
+                file.path.display()
+            ))),
+        }
+    }
+}
+
+impl<I> Dataset<I> for ParquetDataset<I>
+where
+    I: DeserializeOwned + Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let (row_group, row) = locate_row(&self.offsets, index)?;
+
+        let batch = self
+            .cache
+            .get_or_load(row_group, || self.read_row_group(row_group))
+            .expect("Can read the parquet row group");
+
+        Some(deserialize_row(&batch, row).expect("Can deserialize the parquet row"))
+    }
+
+    fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+}
+
+/// Returns the directory of the local HuggingFace hub cache, where the files of the downloaded
+/// datasets are stored.
+///
+/// The directory is `$HF_HUB_CACHE` if set, `$HF_HOME/hub` if `$HF_HOME` is set, and
+/// `~/.cache/huggingface/hub` otherwise.
+pub fn huggingface_hub_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("HF_HUB_CACHE") {
+        return Some(PathBuf::from(dir));
+    }
+
+    if let Some(dir) = std::env::var_os("HF_HOME") {
+        return Some(PathBuf::from(dir).join("hub"));
+    }
+
+    dirs::home_dir().map(|home| home.join(".cache").join("huggingface").join("hub"))
+}
+
+/// Finds the parquet files of a split of a dataset in a HuggingFace hub cache directory.
+///
+/// The files are searched in the snapshot of the `main` revision first, then in the snapshot of
+/// the parquet conversion made by the hub (`refs/convert/parquet`), and finally in any other
+/// snapshot. A file belongs to the split when its name starts with the split name (e.g.
+/// `train-00000-of-00002.parquet`) or when it is in a directory named after the split (e.g.
+/// `default/train/0000.parquet`).
+///
+/// # Arguments
+///
+/// * `cache_dir` - The HuggingFace hub cache directory.
+/// * `name` - The name of the dataset, e.g. `ylecun/mnist`.
+/// * `subset` - The subset (configuration) of the dataset, only files in a directory named after
+///   the subset are kept when given.
+/// * `split` - The split of the dataset, e.g. `train`.
+///
+/// # Returns
+///
+/// The parquet files sorted by path.
+pub fn huggingface_parquet_files<P: AsRef<Path>>(
+    cache_dir: P,
+    name: &str,
+    subset: Option<&str>,
+    split: &str,
+) -> Result<Vec<PathBuf>, ArrowDatasetError> {
+    let repo_dir = cache_dir
+        .as_ref()
+        .join(format!("datasets--{}", name.replace('/', "--")));
+    let snapshots_dir = repo_dir.join("snapshots");
+
+    if !snapshots_dir.is_dir() {
+        return Err(ArrowDatasetError::Other(format!(
+            "Dataset {name} not found in the HuggingFace cache {}",
+            cache_dir.as_ref().display()
+        )));
+    }
+
+    let mut snapshots = Vec::new();
+    for reference in ["main", "convert/parquet"] {
+        if let Ok(commit) = fs::read_to_string(repo_dir.join("refs").join(reference)) {
+            snapshots.push(snapshots_dir.join(commit.trim()));
+        }
+    }
+
+    let mut others = fs::read_dir(&snapshots_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    others.sort();
+    snapshots.extend(others);
+
+    for snapshot in snapshots.iter().filter(|snapshot| snapshot.is_dir()) {
+        let files = find_parquet_files(snapshot)?
+            .into_iter()
+            .filter(|file| {
+                let relative = file.strip_prefix(snapshot).unwrap_or(file);
+                let components = relative
+                    .parent()
+                    .into_iter()
+                    .flat_map(|parent| parent.iter())
+                    .filter_map(|component| component.to_str())
+                    .collect::<Vec<_>>();
+                let file_name = relative
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default();
+
+                let in_subset = match subset {
+                    Some(subset) => components.contains(&subset),
+                    None => true,
+                };
+                let in_split = components.iter().any(|component| {
+                    *component == split || *component == format!("partial-{split}")
+                }) || file_name == split
+                    || file_name.starts_with(&format!("{split}-"));
+
+                in_subset && in_split
+            })
+            .collect::<Vec<_>>();
+
+        if !files.is_empty() {
+            return Ok(files);
+        }
+    }
+
+    Err(ArrowDatasetError::Other(format!(
+        "No parquet file found for the split {split} of the dataset {name} in the HuggingFace \
+         cache {}",
+        cache_dir.as_ref().display()
+    )))
+}
+
+/// Finds all the parquet files in a directory and its subdirectories, sorted by path.
+fn find_parquet_files(dir: &Path) -> Result<Vec<PathBuf>, ArrowDatasetError> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            // Files of the HuggingFace cache are symbolic links, which are followed here.
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "parquet")
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Float32Array, Int64Array, RecordBatch, StringArray};
+    use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    #[derive(Deserialize, Debug, PartialEq, Clone)]
+    struct Item {
+        id: i64,
+        text: String,
+        score: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Id {
+        id: i64,
+    }
+
+    /// Writes a parquet file with the ids `start..start + len`, in row groups of 2 rows.
+    fn write_parquet(path: &Path, start: i64, len: i64) {
+        let ids = (start..start + len).collect::<Vec<_>>();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids.clone())) as _),
+            (
+                "text",
+                Arc::new(StringArray::from_iter_values(
+                    ids.iter().map(|id| format!("item {id}")),
+                )) as _,
+            ),
+            (
+                "score",
+                Arc::new(Float32Array::from_iter_values(
+                    ids.iter().map(|id| *id as f32 / 2.0),
+                )) as _,
+            ),
+        ])
+        .unwrap();
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(2)
+            .build();
+        let mut writer = ArrowWriter::try_new(
+            File::create(path).unwrap(),
+            batch.schema(),
+            Some(properties),
+        )
+        .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    fn item(id: i64) -> Item {
+        Item {
+            id,
+            text: format!("item {id}"),
+            score: id as f32 / 2.0,
+        }
+    }
+
+    #[test]
+    fn given_parquet_files_when_get_should_read_items_across_row_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        write_parquet(&dir.path().join("a.parquet"), 0, 5);
+        write_parquet(&dir.path().join("b.parquet"), 5, 2);
+
+        let dataset = ParquetDataset::<Item>::from_dir(dir.path()).unwrap();
+
+        assert_eq!(dataset.len(), 7);
+        assert_eq!(dataset.num_row_groups(), 4);
+        assert_eq!(dataset.columns(), vec!["id", "text", "score"]);
+        assert_eq!(dataset.get(6), Some(item(6)));
+        assert_eq!(dataset.get(3), Some(item(3)));
+        assert_eq!(dataset.get(7), None);
+        assert_eq!(
+            dataset.iter().collect::<Vec<_>>(),
+            (0..7).map(item).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn given_columns_when_get_should_only_read_projected_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        write_parquet(&path, 0, 3);
+
+        let dataset = ParquetDataset::<Id>::from_file(&path)
+            .unwrap()
+            .with_columns(&["id"])
+            .unwrap();
+
+        assert_eq!(dataset.columns(), vec!["id"]);
+        assert_eq!(dataset.get(2), Some(Id { id: 2 }));
+        assert!(matches!(
+            ParquetDataset::<Id>::from_file(&path)
+                .unwrap()
+                .with_columns(&["label"]),
+            Err(ArrowDatasetError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn given_huggingface_cache_when_find_files_should_select_split_and_subset() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("datasets--user--data");
+        let snapshot = repo.join("snapshots").join("abc123");
+        fs::create_dir_all(repo.join("refs")).unwrap();
+        fs::write(repo.join("refs").join("main"), "abc123").unwrap();
+
+        write_parquet(&snapshot.join("data/train-00000-of-00002.parquet"), 0, 2);
+        write_parquet(&snapshot.join("data/train-00001-of-00002.parquet"), 2, 2);
+        write_parquet(&snapshot.join("data/test-00000-of-00001.parquet"), 4, 1);
+        write_parquet(&snapshot.join("extra/train/0000.parquet"), 5, 1);
+
+        let train = huggingface_parquet_files(dir.path(), "user/data", Some("data"), "train");
+        let test = huggingface_parquet_files(dir.path(), "user/data", None, "test").unwrap();
+        let extra = huggingface_parquet_files(dir.path(), "user/data", Some("extra"), "train");
+
+        assert_eq!(train.unwrap().len(), 2);
+        assert_eq!(
+            test,
+            vec![snapshot.join("data/test-00000-of-00001.parquet")]
+        );
+        assert_eq!(
+            extra.unwrap(),
+            vec![snapshot.join("extra/train/0000.parquet")]
+        );
+        assert!(huggingface_parquet_files(dir.path(), "user/data", None, "validation").is_err());
+        assert!(huggingface_parquet_files(dir.path(), "user/other", None, "train").is_err());
+    }
+}
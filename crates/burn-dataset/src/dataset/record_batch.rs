@@ -0,0 +1,320 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use arrow_array::{cast::AsArray, types::*, Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{ArrowError, DataType};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+/// Error of the datasets reading [Arrow](https://arrow.apache.org/) record batches, i.e. the
+/// [parquet](super::ParquetDataset) and [Arrow IPC](super::ArrowIpcDataset) datasets.
+#[derive(thiserror::Error, Debug)]
+pub enum ArrowDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Arrow related error.
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// Parquet related error.
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    /// Serde related error.
+    #[error("Serde error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    /// The column doesn't exist in the files.
+    #[error("Column not found: {0}")]
+    ColumnNotFound(String),
+
+    /// Any other error.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Deserializes a row of a record batch, mapping each column name to the field with the same
+/// name.
+pub(crate) fn deserialize_row<I: DeserializeOwned>(
+    batch: &RecordBatch,
+    row: usize,
+) -> Result<I, ArrowDatasetError> {
+    let mut fields = Map::new();
+
+    for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+        fields.insert(field.name().clone(), array_value(column.as_ref(), row)?);
+    }
+
+    Ok(serde_json::from_value(Value::Object(fields))?)
+}
+
+/// Converts the value of an array at the given index to a serde value.
+///
+/// Binary values are converted to sequences of bytes, lists to sequences, structs and maps to
+/// maps. Types without a serde equivalent, e.g. dates or decimals, are converted to their string
+/// representation.
+fn array_value(array: &dyn Array, index: usize) -> Result<Value, ArrowDatasetError> {
+    if array.is_null(index) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Null => Value::Null,
+        DataType::Boolean => Value::Bool(array.as_boolean().value(index)),
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(index).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(index).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(index).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(index).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(index).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(index).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(index).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(index).into(),
+        DataType::Float16 => float_value(array.as_primitive::<Float16Type>().value(index).to_f64()),
+        DataType::Float32 => float_value(array.as_primitive::<Float32Type>().value(index) as f64),
+        DataType::Float64 => float_value(array.as_primitive::<Float64Type>().value(index)),
+        DataType::Utf8 => array.as_string::<i32>().value(index).into(),
+        DataType::LargeUtf8 => array.as_string::<i64>().value(index).into(),
+        DataType::Utf8View => array.as_string_view().value(index).into(),
+        DataType::Binary => bytes_value(array.as_binary::<i32>().value(index)),
+        DataType::LargeBinary => bytes_value(array.as_binary::<i64>().value(index)),
+        DataType::BinaryView => bytes_value(array.as_binary_view().value(index)),
+        DataType::FixedSizeBinary(_) => bytes_value(array.as_fixed_size_binary().value(index)),
+        DataType::List(_) => list_value(array.as_list::<i32>().value(index).as_ref())?,
+        DataType::LargeList(_) => list_value(array.as_list::<i64>().value(index).as_ref())?,
+        DataType::FixedSizeList(_, _) => {
+            list_value(array.as_fixed_size_list().value(index).as_ref())?
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut values = Map::new();
+
+            for (field, column) in fields.iter().zip(array.columns()) {
+                values.insert(field.name().clone(), array_value(column.as_ref(), index)?);
+            }
+
+            Value::Object(values)
+        }
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(index);
+            let (keys, values) = (entries.column(0), entries.column(1));
+            let mut map = Map::new();
+
+            for i in 0..entries.len() {
+                let key = match array_value(keys.as_ref(), i)? {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+                map.insert(key, array_value(values.as_ref(), i)?);
+            }
+
+            Value::Object(map)
+        }
+        DataType::Dictionary(_, _) => {
+            let array = array.as_any_dictionary();
+            let key = array.normalized_keys()[index];
+
+            array_value(array.values().as_ref(), key)?
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+            Value::String(formatter.value(index).to_string())
+        }
+    };
+
+    Ok(value)
+}
+
+fn float_value(value: f64) -> Value {
+    // Non-finite values can't be represented by serde json numbers.
+    Number::from_f64(value)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+fn bytes_value(bytes: &[u8]) -> Value {
+    Value::Array(bytes.iter().map(|byte| Value::from(*byte)).collect())
+}
+
+fn list_value(array: &dyn Array) -> Result<Value, ArrowDatasetError> {
+    (0..array.len())
+        .map(|i| array_value(array, i))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+/// Locates the chunk, e.g. row group or record batch, containing the given row.
+///
+/// The offsets are the index of the first row of each chunk, followed by the total number of rows.
+/// Returns the index of the chunk and the index of the row within the chunk.
+pub(crate) fn locate_row(offsets: &[usize], index: usize) -> Option<(usize, usize)> {
+    if index >= *offsets.last()? {
+        return None;
+    }
+
+    // The first chunk whose first row is after the index is the one after the right chunk.
+    let chunk = offsets.partition_point(|offset| *offset <= index) - 1;
+
+    Some((chunk, index - offsets[chunk]))
+}
+
+/// Least recently used cache of decoded record batches, avoiding decoding a whole chunk for each
+/// row that is read.
+#[derive(Debug)]
+pub(crate) struct RecordBatchCache {
+    batches: Mutex<Vec<(usize, Arc<RecordBatch>)>>,
+    capacity: usize,
+}
+
+impl RecordBatchCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            batches: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Returns the record batch of the given chunk, decoding it with the given function when it
+    /// isn't cached.
+    pub(crate) fn get_or_load<F>(
+        &self,
+        chunk: usize,
+        load: F,
+    ) -> Result<Arc<RecordBatch>, ArrowDatasetError>
+    where
+        F: FnOnce() -> Result<RecordBatch, ArrowDatasetError>,
+    {
+        {
+            let mut batches = self.batches.lock().unwrap();
+
+            if let Some(position) = batches.iter().position(|(id, _)| *id == chunk) {
+                // Move the batch to the back since it is now the most recently used.
+                let entry = batches.remove(position);
+                let batch = entry.1.clone();
+                batches.push(entry);
+
+                return Ok(batch);
+            }
+        }
+
+        // The lock isn't held while decoding, so other chunks can be read concurrently.
+        let batch = Arc::new(load()?);
+
+        if self.capacity > 0 {
+            let mut batches = self.batches.lock().unwrap();
+
+            if batches.len() >= self.capacity {
+                batches.remove(0);
+            }
+            batches.push((chunk, batch.clone()));
+        }
+
+        Ok(batch)
+    }
+
+    /// Removes all the cached record batches.
+    pub(crate) fn clear(&self) {
+        self.batches.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{
+        builder::{ListBuilder, StringDictionaryBuilder},
+        BinaryArray, Float32Array, Int64Array, StringArray, StructArray,
+    };
+    use arrow_schema::{Field, Fields};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Image {
+        bytes: Vec<u8>,
+        path: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: i64,
+        score: Option<f32>,
+        tags: Vec<String>,
+        label: String,
+        image: Image,
+    }
+
+    #[test]
+    fn deserialize_row_should_convert_nested_columns() {
+        let mut tags = ListBuilder::new(arrow_array::builder::StringBuilder::new());
+        tags.values().append_value("a");
+        tags.values().append_value("b");
+        tags.append(true);
+        tags.append(true);
+
+        let mut labels = StringDictionaryBuilder::<Int32Type>::new();
+        labels.append_value("cat");
+        labels.append_value("dog");
+
+        let image = StructArray::new(
+            Fields::from(vec![
+                Field::new("bytes", DataType::Binary, true),
+                Field::new("path", DataType::Utf8, true),
+            ]),
+            vec![
+                Arc::new(BinaryArray::from(vec![&[1u8, 2][..], &[3u8][..]])),
+                Arc::new(StringArray::from(vec![Some("0.png"), None])),
+            ],
+            None,
+        );
+
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![4, 5])) as _),
+            (
+                "score",
+                Arc::new(Float32Array::from(vec![Some(0.5), None])) as _,
+            ),
+            ("tags", Arc::new(tags.finish()) as _),
+            ("label", Arc::new(labels.finish()) as _),
+            ("image", Arc::new(image) as _),
+        ])
+        .unwrap();
+
+        let first: Item = deserialize_row(&batch, 0).unwrap();
+        let second: Item = deserialize_row(&batch, 1).unwrap();
+
+        assert_eq!(
+            first,
+            Item {
+                id: 4,
+                score: Some(0.5),
+                tags: vec!["a".to_string(), "b".to_string()],
+                label: "cat".to_string(),
+                image: Image {
+                    bytes: vec![1, 2],
+                    path: Some("0.png".to_string()),
+                },
+            }
+        );
+        assert_eq!(second.score, None);
+        assert!(second.tags.is_empty());
+        assert_eq!(second.label, "dog");
+        assert_eq!(second.image.bytes, vec![3]);
+    }
+
+    #[test]
+    fn locate_row_should_find_chunk() {
+        let offsets = [0, 2, 2, 5];
+
+        assert_eq!(locate_row(&offsets, 0), Some((0, 0)));
+        assert_eq!(locate_row(&offsets, 1), Some((0, 1)));
+        // Empty chunks are skipped.
+        assert_eq!(locate_row(&offsets, 2), Some((2, 0)));
+        assert_eq!(locate_row(&offsets, 4), Some((2, 2)));
+        assert_eq!(locate_row(&offsets, 5), None);
+        assert_eq!(locate_row(&[0], 0), None);
+    }
+}
@@ -0,0 +1,604 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::{Dataset, IterableDataset, IterableDatasetIterator};
+
+use image::{DynamicImage, ImageFormat};
+use serde::{de::DeserializeOwned, Serialize};
+use tar::{Archive, Builder, EntryType, Header};
+
+/// The number of samples written in each shard by default.
+const DEFAULT_SAMPLES_PER_SHARD: usize = 10_000;
+
+/// WebDataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum WebDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Image decoding or encoding error.
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+
+    /// Serde related error.
+    #[error("Serde error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    /// The sample doesn't contain a file with the extension.
+    #[error("Sample {key} doesn't have a file with the extension {extension}")]
+    MissingFile {
+        /// The key of the sample.
+        key: String,
+        /// The extension of the missing file.
+        extension: String,
+    },
+
+    /// The overwrite flag is set to false and the shard file already exists.
+    #[error("Overwrite flag is set to false and the shard file already exists: {0}")]
+    FileExists(PathBuf),
+
+    /// Any other error.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A sample of a [WebDataset](https://github.com/webdataset/webdataset), made of all the files
+/// of a tar shard sharing the same key.
+///
+/// The key of a file is its path up to the first dot of its name, and the extension is the rest
+/// of the name, e.g. the files `images/0001.jpg` and `images/0001.seg.png` belong to the sample
+/// `images/0001` with the extensions `jpg` and `seg.png`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebDatasetSample {
+    /// The key of the sample.
+    pub key: String,
+
+    /// The content of the files of the sample, by extension.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+impl WebDatasetSample {
+    /// Creates a sample without any file.
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            key: key.into(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a file with the given extension to the sample.
+    pub fn with_file<S: Into<String>>(mut self, extension: S, content: Vec<u8>) -> Self {
+        self.files.insert(extension.into(), content);
+        self
+    }
+
+    /// Adds a text file with the given extension to the sample.
+    pub fn with_text<S: Into<String>>(self, extension: S, text: &str) -> Self {
+        self.with_file(extension, text.as_bytes().to_vec())
+    }
+
+    /// Adds a JSON file with the given extension to the sample.
+    pub fn with_json<S: Into<String>, T: Serialize>(
+        self,
+        extension: S,
+        value: &T,
+    ) -> Result<Self, WebDatasetError> {
+        let content = serde_json::to_vec(value)?;
+        Ok(self.with_file(extension, content))
+    }
+
+    /// Adds an image to the sample, encoded in the format corresponding to the extension.
+    pub fn with_image<S: Into<String>>(
+        self,
+        extension: S,
+        image: &DynamicImage,
+    ) -> Result<Self, WebDatasetError> {
+        let extension = extension.into();
+        let format = image_format(&extension).ok_or_else(|| {
+            WebDatasetError::Other(format!("Unsupported image extension: {extension}"))
+        })?;
+
+        let mut content = io::Cursor::new(Vec::new());
+        image.write_to(&mut content, format)?;
+
+        Ok(self.with_file(extension, content.into_inner()))
+    }
+
+    /// Returns the content of the file with the given extension.
+    pub fn file(&self, extension: &str) -> Result<&[u8], WebDatasetError> {
+        self.files
+            .get(extension)
+            .map(|content| content.as_slice())
+            .ok_or_else(|| WebDatasetError::MissingFile {
+                key: self.key.clone(),
+                extension: extension.to_string(),
+            })
+    }
+
+    /// Returns the content of the text file with the given extension, e.g. a caption (`txt`) or a
+    /// class label (`cls`).
+    pub fn text(&self, extension: &str) -> Result<String, WebDatasetError> {
+        String::from_utf8(self.file(extension)?.to_vec())
+            .map_err(|err| WebDatasetError::Other(format!("Invalid utf-8 text: {err}")))
+    }
+
+    /// Deserializes the JSON file with the given extension.
+    pub fn json<T: DeserializeOwned>(&self, extension: &str) -> Result<T, WebDatasetError> {
+        Ok(serde_json::from_slice(self.file(extension)?)?)
+    }
+
+    /// Decodes the image file with the given extension, e.g. `jpg` or `png`.
+    pub fn image(&self, extension: &str) -> Result<DynamicImage, WebDatasetError> {
+        let content = self.file(extension)?;
+
+        let image = match image_format(extension) {
+            Some(format) => image::load_from_memory_with_format(content, format)?,
+            None => image::load_from_memory(content)?,
+        };
+
+        Ok(image)
+    }
+}
+
+/// The image format of the last component of an extension, e.g. `png` for `seg.png`.
+fn image_format(extension: &str) -> Option<ImageFormat> {
+    let extension = extension.rsplit('.').next()?;
+    ImageFormat::from_extension(extension)
+}
+
+/// Splits the path of a file into the key of its sample and its extension.
+fn split_key(path: &str) -> Option<(&str, &str)> {
+    let name_start = path.rfind('/').map(|index| index + 1).unwrap_or(0);
+    let dot = path[name_start..].find('.')? + name_start;
+
+    Some((&path[..dot], &path[dot + 1..]))
+}
+
+/// Location of a file of a sample in a tar shard.
+#[derive(Debug, Clone)]
+struct FileLocation {
+    extension: String,
+    offset: u64,
+    size: u64,
+}
+
+/// Location of a sample in the tar shards.
+#[derive(Debug, Clone)]
+struct SampleLocation {
+    shard: usize,
+    key: String,
+    files: Vec<FileLocation>,
+}
+
+/// Dataset of [WebDataset](https://github.com/webdataset/webdataset) samples stored in tar shards,
+/// with random access.
+///
+/// The shards are scanned once when the dataset is created to build a table with the location of
+/// each file, so reading a sample only reads its files. The files of a sample must be stored
+/// consecutively in a shard, as done by the [writer](WebDatasetWriter).
+///
+/// When random access isn't needed, [WebDatasetStream] iterates over the shards sequentially
+/// without building the table first.
+#[derive(Debug)]
+pub struct WebDataset {
+    shards: Vec<PathBuf>,
+    samples: Vec<SampleLocation>,
+}
+
+impl WebDataset {
+    /// Creates a dataset from the given tar shards, with the samples of each shard following the
+    /// samples of the previous one.
+    pub fn new<P, It>(shards: It) -> Result<Self, WebDatasetError>
+    where
+        P: AsRef<Path>,
+        It: IntoIterator<Item = P>,
+    {
+        let shards = shards
+            .into_iter()
+            .map(|shard| shard.as_ref().to_path_buf())
+            .collect::<Vec<_>>();
+        let mut samples = Vec::new();
+
+        for (index, shard) in shards.iter().enumerate() {
+            samples.extend(index_shard(index, shard)?);
+        }
+
+        Ok(Self { shards, samples })
+    }
+
+    /// Creates a dataset from all the tar shards of a directory, sorted by name.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, WebDatasetError> {
+        Self::new(find_shards(dir.as_ref())?)
+    }
+
+    /// The tar shards of the dataset.
+    pub fn shards(&self) -> &[PathBuf] {
+        &self.shards
+    }
+}
+
+impl Dataset<WebDatasetSample> for WebDataset {
+    fn get(&self, index: usize) -> Option<WebDatasetSample> {
+        let location = self.samples.get(index)?;
+
+        Some(
+            read_sample(&self.shards[location.shard], location)
+                .expect("Can read the sample from the tar shard"),
+        )
+    }
+
+    fn len(&self) -> usize {
+        self.samples.len()
+    }
+}
+
+/// Iterable dataset of [WebDataset](https://github.com/webdataset/webdataset) samples stored in
+/// tar shards, reading the samples of each shard in order.
+///
+/// Each tar file is a shard of the [iterable dataset](IterableDataset), so the shards can be
+/// distributed across the data loader workers. A shard is only scanned when it is iterated over,
+/// so no work is done upfront, unlike [WebDataset].
+#[derive(new, Debug, Clone)]
+pub struct WebDatasetStream {
+    shards: Vec<PathBuf>,
+}
+
+impl WebDatasetStream {
+    /// Creates a stream over all the tar shards of a directory, sorted by name.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, WebDatasetError> {
+        Ok(Self::new(find_shards(dir.as_ref())?))
+    }
+}
+
+impl IterableDataset<WebDatasetSample> for WebDatasetStream {
+    fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, WebDatasetSample> {
+        let path = &self.shards[shard];
+        let samples = index_shard(shard, path).expect("Can read the tar shard");
+
+        Box::new(samples.into_iter().map(move |location| {
+            read_sample(path, &location).expect("Can read the sample from the tar shard")
+        }))
+    }
+}
+
+/// Scans the headers of a tar shard, grouping its consecutive files with the same key.
+fn index_shard(shard: usize, path: &Path) -> Result<Vec<SampleLocation>, WebDatasetError> {
+    let mut archive = Archive::new(BufReader::new(File::open(path)?));
+    let mut samples: Vec<SampleLocation> = Vec::new();
+
+    // The content of the files is skipped by seeking, only the headers are read.
+    for entry in archive.entries_with_seek()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.to_string_lossy().to_string();
+        let Some((key, extension)) = split_key(&path) else {
+            continue;
+        };
+        let file = FileLocation {
+            extension: extension.to_string(),
+            offset: entry.raw_file_position(),
+            size: entry.size(),
+        };
+
+        match samples.last_mut() {
+            Some(sample) if sample.key == key => sample.files.push(file),
+            _ => samples.push(SampleLocation {
+                shard,
+                key: key.to_string(),
+                files: vec![file],
+            }),
+        }
+    }
+
+    Ok(samples)
+}
+
+/// Reads the files of a sample from its tar shard.
+fn read_sample(
+    path: &Path,
+    location: &SampleLocation,
+) -> Result<WebDatasetSample, WebDatasetError> {
+    let mut reader = File::open(path)?;
+    let mut sample = WebDatasetSample::new(location.key.clone());
+
+    for file in location.files.iter() {
+        let mut content = vec![0; file.size as usize];
+        reader.seek(SeekFrom::Start(file.offset))?;
+        reader.read_exact(&mut content)?;
+        sample.files.insert(file.extension.clone(), content);
+    }
+
+    Ok(sample)
+}
+
+/// Finds all the tar files of a directory, sorted by name.
+fn find_shards(dir: &Path) -> Result<Vec<PathBuf>, WebDatasetError> {
+    let mut shards = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    shards.retain(|path| path.extension().is_some_and(|extension| extension == "tar"));
+    shards.sort();
+
+    Ok(shards)
+}
+
+/// This `WebDatasetWriter` struct is a WebDataset writer that writes samples to tar shards named
+/// `{prefix}-{index:06}.tar` in a directory, starting a new shard every fixed number of samples.
+///
+/// Each shard is written to a temporary file that is renamed once the shard is complete, so
+/// the shards of an interrupted writer aren't mistaken for complete ones.
+///
+/// Being thread-safe, this writer can be concurrently used across multiple threads.
+pub struct WebDatasetWriter {
+    dir: PathBuf,
+    prefix: String,
+    samples_per_shard: usize,
+    state: Mutex<WriterState>,
+}
+
+#[derive(Default)]
+struct WriterState {
+    builder: Option<Builder<BufWriter<File>>>,
+    num_samples: usize,
+    shards: Vec<PathBuf>,
+    is_completed: bool,
+}
+
+impl WebDatasetWriter {
+    /// Creates a new instance of `WebDatasetWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory where the shards are written.
+    /// * `prefix` - The prefix of the shard file names.
+    /// * `overwrite` - A boolean indicating if existing shards with the same prefix should be
+    ///   removed.
+    ///
+    /// # Returns
+    ///
+    /// * A `Result` which is `Ok` if the writer could be created, `Err` otherwise.
+    pub fn new<P: AsRef<Path>>(
+        dir: P,
+        prefix: &str,
+        overwrite: bool,
+    ) -> Result<Self, WebDatasetError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        for path in find_shards(&dir)? {
+            let is_shard = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&format!("{prefix}-")));
+
+            if is_shard {
+                if overwrite {
+                    fs::remove_file(&path)?;
+                } else {
+                    return Err(WebDatasetError::FileExists(path));
+                }
+            }
+        }
+
+        Ok(Self {
+            dir,
+            prefix: prefix.to_string(),
+            samples_per_shard: DEFAULT_SAMPLES_PER_SHARD,
+            state: Mutex::new(WriterState::default()),
+        })
+    }
+
+    /// Sets the maximum number of samples written in each shard.
+    pub fn with_samples_per_shard(mut self, samples_per_shard: usize) -> Self {
+        self.samples_per_shard = samples_per_shard.max(1);
+        self
+    }
+
+    /// Writes a sample, starting a new shard when the current one is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - A reference to the sample to be written.
+    ///
+    /// # Returns
+    ///
+    /// * A `Result` containing the index of the written sample if successful, an error otherwise.
+    pub fn write(&self, sample: &WebDatasetSample) -> Result<usize, WebDatasetError> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.is_completed {
+            return Err(WebDatasetError::Other(
+                "Cannot save to a completed dataset writer".to_string(),
+            ));
+        }
+
+        if state.num_samples % self.samples_per_shard == 0 {
+            finish_shard(&mut state)?;
+
+            let shard = self.dir.join(format!(
+                "{}-{:06}.tar",
+                self.prefix,
+                state.num_samples / self.samples_per_shard
+            ));
+            let file = File::create(tmp_path(&shard))?;
+            let mut builder = Builder::new(BufWriter::new(file));
+            builder.mode(tar::HeaderMode::Deterministic);
+
+            state.builder = Some(builder);
+            state.shards.push(shard);
+        }
+
+        let builder = state.builder.as_mut().unwrap();
+        for (extension, content) in sample.files.iter() {
+            let mut header = Header::new_gnu();
+            header.set_entry_type(EntryType::Regular);
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+
+            let path = format!("{}.{extension}", sample.key);
+            builder.append_data(&mut header, path, content.as_slice())?;
+        }
+
+        let index = state.num_samples;
+        state.num_samples += 1;
+
+        Ok(index)
+    }
+
+    /// Marks the dataset as completed and persists the last shard.
+    pub fn set_completed(&mut self) -> Result<(), WebDatasetError> {
+        let state = self.state.get_mut().unwrap();
+
+        finish_shard(state)?;
+        state.is_completed = true;
+
+        Ok(())
+    }
+
+    /// The shards written so far.
+    pub fn shards(&self) -> Vec<PathBuf> {
+        self.state.lock().unwrap().shards.clone()
+    }
+}
+
+/// Completes the shard being written, if any, and renames its temporary file.
+fn finish_shard(state: &mut WriterState) -> Result<(), WebDatasetError> {
+    if let Some(builder) = state.builder.take() {
+        let mut writer = builder.into_inner()?;
+        io::Write::flush(&mut writer)?;
+
+        let shard = state.shards.last().unwrap();
+        fs::rename(tmp_path(shard), shard)?;
+    }
+
+    Ok(())
+}
+
+/// The path of the temporary file of a shard, e.g. `train-000000.tar.tmp`.
+fn tmp_path(shard: &Path) -> PathBuf {
+    let mut path = shard.as_os_str().to_os_string();
+    path.push(".tmp");
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Metadata {
+        id: usize,
+        caption: String,
+    }
+
+    fn sample(id: usize) -> WebDatasetSample {
+        let image = RgbImage::from_fn(3, 2, |x, y| Rgb([x as u8, y as u8, id as u8]));
+        let metadata = Metadata {
+            id,
+            caption: format!("caption {id}"),
+        };
+
+        WebDatasetSample::new(format!("sample{id:04}"))
+            .with_image("png", &DynamicImage::ImageRgb8(image))
+            .unwrap()
+            .with_json("json", &metadata)
+            .unwrap()
+            .with_text("cls", &(id % 2).to_string())
+    }
+
+    fn write_shards(dir: &Path, len: usize) -> Vec<PathBuf> {
+        let mut writer = WebDatasetWriter::new(dir, "train", false)
+            .unwrap()
+            .with_samples_per_shard(2);
+
+        for id in 0..len {
+            assert_eq!(writer.write(&sample(id)).unwrap(), id);
+        }
+        writer.set_completed().unwrap();
+
+        writer.shards()
+    }
+
+    #[test]
+    fn given_shards_when_get_should_read_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let shards = write_shards(dir.path(), 5);
+
+        let dataset = WebDataset::from_dir(dir.path()).unwrap();
+
+        assert_eq!(shards.len(), 3);
+        assert_eq!(dataset.shards(), shards.as_slice());
+        assert_eq!(dataset.len(), 5);
+        assert_eq!(dataset.get(3), Some(sample(3)));
+        assert_eq!(dataset.get(0), Some(sample(0)));
+        assert_eq!(dataset.get(5), None);
+
+        let item = dataset.get(4).unwrap();
+        let image = item.image("png").unwrap().into_rgb8();
+        let metadata: Metadata = item.json("json").unwrap();
+
+        assert_eq!(image.dimensions(), (3, 2));
+        assert_eq!(image.get_pixel(2, 1), &Rgb([2, 1, 4]));
+        assert_eq!(metadata.caption, "caption 4");
+        assert_eq!(item.text("cls").unwrap(), "0");
+        assert!(matches!(
+            item.file("jpg"),
+            Err(WebDatasetError::MissingFile { .. })
+        ));
+    }
+
+    #[test]
+    fn given_shards_when_stream_should_iterate_over_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        write_shards(dir.path(), 5);
+
+        let dataset = WebDatasetStream::from_dir(dir.path()).unwrap();
+
+        assert_eq!(dataset.num_shards(), 3);
+        assert_eq!(
+            dataset.iter().collect::<Vec<_>>(),
+            (0..5).map(sample).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            dataset.iter_shard(1).collect::<Vec<_>>(),
+            vec![sample(2), sample(3)]
+        );
+    }
+
+    #[test]
+    fn given_existing_shards_when_new_writer_should_overwrite_if_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        write_shards(dir.path(), 3);
+
+        assert!(matches!(
+            WebDatasetWriter::new(dir.path(), "train", false),
+            Err(WebDatasetError::FileExists(_))
+        ));
+        assert!(WebDatasetWriter::new(dir.path(), "test", false).is_ok());
+
+        let _writer = WebDatasetWriter::new(dir.path(), "train", true).unwrap();
+        assert!(find_shards(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_key_should_use_first_dot_of_file_name() {
+        assert_eq!(split_key("0001.jpg"), Some(("0001", "jpg")));
+        assert_eq!(split_key("a.b/0001.seg.png"), Some(("a.b/0001", "seg.png")));
+        assert_eq!(split_key("README"), None);
+    }
+}
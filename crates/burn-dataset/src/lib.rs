@@ -24,6 +24,10 @@ pub mod audio;
 #[cfg(feature = "vision")]
 pub mod vision;
 
+/// Text tokenizers and batchers.
+#[cfg(feature = "text")]
+pub mod text;
+
 mod dataset;
 pub use dataset::*;
 #[cfg(any(feature = "sqlite", feature = "sqlite-bundled"))]
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use burn_tensor::{backend::Backend, Bool, Int, Tensor, TensorData};
+
+use super::Tokenizer;
+
+/// Batch of tokenized texts.
+#[derive(Debug, Clone)]
+pub struct TextBatch<B: Backend> {
+    /// Token ids of shape `[batch_size, seq_length]`, padded with the padding token.
+    pub tokens: Tensor<B, 2, Int>,
+
+    /// Padding mask of shape `[batch_size, seq_length]`, true for the padding tokens, as expected
+    /// by the `mask_pad` of the transformer encoder input.
+    pub mask_pad: Tensor<B, 2, Bool>,
+}
+
+/// Batcher tokenizing texts into a [text batch](TextBatch), the sequences being padded to the
+/// longest sequence of the batch.
+#[derive(Clone, Debug)]
+pub struct TextBatcher {
+    tokenizer: Arc<Tokenizer>,
+    pad_id: usize,
+    max_seq_length: Option<usize>,
+    add_special_tokens: bool,
+}
+
+impl TextBatcher {
+    /// Creates a text batcher.
+    ///
+    /// The padding token is the one configured in the tokenizer file, or 0 otherwise, and the
+    /// special tokens are added to the sequences.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenizer` - The tokenizer.
+    pub fn new(tokenizer: Arc<Tokenizer>) -> Self {
+        Self {
+            pad_id: tokenizer.pad_id().unwrap_or(0),
+            tokenizer,
+            max_seq_length: None,
+            add_special_tokens: true,
+        }
+    }
+
+    /// Sets the id of the padding token.
+    pub fn with_pad_id(mut self, pad_id: usize) -> Self {
+        self.pad_id = pad_id;
+        self
+    }
+
+    /// Sets the maximum length of the sequences, the longer sequences being truncated.
+    pub fn with_max_seq_length(mut self, max_seq_length: usize) -> Self {
+        self.max_seq_length = Some(max_seq_length);
+        self
+    }
+
+    /// Sets whether to add the special tokens of the tokenizer to the sequences.
+    pub fn with_special_tokens(mut self, add_special_tokens: bool) -> Self {
+        self.add_special_tokens = add_special_tokens;
+        self
+    }
+
+    /// Tokenizes and pads texts, creating the batch on the given device.
+    pub fn batch_texts<B: Backend, S: AsRef<str>>(
+        &self,
+        texts: &[S],
+        device: &B::Device,
+    ) -> TextBatch<B> {
+        let sequences = texts
+            .iter()
+            .map(|text| {
+                self.tokenizer
+                    .encode(text.as_ref(), self.add_special_tokens)
+                    .ids
+            })
+            .collect::<Vec<_>>();
+
+        self.batch_ids(sequences, device)
+    }
+
+    /// Pads sequences of token ids, creating the batch on the given device.
+    pub fn batch_ids<B: Backend>(
+        &self,
+        sequences: Vec<Vec<usize>>,
+        device: &B::Device,
+    ) -> TextBatch<B> {
+        let batch_size = sequences.len();
+        let (tokens, mask_pad, seq_length) = pad(sequences, self.pad_id, self.max_seq_length);
+        let shape = [batch_size, seq_length];
+
+        TextBatch {
+            tokens: Tensor::from_data(
+                TensorData::new(tokens, shape).convert::<B::IntElem>(),
+                device,
+            ),
+            mask_pad: Tensor::from_data(TensorData::new(mask_pad, shape), device),
+        }
+    }
+}
+
+/// Pads the sequences to the longest sequence, returning the flattened token ids, the padding mask
+/// and the sequence length.
+fn pad(
+    sequences: Vec<Vec<usize>>,
+    pad_id: usize,
+    max_seq_length: Option<usize>,
+) -> (Vec<i64>, Vec<bool>, usize) {
+    let longest = sequences.iter().map(Vec::len).max().unwrap_or(0);
+    let seq_length = match max_seq_length {
+        Some(max_seq_length) => longest.min(max_seq_length),
+        None => longest,
+    };
+
+    let mut tokens = Vec::with_capacity(sequences.len() * seq_length);
+    let mut mask_pad = Vec::with_capacity(sequences.len() * seq_length);
+
+    for sequence in sequences {
+        let length = sequence.len().min(seq_length);
+
+        tokens.extend(sequence[..length].iter().map(|id| *id as i64));
+        tokens.resize(tokens.len() + seq_length - length, pad_id as i64);
+        mask_pad.extend((0..seq_length).map(|position| position >= length));
+    }
+
+    (tokens, mask_pad, seq_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_should_pad_to_longest_sequence() {
+        let (tokens, mask_pad, seq_length) = pad(vec![vec![5, 6, 7], vec![8]], 0, None);
+
+        assert_eq!(seq_length, 3);
+        assert_eq!(tokens, vec![5, 6, 7, 8, 0, 0]);
+        assert_eq!(mask_pad, vec![false, false, false, false, true, true]);
+    }
+
+    #[test]
+    fn pad_should_truncate_to_max_seq_length() {
+        let (tokens, mask_pad, seq_length) = pad(vec![vec![5, 6, 7], vec![8]], 1, Some(2));
+
+        assert_eq!(seq_length, 2);
+        assert_eq!(tokens, vec![5, 6, 8, 1]);
+        assert_eq!(mask_pad, vec![false, false, false, true]);
+    }
+}
@@ -0,0 +1,9 @@
+mod batcher;
+mod model;
+mod normalizer;
+mod pre_tokenizer;
+mod processor;
+mod tokenizer;
+
+pub use batcher::*;
+pub use tokenizer::*;
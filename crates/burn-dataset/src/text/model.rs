@@ -0,0 +1,676 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// Token produced by a model, with its byte range in the tokenized word.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub(crate) id: usize,
+    pub(crate) value: String,
+    pub(crate) offsets: (usize, usize),
+}
+
+/// Model of a [tokenizer](super::Tokenizer), splitting words into tokens of its vocabulary.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub(crate) enum Model {
+    #[serde(rename = "BPE")]
+    Bpe(Bpe),
+    WordPiece(WordPiece),
+    Unigram(Unigram),
+    WordLevel(WordLevel),
+}
+
+impl Model {
+    /// Splits a word into tokens.
+    pub(crate) fn tokenize(&self, word: &str) -> Vec<Token> {
+        match self {
+            Model::Bpe(model) => model.tokenize(word),
+            Model::WordPiece(model) => model.tokenize(word),
+            Model::Unigram(model) => model.tokenize(word),
+            Model::WordLevel(model) => model.tokenize(word),
+        }
+    }
+
+    /// The id of a token of the vocabulary.
+    pub(crate) fn token_to_id(&self, token: &str) -> Option<usize> {
+        match self {
+            Model::Bpe(model) => model.vocab.get(token).copied(),
+            Model::WordPiece(model) => model.vocab.get(token).copied(),
+            Model::Unigram(model) => model.ids.get(token).copied(),
+            Model::WordLevel(model) => model.vocab.get(token).copied(),
+        }
+    }
+
+    /// The token of an id of the vocabulary.
+    pub(crate) fn id_to_token(&self, id: usize) -> Option<&str> {
+        let token = match self {
+            Model::Bpe(model) => model.tokens.get(id),
+            Model::WordPiece(model) => model.tokens.get(id),
+            Model::Unigram(model) => model.vocab.get(id).map(|(token, _)| token),
+            Model::WordLevel(model) => model.tokens.get(id),
+        };
+
+        token.filter(|token| !token.is_empty()).map(String::as_str)
+    }
+
+    /// The number of tokens of the vocabulary.
+    pub(crate) fn vocab_size(&self) -> usize {
+        match self {
+            Model::Bpe(model) => model.vocab.len(),
+            Model::WordPiece(model) => model.vocab.len(),
+            Model::Unigram(model) => model.vocab.len(),
+            Model::WordLevel(model) => model.vocab.len(),
+        }
+    }
+}
+
+/// Tokens indexed by id, the ids missing from the vocabulary being empty strings.
+fn tokens_by_id(vocab: &HashMap<String, usize>) -> Vec<String> {
+    let size = vocab.values().max().map(|id| id + 1).unwrap_or(0);
+    let mut tokens = vec![String::new(); size];
+
+    for (token, id) in vocab {
+        tokens[*id] = token.clone();
+    }
+
+    tokens
+}
+
+/// The tokens of the bytes of a string, e.g. `<0x41>`, when they are all in the vocabulary.
+fn byte_fallback(
+    text: &str,
+    offsets: (usize, usize),
+    vocab: impl Fn(&str) -> Option<usize>,
+) -> Option<Vec<Token>> {
+    text.bytes()
+        .map(|byte| {
+            let value = format!("<0x{byte:02X}>");
+            vocab(&value).map(|id| Token { id, value, offsets })
+        })
+        .collect()
+}
+
+/// [Byte pair encoding](https://arxiv.org/abs/1508.07909) model, merging the pairs of symbols of
+/// words in the order of the learned merges.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(from = "BpeConfig")]
+pub(crate) struct Bpe {
+    vocab: HashMap<String, usize>,
+    tokens: Vec<String>,
+    /// Rank and merged token of each pair of tokens.
+    merges: HashMap<(usize, usize), (usize, usize)>,
+    unk_token: Option<String>,
+    continuing_subword_prefix: Option<String>,
+    end_of_word_suffix: Option<String>,
+    fuse_unk: bool,
+    byte_fallback: bool,
+    ignore_merges: bool,
+}
+
+#[derive(Deserialize)]
+struct BpeConfig {
+    vocab: HashMap<String, usize>,
+    merges: Vec<Merge>,
+    #[serde(default)]
+    unk_token: Option<String>,
+    #[serde(default)]
+    continuing_subword_prefix: Option<String>,
+    #[serde(default)]
+    end_of_word_suffix: Option<String>,
+    #[serde(default)]
+    fuse_unk: bool,
+    #[serde(default)]
+    byte_fallback: bool,
+    #[serde(default)]
+    ignore_merges: bool,
+}
+
+/// Merge, either `"a b"` or `["a", "b"]` depending on the version of the tokenizer file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Merge {
+    String(String),
+    Pair(String, String),
+}
+
+impl From<BpeConfig> for Bpe {
+    fn from(config: BpeConfig) -> Self {
+        let prefix_len = config
+            .continuing_subword_prefix
+            .as_ref()
+            .map(String::len)
+            .unwrap_or(0);
+
+        let merges = config
+            .merges
+            .into_iter()
+            .filter_map(|merge| match merge {
+                Merge::String(merge) => merge
+                    .split_once(' ')
+                    .map(|(a, b)| (a.to_string(), b.to_string())),
+                Merge::Pair(a, b) => Some((a, b)),
+            })
+            .enumerate()
+            .filter_map(|(rank, (a, b))| {
+                // The prefix of the second token isn't part of the merged token.
+                let merged = match &config.continuing_subword_prefix {
+                    Some(prefix) if b.starts_with(prefix.as_str()) => {
+                        format!("{a}{}", &b[prefix_len..])
+                    }
+                    _ => format!("{a}{b}"),
+                };
+                let ids = (*config.vocab.get(&a)?, *config.vocab.get(&b)?);
+
+                Some((ids, (rank, *config.vocab.get(&merged)?)))
+            })
+            .collect();
+
+        Self {
+            tokens: tokens_by_id(&config.vocab),
+            vocab: config.vocab,
+            merges,
+            unk_token: config.unk_token,
+            continuing_subword_prefix: config.continuing_subword_prefix,
+            end_of_word_suffix: config.end_of_word_suffix,
+            fuse_unk: config.fuse_unk,
+            byte_fallback: config.byte_fallback,
+            ignore_merges: config.ignore_merges,
+        }
+    }
+}
+
+impl Bpe {
+    fn tokenize(&self, word: &str) -> Vec<Token> {
+        if word.is_empty() {
+            return Vec::new();
+        }
+        if self.ignore_merges {
+            if let Some(id) = self.vocab.get(word) {
+                return vec![self.token(*id, (0, word.len()))];
+            }
+        }
+
+        // Symbols of the word, as (id, start, end, is_unknown).
+        let mut symbols: Vec<(usize, usize, usize, bool)> = Vec::new();
+        let unk_id = self
+            .unk_token
+            .as_ref()
+            .and_then(|token| self.vocab.get(token));
+        let num_chars = word.chars().count();
+
+        for (index, (start, c)) in word.char_indices().enumerate() {
+            let end = start + c.len_utf8();
+            let mut symbol = c.to_string();
+            if let (true, Some(prefix)) = (index > 0, &self.continuing_subword_prefix) {
+                symbol.insert_str(0, prefix);
+            }
+            if let (true, Some(suffix)) = (index == num_chars - 1, &self.end_of_word_suffix) {
+                symbol.push_str(suffix);
+            }
+
+            if let Some(id) = self.vocab.get(&symbol) {
+                symbols.push((*id, start, end, false));
+                continue;
+            }
+
+            let bytes = match self.byte_fallback {
+                true => byte_fallback(&c.to_string(), (start, end), |token| {
+                    self.vocab.get(token).copied()
+                }),
+                false => None,
+            };
+            match (bytes, unk_id) {
+                (Some(bytes), _) => {
+                    symbols.extend(bytes.into_iter().map(|token| (token.id, start, end, true)))
+                }
+                (None, Some(unk_id)) => match symbols.last_mut() {
+                    Some(last) if self.fuse_unk && last.0 == *unk_id && last.3 => last.2 = end,
+                    _ => symbols.push((*unk_id, start, end, true)),
+                },
+                // Without an unknown token, unknown characters are dropped.
+                (None, None) => {}
+            }
+        }
+
+        // Merge the pair with the lowest rank until no pair can be merged.
+        loop {
+            let best = symbols
+                .windows(2)
+                .enumerate()
+                .filter(|(_, pair)| !pair[0].3 && !pair[1].3)
+                .filter_map(|(index, pair)| {
+                    let (rank, id) = self.merges.get(&(pair[0].0, pair[1].0))?;
+                    Some((*rank, index, *id))
+                })
+                .min();
+
+            let Some((_, index, id)) = best else {
+                break;
+            };
+            let next = symbols.remove(index + 1);
+            symbols[index] = (id, symbols[index].1, next.2, false);
+        }
+
+        symbols
+            .into_iter()
+            .map(|(id, start, end, _)| self.token(id, (start, end)))
+            .collect()
+    }
+
+    fn token(&self, id: usize, offsets: (usize, usize)) -> Token {
+        Token {
+            id,
+            value: self.tokens[id].clone(),
+            offsets,
+        }
+    }
+}
+
+/// [WordPiece](https://arxiv.org/abs/1609.08144) model, greedily splitting words into the longest
+/// tokens of the vocabulary, as used by BERT.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(from = "WordPieceConfig")]
+pub(crate) struct WordPiece {
+    vocab: HashMap<String, usize>,
+    tokens: Vec<String>,
+    unk_token: String,
+    continuing_subword_prefix: String,
+    max_input_chars_per_word: usize,
+}
+
+#[derive(Deserialize)]
+struct WordPieceConfig {
+    vocab: HashMap<String, usize>,
+    #[serde(default = "default_unk_token")]
+    unk_token: String,
+    #[serde(default = "default_continuing_subword_prefix")]
+    continuing_subword_prefix: String,
+    #[serde(default = "default_max_input_chars_per_word")]
+    max_input_chars_per_word: usize,
+}
+
+fn default_unk_token() -> String {
+    "[UNK]".to_string()
+}
+
+fn default_continuing_subword_prefix() -> String {
+    "##".to_string()
+}
+
+fn default_max_input_chars_per_word() -> usize {
+    100
+}
+
+impl From<WordPieceConfig> for WordPiece {
+    fn from(config: WordPieceConfig) -> Self {
+        Self {
+            tokens: tokens_by_id(&config.vocab),
+            vocab: config.vocab,
+            unk_token: config.unk_token,
+            continuing_subword_prefix: config.continuing_subword_prefix,
+            max_input_chars_per_word: config.max_input_chars_per_word,
+        }
+    }
+}
+
+impl WordPiece {
+    fn tokenize(&self, word: &str) -> Vec<Token> {
+        if word.is_empty() {
+            return Vec::new();
+        }
+
+        let unknown = || match self.vocab.get(&self.unk_token) {
+            Some(id) => vec![Token {
+                id: *id,
+                value: self.unk_token.clone(),
+                offsets: (0, word.len()),
+            }],
+            None => Vec::new(),
+        };
+
+        if word.chars().count() > self.max_input_chars_per_word {
+            return unknown();
+        }
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        while start < word.len() {
+            // The longest token of the vocabulary starting at the current position.
+            let token = word[start..]
+                .char_indices()
+                .map(|(index, c)| start + index + c.len_utf8())
+                .rev()
+                .find_map(|end| {
+                    let value = match start {
+                        0 => word[start..end].to_string(),
+                        _ => format!("{}{}", self.continuing_subword_prefix, &word[start..end]),
+                    };
+                    let id = *self.vocab.get(&value)?;
+
+                    Some(Token {
+                        id,
+                        value,
+                        offsets: (start, end),
+                    })
+                });
+
+            // The whole word is unknown when any of its parts isn't in the vocabulary.
+            let Some(token) = token else {
+                return unknown();
+            };
+            start = token.offsets.1;
+            tokens.push(token);
+        }
+
+        tokens
+    }
+}
+
+/// [Unigram](https://arxiv.org/abs/1804.10959) model, splitting words into the most likely
+/// sequence of tokens, as used by SentencePiece.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(from = "UnigramConfig")]
+pub(crate) struct Unigram {
+    vocab: Vec<(String, f64)>,
+    ids: HashMap<String, usize>,
+    unk_id: Option<usize>,
+    byte_fallback: bool,
+    max_token_chars: usize,
+    min_score: f64,
+}
+
+#[derive(Deserialize)]
+struct UnigramConfig {
+    vocab: Vec<(String, f64)>,
+    #[serde(default)]
+    unk_id: Option<usize>,
+    #[serde(default)]
+    byte_fallback: bool,
+}
+
+impl From<UnigramConfig> for Unigram {
+    fn from(config: UnigramConfig) -> Self {
+        let ids = config
+            .vocab
+            .iter()
+            .enumerate()
+            .map(|(id, (token, _))| (token.clone(), id))
+            .collect();
+        let max_token_chars = config
+            .vocab
+            .iter()
+            .map(|(token, _)| token.chars().count())
+            .max()
+            .unwrap_or(1);
+        let min_score = config
+            .vocab
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::INFINITY, f64::min);
+
+        Self {
+            vocab: config.vocab,
+            ids,
+            unk_id: config.unk_id,
+            byte_fallback: config.byte_fallback,
+            max_token_chars,
+            min_score,
+        }
+    }
+}
+
+/// Penalty of unknown characters relative to the least likely token, like SentencePiece.
+const UNKNOWN_PENALTY: f64 = 10.0;
+
+impl Unigram {
+    fn tokenize(&self, word: &str) -> Vec<Token> {
+        let positions = word
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(word.len()))
+            .collect::<Vec<_>>();
+
+        // Best score of the segmentations ending at each character, with the start of the last
+        // token and its id (None for unknown characters).
+        let mut best: Vec<Option<(f64, usize, Option<usize>)>> = vec![None; positions.len()];
+        best[0] = Some((0.0, 0, None));
+
+        for start in 0..positions.len() - 1 {
+            let Some((score, _, _)) = best[start] else {
+                continue;
+            };
+            let mut has_single_char = false;
+
+            for end in start + 1..positions.len().min(start + self.max_token_chars + 1) {
+                let piece = &word[positions[start]..positions[end]];
+                let Some(id) = self.ids.get(piece) else {
+                    continue;
+                };
+                has_single_char |= end == start + 1;
+
+                let candidate = score + self.vocab[*id].1;
+                if !matches!(best[end], Some((best, _, _)) if best >= candidate) {
+                    best[end] = Some((candidate, start, Some(*id)));
+                }
+            }
+
+            if !has_single_char {
+                let candidate = score + self.min_score - UNKNOWN_PENALTY;
+                if !matches!(best[start + 1], Some((best, _, _)) if best >= candidate) {
+                    best[start + 1] = Some((candidate, start, None));
+                }
+            }
+        }
+
+        // Backtrack the best segmentation.
+        let mut pieces = Vec::new();
+        let mut end = positions.len() - 1;
+        while end > 0 {
+            let (_, start, id) = best[end].expect("Every character can be reached");
+            pieces.push((positions[start], positions[end], id));
+            end = start;
+        }
+        pieces.reverse();
+
+        let mut tokens: Vec<Token> = Vec::new();
+        for (start, end, id) in pieces {
+            let piece = &word[start..end];
+
+            match id {
+                Some(id) => tokens.push(Token {
+                    id,
+                    value: piece.to_string(),
+                    offsets: (start, end),
+                }),
+                None => {
+                    let bytes = match self.byte_fallback {
+                        true => {
+                            byte_fallback(piece, (start, end), |token| self.ids.get(token).copied())
+                        }
+                        false => None,
+                    };
+
+                    match (bytes, self.unk_id) {
+                        (Some(bytes), _) => tokens.extend(bytes),
+                        // Consecutive unknown characters are fused.
+                        (None, Some(unk_id)) => match tokens.last_mut() {
+                            Some(last) if last.id == unk_id => last.offsets.1 = end,
+                            _ => tokens.push(Token {
+                                id: unk_id,
+                                value: self.vocab[unk_id].0.clone(),
+                                offsets: (start, end),
+                            }),
+                        },
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+
+        tokens
+    }
+}
+
+/// Word level model, mapping each word to a token of the vocabulary.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(from = "WordLevelConfig")]
+pub(crate) struct WordLevel {
+    vocab: HashMap<String, usize>,
+    tokens: Vec<String>,
+    unk_token: String,
+}
+
+#[derive(Deserialize)]
+struct WordLevelConfig {
+    vocab: HashMap<String, usize>,
+    #[serde(default = "default_word_level_unk_token")]
+    unk_token: String,
+}
+
+fn default_word_level_unk_token() -> String {
+    "<unk>".to_string()
+}
+
+impl From<WordLevelConfig> for WordLevel {
+    fn from(config: WordLevelConfig) -> Self {
+        Self {
+            tokens: tokens_by_id(&config.vocab),
+            vocab: config.vocab,
+            unk_token: config.unk_token,
+        }
+    }
+}
+
+impl WordLevel {
+    fn tokenize(&self, word: &str) -> Vec<Token> {
+        let (value, id) = match self.vocab.get(word) {
+            Some(id) => (word, id),
+            None => match self.vocab.get(&self.unk_token) {
+                Some(id) => (self.unk_token.as_str(), id),
+                None => return Vec::new(),
+            },
+        };
+
+        vec![Token {
+            id: *id,
+            value: value.to_string(),
+            offsets: (0, word.len()),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(tokens: Vec<Token>) -> Vec<(String, (usize, usize))> {
+        tokens
+            .into_iter()
+            .map(|token| (token.value, token.offsets))
+            .collect()
+    }
+
+    fn vocab(tokens: &[&str]) -> HashMap<String, usize> {
+        tokens
+            .iter()
+            .enumerate()
+            .map(|(id, token)| (token.to_string(), id))
+            .collect()
+    }
+
+    #[test]
+    fn bpe_should_apply_merges_by_rank() {
+        let model: Model = serde_json::from_str(
+            r#"{
+                "type": "BPE",
+                "unk_token": "<unk>",
+                "fuse_unk": true,
+                "vocab": {"<unk>": 0, "l": 1, "o": 2, "w": 3, "lo": 4, "low": 5, "ow": 6},
+                "merges": ["o w", ["l", "o"], "lo w"]
+            }"#,
+        )
+        .unwrap();
+
+        // "o w" has the lowest rank, so "low" is never built.
+        assert_eq!(
+            values(model.tokenize("lowxy")),
+            vec![
+                ("l".to_string(), (0, 1)),
+                ("ow".to_string(), (1, 3)),
+                ("<unk>".to_string(), (3, 5)),
+            ]
+        );
+        assert_eq!(model.token_to_id("lo"), Some(4));
+        assert_eq!(model.id_to_token(5), Some("low"));
+    }
+
+    #[test]
+    fn bpe_should_fallback_to_bytes() {
+        let model = Model::Bpe(Bpe::from(BpeConfig {
+            vocab: vocab(&["a", "<0xC3>", "<0xA9>"]),
+            merges: Vec::new(),
+            unk_token: None,
+            continuing_subword_prefix: None,
+            end_of_word_suffix: None,
+            fuse_unk: false,
+            byte_fallback: true,
+            ignore_merges: false,
+        }));
+
+        assert_eq!(
+            values(model.tokenize("aé")),
+            vec![
+                ("a".to_string(), (0, 1)),
+                ("<0xC3>".to_string(), (1, 3)),
+                ("<0xA9>".to_string(), (1, 3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn wordpiece_should_split_longest_tokens() {
+        let model = Model::WordPiece(WordPiece::from(WordPieceConfig {
+            vocab: vocab(&["[UNK]", "un", "##aff", "##able", "##a"]),
+            unk_token: default_unk_token(),
+            continuing_subword_prefix: default_continuing_subword_prefix(),
+            max_input_chars_per_word: 100,
+        }));
+
+        assert_eq!(
+            values(model.tokenize("unaffable")),
+            vec![
+                ("un".to_string(), (0, 2)),
+                ("##aff".to_string(), (2, 5)),
+                ("##able".to_string(), (5, 9)),
+            ]
+        );
+        assert_eq!(
+            values(model.tokenize("unabl")),
+            vec![("[UNK]".to_string(), (0, 5))]
+        );
+    }
+
+    #[test]
+    fn unigram_should_find_most_likely_tokens() {
+        let model: Model = serde_json::from_str(
+            r#"{
+                "type": "Unigram",
+                "unk_id": 0,
+                "vocab": [["<unk>", 0.0], ["a", -1.0], ["b", -1.0], ["ab", -1.5], ["abc", -5.0], ["c", -2.0]]
+            }"#,
+        )
+        .unwrap();
+
+        // "ab" + "c" (-3.5) is more likely than "abc" (-5) and "a" + "b" + "c" (-4).
+        assert_eq!(
+            values(model.tokenize("abcxy")),
+            vec![
+                ("ab".to_string(), (0, 2)),
+                ("c".to_string(), (2, 3)),
+                ("<unk>".to_string(), (3, 5)),
+            ]
+        );
+    }
+}
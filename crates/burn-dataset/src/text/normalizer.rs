@@ -0,0 +1,471 @@
+use std::ops::Range;
+
+use regex::Regex;
+use serde::Deserialize;
+use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+
+/// Text transformed by the normalizers and pre-tokenizers, keeping track of the byte range of the
+/// input text each of its bytes comes from, so that token offsets refer to the input text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NormalizedString {
+    text: String,
+    alignments: Vec<(usize, usize)>,
+}
+
+impl NormalizedString {
+    /// Creates a normalized string from a slice of the input text starting at the given offset.
+    pub(crate) fn new(text: &str, offset: usize) -> Self {
+        let mut alignments = Vec::with_capacity(text.len());
+
+        for (start, c) in text.char_indices() {
+            let range = (offset + start, offset + start + c.len_utf8());
+            alignments.extend((0..c.len_utf8()).map(|_| range));
+        }
+
+        Self {
+            text: text.to_string(),
+            alignments,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// The byte range of the input text corresponding to a byte range of the normalized text.
+    pub(crate) fn offsets(&self, range: Range<usize>) -> (usize, usize) {
+        if range.is_empty() {
+            let position = match self.alignments.get(range.start) {
+                Some((start, _)) => *start,
+                None => self.alignments.last().map(|(_, end)| *end).unwrap_or(0),
+            };
+            return (position, position);
+        }
+
+        (
+            self.alignments[range.start].0,
+            self.alignments[range.end - 1].1,
+        )
+    }
+
+    /// The normalized string of a byte range of the text.
+    pub(crate) fn slice(&self, range: Range<usize>) -> Self {
+        Self {
+            text: self.text[range.clone()].to_string(),
+            alignments: self.alignments[range].to_vec(),
+        }
+    }
+
+    /// Replaces byte ranges of the text, which must be sorted and not overlap, by new strings.
+    ///
+    /// The new bytes are aligned with the input range of the replaced bytes, or with the position
+    /// of the replaced range when it is empty.
+    pub(crate) fn replace_ranges<I>(&mut self, replacements: I)
+    where
+        I: IntoIterator<Item = (Range<usize>, String)>,
+    {
+        let mut text = String::with_capacity(self.text.len());
+        let mut alignments = Vec::with_capacity(self.alignments.len());
+        let mut position = 0;
+
+        for (range, replacement) in replacements {
+            text.push_str(&self.text[position..range.start]);
+            alignments.extend_from_slice(&self.alignments[position..range.start]);
+
+            let alignment = self.offsets(range.clone());
+            text.push_str(&replacement);
+            alignments.extend((0..replacement.len()).map(|_| alignment));
+            position = range.end;
+        }
+
+        text.push_str(&self.text[position..]);
+        alignments.extend_from_slice(&self.alignments[position..]);
+
+        self.text = text;
+        self.alignments = alignments;
+    }
+
+    /// Replaces each character by the string returned by the function.
+    pub(crate) fn map_chars<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> String,
+    {
+        let replacements = self
+            .text
+            .char_indices()
+            .map(|(start, c)| (start..start + c.len_utf8(), f(c)))
+            .collect::<Vec<_>>();
+
+        self.replace_ranges(replacements);
+    }
+
+    /// Applies a composing normalization to each sequence of a starter character followed by
+    /// combining characters, which are the units composition can change.
+    fn compose<F>(&mut self, f: F)
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut segments = Vec::new();
+        let mut start = 0;
+
+        for (index, c) in self.text.char_indices() {
+            if index > 0 && canonical_combining_class(c) == 0 {
+                segments.push(start..index);
+                start = index;
+            }
+        }
+        if start < self.text.len() {
+            segments.push(start..self.text.len());
+        }
+
+        let replacements = segments
+            .into_iter()
+            .map(|range| {
+                let replacement = f(&self.text[range.clone()]);
+                (range, replacement)
+            })
+            .collect::<Vec<_>>();
+
+        self.replace_ranges(replacements);
+    }
+
+    /// Adds a string at the start of the text.
+    pub(crate) fn prepend(&mut self, prefix: &str) {
+        self.replace_ranges([(0..0, prefix.to_string())]);
+    }
+
+    /// Replaces the matches of a pattern.
+    pub(crate) fn replace(&mut self, pattern: &Pattern, content: &str) {
+        let replacements = pattern
+            .find_matches(&self.text)
+            .into_iter()
+            .map(|range| (range, content.to_string()))
+            .collect::<Vec<_>>();
+
+        self.replace_ranges(replacements);
+    }
+
+    /// Removes the whitespaces at the start and at the end of the text.
+    pub(crate) fn strip(&mut self, left: bool, right: bool) {
+        let start = match left {
+            true => self.text.len() - self.text.trim_start().len(),
+            false => 0,
+        };
+        let end = match right {
+            true => self.text.trim_end().len(),
+            false => self.text.len(),
+        };
+
+        *self = self.slice(start..end.max(start));
+    }
+
+    /// Splits the text around the given matches, which must be sorted and not overlap.
+    pub(crate) fn split(&self, matches: &[Range<usize>], behavior: SplitBehavior) -> Vec<Self> {
+        // Pieces of the text as (range, is_match).
+        let mut pieces = Vec::new();
+        let mut position = 0;
+
+        for range in matches {
+            if position < range.start {
+                pieces.push((position..range.start, false));
+            }
+            if !range.is_empty() {
+                pieces.push((range.clone(), true));
+            }
+            position = range.end;
+        }
+        if position < self.text.len() {
+            pieces.push((position..self.text.len(), false));
+        }
+
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        let mut previous_match = false;
+
+        for (range, is_match) in pieces {
+            let merge = match behavior {
+                SplitBehavior::Removed if is_match => continue,
+                SplitBehavior::Removed | SplitBehavior::Isolated => false,
+                SplitBehavior::MergedWithPrevious => is_match && !previous_match,
+                SplitBehavior::MergedWithNext => !is_match && previous_match,
+                SplitBehavior::Contiguous => is_match && previous_match,
+            };
+
+            match ranges.last_mut() {
+                Some(last) if merge => last.end = range.end,
+                _ => ranges.push(range),
+            }
+            previous_match = is_match;
+        }
+
+        ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| self.slice(range))
+            .collect()
+    }
+}
+
+/// How the matches of a pattern are kept when splitting a text.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SplitBehavior {
+    /// The matches are removed.
+    Removed,
+    /// Each match is a piece.
+    #[default]
+    Isolated,
+    /// Each match is added to the previous piece.
+    MergedWithPrevious,
+    /// Each match is added to the next piece.
+    MergedWithNext,
+    /// Consecutive matches are grouped in a single piece.
+    Contiguous,
+}
+
+/// Pattern of the split pre-tokenizer and of the replace normalizer and decoder.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "PatternConfig")]
+pub(crate) enum Pattern {
+    String(String),
+    Regex(Regex),
+}
+
+#[derive(Deserialize)]
+enum PatternConfig {
+    String(String),
+    Regex(String),
+}
+
+impl TryFrom<PatternConfig> for Pattern {
+    type Error = regex::Error;
+
+    fn try_from(config: PatternConfig) -> Result<Self, Self::Error> {
+        match config {
+            PatternConfig::String(string) => Ok(Pattern::String(string)),
+            PatternConfig::Regex(regex) => Ok(Pattern::Regex(Regex::new(&regex)?)),
+        }
+    }
+}
+
+impl Pattern {
+    /// The byte ranges of the non-overlapping matches of the pattern.
+    pub(crate) fn find_matches(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            Pattern::String(pattern) if pattern.is_empty() => Vec::new(),
+            Pattern::String(pattern) => text
+                .match_indices(pattern.as_str())
+                .map(|(start, matched)| start..start + matched.len())
+                .collect(),
+            Pattern::Regex(regex) => regex
+                .find_iter(text)
+                .map(|matched| matched.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Normalizer of a [tokenizer](super::Tokenizer), transforming the input text before it is split.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub(crate) enum Normalizer {
+    Sequence {
+        normalizers: Vec<Normalizer>,
+    },
+    #[serde(rename = "NFC")]
+    Nfc,
+    #[serde(rename = "NFD")]
+    Nfd,
+    #[serde(rename = "NFKC")]
+    Nfkc,
+    #[serde(rename = "NFKD")]
+    Nfkd,
+    Lowercase,
+    StripAccents,
+    #[serde(rename = "BertNormalizer")]
+    Bert {
+        #[serde(default = "default_true")]
+        clean_text: bool,
+        #[serde(default = "default_true")]
+        handle_chinese_chars: bool,
+        #[serde(default)]
+        strip_accents: Option<bool>,
+        #[serde(default = "default_true")]
+        lowercase: bool,
+    },
+    Replace {
+        pattern: Pattern,
+        content: String,
+    },
+    Prepend {
+        prepend: String,
+    },
+    Strip {
+        #[serde(default)]
+        strip_left: bool,
+        #[serde(default)]
+        strip_right: bool,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Normalizer {
+    pub(crate) fn normalize(&self, text: &mut NormalizedString) {
+        match self {
+            Normalizer::Sequence { normalizers } => normalizers
+                .iter()
+                .for_each(|normalizer| normalizer.normalize(text)),
+            Normalizer::Nfc => text.compose(|segment| segment.nfc().collect()),
+            Normalizer::Nfkc => text.compose(|segment| segment.nfkc().collect()),
+            Normalizer::Nfd => text.map_chars(|c| std::iter::once(c).nfd().collect()),
+            Normalizer::Nfkd => text.map_chars(|c| std::iter::once(c).nfkd().collect()),
+            Normalizer::Lowercase => text.map_chars(|c| c.to_lowercase().collect()),
+            Normalizer::StripAccents => strip_accents(text),
+            Normalizer::Bert {
+                clean_text,
+                handle_chinese_chars,
+                strip_accents: strip,
+                lowercase,
+            } => {
+                if *clean_text {
+                    text.map_chars(|c| match c {
+                        '\0' | '\u{fffd}' => String::new(),
+                        c if c.is_control() && !c.is_whitespace() => String::new(),
+                        c if c.is_whitespace() => " ".to_string(),
+                        c => c.to_string(),
+                    });
+                }
+                if *handle_chinese_chars {
+                    text.map_chars(|c| match is_chinese_char(c) {
+                        true => format!(" {c} "),
+                        false => c.to_string(),
+                    });
+                }
+                // Accents are stripped by default when lowercasing, like the original BERT.
+                if strip.unwrap_or(*lowercase) {
+                    text.map_chars(|c| std::iter::once(c).nfd().collect());
+                    strip_accents(text);
+                }
+                if *lowercase {
+                    text.map_chars(|c| c.to_lowercase().collect());
+                }
+            }
+            Normalizer::Replace { pattern, content } => text.replace(pattern, content),
+            Normalizer::Prepend { prepend } => {
+                if !text.is_empty() {
+                    text.prepend(prepend)
+                }
+            }
+            Normalizer::Strip {
+                strip_left,
+                strip_right,
+            } => text.strip(*strip_left, *strip_right),
+        }
+    }
+}
+
+/// Removes the combining marks, which carry the accents of decomposed characters.
+fn strip_accents(text: &mut NormalizedString) {
+    text.map_chars(
+        |c| match canonical_combining_class(c) != 0 || is_combining_mark(c) {
+            true => String::new(),
+            false => c.to_string(),
+        },
+    );
+}
+
+/// Whether the character is in one of the combining diacritical marks blocks.
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+/// Whether the character is a CJK ideograph, as defined by BERT.
+pub(crate) fn is_chinese_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x4E00..=0x9FFF
+            | 0x3400..=0x4DBF
+            | 0x20000..=0x2A6DF
+            | 0x2A700..=0x2B73F
+            | 0x2B740..=0x2B81F
+            | 0x2B920..=0x2CEAF
+            | 0xF900..=0xFAFF
+            | 0x2F800..=0x2FA1F
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_string_should_track_offsets() {
+        let mut text = NormalizedString::new("Héllo wörld", 0);
+
+        Normalizer::Lowercase.normalize(&mut text);
+        Normalizer::Nfd.normalize(&mut text);
+        Normalizer::StripAccents.normalize(&mut text);
+        text.prepend("_");
+
+        assert_eq!(text.as_str(), "_hello world");
+        // "world" comes from "wörld", where "ö" is two bytes long.
+        assert_eq!(text.offsets(7..12), (7, 13));
+        assert_eq!(text.offsets(0..6), (0, 6));
+        assert_eq!(text.offsets(0..1), (0, 0));
+    }
+
+    #[test]
+    fn bert_normalizer_should_clean_and_split_chinese_chars() {
+        let normalizer = Normalizer::Bert {
+            clean_text: true,
+            handle_chinese_chars: true,
+            strip_accents: None,
+            lowercase: true,
+        };
+        let mut text = NormalizedString::new("Café\t中\u{0}!", 0);
+
+        normalizer.normalize(&mut text);
+
+        assert_eq!(text.as_str(), "cafe  中 !");
+    }
+
+    #[test]
+    fn split_should_apply_behavior() {
+        let text = NormalizedString::new("a-b--c", 0);
+        let matches = [1..2, 3..4, 4..5];
+        let split = |behavior| {
+            text.split(&matches, behavior)
+                .iter()
+                .map(|piece| piece.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(split(SplitBehavior::Removed), vec!["a", "b", "c"]);
+        assert_eq!(
+            split(SplitBehavior::Isolated),
+            vec!["a", "-", "b", "-", "-", "c"]
+        );
+        // Only the last of consecutive matches is merged, like the HuggingFace tokenizers.
+        assert_eq!(
+            split(SplitBehavior::MergedWithPrevious),
+            vec!["a-", "b-", "-", "c"]
+        );
+        assert_eq!(
+            split(SplitBehavior::MergedWithNext),
+            vec!["a", "-b", "-", "-c"]
+        );
+        assert_eq!(
+            split(SplitBehavior::Contiguous),
+            vec!["a", "-", "b", "--", "c"]
+        );
+    }
+}
@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::normalizer::{NormalizedString, Pattern, SplitBehavior};
+
+/// Pre-tokenizer of a [tokenizer](super::Tokenizer), splitting the normalized text into the words
+/// tokenized by the model.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub(crate) enum PreTokenizer {
+    Sequence {
+        pretokenizers: Vec<PreTokenizer>,
+    },
+    Whitespace,
+    WhitespaceSplit,
+    #[serde(rename = "BertPreTokenizer")]
+    Bert,
+    Punctuation {
+        #[serde(default)]
+        behavior: SplitBehavior,
+    },
+    Digits {
+        #[serde(default)]
+        individual_digits: bool,
+    },
+    CharDelimiterSplit {
+        delimiter: char,
+    },
+    Metaspace {
+        #[serde(default = "default_replacement")]
+        replacement: char,
+        #[serde(flatten)]
+        prepend_scheme: PrependScheme,
+        #[serde(default = "default_true")]
+        split: bool,
+    },
+    ByteLevel {
+        #[serde(default = "default_true")]
+        add_prefix_space: bool,
+        #[serde(default = "default_true")]
+        use_regex: bool,
+    },
+    Split {
+        pattern: Pattern,
+        behavior: SplitBehavior,
+        #[serde(default)]
+        invert: bool,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_replacement() -> char {
+    '▁'
+}
+
+/// When the metaspace replacement character is added at the start of the text, either set with
+/// `prepend_scheme` or with the legacy `add_prefix_space` field.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "PrependSchemeConfig")]
+pub(crate) enum PrependScheme {
+    Always,
+    First,
+    Never,
+}
+
+#[derive(Deserialize)]
+struct PrependSchemeConfig {
+    #[serde(default)]
+    prepend_scheme: Option<String>,
+    #[serde(default)]
+    add_prefix_space: Option<bool>,
+}
+
+impl From<PrependSchemeConfig> for PrependScheme {
+    fn from(config: PrependSchemeConfig) -> Self {
+        match (config.prepend_scheme.as_deref(), config.add_prefix_space) {
+            (Some("first"), _) => PrependScheme::First,
+            (Some("never"), _) | (None, Some(false)) => PrependScheme::Never,
+            _ => PrependScheme::Always,
+        }
+    }
+}
+
+impl PreTokenizer {
+    /// Splits each piece of the text.
+    ///
+    /// The first piece of the sequence is the start of the input text, which matters for the
+    /// metaspace `first` prepend scheme.
+    pub(crate) fn pre_tokenize(
+        &self,
+        pieces: Vec<NormalizedString>,
+        first: bool,
+    ) -> Vec<NormalizedString> {
+        match self {
+            PreTokenizer::Sequence { pretokenizers } => {
+                pretokenizers.iter().fold(pieces, |pieces, pre_tokenizer| {
+                    pre_tokenizer.pre_tokenize(pieces, first)
+                })
+            }
+            _ => pieces
+                .into_iter()
+                .enumerate()
+                .flat_map(|(index, piece)| self.split(piece, first && index == 0))
+                .collect(),
+        }
+    }
+
+    fn split(&self, mut piece: NormalizedString, first: bool) -> Vec<NormalizedString> {
+        let text = piece.as_str();
+
+        match self {
+            PreTokenizer::Sequence { .. } => unreachable!("Sequences split each of their pieces"),
+            PreTokenizer::Whitespace => {
+                static WHITESPACE: OnceLock<Regex> = OnceLock::new();
+                let regex = WHITESPACE.get_or_init(|| Regex::new(r"\w+|[^\w\s]+").unwrap());
+                let words = regex
+                    .find_iter(text)
+                    .map(|matched| matched.range())
+                    .collect::<Vec<_>>();
+
+                // The words are kept, and the whitespaces between them removed.
+                piece.split(&complement(&words, text.len()), SplitBehavior::Removed)
+            }
+            PreTokenizer::WhitespaceSplit => piece.split(
+                &char_matches(text, char::is_whitespace),
+                SplitBehavior::Removed,
+            ),
+            PreTokenizer::Bert => piece
+                .split(
+                    &char_matches(text, char::is_whitespace),
+                    SplitBehavior::Removed,
+                )
+                .into_iter()
+                .flat_map(|word| {
+                    let matches = char_matches(word.as_str(), is_punctuation);
+                    word.split(&matches, SplitBehavior::Isolated)
+                })
+                .collect(),
+            PreTokenizer::Punctuation { behavior } => {
+                piece.split(&char_matches(text, is_punctuation), *behavior)
+            }
+            PreTokenizer::Digits { individual_digits } => {
+                let behavior = match individual_digits {
+                    true => SplitBehavior::Isolated,
+                    false => SplitBehavior::Contiguous,
+                };
+                piece.split(&char_matches(text, |c| c.is_ascii_digit()), behavior)
+            }
+            PreTokenizer::CharDelimiterSplit { delimiter } => piece.split(
+                &char_matches(text, |c| c == *delimiter),
+                SplitBehavior::Removed,
+            ),
+            PreTokenizer::Metaspace {
+                replacement,
+                prepend_scheme,
+                split,
+            } => {
+                piece.map_chars(|c| match c {
+                    ' ' => replacement.to_string(),
+                    c => c.to_string(),
+                });
+
+                let prepend = match prepend_scheme {
+                    PrependScheme::Always => true,
+                    PrependScheme::First => first,
+                    PrependScheme::Never => false,
+                };
+                if prepend && !piece.as_str().starts_with(*replacement) {
+                    piece.prepend(&replacement.to_string());
+                }
+
+                match split {
+                    true => {
+                        let matches = char_matches(piece.as_str(), |c| c == *replacement);
+                        piece.split(&matches, SplitBehavior::MergedWithNext)
+                    }
+                    false => vec![piece],
+                }
+            }
+            PreTokenizer::ByteLevel {
+                add_prefix_space,
+                use_regex,
+            } => {
+                if *add_prefix_space && !text.starts_with(' ') {
+                    piece.prepend(" ");
+                }
+
+                let mut words = match use_regex {
+                    true => {
+                        let words = gpt2_split(piece.as_str());
+                        piece.split(&words, SplitBehavior::Isolated)
+                    }
+                    false => vec![piece],
+                };
+
+                let chars = bytes_char();
+                for word in words.iter_mut() {
+                    word.map_chars(|c| {
+                        let mut buffer = [0; 4];
+                        c.encode_utf8(&mut buffer)
+                            .bytes()
+                            .map(|byte| chars[&byte])
+                            .collect()
+                    });
+                }
+
+                words
+            }
+            PreTokenizer::Split {
+                pattern,
+                behavior,
+                invert,
+            } => {
+                let matches = pattern.find_matches(text);
+                match invert {
+                    // The matches are the words, so the delimiters are the rest of the text.
+                    true => piece.split(&complement(&matches, text.len()), *behavior),
+                    false => piece.split(&matches, *behavior),
+                }
+            }
+        }
+    }
+}
+
+/// The byte ranges of the characters matching the predicate.
+fn char_matches<F: Fn(char) -> bool>(text: &str, predicate: F) -> Vec<Range<usize>> {
+    text.char_indices()
+        .filter(|(_, c)| predicate(*c))
+        .map(|(start, c)| start..start + c.len_utf8())
+        .collect()
+}
+
+/// The byte ranges between the given sorted ranges.
+fn complement(ranges: &[Range<usize>], len: usize) -> Vec<Range<usize>> {
+    let mut complement = Vec::new();
+    let mut position = 0;
+
+    for range in ranges {
+        if position < range.start {
+            complement.push(position..range.start);
+        }
+        position = range.end;
+    }
+    if position < len {
+        complement.push(position..len);
+    }
+
+    complement
+}
+
+/// Whether the character is a punctuation character.
+///
+/// Only the ASCII punctuation and the main Unicode punctuation blocks are considered, which
+/// covers the general punctuation category in practice.
+pub(crate) fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(
+            c as u32,
+            0x00A1..=0x00BF
+                | 0x2010..=0x2027
+                | 0x2030..=0x205E
+                | 0x2E00..=0x2E7F
+                | 0x3001..=0x3003
+                | 0x3008..=0x3011
+                | 0x3014..=0x301F
+                | 0xFE10..=0xFE19
+                | 0xFE30..=0xFE4F
+                | 0xFE50..=0xFE6B
+                | 0xFF01..=0xFF0F
+                | 0xFF1A..=0xFF20
+                | 0xFF3B..=0xFF40
+                | 0xFF5B..=0xFF65
+        )
+}
+
+/// Splits the text like the GPT-2 regex
+/// `'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+| ?\p{N}+| ?[^\s\p{L}\p{N}]+|\s+(?!\S)|\s+`, whose look-ahead
+/// isn't supported by the regex crate.
+pub(crate) fn gpt2_split(text: &str) -> Vec<Range<usize>> {
+    #[derive(PartialEq, Clone, Copy)]
+    enum Class {
+        Letter,
+        Number,
+        Space,
+        Other,
+    }
+
+    fn class(c: char) -> Class {
+        if c.is_alphabetic() {
+            Class::Letter
+        } else if c.is_numeric() {
+            Class::Number
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    }
+
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let byte = |index: usize| {
+        chars
+            .get(index)
+            .map(|(byte, _)| *byte)
+            .unwrap_or(text.len())
+    };
+    let mut words = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let rest = &text[chars[i].0..];
+
+        // Contractions.
+        if let Some(contraction) = ["'s", "'t", "'re", "'ve", "'m", "'ll", "'d"]
+            .iter()
+            .find(|contraction| rest.starts_with(*contraction))
+        {
+            words.push(chars[i].0..chars[i].0 + contraction.len());
+            i += contraction.len();
+            continue;
+        }
+
+        // A word of letters, numbers or other characters, optionally preceded by a space.
+        let start = match chars[i].1 == ' ' {
+            true if chars
+                .get(i + 1)
+                .is_some_and(|(_, c)| class(*c) != Class::Space) =>
+            {
+                i + 1
+            }
+            _ => i,
+        };
+        let word_class = class(chars[start].1);
+
+        if word_class != Class::Space {
+            let mut end = start + 1;
+            while end < chars.len() && class(chars[end].1) == word_class {
+                end += 1;
+            }
+            words.push(byte(i)..byte(end));
+            i = end;
+            continue;
+        }
+
+        // Whitespaces, without the last one when followed by a word.
+        let mut end = i + 1;
+        while end < chars.len() && class(chars[end].1) == Class::Space {
+            end += 1;
+        }
+        if end < chars.len() && end - i > 1 {
+            end -= 1;
+        }
+        words.push(byte(i)..byte(end));
+        i = end;
+    }
+
+    words
+}
+
+/// The printable character each byte is mapped to by the byte-level pre-tokenizer, as in GPT-2.
+pub(crate) fn bytes_char() -> &'static HashMap<u8, char> {
+    static BYTES_CHAR: OnceLock<HashMap<u8, char>> = OnceLock::new();
+
+    BYTES_CHAR.get_or_init(|| {
+        let printable = |byte: u8| {
+            (b'!'..=b'~').contains(&byte)
+                || (0xA1..=0xAC).contains(&byte)
+                || (0xAE..=0xFF).contains(&byte)
+        };
+        let mut shift = 0;
+
+        (0..=255u8)
+            .map(|byte| match printable(byte) {
+                true => (byte, char::from(byte)),
+                false => {
+                    shift += 1;
+                    (byte, char::from_u32(255 + shift).unwrap())
+                }
+            })
+            .collect()
+    })
+}
+
+/// The byte each character of the byte-level pre-tokenizer comes from.
+pub(crate) fn char_bytes() -> &'static HashMap<char, u8> {
+    static CHAR_BYTES: OnceLock<HashMap<char, u8>> = OnceLock::new();
+
+    CHAR_BYTES.get_or_init(|| bytes_char().iter().map(|(byte, c)| (*c, *byte)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(pre_tokenizer: &PreTokenizer, text: &str) -> Vec<(String, (usize, usize))> {
+        pre_tokenizer
+            .pre_tokenize(vec![NormalizedString::new(text, 0)], true)
+            .into_iter()
+            .map(|word| {
+                let offsets = word.offsets(0..word.as_str().len());
+                (word.as_str().to_string(), offsets)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gpt2_split_should_match_regex() {
+        let text = "Hello world's  123 !!  \nok   ";
+
+        let words = gpt2_split(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            words,
+            vec!["Hello", " world", "'s", " ", " 123", " !!", "  ", "\n", "ok", "   "]
+        );
+    }
+
+    #[test]
+    fn byte_level_should_map_bytes_and_keep_offsets() {
+        let pre_tokenizer = PreTokenizer::ByteLevel {
+            add_prefix_space: false,
+            use_regex: true,
+        };
+
+        assert_eq!(
+            words(&pre_tokenizer, "Hi é!"),
+            vec![
+                ("Hi".to_string(), (0, 2)),
+                ("ĠÃ©".to_string(), (2, 5)),
+                ("!".to_string(), (5, 6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn bert_pre_tokenizer_should_split_punctuation() {
+        assert_eq!(
+            words(&PreTokenizer::Bert, "Hey, you!"),
+            vec![
+                ("Hey".to_string(), (0, 3)),
+                (",".to_string(), (3, 4)),
+                ("you".to_string(), (5, 8)),
+                ("!".to_string(), (8, 9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn metaspace_should_replace_spaces() {
+        let pre_tokenizer = PreTokenizer::Metaspace {
+            replacement: '▁',
+            prepend_scheme: PrependScheme::Always,
+            split: true,
+        };
+
+        assert_eq!(
+            words(&pre_tokenizer, "Hey you"),
+            vec![("▁Hey".to_string(), (0, 3)), ("▁you".to_string(), (3, 7)),]
+        );
+    }
+
+    #[test]
+    fn byte_chars_should_be_bijective() {
+        assert_eq!(bytes_char().len(), 256);
+        assert_eq!(char_bytes().len(), 256);
+        assert_eq!(bytes_char()[&b' '], 'Ġ');
+        assert_eq!(char_bytes()[&'Ċ'], b'\n');
+    }
+}
@@ -0,0 +1,573 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::normalizer::Pattern;
+use super::pre_tokenizer::{char_bytes, PrependScheme};
+
+/// Token of an encoded sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EncodedToken {
+    pub(crate) id: usize,
+    pub(crate) value: String,
+    pub(crate) offsets: (usize, usize),
+    pub(crate) special: bool,
+}
+
+/// Post-processor of a [tokenizer](super::Tokenizer), adding the special tokens around the
+/// encoded sequences and assigning their type ids.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub(crate) enum PostProcessor {
+    Sequence {
+        processors: Vec<PostProcessor>,
+    },
+    TemplateProcessing {
+        single: Vec<TemplatePiece>,
+        pair: Vec<TemplatePiece>,
+        #[serde(default)]
+        special_tokens: HashMap<String, SpecialTokens>,
+    },
+    BertProcessing {
+        sep: (String, usize),
+        cls: (String, usize),
+    },
+    RobertaProcessing {
+        sep: (String, usize),
+        cls: (String, usize),
+        #[serde(default = "default_true")]
+        trim_offsets: bool,
+    },
+    ByteLevel {
+        #[serde(default = "default_true")]
+        trim_offsets: bool,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Piece of a template, either one of the sequences (`A` or `B`) or a special token.
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) enum TemplatePiece {
+    Sequence { id: String, type_id: usize },
+    SpecialToken { id: String, type_id: usize },
+}
+
+/// Tokens added for a special token of a template.
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) struct SpecialTokens {
+    ids: Vec<usize>,
+    tokens: Vec<String>,
+}
+
+impl PostProcessor {
+    /// Combines the encoded sequences into the tokens of the encoding, with their type ids.
+    pub(crate) fn process(
+        &self,
+        first: Vec<EncodedToken>,
+        second: Option<Vec<EncodedToken>>,
+        add_special_tokens: bool,
+    ) -> Vec<(EncodedToken, usize)> {
+        let (first, second) = self.trim(first, second);
+
+        self.combine(first, second, add_special_tokens)
+    }
+
+    fn combine(
+        &self,
+        first: Vec<EncodedToken>,
+        second: Option<Vec<EncodedToken>>,
+        add_special_tokens: bool,
+    ) -> Vec<(EncodedToken, usize)> {
+        match self {
+            PostProcessor::Sequence { processors } => {
+                // The byte-level processors only trim the offsets, the last of the other
+                // processors combines the sequences.
+                match processors
+                    .iter()
+                    .rev()
+                    .find(|processor| !matches!(processor, PostProcessor::ByteLevel { .. }))
+                {
+                    Some(processor) => processor.combine(first, second, add_special_tokens),
+                    None => surround(first, second, false, |_, _| unreachable!()),
+                }
+            }
+            PostProcessor::TemplateProcessing {
+                single,
+                pair,
+                special_tokens,
+            } => {
+                let sequence = |id: &str| match id {
+                    "A" => Some(&first),
+                    _ => second.as_ref(),
+                };
+                let template = match second {
+                    Some(_) => pair,
+                    None => single,
+                };
+
+                template
+                    .iter()
+                    .flat_map(|piece| match piece {
+                        TemplatePiece::Sequence { id, type_id } => sequence(id)
+                            .into_iter()
+                            .flatten()
+                            .map(|token| (token.clone(), *type_id))
+                            .collect::<Vec<_>>(),
+                        TemplatePiece::SpecialToken { id, type_id } if add_special_tokens => {
+                            special_tokens
+                                .get(id)
+                                .into_iter()
+                                .flat_map(|tokens| tokens.ids.iter().zip(tokens.tokens.iter()))
+                                .map(|(id, token)| (special_token(token, *id), *type_id))
+                                .collect()
+                        }
+                        TemplatePiece::SpecialToken { .. } => Vec::new(),
+                    })
+                    .collect()
+            }
+            PostProcessor::BertProcessing { sep, cls } => {
+                let sep = special_token(&sep.0, sep.1);
+                let cls = special_token(&cls.0, cls.1);
+
+                surround(first, second, add_special_tokens, |first, second| {
+                    let mut tokens = vec![(cls.clone(), 0)];
+                    tokens.extend(first.into_iter().map(|token| (token, 0)));
+                    tokens.push((sep.clone(), 0));
+
+                    if let Some(second) = second {
+                        tokens.extend(second.into_iter().map(|token| (token, 1)));
+                        tokens.push((sep.clone(), 1));
+                    }
+                    tokens
+                })
+            }
+            PostProcessor::RobertaProcessing { sep, cls, .. } => {
+                let sep = special_token(&sep.0, sep.1);
+                let cls = special_token(&cls.0, cls.1);
+
+                // RoBERTa doesn't use type ids.
+                surround(first, second, add_special_tokens, |first, second| {
+                    let mut tokens = vec![cls.clone()];
+                    tokens.extend(first);
+                    tokens.push(sep.clone());
+
+                    if let Some(second) = second {
+                        tokens.push(sep.clone());
+                        tokens.extend(second);
+                        tokens.push(sep.clone());
+                    }
+                    tokens.into_iter().map(|token| (token, 0)).collect()
+                })
+            }
+            PostProcessor::ByteLevel { .. } => {
+                surround(first, second, false, |_, _| unreachable!())
+            }
+        }
+    }
+
+    /// Trims the offsets of the sequences with the processors of byte-level tokens.
+    fn trim(
+        &self,
+        first: Vec<EncodedToken>,
+        second: Option<Vec<EncodedToken>>,
+    ) -> (Vec<EncodedToken>, Option<Vec<EncodedToken>>) {
+        match self {
+            PostProcessor::Sequence { processors } => processors
+                .iter()
+                .fold((first, second), |(first, second), processor| {
+                    processor.trim(first, second)
+                }),
+            PostProcessor::ByteLevel { trim_offsets: true }
+            | PostProcessor::RobertaProcessing {
+                trim_offsets: true, ..
+            } => (trim_offsets(first), second.map(trim_offsets)),
+            _ => (first, second),
+        }
+    }
+}
+
+/// Adds the special tokens around the sequences, or concatenates them with the type ids 0 and 1.
+fn surround<F>(
+    first: Vec<EncodedToken>,
+    second: Option<Vec<EncodedToken>>,
+    add_special_tokens: bool,
+    add: F,
+) -> Vec<(EncodedToken, usize)>
+where
+    F: FnOnce(Vec<EncodedToken>, Option<Vec<EncodedToken>>) -> Vec<(EncodedToken, usize)>,
+{
+    if add_special_tokens {
+        return add(first, second);
+    }
+
+    first
+        .into_iter()
+        .map(|token| (token, 0))
+        .chain(second.into_iter().flatten().map(|token| (token, 1)))
+        .collect()
+}
+
+fn special_token(value: &str, id: usize) -> EncodedToken {
+    EncodedToken {
+        id,
+        value: value.to_string(),
+        offsets: (0, 0),
+        special: true,
+    }
+}
+
+/// Removes the spaces encoded by the byte-level pre-tokenizer (`Ġ`) from the token offsets.
+fn trim_offsets(tokens: Vec<EncodedToken>) -> Vec<EncodedToken> {
+    let space = char_bytes()
+        .iter()
+        .find_map(|(c, byte)| (*byte == b' ').then_some(*c))
+        .expect("The space byte is mapped to a character");
+
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            let (start, end) = token.offsets;
+            let leading = token.value.chars().take_while(|c| *c == space).count();
+            let trailing = match leading == token.value.chars().count() {
+                true => 0,
+                false => token
+                    .value
+                    .chars()
+                    .rev()
+                    .take_while(|c| *c == space)
+                    .count(),
+            };
+
+            // Tokens made only of spaces keep at least one of them.
+            let leading = leading.min((end - start).saturating_sub(1));
+            let start = start + leading;
+            let end = end - trailing.min(end - start);
+            token.offsets = (start, end);
+            token
+        })
+        .collect()
+}
+
+/// Decoder of a [tokenizer](super::Tokenizer), converting the tokens back into text.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub(crate) enum Decoder {
+    Sequence {
+        decoders: Vec<Decoder>,
+    },
+    ByteLevel,
+    WordPiece {
+        #[serde(default = "default_prefix")]
+        prefix: String,
+        #[serde(default = "default_true")]
+        cleanup: bool,
+    },
+    Metaspace {
+        #[serde(default = "default_replacement")]
+        replacement: char,
+        #[serde(flatten)]
+        prepend_scheme: PrependScheme,
+    },
+    #[serde(rename = "BPEDecoder")]
+    Bpe {
+        #[serde(default = "default_suffix")]
+        suffix: String,
+    },
+    Replace {
+        pattern: Pattern,
+        content: String,
+    },
+    ByteFallback,
+    Fuse,
+    Strip {
+        content: char,
+        start: usize,
+        stop: usize,
+    },
+}
+
+fn default_prefix() -> String {
+    "##".to_string()
+}
+
+fn default_replacement() -> char {
+    '▁'
+}
+
+fn default_suffix() -> String {
+    "</w>".to_string()
+}
+
+impl Decoder {
+    /// Decodes the tokens, returning the decoded pieces of text to concatenate.
+    pub(crate) fn decode(&self, tokens: Vec<String>) -> Vec<String> {
+        match self {
+            Decoder::Sequence { decoders } => decoders
+                .iter()
+                .fold(tokens, |tokens, decoder| decoder.decode(tokens)),
+            Decoder::ByteLevel => {
+                let bytes = tokens
+                    .iter()
+                    .flat_map(|token| token.chars())
+                    .flat_map(|c| match char_bytes().get(&c) {
+                        Some(byte) => vec![*byte],
+                        None => c.to_string().into_bytes(),
+                    })
+                    .collect::<Vec<_>>();
+
+                vec![String::from_utf8_lossy(&bytes).into_owned()]
+            }
+            Decoder::WordPiece { prefix, cleanup } => tokens
+                .into_iter()
+                .enumerate()
+                .map(|(index, token)| {
+                    let token = match (index, token.strip_prefix(prefix.as_str())) {
+                        (0, _) => token,
+                        (_, Some(rest)) => rest.to_string(),
+                        (_, None) => format!(" {token}"),
+                    };
+                    match cleanup {
+                        true => cleanup_tokenization(&token),
+                        false => token,
+                    }
+                })
+                .collect(),
+            Decoder::Metaspace {
+                replacement,
+                prepend_scheme,
+            } => tokens
+                .into_iter()
+                .enumerate()
+                .map(|(index, token)| {
+                    let token = token.replace(*replacement, " ");
+                    match (index, prepend_scheme) {
+                        (0, PrependScheme::Always | PrependScheme::First) => {
+                            token.strip_prefix(' ').map(str::to_string).unwrap_or(token)
+                        }
+                        _ => token,
+                    }
+                })
+                .collect(),
+            Decoder::Bpe { suffix } => {
+                let last = tokens.len().saturating_sub(1);
+                tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, token)| match index == last {
+                        true => token.replace(suffix.as_str(), ""),
+                        false => token.replace(suffix.as_str(), " "),
+                    })
+                    .collect()
+            }
+            Decoder::Replace { pattern, content } => tokens
+                .into_iter()
+                .map(|token| {
+                    let mut replaced = String::new();
+                    let mut last = 0;
+                    for range in pattern.find_matches(&token) {
+                        replaced.push_str(&token[last..range.start]);
+                        replaced.push_str(content);
+                        last = range.end;
+                    }
+                    replaced.push_str(&token[last..]);
+                    replaced
+                })
+                .collect(),
+            Decoder::ByteFallback => {
+                let mut decoded = Vec::new();
+                let mut bytes = Vec::new();
+
+                for token in tokens {
+                    match parse_byte_token(&token) {
+                        Some(byte) => bytes.push(byte),
+                        None => {
+                            flush_bytes(&mut bytes, &mut decoded);
+                            decoded.push(token);
+                        }
+                    }
+                }
+                flush_bytes(&mut bytes, &mut decoded);
+
+                decoded
+            }
+            Decoder::Fuse => vec![tokens.concat()],
+            Decoder::Strip {
+                content,
+                start,
+                stop,
+            } => tokens
+                .into_iter()
+                .map(|token| {
+                    let chars = token.chars().collect::<Vec<_>>();
+                    let leading = chars.iter().take(*start).take_while(|c| *c == content);
+                    let begin = leading.count();
+                    let trailing = chars[begin..]
+                        .iter()
+                        .rev()
+                        .take(*stop)
+                        .take_while(|c| *c == content);
+                    let end = chars.len() - trailing.count();
+
+                    chars[begin..end].iter().collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Parses the tokens of bytes like `<0x41>`.
+fn parse_byte_token(token: &str) -> Option<u8> {
+    let hex = token.strip_prefix("<0x")?.strip_suffix('>')?;
+
+    match hex.len() {
+        2 => u8::from_str_radix(hex, 16).ok(),
+        _ => None,
+    }
+}
+
+/// Decodes consecutive bytes, each invalid byte being replaced by the replacement character.
+fn flush_bytes(bytes: &mut Vec<u8>, decoded: &mut Vec<String>) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    match String::from_utf8(std::mem::take(bytes)) {
+        Ok(text) => decoded.push(text),
+        Err(error) => decoded.extend(
+            error
+                .as_bytes()
+                .iter()
+                .map(|_| char::REPLACEMENT_CHARACTER.to_string()),
+        ),
+    }
+}
+
+/// Removes the spaces added before punctuation and contractions when joining WordPiece tokens.
+fn cleanup_tokenization(text: &str) -> String {
+    text.replace(" .", ".")
+        .replace(" ?", "?")
+        .replace(" !", "!")
+        .replace(" ,", ",")
+        .replace(" ' ", "'")
+        .replace(" n't", "n't")
+        .replace(" 'm", "'m")
+        .replace(" do not", " don't")
+        .replace(" 's", "'s")
+        .replace(" 've", "'ve")
+        .replace(" 're", "'re")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(values: &[&str]) -> Vec<EncodedToken> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| EncodedToken {
+                id: index + 10,
+                value: value.to_string(),
+                offsets: (index, index + 1),
+                special: false,
+            })
+            .collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn template_should_add_special_tokens_with_type_ids() {
+        let processor: PostProcessor = serde_json::from_str(
+            r#"{
+                "type": "TemplateProcessing",
+                "single": [
+                    {"SpecialToken": {"id": "[CLS]", "type_id": 0}},
+                    {"Sequence": {"id": "A", "type_id": 0}},
+                    {"SpecialToken": {"id": "[SEP]", "type_id": 0}}
+                ],
+                "pair": [
+                    {"SpecialToken": {"id": "[CLS]", "type_id": 0}},
+                    {"Sequence": {"id": "A", "type_id": 0}},
+                    {"SpecialToken": {"id": "[SEP]", "type_id": 0}},
+                    {"Sequence": {"id": "B", "type_id": 1}},
+                    {"SpecialToken": {"id": "[SEP]", "type_id": 1}}
+                ],
+                "special_tokens": {
+                    "[CLS]": {"id": "[CLS]", "ids": [1], "tokens": ["[CLS]"]},
+                    "[SEP]": {"id": "[SEP]", "ids": [2], "tokens": ["[SEP]"]}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let output = processor.process(tokens(&["a"]), Some(tokens(&["b", "c"])), true);
+        let output = output
+            .iter()
+            .map(|(token, type_id)| (token.id, token.special, *type_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            output,
+            vec![
+                (1, true, 0),
+                (10, false, 0),
+                (2, true, 0),
+                (10, false, 1),
+                (11, false, 1),
+                (2, true, 1)
+            ]
+        );
+
+        let output = processor.process(tokens(&["a"]), None, false);
+        assert_eq!(output.len(), 1);
+    }
+
+    #[test]
+    fn byte_level_should_trim_offsets() {
+        let processor = PostProcessor::ByteLevel { trim_offsets: true };
+        let token = EncodedToken {
+            id: 0,
+            value: "Ġhello".to_string(),
+            offsets: (5, 11),
+            special: false,
+        };
+
+        let output = processor.process(vec![token], None, true);
+
+        assert_eq!(output[0].0.offsets, (6, 11));
+    }
+
+    #[test]
+    fn decoders_should_join_tokens() {
+        let wordpiece = Decoder::WordPiece {
+            prefix: default_prefix(),
+            cleanup: true,
+        };
+        let output = wordpiece.decode(strings(&["hello", "##world", "!", "it", "'s"]));
+        assert_eq!(output.concat(), "helloworld! it's");
+
+        let byte_level = Decoder::ByteLevel;
+        let output = byte_level.decode(strings(&["Hello", "ĠÃ©"]));
+        assert_eq!(output.concat(), "Hello é");
+
+        let sentencepiece: Decoder = serde_json::from_str(
+            r#"{
+                "type": "Sequence",
+                "decoders": [
+                    {"type": "Replace", "pattern": {"String": "▁"}, "content": " "},
+                    {"type": "ByteFallback"},
+                    {"type": "Fuse"},
+                    {"type": "Strip", "content": " ", "start": 1, "stop": 0}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let output = sentencepiece.decode(strings(&["▁caf", "<0xC3>", "<0xA9>", "▁ok", "<0xFF>"]));
+        assert_eq!(output.concat(), "café ok\u{FFFD}");
+    }
+}
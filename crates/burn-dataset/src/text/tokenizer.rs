@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::ops::Range;
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::model::Model;
+use super::normalizer::{NormalizedString, Normalizer};
+use super::pre_tokenizer::PreTokenizer;
+use super::processor::{Decoder, EncodedToken, PostProcessor};
+
+/// Error type for [Tokenizer](Tokenizer).
+#[derive(Error, Debug)]
+pub enum TokenizerError {
+    /// I/O operation error.
+    #[error("I/O error: `{0}`")]
+    Io(#[from] std::io::Error),
+
+    /// Invalid or unsupported tokenizer file error.
+    #[error("JSON error: `{0}`")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Text encoded by a [tokenizer](Tokenizer).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Encoding {
+    /// Token ids.
+    pub ids: Vec<usize>,
+
+    /// Tokens of the vocabulary.
+    pub tokens: Vec<String>,
+
+    /// Byte range of the input text of each token, `(0, 0)` for the added special tokens.
+    ///
+    /// The offsets of the tokens of the second sequence of a pair refer to the second text.
+    pub offsets: Vec<(usize, usize)>,
+
+    /// Type id of each token, 0 for the first sequence and 1 for the second sequence of a pair
+    /// with most tokenizers.
+    pub type_ids: Vec<usize>,
+
+    /// Whether each token is a special token.
+    pub special_tokens_mask: Vec<bool>,
+}
+
+impl Encoding {
+    /// Returns the number of tokens.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks if the encoding has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Tokenizer loaded from a HuggingFace `tokenizer.json` file.
+///
+/// The text is normalized and split into words by the pre-tokenizer, then each word is split into
+/// tokens by the model (BPE, WordPiece, Unigram or WordLevel). The post-processor adds the special
+/// tokens around the encoded sequences, and the decoder converts the tokens back into text.
+///
+/// # Example
+///
+/// ```no_run
+/// use burn_dataset::text::Tokenizer;
+///
+/// let tokenizer = Tokenizer::from_file("tokenizer.json").unwrap();
+/// let encoding = tokenizer.encode("Hello world!", true);
+/// let text = tokenizer.decode(&encoding.ids, true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    added_tokens: Vec<AddedToken>,
+    added_ids: HashMap<String, usize>,
+    normalizer: Option<Normalizer>,
+    pre_tokenizer: Option<PreTokenizer>,
+    model: Model,
+    post_processor: Option<PostProcessor>,
+    decoder: Option<Decoder>,
+    pad_id: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TokenizerConfig {
+    #[serde(default)]
+    added_tokens: Vec<AddedToken>,
+    normalizer: Option<Normalizer>,
+    pre_tokenizer: Option<PreTokenizer>,
+    model: Model,
+    post_processor: Option<PostProcessor>,
+    decoder: Option<Decoder>,
+    padding: Option<PaddingConfig>,
+}
+
+#[derive(Deserialize)]
+struct PaddingConfig {
+    pad_id: usize,
+}
+
+/// Token added to the vocabulary, matched in the input text before the normalization.
+#[derive(Deserialize, Debug, Clone)]
+struct AddedToken {
+    id: usize,
+    content: String,
+    #[serde(default)]
+    single_word: bool,
+    #[serde(default)]
+    lstrip: bool,
+    #[serde(default)]
+    rstrip: bool,
+    #[serde(default)]
+    special: bool,
+}
+
+impl Tokenizer {
+    /// Loads a tokenizer from a `tokenizer.json` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the tokenizer file.
+    ///
+    /// # Returns
+    ///
+    /// The tokenizer, or an error if the file can't be read or uses unsupported components.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, TokenizerError> {
+        let file = File::open(path)?;
+        let config = serde_json::from_reader(BufReader::new(file))?;
+
+        Ok(Self::from_config(config))
+    }
+
+    /// Loads a tokenizer from the content of a `tokenizer.json` file.
+    pub fn from_json(json: &str) -> Result<Self, TokenizerError> {
+        let config = serde_json::from_str(json)?;
+
+        Ok(Self::from_config(config))
+    }
+
+    fn from_config(config: TokenizerConfig) -> Self {
+        let added_ids = config
+            .added_tokens
+            .iter()
+            .map(|token| (token.content.clone(), token.id))
+            .collect();
+
+        Self {
+            added_tokens: config.added_tokens,
+            added_ids,
+            normalizer: config.normalizer,
+            pre_tokenizer: config.pre_tokenizer,
+            model: config.model,
+            post_processor: config.post_processor,
+            decoder: config.decoder,
+            pad_id: config.padding.map(|padding| padding.pad_id),
+        }
+    }
+
+    /// Encodes a text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to encode.
+    /// * `add_special_tokens` - Whether to add the special tokens of the post-processor, like
+    ///   `[CLS]` and `[SEP]` for BERT.
+    pub fn encode(&self, text: &str, add_special_tokens: bool) -> Encoding {
+        self.post_process(self.encode_sequence(text), None, add_special_tokens)
+    }
+
+    /// Encodes a pair of texts, like a question and its context.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first text of the pair.
+    /// * `second` - The second text of the pair.
+    /// * `add_special_tokens` - Whether to add the special tokens of the post-processor.
+    pub fn encode_pair(&self, first: &str, second: &str, add_special_tokens: bool) -> Encoding {
+        self.post_process(
+            self.encode_sequence(first),
+            Some(self.encode_sequence(second)),
+            add_special_tokens,
+        )
+    }
+
+    /// Decodes token ids into text.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The token ids, the ids missing from the vocabulary being ignored.
+    /// * `skip_special_tokens` - Whether to remove the special tokens from the text.
+    pub fn decode(&self, ids: &[usize], skip_special_tokens: bool) -> String {
+        let tokens = ids
+            .iter()
+            .filter(|id| {
+                !skip_special_tokens
+                    || !self
+                        .added_tokens
+                        .iter()
+                        .any(|token| token.special && token.id == **id)
+            })
+            .filter_map(|id| self.id_to_token(*id))
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        match &self.decoder {
+            Some(decoder) => decoder.decode(tokens).concat(),
+            None => tokens.join(" "),
+        }
+    }
+
+    /// Returns the id of a token, including the added tokens.
+    pub fn token_to_id(&self, token: &str) -> Option<usize> {
+        self.added_ids
+            .get(token)
+            .copied()
+            .or_else(|| self.model.token_to_id(token))
+    }
+
+    /// Returns the token of an id, including the added tokens.
+    pub fn id_to_token(&self, id: usize) -> Option<&str> {
+        self.model.id_to_token(id).or_else(|| {
+            self.added_tokens
+                .iter()
+                .find(|token| token.id == id)
+                .map(|token| token.content.as_str())
+        })
+    }
+
+    /// Returns the size of the vocabulary, including the added tokens.
+    pub fn vocab_size(&self) -> usize {
+        let added = self
+            .added_tokens
+            .iter()
+            .filter(|token| self.model.token_to_id(&token.content).is_none())
+            .count();
+
+        self.model.vocab_size() + added
+    }
+
+    /// Returns the id of the padding token, when padding is configured in the tokenizer file.
+    pub fn pad_id(&self) -> Option<usize> {
+        self.pad_id
+    }
+
+    fn post_process(
+        &self,
+        first: Vec<EncodedToken>,
+        second: Option<Vec<EncodedToken>>,
+        add_special_tokens: bool,
+    ) -> Encoding {
+        let tokens = match &self.post_processor {
+            Some(processor) => processor.process(first, second, add_special_tokens),
+            None => first
+                .into_iter()
+                .map(|token| (token, 0))
+                .chain(second.into_iter().flatten().map(|token| (token, 1)))
+                .collect(),
+        };
+
+        let mut encoding = Encoding::default();
+        for (token, type_id) in tokens {
+            encoding.ids.push(token.id);
+            encoding.tokens.push(token.value);
+            encoding.offsets.push(token.offsets);
+            encoding.type_ids.push(type_id);
+            encoding.special_tokens_mask.push(token.special);
+        }
+
+        encoding
+    }
+
+    /// Encodes a text without the special tokens of the post-processor.
+    fn encode_sequence(&self, text: &str) -> Vec<EncodedToken> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        for (range, token) in self.find_added_tokens(text) {
+            self.encode_segment(text, start..range.start, &mut tokens);
+            tokens.push(EncodedToken {
+                id: token.id,
+                value: token.content.clone(),
+                offsets: (range.start, range.end),
+                special: token.special,
+            });
+            start = range.end;
+        }
+        self.encode_segment(text, start..text.len(), &mut tokens);
+
+        tokens
+    }
+
+    /// Encodes a segment of the text without added tokens.
+    fn encode_segment(&self, text: &str, range: Range<usize>, tokens: &mut Vec<EncodedToken>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let mut normalized = NormalizedString::new(&text[range.clone()], range.start);
+        if let Some(normalizer) = &self.normalizer {
+            normalizer.normalize(&mut normalized);
+        }
+
+        let words = match &self.pre_tokenizer {
+            Some(pre_tokenizer) => pre_tokenizer.pre_tokenize(vec![normalized], range.start == 0),
+            None => vec![normalized],
+        };
+
+        for word in words.iter().filter(|word| !word.is_empty()) {
+            for token in self.model.tokenize(word.as_str()) {
+                tokens.push(EncodedToken {
+                    id: token.id,
+                    value: token.value,
+                    offsets: word.offsets(token.offsets.0..token.offsets.1),
+                    special: false,
+                });
+            }
+        }
+    }
+
+    /// Finds the added tokens in the text, preferring the longest of the tokens starting at the
+    /// same position.
+    fn find_added_tokens(&self, text: &str) -> Vec<(Range<usize>, &AddedToken)> {
+        let mut matches = Vec::new();
+        let mut position = 0;
+
+        while position < text.len() {
+            let found = text[position..]
+                .char_indices()
+                .map(|(index, _)| position + index)
+                .find_map(|start| {
+                    self.added_tokens
+                        .iter()
+                        .filter(|token| !token.content.is_empty())
+                        .filter(|token| text[start..].starts_with(token.content.as_str()))
+                        .filter(|token| {
+                            !token.single_word
+                                || is_word_boundary(text, start, start + token.content.len())
+                        })
+                        .max_by_key(|token| token.content.len())
+                        .map(|token| (start, token))
+                });
+
+            let Some((start, token)) = found else {
+                break;
+            };
+
+            // The surrounding whitespaces are part of the added tokens stripping them.
+            let mut end = start + token.content.len();
+            let mut start = start;
+            if token.lstrip {
+                let stripped = text[position..start].trim_end();
+                start = position + stripped.len();
+            }
+            if token.rstrip {
+                end = text.len() - text[end..].trim_start().len();
+            }
+
+            matches.push((start..end, token));
+            position = end;
+        }
+
+        matches
+    }
+}
+
+/// Checks if a range of the text isn't preceded or followed by alphanumeric characters.
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENIZERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/tokenizers");
+
+    fn tokenizer(name: &str) -> Tokenizer {
+        Tokenizer::from_file(format!("{TOKENIZERS}/{name}.json")).unwrap()
+    }
+
+    fn tokens_of(encoding: &Encoding, text: &str) -> Vec<String> {
+        encoding
+            .offsets
+            .iter()
+            .map(|(start, end)| text[*start..*end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn wordpiece_tokenizer_should_encode_with_special_tokens() {
+        let tokenizer = tokenizer("wordpiece");
+        let text = "Héllo, unaffable world!";
+
+        let encoding = tokenizer.encode(text, true);
+
+        assert_eq!(
+            encoding.tokens,
+            vec!["[CLS]", "hello", ",", "un", "##aff", "##able", "world", "!", "[SEP]"]
+        );
+        assert_eq!(encoding.ids, vec![2, 5, 6, 7, 8, 9, 10, 11, 3]);
+        assert_eq!(
+            encoding.special_tokens_mask,
+            vec![true, false, false, false, false, false, false, false, true]
+        );
+        assert_eq!(
+            tokens_of(&encoding, text)[1..8],
+            ["Héllo", ",", "un", "aff", "able", "world", "!"]
+        );
+        assert_eq!(
+            tokenizer.decode(&encoding.ids, true),
+            "hello, unaffable world!"
+        );
+        assert_eq!(tokenizer.pad_id(), Some(0));
+    }
+
+    #[test]
+    fn wordpiece_tokenizer_should_encode_pairs() {
+        let tokenizer = tokenizer("wordpiece");
+
+        let encoding = tokenizer.encode_pair("hello", "world [MASK]", true);
+
+        assert_eq!(
+            encoding.tokens,
+            vec!["[CLS]", "hello", "[SEP]", "world", "[MASK]", "[SEP]"]
+        );
+        assert_eq!(encoding.type_ids, vec![0, 0, 0, 1, 1, 1]);
+        assert_eq!(encoding.offsets[4], (6, 12));
+    }
+
+    #[test]
+    fn byte_level_bpe_tokenizer_should_encode_and_decode() {
+        let tokenizer = tokenizer("bpe");
+        let text = "low lower<|endoftext|>";
+
+        let encoding = tokenizer.encode(text, true);
+
+        assert_eq!(encoding.tokens, vec!["low", "Ġlow", "er", "<|endoftext|>"]);
+        assert_eq!(
+            tokens_of(&encoding, text),
+            ["low", "low", "er", "<|endoftext|>"]
+        );
+        assert_eq!(tokenizer.decode(&encoding.ids, false), text);
+        assert_eq!(tokenizer.decode(&encoding.ids, true), "low lower");
+    }
+
+    #[test]
+    fn unigram_tokenizer_should_encode_and_decode() {
+        let tokenizer = tokenizer("unigram");
+        let text = "hello world";
+
+        let encoding = tokenizer.encode(text, true);
+
+        assert_eq!(encoding.tokens, vec!["▁hell", "o", "▁world", "</s>"]);
+        assert_eq!(tokens_of(&encoding, text)[..3], ["hell", "o", " world"]);
+        assert_eq!(tokenizer.decode(&encoding.ids, true), text);
+        assert_eq!(tokenizer.vocab_size(), 8);
+    }
+}
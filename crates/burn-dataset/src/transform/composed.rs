@@ -21,6 +21,17 @@ where
         }
         None
     }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        let mut current_index = 0;
+        for dataset in self.datasets.iter() {
+            if index < dataset.len() + current_index {
+                return dataset.get_for_epoch(index - current_index, epoch);
+            }
+            current_index += dataset.len();
+        }
+        None
+    }
     fn len(&self) -> usize {
         let mut total = 0;
         for dataset in self.datasets.iter() {
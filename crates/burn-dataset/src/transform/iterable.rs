@@ -0,0 +1,370 @@
+use crate::transform::Mapper;
+use crate::{IterableDataset, IterableDatasetIterator};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Iterable dataset mapping each item of an inner iterable dataset to another item type lazily.
+#[derive(new)]
+pub struct MapperIterableDataset<D, M, I> {
+    dataset: D,
+    mapper: M,
+    input: PhantomData<I>,
+}
+
+impl<D, M, I, O> IterableDataset<O> for MapperIterableDataset<D, M, I>
+where
+    D: IterableDataset<I>,
+    M: Mapper<I, O>,
+    I: Send + Sync,
+    O: Send + Sync,
+{
+    fn num_shards(&self) -> usize {
+        self.dataset.num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, O> {
+        Box::new(
+            self.dataset
+                .iter_shard(shard)
+                .map(|item| self.mapper.map(&item)),
+        )
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.dataset.len_hint()
+    }
+}
+
+/// Iterable dataset only keeping the items of an inner iterable dataset that satisfy a predicate.
+#[derive(new)]
+pub struct FilterIterableDataset<D, F, I> {
+    dataset: D,
+    predicate: F,
+    input: PhantomData<I>,
+}
+
+impl<D, F, I> IterableDataset<I> for FilterIterableDataset<D, F, I>
+where
+    D: IterableDataset<I>,
+    F: Fn(&I) -> bool + Send + Sync,
+    I: Send + Sync,
+{
+    fn num_shards(&self) -> usize {
+        self.dataset.num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        Box::new(
+            self.dataset
+                .iter_shard(shard)
+                .filter(|item| (self.predicate)(item)),
+        )
+    }
+}
+
+/// Iterable dataset shuffling the items of an inner iterable dataset approximately, by sampling
+/// each item randomly from a buffer of the upcoming items.
+///
+/// Each shard is shuffled independently, with a different order each time it is iterated over.
+/// Larger buffers give a better shuffling at the cost of more memory.
+pub struct ShuffleBufferDataset<D, I> {
+    dataset: D,
+    buffer_size: usize,
+    seed: u64,
+    iterations: Vec<AtomicUsize>,
+    input: PhantomData<I>,
+}
+
+impl<D, I> ShuffleBufferDataset<D, I>
+where
+    D: IterableDataset<I>,
+{
+    /// Creates a new shuffle buffer dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The inner dataset.
+    /// * `buffer_size` - The number of items kept in the buffer.
+    /// * `seed` - The seed used to sample the items.
+    ///
+    /// # Returns
+    ///
+    /// The shuffle buffer dataset.
+    pub fn new(dataset: D, buffer_size: usize, seed: u64) -> Self {
+        let iterations = (0..dataset.num_shards())
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+
+        Self {
+            dataset,
+            buffer_size: buffer_size.max(1),
+            seed,
+            iterations,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<D, I> IterableDataset<I> for ShuffleBufferDataset<D, I>
+where
+    D: IterableDataset<I>,
+    I: Send + Sync,
+{
+    fn num_shards(&self) -> usize {
+        self.dataset.num_shards()
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        // The rng depends on the shard and on the number of times it was iterated over, so that
+        // the order is reproducible regardless of which worker iterates over the shard.
+        let iteration = self
+            .iterations
+            .get(shard)
+            .map(|iteration| iteration.fetch_add(1, Ordering::Relaxed))
+            .unwrap_or(0);
+        let seed = self
+            .seed
+            .wrapping_add((shard as u64) << 32)
+            .wrapping_add(iteration as u64);
+
+        Box::new(ShuffleBufferIterator {
+            iterator: self.dataset.iter_shard(shard),
+            buffer: Vec::with_capacity(self.buffer_size),
+            buffer_size: self.buffer_size,
+            rng: StdRng::seed_from_u64(seed),
+        })
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.dataset.len_hint()
+    }
+}
+
+struct ShuffleBufferIterator<'a, I> {
+    iterator: IterableDatasetIterator<'a, I>,
+    buffer: Vec<I>,
+    buffer_size: usize,
+    rng: StdRng,
+}
+
+impl<I> Iterator for ShuffleBufferIterator<'_, I> {
+    type Item = I;
+
+    fn next(&mut self) -> Option<I> {
+        while self.buffer.len() < self.buffer_size {
+            match self.iterator.next() {
+                Some(item) => self.buffer.push(item),
+                None => break,
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let index = self.rng.gen_range(0..self.buffer.len());
+        Some(self.buffer.swap_remove(index))
+    }
+}
+
+/// Iterable dataset only keeping the first items of an inner iterable dataset.
+///
+/// Since the number of items of each shard is unknown, the shards are iterated over one after
+/// the other as a single shard.
+#[derive(new)]
+pub struct TakeIterableDataset<D, I> {
+    dataset: D,
+    count: usize,
+    input: PhantomData<I>,
+}
+
+impl<D, I> IterableDataset<I> for TakeIterableDataset<D, I>
+where
+    D: IterableDataset<I>,
+    I: Send + Sync + 'static,
+{
+    fn iter_shard(&self, _shard: usize) -> IterableDatasetIterator<'_, I> {
+        Box::new(self.dataset.iter().take(self.count))
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.dataset.len_hint().map(|len| len.min(self.count))
+    }
+}
+
+/// Iterable dataset skipping the first items of an inner iterable dataset.
+///
+/// Since the number of items of each shard is unknown, the shards are iterated over one after
+/// the other as a single shard.
+#[derive(new)]
+pub struct SkipIterableDataset<D, I> {
+    dataset: D,
+    count: usize,
+    input: PhantomData<I>,
+}
+
+impl<D, I> IterableDataset<I> for SkipIterableDataset<D, I>
+where
+    D: IterableDataset<I>,
+    I: Send + Sync + 'static,
+{
+    fn iter_shard(&self, _shard: usize) -> IterableDatasetIterator<'_, I> {
+        Box::new(self.dataset.iter().skip(self.count))
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.dataset
+            .len_hint()
+            .map(|len| len.saturating_sub(self.count))
+    }
+}
+
+/// Iterable dataset alternating between the items of multiple iterable datasets, one item of
+/// each dataset at a time, until all of them are exhausted.
+///
+/// The shard `i` interleaves the shard `i` of each dataset, so the number of shards is the
+/// largest number of shards of the datasets.
+#[derive(new)]
+pub struct InterleaveDataset<D, I> {
+    datasets: Vec<D>,
+    input: PhantomData<I>,
+}
+
+impl<D, I> IterableDataset<I> for InterleaveDataset<D, I>
+where
+    D: IterableDataset<I>,
+    I: Send + Sync,
+{
+    fn num_shards(&self) -> usize {
+        self.datasets
+            .iter()
+            .map(|dataset| dataset.num_shards())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn iter_shard(&self, shard: usize) -> IterableDatasetIterator<'_, I> {
+        let iterators = self
+            .datasets
+            .iter()
+            .filter(|dataset| shard < dataset.num_shards())
+            .map(|dataset| dataset.iter_shard(shard))
+            .collect();
+
+        Box::new(InterleaveIterator {
+            iterators,
+            current: 0,
+        })
+    }
+
+    fn len_hint(&self) -> Option<usize> {
+        self.datasets.iter().map(|dataset| dataset.len_hint()).sum()
+    }
+}
+
+struct InterleaveIterator<'a, I> {
+    iterators: Vec<IterableDatasetIterator<'a, I>>,
+    current: usize,
+}
+
+impl<I> Iterator for InterleaveIterator<'_, I> {
+    type Item = I;
+
+    fn next(&mut self) -> Option<I> {
+        while !self.iterators.is_empty() {
+            let index = self.current % self.iterators.len();
+
+            match self.iterators[index].next() {
+                Some(item) => {
+                    self.current = index + 1;
+                    return Some(item);
+                }
+                // Exhausted iterators are removed, the next one takes their index.
+                None => {
+                    let _exhausted = self.iterators.remove(index);
+                    self.current = index;
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GeneratorDataset;
+    use std::collections::HashSet;
+
+    fn dataset(num_shards: usize) -> impl IterableDataset<usize> {
+        GeneratorDataset::new(num_shards, |shard| (0..10).map(move |i| shard * 10 + i))
+    }
+
+    #[test]
+    fn given_mapper_and_filter_when_iterate_should_transform_items() {
+        struct Double;
+
+        impl Mapper<usize, usize> for Double {
+            fn map(&self, item: &usize) -> usize {
+                item * 2
+            }
+        }
+
+        let dataset = MapperIterableDataset::new(dataset(2), Double);
+        let dataset = FilterIterableDataset::new(dataset, |item: &usize| item % 4 == 0);
+
+        let items: Vec<usize> = dataset.iter().collect();
+
+        assert_eq!(dataset.num_shards(), 2);
+        assert_eq!(items, vec![0, 4, 8, 12, 16, 20, 24, 28, 32, 36]);
+    }
+
+    #[test]
+    fn given_shuffle_buffer_when_iterate_should_shuffle_all_items() {
+        let dataset = ShuffleBufferDataset::new(dataset(2), 5, 42);
+
+        let first: Vec<usize> = dataset.iter().collect();
+        let second: Vec<usize> = dataset.iter().collect();
+
+        assert_ne!(first, (0..20).collect::<Vec<_>>());
+        assert_ne!(first, second, "Each iteration should shuffle differently.");
+        assert_eq!(
+            first.iter().copied().collect::<HashSet<_>>(),
+            (0..20).collect::<HashSet<_>>()
+        );
+        // Items can't be moved before the buffer is filled with them.
+        assert!(first[0] < 5);
+    }
+
+    #[test]
+    fn given_take_and_skip_when_iterate_should_limit_items() {
+        let take = TakeIterableDataset::new(dataset(2), 12);
+        let skip = SkipIterableDataset::new(dataset(2), 12);
+
+        assert_eq!(take.iter().collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+        assert_eq!(take.len_hint(), None);
+        let sized = |len| GeneratorDataset::new(1, move |_| 0..len).with_len(len);
+        assert_eq!(TakeIterableDataset::new(sized(20), 12).len_hint(), Some(12));
+        assert_eq!(TakeIterableDataset::new(sized(10), 12).len_hint(), Some(10));
+        assert_eq!(
+            skip.iter().collect::<Vec<_>>(),
+            (12..20).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn given_interleave_when_iterate_should_alternate_items() {
+        let short = GeneratorDataset::new(1, |_| 0usize..2);
+        let long = GeneratorDataset::new(1, |_| 10usize..14);
+        let dataset = InterleaveDataset::new(vec![
+            Box::new(short) as Box<dyn IterableDataset<usize>>,
+            Box::new(long),
+        ]);
+
+        let items: Vec<usize> = dataset.iter().collect();
+
+        assert_eq!(items, vec![0, 10, 1, 11, 12, 13]);
+    }
+}
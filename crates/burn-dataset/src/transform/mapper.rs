@@ -27,6 +27,11 @@ where
         item.map(|item| self.mapper.map(&item))
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<O> {
+        let item = self.dataset.get_for_epoch(index, epoch);
+        item.map(|item| self.mapper.map(&item))
+    }
+
     fn len(&self) -> usize {
         self.dataset.len()
     }
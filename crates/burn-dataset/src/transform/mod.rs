@@ -1,13 +1,21 @@
 mod composed;
+mod iterable;
 mod mapper;
 mod partial;
 mod random;
 mod sampler;
+mod sharded;
+mod stratified;
+mod weighted;
 mod window;
 
 pub use composed::*;
+pub use iterable::*;
 pub use mapper::*;
 pub use partial::*;
 pub use random::*;
 pub use sampler::*;
+pub use sharded::*;
+pub use stratified::*;
+pub use weighted::*;
 pub use window::*;
@@ -39,6 +39,15 @@ where
 
         datasets
     }
+
+    /// The index of the item in the original dataset.
+    fn index(&self, index: usize) -> Option<usize> {
+        let index = index + self.start_index;
+        if index < self.start_index || index >= self.end_index {
+            return None;
+        }
+        Some(index)
+    }
 }
 
 impl<D, I> Dataset<I> for PartialDataset<D, I>
@@ -47,11 +56,11 @@ where
     I: Clone + Send + Sync,
 {
     fn get(&self, index: usize) -> Option<I> {
-        let index = index + self.start_index;
-        if index < self.start_index || index >= self.end_index {
-            return None;
-        }
-        self.dataset.get(index)
+        self.dataset.get(self.index(index)?)
+    }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.dataset.get_for_epoch(self.index(index)?, epoch)
     }
 
     fn len(&self) -> usize {
@@ -46,6 +46,11 @@ where
         self.dataset.get(*index)
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        let index = self.indices.get(index)?;
+        self.dataset.get_for_epoch(*index, epoch)
+    }
+
     fn len(&self) -> usize {
         self.dataset.len()
     }
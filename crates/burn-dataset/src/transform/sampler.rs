@@ -89,6 +89,14 @@ where
         self.dataset.get(self.index())
     }
 
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        if index >= self.size {
+            return None;
+        }
+
+        self.dataset.get_for_epoch(self.index(), epoch)
+    }
+
     fn len(&self) -> usize {
         self.size
     }
@@ -125,7 +133,7 @@ mod tests {
             FakeDataset::<String>::new(len_original),
             len_original * factor,
         );
-        let mut buckets = HashMap::new();
+        let mut buckets = HashMap::<String, usize>::new();
 
         for item in dataset_sampler.iter() {
             let count = match buckets.get(&item) {
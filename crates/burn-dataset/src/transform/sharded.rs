@@ -0,0 +1,138 @@
+use crate::Dataset;
+use std::marker::PhantomData;
+
+/// How the items left over when a dataset can't be evenly divided between the shards are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShardRemainder {
+    /// The shards are padded with items from the start of the dataset, so that every shard has
+    /// the same number of items and all the items are used.
+    #[default]
+    Pad,
+    /// The last items are dropped, so that every shard has the same number of items.
+    Drop,
+}
+
+/// Only use the items of one shard of an existing dataset, e.g. the items of one process when the
+/// training is distributed across multiple processes.
+///
+/// The items are assigned to the shards in a round-robin fashion, so the shards of the same
+/// dataset never overlap, except for the padding items.
+pub struct ShardedDataset<D, I> {
+    dataset: D,
+    rank: usize,
+    world_size: usize,
+    remainder: ShardRemainder,
+    input: PhantomData<I>,
+}
+
+impl<D, I> ShardedDataset<D, I>
+where
+    D: Dataset<I>,
+{
+    /// Creates a new sharded dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to shard.
+    /// * `rank` - The index of the shard.
+    /// * `world_size` - The number of shards.
+    /// * `remainder` - How the items that can't be evenly divided between the shards are handled.
+    ///
+    /// # Returns
+    ///
+    /// The sharded dataset.
+    pub fn new(dataset: D, rank: usize, world_size: usize, remainder: ShardRemainder) -> Self {
+        assert!(world_size > 0, "The world size should be at least 1");
+        assert!(
+            rank < world_size,
+            "The rank {rank} should be smaller than the world size {world_size}"
+        );
+
+        Self {
+            dataset,
+            rank,
+            world_size,
+            remainder,
+            input: PhantomData,
+        }
+    }
+
+    /// The index of the item in the original dataset.
+    fn index(&self, index: usize) -> Option<usize>
+    where
+        I: Send + Sync,
+    {
+        if index >= self.len() {
+            return None;
+        }
+
+        // Only the padding items wrap around to the start of the dataset.
+        Some((index * self.world_size + self.rank) % self.dataset.len())
+    }
+}
+
+impl<D, I> Dataset<I> for ShardedDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        self.dataset.get(self.index(index)?)
+    }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        self.dataset.get_for_epoch(self.index(index)?, epoch)
+    }
+
+    fn len(&self) -> usize {
+        let len = self.dataset.len();
+
+        match self.remainder {
+            ShardRemainder::Pad => len.div_ceil(self.world_size),
+            ShardRemainder::Drop => len / self.world_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+    use std::sync::Arc;
+
+    fn shards(len: usize, remainder: ShardRemainder) -> Vec<Vec<usize>> {
+        let dataset = Arc::new(InMemDataset::new((0..len).collect()));
+
+        (0..3)
+            .map(|rank| {
+                ShardedDataset::new(dataset.clone(), rank, 3, remainder)
+                    .iter()
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shards_should_not_overlap() {
+        let shards = shards(9, ShardRemainder::Pad);
+
+        assert_eq!(shards, vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]]);
+    }
+
+    #[test]
+    fn shards_should_be_padded_with_first_items() {
+        let shards = shards(10, ShardRemainder::Pad);
+
+        assert_eq!(
+            shards,
+            vec![vec![0, 3, 6, 9], vec![1, 4, 7, 0], vec![2, 5, 8, 1]]
+        );
+    }
+
+    #[test]
+    fn shards_should_drop_last_items() {
+        let shards = shards(11, ShardRemainder::Drop);
+
+        assert_eq!(shards, vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]]);
+    }
+}
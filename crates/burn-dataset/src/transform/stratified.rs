@@ -0,0 +1,146 @@
+use crate::Dataset;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, sync::Arc};
+
+/// Only use a subset of the items of an existing dataset, selected by their indices.
+#[derive(new)]
+pub struct SubsetDataset<D, I> {
+    dataset: D,
+    indices: Vec<usize>,
+    input: PhantomData<I>,
+}
+
+impl<D, I> SubsetDataset<D, I>
+where
+    D: Dataset<I>,
+{
+    /// Returns the indices of the items of the subset in the original dataset.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Splits a dataset into a train and a validation subsets, preserving the proportion of each
+    /// label in both subsets.
+    ///
+    /// The items of each label are shuffled with the given seed before being split, so the same
+    /// seed always leads to the same split. Both subsets keep the order of the original dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to split.
+    /// * `validation_ratio` - The fraction of the items of each label used for validation.
+    /// * `seed` - The seed used to select the validation items.
+    /// * `label` - The function returning the label of an item.
+    ///
+    /// # Returns
+    ///
+    /// The train and validation subsets.
+    pub fn stratified_split<L, F>(
+        dataset: D,
+        validation_ratio: f64,
+        seed: u64,
+        label: F,
+    ) -> (SubsetDataset<Arc<D>, I>, SubsetDataset<Arc<D>, I>)
+    where
+        L: Eq + Hash,
+        F: Fn(&I) -> L,
+    {
+        assert!(
+            (0.0..=1.0).contains(&validation_ratio),
+            "The validation ratio should be between 0 and 1"
+        );
+
+        // Groups are kept in order of first appearance so the split doesn't depend on hashing.
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_of_label = HashMap::new();
+
+        for index in 0..dataset.len() {
+            let item = dataset.get(index).expect("Item should exist");
+            let group = *group_of_label.entry(label(&item)).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(index);
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut train = Vec::with_capacity(dataset.len());
+        let mut valid = Vec::new();
+
+        for mut indices in groups {
+            indices.shuffle(&mut rng);
+            let num_valid = (indices.len() as f64 * validation_ratio).round() as usize;
+
+            valid.extend_from_slice(&indices[..num_valid]);
+            train.extend_from_slice(&indices[num_valid..]);
+        }
+
+        train.sort_unstable();
+        valid.sort_unstable();
+
+        let dataset = Arc::new(dataset); // cheap cloning.
+
+        (
+            SubsetDataset::new(dataset.clone(), train),
+            SubsetDataset::new(dataset, valid),
+        )
+    }
+}
+
+impl<D, I> Dataset<I> for SubsetDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let index = self.indices.get(index)?;
+        self.dataset.get(*index)
+    }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        let index = self.indices.get(index)?;
+        self.dataset.get_for_epoch(*index, epoch)
+    }
+
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    fn dataset() -> InMemDataset<(usize, usize)> {
+        // 80 items of the label 0 and 20 items of the label 1.
+        InMemDataset::new((0..100).map(|i| (i, i / 80)).collect())
+    }
+
+    #[test]
+    fn stratified_split_should_preserve_label_proportions() {
+        let (train, valid) =
+            SubsetDataset::stratified_split(dataset(), 0.25, 42, |(_, label)| *label);
+
+        assert_eq!(train.len(), 75);
+        assert_eq!(valid.len(), 25);
+        assert_eq!(valid.iter().filter(|(_, label)| *label == 1).count(), 5);
+        assert_eq!(train.iter().filter(|(_, label)| *label == 1).count(), 15);
+
+        let mut indices = [train.indices(), valid.indices()].concat();
+        indices.sort();
+        assert_eq!(indices, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stratified_split_should_be_deterministic_with_seed() {
+        let split = |seed| {
+            let (_, valid) =
+                SubsetDataset::stratified_split(dataset(), 0.2, seed, |(_, label)| *label);
+            valid.indices().to_vec()
+        };
+
+        assert_eq!(split(1), split(1));
+        assert_ne!(split(1), split(2));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::Dataset;
+use rand::{
+    distributions::{Standard, WeightedIndex},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+/// Sample items from a dataset with replacement, each item being drawn with a probability
+/// proportional to its weight.
+///
+/// The sampled indices are drawn again for each epoch the data loader reads the items
+/// [for](Dataset::get_for_epoch), from a generator derived from the seed and the epoch, so that
+/// every item can be sampled during the training while the items of an epoch don't depend on the
+/// order in which they are accessed. With a seed, the same items are always sampled for the same
+/// epoch. Reading the items with [get](Dataset::get) samples them as in the first epoch.
+///
+/// Weights can be computed from the labels of the items with
+/// [class_balanced](WeightedSamplerDataset::class_balanced), so that every class is sampled as often.
+pub struct WeightedSamplerDataset<D, I> {
+    dataset: D,
+    distribution: Option<WeightedIndex<f64>>,
+    size: usize,
+    seed: u64,
+    /// The indices sampled for the latest epoch read.
+    indices: Mutex<Option<(usize, Arc<Vec<usize>>)>>,
+    input: PhantomData<I>,
+}
+
+impl<D, I> WeightedSamplerDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Send + Sync,
+{
+    /// Creates a new weighted sampler dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to sample from.
+    /// * `weights` - The weight of each item of the dataset.
+    /// * `size` - The number of sampled items.
+    ///
+    /// # Panics
+    ///
+    /// If the number of weights differs from the number of items, or if the weights are negative
+    /// or all zeros.
+    pub fn new(dataset: D, weights: Vec<f64>, size: usize) -> Self {
+        Self::with_seed(
+            dataset,
+            weights,
+            size,
+            StdRng::from_entropy().sample(Standard),
+        )
+    }
+
+    /// Creates a new weighted sampler dataset with a fixed seed.
+    pub fn with_seed(dataset: D, weights: Vec<f64>, size: usize, seed: u64) -> Self {
+        assert_eq!(
+            weights.len(),
+            dataset.len(),
+            "There should be one weight per item of the dataset"
+        );
+
+        let distribution = match size {
+            0 => None,
+            _ => Some(
+                WeightedIndex::new(&weights)
+                    .expect("Weights should be non-negative with a positive sum"),
+            ),
+        };
+
+        Self {
+            dataset,
+            distribution,
+            size,
+            seed,
+            indices: Mutex::new(None),
+            input: PhantomData,
+        }
+    }
+
+    /// Creates a new class-balanced sampler dataset, each item being weighted by the inverse of
+    /// the number of items with the same label.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to sample from.
+    /// * `size` - The number of sampled items.
+    /// * `label` - The function returning the label of an item.
+    pub fn class_balanced<L, F>(dataset: D, size: usize, label: F) -> Self
+    where
+        L: Eq + Hash,
+        F: Fn(&I) -> L,
+    {
+        let weights = class_balanced_weights(&dataset, label);
+        Self::new(dataset, weights, size)
+    }
+
+    /// Creates a new class-balanced sampler dataset with a fixed seed.
+    pub fn class_balanced_with_seed<L, F>(dataset: D, size: usize, seed: u64, label: F) -> Self
+    where
+        L: Eq + Hash,
+        F: Fn(&I) -> L,
+    {
+        let weights = class_balanced_weights(&dataset, label);
+        Self::with_seed(dataset, weights, size, seed)
+    }
+
+    /// The indices sampled for the given epoch, which are drawn again when the epoch changes.
+    fn indices(&self, epoch: usize) -> Arc<Vec<usize>> {
+        let mut indices = self.indices.lock().unwrap();
+
+        match indices.as_ref() {
+            Some((sampled, indices)) if *sampled == epoch => indices.clone(),
+            _ => {
+                let sampled = Arc::new(self.sample(epoch));
+                *indices = Some((epoch, sampled.clone()));
+                sampled
+            }
+        }
+    }
+
+    /// Draw the indices of an epoch, the generator being advanced once for each epoch before it
+    /// so that each epoch is sampled differently while being reproducible.
+    fn sample(&self, epoch: usize) -> Vec<usize> {
+        let Some(distribution) = &self.distribution else {
+            return Vec::new();
+        };
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for _ in 0..epoch {
+            let _seed: u64 = rng.sample(Standard);
+        }
+        let rng = StdRng::seed_from_u64(rng.sample(Standard));
+
+        rng.sample_iter(distribution).take(self.size).collect()
+    }
+}
+
+/// Computes the weight of each item of a dataset as the inverse of the number of items with the
+/// same label, so that the total weight of every label is the same.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset.
+/// * `label` - The function returning the label of an item.
+pub fn class_balanced_weights<D, I, L, F>(dataset: &D, label: F) -> Vec<f64>
+where
+    D: Dataset<I>,
+    L: Eq + Hash,
+    F: Fn(&I) -> L,
+{
+    let labels = (0..dataset.len())
+        .map(|index| {
+            let item = dataset.get(index).expect("Item should exist");
+            label(&item)
+        })
+        .collect::<Vec<_>>();
+
+    let mut counts = HashMap::new();
+    for label in labels.iter() {
+        *counts.entry(label).or_insert(0usize) += 1;
+    }
+
+    labels
+        .iter()
+        .map(|label| 1.0 / counts[label] as f64)
+        .collect()
+}
+
+impl<D, I> Dataset<I> for WeightedSamplerDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        self.get_for_epoch(index, 0)
+    }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<I> {
+        let index = *self.indices(epoch).get(index)?;
+        self.dataset.get_for_epoch(index, epoch)
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    #[test]
+    fn weighted_sampler_should_follow_weights() {
+        let dataset = InMemDataset::new(vec![0, 1, 2]);
+
+        let sampler = WeightedSamplerDataset::with_seed(dataset, vec![1.0, 0.0, 3.0], 4000, 42);
+        let items = sampler.iter().collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 4000);
+        assert!(!items.contains(&1));
+        let count = items.iter().filter(|item| **item == 2).count();
+        assert!((2800..3200).contains(&count), "{count}");
+    }
+
+    #[test]
+    fn weighted_sampler_should_be_deterministic_with_seed() {
+        let sample = |seed| {
+            let dataset = InMemDataset::new((0..10).collect::<Vec<_>>());
+            WeightedSamplerDataset::with_seed(dataset, vec![1.0; 10], 20, seed)
+                .iter()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(1), sample(1));
+        assert_ne!(sample(1), sample(2));
+    }
+
+    #[test]
+    fn class_balanced_sampler_should_balance_labels() {
+        // 90 items of the label 0 and 10 items of the label 1.
+        let dataset = InMemDataset::new((0..100).map(|i| (i, i / 90)).collect::<Vec<_>>());
+
+        let sampler = WeightedSamplerDataset::class_balanced_with_seed(
+            dataset,
+            2000,
+            42,
+            |(_, label): &(usize, usize)| *label,
+        );
+
+        let count = sampler.iter().filter(|(_, label)| *label == 1).count();
+        assert!((900..1100).contains(&count), "{count}");
+    }
+
+    #[test]
+    fn weighted_sampler_should_sample_each_epoch_differently() {
+        // 90 items of the label 0 and 10 items of the label 1.
+        let dataset = InMemDataset::new((0..100).map(|i| (i, i / 90)).collect::<Vec<_>>());
+        let sampler = WeightedSamplerDataset::class_balanced_with_seed(
+            dataset,
+            100,
+            42,
+            |(_, label): &(usize, usize)| *label,
+        );
+        let epoch = |epoch| {
+            (0..sampler.len())
+                .map(|i| sampler.get_for_epoch(i, epoch).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let (epoch_0, epoch_1) = (epoch(0), epoch(1));
+
+        assert_ne!(epoch_0, epoch_1);
+        assert_eq!(epoch(0), epoch_0);
+        assert_eq!(sampler.iter().collect::<Vec<_>>(), epoch_0);
+
+        // Over a few epochs, more items of the majority label are seen than in a single one.
+        let seen = |epochs: std::ops::Range<usize>| {
+            epochs
+                .flat_map(epoch)
+                .filter(|(_, label)| *label == 0)
+                .map(|(i, _)| i)
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+        };
+        assert!(seen(0..5) > seen(0..1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn weighted_sampler_should_panic_with_zero_weights() {
+        let dataset = InMemDataset::new(vec![0, 1]);
+
+        WeightedSamplerDataset::with_seed(dataset, vec![0.0, 0.0], 1, 42);
+    }
+}
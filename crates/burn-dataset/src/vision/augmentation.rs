@@ -0,0 +1,1515 @@
+use super::{Annotation, BoundingBox, ImageDatasetItem, PixelDepth, SegmentationMask};
+use crate::transform::Mapper;
+use crate::Dataset;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Beta, Distribution};
+use std::sync::Mutex;
+
+// Used as mappers, random augmentations draw from their own generator, seeded at construction.
+// Mapping the same items in the same order, from a single thread, therefore always produces the
+// same augmented items. The [augmented dataset](AugmentedDataset) instead seeds a generator for
+// each item, which doesn't depend on the order or the thread the items are read from.
+
+/// An image augmentation drawing its randomness from the given generator.
+pub trait Augmentation: Send + Sync {
+    /// Augments an item.
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem;
+}
+
+/// Dataset augmenting the items of an inner dataset lazily, with a generator seeded from the
+/// seed, the index of the item and the epoch.
+///
+/// Data loaders read the items [for their epoch](Dataset::get_for_epoch), so the items are
+/// augmented differently at each epoch while the augmented items don't depend on the order, nor
+/// on the thread, they are read from, nor on the items read before. Resuming a training in the
+/// middle of an epoch therefore augments the remaining items the same way. Reading an item with
+/// [get](Dataset::get) augments it as in the first epoch.
+pub struct AugmentedDataset<D, A> {
+    dataset: D,
+    augmentation: A,
+    seed: u64,
+}
+
+impl<D, A> AugmentedDataset<D, A>
+where
+    D: Dataset<ImageDatasetItem>,
+    A: Augmentation,
+{
+    /// Creates a dataset augmenting the items of the given dataset.
+    pub fn new(dataset: D, augmentation: A, seed: u64) -> Self {
+        Self {
+            dataset,
+            augmentation,
+            seed,
+        }
+    }
+
+    /// The generator of the given epoch of an item.
+    fn rng(&self, index: usize, epoch: usize) -> StdRng {
+        StdRng::seed_from_u64(mix(mix(self.seed ^ mix(index as u64)) ^ epoch as u64))
+    }
+}
+
+impl<D, A> Dataset<ImageDatasetItem> for AugmentedDataset<D, A>
+where
+    D: Dataset<ImageDatasetItem>,
+    A: Augmentation,
+{
+    fn get(&self, index: usize) -> Option<ImageDatasetItem> {
+        self.get_for_epoch(index, 0)
+    }
+
+    fn get_for_epoch(&self, index: usize, epoch: usize) -> Option<ImageDatasetItem> {
+        let item = self.dataset.get_for_epoch(index, epoch)?;
+
+        Some(
+            self.augmentation
+                .augment(&item, &mut self.rng(index, epoch)),
+        )
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+/// Mixes the bits of a value, following the finalizer of SplitMix64.
+fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+/// Resizes images to a fixed size with bilinear interpolation.
+///
+/// Bounding boxes are scaled and segmentation masks are resized with nearest neighbor
+/// interpolation.
+#[derive(new, Debug, Clone, Copy)]
+pub struct Resize {
+    width: usize,
+    height: usize,
+}
+
+impl Augmentation for Resize {
+    fn augment(&self, item: &ImageDatasetItem, _rng: &mut StdRng) -> ImageDatasetItem {
+        self.map(item)
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for Resize {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        let transform = Affine::scale(
+            self.width as f32 / item.width as f32,
+            self.height as f32 / item.height as f32,
+        );
+
+        warp(item, transform, self.width, self.height)
+    }
+}
+
+/// Crops a random area of images and resizes it to a fixed size.
+///
+/// The area of the crop is sampled uniformly in the `scale` range relative to the image area, and
+/// the aspect ratio of the crop is sampled log-uniformly in the `ratio` range.
+#[derive(Debug)]
+pub struct RandomResizedCrop {
+    width: usize,
+    height: usize,
+    scale: (f32, f32),
+    ratio: (f32, f32),
+    rng: Mutex<StdRng>,
+}
+
+impl RandomResizedCrop {
+    /// Creates a random resized crop with the default scale `(0.08, 1.0)` and ratio
+    /// `(3/4, 4/3)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the output images.
+    /// * `height` - The height of the output images.
+    /// * `seed` - The seed used to sample the crops.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        Self {
+            width,
+            height,
+            scale: (0.08, 1.0),
+            ratio: (3.0 / 4.0, 4.0 / 3.0),
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Sets the range of the crop area relative to the image area.
+    pub fn with_scale(mut self, scale: (f32, f32)) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the range of the crop aspect ratio (width over height).
+    pub fn with_ratio(mut self, ratio: (f32, f32)) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Samples the crop as `(x, y, width, height)`.
+    fn crop(&self, rng: &mut StdRng, width: usize, height: usize) -> (usize, usize, usize, usize) {
+        let area = (width * height) as f32;
+        let (log_min, log_max) = (self.ratio.0.ln(), self.ratio.1.ln());
+
+        for _ in 0..10 {
+            let target_area = area * sample(rng, self.scale);
+            let ratio = sample(rng, (log_min, log_max)).exp();
+            let crop_width = (target_area * ratio).sqrt().round() as usize;
+            let crop_height = (target_area / ratio).sqrt().round() as usize;
+
+            if crop_width > 0 && crop_width <= width && crop_height > 0 && crop_height <= height {
+                let x = rng.gen_range(0..=width - crop_width);
+                let y = rng.gen_range(0..=height - crop_height);
+
+                return (x, y, crop_width, crop_height);
+            }
+        }
+
+        // Fallback to a center crop with the closest valid aspect ratio.
+        let ratio = width as f32 / height as f32;
+        let (crop_width, crop_height) = if ratio < self.ratio.0 {
+            (width, (width as f32 / self.ratio.0).round() as usize)
+        } else if ratio > self.ratio.1 {
+            ((height as f32 * self.ratio.1).round() as usize, height)
+        } else {
+            (width, height)
+        };
+
+        (
+            (width - crop_width) / 2,
+            (height - crop_height) / 2,
+            crop_width,
+            crop_height,
+        )
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for RandomResizedCrop {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for RandomResizedCrop {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        let (x, y, width, height) = self.crop(rng, item.width, item.height);
+        let scale_x = self.width as f32 / width as f32;
+        let scale_y = self.height as f32 / height as f32;
+        let transform = Affine([
+            scale_x,
+            0.0,
+            -(x as f32) * scale_x,
+            0.0,
+            scale_y,
+            -(y as f32) * scale_y,
+        ]);
+
+        warp(item, transform, self.width, self.height)
+    }
+}
+
+/// Flips images horizontally with the given probability.
+#[derive(Debug)]
+pub struct RandomHorizontalFlip {
+    probability: f64,
+    rng: Mutex<StdRng>,
+}
+
+impl RandomHorizontalFlip {
+    /// Creates a horizontal flip applied with the given probability.
+    pub fn new(probability: f64, seed: u64) -> Self {
+        Self {
+            probability,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for RandomHorizontalFlip {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for RandomHorizontalFlip {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        if rng.gen_bool(self.probability) {
+            flip(item, true)
+        } else {
+            item.clone()
+        }
+    }
+}
+
+/// Flips images vertically with the given probability.
+#[derive(Debug)]
+pub struct RandomVerticalFlip {
+    probability: f64,
+    rng: Mutex<StdRng>,
+}
+
+impl RandomVerticalFlip {
+    /// Creates a vertical flip applied with the given probability.
+    pub fn new(probability: f64, seed: u64) -> Self {
+        Self {
+            probability,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for RandomVerticalFlip {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for RandomVerticalFlip {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        if rng.gen_bool(self.probability) {
+            flip(item, false)
+        } else {
+            item.clone()
+        }
+    }
+}
+
+/// Rotates images around their center by a random angle, keeping their size.
+///
+/// The angle is sampled uniformly in `[-degrees, degrees]`, positive angles rotating
+/// counter-clockwise. Areas outside of the original image are filled with zeros, and bounding
+/// boxes are replaced by the smallest boxes containing their rotated corners.
+#[derive(Debug)]
+pub struct RandomRotation {
+    degrees: f32,
+    rng: Mutex<StdRng>,
+}
+
+impl RandomRotation {
+    /// Creates a random rotation of at most the given number of degrees.
+    pub fn new(degrees: f32, seed: u64) -> Self {
+        Self {
+            degrees,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for RandomRotation {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for RandomRotation {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        let degrees = sample(rng, (-self.degrees, self.degrees));
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (center_x, center_y) = (item.width as f32 / 2.0, item.height as f32 / 2.0);
+        let transform = Affine([
+            cos,
+            sin,
+            center_x - cos * center_x - sin * center_y,
+            -sin,
+            cos,
+            center_y + sin * center_x - cos * center_y,
+        ]);
+
+        warp(item, transform, item.width, item.height)
+    }
+}
+
+/// Randomly changes the brightness, contrast, saturation and hue of images.
+///
+/// The brightness, contrast and saturation factors are sampled uniformly in
+/// `[max(0, 1 - value), 1 + value]` and the hue shift in `[-hue, hue]`, then applied in this
+/// order. Saturation and hue only affect images with at least three channels, and the alpha
+/// channel is left unchanged. Annotations are left unchanged.
+#[derive(Debug)]
+pub struct ColorJitter {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    hue: f32,
+    rng: Mutex<StdRng>,
+}
+
+impl ColorJitter {
+    /// Creates a color jitter which doesn't change images until a factor is set.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 0.0,
+            saturation: 0.0,
+            hue: 0.0,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Sets how much the brightness can change.
+    pub fn with_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Sets how much the contrast can change.
+    pub fn with_contrast(mut self, contrast: f32) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    /// Sets how much the saturation can change.
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// Sets how much the hue can be shifted, between 0 and 0.5.
+    pub fn with_hue(mut self, hue: f32) -> Self {
+        self.hue = hue;
+        self
+    }
+
+    fn factor(rng: &mut StdRng, value: f32) -> Option<f32> {
+        (value > 0.0).then(|| sample(rng, (f32::max(0.0, 1.0 - value), 1.0 + value)))
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for ColorJitter {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for ColorJitter {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        let brightness = Self::factor(rng, self.brightness);
+        let contrast = Self::factor(rng, self.contrast);
+        let saturation = Self::factor(rng, self.saturation);
+        let hue = (self.hue > 0.0).then(|| sample(rng, (-self.hue, self.hue)));
+
+        let channels = channels(item);
+        let color_channels = if channels >= 3 { 3 } else { 1 };
+        let mut pixels = item
+            .image
+            .chunks(channels)
+            .map(|pixel| {
+                let max = max_value(&pixel[0]);
+                pixel[..color_channels]
+                    .iter()
+                    .map(|value| pixel_value(value) / max)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(factor) = brightness {
+            for pixel in pixels.iter_mut() {
+                pixel.iter_mut().for_each(|value| *value *= factor);
+                clamp(pixel);
+            }
+        }
+
+        if let Some(factor) = contrast {
+            let mean = pixels.iter().map(|pixel| grayscale(pixel)).sum::<f32>()
+                / pixels.len().max(1) as f32;
+
+            for pixel in pixels.iter_mut() {
+                pixel
+                    .iter_mut()
+                    .for_each(|value| *value = factor * *value + (1.0 - factor) * mean);
+                clamp(pixel);
+            }
+        }
+
+        if let (Some(factor), 3) = (saturation, color_channels) {
+            for pixel in pixels.iter_mut() {
+                let gray = grayscale(pixel);
+                pixel
+                    .iter_mut()
+                    .for_each(|value| *value = factor * *value + (1.0 - factor) * gray);
+                clamp(pixel);
+            }
+        }
+
+        if let (Some(shift), 3) = (hue, color_channels) {
+            for pixel in pixels.iter_mut() {
+                let (hue, saturation, value) = rgb_to_hsv(pixel[0], pixel[1], pixel[2]);
+                let (r, g, b) = hsv_to_rgb((hue + shift).rem_euclid(1.0), saturation, value);
+                pixel.copy_from_slice(&[r, g, b]);
+            }
+        }
+
+        let image = item
+            .image
+            .chunks(channels)
+            .zip(pixels)
+            .flat_map(|(pixel, colors)| {
+                let max = max_value(&pixel[0]);
+                let colors = colors
+                    .into_iter()
+                    .zip(pixel)
+                    .map(move |(value, like)| pixel_like(value * max, like));
+
+                colors.chain(pixel[color_channels..].iter().cloned())
+            })
+            .collect();
+
+        ImageDatasetItem {
+            image,
+            ..item.clone()
+        }
+    }
+}
+
+/// Normalizes images with the mean and standard deviation of each channel.
+///
+/// Pixel values are first scaled to `[0, 1]` according to their depth, so the statistics are the
+/// usual ones (e.g. `[0.485, 0.456, 0.406]` and `[0.229, 0.224, 0.225]` for ImageNet). The output
+/// pixels are [32-bit floating points](PixelDepth::F32).
+#[derive(new, Debug, Clone)]
+pub struct Normalize {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl Augmentation for Normalize {
+    fn augment(&self, item: &ImageDatasetItem, _rng: &mut StdRng) -> ImageDatasetItem {
+        self.map(item)
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for Normalize {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        let channels = channels(item);
+        assert!(
+            self.mean.len() == channels && self.std.len() == channels,
+            "Normalize statistics should have one value per channel, got {} and {} for {} channels",
+            self.mean.len(),
+            self.std.len(),
+            channels
+        );
+
+        let image = item
+            .image
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let channel = i % channels;
+                let value = pixel_value(value) / max_value(value);
+
+                PixelDepth::F32((value - self.mean[channel]) / self.std[channel])
+            })
+            .collect();
+
+        ImageDatasetItem {
+            image,
+            ..item.clone()
+        }
+    }
+}
+
+/// Erases a random rectangle of images with the given probability.
+///
+/// The area of the rectangle is sampled uniformly in the `scale` range relative to the image area,
+/// and its aspect ratio log-uniformly in the `ratio` range. Annotations are left unchanged.
+#[derive(Debug)]
+pub struct RandomErasing {
+    probability: f64,
+    scale: (f32, f32),
+    ratio: (f32, f32),
+    value: f32,
+    rng: Mutex<StdRng>,
+}
+
+impl RandomErasing {
+    /// Creates a random erasing with the default scale `(0.02, 0.33)` and ratio `(0.3, 3.3)`,
+    /// filling the rectangle with zeros.
+    pub fn new(probability: f64, seed: u64) -> Self {
+        Self {
+            probability,
+            scale: (0.02, 0.33),
+            ratio: (0.3, 3.3),
+            value: 0.0,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Sets the range of the erased area relative to the image area.
+    pub fn with_scale(mut self, scale: (f32, f32)) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the range of the erased rectangle aspect ratio (width over height).
+    pub fn with_ratio(mut self, ratio: (f32, f32)) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the value of the erased pixels, in the unit of the image pixel depth.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Samples the erased rectangle as `(x, y, width, height)`.
+    fn rectangle(
+        &self,
+        rng: &mut StdRng,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize, usize, usize)> {
+        if !rng.gen_bool(self.probability) {
+            return None;
+        }
+
+        let area = (width * height) as f32;
+        let (log_min, log_max) = (self.ratio.0.ln(), self.ratio.1.ln());
+
+        for _ in 0..10 {
+            let target_area = area * sample(rng, self.scale);
+            let ratio = sample(rng, (log_min, log_max)).exp();
+            let erase_width = (target_area * ratio).sqrt().round() as usize;
+            let erase_height = (target_area / ratio).sqrt().round() as usize;
+
+            if erase_width > 0 && erase_width < width && erase_height > 0 && erase_height < height {
+                let x = rng.gen_range(0..=width - erase_width);
+                let y = rng.gen_range(0..=height - erase_height);
+
+                return Some((x, y, erase_width, erase_height));
+            }
+        }
+
+        None
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for RandomErasing {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        self.augment(item, &mut self.rng.lock().unwrap())
+    }
+}
+
+impl Augmentation for RandomErasing {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        let mut item = item.clone();
+        let channels = channels(&item);
+
+        if let Some((x, y, width, height)) = self.rectangle(rng, item.width, item.height) {
+            for row in y..y + height {
+                let start = (row * item.width + x) * channels;
+                for value in item.image[start..start + width * channels].iter_mut() {
+                    *value = pixel_like(self.value, value);
+                }
+            }
+        }
+
+        item
+    }
+}
+
+/// Applies a sequence of augmentations to images, in order.
+///
+/// As a mapper, each augmentation draws from its own generator, while as an
+/// [augmentation](Augmentation) they all draw from the given generator.
+#[derive(Default)]
+pub struct Compose {
+    augmentations: Vec<Box<dyn ComposedAugmentation>>,
+}
+
+/// An augmentation that can be used both as a mapper and with a given generator.
+trait ComposedAugmentation: Mapper<ImageDatasetItem, ImageDatasetItem> + Augmentation {}
+
+impl<A: Mapper<ImageDatasetItem, ImageDatasetItem> + Augmentation> ComposedAugmentation for A {}
+
+impl Compose {
+    /// Creates an empty sequence of augmentations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an augmentation to the sequence.
+    pub fn with<M>(mut self, augmentation: M) -> Self
+    where
+        M: Mapper<ImageDatasetItem, ImageDatasetItem> + Augmentation + 'static,
+    {
+        self.augmentations.push(Box::new(augmentation));
+        self
+    }
+}
+
+impl Mapper<ImageDatasetItem, ImageDatasetItem> for Compose {
+    fn map(&self, item: &ImageDatasetItem) -> ImageDatasetItem {
+        let mut item = item.clone();
+
+        for augmentation in self.augmentations.iter() {
+            item = augmentation.map(&item);
+        }
+
+        item
+    }
+}
+
+impl Augmentation for Compose {
+    fn augment(&self, item: &ImageDatasetItem, rng: &mut StdRng) -> ImageDatasetItem {
+        let mut item = item.clone();
+
+        for augmentation in self.augmentations.iter() {
+            item = augmentation.augment(&item, rng);
+        }
+
+        item
+    }
+}
+
+/// Image mixed with another one of its batch by [MixUp] or [CutMix].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixedImageItem {
+    /// Mixed image, with the same pixel depth as the original image.
+    pub image: Vec<PixelDepth>,
+
+    /// Image width in pixels.
+    pub width: usize,
+
+    /// Image height in pixels.
+    pub height: usize,
+
+    /// Class probabilities, mixed in the same proportion as the images.
+    pub targets: Vec<f32>,
+}
+
+/// Mixes each image of a batch with the next one (the last with the first), following
+/// [mixup: Beyond Empirical Risk Minimization](https://arxiv.org/abs/1710.09412).
+///
+/// The mixing proportion `lambda` is sampled from `Beta(alpha, alpha)` for each batch, and the
+/// targets of the two images are mixed in the same proportion. Only label and multi-label
+/// annotations are supported, and all the images of a batch must have the same size.
+///
+/// The proportions are drawn from a generator seeded at construction, so the mixed batches are
+/// only reproducible when the same batches are mixed in the same order, from a single thread.
+#[derive(Debug)]
+pub struct MixUp {
+    alpha: f32,
+    num_classes: usize,
+    rng: Mutex<StdRng>,
+}
+
+impl MixUp {
+    /// Creates a MixUp augmentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The parameter of the beta distribution, must be positive.
+    /// * `num_classes` - The number of classes of the targets.
+    /// * `seed` - The seed used to sample the mixing proportions.
+    pub fn new(alpha: f32, num_classes: usize, seed: u64) -> Self {
+        Self {
+            alpha,
+            num_classes,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Mixes the items of a batch.
+    pub fn mix(&self, items: &[ImageDatasetItem]) -> Vec<MixedImageItem> {
+        let lambda = sample_beta(&self.rng, self.alpha);
+
+        mix_pairs(items, self.num_classes, |item, other| {
+            let image = item
+                .image
+                .iter()
+                .zip(other.image.iter())
+                .map(|(a, b)| {
+                    pixel_like(lambda * pixel_value(a) + (1.0 - lambda) * pixel_value(b), a)
+                })
+                .collect();
+
+            (image, lambda)
+        })
+    }
+}
+
+/// Pastes a random rectangle of the next image of a batch (the first for the last) on each image,
+/// following [CutMix](https://arxiv.org/abs/1905.04899).
+///
+/// The area of the rectangle is `1 - lambda` times the image area, with `lambda` sampled from
+/// `Beta(alpha, alpha)` for each batch. The targets are mixed in proportion to the area of each
+/// image after the rectangle is clipped to the image. Only label and multi-label annotations are
+/// supported, and all the images of a batch must have the same size.
+///
+/// The rectangles are drawn from a generator seeded at construction, so the mixed batches are
+/// only reproducible when the same batches are mixed in the same order, from a single thread.
+#[derive(Debug)]
+pub struct CutMix {
+    alpha: f32,
+    num_classes: usize,
+    rng: Mutex<StdRng>,
+}
+
+impl CutMix {
+    /// Creates a CutMix augmentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The parameter of the beta distribution, must be positive.
+    /// * `num_classes` - The number of classes of the targets.
+    /// * `seed` - The seed used to sample the rectangles.
+    pub fn new(alpha: f32, num_classes: usize, seed: u64) -> Self {
+        Self {
+            alpha,
+            num_classes,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Mixes the items of a batch.
+    pub fn mix(&self, items: &[ImageDatasetItem]) -> Vec<MixedImageItem> {
+        let Some(first) = items.first() else {
+            return Vec::new();
+        };
+        let (width, height) = (first.width, first.height);
+        let lambda = sample_beta(&self.rng, self.alpha);
+
+        // The rectangle is sampled once per batch, like the mixing proportion.
+        let (x_min, y_min, x_max, y_max) = {
+            let mut rng = self.rng.lock().unwrap();
+            let cut = (1.0 - lambda).sqrt();
+            let (cut_width, cut_height) = (width as f32 * cut, height as f32 * cut);
+            let center_x = rng.gen_range(0..width.max(1)) as f32;
+            let center_y = rng.gen_range(0..height.max(1)) as f32;
+            let clip = |value: f32, max: usize| value.round().clamp(0.0, max as f32) as usize;
+
+            (
+                clip(center_x - cut_width / 2.0, width),
+                clip(center_y - cut_height / 2.0, height),
+                clip(center_x + cut_width / 2.0, width),
+                clip(center_y + cut_height / 2.0, height),
+            )
+        };
+        let area = ((x_max - x_min) * (y_max - y_min)) as f32;
+        let lambda = 1.0 - area / (width * height).max(1) as f32;
+
+        mix_pairs(items, self.num_classes, |item, other| {
+            let channels = channels(item);
+            let mut image = item.image.clone();
+
+            for y in y_min..y_max {
+                let start = (y * width + x_min) * channels;
+                let end = (y * width + x_max) * channels;
+                image[start..end].clone_from_slice(&other.image[start..end]);
+            }
+
+            (image, lambda)
+        })
+    }
+}
+
+/// Mixes each item with the next one using the given function, which returns the mixed image and
+/// the proportion of the first item.
+fn mix_pairs<F>(items: &[ImageDatasetItem], num_classes: usize, mix: F) -> Vec<MixedImageItem>
+where
+    F: Fn(&ImageDatasetItem, &ImageDatasetItem) -> (Vec<PixelDepth>, f32),
+{
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let other = &items[(i + 1) % items.len()];
+            assert!(
+                item.width == other.width
+                    && item.height == other.height
+                    && item.image.len() == other.image.len(),
+                "Mixed images should have the same size"
+            );
+
+            let (image, lambda) = mix(item, other);
+            let targets = targets(&item.annotation, num_classes)
+                .into_iter()
+                .zip(targets(&other.annotation, num_classes))
+                .map(|(a, b)| lambda * a + (1.0 - lambda) * b)
+                .collect();
+
+            MixedImageItem {
+                image,
+                width: item.width,
+                height: item.height,
+                targets,
+            }
+        })
+        .collect()
+}
+
+/// Converts a label annotation to class probabilities.
+fn targets(annotation: &Annotation, num_classes: usize) -> Vec<f32> {
+    let mut targets = vec![0.0; num_classes];
+
+    match annotation {
+        Annotation::Label(label) => targets[*label] = 1.0,
+        Annotation::MultiLabel(labels) => labels.iter().for_each(|label| targets[*label] = 1.0),
+        _ => panic!("Only label and multi-label annotations can be mixed"),
+    }
+
+    targets
+}
+
+fn sample_beta(rng: &Mutex<StdRng>, alpha: f32) -> f32 {
+    let beta = Beta::new(alpha, alpha).expect("Beta distribution parameter should be positive");
+
+    beta.sample(&mut *rng.lock().unwrap())
+}
+
+/// Samples a value uniformly in the given range, which may be empty.
+fn sample(rng: &mut StdRng, (low, high): (f32, f32)) -> f32 {
+    if low < high {
+        rng.gen_range(low..high)
+    } else {
+        low
+    }
+}
+
+/// Affine transform of pixel coordinates, mapping `(x, y)` to `(a x + b y + c, d x + e y + f)`.
+#[derive(Debug, Clone, Copy)]
+struct Affine([f32; 6]);
+
+impl Affine {
+    fn scale(x: f32, y: f32) -> Self {
+        Self([x, 0.0, 0.0, 0.0, y, 0.0])
+    }
+
+    fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let [a, b, c, d, e, f] = self.0;
+
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+
+    fn inverse(&self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let det = a * e - b * d;
+
+        Self([
+            e / det,
+            -b / det,
+            (b * f - c * e) / det,
+            -d / det,
+            a / det,
+            (c * d - a * f) / det,
+        ])
+    }
+}
+
+/// Applies a geometric transform to an item, producing an item of the given size.
+///
+/// Images are sampled with bilinear interpolation and masks with nearest neighbor interpolation,
+/// using zeros outside of the original image. Bounding boxes are replaced by the smallest boxes
+/// containing their transformed corners, clipped to the new image, and removed when empty.
+fn warp(
+    item: &ImageDatasetItem,
+    transform: Affine,
+    width: usize,
+    height: usize,
+) -> ImageDatasetItem {
+    let channels = channels(item);
+    let inverse = transform.inverse();
+    let (src_width, src_height) = (item.width as f32, item.height as f32);
+
+    // Source coordinates of the center of each output pixel.
+    let sources = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| inverse.apply(x as f32 + 0.5, y as f32 + 0.5))
+        .map(|(x, y)| {
+            let inside = x >= 0.0 && x < src_width && y >= 0.0 && y < src_height;
+            inside.then_some((x, y))
+        })
+        .collect::<Vec<_>>();
+
+    let mut image = Vec::with_capacity(width * height * channels);
+    for source in sources.iter() {
+        for channel in 0..channels {
+            let value = match source {
+                Some((x, y)) => bilinear(item, channels, channel, x - 0.5, y - 0.5),
+                None => 0.0,
+            };
+            image.push(pixel_like(value, &item.image[channel]));
+        }
+    }
+
+    let annotation = match &item.annotation {
+        Annotation::BoundingBoxes(boxes) => Annotation::BoundingBoxes(
+            boxes
+                .iter()
+                .filter_map(|bbox| {
+                    let [x_min, y_min, x_max, y_max] = bbox.coords;
+                    let corners = [
+                        transform.apply(x_min, y_min),
+                        transform.apply(x_max, y_min),
+                        transform.apply(x_min, y_max),
+                        transform.apply(x_max, y_max),
+                    ];
+                    let clip = |value: f32, max: usize| value.clamp(0.0, max as f32);
+                    let xs = corners.iter().map(|(x, _)| clip(*x, width));
+                    let ys = corners.iter().map(|(_, y)| clip(*y, height));
+                    let coords = [
+                        xs.clone().fold(f32::INFINITY, f32::min),
+                        ys.clone().fold(f32::INFINITY, f32::min),
+                        xs.fold(f32::NEG_INFINITY, f32::max),
+                        ys.fold(f32::NEG_INFINITY, f32::max),
+                    ];
+
+                    (coords[2] > coords[0] && coords[3] > coords[1]).then_some(BoundingBox {
+                        coords,
+                        label: bbox.label,
+                    })
+                })
+                .collect(),
+        ),
+        Annotation::SegmentationMask(mask) => {
+            let size = item.width * item.height;
+            let num_masks = mask.mask.len() / size.max(1);
+            let mask = (0..num_masks)
+                .flat_map(|index| {
+                    let offset = index * size;
+                    sources.iter().map(move |source| match source {
+                        Some((x, y)) => mask.mask[offset + *y as usize * item.width + *x as usize],
+                        None => 0,
+                    })
+                })
+                .collect();
+
+            Annotation::SegmentationMask(SegmentationMask { mask })
+        }
+        annotation => annotation.clone(),
+    };
+
+    ImageDatasetItem {
+        image,
+        width,
+        height,
+        annotation,
+    }
+}
+
+fn bilinear(item: &ImageDatasetItem, channels: usize, channel: usize, x: f32, y: f32) -> f32 {
+    let clamp_x = |x: f32| x.clamp(0.0, (item.width - 1) as f32);
+    let clamp_y = |y: f32| y.clamp(0.0, (item.height - 1) as f32);
+    let (x, y) = (clamp_x(x), clamp_y(y));
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(item.width - 1), (y0 + 1).min(item.height - 1));
+    let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+    let value =
+        |x: usize, y: usize| pixel_value(&item.image[(y * item.width + x) * channels + channel]);
+
+    let top = value(x0, y0) * (1.0 - tx) + value(x1, y0) * tx;
+    let bottom = value(x0, y1) * (1.0 - tx) + value(x1, y1) * tx;
+
+    top * (1.0 - ty) + bottom * ty
+}
+
+/// Flips an item horizontally or vertically.
+fn flip(item: &ImageDatasetItem, horizontal: bool) -> ImageDatasetItem {
+    let (width, height) = (item.width, item.height);
+    let source = |x: usize, y: usize| match horizontal {
+        true => y * width + width - 1 - x,
+        false => (height - 1 - y) * width + x,
+    };
+    let channels = channels(item);
+
+    let image = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let start = source(x, y) * channels;
+            item.image[start..start + channels].iter().cloned()
+        })
+        .collect();
+
+    let annotation = match &item.annotation {
+        Annotation::BoundingBoxes(boxes) => Annotation::BoundingBoxes(
+            boxes
+                .iter()
+                .map(|bbox| {
+                    let [x_min, y_min, x_max, y_max] = bbox.coords;
+                    let coords = match horizontal {
+                        true => [width as f32 - x_max, y_min, width as f32 - x_min, y_max],
+                        false => [x_min, height as f32 - y_max, x_max, height as f32 - y_min],
+                    };
+
+                    BoundingBox {
+                        coords,
+                        label: bbox.label,
+                    }
+                })
+                .collect(),
+        ),
+        Annotation::SegmentationMask(mask) => {
+            let size = width * height;
+            let mask = mask
+                .mask
+                .chunks(size.max(1))
+                .flat_map(|mask| {
+                    (0..height)
+                        .flat_map(move |y| (0..width).map(move |x| (x, y)))
+                        .map(move |(x, y)| mask[source(x, y)])
+                })
+                .collect();
+
+            Annotation::SegmentationMask(SegmentationMask { mask })
+        }
+        annotation => annotation.clone(),
+    };
+
+    ImageDatasetItem {
+        image,
+        width,
+        height,
+        annotation,
+    }
+}
+
+/// The number of channels of an item image.
+fn channels(item: &ImageDatasetItem) -> usize {
+    (item.image.len() / (item.width * item.height).max(1)).max(1)
+}
+
+fn pixel_value(pixel: &PixelDepth) -> f32 {
+    match pixel {
+        PixelDepth::U8(value) => *value as f32,
+        PixelDepth::U16(value) => *value as f32,
+        PixelDepth::F32(value) => *value,
+    }
+}
+
+/// Converts a value to a pixel with the same depth as the given pixel.
+fn pixel_like(value: f32, like: &PixelDepth) -> PixelDepth {
+    match like {
+        PixelDepth::U8(_) => PixelDepth::U8(value.round().clamp(0.0, u8::MAX as f32) as u8),
+        PixelDepth::U16(_) => PixelDepth::U16(value.round().clamp(0.0, u16::MAX as f32) as u16),
+        PixelDepth::F32(_) => PixelDepth::F32(value),
+    }
+}
+
+/// The value of a white pixel with the depth of the given pixel.
+fn max_value(pixel: &PixelDepth) -> f32 {
+    match pixel {
+        PixelDepth::U8(_) => u8::MAX as f32,
+        PixelDepth::U16(_) => u16::MAX as f32,
+        PixelDepth::F32(_) => 1.0,
+    }
+}
+
+fn clamp(pixel: &mut [f32]) {
+    pixel
+        .iter_mut()
+        .for_each(|value| *value = value.clamp(0.0, 1.0));
+}
+
+fn grayscale(pixel: &[f32]) -> f32 {
+    match pixel {
+        [r, g, b] => 0.299 * r + 0.587 * g + 0.114 * b,
+        _ => pixel[0],
+    }
+}
+
+fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max)
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (f32, f32, f32) {
+    let sector = hue * 6.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let min = value - chroma;
+
+    let (r, g, b) = match sector as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    (r + min, g + min, b + min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{PartialDataset, ShuffledDataset};
+    use crate::InMemDataset;
+    use std::sync::Arc;
+
+    /// A `width` x `height` RGB image whose pixel values encode their position.
+    fn rgb_item(width: usize, height: usize, annotation: Annotation) -> ImageDatasetItem {
+        let image = (0..width * height)
+            .flat_map(|i| [i as u8, 100, 200])
+            .map(PixelDepth::U8)
+            .collect();
+
+        ImageDatasetItem {
+            image,
+            width,
+            height,
+            annotation,
+        }
+    }
+
+    fn red(item: &ImageDatasetItem) -> Vec<u8> {
+        item.image
+            .chunks(3)
+            .map(|pixel| u8::try_from(pixel[0].clone()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn horizontal_flip_should_flip_image_boxes_and_masks() {
+        let flip = RandomHorizontalFlip::new(1.0, 0);
+        let boxes = Annotation::BoundingBoxes(vec![BoundingBox {
+            coords: [0.0, 0.0, 1.0, 2.0],
+            label: 1,
+        }]);
+
+        let item = flip.map(&rgb_item(3, 2, boxes));
+
+        assert_eq!(red(&item), vec![2, 1, 0, 5, 4, 3]);
+        assert_eq!(
+            item.annotation,
+            Annotation::BoundingBoxes(vec![BoundingBox {
+                coords: [2.0, 0.0, 3.0, 2.0],
+                label: 1,
+            }])
+        );
+
+        let mask = Annotation::SegmentationMask(SegmentationMask {
+            mask: vec![1, 2, 3, 4, 5, 6],
+        });
+        let item = RandomVerticalFlip::new(1.0, 0).map(&rgb_item(3, 2, mask));
+
+        assert_eq!(red(&item), vec![3, 4, 5, 0, 1, 2]);
+        assert_eq!(
+            item.annotation,
+            Annotation::SegmentationMask(SegmentationMask {
+                mask: vec![4, 5, 6, 1, 2, 3],
+            })
+        );
+    }
+
+    #[test]
+    fn flip_with_zero_probability_should_keep_item() {
+        let item = rgb_item(3, 2, Annotation::Label(0));
+
+        assert_eq!(RandomHorizontalFlip::new(0.0, 0).map(&item), item);
+    }
+
+    #[test]
+    fn resize_should_scale_image_boxes_and_masks() {
+        let boxes = Annotation::BoundingBoxes(vec![BoundingBox {
+            coords: [1.0, 0.0, 2.0, 1.0],
+            label: 0,
+        }]);
+
+        let item = Resize::new(4, 2).map(&rgb_item(2, 1, boxes));
+
+        assert_eq!((item.width, item.height), (4, 2));
+        assert_eq!(item.image.len(), 4 * 2 * 3);
+        assert_eq!(red(&item), vec![0, 0, 1, 1, 0, 0, 1, 1]);
+        assert_eq!(
+            item.annotation,
+            Annotation::BoundingBoxes(vec![BoundingBox {
+                coords: [2.0, 0.0, 4.0, 2.0],
+                label: 0,
+            }])
+        );
+
+        let mask = Annotation::SegmentationMask(SegmentationMask {
+            mask: vec![1, 2, 3, 4],
+        });
+        let item = Resize::new(1, 1).map(&rgb_item(2, 2, mask));
+
+        assert_eq!(
+            item.annotation,
+            Annotation::SegmentationMask(SegmentationMask { mask: vec![4] })
+        );
+    }
+
+    #[test]
+    fn random_resized_crop_should_be_reproducible() {
+        let mask = Annotation::SegmentationMask(SegmentationMask {
+            mask: (0..64).collect(),
+        });
+        let item = rgb_item(8, 8, mask);
+        let crop = |seed| {
+            let crop = RandomResizedCrop::new(4, 4, seed).with_scale((0.25, 0.5));
+            (0..3).map(|_| crop.map(&item)).collect::<Vec<_>>()
+        };
+
+        let items = crop(42);
+
+        assert_eq!(items, crop(42));
+        for item in items {
+            assert_eq!((item.width, item.height), (4, 4));
+            // The mask follows the image, whose red value is the index of the pixel.
+            let Annotation::SegmentationMask(mask) = &item.annotation else {
+                panic!("Expected a segmentation mask");
+            };
+            let red = red(&item);
+            assert!(mask
+                .mask
+                .iter()
+                .zip(red)
+                .all(|(mask, red)| (*mask as f32 - red as f32).abs() <= 9.0));
+        }
+    }
+
+    #[test]
+    fn rotation_should_rotate_boxes_around_center() {
+        let boxes = Annotation::BoundingBoxes(vec![BoundingBox {
+            coords: [0.0, 0.0, 2.0, 2.0],
+            label: 0,
+        }]);
+        let rotation = RandomRotation::new(90.0, 0);
+        // Sample an angle with the same seed to know the rotation.
+        let degrees = sample(&mut StdRng::seed_from_u64(0), (-90.0, 90.0));
+
+        let item = rotation.map(&rgb_item(4, 4, boxes));
+
+        let Annotation::BoundingBoxes(boxes) = item.annotation else {
+            panic!("Expected bounding boxes");
+        };
+        let [x_min, y_min, x_max, y_max] = boxes[0].coords;
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let expected_size = 2.0 * (sin.abs() + cos.abs());
+        // The box is rotated around the image center, and clipped to the image.
+        assert!(x_max <= 4.0 && y_max <= 4.0 && x_min >= 0.0 && y_min >= 0.0);
+        assert!((x_max - x_min) <= expected_size + 1e-4);
+        assert!((y_max - y_min) <= expected_size + 1e-4);
+    }
+
+    #[test]
+    fn color_jitter_should_only_change_colors() {
+        let jitter = ColorJitter::new(1)
+            .with_brightness(0.5)
+            .with_contrast(0.5)
+            .with_saturation(0.5)
+            .with_hue(0.1);
+        let item = rgb_item(2, 2, Annotation::Label(3));
+
+        let jittered = jitter.map(&item);
+
+        assert_eq!(jittered.annotation, Annotation::Label(3));
+        assert_eq!(jittered.image.len(), item.image.len());
+        assert_ne!(jittered.image, item.image);
+        assert_eq!(ColorJitter::new(1).map(&item), item);
+    }
+
+    #[test]
+    fn hsv_conversion_should_round_trip() {
+        let (h, s, v) = rgb_to_hsv(0.2, 0.6, 0.4);
+        let (r, g, b) = hsv_to_rgb(h, s, v);
+
+        assert!((r - 0.2).abs() < 1e-5 && (g - 0.6).abs() < 1e-5 && (b - 0.4).abs() < 1e-5);
+    }
+
+    #[test]
+    fn normalize_should_output_float_pixels() {
+        let normalize = Normalize::new(vec![0.0, 0.5, 0.5], vec![1.0, 0.5, 0.25]);
+
+        let item = normalize.map(&rgb_item(1, 1, Annotation::Label(0)));
+
+        assert_eq!(
+            item.image,
+            vec![
+                PixelDepth::F32(0.0),
+                PixelDepth::F32((100.0 / 255.0 - 0.5) / 0.5),
+                PixelDepth::F32((200.0 / 255.0 - 0.5) / 0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn random_erasing_should_erase_rectangle() {
+        let erasing = RandomErasing::new(1.0, 3).with_value(255.0);
+
+        let item = erasing.map(&rgb_item(10, 10, Annotation::Label(0)));
+
+        let erased = item
+            .image
+            .iter()
+            .filter(|value| **value == PixelDepth::U8(255))
+            .count();
+        // All the channels of the erased pixels are erased.
+        assert!(erased > 0 && erased % 3 == 0 && erased < 3 * 50);
+    }
+
+    #[test]
+    fn compose_should_apply_augmentations_in_order() {
+        let compose = Compose::new()
+            .with(RandomHorizontalFlip::new(1.0, 0))
+            .with(Resize::new(6, 2));
+
+        let item = compose.map(&rgb_item(3, 1, Annotation::Label(0)));
+
+        assert_eq!((item.width, item.height), (6, 2));
+        assert_eq!(red(&item)[..6], [2, 2, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn mixup_should_mix_images_and_targets() {
+        let items = [
+            ImageDatasetItem {
+                image: vec![PixelDepth::F32(0.0); 4],
+                width: 2,
+                height: 2,
+                annotation: Annotation::Label(0),
+            },
+            ImageDatasetItem {
+                image: vec![PixelDepth::F32(1.0); 4],
+                width: 2,
+                height: 2,
+                annotation: Annotation::MultiLabel(vec![1, 2]),
+            },
+        ];
+
+        let mixed = MixUp::new(0.4, 3, 5).mix(&items);
+
+        assert_eq!(mixed.len(), 2);
+        let lambda = mixed[0].targets[0];
+        let expected = [1.0 - lambda, lambda];
+        for (mixed, expected) in mixed.iter().zip(expected) {
+            let PixelDepth::F32(value) = mixed.image[0] else {
+                panic!("Expected float pixels");
+            };
+            assert!((value - expected).abs() < 1e-6);
+        }
+        assert!((mixed[0].targets[1] - (1.0 - lambda)).abs() < 1e-6);
+        assert_eq!(mixed, MixUp::new(0.4, 3, 5).mix(&items));
+    }
+
+    #[test]
+    fn cutmix_should_mix_targets_with_pasted_area() {
+        let items = [
+            ImageDatasetItem {
+                image: vec![PixelDepth::U8(0); 16],
+                width: 4,
+                height: 4,
+                annotation: Annotation::Label(0),
+            },
+            ImageDatasetItem {
+                image: vec![PixelDepth::U8(1); 16],
+                width: 4,
+                height: 4,
+                annotation: Annotation::Label(1),
+            },
+        ];
+
+        for seed in 0..5 {
+            let mixed = CutMix::new(1.0, 2, seed).mix(&items);
+
+            let pasted = mixed[0]
+                .image
+                .iter()
+                .filter(|value| **value == PixelDepth::U8(1))
+                .count() as f32;
+            assert_eq!(mixed[0].targets, vec![1.0 - pasted / 16.0, pasted / 16.0]);
+            assert_eq!(mixed[1].targets, vec![pasted / 16.0, 1.0 - pasted / 16.0]);
+        }
+    }
+
+    #[test]
+    fn augmented_dataset_should_not_depend_on_read_order_or_thread() {
+        let augmentation = || {
+            Compose::new()
+                .with(RandomResizedCrop::new(4, 4, 0).with_scale((0.25, 0.5)))
+                .with(RandomHorizontalFlip::new(0.5, 0))
+        };
+        let items = (0..8)
+            .map(|_| rgb_item(8, 8, Annotation::Label(0)))
+            .collect::<Vec<_>>();
+        let dataset = || AugmentedDataset::new(InMemDataset::new(items.clone()), augmentation(), 7);
+
+        let in_order = dataset();
+        let epoch = |epoch| {
+            (0..8)
+                .map(|i| in_order.get_for_epoch(i, epoch).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let (epoch_0, epoch_1) = (epoch(0), epoch(1));
+
+        // Each thread reads the items of every other index, in reverse order, some of them twice.
+        let threaded = dataset();
+        let mut items = std::thread::scope(|scope| {
+            let threads = (0..2)
+                .map(|thread| {
+                    let dataset = &threaded;
+                    scope.spawn(move || {
+                        let _item = dataset.get_for_epoch(thread, 0);
+                        (0..8)
+                            .rev()
+                            .filter(|i| i % 2 == thread)
+                            .map(|i| (i, dataset.get_for_epoch(i, 1).unwrap()))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            threads
+                .into_iter()
+                .flat_map(|thread| thread.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        items.sort_by_key(|(i, _)| *i);
+
+        assert_eq!(
+            items.into_iter().map(|(_, item)| item).collect::<Vec<_>>(),
+            epoch_1
+        );
+        assert_eq!(
+            (0..8).map(|i| threaded.get(i).unwrap()).collect::<Vec<_>>(),
+            epoch_0
+        );
+        assert_ne!(epoch_0, epoch_1);
+    }
+
+    #[test]
+    fn augmented_dataset_should_read_the_epoch_through_the_transforms() {
+        let items = (0..4)
+            .map(|i| rgb_item(8, 8, Annotation::Label(i)))
+            .collect::<Vec<_>>();
+        let augmented = Arc::new(AugmentedDataset::new(
+            InMemDataset::new(items),
+            RandomResizedCrop::new(4, 4, 0).with_scale((0.25, 0.5)),
+            7,
+        ));
+        let partial = PartialDataset::new(ShuffledDataset::with_seed(augmented.clone(), 3), 1, 3);
+
+        let items = (0..3)
+            .map(|epoch| partial.get_for_epoch(0, epoch).unwrap())
+            .collect::<Vec<_>>();
+        for (epoch, item) in items.iter().enumerate() {
+            let Annotation::Label(index) = item.annotation else {
+                panic!("the items should have a label");
+            };
+            assert_eq!(item, &augmented.get_for_epoch(index, epoch).unwrap());
+        }
+        assert_ne!(items[0], items[1]);
+    }
+}
@@ -0,0 +1,507 @@
+use super::{load_image, Annotation, BoundingBox, ImageDatasetItem, SegmentationMask};
+use crate::Dataset;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Error type for [CocoDataset](CocoDataset).
+#[derive(Error, Debug)]
+pub enum CocoDatasetError {
+    /// I/O operation error.
+    #[error("I/O error: `{0}`")]
+    Io(#[from] std::io::Error),
+
+    /// Invalid annotation file error.
+    #[error("JSON error: `{0}`")]
+    Json(#[from] serde_json::Error),
+
+    /// The category isn't defined in the annotation file.
+    #[error("Unknown category: `{0}`")]
+    UnknownCategory(String),
+}
+
+/// The annotations loaded by a [COCO dataset](CocoDataset).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CocoTask {
+    Detection,
+    Segmentation,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CocoFile {
+    images: Vec<CocoImage>,
+    #[serde(default)]
+    annotations: Vec<CocoObject>,
+    categories: Vec<CocoCategory>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CocoImage {
+    id: u64,
+    file_name: String,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CocoCategory {
+    id: u64,
+    name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CocoObject {
+    image_id: u64,
+    category_id: u64,
+    /// Bounding box as `[x, y, width, height]`.
+    bbox: [f32; 4],
+    #[serde(default)]
+    segmentation: Option<CocoSegmentation>,
+}
+
+/// Object segmentation, either polygons (single objects) or a run-length encoded mask (crowds).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum CocoSegmentation {
+    Polygons(Vec<Vec<f32>>),
+    Rle(CocoRle),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CocoRle {
+    /// Mask size as `[height, width]`.
+    size: [usize; 2],
+    counts: CocoRleCounts,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum CocoRleCounts {
+    Uncompressed(Vec<usize>),
+    Compressed(String),
+}
+
+#[derive(Debug, Clone)]
+struct CocoItem {
+    image_path: PathBuf,
+    width: usize,
+    height: usize,
+    objects: Vec<CocoObject>,
+}
+
+/// Dataset loading images annotated in the [COCO](https://cocodataset.org/#format-data) format.
+///
+/// Each image of the annotation file is an item, including the images without objects. The
+/// sparse COCO category ids are remapped to contiguous labels, in the order of the category ids
+/// by default (see [classes](CocoDataset::classes)).
+///
+/// Images are loaded from disk when items are read.
+#[derive(Debug, Clone)]
+pub struct CocoDataset {
+    items: Vec<CocoItem>,
+    categories: Vec<CocoCategory>,
+    labels: HashMap<u64, usize>,
+    task: CocoTask,
+}
+
+impl CocoDataset {
+    /// Create an object detection dataset from a COCO annotation file.
+    ///
+    /// # Arguments
+    ///
+    /// * `annotations_file` - Path to the JSON annotation file, e.g. `instances_train2017.json`.
+    /// * `images_dir` - Directory containing the images of the annotation file.
+    ///
+    /// # Returns
+    /// A new dataset instance with [bounding boxes](Annotation::BoundingBoxes) as
+    /// `[x_min, y_min, x_max, y_max]`.
+    pub fn new_detection<P: AsRef<Path>, Q: AsRef<Path>>(
+        annotations_file: P,
+        images_dir: Q,
+    ) -> Result<Self, CocoDatasetError> {
+        Self::load(annotations_file, images_dir, CocoTask::Detection)
+    }
+
+    /// Create a semantic segmentation dataset from a COCO annotation file.
+    ///
+    /// Polygon and run-length encoded segmentations are rasterized into a single mask whose
+    /// values are `label + 1`, `0` being the background. Overlapping objects are drawn in the
+    /// order of the annotation file.
+    ///
+    /// # Arguments
+    ///
+    /// * `annotations_file` - Path to the JSON annotation file, e.g. `instances_train2017.json`.
+    /// * `images_dir` - Directory containing the images of the annotation file.
+    ///
+    /// # Returns
+    /// A new dataset instance with [segmentation masks](Annotation::SegmentationMask).
+    pub fn new_segmentation<P: AsRef<Path>, Q: AsRef<Path>>(
+        annotations_file: P,
+        images_dir: Q,
+    ) -> Result<Self, CocoDatasetError> {
+        Self::load(annotations_file, images_dir, CocoTask::Segmentation)
+    }
+
+    /// Remap the categories to the given category names, the label of each category being its
+    /// index in the list.
+    ///
+    /// Objects of the other categories are ignored, which makes it possible to train on a subset
+    /// of the categories.
+    pub fn with_categories<S: AsRef<str>>(
+        mut self,
+        categories: &[S],
+    ) -> Result<Self, CocoDatasetError> {
+        let categories = categories
+            .iter()
+            .map(|name| {
+                self.categories
+                    .iter()
+                    .find(|category| category.name == name.as_ref())
+                    .cloned()
+                    .ok_or_else(|| CocoDatasetError::UnknownCategory(name.as_ref().to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.labels = labels(&categories);
+        self.categories = categories;
+
+        Ok(self)
+    }
+
+    /// The category names, indexed by label.
+    pub fn classes(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .map(|category| category.name.clone())
+            .collect()
+    }
+
+    fn load<P: AsRef<Path>, Q: AsRef<Path>>(
+        annotations_file: P,
+        images_dir: Q,
+        task: CocoTask,
+    ) -> Result<Self, CocoDatasetError> {
+        let file = File::open(annotations_file)?;
+        let coco: CocoFile = serde_json::from_reader(BufReader::new(file))?;
+
+        let mut objects = HashMap::<u64, Vec<CocoObject>>::new();
+        for object in coco.annotations {
+            objects.entry(object.image_id).or_default().push(object);
+        }
+
+        let items = coco
+            .images
+            .into_iter()
+            .map(|image| CocoItem {
+                image_path: images_dir.as_ref().join(&image.file_name),
+                width: image.width,
+                height: image.height,
+                objects: objects.remove(&image.id).unwrap_or_default(),
+            })
+            .collect();
+
+        let mut categories = coco.categories;
+        categories.sort_by_key(|category| category.id);
+
+        Ok(Self {
+            items,
+            labels: labels(&categories),
+            categories,
+            task,
+        })
+    }
+}
+
+/// Map each category id to its index.
+fn labels(categories: &[CocoCategory]) -> HashMap<u64, usize> {
+    categories
+        .iter()
+        .enumerate()
+        .map(|(label, category)| (category.id, label))
+        .collect()
+}
+
+impl Dataset<ImageDatasetItem> for CocoDataset {
+    fn get(&self, index: usize) -> Option<ImageDatasetItem> {
+        let item = self.items.get(index)?;
+        let (image, width, height) = load_image(&item.image_path);
+
+        // Objects of the categories that aren't used are ignored.
+        let objects = item
+            .objects
+            .iter()
+            .filter_map(|object| Some((object, *self.labels.get(&object.category_id)?)));
+
+        let annotation = match self.task {
+            CocoTask::Detection => Annotation::BoundingBoxes(
+                objects
+                    .map(|(object, label)| {
+                        let [x, y, w, h] = object.bbox;
+                        BoundingBox {
+                            coords: [x, y, x + w, y + h],
+                            label,
+                        }
+                    })
+                    .collect(),
+            ),
+            CocoTask::Segmentation => {
+                let mut mask = vec![0; item.width * item.height];
+
+                for (object, label) in objects {
+                    let pixels = match &object.segmentation {
+                        Some(segmentation) => rasterize(segmentation, item.width, item.height),
+                        None => continue,
+                    };
+
+                    for (value, _) in mask.iter_mut().zip(pixels).filter(|(_, inside)| *inside) {
+                        *value = label + 1;
+                    }
+                }
+
+                Annotation::SegmentationMask(SegmentationMask { mask })
+            }
+        };
+
+        Some(ImageDatasetItem {
+            image,
+            width,
+            height,
+            annotation,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// Rasterize an object segmentation into a row-major binary mask.
+fn rasterize(segmentation: &CocoSegmentation, width: usize, height: usize) -> Vec<bool> {
+    match segmentation {
+        CocoSegmentation::Polygons(polygons) => {
+            let mut mask = vec![false; width * height];
+            for polygon in polygons {
+                fill_polygon(&mut mask, polygon, width, height);
+            }
+            mask
+        }
+        CocoSegmentation::Rle(rle) => {
+            assert_eq!(
+                rle.size,
+                [height, width],
+                "The run-length encoded mask should have the size of the image"
+            );
+
+            let counts = match &rle.counts {
+                CocoRleCounts::Uncompressed(counts) => counts.clone(),
+                CocoRleCounts::Compressed(counts) => decode_rle_counts(counts),
+            };
+
+            decode_rle(&counts, width, height)
+        }
+    }
+}
+
+/// Fill the pixels whose center is inside the polygon `[x1, y1, x2, y2, ...]`, using the even-odd
+/// rule.
+fn fill_polygon(mask: &mut [bool], polygon: &[f32], width: usize, height: usize) {
+    let points = polygon
+        .chunks_exact(2)
+        .map(|point| (point[0], point[1]))
+        .collect::<Vec<_>>();
+
+    for y in 0..height {
+        let center = y as f32 + 0.5;
+
+        // Intersections of the polygon edges with the row.
+        let mut xs = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .filter(|((_, y0), (_, y1))| (*y0 <= center) != (*y1 <= center))
+            .map(|((x0, y0), (x1, y1))| x0 + (center - y0) * (x1 - x0) / (y1 - y0))
+            .collect::<Vec<_>>();
+        xs.sort_by(|a, b| a.total_cmp(b));
+
+        for span in xs.chunks_exact(2) {
+            // Pixels whose center is in [start, end).
+            let start = (span[0] - 0.5).ceil().clamp(0.0, width as f32) as usize;
+            let end = (span[1] - 0.5).ceil().clamp(0.0, width as f32) as usize;
+
+            mask[y * width + start..y * width + end.max(start)].fill(true);
+        }
+    }
+}
+
+/// Decode the compressed counts of a COCO run-length encoding, as done by
+/// [pycocotools](https://github.com/cocodataset/cocoapi/blob/master/common/maskApi.c).
+fn decode_rle_counts(counts: &str) -> Vec<usize> {
+    let bytes = counts.as_bytes();
+    let mut decoded = Vec::<i64>::new();
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let mut value = 0i64;
+        let mut shift = 0;
+
+        loop {
+            let c = bytes[position] as i64 - 48;
+            value |= (c & 0x1f) << shift;
+            position += 1;
+            shift += 5;
+
+            if c & 0x20 == 0 {
+                // The sign bit of the last chunk.
+                if c & 0x10 != 0 {
+                    value |= -1 << shift;
+                }
+                break;
+            }
+        }
+
+        // Counts after the second one are encoded as differences.
+        if decoded.len() > 2 {
+            value += decoded[decoded.len() - 2];
+        }
+        decoded.push(value);
+    }
+
+    decoded.into_iter().map(|count| count as usize).collect()
+}
+
+/// Decode a run-length encoding, alternating counts of background and object pixels in
+/// column-major order, into a row-major binary mask.
+fn decode_rle(counts: &[usize], width: usize, height: usize) -> Vec<bool> {
+    let mut mask = vec![false; width * height];
+    let mut position = 0;
+
+    for (i, count) in counts.iter().enumerate() {
+        let end = (position + count).min(width * height);
+
+        if i % 2 == 1 {
+            for index in position..end {
+                let (x, y) = (index / height, index % height);
+                mask[y * width + x] = true;
+            }
+        }
+        position = end;
+    }
+
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANNOTATIONS_FILE: &str = "tests/data/coco/annotations.json";
+    const IMAGES_DIR: &str = "tests/data/coco/images";
+
+    fn bbox(coords: [f32; 4], label: usize) -> BoundingBox {
+        BoundingBox { coords, label }
+    }
+
+    #[test]
+    pub fn coco_detection_dataset() {
+        let dataset = CocoDataset::new_detection(ANNOTATIONS_FILE, IMAGES_DIR).unwrap();
+
+        // Categories are sorted by id: cat (3), bird (5) and dog (7).
+        assert_eq!(dataset.classes(), vec!["cat", "bird", "dog"]);
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.get(3), None);
+
+        let item = dataset.get(0).unwrap();
+        assert_eq!((item.width, item.height), (4, 4));
+        assert_eq!(item.image.len(), 4 * 4 * 3);
+        assert_eq!(
+            item.annotation,
+            Annotation::BoundingBoxes(vec![
+                bbox([0.0, 0.0, 2.0, 2.0], 0),
+                bbox([3.0, 0.0, 4.0, 4.0], 2)
+            ])
+        );
+        assert_eq!(
+            dataset.get(1).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![bbox([1.0, 1.0, 3.0, 3.0], 1)])
+        );
+
+        // Images without objects are kept.
+        let item = dataset.get(2).unwrap();
+        assert_eq!((item.width, item.height), (2, 3));
+        assert_eq!(item.annotation, Annotation::BoundingBoxes(vec![]));
+    }
+
+    #[test]
+    pub fn coco_segmentation_dataset() {
+        let dataset = CocoDataset::new_segmentation(ANNOTATIONS_FILE, IMAGES_DIR).unwrap();
+
+        // Polygon of the cat (1) and uncompressed run-length encoding of the dog (3).
+        assert_eq!(
+            dataset.get(0).unwrap().annotation,
+            Annotation::SegmentationMask(SegmentationMask {
+                mask: vec![1, 1, 0, 3, 1, 1, 0, 3, 0, 0, 0, 3, 0, 0, 0, 3],
+            })
+        );
+        // Compressed run-length encoding of the bird (2).
+        assert_eq!(
+            dataset.get(1).unwrap().annotation,
+            Annotation::SegmentationMask(SegmentationMask {
+                mask: vec![0, 0, 0, 0, 0, 2, 2, 0, 0, 2, 2, 0, 0, 0, 0, 0],
+            })
+        );
+        assert_eq!(
+            dataset.get(2).unwrap().annotation,
+            Annotation::SegmentationMask(SegmentationMask { mask: vec![0; 6] })
+        );
+    }
+
+    #[test]
+    pub fn coco_dataset_with_categories() {
+        let dataset = CocoDataset::new_detection(ANNOTATIONS_FILE, IMAGES_DIR)
+            .unwrap()
+            .with_categories(&["dog", "cat"])
+            .unwrap();
+
+        assert_eq!(dataset.classes(), vec!["dog", "cat"]);
+        assert_eq!(
+            dataset.get(0).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![
+                bbox([0.0, 0.0, 2.0, 2.0], 1),
+                bbox([3.0, 0.0, 4.0, 4.0], 0)
+            ])
+        );
+        // The bird is ignored.
+        assert_eq!(
+            dataset.get(1).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![])
+        );
+
+        let result = CocoDataset::new_detection(ANNOTATIONS_FILE, IMAGES_DIR)
+            .unwrap()
+            .with_categories(&["horse"]);
+        assert!(matches!(result, Err(CocoDatasetError::UnknownCategory(_))));
+    }
+
+    #[test]
+    pub fn decode_rle_counts_should_match_pycocotools() {
+        assert_eq!(decode_rle_counts("52203"), vec![5, 2, 2, 2, 5]);
+        // Large counts span multiple characters, and differences can be negative.
+        assert_eq!(decode_rle_counts("\\92:O"), vec![300, 2, 10, 1]);
+    }
+
+    #[test]
+    pub fn fill_polygon_should_fill_pixel_centers_inside() {
+        let mut mask = vec![false; 9];
+
+        // Triangle covering the upper left half of a 3x3 image.
+        fill_polygon(&mut mask, &[0.0, 0.0, 3.2, 0.0, 0.0, 3.2], 3, 3);
+
+        assert_eq!(
+            mask,
+            vec![true, true, true, true, true, false, true, false, false]
+        );
+    }
+}
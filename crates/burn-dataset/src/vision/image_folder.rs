@@ -75,14 +75,14 @@ pub enum Annotation {
 /// For instance segmentation, there may be multiple masks per image (C >= 1).
 #[derive(Debug, Clone, PartialEq)]
 pub struct SegmentationMask {
-    /// Segmentation mask.
+    /// Segmentation mask, with the `C` masks of the image size stored one after the other.
     pub mask: Vec<usize>,
 }
 
 /// Object detection bounding box annotation.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct BoundingBox {
-    /// Coordinates.
+    /// Coordinates in pixels, as `[x_min, y_min, x_max, y_max]`.
     pub coords: [f32; 4],
 
     /// Box class label.
@@ -92,9 +92,15 @@ pub struct BoundingBox {
 /// Image dataset item.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImageDatasetItem {
-    /// Image as a vector with a valid image type.
+    /// Image as a vector with a valid image type, with the channels of each pixel interleaved.
     pub image: Vec<PixelDepth>,
 
+    /// Image width in pixels.
+    pub width: usize,
+
+    /// Image height in pixels.
+    pub height: usize,
+
     /// Annotation for the image.
     pub annotation: Annotation,
 }
@@ -155,72 +161,81 @@ impl Mapper<ImageDatasetItemRaw, ImageDatasetItem> for PathToImageDatasetItem {
     /// Convert a raw image dataset item (path-like) to a 3D image array with a target label.
     fn map(&self, item: &ImageDatasetItemRaw) -> ImageDatasetItem {
         let annotation = parse_image_annotation(&item.annotation, &self.classes);
-
-        // Load image from disk
-        let image = image::open(&item.image_path).unwrap();
-
-        // Image as Vec<PixelDepth>
-        let img_vec = match image.color() {
-            ColorType::L8 => image
-                .into_luma8()
-                .iter()
-                .map(|&x| PixelDepth::U8(x))
-                .collect(),
-            ColorType::La8 => image
-                .into_luma_alpha8()
-                .iter()
-                .map(|&x| PixelDepth::U8(x))
-                .collect(),
-            ColorType::L16 => image
-                .into_luma16()
-                .iter()
-                .map(|&x| PixelDepth::U16(x))
-                .collect(),
-            ColorType::La16 => image
-                .into_luma_alpha16()
-                .iter()
-                .map(|&x| PixelDepth::U16(x))
-                .collect(),
-            ColorType::Rgb8 => image
-                .into_rgb8()
-                .iter()
-                .map(|&x| PixelDepth::U8(x))
-                .collect(),
-            ColorType::Rgba8 => image
-                .into_rgba8()
-                .iter()
-                .map(|&x| PixelDepth::U8(x))
-                .collect(),
-            ColorType::Rgb16 => image
-                .into_rgb16()
-                .iter()
-                .map(|&x| PixelDepth::U16(x))
-                .collect(),
-            ColorType::Rgba16 => image
-                .into_rgba16()
-                .iter()
-                .map(|&x| PixelDepth::U16(x))
-                .collect(),
-            ColorType::Rgb32F => image
-                .into_rgb32f()
-                .iter()
-                .map(|&x| PixelDepth::F32(x))
-                .collect(),
-            ColorType::Rgba32F => image
-                .into_rgba32f()
-                .iter()
-                .map(|&x| PixelDepth::F32(x))
-                .collect(),
-            _ => panic!("Unrecognized image color type"),
-        };
+        let (image, width, height) = load_image(&item.image_path);
 
         ImageDatasetItem {
-            image: img_vec,
+            image,
+            width,
+            height,
             annotation,
         }
     }
 }
 
+/// Load an image from disk as a vector of pixels, along with its width and height.
+pub(crate) fn load_image<P: AsRef<Path>>(path: P) -> (Vec<PixelDepth>, usize, usize) {
+    // Load image from disk
+    let image = image::open(path).unwrap();
+    let (width, height) = (image.width() as usize, image.height() as usize);
+
+    // Image as Vec<PixelDepth>
+    let img_vec = match image.color() {
+        ColorType::L8 => image
+            .into_luma8()
+            .iter()
+            .map(|&x| PixelDepth::U8(x))
+            .collect(),
+        ColorType::La8 => image
+            .into_luma_alpha8()
+            .iter()
+            .map(|&x| PixelDepth::U8(x))
+            .collect(),
+        ColorType::L16 => image
+            .into_luma16()
+            .iter()
+            .map(|&x| PixelDepth::U16(x))
+            .collect(),
+        ColorType::La16 => image
+            .into_luma_alpha16()
+            .iter()
+            .map(|&x| PixelDepth::U16(x))
+            .collect(),
+        ColorType::Rgb8 => image
+            .into_rgb8()
+            .iter()
+            .map(|&x| PixelDepth::U8(x))
+            .collect(),
+        ColorType::Rgba8 => image
+            .into_rgba8()
+            .iter()
+            .map(|&x| PixelDepth::U8(x))
+            .collect(),
+        ColorType::Rgb16 => image
+            .into_rgb16()
+            .iter()
+            .map(|&x| PixelDepth::U16(x))
+            .collect(),
+        ColorType::Rgba16 => image
+            .into_rgba16()
+            .iter()
+            .map(|&x| PixelDepth::U16(x))
+            .collect(),
+        ColorType::Rgb32F => image
+            .into_rgb32f()
+            .iter()
+            .map(|&x| PixelDepth::F32(x))
+            .collect(),
+        ColorType::Rgba32F => image
+            .into_rgba32f()
+            .iter()
+            .map(|&x| PixelDepth::F32(x))
+            .collect(),
+        _ => panic!("Unrecognized image color type"),
+    };
+
+    (img_vec, width, height)
+}
+
 /// Error type for [ImageFolderDataset](ImageFolderDataset).
 #[derive(Error, Debug)]
 pub enum ImageLoaderError {
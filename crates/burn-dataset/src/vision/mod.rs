@@ -1,5 +1,11 @@
+mod augmentation;
+mod coco;
 mod image_folder;
 mod mnist;
+mod voc;
 
+pub use augmentation::*;
+pub use coco::*;
 pub use image_folder::*;
 pub use mnist::*;
+pub use voc::*;
@@ -0,0 +1,503 @@
+use super::{load_image, Annotation, BoundingBox, ImageDatasetItem};
+use crate::Dataset;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The 20 object classes of the [Pascal VOC](http://host.robots.ox.ac.uk/pascal/VOC/) challenge,
+/// indexed by label.
+pub const VOC_CLASSES: [&str; 20] = [
+    "aeroplane",
+    "bicycle",
+    "bird",
+    "boat",
+    "bottle",
+    "bus",
+    "car",
+    "cat",
+    "chair",
+    "cow",
+    "diningtable",
+    "dog",
+    "horse",
+    "motorbike",
+    "person",
+    "pottedplant",
+    "sheep",
+    "sofa",
+    "train",
+    "tvmonitor",
+];
+
+/// Error type for [VocDataset](VocDataset).
+#[derive(Error, Debug)]
+pub enum VocDatasetError {
+    /// I/O operation error.
+    #[error("I/O error: `{0}`")]
+    Io(#[from] std::io::Error),
+
+    /// Malformed XML annotation file.
+    #[error("XML error in `{0}`: {1}")]
+    Xml(PathBuf, String),
+
+    /// Missing or invalid annotation field.
+    #[error("Invalid annotation in `{0}`: {1}")]
+    InvalidAnnotation(PathBuf, String),
+}
+
+#[derive(Debug, Clone)]
+struct VocItem {
+    image_path: PathBuf,
+    boxes: Vec<BoundingBox>,
+}
+
+/// Object detection dataset loading images annotated with
+/// [Pascal VOC](http://host.robots.ox.ac.uk/pascal/VOC/) XML files.
+///
+/// The annotation files are parsed when the dataset is created, and the images are loaded from
+/// disk when items are read. The 1-based pixel coordinates of VOC are converted to
+/// `[x_min, y_min, x_max, y_max]` bounding boxes with `0` as the image border. Objects marked as
+/// difficult are included, while objects of other classes than the dataset classes are ignored.
+#[derive(Debug, Clone)]
+pub struct VocDataset {
+    items: Vec<VocItem>,
+    classes: Vec<String>,
+}
+
+impl VocDataset {
+    /// Create an object detection dataset from a split of the VOC folder structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Dataset root folder, e.g. `VOCdevkit/VOC2012`, containing the `Annotations`,
+    ///   `JPEGImages` and `ImageSets/Main` folders.
+    /// * `split` - The split listed in `ImageSets/Main`, e.g. `train`, `val` or `trainval`.
+    ///
+    /// # Returns
+    /// A new dataset instance with the [VOC classes](VOC_CLASSES).
+    pub fn new_detection<P: AsRef<Path>>(root: P, split: &str) -> Result<Self, VocDatasetError> {
+        Self::new_detection_with_classes(root, split, &VOC_CLASSES)
+    }
+
+    /// Create an object detection dataset from a split of the VOC folder structure, with the given
+    /// classes.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Dataset root folder containing the `Annotations`, `JPEGImages` and
+    ///   `ImageSets/Main` folders.
+    /// * `split` - The split listed in `ImageSets/Main`.
+    /// * `classes` - The object classes, the label of each class being its index in the list.
+    ///
+    /// # Returns
+    /// A new dataset instance.
+    pub fn new_detection_with_classes<P: AsRef<Path>, S: AsRef<str>>(
+        root: P,
+        split: &str,
+        classes: &[S],
+    ) -> Result<Self, VocDatasetError> {
+        let root = root.as_ref();
+        let split_file = root
+            .join("ImageSets")
+            .join("Main")
+            .join(format!("{split}.txt"));
+        let annotation_files = fs::read_to_string(split_file)?
+            .lines()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| root.join("Annotations").join(format!("{id}.xml")))
+            .collect();
+
+        Self::with_annotation_files(annotation_files, &root.join("JPEGImages"), classes)
+    }
+
+    /// Create an object detection dataset from all the XML annotation files of a folder.
+    ///
+    /// # Arguments
+    ///
+    /// * `annotations_dir` - Folder containing the XML annotation files.
+    /// * `images_dir` - Folder containing the images referenced by the annotation files.
+    /// * `classes` - The object classes, the label of each class being its index in the list.
+    ///
+    /// # Returns
+    /// A new dataset instance, with the items sorted by annotation file name.
+    pub fn from_annotations_dir<P: AsRef<Path>, Q: AsRef<Path>, S: AsRef<str>>(
+        annotations_dir: P,
+        images_dir: Q,
+        classes: &[S],
+    ) -> Result<Self, VocDatasetError> {
+        let mut annotation_files = fs::read_dir(annotations_dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        annotation_files.retain(|path| path.extension().is_some_and(|ext| ext == "xml"));
+        annotation_files.sort();
+
+        Self::with_annotation_files(annotation_files, images_dir.as_ref(), classes)
+    }
+
+    /// The class names, indexed by label.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn with_annotation_files<S: AsRef<str>>(
+        annotation_files: Vec<PathBuf>,
+        images_dir: &Path,
+        classes: &[S],
+    ) -> Result<Self, VocDatasetError> {
+        let classes = classes
+            .iter()
+            .map(|name| name.as_ref().to_string())
+            .collect::<Vec<_>>();
+
+        let items = annotation_files
+            .into_iter()
+            .map(|path| parse_annotation_file(&path, images_dir, &classes))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { items, classes })
+    }
+}
+
+impl Dataset<ImageDatasetItem> for VocDataset {
+    fn get(&self, index: usize) -> Option<ImageDatasetItem> {
+        let item = self.items.get(index)?;
+        let (image, width, height) = load_image(&item.image_path);
+
+        Some(ImageDatasetItem {
+            image,
+            width,
+            height,
+            annotation: Annotation::BoundingBoxes(item.boxes.clone()),
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// Parse a VOC annotation file into the image path and the bounding boxes of the known classes.
+fn parse_annotation_file(
+    path: &Path,
+    images_dir: &Path,
+    classes: &[String],
+) -> Result<VocItem, VocDatasetError> {
+    let content = fs::read_to_string(path)?;
+    let annotation = XmlParser::new(&content)
+        .parse()
+        .map_err(|err| VocDatasetError::Xml(path.to_path_buf(), err))?;
+    let invalid = |message: String| VocDatasetError::InvalidAnnotation(path.to_path_buf(), message);
+
+    let filename = annotation
+        .child("filename")
+        .ok_or_else(|| invalid("missing `filename`".to_string()))?;
+
+    let mut boxes = Vec::new();
+    for object in annotation.children("object") {
+        let name = object
+            .child("name")
+            .ok_or_else(|| invalid("missing object `name`".to_string()))?;
+        let Some(label) = classes.iter().position(|class| class == name) else {
+            continue;
+        };
+
+        let bndbox = object
+            .find("bndbox")
+            .ok_or_else(|| invalid(format!("missing `bndbox` for object `{name}`")))?;
+        let coordinate = |field: &str| {
+            bndbox
+                .child(field)
+                .and_then(|value| value.parse::<f32>().ok())
+                .ok_or_else(|| invalid(format!("missing or invalid `{field}` for `{name}`")))
+        };
+
+        boxes.push(BoundingBox {
+            coords: [
+                coordinate("xmin")? - 1.0,
+                coordinate("ymin")? - 1.0,
+                coordinate("xmax")?,
+                coordinate("ymax")?,
+            ],
+            label,
+        });
+    }
+
+    Ok(VocItem {
+        image_path: images_dir.join(filename),
+        boxes,
+    })
+}
+
+/// XML element, only keeping what is needed to read annotation files.
+#[derive(Debug, Default, PartialEq)]
+struct XmlElement {
+    name: String,
+    text: String,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn find(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The trimmed text of the first child with the given name.
+    fn child(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|child| child.text.trim())
+    }
+}
+
+/// Minimal XML parser, ignoring attributes, comments, processing instructions and declarations.
+struct XmlParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> XmlParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    /// Parse the root element of the document.
+    fn parse(mut self) -> Result<XmlElement, String> {
+        self.skip_misc()?;
+        let root = self.element()?;
+        self.skip_misc()?;
+
+        if self.position < self.input.len() {
+            return Err("unexpected content after the root element".to_string());
+        }
+
+        Ok(root)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// Skip whitespaces, comments, processing instructions and declarations.
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+
+            if trimmed.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if trimmed.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if trimmed.starts_with("<!") && !trimmed.starts_with("<![CDATA[") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<&'a str, String> {
+        let rest = self.rest();
+        let index = rest
+            .find(end)
+            .ok_or_else(|| format!("missing `{end}` at byte {}", self.position))?;
+        self.position += index + end.len();
+
+        Ok(&rest[..index])
+    }
+
+    fn element(&mut self) -> Result<XmlElement, String> {
+        if !self.rest().starts_with('<') {
+            return Err(format!("expected an element at byte {}", self.position));
+        }
+        self.position += 1;
+
+        let tag = self.skip_until(">")?;
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_end_matches('/')
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| format!("missing element name at byte {}", self.position))?
+            .to_string();
+
+        let mut element = XmlElement {
+            name,
+            ..Default::default()
+        };
+
+        if self_closing {
+            return Ok(element);
+        }
+
+        loop {
+            let rest = self.rest();
+
+            if rest.starts_with("</") {
+                let end = self.skip_until(">")?;
+                if end[2..].trim() != element.name {
+                    return Err(format!(
+                        "`{}` is closed by `{}`",
+                        element.name,
+                        end[2..].trim()
+                    ));
+                }
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let text = self.skip_until("]]>")?;
+                element.text.push_str(text);
+            } else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            } else if rest.starts_with('<') {
+                element.children.push(self.element()?);
+            } else if rest.is_empty() {
+                return Err(format!("`{}` isn't closed", element.name));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&unescape(&rest[..end]));
+                self.position += end;
+            }
+        }
+    }
+}
+
+/// Replace the predefined and numeric character references.
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let character = rest.find(';').and_then(|end| {
+            let character = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                reference => reference
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| reference.strip_prefix('#').map(|dec| dec.parse()))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            character.map(|character| (character, end))
+        });
+
+        match character {
+            Some((character, end)) => {
+                result.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATASET_ROOT: &str = "tests/data/voc";
+
+    fn bbox(coords: [f32; 4], label: usize) -> BoundingBox {
+        BoundingBox { coords, label }
+    }
+
+    #[test]
+    pub fn voc_detection_dataset() {
+        let dataset = VocDataset::new_detection(DATASET_ROOT, "trainval").unwrap();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.get(2), None);
+        assert_eq!(dataset.classes().len(), 20);
+
+        let item = dataset.get(0).unwrap();
+        assert_eq!((item.width, item.height), (6, 4));
+        assert_eq!(item.image.len(), 6 * 4 * 3);
+        // The dog (11) and the difficult person (14).
+        assert_eq!(
+            item.annotation,
+            Annotation::BoundingBoxes(vec![
+                bbox([0.0, 0.0, 3.0, 4.0], 11),
+                bbox([3.5, 1.0, 6.0, 3.0], 14)
+            ])
+        );
+        // The cat (7), the unknown class is ignored.
+        assert_eq!(
+            dataset.get(1).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![bbox([1.0, 0.0, 3.0, 5.0], 7)])
+        );
+
+        let dataset = VocDataset::new_detection(DATASET_ROOT, "val").unwrap();
+        assert_eq!(dataset.len(), 1);
+        assert_eq!((dataset.get(0).unwrap().width), 3);
+    }
+
+    #[test]
+    pub fn voc_dataset_from_annotations_dir() {
+        let dataset = VocDataset::from_annotations_dir(
+            "tests/data/voc/Annotations",
+            "tests/data/voc/JPEGImages",
+            &["cat", "dog", "sheep & wolf"],
+        )
+        .unwrap();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(
+            dataset.get(0).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![bbox([0.0, 0.0, 3.0, 4.0], 1)])
+        );
+        assert_eq!(
+            dataset.get(1).unwrap().annotation,
+            Annotation::BoundingBoxes(vec![
+                bbox([1.0, 0.0, 3.0, 5.0], 0),
+                bbox([0.0, 0.0, 1.0, 1.0], 2)
+            ])
+        );
+    }
+
+    #[test]
+    pub fn voc_dataset_missing_split() {
+        let result = VocDataset::new_detection(DATASET_ROOT, "test");
+
+        assert!(matches!(result, Err(VocDatasetError::Io(_))));
+    }
+
+    #[test]
+    pub fn xml_parser_should_parse_elements() {
+        let xml = r#"<?xml version="1.0"?>
+            <!-- comment -->
+            <annotation verified="yes">
+                <filename>a &lt;b&gt; &#x41;&#66;.jpg</filename>
+                <empty/>
+                <object><name><![CDATA[x<y]]></name></object>
+            </annotation>"#;
+
+        let root = XmlParser::new(xml).parse().unwrap();
+
+        assert_eq!(root.name, "annotation");
+        assert_eq!(root.child("filename"), Some("a <b> AB.jpg"));
+        assert_eq!(root.child("empty"), Some(""));
+        assert_eq!(
+            root.find("object").and_then(|object| object.child("name")),
+            Some("x<y")
+        );
+        assert!(XmlParser::new("<a><b></a>").parse().is_err());
+        assert!(XmlParser::new("<a>").parse().is_err());
+    }
+}
@@ -9,6 +9,7 @@ fn main() {
         .input("tests/add/add.onnx")
         .input("tests/add/add_int.onnx")
         .input("tests/argmax/argmax.onnx")
+        .input("tests/argmin/argmin.onnx")
         .input("tests/avg_pool1d/avg_pool1d.onnx")
         .input("tests/avg_pool2d/avg_pool2d.onnx")
         .input("tests/batch_norm/batch_norm.onnx")
@@ -28,6 +29,7 @@ fn main() {
         .input("tests/div/div.onnx")
         .input("tests/dropout/dropout_opset16.onnx")
         .input("tests/dropout/dropout_opset7.onnx")
+        .input("tests/einsum/einsum.onnx")
         .input("tests/equal/equal.onnx")
         .input("tests/erf/erf.onnx")
         .input("tests/exp/exp.onnx")
@@ -40,13 +42,20 @@ fn main() {
         .input("tests/gather/gather_scalar.onnx")
         .input("tests/gather/gather_shape.onnx")
         .input("tests/gather_elements/gather_elements.onnx")
+        .input("tests/gather_nd/gather_nd.onnx")
         .input("tests/gelu/gelu.onnx")
         .input("tests/global_avr_pool/global_avr_pool.onnx")
+        .input("tests/graph_passes/graph_passes.onnx")
         .input("tests/greater/greater.onnx")
         .input("tests/greater/greater_scalar.onnx")
         .input("tests/greater_or_equal/greater_or_equal.onnx")
         .input("tests/greater_or_equal/greater_or_equal_scalar.onnx")
+        .input("tests/group_norm/group_norm.onnx")
+        .input("tests/gru/gru.onnx")
+        .input("tests/gru/gru_bidirectional.onnx")
         .input("tests/hard_sigmoid/hard_sigmoid.onnx")
+        .input("tests/if_else/if_else.onnx")
+        .input("tests/instance_norm/instance_norm.onnx")
         .input("tests/layer_norm/layer_norm.onnx")
         .input("tests/leaky_relu/leaky_relu.onnx")
         .input("tests/less/less.onnx")
@@ -56,6 +65,9 @@ fn main() {
         .input("tests/linear/linear.onnx")
         .input("tests/log/log.onnx")
         .input("tests/log_softmax/log_softmax.onnx")
+        .input("tests/loop_scan/loop_scan.onnx")
+        .input("tests/lstm/lstm.onnx")
+        .input("tests/lstm/lstm_bidirectional.onnx")
         .input("tests/mask_where/mask_where.onnx")
         .input("tests/mask_where/mask_where_broadcast.onnx")
         .input("tests/mask_where/mask_where_scalar_x.onnx")
@@ -69,11 +81,16 @@ fn main() {
         .input("tests/mean/mean.onnx")
         .input("tests/mul/mul.onnx")
         .input("tests/neg/neg.onnx")
+        .input("tests/non_max_suppression/non_max_suppression.onnx")
+        .input("tests/non_zero/non_zero.onnx")
         .input("tests/not/not.onnx")
+        .input("tests/one_hot/one_hot.onnx")
         .input("tests/pad/pad.onnx")
         .input("tests/pow/pow.onnx")
         .input("tests/pow/pow_int.onnx")
         .input("tests/prelu/prelu.onnx")
+        .input("tests/qdq_conv/qdq_conv.onnx")
+        .input("tests/qlinear_conv_matmul/qlinear_conv_matmul.onnx")
         .input("tests/random_normal/random_normal.onnx")
         .input("tests/random_uniform/random_uniform.onnx")
         .input("tests/range/range.onnx")
@@ -92,12 +109,19 @@ fn main() {
         .input("tests/resize/resize_2d_bicubic_scale.onnx")
         .input("tests/resize/resize_2d_bilinear_scale.onnx")
         .input("tests/resize/resize_2d_nearest_scale.onnx")
+        .input("tests/rnn/rnn.onnx")
+        .input("tests/rnn/rnn_bidirectional.onnx")
+        .input("tests/roi_align/roi_align.onnx")
+        .input("tests/scan/scan.onnx")
+        .input("tests/scatter_elements/scatter_elements.onnx")
+        .input("tests/scatter_nd/scatter_nd.onnx")
         .input("tests/shape/shape.onnx")
         .input("tests/sigmoid/sigmoid.onnx")
         .input("tests/sign/sign.onnx")
         .input("tests/sin/sin.onnx")
         .input("tests/slice/slice.onnx")
         .input("tests/softmax/softmax.onnx")
+        .input("tests/split/split.onnx")
         .input("tests/sqrt/sqrt.onnx")
         .input("tests/squeeze/squeeze_multiple.onnx")
         .input("tests/squeeze/squeeze_opset13.onnx")
@@ -108,6 +132,7 @@ fn main() {
         .input("tests/sum/sum_int.onnx")
         .input("tests/tanh/tanh.onnx")
         .input("tests/tile/tile.onnx")
+        .input("tests/top_k/top_k.onnx")
         .input("tests/trilu/trilu_upper.onnx")
         .input("tests/trilu/trilu_lower.onnx")
         .input("tests/transpose/transpose.onnx")
@@ -0,0 +1,49 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/argmin/argmin.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "ArgMin",
+        inputs=["input"],
+        outputs=["output"],
+        name="/ArgMin",
+        axis=1,
+        keepdims=0,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="ArgMinGraph",
+        inputs=[helper.make_tensor_value_info("input", TensorProto.FLOAT, [2, 3])],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.INT64, [2])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="argmin",
+        opset_imports=[helper.make_opsetid("", 13)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "argmin.onnx")
+
+    # The inputs used in the test
+    x = numpy.array([[1.0, -2.0, 3.0], [4.0, 5.0, -6.0]], dtype=numpy.float32)
+    feeds = {"input": x}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,74 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/einsum/einsum.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    matmul = helper.make_node(
+        "Einsum",
+        inputs=["x", "y"],
+        outputs=["output1"],
+        name="/Einsum",
+        equation="ij,jk->ik",
+    )
+    attention = helper.make_node(
+        "Einsum",
+        inputs=["query", "key"],
+        outputs=["output2"],
+        name="/Einsum_1",
+        equation="bhid,bhjd->bhij",
+    )
+    reduce = helper.make_node(
+        "Einsum",
+        inputs=["query"],
+        outputs=["output3"],
+        name="/Einsum_2",
+        equation="bhid->hd",
+    )
+
+    graph = helper.make_graph(
+        nodes=[matmul, attention, reduce],
+        name="EinsumGraph",
+        inputs=[
+            helper.make_tensor_value_info("x", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("y", TensorProto.FLOAT, [3, 4]),
+            helper.make_tensor_value_info("query", TensorProto.FLOAT, [1, 2, 3, 4]),
+            helper.make_tensor_value_info("key", TensorProto.FLOAT, [1, 2, 2, 4]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output1", TensorProto.FLOAT, [2, 4]),
+            helper.make_tensor_value_info("output2", TensorProto.FLOAT, [1, 2, 3, 2]),
+            helper.make_tensor_value_info("output3", TensorProto.FLOAT, [2, 4]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="einsum",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "einsum.onnx")
+
+    # The inputs used in the test
+    x = numpy.arange(6, dtype=numpy.float32).reshape([2, 3]) * 0.1
+    y = numpy.arange(12, dtype=numpy.float32).reshape([3, 4]) * 0.1
+    query = numpy.arange(24, dtype=numpy.float32).reshape([1, 2, 3, 4]) * 0.1 - 1.0
+    key = numpy.arange(16, dtype=numpy.float32).reshape([1, 2, 2, 4]) * 0.05
+    feeds = {"x": x, "y": y, "query": query, "key": key}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output1", "output2", "output3"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/gather_nd/gather_nd.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "GatherND",
+        inputs=["data", "indices"],
+        outputs=["output"],
+        name="/GatherND",
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="GatherNDGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, [2, 2, 2]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, [2, 2]),
+        ],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [2, 2])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="gather_nd",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "gather_nd.onnx")
+
+    # The inputs used in the test
+    data = numpy.arange(8, dtype=numpy.float32).reshape([2, 2, 2])
+    indices = numpy.array([[0, 1], [1, 0]], dtype=numpy.int64)
+    feeds = {"data": data, "indices": indices}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
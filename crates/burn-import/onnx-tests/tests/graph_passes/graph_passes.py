@@ -0,0 +1,96 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/graph_passes/graph_passes.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [1, 2, 4, 4]
+OUTPUT_SHAPE = [1, 48]
+
+
+def build_model() -> onnx.ModelProto:
+    # A convolution followed by a batch normalization, an identity and a dropout, then flattened
+    # with a target shape computed from the input shape. The unused initializer and the shape
+    # computation are removed by the graph passes.
+    nodes = [
+        helper.make_node(
+            "Conv",
+            inputs=["input", "conv_weight", "conv_bias"],
+            outputs=["conv_out"],
+            name="/Conv",
+            kernel_shape=[1, 1],
+        ),
+        helper.make_node(
+            "BatchNormalization",
+            inputs=["conv_out", "bn_scale", "bn_bias", "bn_mean", "bn_var"],
+            outputs=["bn_out"],
+            name="/BatchNormalization",
+            epsilon=1e-5,
+        ),
+        helper.make_node("Identity", inputs=["bn_out"], outputs=["identity_out"], name="/Identity"),
+        helper.make_node("Dropout", inputs=["identity_out"], outputs=["dropout_out"], name="/Dropout"),
+        helper.make_node("Shape", inputs=["input"], outputs=["shape"], name="/Shape"),
+        helper.make_node(
+            "Gather", inputs=["shape", "batch_index"], outputs=["batch"], name="/Gather", axis=0
+        ),
+        helper.make_node(
+            "Unsqueeze", inputs=["batch", "unsqueeze_axes"], outputs=["batch_1d"], name="/Unsqueeze"
+        ),
+        helper.make_node(
+            "Concat", inputs=["batch_1d", "flat_dim"], outputs=["target_shape"], name="/Concat", axis=0
+        ),
+        helper.make_node(
+            "Reshape", inputs=["dropout_out", "target_shape"], outputs=["output"], name="/Reshape"
+        ),
+    ]
+
+    graph = helper.make_graph(
+        nodes=nodes,
+        name="GraphPassesGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, OUTPUT_SHAPE),
+        ],
+        initializer=[
+            helper.make_tensor(
+                "conv_weight", TensorProto.FLOAT, [3, 2, 1, 1], [1.0, 2.0, 0.5, -1.0, -1.0, 0.25]
+            ),
+            helper.make_tensor("conv_bias", TensorProto.FLOAT, [3], [0.1, 0.2, 0.3]),
+            helper.make_tensor("bn_scale", TensorProto.FLOAT, [3], [1.0, 2.0, 0.5]),
+            helper.make_tensor("bn_bias", TensorProto.FLOAT, [3], [0.0, 1.0, -1.0]),
+            helper.make_tensor("bn_mean", TensorProto.FLOAT, [3], [1.0, 0.0, -0.5]),
+            helper.make_tensor("bn_var", TensorProto.FLOAT, [3], [4.0, 1.0, 0.25]),
+            helper.make_tensor("batch_index", TensorProto.INT64, [], [0]),
+            helper.make_tensor("unsqueeze_axes", TensorProto.INT64, [1], [0]),
+            helper.make_tensor("flat_dim", TensorProto.INT64, [1], [-1]),
+            helper.make_tensor("unused", TensorProto.FLOAT, [2], [1.0, 2.0]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="graph_passes",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "graph_passes.onnx")
+
+    # The input used in the test
+    feeds = {"input": numpy.ones(INPUT_SHAPE, dtype=numpy.float32)}
+
+    (output,) = ReferenceEvaluator(model).run(None, feeds)
+    print(f"Test output shape: {output.shape}")
+    print(f"Test output sum: {output.sum()}")
+
+
+if __name__ == "__main__":
+    main()
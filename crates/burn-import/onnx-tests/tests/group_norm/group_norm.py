@@ -0,0 +1,61 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/group_norm/group_norm.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [2, 4, 3]
+NUM_GROUPS = 2
+
+
+def build_model() -> onnx.ModelProto:
+    # Opset 18 carries one scale and bias value per group
+    node = helper.make_node(
+        "GroupNormalization",
+        inputs=["input", "scale", "bias"],
+        outputs=["output"],
+        name="/GroupNormalization",
+        epsilon=1e-5,
+        num_groups=NUM_GROUPS,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="GroupNormGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        initializer=[
+            helper.make_tensor("scale", TensorProto.FLOAT, [NUM_GROUPS], [1.0, 2.0]),
+            helper.make_tensor("bias", TensorProto.FLOAT, [NUM_GROUPS], [0.5, -0.5]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="group_norm",
+        opset_imports=[helper.make_opsetid("", 18)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "group_norm.onnx")
+
+    # The input used in the test
+    x = numpy.arange(24, dtype=numpy.float32).reshape(INPUT_SHAPE)
+    feeds = {"input": x * x * 0.1 - 1.0}
+
+    (output,) = ReferenceEvaluator(model).run(None, feeds)
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
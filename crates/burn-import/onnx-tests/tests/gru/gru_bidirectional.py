@@ -0,0 +1,80 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/gru/gru_bidirectional.onnx
+
+import random
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+SEQ_LENGTH, BATCH_SIZE, INPUT_SIZE, HIDDEN_SIZE = 3, 2, 4, 5
+NUM_DIRECTIONS, NUM_GATES = 2, 3
+# The batch is the first dimension with layout 1
+X_SHAPE = [BATCH_SIZE, SEQ_LENGTH, INPUT_SIZE]
+STATE_SHAPE = [BATCH_SIZE, NUM_DIRECTIONS, HIDDEN_SIZE]
+Y_SHAPE = [BATCH_SIZE, SEQ_LENGTH, NUM_DIRECTIONS, HIDDEN_SIZE]
+
+
+def random_tensor(rng: random.Random, name: str, dims: list[int]) -> TensorProto:
+    size = int(numpy.prod(dims))
+    values = [rng.uniform(-0.5, 0.5) for _ in range(size)]
+    return helper.make_tensor(name, TensorProto.FLOAT, dims, values)
+
+
+def build_model() -> onnx.ModelProto:
+    rng = random.Random(0)
+    gates = NUM_GATES * HIDDEN_SIZE
+    initializers = [
+        random_tensor(rng, "W", [NUM_DIRECTIONS, gates, INPUT_SIZE]),
+        random_tensor(rng, "R", [NUM_DIRECTIONS, gates, HIDDEN_SIZE]),
+        random_tensor(rng, "B", [NUM_DIRECTIONS, 2 * gates]),
+    ]
+
+    node = helper.make_node(
+        "GRU",
+        inputs=["X", "W", "R", "B"],
+        outputs=["Y", "Y_h"],
+        name="/GRU",
+        hidden_size=HIDDEN_SIZE,
+        direction="bidirectional",
+        layout=1,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="GruBidirectionalGraph",
+        inputs=[
+            helper.make_tensor_value_info("X", TensorProto.FLOAT, X_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("Y", TensorProto.FLOAT, Y_SHAPE),
+            helper.make_tensor_value_info("Y_h", TensorProto.FLOAT, STATE_SHAPE),
+        ],
+        initializer=initializers,
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="gru_bidirectional",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "gru_bidirectional.onnx")
+
+    # The inputs used in the test
+    x = numpy.arange(SEQ_LENGTH * BATCH_SIZE * INPUT_SIZE, dtype=numpy.float32)
+    feeds = {"X": x.reshape(X_SHAPE) * 0.1 - 1.0}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["Y", "Y_h"], outputs):
+        print(f"{name} shape: {output.shape}, sum: {output.sum()}")
+
+
+if __name__ == "__main__":
+    main()
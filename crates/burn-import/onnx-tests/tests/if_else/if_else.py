@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/if_else/if_else.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    # Both branches capture `x` from the outer graph
+    then_branch = helper.make_graph(
+        nodes=[helper.make_node("Add", inputs=["x", "x"], outputs=["then_out"], name="/then/Add")],
+        name="ThenBranch",
+        inputs=[],
+        outputs=[helper.make_tensor_value_info("then_out", TensorProto.FLOAT, [2, 3])],
+    )
+    else_branch = helper.make_graph(
+        nodes=[helper.make_node("Mul", inputs=["x", "x"], outputs=["else_out"], name="/else/Mul")],
+        name="ElseBranch",
+        inputs=[],
+        outputs=[helper.make_tensor_value_info("else_out", TensorProto.FLOAT, [2, 3])],
+    )
+
+    nodes = [
+        helper.make_node(
+            "If",
+            inputs=["cond"],
+            outputs=["branch"],
+            name="/If",
+            then_branch=then_branch,
+            else_branch=else_branch,
+        ),
+        # `x` is still used after the If node
+        helper.make_node("Sub", inputs=["branch", "x"], outputs=["output"], name="/Sub"),
+    ]
+
+    graph = helper.make_graph(
+        nodes=nodes,
+        name="IfElseGraph",
+        inputs=[
+            helper.make_tensor_value_info("cond", TensorProto.BOOL, []),
+            helper.make_tensor_value_info("x", TensorProto.FLOAT, [2, 3]),
+        ],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [2, 3])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="if_else",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "if_else.onnx")
+
+    # The inputs used in the test
+    x = numpy.arange(6, dtype=numpy.float32).reshape(2, 3)
+    for cond in [True, False]:
+        feeds = {"cond": numpy.array(cond), "x": x}
+        outputs = ReferenceEvaluator(model).run(None, feeds)
+        print(f"cond={cond} output: {outputs[0]}")
+
+
+if __name__ == "__main__":
+    main()
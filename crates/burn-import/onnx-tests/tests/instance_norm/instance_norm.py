@@ -0,0 +1,58 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/instance_norm/instance_norm.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [2, 3, 4]
+
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "InstanceNormalization",
+        inputs=["input", "scale", "bias"],
+        outputs=["output"],
+        name="/InstanceNormalization",
+        epsilon=1e-5,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="InstanceNormGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        initializer=[
+            helper.make_tensor("scale", TensorProto.FLOAT, [3], [1.0, 0.5, 2.0]),
+            helper.make_tensor("bias", TensorProto.FLOAT, [3], [0.0, 0.1, -0.2]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="instance_norm",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "instance_norm.onnx")
+
+    # The input used in the test
+    x = numpy.arange(24, dtype=numpy.float32).reshape(INPUT_SHAPE)
+    feeds = {"input": x * x * 0.1 - 1.0}
+
+    (output,) = ReferenceEvaluator(model).run(None, feeds)
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,80 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/loop_scan/loop_scan.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    # Body inputs: iteration number, condition and the loop-carried accumulator.
+    # Body outputs: condition, the updated accumulator and a per-iteration scan output.
+    body = helper.make_graph(
+        nodes=[
+            helper.make_node("Identity", inputs=["cond_in"], outputs=["cond_out"], name="/body/Identity"),
+            # `x` is captured from the outer graph
+            helper.make_node("Add", inputs=["acc_in", "x"], outputs=["acc_out"], name="/body/Add"),
+            helper.make_node("Mul", inputs=["acc_out", "x"], outputs=["scan_out"], name="/body/Mul"),
+        ],
+        name="LoopBody",
+        inputs=[
+            helper.make_tensor_value_info("iter", TensorProto.INT64, []),
+            helper.make_tensor_value_info("cond_in", TensorProto.BOOL, []),
+            helper.make_tensor_value_info("acc_in", TensorProto.FLOAT, [2, 3]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("cond_out", TensorProto.BOOL, []),
+            helper.make_tensor_value_info("acc_out", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("scan_out", TensorProto.FLOAT, [2, 3]),
+        ],
+    )
+
+    nodes = [
+        helper.make_node(
+            "Loop",
+            inputs=["trip_count", "", "x"],
+            outputs=["acc", "scan"],
+            name="/Loop",
+            body=body,
+        ),
+    ]
+
+    graph = helper.make_graph(
+        nodes=nodes,
+        name="LoopScanGraph",
+        inputs=[
+            helper.make_tensor_value_info("trip_count", TensorProto.INT64, []),
+            helper.make_tensor_value_info("x", TensorProto.FLOAT, [2, 3]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("acc", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("scan", TensorProto.FLOAT, [3, 2, 3]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="loop_scan",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "loop_scan.onnx")
+
+    # The inputs used in the test
+    feeds = {
+        "trip_count": numpy.array(3, dtype=numpy.int64),
+        "x": numpy.arange(6, dtype=numpy.float32).reshape(2, 3) * 0.5,
+    }
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["acc", "scan"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,77 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/non_max_suppression/non_max_suppression.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+NUM_BOXES = 6
+
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "NonMaxSuppression",
+        inputs=[
+            "boxes",
+            "scores",
+            "max_output_boxes_per_class",
+            "iou_threshold",
+            "score_threshold",
+        ],
+        outputs=["selected_indices"],
+        name="/NonMaxSuppression",
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="NonMaxSuppressionGraph",
+        inputs=[
+            helper.make_tensor_value_info("boxes", TensorProto.FLOAT, [1, NUM_BOXES, 4]),
+            helper.make_tensor_value_info("scores", TensorProto.FLOAT, [1, 1, NUM_BOXES]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("selected_indices", TensorProto.INT64, [3, 3]),
+        ],
+        initializer=[
+            helper.make_tensor("max_output_boxes_per_class", TensorProto.INT64, [1], [3]),
+            helper.make_tensor("iou_threshold", TensorProto.FLOAT, [1], [0.5]),
+            helper.make_tensor("score_threshold", TensorProto.FLOAT, [1], [0.0]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="non_max_suppression",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "non_max_suppression.onnx")
+
+    # The inputs used in the test
+    boxes = numpy.array(
+        [
+            [
+                [0.0, 0.0, 1.0, 1.0],
+                [0.0, 0.1, 1.0, 1.1],
+                [0.0, -0.1, 1.0, 0.9],
+                [0.0, 10.0, 1.0, 11.0],
+                [0.0, 10.1, 1.0, 11.1],
+                [0.0, 100.0, 1.0, 101.0],
+            ]
+        ],
+        dtype=numpy.float32,
+    )
+    scores = numpy.array([[[0.9, 0.75, 0.6, 0.95, 0.5, 0.3]]], dtype=numpy.float32)
+
+    (output,) = ReferenceEvaluator(model).run(None, {"boxes": boxes, "scores": scores})
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,48 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/non_zero/non_zero.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "NonZero",
+        inputs=["input"],
+        outputs=["output"],
+        name="/NonZero",
+    )
+
+    # The number of non-zero elements is only known at runtime
+    graph = helper.make_graph(
+        nodes=[node],
+        name="NonZeroGraph",
+        inputs=[helper.make_tensor_value_info("input", TensorProto.FLOAT, [2, 3])],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.INT64, [2, 3])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="non_zero",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "non_zero.onnx")
+
+    # The inputs used in the test
+    x = numpy.array([[1.0, 0.0, 2.0], [0.0, 3.0, 0.0]], dtype=numpy.float32)
+    feeds = {"input": x}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/one_hot/one_hot.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "OneHot",
+        inputs=["indices", "depth", "values"],
+        outputs=["output"],
+        name="/OneHot",
+        axis=0,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="OneHotGraph",
+        inputs=[helper.make_tensor_value_info("indices", TensorProto.INT64, [3])],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [4, 3])],
+        initializer=[
+            helper.make_tensor("depth", TensorProto.INT64, [1], [4]),
+            helper.make_tensor("values", TensorProto.FLOAT, [2], [0.5, 2.0]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="one_hot",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "one_hot.onnx")
+
+    # The inputs used in the test
+    # Negative indices count from the end
+    indices = numpy.array([1, -1, 0], dtype=numpy.int64)
+    feeds = {"indices": indices}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,103 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/qdq_conv/qdq_conv.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [1, 1, 2, 2]
+OUTPUT_SHAPE = [1, 2, 2, 2]
+
+
+def build_model() -> onnx.ModelProto:
+    # A convolution in the QDQ format: the input and output are quantized and dequantized, and the
+    # weights and bias are stored quantized and dequantized before the convolution.
+    nodes = [
+        helper.make_node(
+            "QuantizeLinear",
+            inputs=["input", "x_scale", "x_zero_point"],
+            outputs=["x_q"],
+            name="/QuantizeLinear",
+        ),
+        helper.make_node(
+            "DequantizeLinear",
+            inputs=["x_q", "x_scale", "x_zero_point"],
+            outputs=["x_dq"],
+            name="/DequantizeLinear",
+        ),
+        helper.make_node(
+            "DequantizeLinear",
+            inputs=["w_q", "w_scale", "w_zero_point"],
+            outputs=["w_dq"],
+            name="/DequantizeLinear_1",
+        ),
+        helper.make_node(
+            "DequantizeLinear", inputs=["b_q", "b_scale"], outputs=["b_dq"], name="/DequantizeLinear_2"
+        ),
+        helper.make_node(
+            "Conv",
+            inputs=["x_dq", "w_dq", "b_dq"],
+            outputs=["conv_out"],
+            name="/Conv",
+            kernel_shape=[1, 1],
+        ),
+        helper.make_node(
+            "QuantizeLinear",
+            inputs=["conv_out", "y_scale", "y_zero_point"],
+            outputs=["y_q"],
+            name="/QuantizeLinear_1",
+        ),
+        helper.make_node(
+            "DequantizeLinear",
+            inputs=["y_q", "y_scale", "y_zero_point"],
+            outputs=["output"],
+            name="/DequantizeLinear_3",
+        ),
+    ]
+
+    graph = helper.make_graph(
+        nodes=nodes,
+        name="QdqConvGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, OUTPUT_SHAPE),
+        ],
+        initializer=[
+            helper.make_tensor("x_scale", TensorProto.FLOAT, [], [0.5]),
+            helper.make_tensor("x_zero_point", TensorProto.UINT8, [], [130]),
+            helper.make_tensor("w_q", TensorProto.INT8, [2, 1, 1, 1], [4, -2]),
+            helper.make_tensor("w_scale", TensorProto.FLOAT, [], [0.25]),
+            helper.make_tensor("w_zero_point", TensorProto.INT8, [], [0]),
+            # The bias scale is the product of the input and weight scales
+            helper.make_tensor("b_q", TensorProto.INT32, [2], [3, -1]),
+            helper.make_tensor("b_scale", TensorProto.FLOAT, [], [0.125]),
+            helper.make_tensor("y_scale", TensorProto.FLOAT, [], [0.3]),
+            helper.make_tensor("y_zero_point", TensorProto.UINT8, [], [120]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="qdq_conv",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "qdq_conv.onnx")
+
+    # The input used in the test
+    feeds = {"input": numpy.array([[[[1.0, -1.2], [2.3, 0.6]]]], dtype=numpy.float32)}
+
+    (output,) = ReferenceEvaluator(model).run(None, feeds)
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,110 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/qlinear_conv_matmul/qlinear_conv_matmul.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [1, 1, 2, 2]
+OUTPUT_SHAPE = [1, 2, 2, 2]
+
+
+def build_model() -> onnx.ModelProto:
+    # The input is quantized, goes through a quantized convolution and a quantized matmul with
+    # quantized weights, and the output is dequantized.
+    nodes = [
+        helper.make_node(
+            "QuantizeLinear",
+            inputs=["input", "x_scale", "x_zero_point"],
+            outputs=["x_q"],
+            name="/QuantizeLinear",
+        ),
+        helper.make_node(
+            "QLinearConv",
+            inputs=[
+                "x_q",
+                "x_scale",
+                "x_zero_point",
+                "w_q",
+                "w_scale",
+                "w_zero_point",
+                "conv_scale",
+                "conv_zero_point",
+                "b_q",
+            ],
+            outputs=["conv_q"],
+            name="/QLinearConv",
+            kernel_shape=[1, 1],
+        ),
+        helper.make_node(
+            "QLinearMatMul",
+            inputs=[
+                "conv_q",
+                "conv_scale",
+                "conv_zero_point",
+                "m_q",
+                "m_scale",
+                "m_zero_point",
+                "y_scale",
+                "y_zero_point",
+            ],
+            outputs=["y_q"],
+            name="/QLinearMatMul",
+        ),
+        helper.make_node(
+            "DequantizeLinear",
+            inputs=["y_q", "y_scale", "y_zero_point"],
+            outputs=["output"],
+            name="/DequantizeLinear",
+        ),
+    ]
+
+    graph = helper.make_graph(
+        nodes=nodes,
+        name="QLinearConvMatMulGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, OUTPUT_SHAPE),
+        ],
+        initializer=[
+            helper.make_tensor("x_scale", TensorProto.FLOAT, [], [0.5]),
+            helper.make_tensor("x_zero_point", TensorProto.UINT8, [], [130]),
+            helper.make_tensor("w_q", TensorProto.INT8, [2, 1, 1, 1], [4, -2]),
+            helper.make_tensor("w_scale", TensorProto.FLOAT, [], [0.25]),
+            helper.make_tensor("w_zero_point", TensorProto.INT8, [], [0]),
+            helper.make_tensor("b_q", TensorProto.INT32, [2], [3, -1]),
+            helper.make_tensor("conv_scale", TensorProto.FLOAT, [], [0.3]),
+            helper.make_tensor("conv_zero_point", TensorProto.UINT8, [], [120]),
+            helper.make_tensor("m_q", TensorProto.UINT8, [2, 2], [130, 126, 129, 128]),
+            helper.make_tensor("m_scale", TensorProto.FLOAT, [], [0.5]),
+            helper.make_tensor("m_zero_point", TensorProto.UINT8, [], [128]),
+            helper.make_tensor("y_scale", TensorProto.FLOAT, [], [0.25]),
+            helper.make_tensor("y_zero_point", TensorProto.INT8, [], [0]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="qlinear_conv_matmul",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "qlinear_conv_matmul.onnx")
+
+    # The input used in the test
+    feeds = {"input": numpy.array([[[[1.0, -1.2], [2.3, 0.6]]]], dtype=numpy.float32)}
+
+    (output,) = ReferenceEvaluator(model).run(None, feeds)
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,65 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/roi_align/roi_align.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [2, 2, 4, 5]
+NUM_ROIS = 2
+
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "RoiAlign",
+        inputs=["input", "rois", "batch_indices"],
+        outputs=["output"],
+        name="/RoiAlign",
+        coordinate_transformation_mode="half_pixel",
+        mode="avg",
+        output_height=2,
+        output_width=2,
+        sampling_ratio=2,
+        spatial_scale=1.0,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="RoiAlignGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+            helper.make_tensor_value_info("rois", TensorProto.FLOAT, [NUM_ROIS, 4]),
+            helper.make_tensor_value_info("batch_indices", TensorProto.INT64, [NUM_ROIS]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [NUM_ROIS, 2, 2, 2]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="roi_align",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "roi_align.onnx")
+
+    # The inputs used in the test
+    x = numpy.arange(80, dtype=numpy.float32).reshape(INPUT_SHAPE) * 0.1 - 1.0
+    rois = numpy.array([[0.0, 0.0, 3.0, 2.0], [1.0, 0.5, 4.0, 3.5]], dtype=numpy.float32)
+    batch_indices = numpy.array([0, 1], dtype=numpy.int64)
+
+    (output,) = ReferenceEvaluator(model).run(
+        None, {"input": x, "rois": rois, "batch_indices": batch_indices}
+    )
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,75 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/scan/scan.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    # Cumulative sum over the first axis: the state carries the running sum and
+    # the scan output collects it for every step.
+    body = helper.make_graph(
+        nodes=[
+            helper.make_node("Add", inputs=["sum_in", "row"], outputs=["sum_out"], name="/body/Add"),
+            helper.make_node("Mul", inputs=["sum_out", "row"], outputs=["scan_out"], name="/body/Mul"),
+        ],
+        name="ScanBody",
+        inputs=[
+            helper.make_tensor_value_info("sum_in", TensorProto.FLOAT, [2]),
+            helper.make_tensor_value_info("row", TensorProto.FLOAT, [2]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("sum_out", TensorProto.FLOAT, [2]),
+            helper.make_tensor_value_info("scan_out", TensorProto.FLOAT, [2]),
+        ],
+    )
+
+    node = helper.make_node(
+        "Scan",
+        inputs=["initial", "xs"],
+        outputs=["sum", "ys"],
+        name="/Scan",
+        num_scan_inputs=1,
+        body=body,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="ScanGraph",
+        inputs=[
+            helper.make_tensor_value_info("initial", TensorProto.FLOAT, [2]),
+            helper.make_tensor_value_info("xs", TensorProto.FLOAT, [3, 2]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("sum", TensorProto.FLOAT, [2]),
+            helper.make_tensor_value_info("ys", TensorProto.FLOAT, [3, 2]),
+        ],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="scan",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "scan.onnx")
+
+    # The inputs used in the test
+    feeds = {
+        "initial": numpy.array([1.0, -1.0], dtype=numpy.float32),
+        "xs": numpy.arange(6, dtype=numpy.float32).reshape(3, 2),
+    }
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["sum", "ys"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
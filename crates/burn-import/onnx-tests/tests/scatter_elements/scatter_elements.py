@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/scatter_elements/scatter_elements.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "ScatterElements",
+        inputs=["data", "indices", "updates"],
+        outputs=["output"],
+        name="/ScatterElements",
+        axis=0,
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="ScatterElementsGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, [3, 3]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, [2, 3]),
+            helper.make_tensor_value_info("updates", TensorProto.FLOAT, [2, 3]),
+        ],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [3, 3])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="scatter_elements",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "scatter_elements.onnx")
+
+    # The inputs used in the test
+    data = numpy.arange(9, dtype=numpy.float32).reshape([3, 3])
+    indices = numpy.array([[1, 0, 2], [0, 2, 1]], dtype=numpy.int64)
+    updates = numpy.array([[1.0, 1.1, 1.2], [2.0, 2.1, 2.2]], dtype=numpy.float32)
+    feeds = {"data": data, "indices": indices, "updates": updates}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
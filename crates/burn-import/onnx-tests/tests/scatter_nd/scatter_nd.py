@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/scatter_nd/scatter_nd.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    node = helper.make_node(
+        "ScatterND",
+        inputs=["data", "indices", "updates"],
+        outputs=["output"],
+        name="/ScatterND",
+    )
+
+    graph = helper.make_graph(
+        nodes=[node],
+        name="ScatterNDGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, [4, 2]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, [2, 1]),
+            helper.make_tensor_value_info("updates", TensorProto.FLOAT, [2, 2]),
+        ],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [4, 2])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="scatter_nd",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "scatter_nd.onnx")
+
+    # The inputs used in the test
+    data = numpy.arange(8, dtype=numpy.float32).reshape([4, 2])
+    indices = numpy.array([[3], [1]], dtype=numpy.int64)
+    updates = numpy.array([[10.0, 11.0], [12.0, 13.0]], dtype=numpy.float32)
+    feeds = {"data": data, "indices": indices, "updates": updates}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
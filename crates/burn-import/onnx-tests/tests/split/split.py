@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/split/split.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+def build_model() -> onnx.ModelProto:
+    # Explicit split sizes, given as an input since opset 13
+    split_sizes = helper.make_node(
+        "Split",
+        inputs=["input", "split"],
+        outputs=["output1", "output2"],
+        name="/Split",
+        axis=1,
+    )
+    # Equal chunks, the number of outputs being an attribute since opset 18
+    split_chunks = helper.make_node(
+        "Split",
+        inputs=["input"],
+        outputs=["output3", "output4"],
+        name="/Split_1",
+        axis=0,
+        num_outputs=2,
+    )
+
+    graph = helper.make_graph(
+        nodes=[split_sizes, split_chunks],
+        name="SplitGraph",
+        inputs=[helper.make_tensor_value_info("input", TensorProto.FLOAT, [2, 5])],
+        outputs=[
+            helper.make_tensor_value_info("output1", TensorProto.FLOAT, [2, 2]),
+            helper.make_tensor_value_info("output2", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("output3", TensorProto.FLOAT, [1, 5]),
+            helper.make_tensor_value_info("output4", TensorProto.FLOAT, [1, 5]),
+        ],
+        initializer=[helper.make_tensor("split", TensorProto.INT64, [2], [2, 3])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="split",
+        opset_imports=[helper.make_opsetid("", 18)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "split.onnx")
+
+    # The inputs used in the test
+    x = numpy.arange(10, dtype=numpy.float32).reshape([2, 5])
+    feeds = {"input": x}
+
+    outputs = ReferenceEvaluator(model).run(None, feeds)
+    for name, output in zip(["output1", "output2", "output3", "output4"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,143 @@
+// This test suite verifies that the modules exported to ONNX compute the same outputs as the
+// traced forward pass. Each exported model is loaded back with the runtime, so the test covers
+// both the graph and the initializers.
+
+use burn::{
+    module::Module,
+    nn::{
+        conv::{Conv2d, Conv2dConfig},
+        pool::{AdaptiveAvgPool2d, AdaptiveAvgPool2dConfig, MaxPool2d, MaxPool2dConfig},
+        Linear, LinearConfig, Relu,
+    },
+    tensor::{activation::softmax, backend::Backend, Int, Tensor},
+};
+
+#[derive(Module, Debug)]
+struct Mlp<B: Backend> {
+    linear1: Linear<B>,
+    linear2: Linear<B>,
+    activation: Relu,
+}
+
+impl<B: Backend> Mlp<B> {
+    fn new(device: &B::Device) -> Self {
+        Self {
+            linear1: LinearConfig::new(4, 8).init(device),
+            linear2: LinearConfig::new(8, 3).init(device),
+            activation: Relu::new(),
+        }
+    }
+
+    fn forward(&self, x: Tensor<B, 2>) -> Tensor<B, 2> {
+        let x = self.activation.forward(self.linear1.forward(x));
+        softmax(self.linear2.forward(x), 1)
+    }
+}
+
+#[derive(Module, Debug)]
+struct ConvNet<B: Backend> {
+    conv: Conv2d<B>,
+    max_pool: MaxPool2d,
+    avg_pool: AdaptiveAvgPool2d,
+}
+
+impl<B: Backend> ConvNet<B> {
+    fn new(device: &B::Device) -> Self {
+        Self {
+            conv: Conv2dConfig::new([2, 4], [3, 3]).init(device),
+            max_pool: MaxPool2dConfig::new([2, 2]).with_strides([2, 2]).init(),
+            avg_pool: AdaptiveAvgPool2dConfig::new([1, 1]).init(),
+        }
+    }
+
+    fn forward(&self, x: Tensor<B, 4>) -> Tensor<B, 2> {
+        let x = self.max_pool.forward(self.conv.forward(x));
+        self.avg_pool.forward(x).flatten(1, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use burn::tensor::TensorData;
+    use burn_import::onnx::{
+        export::{Error, OnnxExporter, OnnxTrace},
+        runtime::{OnnxModel, Value},
+    };
+
+    type Backend = burn_ndarray::NdArray<f32>;
+    type TraceBackend = OnnxTrace<Backend>;
+
+    /// Loads the exported model with the runtime and evaluates it.
+    fn run_exported(bytes: &[u8], name: &str, input: Value<Backend>) -> Vec<TensorData> {
+        let path = std::env::temp_dir().join(format!("burn_export_{name}.onnx"));
+        std::fs::write(&path, bytes).unwrap();
+
+        let model = OnnxModel::<Backend>::from_file(&path, &Default::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        model
+            .forward(vec![input])
+            .unwrap()
+            .into_iter()
+            .map(Value::into_data)
+            .collect()
+    }
+
+    #[test]
+    fn export_mlp() {
+        let device = Default::default();
+        let model = Mlp::<TraceBackend>::new(&device);
+        let input = Tensor::<TraceBackend, 1, Int>::arange(0..8, &device)
+            .float()
+            .reshape([2, 4])
+            .sub_scalar(4.0);
+
+        let mut exporter = OnnxExporter::<Backend>::new(&device);
+        exporter.module(&model).input("input", &input);
+        let expected = model.forward(input.clone());
+        let bytes = exporter.output("output", &expected).to_bytes().unwrap();
+        drop(exporter);
+
+        let input = Tensor::<Backend, 2>::from_data(input.to_data(), &Default::default());
+        let output = run_exported(&bytes, "mlp", input.into());
+
+        output[0].assert_approx_eq(&expected.to_data(), 5);
+    }
+
+    #[test]
+    fn export_conv() {
+        let device = Default::default();
+        let model = ConvNet::<TraceBackend>::new(&device);
+        let input = Tensor::<TraceBackend, 1, Int>::arange(0..200, &device)
+            .float()
+            .div_scalar(200.0)
+            .reshape([1, 2, 10, 10]);
+
+        let mut exporter = OnnxExporter::<Backend>::new(&device);
+        exporter.module(&model).input("input", &input);
+        let expected = model.forward(input.clone());
+        let bytes = exporter.output("output", &expected).to_bytes().unwrap();
+        drop(exporter);
+
+        let input = Tensor::<Backend, 4>::from_data(input.to_data(), &Default::default());
+        let output = run_exported(&bytes, "conv", input.into());
+
+        output[0].assert_approx_eq(&expected.to_data(), 5);
+    }
+
+    #[test]
+    fn export_unsupported_operation() {
+        let device = Default::default();
+        let input = Tensor::<TraceBackend, 2>::zeros([2, 2], &device);
+
+        let mut exporter = OnnxExporter::<Backend>::new(&device);
+        exporter.input("input", &input);
+        let value = Tensor::<TraceBackend, 2>::ones([1, 2], &device);
+        let output = input.slice_assign([0..1, 0..2], value);
+        let result = exporter.output("output", &output).to_bytes();
+
+        assert!(matches!(result, Err(Error::UnsupportedOperation(op)) if op == "SliceAssign"));
+    }
+}
@@ -18,6 +18,7 @@ include_models!(
     add,
     add_int,
     argmax,
+    argmin,
     avg_pool1d,
     avg_pool2d,
     batch_norm,
@@ -37,6 +38,7 @@ include_models!(
     div,
     dropout_opset16,
     dropout_opset7,
+    einsum,
     equal,
     erf,
     exp,
@@ -49,13 +51,20 @@ include_models!(
     gather_scalar,
     gather_shape,
     gather_elements,
+    gather_nd,
     gelu,
     global_avr_pool,
+    graph_passes,
     greater,
     greater_scalar,
     greater_or_equal,
     greater_or_equal_scalar,
+    group_norm,
+    gru,
+    gru_bidirectional,
     hard_sigmoid,
+    if_else,
+    instance_norm,
     layer_norm,
     leaky_relu,
     less,
@@ -65,6 +74,9 @@ include_models!(
     linear,
     log,
     log_softmax,
+    loop_scan,
+    lstm,
+    lstm_bidirectional,
     mask_where,
     mask_where_broadcast,
     mask_where_scalar_x,
@@ -78,11 +90,16 @@ include_models!(
     mean,
     mul,
     neg,
+    non_max_suppression,
+    non_zero,
     not,
+    one_hot,
     pad,
     pow,
     pow_int,
     prelu,
+    qdq_conv,
+    qlinear_conv_matmul,
     random_normal,
     random_uniform,
     range,
@@ -101,12 +118,19 @@ include_models!(
     resize_2d_bicubic_scale,
     resize_2d_bilinear_scale,
     resize_2d_nearest_scale,
+    rnn,
+    rnn_bidirectional,
+    roi_align,
+    scan,
+    scatter_elements,
+    scatter_nd,
     shape,
     sigmoid,
     sign,
     sin,
     slice,
     softmax,
+    split,
     sqrt,
     squeeze_multiple,
     squeeze_opset13,
@@ -117,6 +141,7 @@ include_models!(
     sum_int,
     tanh,
     tile,
+    top_k,
     trilu_upper,
     trilu_lower,
     transpose,
@@ -131,7 +156,8 @@ mod tests {
 
     use super::*;
 
-    use burn::tensor::{Bool, Int, Shape, Tensor, TensorData};
+    use burn::module::Module;
+    use burn::tensor::{Bool, DType, Int, Shape, Tensor, TensorData};
 
     use float_cmp::ApproxEq;
 
@@ -1231,6 +1257,43 @@ mod tests {
         output.to_data().assert_eq(&expected, true);
     }
 
+    #[test]
+    fn qdq_conv() {
+        let device = Default::default();
+        let model: qdq_conv::Model<Backend> = qdq_conv::Model::default();
+
+        // The dequantized weights are stored quantized in the record
+        let record = model.clone().into_record();
+        let weight = record.conv2d1.weight.val().to_data();
+        assert!(matches!(weight.dtype, DType::QFloat(_)));
+
+        let input = Tensor::<Backend, 4>::from_floats([[[[1.0, -1.2], [2.3, 0.6]]]], &device);
+        let output = model.forward(input);
+        let expected =
+            TensorData::from([[[[1.5f32, -0.6], [3.0, 0.9]], [[-0.6, 0.3], [-1.5, -0.3]]]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
+    #[test]
+    fn qlinear_conv_matmul() {
+        let device = Default::default();
+        let model: qlinear_conv_matmul::Model<Backend> = qlinear_conv_matmul::Model::default();
+
+        let record = model.clone().into_record();
+        let weight = record.qlinearconv1.weight.val().to_data();
+        assert!(matches!(weight.dtype, DType::QFloat(_)));
+        let weight = record.qlinearmatmul1.weight.val().to_data();
+        assert!(matches!(weight.dtype, DType::QFloat(_)));
+
+        let input = Tensor::<Backend, 4>::from_floats([[[[1.0, -1.2], [2.3, 0.6]]]], &device);
+        let output = model.forward(input);
+        let expected =
+            TensorData::from([[[[1.25f32, -1.5], [3.5, -3.0]], [[-0.5, 0.5], [-1.75, 1.5]]]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
     #[test]
     fn relu() {
         // Initialize the model without weights (because the exported file does not contain them)
@@ -1466,6 +1529,526 @@ mod tests {
         assert!(expected_sum3.approx_eq(output_sum3, (1.0e-6, 2)));
     }
 
+    /// Create the deterministic inputs of the recurrent models: `arange(n) * scale - offset`.
+    fn recurrent_input(shape: [usize; 3], scale: f32, offset: f32) -> Tensor<Backend, 3> {
+        let size = shape.iter().product::<usize>() as i64;
+
+        Tensor::<Backend, 1, Int>::arange(0..size, &Default::default())
+            .float()
+            .reshape(shape)
+            .mul_scalar(scale)
+            .sub_scalar(offset)
+    }
+
+    #[test]
+    fn lstm() {
+        // Initialize the model with weights (loaded from the exported file)
+        let model: lstm::Model<Backend> = lstm::Model::default();
+        let input = recurrent_input([3, 2, 4], 0.1, 1.0);
+
+        let (output_y, output_y_h, output_y_c) = model.forward(input);
+
+        assert_eq!(output_y.shape(), Shape::from([3, 1, 2, 5]));
+        assert_eq!(output_y_h.shape(), Shape::from([1, 2, 5]));
+        assert_eq!(output_y_c.shape(), Shape::from([1, 2, 5]));
+
+        // The expected sums are computed with the ONNX reference implementation
+        let output_sum = output_y.sum().into_scalar();
+        assert!(1.280_293.approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!(0.777_818.approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_c.sum().into_scalar();
+        assert!(2.123_219.approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    #[test]
+    fn lstm_bidirectional() {
+        let model: lstm_bidirectional::Model<Backend> = lstm_bidirectional::Model::default();
+        let input = recurrent_input([3, 2, 4], 0.1, 1.0);
+        let initial_h = recurrent_input([2, 2, 5], 0.05, 0.5);
+        let initial_c = recurrent_input([2, 2, 5], 0.05, 0.2);
+
+        let (output_y, output_y_h, output_y_c) = model.forward(input, initial_h, initial_c);
+
+        assert_eq!(output_y.shape(), Shape::from([3, 2, 2, 5]));
+        assert_eq!(output_y_h.shape(), Shape::from([2, 2, 5]));
+        assert_eq!(output_y_c.shape(), Shape::from([2, 2, 5]));
+
+        let output_sum = output_y.sum().into_scalar();
+        assert!(2.667_848.approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!(1.344_197.approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_c.sum().into_scalar();
+        assert!(2.544_643.approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    #[test]
+    fn gru() {
+        // The model applies the linear transformation before the reset gate
+        let model: gru::Model<Backend> = gru::Model::default();
+        let input = recurrent_input([3, 2, 4], 0.1, 1.0);
+        let initial_h = recurrent_input([1, 2, 5], 0.05, 0.5);
+
+        let (output_y, output_y_h) = model.forward(input, initial_h);
+
+        assert_eq!(output_y.shape(), Shape::from([3, 1, 2, 5]));
+        assert_eq!(output_y_h.shape(), Shape::from([1, 2, 5]));
+
+        let output_sum = output_y.sum().into_scalar();
+        assert!(1.056_987.approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!(0.673_972.approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    #[test]
+    fn gru_bidirectional() {
+        // The batch is the first dimension of the inputs and outputs (layout 1)
+        let model: gru_bidirectional::Model<Backend> = gru_bidirectional::Model::default();
+        let input = recurrent_input([2, 3, 4], 0.1, 1.0);
+
+        let (output_y, output_y_h) = model.forward(input);
+
+        assert_eq!(output_y.shape(), Shape::from([2, 3, 2, 5]));
+        assert_eq!(output_y_h.shape(), Shape::from([2, 2, 5]));
+
+        let output_sum = output_y.sum().into_scalar();
+        assert!((-0.499_598).approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!((-0.223_045).approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    #[test]
+    fn rnn() {
+        // The model processes the sequence in reverse
+        let model: rnn::Model<Backend> = rnn::Model::default();
+        let input = recurrent_input([3, 2, 4], 0.1, 1.0);
+
+        let (output_y, output_y_h) = model.forward(input);
+
+        assert_eq!(output_y.shape(), Shape::from([3, 1, 2, 5]));
+        assert_eq!(output_y_h.shape(), Shape::from([1, 2, 5]));
+
+        let output_sum = output_y.sum().into_scalar();
+        assert!((-5.283_99).approx_eq(output_sum, (1.0e-4, 2)));
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!((-5.267_826).approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    #[test]
+    fn rnn_bidirectional() {
+        // Only the final hidden state is an output of the model
+        let model: rnn_bidirectional::Model<Backend> = rnn_bidirectional::Model::default();
+        let input = recurrent_input([3, 2, 4], 0.1, 1.0);
+        let initial_h = recurrent_input([2, 2, 5], 0.05, 0.5);
+
+        let output_y_h = model.forward(input, initial_h);
+
+        assert_eq!(output_y_h.shape(), Shape::from([2, 2, 5]));
+
+        let output_sum = output_y_h.sum().into_scalar();
+        assert!(4.395_761.approx_eq(output_sum, (1.0e-4, 2)));
+    }
+
+    /// Returns `x * x * 0.1 - 1.0` for `x` in `0..24`, the input used by the normalization tests.
+    fn norm_input<const D: usize>(shape: [usize; D]) -> Tensor<Backend, D> {
+        let device = Default::default();
+        let input = Tensor::<Backend, 1, Int>::arange(0..24, &device)
+            .float()
+            .reshape(shape);
+
+        input.clone().mul(input).mul_scalar(0.1).sub_scalar(1.0)
+    }
+
+    #[test]
+    fn instance_norm() {
+        let model: instance_norm::Model<Backend> = instance_norm::Model::default();
+
+        let output = model.forward(norm_input([2, 3, 4]));
+        let expected = TensorData::from([
+            [
+                [-1.0f32, -0.7143, 0.1429, 1.5714],
+                [-0.5281, -0.1634, 0.2823, 0.8091],
+                [-2.7863, -1.1875, 0.5994, 2.5744],
+            ],
+            [
+                [-1.3078, -0.4801, 0.4139, 1.374],
+                [-0.5578, -0.1363, 0.3108, 0.7834],
+                [-2.8411, -1.1358, 0.6526, 2.5243],
+            ],
+        ]);
+
+        output.to_data().assert_approx_eq(&expected, 3);
+    }
+
+    #[test]
+    fn group_norm() {
+        // The model has one scale and bias value per group (opset 18)
+        let model: group_norm::Model<Backend> = group_norm::Model::default();
+
+        let output = model.forward(norm_input([2, 4, 3]));
+        let expected = TensorData::from([
+            [
+                [-0.5304f32, -0.418, -0.0808],
+                [0.4813, 1.2681, 2.2798],
+                [-3.1882, -2.2959, -1.2664],
+                [-0.0996, 1.2044, 2.6457],
+            ],
+            [
+                [-0.8948, -0.3906, 0.1538],
+                [0.7386, 1.3638, 2.0292],
+                [-3.3307, -2.2745, -1.1613],
+                [0.009, 1.2365, 2.521],
+            ],
+        ]);
+
+        output.to_data().assert_approx_eq(&expected, 3);
+    }
+
+    #[test]
+    fn top_k() {
+        let device = Default::default();
+        let model: top_k::Model<Backend> = top_k::Model::new(&device);
+
+        let input = Tensor::<Backend, 2>::from_floats(
+            [
+                [0.5, 2.0, -1.0, 3.0],
+                [4.0, -2.0, 1.5, 0.0],
+                [-0.5, 2.5, 3.5, -3.0],
+            ],
+            &device,
+        );
+        let (values, indices, smallest_values, smallest_indices) = model.forward(input);
+
+        values
+            .to_data()
+            .assert_eq(&TensorData::from([[3f32, 2.], [4., 1.5], [3.5, 2.5]]), true);
+        indices
+            .to_data()
+            .assert_eq(&TensorData::from([[3i64, 1], [0, 2], [2, 1]]), true);
+        smallest_values.to_data().assert_eq(
+            &TensorData::from([[-0.5f32, -2., -1., -3.], [0.5, 2., 1.5, 0.]]),
+            true,
+        );
+        smallest_indices
+            .to_data()
+            .assert_eq(&TensorData::from([[2i64, 1, 0, 2], [0, 0, 1, 1]]), true);
+    }
+
+    #[test]
+    fn non_max_suppression() {
+        let device = Default::default();
+        let model: non_max_suppression::Model<Backend> = non_max_suppression::Model::new(&device);
+
+        let boxes = Tensor::<Backend, 3>::from_floats(
+            [[
+                [0.0, 0.0, 1.0, 1.0],
+                [0.0, 0.1, 1.0, 1.1],
+                [0.0, -0.1, 1.0, 0.9],
+                [0.0, 10.0, 1.0, 11.0],
+                [0.0, 10.1, 1.0, 11.1],
+                [0.0, 100.0, 1.0, 101.0],
+            ]],
+            &device,
+        );
+        let scores =
+            Tensor::<Backend, 3>::from_floats([[[0.9, 0.75, 0.6, 0.95, 0.5, 0.3]]], &device);
+        let output = model.forward(boxes, scores);
+        let expected = TensorData::from([[0i64, 0, 3], [0, 0, 0], [0, 0, 5]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn roi_align() {
+        let device = Default::default();
+        let model: roi_align::Model<Backend> = roi_align::Model::new(&device);
+
+        let input = Tensor::<Backend, 1, Int>::arange(0..80, &device)
+            .float()
+            .reshape([2, 2, 4, 5])
+            .mul_scalar(0.1)
+            .sub_scalar(1.0);
+        let rois = Tensor::<Backend, 2>::from_floats(
+            [[0.0, 0.0, 3.0, 2.0], [1.0, 0.5, 4.0, 3.5]],
+            &device,
+        );
+        let batch_indices = Tensor::<Backend, 1, Int>::from_ints([0, 1], &device);
+        let output = model.forward(input, rois, batch_indices);
+        let expected = TensorData::from([
+            [
+                [[-0.9062f32, -0.7625], [-0.4688, -0.325]],
+                [[1.0938, 1.2375], [1.5312, 1.675]],
+            ],
+            [[[3.5, 3.65], [4.25, 4.4]], [[5.5, 5.65], [6.25, 6.4]]],
+        ]);
+
+        output.to_data().assert_approx_eq(&expected, 3);
+    }
+
+    #[test]
+    fn argmin() {
+        let device = Default::default();
+        let model: argmin::Model<Backend> = argmin::Model::new(&device);
+
+        let input = Tensor::<Backend, 2>::from_floats([[1., -2., 3.], [4., 5., -6.]], &device);
+        let output = model.forward(input);
+        let expected = TensorData::from([1i64, 2]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn split() {
+        let device = Default::default();
+        let model: split::Model<Backend> = split::Model::new(&device);
+
+        let input = Tensor::<Backend, 1, Int>::arange(0..10, &device)
+            .float()
+            .reshape([2, 5]);
+        let (output1, output2, output3, output4) = model.forward(input);
+
+        output1
+            .to_data()
+            .assert_eq(&TensorData::from([[0f32, 1.], [5., 6.]]), true);
+        output2
+            .to_data()
+            .assert_eq(&TensorData::from([[2f32, 3., 4.], [7., 8., 9.]]), true);
+        output3
+            .to_data()
+            .assert_eq(&TensorData::from([[0f32, 1., 2., 3., 4.]]), true);
+        output4
+            .to_data()
+            .assert_eq(&TensorData::from([[5f32, 6., 7., 8., 9.]]), true);
+    }
+
+    #[test]
+    fn scatter_elements() {
+        let device = Default::default();
+        let model: scatter_elements::Model<Backend> = scatter_elements::Model::new(&device);
+
+        let data = Tensor::<Backend, 1, Int>::arange(0..9, &device)
+            .float()
+            .reshape([3, 3]);
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[1, 0, 2], [0, 2, 1]], &device);
+        let updates =
+            Tensor::<Backend, 2>::from_floats([[1.0, 1.1, 1.2], [2.0, 2.1, 2.2]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([[2.0f32, 1.1, 2.0], [1.0, 4.0, 2.2], [6.0, 2.1, 1.2]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
+    #[test]
+    fn scatter_nd() {
+        let device = Default::default();
+        let model: scatter_nd::Model<Backend> = scatter_nd::Model::new(&device);
+
+        let data = Tensor::<Backend, 1, Int>::arange(0..8, &device)
+            .float()
+            .reshape([4, 2]);
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[3], [1]], &device);
+        let updates = Tensor::<Backend, 2>::from_floats([[10., 11.], [12., 13.]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([[0f32, 1.], [12., 13.], [4., 5.], [10., 11.]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn scatter_elements_infinite_data() {
+        let device = Default::default();
+        let model: scatter_elements::Model<Backend> = scatter_elements::Model::new(&device);
+
+        let data = Tensor::<Backend, 2>::from_floats(
+            [
+                [f32::NEG_INFINITY, 1.0, 2.0],
+                [3.0, f32::NEG_INFINITY, 5.0],
+                [6.0, 7.0, f32::NEG_INFINITY],
+            ],
+            &device,
+        );
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[1, 0, 2], [0, 2, 1]], &device);
+        let updates =
+            Tensor::<Backend, 2>::from_floats([[1.0, 1.1, 1.2], [2.0, 2.1, 2.2]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([
+            [2.0f32, 1.1, 2.0],
+            [1.0, f32::NEG_INFINITY, 2.2],
+            [6.0, 2.1, 1.2],
+        ]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn scatter_nd_infinite_data() {
+        let device = Default::default();
+        let model: scatter_nd::Model<Backend> = scatter_nd::Model::new(&device);
+
+        let data = Tensor::<Backend, 2>::from_floats(
+            [
+                [f32::NEG_INFINITY, 1.],
+                [2., 3.],
+                [4., f32::NEG_INFINITY],
+                [f32::NEG_INFINITY, 7.],
+            ],
+            &device,
+        );
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[3], [1]], &device);
+        let updates = Tensor::<Backend, 2>::from_floats([[10., 11.], [12., 13.]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([
+            [f32::NEG_INFINITY, 1.],
+            [12., 13.],
+            [4., f32::NEG_INFINITY],
+            [10., 11.],
+        ]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn gather_nd() {
+        let device = Default::default();
+        let model: gather_nd::Model<Backend> = gather_nd::Model::new(&device);
+
+        let data = Tensor::<Backend, 1, Int>::arange(0..8, &device)
+            .float()
+            .reshape([2, 2, 2]);
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[0, 1], [1, 0]], &device);
+        let output = model.forward(data, indices);
+        let expected = TensorData::from([[2f32, 3.], [4., 5.]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn non_zero() {
+        let device = Default::default();
+        let model: non_zero::Model<Backend> = non_zero::Model::new(&device);
+
+        let input = Tensor::<Backend, 2>::from_floats([[1., 0., 2.], [0., 3., 0.]], &device);
+        let output = model.forward(input);
+        let expected = TensorData::from([[0i64, 0, 1], [0, 2, 1]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn one_hot() {
+        let device = Default::default();
+        let model: one_hot::Model<Backend> = one_hot::Model::new(&device);
+
+        let indices = Tensor::<Backend, 1, Int>::from_ints([1, -1, 0], &device);
+        let output = model.forward(indices);
+        let expected = TensorData::from([
+            [0.5f32, 0.5, 2.0],
+            [2.0, 0.5, 0.5],
+            [0.5, 0.5, 0.5],
+            [0.5, 2.0, 0.5],
+        ]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn einsum() {
+        let device = Default::default();
+        let model: einsum::Model<Backend> = einsum::Model::new(&device);
+
+        let x = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([2, 3])
+            .mul_scalar(0.1);
+        let y = Tensor::<Backend, 1, Int>::arange(0..12, &device)
+            .float()
+            .reshape([3, 4])
+            .mul_scalar(0.1);
+        let query = Tensor::<Backend, 1, Int>::arange(0..24, &device)
+            .float()
+            .reshape([1, 2, 3, 4])
+            .mul_scalar(0.1)
+            .sub_scalar(1.0);
+        let key = Tensor::<Backend, 1, Int>::arange(0..16, &device)
+            .float()
+            .reshape([1, 2, 2, 4])
+            .mul_scalar(0.05);
+        let (matmul, attention, reduce) = model.forward(x, y, query, key);
+
+        matmul.to_data().assert_approx_eq(
+            &TensorData::from([[0.2f32, 0.23, 0.26, 0.29], [0.56, 0.68, 0.8, 0.92]]),
+            4,
+        );
+        attention.to_data().assert_approx_eq(
+            &TensorData::from([[
+                [[-0.23f32, -0.91], [-0.11, -0.47], [0.01, -0.03]],
+                [[0.69, 0.97], [1.45, 2.05], [2.21, 3.13]],
+            ]]),
+            4,
+        );
+        reduce.to_data().assert_approx_eq(
+            &TensorData::from([[-1.8f32, -1.5, -1.2, -0.9], [1.8, 2.1, 2.4, 2.7]]),
+            4,
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        let device = Default::default();
+        let model: if_else::Model<Backend> = if_else::Model::new(&device);
+
+        let x = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([2, 3]);
+
+        // The then branch doubles the input and the else branch squares it
+        let output = model.forward(true, x.clone());
+        let expected = TensorData::from([[0f32, 1., 2.], [3., 4., 5.]]);
+        output.to_data().assert_eq(&expected, true);
+
+        let output = model.forward(false, x);
+        let expected = TensorData::from([[0f32, 0., 2.], [6., 12., 20.]]);
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn loop_scan() {
+        let device = Default::default();
+        let model: loop_scan::Model<Backend> = loop_scan::Model::new(&device);
+
+        let x = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([2, 3])
+            .mul_scalar(0.5);
+        let (acc, scan) = model.forward(3, x);
+
+        let expected = TensorData::from([[0f32, 2., 4.], [6., 8., 10.]]);
+        acc.to_data().assert_approx_eq(&expected, 4);
+        let expected = TensorData::from([
+            [[0f32, 0.5, 2.], [4.5, 8., 12.5]],
+            [[0., 0.75, 3.], [6.75, 12., 18.75]],
+            [[0., 1., 4.], [9., 16., 25.]],
+        ]);
+        scan.to_data().assert_approx_eq(&expected, 4);
+    }
+
+    #[test]
+    fn scan() {
+        let device = Default::default();
+        let model: scan::Model<Backend> = scan::Model::new(&device);
+
+        let initial = Tensor::<Backend, 1>::from_floats([1., -1.], &device);
+        let xs = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([3, 2]);
+        let (sum, ys) = model.forward(initial, xs);
+
+        let expected = TensorData::from([7f32, 8.]);
+        sum.to_data().assert_eq(&expected, true);
+        let expected = TensorData::from([[0f32, 0.], [6., 9.], [28., 40.]]);
+        ys.to_data().assert_eq(&expected, true);
+    }
+
     #[test]
     fn tanh() {
         // Initialize the model
@@ -1656,6 +2239,25 @@ mod tests {
         output.to_data().assert_approx_eq(&expected, 4);
     }
 
+    #[test]
+    fn graph_passes() {
+        // The batch normalization is folded into the convolution and the target shape of the
+        // reshape is computed from the static input shape
+        let device = Default::default();
+        let model: graph_passes::Model<Backend> = graph_passes::Model::default();
+
+        let input = Tensor::<Backend, 4>::ones([1, 2, 4, 4], &device);
+        let output = model.forward(input);
+
+        // Each channel of the convolution output is constant and flattened into 16 values
+        let expected =
+            Tensor::<Backend, 2>::from_floats([[1.049_998_7], [0.400_003], [-0.950_001]], &device)
+                .repeat_dim(1, 16)
+                .reshape([1, 48]);
+
+        output.to_data().assert_approx_eq(&expected.to_data(), 4);
+    }
+
     #[test]
     fn log() {
         let device = Default::default();
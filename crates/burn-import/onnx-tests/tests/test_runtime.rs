@@ -0,0 +1,372 @@
+// This test suite verifies that the runtime evaluation of the ONNX models matches the generated
+// code. Each model is loaded from the same ONNX file as the build script, and both are run with
+// the same inputs.
+
+/// Include generated models in the `model` directory in the target directory.
+macro_rules! include_models {
+    ($($model:ident),*) => {
+        $(
+            // Allow type complexity for generated code
+            #[allow(clippy::type_complexity)]
+            pub mod $model {
+                include!(concat!(env!("OUT_DIR"), concat!("/model/", stringify!($model), ".rs")));
+            }
+        )*
+    };
+}
+
+include_models!(
+    add,
+    batch_norm,
+    concat,
+    conv2d,
+    flatten,
+    gather_2d_idx,
+    if_else,
+    linear,
+    loop_scan,
+    mask_where,
+    matmul,
+    reshape,
+    scan,
+    softmax,
+    split,
+    top_k
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use burn::tensor::{Int, Tensor, TensorData};
+    use burn_import::onnx::runtime::{Error, OnnxModel, Value};
+
+    type Backend = burn_ndarray::NdArray<f32>;
+
+    /// Loads the ONNX file of a test model with the runtime.
+    fn runtime_model(path: &str) -> OnnxModel<Backend> {
+        let path = format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), path);
+        OnnxModel::from_file(path, &Default::default()).unwrap()
+    }
+
+    /// Runs the model with the runtime and returns the data of the outputs.
+    fn forward(model: &OnnxModel<Backend>, inputs: Vec<Value<Backend>>) -> Vec<TensorData> {
+        model
+            .forward(inputs)
+            .unwrap()
+            .into_iter()
+            .map(Value::into_data)
+            .collect()
+    }
+
+    #[test]
+    fn add() {
+        let model: add::Model<Backend> = add::Model::default();
+        let runtime = runtime_model("add/add.onnx");
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 4>::from_floats([[[[1., 2., 3., 4.]]]], &device);
+        let expected = model.forward(input.clone(), 2.0);
+        let output = forward(&runtime, vec![input.into(), 2.0.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn matmul() {
+        let model: matmul::Model<Backend> = matmul::Model::default();
+        let runtime = runtime_model("matmul/matmul.onnx");
+
+        let device = Default::default();
+        let a = Tensor::<Backend, 1, Int>::arange(0..24, &device)
+            .reshape([1, 2, 3, 4])
+            .float();
+        let b = Tensor::<Backend, 1, Int>::arange(0..16, &device)
+            .reshape([1, 2, 4, 2])
+            .float();
+        let c = Tensor::<Backend, 1, Int>::arange(0..96, &device)
+            .reshape([2, 3, 4, 4])
+            .float();
+        let d = Tensor::<Backend, 1, Int>::arange(0..4, &device).float();
+
+        let (mm, mv, vm) = model.forward(a.clone(), b.clone(), c.clone(), d.clone());
+        let output = forward(&runtime, vec![a.into(), b.into(), c.into(), d.into()]);
+
+        output[0].assert_eq(&mm.to_data(), true);
+        output[1].assert_eq(&mv.to_data(), true);
+        output[2].assert_eq(&vm.to_data(), true);
+    }
+
+    #[test]
+    fn conv2d() {
+        let model: conv2d::Model<Backend> = conv2d::Model::default();
+        let runtime = runtime_model("conv2d/conv2d.onnx");
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 1, Int>::arange(0..1200, &device)
+            .float()
+            .div_scalar(1200.)
+            .reshape([2, 4, 10, 15]);
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_approx_eq(&expected.to_data(), 4);
+    }
+
+    #[test]
+    fn batch_norm() {
+        let model: batch_norm::Model<Backend> = batch_norm::Model::default();
+        let runtime = runtime_model("batch_norm/batch_norm.onnx");
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 1, Int>::arange(0..20, &device)
+            .float()
+            .reshape([1, 20, 1]);
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_approx_eq(&expected.to_data(), 4);
+    }
+
+    #[test]
+    fn linear() {
+        let model: linear::Model<Backend> = linear::Model::default();
+        let runtime = runtime_model("linear/linear.onnx");
+
+        let device = Default::default();
+        let input1 = Tensor::<Backend, 2>::full([4, 3], 0.5, &device);
+        let input2 = Tensor::<Backend, 2>::full([2, 5], -1.5, &device);
+        let input3 = Tensor::<Backend, 3>::full([3, 2, 7], 2.0, &device);
+
+        let (output1, output2, output3) =
+            model.forward(input1.clone(), input2.clone(), input3.clone());
+        let output = forward(&runtime, vec![input1.into(), input2.into(), input3.into()]);
+
+        output[0].assert_approx_eq(&output1.to_data(), 4);
+        output[1].assert_approx_eq(&output2.to_data(), 4);
+        output[2].assert_approx_eq(&output3.to_data(), 4);
+    }
+
+    #[test]
+    fn concat() {
+        let device = Default::default();
+        let model: concat::Model<Backend> = concat::Model::new(&device);
+        let runtime = runtime_model("concat/concat.onnx");
+
+        let input = Tensor::<Backend, 1, Int>::arange(0..30, &device)
+            .float()
+            .reshape([1, 2, 3, 5]);
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn flatten() {
+        let device = Default::default();
+        let model: flatten::Model<Backend> = flatten::Model::new(&device);
+        let runtime = runtime_model("flatten/flatten.onnx");
+
+        let input = Tensor::<Backend, 1, Int>::arange(0..75, &device)
+            .float()
+            .reshape([1, 5, 15]);
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn gather_2d_idx() {
+        let model: gather_2d_idx::Model<Backend> = gather_2d_idx::Model::default();
+        let runtime = runtime_model("gather/gather_2d_idx.onnx");
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 2>::from_data([[1.0, 1.2], [2.3, 3.4], [4.5, 5.7]], &device);
+        let index = Tensor::<Backend, 2, Int>::from_data([[0, 1], [1, 2]], &device);
+        let expected = model.forward(input.clone(), index.clone());
+        let output = forward(&runtime, vec![input.into(), index.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn mask_where() {
+        let device = Default::default();
+        let model: mask_where::Model<Backend> = mask_where::Model::new(&device);
+        let runtime = runtime_model("mask_where/mask_where.onnx");
+
+        let x = Tensor::<Backend, 2>::from_floats([[1., 2.], [3., 4.]], &device);
+        let y = Tensor::<Backend, 2>::from_floats([[-1., -2.], [-3., -4.]], &device);
+        let mask = Tensor::from_bool([[true, false], [false, true]].into(), &device);
+        let expected = model.forward(mask.clone(), x.clone(), y.clone());
+        let output = forward(&runtime, vec![mask.into(), x.into(), y.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn reshape() {
+        let device = Default::default();
+        let model: reshape::Model<Backend> = reshape::Model::new(&device);
+        let runtime = runtime_model("reshape/reshape.onnx");
+
+        let input = Tensor::<Backend, 1>::from_floats([0., 1., 2., 3.], &device);
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_eq(&expected.to_data(), true);
+    }
+
+    #[test]
+    fn softmax() {
+        let device = Default::default();
+        let model: softmax::Model<Backend> = softmax::Model::new(&device);
+        let runtime = runtime_model("softmax/softmax.onnx");
+
+        let input = Tensor::<Backend, 2>::from_floats(
+            [
+                [0.33669037, 0.128_809_4, 0.23446237],
+                [0.23033303, -1.122_856_4, -0.18632829],
+            ],
+            &device,
+        );
+        let expected = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_approx_eq(&expected.to_data(), 6);
+    }
+
+    #[test]
+    fn split() {
+        let device = Default::default();
+        let model: split::Model<Backend> = split::Model::new(&device);
+        let runtime = runtime_model("split/split.onnx");
+
+        let input = Tensor::<Backend, 1, Int>::arange(0..10, &device)
+            .float()
+            .reshape([2, 5]);
+        let (output1, output2, output3, output4) = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_eq(&output1.to_data(), true);
+        output[1].assert_eq(&output2.to_data(), true);
+        output[2].assert_eq(&output3.to_data(), true);
+        output[3].assert_eq(&output4.to_data(), true);
+    }
+
+    #[test]
+    fn top_k() {
+        let device = Default::default();
+        let model: top_k::Model<Backend> = top_k::Model::new(&device);
+        let runtime = runtime_model("top_k/top_k.onnx");
+
+        let input = Tensor::<Backend, 2>::from_floats(
+            [
+                [0.5, 2.0, -1.0, 3.0],
+                [4.0, -2.0, 1.5, 0.0],
+                [-0.5, 2.5, 3.5, -3.0],
+            ],
+            &device,
+        );
+        let (values, indices, smallest_values, smallest_indices) = model.forward(input.clone());
+        let output = forward(&runtime, vec![input.into()]);
+
+        output[0].assert_eq(&values.to_data(), true);
+        output[1].assert_eq(&indices.to_data(), true);
+        output[2].assert_eq(&smallest_values.to_data(), true);
+        output[3].assert_eq(&smallest_indices.to_data(), true);
+    }
+
+    #[test]
+    fn if_else() {
+        let device = Default::default();
+        let model: if_else::Model<Backend> = if_else::Model::new(&device);
+        let runtime = runtime_model("if_else/if_else.onnx");
+
+        let x = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([2, 3]);
+
+        for condition in [true, false] {
+            let expected = model.forward(condition, x.clone());
+            let output = forward(&runtime, vec![condition.into(), x.clone().into()]);
+
+            output[0].assert_eq(&expected.to_data(), true);
+        }
+    }
+
+    #[test]
+    fn loop_scan() {
+        let device = Default::default();
+        let model: loop_scan::Model<Backend> = loop_scan::Model::new(&device);
+        let runtime = runtime_model("loop_scan/loop_scan.onnx");
+
+        let x = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([2, 3])
+            .mul_scalar(0.5);
+        let (acc, scan) = model.forward(3, x.clone());
+        let output = forward(&runtime, vec![3i64.into(), x.into()]);
+
+        output[0].assert_approx_eq(&acc.to_data(), 4);
+        output[1].assert_approx_eq(&scan.to_data(), 4);
+    }
+
+    #[test]
+    fn scan() {
+        let device = Default::default();
+        let model: scan::Model<Backend> = scan::Model::new(&device);
+        let runtime = runtime_model("scan/scan.onnx");
+
+        let initial = Tensor::<Backend, 1>::from_floats([1., -1.], &device);
+        let xs = Tensor::<Backend, 1, Int>::arange(0..6, &device)
+            .float()
+            .reshape([3, 2]);
+        let (sum, ys) = model.forward(initial.clone(), xs.clone());
+        let output = forward(&runtime, vec![initial.into(), xs.into()]);
+
+        output[0].assert_eq(&sum.to_data(), true);
+        output[1].assert_eq(&ys.to_data(), true);
+    }
+
+    #[test]
+    fn unsupported_operator() {
+        let result = OnnxModel::<Backend>::from_file(
+            format!("{}/tests/lstm/lstm.onnx", env!("CARGO_MANIFEST_DIR")),
+            &Default::default(),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn malformed_model() {
+        let path = std::env::temp_dir().join("burn_import_runtime_malformed.onnx");
+        std::fs::write(&path, b"not an onnx model").unwrap();
+
+        let result = OnnxModel::<Backend>::from_file(&path, &Default::default());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let runtime = runtime_model("add/add.onnx");
+        let device = Default::default();
+
+        // The first input is a rank 4 float tensor and the second a float scalar
+        let input = Tensor::<Backend, 2>::zeros([1, 4], &device);
+        let result = runtime.forward(vec![input.into(), 2.0.into()]);
+        assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+        let input = Tensor::<Backend, 4, Int>::zeros([1, 1, 1, 4], &device);
+        let result = runtime.forward(vec![input.into(), 2.0.into()]);
+        assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+        let result = runtime.forward(vec![2.0.into()]);
+        assert!(matches!(result, Err(Error::InputCount { .. })));
+    }
+}
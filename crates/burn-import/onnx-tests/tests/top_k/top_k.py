@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/top_k/top_k.onnx
+
+import numpy
+import onnx
+from onnx import TensorProto, helper
+from onnx.reference import ReferenceEvaluator
+
+INPUT_SHAPE = [3, 4]
+
+
+def build_model() -> onnx.ModelProto:
+    largest = helper.make_node(
+        "TopK",
+        inputs=["input", "k"],
+        outputs=["values", "indices"],
+        name="/TopK",
+        axis=1,
+    )
+    smallest = helper.make_node(
+        "TopK",
+        inputs=["input", "k"],
+        outputs=["smallest_values", "smallest_indices"],
+        name="/TopK_1",
+        axis=0,
+        largest=0,
+    )
+
+    graph = helper.make_graph(
+        nodes=[largest, smallest],
+        name="TopKGraph",
+        inputs=[
+            helper.make_tensor_value_info("input", TensorProto.FLOAT, INPUT_SHAPE),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("values", TensorProto.FLOAT, [3, 2]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, [3, 2]),
+            helper.make_tensor_value_info("smallest_values", TensorProto.FLOAT, [2, 4]),
+            helper.make_tensor_value_info("smallest_indices", TensorProto.INT64, [2, 4]),
+        ],
+        initializer=[helper.make_tensor("k", TensorProto.INT64, [1], [2])],
+    )
+
+    return helper.make_model(
+        graph,
+        producer_name="top_k",
+        opset_imports=[helper.make_opsetid("", 16)],
+    )
+
+
+def main() -> None:
+    model = build_model()
+    onnx.checker.check_model(model)
+    onnx.save(model, "top_k.onnx")
+
+    # The input used in the test
+    x = numpy.array(
+        [[0.5, 2.0, -1.0, 3.0], [4.0, -2.0, 1.5, 0.0], [-0.5, 2.5, 3.5, -3.0]],
+        dtype=numpy.float32,
+    )
+
+    outputs = ReferenceEvaluator(model).run(None, {"input": x})
+    for name, output in zip(["values", "indices", "smallest_values", "smallest_indices"], outputs):
+        print(f"{name}: {output}")
+
+
+if __name__ == "__main__":
+    main()
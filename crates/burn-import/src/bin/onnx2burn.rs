@@ -1,19 +1,37 @@
 #[cfg(feature = "onnx")]
-use burn_import::onnx::{ModelGen, RecordType};
+use burn_import::onnx::{GraphPasses, ModelGen, RecordType};
 
 #[cfg(feature = "onnx")]
 /// Takes an ONNX file and generates a model from it
+///
+/// The optimization passes can be disabled with the `--no-constant-folding`,
+/// `--no-fuse-batch-norm`, `--no-remove-identity`, `--no-dead-code-elimination` and
+/// `--no-passes` flags.
 fn main() {
-    let onnx_file = std::env::args().nth(1).expect("No input file provided");
-    let output_dir = std::env::args()
-        .nth(2)
-        .expect("No output directory provided");
+    let mut passes = GraphPasses::default();
+    let mut args = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--no-constant-folding" => passes.constant_folding = false,
+            "--no-fuse-batch-norm" => passes.fuse_batch_norm = false,
+            "--no-remove-identity" => passes.remove_identity = false,
+            "--no-dead-code-elimination" => passes.dead_code_elimination = false,
+            "--no-passes" => passes = GraphPasses::none(),
+            flag if flag.starts_with("--") => panic!("Unknown flag {flag}"),
+            _ => args.push(arg),
+        }
+    }
+
+    let mut args = args.into_iter();
+    let onnx_file = args.next().expect("No input file provided");
+    let output_dir = args.next().expect("No output directory provided");
 
     // Generate the model code from the ONNX file.
     ModelGen::new()
         .input(onnx_file.as_str())
         .development(true)
         .record_type(RecordType::PrettyJson)
+        .passes(passes)
         .out_dir(output_dir.as_str())
         .run_from_cli();
 }
@@ -64,6 +64,12 @@ impl<PS: PrecisionSettings> BurnGraph<PS> {
         self.nodes.push(node);
     }
 
+    /// Consume the graph and return its nodes, used to generate the subgraphs of control flow
+    /// nodes.
+    pub(crate) fn into_nodes(self) -> Vec<Node<PS>> {
+        self.nodes
+    }
+
     /// Save the state of each node in a record file.
     ///
     /// The `Default` trait will be implemented for the generated model, which will load the record
@@ -1,29 +1,45 @@
 use std::marker::PhantomData;
 
 use super::{
-    argmax::ArgMaxNode, avg_pool1d::AvgPool1dNode, avg_pool2d::AvgPool2dNode,
+    argmax::ArgMaxNode, argmin::ArgMinNode, avg_pool1d::AvgPool1dNode, avg_pool2d::AvgPool2dNode,
     batch_norm::BatchNormNode, binary::BinaryNode, clip::ClipNode, concat::ConcatNode,
     constant::ConstantNode, constant_of_shape::ConstantOfShapeNode, conv1d::Conv1dNode,
     conv2d::Conv2dNode, conv3d::Conv3dNode, conv_transpose_1d::ConvTranspose1dNode,
     conv_transpose_2d::ConvTranspose2dNode, conv_transpose_3d::ConvTranspose3dNode,
-    dropout::DropoutNode, expand::ExpandNode, gather::GatherNode,
-    gather_elements::GatherElementsNode, global_avg_pool::GlobalAvgPoolNode,
-    layer_norm::LayerNormNode, linear::LinearNode, mask_where::WhereNode, matmul::MatmulNode,
-    max_pool1d::MaxPool1dNode, max_pool2d::MaxPool2dNode, mean::MeanNode, pad::PadNode,
-    prelu::PReluNode, random_normal::RandomNormalNode, random_uniform::RandomUniformNode,
-    range::RangeNode, reshape::ReshapeNode, resize::ResizeNode, slice::SliceNode,
-    squeeze::SqueezeNode, sum::SumNode, tile::TileNode, trilu::TriluNode, unary::UnaryNode,
-    unsqueeze::UnsqueezeNode,
+    dequantize_linear::DequantizeLinearNode, dropout::DropoutNode, einsum::EinsumNode,
+    expand::ExpandNode, gather::GatherNode, gather_elements::GatherElementsNode,
+    gather_nd::GatherNdNode, global_avg_pool::GlobalAvgPoolNode, group_norm::GroupNormNode,
+    gru::GruNode, if_node::IfNode, instance_norm::InstanceNormNode, layer_norm::LayerNormNode,
+    linear::LinearNode, loop_node::LoopNode, lstm::LstmNode, mask_where::WhereNode,
+    matmul::MatmulNode, max_pool1d::MaxPool1dNode, max_pool2d::MaxPool2dNode, mean::MeanNode,
+    non_max_suppression::NonMaxSuppressionNode, non_zero::NonZeroNode, one_hot::OneHotNode,
+    pad::PadNode, prelu::PReluNode, quantize_linear::QuantizeLinearNode,
+    random_normal::RandomNormalNode, random_uniform::RandomUniformNode, range::RangeNode,
+    reshape::ReshapeNode, resize::ResizeNode, rnn::RnnNode, roi_align::RoiAlignNode,
+    scan::ScanNode, scatter_elements::ScatterElementsNode, scatter_nd::ScatterNdNode,
+    slice::SliceNode, split::SplitNode, squeeze::SqueezeNode, sum::SumNode, tile::TileNode,
+    top_k::TopKNode, trilu::TriluNode, unary::UnaryNode, unsqueeze::UnsqueezeNode,
 };
 use crate::burn::{BurnImports, Scope, Type};
 use burn::backend::NdArray;
 use burn::record::PrecisionSettings;
+use burn::tensor::{DType, TensorData};
 use proc_macro2::TokenStream;
 use serde::Serialize;
 
 /// Backend used for serialization.
 pub type SerializationBackend = NdArray<f32>;
 
+/// Convert the weights to the float element of the precision settings.
+///
+/// Quantized weights are kept quantized, so they are stored quantized in the record.
+pub(crate) fn convert_weights<PS: PrecisionSettings>(data: TensorData) -> TensorData {
+    match data.dtype {
+        DType::QFloat(_) => data,
+        _ => data.convert::<PS::FloatElem>(),
+    }
+}
+
 /// Codegen trait that should be implemented by all [node](Node) entries.
 pub trait NodeCodegen<PS: PrecisionSettings>: std::fmt::Debug {
     /// All types that are used as inputs during the forward pass.
@@ -84,6 +100,7 @@ pub trait NodeCodegen<PS: PrecisionSettings>: std::fmt::Debug {
 #[derive(Debug, Clone)]
 pub enum Node<PS: PrecisionSettings> {
     ArgMax(ArgMaxNode),
+    ArgMin(ArgMinNode),
     AvgPool1d(AvgPool1dNode),
     AvgPool2d(AvgPool2dNode),
     BatchNorm(BatchNormNode),
@@ -98,25 +115,45 @@ pub enum Node<PS: PrecisionSettings> {
     ConvTranspose2d(ConvTranspose2dNode),
     ConvTranspose3d(ConvTranspose3dNode),
     PRelu(PReluNode),
+    DequantizeLinear(DequantizeLinearNode),
     Dropout(DropoutNode),
+    Einsum(EinsumNode),
     Expand(ExpandNode),
     Gather(GatherNode),
     GatherElements(GatherElementsNode),
+    GatherNd(GatherNdNode),
     GlobalAvgPool(GlobalAvgPoolNode),
+    GroupNorm(GroupNormNode),
+    Gru(GruNode),
+    If(IfNode<PS>),
+    InstanceNorm(InstanceNormNode),
     LayerNorm(LayerNormNode),
     Linear(LinearNode),
+    Loop(LoopNode<PS>),
+    Lstm(LstmNode),
     Matmul(MatmulNode),
     MaxPool1d(MaxPool1dNode),
     MaxPool2d(MaxPool2dNode),
     Mean(MeanNode),
+    NonMaxSuppression(NonMaxSuppressionNode),
+    NonZero(NonZeroNode),
+    OneHot(OneHotNode),
     Pad(PadNode),
+    QuantizeLinear(QuantizeLinearNode),
     Range(RangeNode),
     Reshape(ReshapeNode),
     Resize(ResizeNode),
+    Rnn(RnnNode),
+    RoiAlign(RoiAlignNode),
+    Scan(ScanNode<PS>),
+    ScatterElements(ScatterElementsNode),
+    ScatterNd(ScatterNdNode),
     Slice(SliceNode),
+    Split(SplitNode),
     Squeeze(SqueezeNode),
     Sum(SumNode),
     Tile(TileNode),
+    TopK(TopKNode),
     Trilu(TriluNode),
     Unary(UnaryNode),
     Unsqueeze(UnsqueezeNode),
@@ -134,6 +171,7 @@ macro_rules! match_all {
         #[allow(clippy::redundant_closure_call)]
         match $self {
             Node::ArgMax(node) => $func(node),
+            Node::ArgMin(node) => $func(node),
             Node::AvgPool1d(node) => $func(node),
             Node::AvgPool2d(node) => $func(node),
             Node::BatchNorm(node) => $func(node),
@@ -148,25 +186,45 @@ macro_rules! match_all {
             Node::ConvTranspose2d(node) => $func(node),
             Node::ConvTranspose3d(node) => $func(node),
             Node::PRelu(node) => $func(node),
+            Node::DequantizeLinear(node) => $func(node),
             Node::Dropout(node) => $func(node),
+            Node::Einsum(node) => $func(node),
             Node::Expand(node) => $func(node),
             Node::Gather(node) => $func(node),
             Node::GatherElements(node) => $func(node),
+            Node::GatherNd(node) => $func(node),
             Node::GlobalAvgPool(node) => $func(node),
+            Node::GroupNorm(node) => $func(node),
+            Node::Gru(node) => $func(node),
+            Node::If(node) => $func(node),
+            Node::InstanceNorm(node) => $func(node),
             Node::LayerNorm(node) => $func(node),
             Node::Linear(node) => $func(node),
+            Node::Loop(node) => $func(node),
+            Node::Lstm(node) => $func(node),
             Node::Matmul(node) => $func(node),
             Node::MaxPool1d(node) => $func(node),
             Node::MaxPool2d(node) => $func(node),
             Node::Mean(node) => $func(node),
+            Node::NonMaxSuppression(node) => $func(node),
+            Node::NonZero(node) => $func(node),
+            Node::OneHot(node) => $func(node),
             Node::Pad(node) => $func(node),
+            Node::QuantizeLinear(node) => $func(node),
             Node::Range(node) => $func(node),
             Node::Reshape(node) => $func(node),
             Node::Resize(node) => $func(node),
+            Node::Rnn(node) => $func(node),
+            Node::RoiAlign(node) => $func(node),
+            Node::Scan(node) => $func(node),
+            Node::ScatterElements(node) => $func(node),
+            Node::ScatterNd(node) => $func(node),
             Node::Slice(node) => $func(node),
+            Node::Split(node) => $func(node),
             Node::Squeeze(node) => $func(node),
             Node::Sum(node) => $func(node),
             Node::Tile(node) => $func(node),
+            Node::TopK(node) => $func(node),
             Node::Trilu(node) => $func(node),
             Node::Unary(node) => $func(node),
             Node::Unsqueeze(node) => $func(node),
@@ -192,6 +250,7 @@ impl<PS: PrecisionSettings> Node<PS> {
     pub fn name(&self) -> &str {
         match self {
             Node::ArgMax(_) => "argmax",
+            Node::ArgMin(_) => "argmin",
             Node::AvgPool1d(_) => "avg_pool1d",
             Node::AvgPool2d(_) => "avg_pool2d",
             Node::BatchNorm(_) => "batch_norm",
@@ -206,25 +265,45 @@ impl<PS: PrecisionSettings> Node<PS> {
             Node::ConvTranspose2d(_) => "conv_transpose2d",
             Node::ConvTranspose3d(_) => "conv_transpose3d",
             Node::PRelu(_) => "prelu",
+            Node::DequantizeLinear(_) => "dequantize_linear",
             Node::Dropout(_) => "dropout",
+            Node::Einsum(_) => "einsum",
             Node::Expand(_) => "expand",
             Node::Gather(_) => "gather",
             Node::GatherElements(_) => "gather_elements",
+            Node::GatherNd(_) => "gather_nd",
             Node::GlobalAvgPool(_) => "global_avg_pool",
+            Node::GroupNorm(_) => "group_norm",
+            Node::Gru(_) => "gru",
+            Node::If(_) => "if",
+            Node::InstanceNorm(_) => "instance_norm",
             Node::LayerNorm(_) => "layer_norm",
             Node::Linear(_) => "linear",
+            Node::Loop(_) => "loop",
+            Node::Lstm(_) => "lstm",
             Node::Matmul(_) => "matmul",
             Node::MaxPool1d(_) => "max_pool1d",
             Node::MaxPool2d(_) => "max_pool2d",
             Node::Mean(_) => "mean",
+            Node::NonMaxSuppression(_) => "non_max_suppression",
+            Node::NonZero(_) => "non_zero",
+            Node::OneHot(_) => "one_hot",
             Node::Pad(_) => "pad",
+            Node::QuantizeLinear(_) => "quantize_linear",
             Node::Range(_) => "range",
             Node::Reshape(_) => "reshape",
             Node::Resize(_) => "resize",
+            Node::Rnn(_) => "rnn",
+            Node::RoiAlign(_) => "roi_align",
+            Node::Scan(_) => "scan",
+            Node::ScatterElements(_) => "scatter_elements",
+            Node::ScatterNd(_) => "scatter_nd",
             Node::Slice(_) => "slice",
+            Node::Split(_) => "split",
             Node::Squeeze(_) => "squeeze",
             Node::Sum(_) => "add",
             Node::Tile(_) => "tile",
+            Node::TopK(_) => "top_k",
             Node::Trilu(_) => "trilu",
             Node::Unary(unary) => unary.kind.as_str(),
             Node::Unsqueeze(_) => "unsqueeze",
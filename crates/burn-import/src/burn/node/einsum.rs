@@ -0,0 +1,323 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+#[derive(Config, Debug)]
+pub struct EinsumConfig {
+    /// The subscripts of each input, e.g. `["bij", "bjk"]`.
+    pub input_subscripts: Vec<String>,
+    /// The subscripts of the output, e.g. `"bik"`.
+    pub output_subscripts: String,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct EinsumNode {
+    pub inputs: Vec<TensorType>,
+    pub output: TensorType,
+    pub config: EinsumConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for EinsumNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        self.inputs
+            .iter()
+            .map(|input| Type::Tensor(input.clone()))
+            .collect()
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let output = &self.output.name;
+        let output_subscripts = self.config.output_subscripts.chars().collect::<Vec<_>>();
+        let operands = self
+            .config
+            .input_subscripts
+            .iter()
+            .map(|subscripts| subscripts.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // The operands are contracted from left to right, keeping the subscripts that are needed
+        // by the remaining operands or the output.
+        let input = scope.tensor_use_owned(&self.inputs[0], node_position);
+        let mut body = quote! { let lhs = #input; };
+        let mut lhs = operands[0].clone();
+
+        for (i, rhs) in operands.iter().enumerate().skip(1) {
+            let input = scope.tensor_use_owned(&self.inputs[i], node_position);
+            let needed = operands[i + 1..]
+                .iter()
+                .flatten()
+                .chain(output_subscripts.iter())
+                .copied()
+                .collect::<Vec<_>>();
+
+            let lhs_keep = keep_subscripts(&lhs, rhs, &needed);
+            let rhs_keep = keep_subscripts(rhs, &lhs, &needed);
+            body.extend(quote! { let rhs = #input; });
+            body.extend(reduce("lhs", &lhs, &lhs_keep));
+            body.extend(reduce("rhs", rhs, &rhs_keep));
+
+            let (tokens, subscripts) = contract(&lhs_keep, &rhs_keep, &needed);
+            body.extend(tokens);
+            lhs = subscripts;
+        }
+
+        let keep = lhs
+            .iter()
+            .filter(|c| output_subscripts.contains(c))
+            .copied()
+            .collect::<Vec<_>>();
+        body.extend(reduce("lhs", &lhs, &keep));
+        body.extend(permute("lhs", &keep, &output_subscripts));
+
+        quote! {
+            let #output = {
+                #body
+
+                lhs
+            };
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::Einsum(self)
+    }
+}
+
+/// The subscripts of the operand that are shared with the other operand or needed afterward.
+fn keep_subscripts(operand: &[char], other: &[char], needed: &[char]) -> Vec<char> {
+    operand
+        .iter()
+        .filter(|c| other.contains(c) || needed.contains(c))
+        .copied()
+        .collect()
+}
+
+/// The identifier of the variable holding the size of a subscript.
+fn dim_ident(subscript: char) -> Ident {
+    match subscript.is_ascii_uppercase() {
+        true => format_ident!("dim_upper_{}", subscript.to_ascii_lowercase()),
+        false => format_ident!("dim_{}", subscript),
+    }
+}
+
+/// The product of the sizes of the subscripts.
+fn dim_product(subscripts: &[char]) -> TokenStream {
+    match subscripts.is_empty() {
+        true => quote! { 1 },
+        false => {
+            let first = dim_ident(subscripts[0]);
+            let others = subscripts[1..].iter().map(|c| dim_ident(*c));
+            quote! { #first #(* #others)* }
+        }
+    }
+}
+
+/// Permute the tensor from the `from` subscripts to the `to` subscripts.
+fn permute(tensor: &str, from: &[char], to: &[char]) -> TokenStream {
+    if from == to {
+        return quote! {};
+    }
+
+    let tensor = format_ident!("{}", tensor);
+    let axes = to.iter().map(|c| {
+        from.iter()
+            .position(|other| other == c)
+            .unwrap()
+            .to_tokens()
+    });
+
+    quote! { let #tensor = #tensor.permute([#(#axes),*]); }
+}
+
+/// Sum the tensor over the subscripts that are not kept.
+fn reduce(tensor: &str, subscripts: &[char], keep: &[char]) -> TokenStream {
+    let summed = subscripts
+        .iter()
+        .filter(|c| !keep.contains(c))
+        .copied()
+        .collect::<Vec<_>>();
+    if summed.is_empty() {
+        return quote! {};
+    }
+    if keep.is_empty() {
+        panic!("Einsum: reducing an operand to a scalar is not supported");
+    }
+
+    let mut tokens = permute(tensor, subscripts, &[keep, &summed].concat());
+    let tensor = format_ident!("{}", tensor);
+    let num_kept = keep.len().to_tokens();
+
+    // The summed dimensions are merged into one before being reduced
+    if summed.len() > 1 {
+        let rank = (keep.len() + 1).to_tokens();
+        let end_dim = (subscripts.len() - 1).to_tokens();
+        tokens.extend(quote! {
+            let #tensor = #tensor.flatten::<#rank>(#num_kept, #end_dim);
+        });
+    }
+    tokens.extend(quote! {
+        let #tensor = #tensor.sum_dim(#num_kept).squeeze::<#num_kept>(#num_kept);
+    });
+
+    tokens
+}
+
+/// Contract the `lhs` and `rhs` tensors with a batched matrix multiplication.
+///
+/// Returns the tokens and the subscripts of the result, stored in `lhs`.
+fn contract(lhs: &[char], rhs: &[char], needed: &[char]) -> (TokenStream, Vec<char>) {
+    let filter = |subscripts: &[char], predicate: &dyn Fn(&char) -> bool| {
+        subscripts
+            .iter()
+            .filter(|c| predicate(c))
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    let batch = filter(lhs, &|c| rhs.contains(c) && needed.contains(c));
+    let contracted = filter(lhs, &|c| rhs.contains(c) && !needed.contains(c));
+    let lhs_only = filter(lhs, &|c| !rhs.contains(c));
+    let rhs_only = filter(rhs, &|c| !lhs.contains(c));
+
+    let lhs_order = [batch.as_slice(), &lhs_only, &contracted].concat();
+    let rhs_order = [batch.as_slice(), &contracted, &rhs_only].concat();
+    let output = [batch.as_slice(), &lhs_only, &rhs_only].concat();
+    if output.is_empty() {
+        panic!("Einsum: contracting operands to a scalar is not supported");
+    }
+
+    let mut tokens = permute("lhs", lhs, &lhs_order);
+    tokens.extend(permute("rhs", rhs, &rhs_order));
+
+    let lhs_dims = lhs_order.iter().map(|c| dim_ident(*c));
+    let rhs_dims = rhs_order.iter().map(|c| match rhs_only.contains(c) {
+        true => {
+            let dim = dim_ident(*c);
+            quote! { #dim }
+        }
+        false => quote! { _ },
+    });
+    let batch_size = dim_product(&batch);
+    let lhs_size = dim_product(&lhs_only);
+    let rhs_size = dim_product(&rhs_only);
+    let contracted_size = dim_product(&contracted);
+    let output_dims = output.iter().map(|c| dim_ident(*c));
+
+    tokens.extend(quote! {
+        let [#(#lhs_dims),*] = lhs.dims();
+        let [#(#rhs_dims),*] = rhs.dims();
+        let lhs = lhs.reshape([#batch_size, #lhs_size, #contracted_size]);
+        let rhs = rhs.reshape([#batch_size, #contracted_size, #rhs_size]);
+        let lhs = lhs.matmul(rhs).reshape([#(#output_dims),*]);
+    });
+
+    (tokens, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    fn codegen(inputs: &[(&str, usize)], equation: (&[&str], &str)) -> TokenStream {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        let (input_subscripts, output_subscripts) = equation;
+        graph.register(EinsumNode::new(
+            inputs
+                .iter()
+                .map(|(name, dim)| TensorType::new_float(*name, *dim))
+                .collect(),
+            TensorType::new_float("output", output_subscripts.len()),
+            EinsumConfig::new(
+                input_subscripts.iter().map(|s| s.to_string()).collect(),
+                output_subscripts.to_string(),
+            ),
+        ));
+        graph.register_input_output(
+            inputs.iter().map(|(name, _)| name.to_string()).collect(),
+            vec!["output".to_string()],
+        );
+
+        graph.codegen()
+    }
+
+    fn expected(inputs: TokenStream, output_dim: usize, forward: TokenStream) -> TokenStream {
+        let output_dim = output_dim.to_tokens();
+
+        quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, #inputs) -> Tensor<B, #output_dim> {
+                    let output = {
+                        #forward
+
+                        lhs
+                    };
+
+                    output
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_codegen_einsum_attention_scores() {
+        let forward = quote! {
+            let lhs = query;
+            let rhs = key;
+            let rhs = rhs.permute([0, 1, 3, 2]);
+            let [dim_b, dim_h, dim_i, dim_d] = lhs.dims();
+            let [_, _, _, dim_j] = rhs.dims();
+            let lhs = lhs.reshape([dim_b * dim_h, dim_i, dim_d]);
+            let rhs = rhs.reshape([dim_b * dim_h, dim_d, dim_j]);
+            let lhs = lhs.matmul(rhs).reshape([dim_b, dim_h, dim_i, dim_j]);
+        };
+
+        assert_tokens(
+            codegen(&[("query", 4), ("key", 4)], (&["bhid", "bhjd"], "bhij")),
+            expected(
+                quote! { query: Tensor<B, 4>, key: Tensor<B, 4> },
+                4,
+                forward,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_codegen_einsum_reduce_transpose() {
+        let forward = quote! {
+            let lhs = input;
+            let lhs = lhs.permute([2, 0, 1]);
+            let lhs = lhs.flatten::<2>(1, 2);
+            let lhs = lhs.sum_dim(1).squeeze::<1>(1);
+        };
+
+        assert_tokens(
+            codegen(&[("input", 3)], (&["ijk"], "k")),
+            expected(quote! { input: Tensor<B, 3> }, 1, forward),
+        );
+    }
+}
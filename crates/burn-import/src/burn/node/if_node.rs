@@ -0,0 +1,177 @@
+use super::{
+    subgraph::{captured_union, clone_captured, output_names, tuple, use_captured, SubGraph},
+    Node, NodeCodegen,
+};
+use crate::burn::{BurnImports, Scope, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Conditional execution of one of two subgraphs (ONNX `If`).
+#[derive(Debug, Clone, new)]
+pub struct IfNode<PS: PrecisionSettings> {
+    /// A boolean scalar or a boolean tensor with a single element.
+    pub condition: Type,
+    pub outputs: Vec<Type>,
+    pub then_branch: SubGraph<PS>,
+    pub else_branch: SubGraph<PS>,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for IfNode<PS> {
+    fn output_types(&self) -> Vec<Type> {
+        self.outputs.clone()
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        let mut inputs = vec![self.condition.clone()];
+        inputs.extend(captured_union(&[&self.then_branch, &self.else_branch]));
+        inputs
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let condition = match &self.condition {
+            Type::Scalar(scalar) => {
+                let name = &scalar.name;
+                quote! { #name }
+            }
+            Type::Tensor(tensor) => {
+                let tensor = scope.tensor_use_owned(tensor, node_position);
+                quote! { #tensor.into_scalar() }
+            }
+            _ => panic!("If: the condition must be a boolean scalar or tensor"),
+        };
+        let cloned = use_captured(
+            &[&self.then_branch, &self.else_branch],
+            scope,
+            node_position,
+        );
+
+        let branch = |subgraph: &SubGraph<PS>| {
+            let captured = clone_captured(subgraph, |name| cloned.contains(name));
+            let (body, outputs) = subgraph.codegen(&[]);
+            let outputs = tuple(&outputs.into_iter().flatten().collect::<Vec<_>>());
+
+            quote! {
+                #captured
+                #body
+                #outputs
+            }
+        };
+        let then_branch = branch(&self.then_branch);
+        let else_branch = branch(&self.else_branch);
+
+        let outputs = tuple(&output_names(&self.outputs));
+
+        quote! {
+            let #outputs = if #condition {
+                #then_branch
+            } else {
+                #else_branch
+            };
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::If(self)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        self.then_branch.register_imports(imports);
+        self.else_branch.register_imports(imports);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{
+        graph::BurnGraph,
+        node::{binary::BinaryNode, test::assert_tokens, unary::UnaryNode},
+        ScalarKind, ScalarType, TensorType,
+    };
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_if() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        let tensor = |name: &str| TensorType::new_float(name, 2);
+
+        // The then branch uses the captured input twice, the else branch returns it unchanged
+        let then_branch = SubGraph::new(
+            vec![BinaryNode::add(
+                Type::Tensor(tensor("input")),
+                Type::Tensor(tensor("input")),
+                Type::Tensor(tensor("add1_out1")),
+            )
+            .into_node()],
+            vec![],
+            vec![Type::Tensor(tensor("add1_out1"))],
+        );
+        let else_branch = SubGraph::new(
+            vec![UnaryNode::relu(
+                Type::Tensor(tensor("input")),
+                Type::Tensor(tensor("relu1_out1")),
+            )
+            .into_node()],
+            vec![],
+            vec![Type::Tensor(tensor("relu1_out1"))],
+        );
+
+        graph.register(IfNode::new(
+            Type::Scalar(ScalarType::new("cond", ScalarKind::Bool)),
+            vec![Type::Tensor(tensor("output"))],
+            then_branch,
+            else_branch,
+        ));
+        graph.register(BinaryNode::mul(
+            Type::Tensor(tensor("output")),
+            Type::Tensor(tensor("input")),
+            Type::Tensor(tensor("output2")),
+        ));
+        graph.register_input_output(
+            vec!["cond".to_string(), "input".to_string()],
+            vec!["output2".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, cond: bool, input: Tensor<B, 2>) -> Tensor<B, 2> {
+                    let output = if cond {
+                        let input = input.clone();
+                        let add1_out1 = input.clone().add(input);
+                        add1_out1
+                    } else {
+                        let input = input.clone();
+                        let relu1_out1 = burn::tensor::activation::relu(input);
+                        relu1_out1
+                    };
+                    let output2 = output.mul(input);
+
+                    output2
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,280 @@
+use super::{
+    subgraph::{
+        clone_captured, output_names, tuple, update_loop_outputs, use_captured, use_value, SubGraph,
+    },
+    Node, NodeCodegen,
+};
+use crate::burn::{BurnImports, Scope, ToTokens, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Repeated execution of a subgraph with loop-carried values (ONNX `Loop`).
+///
+/// The body inputs are the iteration number, the condition and the loop-carried values. The body
+/// outputs are the condition, the loop-carried values and the scan outputs, which are stacked on
+/// a new first dimension.
+#[derive(Debug, Clone, new)]
+pub struct LoopNode<PS: PrecisionSettings> {
+    /// The maximum number of iterations, unbounded if `None`.
+    pub max_trip_count: Option<Type>,
+    /// The initial condition, `true` if `None`.
+    pub condition: Option<Type>,
+    pub initial_values: Vec<Type>,
+    /// The final loop-carried values followed by the scan outputs.
+    pub outputs: Vec<Type>,
+    pub body: SubGraph<PS>,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for LoopNode<PS> {
+    fn output_types(&self) -> Vec<Type> {
+        self.outputs.clone()
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        self.max_trip_count
+            .iter()
+            .chain(self.condition.iter())
+            .chain(self.initial_values.iter())
+            .cloned()
+            .chain(self.body.captured())
+            .collect()
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let num_carried = self.initial_values.len();
+        let iteration = self.body.inputs[0].name();
+        let condition = self.body.inputs[1].name();
+        let carried = &self.body.inputs[2..];
+        let condition_out = &self.body.outputs[0];
+        let carried_out = &self.body.outputs[1..num_carried + 1];
+
+        // The loop-carried values that are passed through are never updated
+        let kept = carried
+            .iter()
+            .zip(carried_out)
+            .filter(|(carried, output)| carried.name() == output.name())
+            .map(|(carried, _)| carried.name().clone())
+            .collect::<Vec<_>>();
+
+        let mut init = quote! {};
+        for (initial_value, carried) in self.initial_values.iter().zip(carried) {
+            let name = carried.name();
+            let value = use_value(initial_value, scope, node_position);
+            match kept.contains(name) {
+                true => init.extend(quote! { let #name = #value; }),
+                false => init.extend(quote! { let mut #name = #value; }),
+            }
+        }
+
+        // The condition is only tracked when it can stop the loop or is used by the body
+        let track_condition = self.condition.is_some()
+            || condition_out.name() != condition
+            || self.body.uses(condition);
+        let mut check_condition = quote! {};
+        if track_condition {
+            let value = match &self.condition {
+                Some(ty) => use_value(ty, scope, node_position),
+                None => quote! { true },
+            };
+            match condition_out.name() == condition {
+                true => init.extend(quote! { let #condition = #value; }),
+                false => init.extend(quote! { let mut #condition = #value; }),
+            }
+            check_condition = quote! {
+                if !#condition {
+                    break;
+                }
+            };
+        }
+
+        let range = match &self.max_trip_count {
+            Some(Type::Scalar(scalar)) => {
+                let name = &scalar.name;
+                quote! { 0..#name }
+            }
+            Some(_) => panic!("Loop: the trip count must be a scalar"),
+            None => quote! { 0i64.. },
+        };
+        let iteration = match self.body.uses(iteration) {
+            true => quote! { #iteration },
+            false => quote! { _ },
+        };
+
+        // The captured tensors are cloned on every iteration
+        use_captured(&[&self.body], scope, node_position);
+        let captured = clone_captured(&self.body, |_| true);
+        let (body, outputs) = self.body.codegen(&kept);
+
+        let mut carried_values = Vec::new();
+        if track_condition && condition_out.name() != condition {
+            let value = outputs[0].clone().unwrap();
+            let value = match condition_out {
+                Type::Tensor(_) => quote! { #value.into_scalar() },
+                _ => value,
+            };
+            carried_values.push((condition.clone(), Some(value)));
+        }
+        for (carried, value) in carried.iter().zip(&outputs[1..num_carried + 1]) {
+            carried_values.push((carried.name().clone(), value.clone()));
+        }
+
+        let mut results = carried
+            .iter()
+            .map(|carried| {
+                let name = carried.name();
+                quote! { #name }
+            })
+            .collect::<Vec<_>>();
+        let mut scan_values = Vec::new();
+        for (output, value) in self.outputs[num_carried..]
+            .iter()
+            .zip(&outputs[num_carried + 1..])
+        {
+            let name = output.name();
+            let dim = match output {
+                Type::Tensor(tensor) => tensor.dim.to_tokens(),
+                _ => panic!("Loop: only tensor scan outputs are supported"),
+            };
+            init.extend(quote! { let mut #name = [].to_vec(); });
+            scan_values.push((name.clone(), value.clone().unwrap()));
+            results.push(quote! { burn::tensor::Tensor::stack::<#dim>(#name, 0) });
+        }
+        let update = update_loop_outputs(&carried_values, &scan_values);
+        let results = tuple(&results);
+        let outputs = tuple(&output_names(&self.outputs));
+
+        quote! {
+            let #outputs = {
+                #init
+                for #iteration in #range {
+                    #check_condition
+                    #captured
+                    #body
+                    #update
+                }
+
+                #results
+            };
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Loop(self)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        self.body.register_imports(imports);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{
+        graph::BurnGraph,
+        node::{binary::BinaryNode, test::assert_tokens},
+        ScalarKind, ScalarType, TensorType,
+    };
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_loop() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        let tensor = |name: &str| TensorType::new_float(name, 2);
+
+        // The body accumulates the captured input and collects a scan output per iteration
+        let body = SubGraph::new(
+            vec![
+                BinaryNode::add(
+                    Type::Tensor(tensor("acc_in")),
+                    Type::Tensor(tensor("input")),
+                    Type::Tensor(tensor("acc_out")),
+                )
+                .into_node(),
+                BinaryNode::mul(
+                    Type::Tensor(tensor("acc_out")),
+                    Type::Tensor(tensor("input")),
+                    Type::Tensor(tensor("scan_out")),
+                )
+                .into_node(),
+            ],
+            vec![
+                Type::Scalar(ScalarType::new("iter", ScalarKind::Int64)),
+                Type::Scalar(ScalarType::new("cond_in", ScalarKind::Bool)),
+                Type::Tensor(tensor("acc_in")),
+            ],
+            vec![
+                Type::Scalar(ScalarType::new("cond_in", ScalarKind::Bool)),
+                Type::Tensor(tensor("acc_out")),
+                Type::Tensor(tensor("scan_out")),
+            ],
+        );
+
+        graph.register(LoopNode::new(
+            Some(Type::Scalar(ScalarType::new(
+                "trip_count",
+                ScalarKind::Int64,
+            ))),
+            None,
+            vec![Type::Tensor(tensor("input"))],
+            vec![
+                Type::Tensor(tensor("acc")),
+                Type::Tensor(TensorType::new_float("scan", 3)),
+            ],
+            body,
+        ));
+        graph.register_input_output(
+            vec!["trip_count".to_string(), "input".to_string()],
+            vec!["acc".to_string(), "scan".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    trip_count: i64,
+                    input: Tensor<B, 2>
+                ) -> (Tensor<B, 2>, Tensor<B, 3>) {
+                    let (acc, scan) = {
+                        let mut acc_in = input.clone();
+                        let mut scan = [].to_vec();
+                        for _ in 0..trip_count {
+                            let input = input.clone();
+                            let acc_out = acc_in.add(input.clone());
+                            let scan_out = acc_out.clone().mul(input);
+                            let (acc_in_next, scan_item) = (acc_out, scan_out);
+                            acc_in = acc_in_next;
+                            scan.push(scan_item);
+                        }
+                        (acc_in, burn::tensor::Tensor::stack::<3>(scan, 0))
+                    };
+
+                    (acc, scan)
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
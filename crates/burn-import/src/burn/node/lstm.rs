@@ -0,0 +1,373 @@
+use super::recurrent::{recurrent_forward, GateWeights, RecurrentConfig, RecurrentDirection};
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, OtherType, Scope, TensorType, ToTokens, Type};
+use burn::{
+    module::ConstantRecord,
+    nn::{BiLstmRecord, LstmRecord},
+    record::{PrecisionSettings, Record},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+pub struct LstmNode {
+    pub field: OtherType,
+    pub input: TensorType,
+    pub initial_h: Option<TensorType>,
+    pub initial_c: Option<TensorType>,
+    pub output_y: Option<TensorType>,
+    pub output_y_h: Option<TensorType>,
+    pub output_y_c: Option<TensorType>,
+    /// The weights of the input, output, forget and cell gates (ONNX order) of each direction.
+    pub weights: Vec<Vec<GateWeights>>,
+    pub config: RecurrentConfig,
+}
+
+impl LstmNode {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        initial_h: Option<TensorType>,
+        initial_c: Option<TensorType>,
+        output_y: Option<TensorType>,
+        output_y_h: Option<TensorType>,
+        output_y_c: Option<TensorType>,
+        weights: Vec<Vec<GateWeights>>,
+        config: RecurrentConfig,
+    ) -> Self {
+        let ty = match config.direction {
+            RecurrentDirection::Bidirectional => quote! { BiLstm<B> },
+            _ => quote! { Lstm<B> },
+        };
+
+        Self {
+            field: OtherType::new(name, ty),
+            input,
+            initial_h,
+            initial_c,
+            output_y,
+            output_y_h,
+            output_y_c,
+            weights,
+            config,
+        }
+    }
+
+    fn record<PS: PrecisionSettings>(
+        weights: &[GateWeights],
+    ) -> LstmRecord<super::SerializationBackend> {
+        let [input, output, forget, cell] = weights else {
+            panic!("LSTM: expected the weights of 4 gates");
+        };
+
+        LstmRecord {
+            input_gate: input.clone().into_record::<PS>(),
+            forget_gate: forget.clone().into_record::<PS>(),
+            output_gate: output.clone().into_record::<PS>(),
+            cell_gate: cell.clone().into_record::<PS>(),
+            d_hidden: ConstantRecord::new(),
+        }
+    }
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for LstmNode {
+    fn input_types(&self) -> Vec<Type> {
+        [
+            Some(&self.input),
+            self.initial_h.as_ref(),
+            self.initial_c.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|tensor| Type::Tensor(tensor.clone()))
+        .collect()
+    }
+
+    fn output_types(&self) -> Vec<Type> {
+        [&self.output_y, &self.output_y_h, &self.output_y_c]
+            .into_iter()
+            .flatten()
+            .map(|tensor| Type::Tensor(tensor.clone()))
+            .collect()
+    }
+
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let d_input = self.config.d_input.to_tokens();
+        let d_hidden = self.config.d_hidden.to_tokens();
+        let bias = self.config.bias;
+        let config = match self.config.direction {
+            RecurrentDirection::Bidirectional => quote! { BiLstmConfig },
+            _ => quote! { LstmConfig },
+        };
+
+        let tokens = quote! {
+            let #name = #config::new(#d_input, #d_hidden, #bias).init(device);
+        };
+
+        Some(tokens)
+    }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.config.direction {
+            RecurrentDirection::Bidirectional => {
+                let record = BiLstmRecord {
+                    forward: Self::record::<PS>(&self.weights[0]),
+                    reverse: Self::record::<PS>(&self.weights[1]),
+                    d_hidden: ConstantRecord::new(),
+                };
+
+                Record::into_item::<PS>(record).serialize(serializer)
+            }
+            _ => {
+                Record::into_item::<PS>(Self::record::<PS>(&self.weights[0])).serialize(serializer)
+            }
+        }
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let input = self.config.input_tokens(input);
+        let field = &self.field.name;
+
+        let initial_h = self.initial_h.as_ref().map(|initial_h| {
+            let initial_h = scope.tensor_use_owned(initial_h, node_position);
+            self.config.state_tokens(initial_h)
+        });
+        let initial_c = self.initial_c.as_ref().map(|initial_c| {
+            let initial_c = scope.tensor_use_owned(initial_c, node_position);
+            self.config.state_tokens(initial_c)
+        });
+        // A missing initial state is filled with zeros.
+        let state = match (initial_h, initial_c) {
+            (None, None) => quote! { None },
+            (Some(initial_h), Some(initial_c)) => quote! {
+                Some(LstmState::new(#initial_c, #initial_h))
+            },
+            (Some(initial_h), None) => quote! {{
+                let initial_h = #initial_h;
+                Some(LstmState::new(initial_h.zeros_like(), initial_h))
+            }},
+            (None, Some(initial_c)) => quote! {{
+                let initial_c = #initial_c;
+                let initial_h = initial_c.zeros_like();
+                Some(LstmState::new(initial_c, initial_h))
+            }},
+        };
+
+        let output = match self.output_y {
+            Some(_) => quote! { output },
+            None => quote! { _ },
+        };
+        let output_reshape = self.output_y.as_ref().map(|_| self.config.output_tokens());
+        let states = match (&self.output_y_h, &self.output_y_c) {
+            (None, None) => quote! { _ },
+            _ => quote! { state },
+        };
+
+        let mut outputs = Vec::new();
+        if let Some(output_y) = &self.output_y {
+            outputs.push((&output_y.name, self.config.output_y_tokens()));
+        }
+        if let Some(output_y_h) = &self.output_y_h {
+            let value = self.config.output_state_tokens(quote! { state.hidden });
+            outputs.push((&output_y_h.name, value));
+        }
+        if let Some(output_y_c) = &self.output_y_c {
+            let value = self.config.output_state_tokens(quote! { state.cell });
+            outputs.push((&output_y_c.name, value));
+        }
+
+        let body = quote! {
+            let (#output, #states) = self.#field.forward(#input, #state);
+            #output_reshape
+        };
+
+        recurrent_forward(outputs, body)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        match self.config.direction {
+            RecurrentDirection::Bidirectional => {
+                imports.register("burn::nn::BiLstm");
+                imports.register("burn::nn::BiLstmConfig");
+            }
+            _ => {
+                imports.register("burn::nn::Lstm");
+                imports.register("burn::nn::LstmConfig");
+            }
+        }
+
+        if self.initial_h.is_some() || self.initial_c.is_some() {
+            imports.register("burn::nn::LstmState");
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Lstm(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(LstmNode::new(
+            "lstm",
+            TensorType::new_float("input", 3),
+            None,
+            None,
+            Some(TensorType::new_float("y", 4)),
+            Some(TensorType::new_float("y_h", 3)),
+            Some(TensorType::new_float("y_c", 3)),
+            vec![],
+            RecurrentConfig::new(8, 16, true, RecurrentDirection::Forward, false),
+        ));
+
+        graph.register_input_output(
+            vec!["input".to_string()],
+            vec!["y".to_string(), "y_h".to_string(), "y_c".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::nn::Lstm;
+            use burn::nn::LstmConfig;
+
+            #[derive(Module, Debug)]
+            pub struct Model <B: Backend> {
+                lstm: Lstm<B>,
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    let lstm = LstmConfig::new(8, 16, true).init(device);
+
+                    Self {
+                        lstm,
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    input: Tensor<B, 3>
+                ) -> (Tensor<B, 4>, Tensor<B, 3>, Tensor<B, 3>) {
+                    let (y, y_h, y_c) = {
+                        let (output, state) = self.lstm.forward(input.swap_dims(0, 1), None);
+                        let output = output.reshape([0, 0, 1, 16]);
+
+                        (
+                            output.permute([1, 2, 0, 3]),
+                            state.hidden.unsqueeze_dim::<3>(0),
+                            state.cell.unsqueeze_dim::<3>(0)
+                        )
+                    };
+
+                    (y, y_h, y_c)
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+
+    #[test]
+    fn test_codegen_bidirectional_initial_states() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(LstmNode::new(
+            "lstm",
+            TensorType::new_float("input", 3),
+            Some(TensorType::new_float("initial_h", 3)),
+            Some(TensorType::new_float("initial_c", 3)),
+            Some(TensorType::new_float("y", 4)),
+            None,
+            None,
+            vec![],
+            RecurrentConfig::new(8, 16, false, RecurrentDirection::Bidirectional, true),
+        ));
+
+        graph.register_input_output(
+            vec![
+                "input".to_string(),
+                "initial_h".to_string(),
+                "initial_c".to_string(),
+            ],
+            vec!["y".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::nn::BiLstm;
+            use burn::nn::BiLstmConfig;
+            use burn::nn::LstmState;
+
+            #[derive(Module, Debug)]
+            pub struct Model <B: Backend> {
+                lstm: BiLstm<B>,
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    let lstm = BiLstmConfig::new(8, 16, false).init(device);
+
+                    Self {
+                        lstm,
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    input: Tensor<B, 3>,
+                    initial_h: Tensor<B, 3>,
+                    initial_c: Tensor<B, 3>
+                ) -> Tensor<B, 4> {
+                    let y = {
+                        let (output, _) = self.lstm.forward(
+                            input,
+                            Some(LstmState::new(
+                                initial_c.swap_dims(0, 1),
+                                initial_h.swap_dims(0, 1)
+                            ))
+                        );
+                        let output = output.reshape([0, 0, 2, 16]);
+
+                        output
+                    };
+
+                    y
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -1,6 +1,7 @@
 mod base;
 
 pub(crate) mod argmax;
+pub(crate) mod argmin;
 pub(crate) mod avg_pool1d;
 pub(crate) mod avg_pool2d;
 pub(crate) mod batch_norm;
@@ -15,29 +16,51 @@ pub(crate) mod conv3d;
 pub(crate) mod conv_transpose_1d;
 pub(crate) mod conv_transpose_2d;
 pub(crate) mod conv_transpose_3d;
+pub(crate) mod dequantize_linear;
 pub(crate) mod dropout;
+pub(crate) mod einsum;
 pub(crate) mod expand;
 pub(crate) mod gather;
 pub(crate) mod gather_elements;
+pub(crate) mod gather_nd;
 pub(crate) mod global_avg_pool;
+pub(crate) mod group_norm;
+pub(crate) mod gru;
+pub(crate) mod if_node;
+pub(crate) mod instance_norm;
 pub(crate) mod layer_norm;
 pub(crate) mod linear;
+pub(crate) mod loop_node;
+pub(crate) mod lstm;
 pub(crate) mod mask_where;
 pub(crate) mod matmul;
 pub(crate) mod max_pool1d;
 pub(crate) mod max_pool2d;
 pub(crate) mod mean;
+pub(crate) mod non_max_suppression;
+pub(crate) mod non_zero;
+pub(crate) mod one_hot;
 pub(crate) mod pad;
 pub(crate) mod prelu;
+pub(crate) mod quantize_linear;
 pub(crate) mod random_normal;
 pub(crate) mod random_uniform;
 pub(crate) mod range;
+pub(crate) mod recurrent;
 pub(crate) mod reshape;
 pub(crate) mod resize;
+pub(crate) mod rnn;
+pub(crate) mod roi_align;
+pub(crate) mod scan;
+pub(crate) mod scatter_elements;
+pub(crate) mod scatter_nd;
 pub(crate) mod slice;
+pub(crate) mod split;
 pub(crate) mod squeeze;
+pub(crate) mod subgraph;
 pub(crate) mod sum;
 pub(crate) mod tile;
+pub(crate) mod top_k;
 pub(crate) mod trilu;
 pub(crate) mod unary;
 pub(crate) mod unsqueeze;
@@ -0,0 +1,133 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct NonMaxSuppressionConfig {
+    /// If the boxes are `[x_center, y_center, width, height]` instead of `[y1, x1, y2, x2]`.
+    pub center_point_box: bool,
+    pub iou_threshold: f32,
+    pub max_output_boxes_per_class: usize,
+    pub score_threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct NonMaxSuppressionNode {
+    pub boxes: TensorType,
+    pub scores: TensorType,
+    pub output: TensorType,
+    pub config: NonMaxSuppressionConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for NonMaxSuppressionNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.boxes.clone()),
+            Type::Tensor(self.scores.clone()),
+        ]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let boxes = scope.tensor_use_owned(&self.boxes, node_position);
+        let scores = scope.tensor_use_owned(&self.scores, node_position);
+        let output = &self.output.name;
+
+        let box_format = match self.config.center_point_box {
+            true => quote! { BoxFormat::CenterSize },
+            false => quote! { BoxFormat::Corners },
+        };
+        let iou_threshold = self.config.iou_threshold;
+        let score_threshold = match self.config.score_threshold {
+            Some(threshold) => quote! { Some(#threshold) },
+            None => quote! { None },
+        };
+        let max_output_boxes_per_class = self.config.max_output_boxes_per_class.to_tokens();
+
+        quote! {
+            let #output = non_max_suppression(
+                #boxes,
+                #scores,
+                NmsOptions::new(
+                    #box_format,
+                    #iou_threshold,
+                    #score_threshold,
+                    Some(#max_output_boxes_per_class),
+                ),
+            );
+        }
+    }
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::vision::non_max_suppression");
+        imports.register("burn::tensor::vision::BoxFormat");
+        imports.register("burn::tensor::vision::NmsOptions");
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::NonMaxSuppression(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(NonMaxSuppressionNode::new(
+            TensorType::new_float("boxes", 3),
+            TensorType::new_float("scores", 3),
+            TensorType::new_int("selected", 2),
+            NonMaxSuppressionConfig::new(false, 0.5, 10).with_score_threshold(Some(0.25)),
+        ));
+        graph.register_input_output(
+            vec!["boxes".to_string(), "scores".to_string()],
+            vec!["selected".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::tensor::vision::non_max_suppression;
+            use burn::tensor::vision::BoxFormat;
+            use burn::tensor::vision::NmsOptions;
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, boxes: Tensor<B, 3>, scores: Tensor<B, 3>) -> Tensor<B, 2, Int> {
+                    let selected = non_max_suppression(
+                        boxes,
+                        scores,
+                        NmsOptions::new(BoxFormat::Corners, 0.5f32, Some(0.25f32), Some(10)),
+                    );
+
+                    selected
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
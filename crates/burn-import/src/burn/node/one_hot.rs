@@ -0,0 +1,155 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorKind, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct OneHotConfig {
+    pub depth: usize,
+    /// The `[off_value, on_value]` pair.
+    pub values: [f32; 2],
+    /// The output dimension of the one-hot encoding.
+    pub axis: usize,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct OneHotNode {
+    pub indices: TensorType,
+    pub output: TensorType,
+    pub config: OneHotConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for OneHotNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.indices.clone())]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let indices = scope.tensor_use_owned(&self.indices, node_position);
+        let output = &self.output.name;
+        let depth = self.config.depth.to_tokens();
+
+        let indices = match self.indices.kind {
+            TensorKind::Float => quote! { #indices.int() },
+            _ => indices,
+        };
+        let shape = (0..self.indices.dim).map(|i| {
+            let i = i.to_tokens();
+            quote! { shape[#i] }
+        });
+
+        // Negative indices count from the end
+        let mut one_hot = quote! {
+            indices
+                .reshape([-1])
+                .add_scalar(#depth)
+                .remainder_scalar(#depth)
+                .one_hot(#depth)
+                .reshape([#(#shape,)* #depth])
+        };
+        if self.config.axis != self.indices.dim {
+            let last = self.indices.dim.to_tokens();
+            let axis = self.config.axis.to_tokens();
+            one_hot = quote! { #one_hot.movedim(#last, #axis) };
+        }
+        if self.output.kind == TensorKind::Float {
+            one_hot = quote! { #one_hot.float() };
+        }
+
+        let [off_value, on_value] = self.config.values;
+        if off_value != 0.0 || on_value != 1.0 {
+            let (scale, offset) = match self.output.kind {
+                TensorKind::Float => {
+                    let scale = on_value - off_value;
+                    (quote! { #scale }, quote! { #off_value })
+                }
+                _ => {
+                    let (scale, offset) = ((on_value - off_value) as i64, off_value as i64);
+                    (quote! { #scale }, quote! { #offset })
+                }
+            };
+            one_hot = quote! { #one_hot.mul_scalar(#scale).add_scalar(#offset) };
+        }
+
+        quote! {
+            let #output = {
+                let indices = #indices;
+                let shape = indices.dims();
+
+                #one_hot
+            };
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::OneHot(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_one_hot() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(OneHotNode::new(
+            TensorType::new_int("indices", 2),
+            TensorType::new_float("output", 3),
+            OneHotConfig::new(5, [-1.0, 2.0], 1),
+        ));
+        graph.register_input_output(vec!["indices".to_string()], vec!["output".to_string()]);
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, indices: Tensor<B, 2, Int>) -> Tensor<B, 3> {
+                    let output = {
+                        let indices = indices;
+                        let shape = indices.dims();
+
+                        indices
+                            .reshape([-1])
+                            .add_scalar(5)
+                            .remainder_scalar(5)
+                            .one_hot(5)
+                            .reshape([shape[0], shape[1], 5])
+                            .movedim(2, 1)
+                            .float()
+                            .mul_scalar(3f32)
+                            .add_scalar(-1f32)
+                    };
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,109 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Quantize a float tensor with a per tensor affine `int8` quantization.
+#[derive(Debug, Clone, new)]
+pub struct QuantizeLinearNode {
+    pub input: TensorType,
+    pub output: TensorType,
+    pub scale: f32,
+    pub zero_point: i8,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for QuantizeLinearNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.input.clone())]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let output = &self.output.name;
+        let scale = self.scale.to_tokens();
+        let zero_point = (self.zero_point as i64).to_tokens();
+
+        quote! {
+            let #output = #input.quantize(
+                &QuantizationScheme::PerTensorAffine(QuantizationType::QInt8),
+                QuantizationParameters {
+                    scale: Tensor::from_floats([#scale], &*self.device),
+                    offset: Some(Tensor::from_ints([#zero_point], &*self.device)),
+                },
+            );
+        }
+    }
+    fn into_node(self) -> Node<PS> {
+        Node::QuantizeLinear(self)
+    }
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::quantization::QuantizationParameters");
+        imports.register("burn::tensor::quantization::QuantizationScheme");
+        imports.register("burn::tensor::quantization::QuantizationType");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{
+        graph::BurnGraph,
+        node::{quantize_linear::QuantizeLinearNode, test::assert_tokens},
+        TensorType,
+    };
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_quantize_linear() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(QuantizeLinearNode::new(
+            TensorType::new_float("input", 4),
+            TensorType::new_float("output", 4),
+            0.5,
+            -128,
+        ));
+        graph.register_input_output(vec!["input".to_string()], vec!["output".to_string()]);
+
+        let expected = quote! {
+            use burn::tensor::quantization::QuantizationParameters;
+            use burn::tensor::quantization::QuantizationScheme;
+            use burn::tensor::quantization::QuantizationType;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+                    let output = input.quantize(
+                        &QuantizationScheme::PerTensorAffine(QuantizationType::QInt8),
+                        QuantizationParameters {
+                            scale: Tensor::from_floats([0.5], &*self.device),
+                            offset: Some(Tensor::from_ints([-128], &*self.device)),
+                        },
+                    );
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
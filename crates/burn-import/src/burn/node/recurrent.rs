@@ -0,0 +1,266 @@
+use super::SerializationBackend;
+use crate::burn::ToTokens;
+use burn::{
+    config::Config,
+    module::{Param, ParamId},
+    nn::{GateControllerRecord, LinearRecord},
+    record::PrecisionSettings,
+    tensor::{Tensor, TensorData},
+};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+/// The direction in which a recurrent node processes the sequence.
+#[derive(Config, Debug, PartialEq, Eq)]
+pub enum RecurrentDirection {
+    Forward,
+    Reverse,
+    Bidirectional,
+}
+
+/// The configuration shared by the LSTM, GRU and RNN nodes.
+#[derive(Config, Debug)]
+pub struct RecurrentConfig {
+    pub d_input: usize,
+    pub d_hidden: usize,
+    pub bias: bool,
+    pub direction: RecurrentDirection,
+    /// If the batch is the first dimension of the inputs and outputs (ONNX `layout` 1) instead
+    /// of the sequence (ONNX `layout` 0).
+    pub batch_first: bool,
+}
+
+/// The weights of one gate of one direction, transposed to the `[d_input, d_output]` layout of
+/// Burn's linear layers.
+#[derive(Debug, Clone)]
+pub struct GateWeights {
+    pub input_weight: TensorData,
+    pub input_bias: Option<TensorData>,
+    pub hidden_weight: TensorData,
+    pub hidden_bias: Option<TensorData>,
+}
+
+impl GateWeights {
+    /// Split the stacked weights of a recurrent node into the weights of each gate.
+    ///
+    /// ONNX stacks the gates in the `W` `[num_directions, num_gates * hidden_size, input_size]`,
+    /// `R` `[num_directions, num_gates * hidden_size, hidden_size]` and `B`
+    /// `[num_directions, 2 * num_gates * hidden_size]` inputs, the biases of `R` following the
+    /// ones of `W`.
+    ///
+    /// # Returns
+    ///
+    /// The weights of each gate, in the ONNX gate order, for each direction.
+    pub fn from_onnx(
+        weight: TensorData,
+        recurrence: TensorData,
+        bias: Option<TensorData>,
+        num_gates: usize,
+    ) -> Vec<Vec<Self>> {
+        let device = Default::default();
+        let weight = Tensor::<SerializationBackend, 3>::from_data(weight.convert::<f32>(), &device);
+        let recurrence =
+            Tensor::<SerializationBackend, 3>::from_data(recurrence.convert::<f32>(), &device);
+        let bias = bias.map(|bias| {
+            Tensor::<SerializationBackend, 2>::from_data(bias.convert::<f32>(), &device)
+        });
+
+        let [num_directions, _, d_input] = weight.dims();
+        let [_, _, d_hidden] = recurrence.dims();
+
+        let transposed = |tensor: &Tensor<SerializationBackend, 3>, direction, gate, size| {
+            tensor
+                .clone()
+                .slice([
+                    direction..direction + 1,
+                    gate * d_hidden..(gate + 1) * d_hidden,
+                    0..size,
+                ])
+                .reshape([d_hidden, size])
+                .transpose()
+                .into_data()
+        };
+        let sliced = |direction, offset| {
+            bias.as_ref().map(|bias| {
+                bias.clone()
+                    .slice([direction..direction + 1, offset..offset + d_hidden])
+                    .reshape([d_hidden])
+                    .into_data()
+            })
+        };
+
+        (0..num_directions)
+            .map(|direction| {
+                (0..num_gates)
+                    .map(|gate| GateWeights {
+                        input_weight: transposed(&weight, direction, gate, d_input),
+                        input_bias: sliced(direction, gate * d_hidden),
+                        hidden_weight: transposed(&recurrence, direction, gate, d_hidden),
+                        hidden_bias: sliced(direction, (num_gates + gate) * d_hidden),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Create the record of the gate controller.
+    pub fn into_record<PS: PrecisionSettings>(self) -> GateControllerRecord<SerializationBackend> {
+        GateControllerRecord {
+            input_transform: linear_record::<PS>(self.input_weight, self.input_bias),
+            hidden_transform: linear_record::<PS>(self.hidden_weight, self.hidden_bias),
+        }
+    }
+}
+
+fn linear_record<PS: PrecisionSettings>(
+    weight: TensorData,
+    bias: Option<TensorData>,
+) -> LinearRecord<SerializationBackend> {
+    let device = Default::default();
+
+    LinearRecord {
+        weight: Param::initialized(
+            ParamId::new(),
+            Tensor::from_data(weight.convert::<PS::FloatElem>(), &device),
+        ),
+        bias: bias.map(|bias| {
+            Param::initialized(
+                ParamId::new(),
+                Tensor::from_data(bias.convert::<PS::FloatElem>(), &device),
+            )
+        }),
+    }
+}
+
+impl RecurrentConfig {
+    pub fn num_directions(&self) -> usize {
+        match self.direction {
+            RecurrentDirection::Bidirectional => 2,
+            _ => 1,
+        }
+    }
+
+    /// Convert the ONNX input `X` to the `[batch_size, seq_length, d_input]` input of the Burn
+    /// modules. The reverse direction is computed on the reversed sequence.
+    pub fn input_tokens(&self, input: TokenStream) -> TokenStream {
+        let input = match self.batch_first {
+            true => input,
+            false => quote! { #input.swap_dims(0, 1) },
+        };
+
+        match self.direction {
+            RecurrentDirection::Reverse => quote! { #input.flip([1]) },
+            _ => input,
+        }
+    }
+
+    /// Convert an ONNX initial state to the `[batch_size, d_hidden]` state of the unidirectional
+    /// Burn modules, or the `[2, batch_size, d_hidden]` state of the bidirectional ones.
+    pub fn state_tokens(&self, state: TokenStream) -> TokenStream {
+        let state = match self.batch_first {
+            true => quote! { #state.swap_dims(0, 1) },
+            false => state,
+        };
+
+        match self.direction {
+            RecurrentDirection::Bidirectional => state,
+            _ => quote! { #state.squeeze::<2>(0) },
+        }
+    }
+
+    /// Reshape the `[batch_size, seq_length, num_directions * d_hidden]` Burn `output` to
+    /// `[batch_size, seq_length, num_directions, d_hidden]`, restoring the order of the sequence
+    /// for the reverse direction.
+    pub fn output_tokens(&self) -> TokenStream {
+        let num_directions = self.num_directions().to_tokens();
+        let d_hidden = self.d_hidden.to_tokens();
+        let output = match self.direction {
+            RecurrentDirection::Reverse => quote! { output.flip([1]) },
+            _ => quote! { output },
+        };
+
+        quote! {
+            let output = #output.reshape([0, 0, #num_directions, #d_hidden]);
+        }
+    }
+
+    /// Convert the reshaped `output` to the ONNX output `Y`.
+    pub fn output_y_tokens(&self) -> TokenStream {
+        match self.batch_first {
+            true => quote! { output },
+            false => quote! { output.permute([1, 2, 0, 3]) },
+        }
+    }
+
+    /// Extract the final hidden state `Y_h` from the reshaped `output`, which is the last
+    /// timestep of the forward direction and the first timestep of the reverse direction.
+    pub fn output_y_h_tokens(&self) -> TokenStream {
+        // [batch_size, num_directions, d_hidden]
+        let hidden = match self.direction {
+            RecurrentDirection::Forward => quote! {
+                {
+                    let seq_length = output.dims()[1];
+                    output.clone().narrow(1, seq_length - 1, 1).squeeze::<3>(1)
+                }
+            },
+            RecurrentDirection::Reverse => quote! {
+                output.clone().narrow(1, 0, 1).squeeze::<3>(1)
+            },
+            RecurrentDirection::Bidirectional => quote! {
+                {
+                    let seq_length = output.dims()[1];
+                    burn::tensor::Tensor::cat(
+                        [
+                            output.clone().narrow(1, seq_length - 1, 1).narrow(2, 0, 1),
+                            output.clone().narrow(1, 0, 1).narrow(2, 1, 1),
+                        ]
+                        .into(),
+                        2,
+                    )
+                    .squeeze::<3>(1)
+                }
+            },
+        };
+
+        match self.batch_first {
+            true => hidden,
+            false => quote! { #hidden.swap_dims(0, 1) },
+        }
+    }
+
+    /// Convert a final state of the Burn modules to the ONNX `Y_h` or `Y_c` outputs.
+    pub fn output_state_tokens(&self, state: TokenStream) -> TokenStream {
+        // [num_directions, batch_size, d_hidden]
+        let state = match self.direction {
+            RecurrentDirection::Bidirectional => state,
+            _ => quote! { #state.unsqueeze_dim::<3>(0) },
+        };
+
+        match self.batch_first {
+            true => quote! { #state.swap_dims(0, 1) },
+            false => state,
+        }
+    }
+}
+
+/// Bind the outputs of a recurrent node, computed from the local variables of the `body` block.
+pub fn recurrent_forward(outputs: Vec<(&Ident, TokenStream)>, body: TokenStream) -> TokenStream {
+    let (names, values): (Vec<_>, Vec<_>) = outputs.into_iter().unzip();
+
+    match names.len() {
+        1 => quote! {
+            let #(#names)* = {
+                #body
+
+                #(#values)*
+            };
+        },
+        _ => quote! {
+            let (#(#names),*) = {
+                #body
+
+                (#(#values),*)
+            };
+        },
+    }
+}
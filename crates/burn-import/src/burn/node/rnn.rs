@@ -0,0 +1,241 @@
+use super::recurrent::{recurrent_forward, GateWeights, RecurrentConfig, RecurrentDirection};
+use super::{Node, NodeCodegen, SerializationBackend};
+use crate::burn::{BurnImports, OtherType, Scope, TensorType, ToTokens, Type};
+use burn::{
+    module::ConstantRecord,
+    nn::{BiRnnRecord, RnnRecord},
+    record::{PrecisionSettings, Record},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+pub struct RnnNode {
+    pub field: OtherType,
+    pub input: TensorType,
+    pub initial_h: Option<TensorType>,
+    pub output_y: Option<TensorType>,
+    pub output_y_h: Option<TensorType>,
+    /// The weights of the hidden gate of each direction.
+    pub weights: Vec<Vec<GateWeights>>,
+    pub config: RecurrentConfig,
+}
+
+impl RnnNode {
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        initial_h: Option<TensorType>,
+        output_y: Option<TensorType>,
+        output_y_h: Option<TensorType>,
+        weights: Vec<Vec<GateWeights>>,
+        config: RecurrentConfig,
+    ) -> Self {
+        let ty = match config.direction {
+            RecurrentDirection::Bidirectional => quote! { BiRnn<B> },
+            _ => quote! { Rnn<B> },
+        };
+
+        Self {
+            field: OtherType::new(name, ty),
+            input,
+            initial_h,
+            output_y,
+            output_y_h,
+            weights,
+            config,
+        }
+    }
+
+    fn record<PS: PrecisionSettings>(weights: &[GateWeights]) -> RnnRecord<SerializationBackend> {
+        let [hidden] = weights else {
+            panic!("RNN: expected the weights of 1 gate");
+        };
+
+        RnnRecord {
+            hidden_gate: hidden.clone().into_record::<PS>(),
+            d_hidden: ConstantRecord::new(),
+        }
+    }
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for RnnNode {
+    fn input_types(&self) -> Vec<Type> {
+        [Some(&self.input), self.initial_h.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|tensor| Type::Tensor(tensor.clone()))
+            .collect()
+    }
+
+    fn output_types(&self) -> Vec<Type> {
+        [&self.output_y, &self.output_y_h]
+            .into_iter()
+            .flatten()
+            .map(|tensor| Type::Tensor(tensor.clone()))
+            .collect()
+    }
+
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let d_input = self.config.d_input.to_tokens();
+        let d_hidden = self.config.d_hidden.to_tokens();
+        let bias = self.config.bias;
+        let config = match self.config.direction {
+            RecurrentDirection::Bidirectional => quote! { BiRnnConfig },
+            _ => quote! { RnnConfig },
+        };
+        let tokens = quote! {
+            let #name = #config::new(#d_input, #d_hidden, #bias).init(device);
+        };
+
+        Some(tokens)
+    }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.config.direction {
+            RecurrentDirection::Bidirectional => {
+                let record = BiRnnRecord {
+                    forward: Self::record::<PS>(&self.weights[0]),
+                    reverse: Self::record::<PS>(&self.weights[1]),
+                    d_hidden: ConstantRecord::new(),
+                };
+
+                Record::into_item::<PS>(record).serialize(serializer)
+            }
+            _ => {
+                let record = Self::record::<PS>(&self.weights[0]);
+
+                Record::into_item::<PS>(record).serialize(serializer)
+            }
+        }
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let input = self.config.input_tokens(input);
+        let field = &self.field.name;
+
+        let state = match &self.initial_h {
+            Some(initial_h) => {
+                let initial_h = scope.tensor_use_owned(initial_h, node_position);
+                let initial_h = self.config.state_tokens(initial_h);
+                quote! { Some(#initial_h) }
+            }
+            None => quote! { None },
+        };
+        let output_reshape = self.config.output_tokens();
+
+        let mut outputs = Vec::new();
+        let mut hidden = None;
+        if let Some(output_y_h) = &self.output_y_h {
+            let value = self.config.output_y_h_tokens();
+            hidden = Some(quote! { let hidden = #value; });
+            outputs.push((&output_y_h.name, quote! { hidden }));
+        }
+        if let Some(output_y) = &self.output_y {
+            outputs.insert(0, (&output_y.name, self.config.output_y_tokens()));
+        }
+
+        let body = quote! {
+            let output = self.#field.forward(#input, #state);
+            #output_reshape
+            #hidden
+        };
+
+        recurrent_forward(outputs, body)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        match self.config.direction {
+            RecurrentDirection::Bidirectional => {
+                imports.register("burn::nn::BiRnn");
+                imports.register("burn::nn::BiRnnConfig");
+            }
+            _ => {
+                imports.register("burn::nn::Rnn");
+                imports.register("burn::nn::RnnConfig");
+            }
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Rnn(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(RnnNode::new(
+            "rnn",
+            TensorType::new_float("input", 3),
+            None,
+            None,
+            Some(TensorType::new_float("y_h", 3)),
+            vec![],
+            RecurrentConfig::new(8, 16, true, RecurrentDirection::Reverse, false),
+        ));
+
+        graph.register_input_output(vec!["input".to_string()], vec!["y_h".to_string()]);
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::nn::Rnn;
+            use burn::nn::RnnConfig;
+
+            #[derive(Module, Debug)]
+            pub struct Model <B: Backend> {
+                rnn: Rnn<B>,
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    let rnn = RnnConfig::new(8, 16, true).init(device);
+
+                    Self {
+                        rnn,
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+                    let y_h = {
+                        let output = self.rnn.forward(input.swap_dims(0, 1).flip([1]), None);
+                        let output = output.flip([1]).reshape([0, 0, 1, 16]);
+                        let hidden = output
+                            .clone()
+                            .narrow(1, 0, 1)
+                            .squeeze::<3>(1)
+                            .swap_dims(0, 1);
+
+                        hidden
+                    };
+
+                    y_h
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
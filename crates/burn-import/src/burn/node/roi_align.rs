@@ -0,0 +1,149 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The pooling method of the samples of each output bin.
+#[derive(Config, Debug, PartialEq, Eq)]
+pub enum RoiAlignPooling {
+    Avg,
+    Max,
+}
+
+#[derive(Config, Debug)]
+pub struct RoiAlignConfig {
+    pub output_size: [usize; 2],
+    pub spatial_scale: f32,
+    pub sampling_ratio: usize,
+    /// If the pixels are shifted by `-0.5` (ONNX `half_pixel` coordinate transformation mode).
+    pub aligned: bool,
+    pub pooling: RoiAlignPooling,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct RoiAlignNode {
+    pub input: TensorType,
+    pub rois: TensorType,
+    pub batch_indices: TensorType,
+    pub output: TensorType,
+    pub config: RoiAlignConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for RoiAlignNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.input.clone()),
+            Type::Tensor(self.rois.clone()),
+            Type::Tensor(self.batch_indices.clone()),
+        ]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let rois = scope.tensor_use_owned(&self.rois, node_position);
+        let batch_indices = scope.tensor_use_owned(&self.batch_indices, node_position);
+        let output = &self.output.name;
+
+        let output_size = self.config.output_size.to_tokens();
+        let spatial_scale = self.config.spatial_scale;
+        let sampling_ratio = self.config.sampling_ratio.to_tokens();
+        let aligned = self.config.aligned;
+        let mode = match self.config.pooling {
+            RoiAlignPooling::Avg => quote! { RoiAlignMode::Avg },
+            RoiAlignPooling::Max => quote! { RoiAlignMode::Max },
+        };
+
+        quote! {
+            let #output = roi_align(
+                #input,
+                #rois,
+                #batch_indices,
+                RoiAlignOptions::new(#output_size, #spatial_scale, #sampling_ratio, #aligned, #mode),
+            );
+        }
+    }
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::vision::roi_align");
+        imports.register("burn::tensor::vision::RoiAlignMode");
+        imports.register("burn::tensor::vision::RoiAlignOptions");
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::RoiAlign(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(RoiAlignNode::new(
+            TensorType::new_float("input", 4),
+            TensorType::new_float("rois", 2),
+            TensorType::new_int("batch_indices", 1),
+            TensorType::new_float("output", 4),
+            RoiAlignConfig::new([7, 7], 0.0625, 2, true, RoiAlignPooling::Avg),
+        ));
+        graph.register_input_output(
+            vec![
+                "input".to_string(),
+                "rois".to_string(),
+                "batch_indices".to_string(),
+            ],
+            vec!["output".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::tensor::vision::roi_align;
+            use burn::tensor::vision::RoiAlignMode;
+            use burn::tensor::vision::RoiAlignOptions;
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    input: Tensor<B, 4>,
+                    rois: Tensor<B, 2>,
+                    batch_indices: Tensor<B, 1, Int>
+                ) -> Tensor<B, 4> {
+                    let output = roi_align(
+                        input,
+                        rois,
+                        batch_indices,
+                        RoiAlignOptions::new([7, 7], 0.0625f32, 2, true, RoiAlignMode::Avg),
+                    );
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,234 @@
+use super::{
+    subgraph::{
+        clone_captured, output_names, tuple, update_loop_outputs, use_captured, use_value, SubGraph,
+    },
+    Node, NodeCodegen,
+};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Iteration of a subgraph over the first dimension of the scan inputs (ONNX `Scan`).
+///
+/// The body inputs are the state values and one slice of each scan input. The body outputs are
+/// the state values and the scan outputs, which are stacked on a new first dimension.
+#[derive(Debug, Clone, new)]
+pub struct ScanNode<PS: PrecisionSettings> {
+    pub initial_states: Vec<Type>,
+    pub scan_inputs: Vec<TensorType>,
+    /// The final state values followed by the scan outputs.
+    pub outputs: Vec<Type>,
+    pub body: SubGraph<PS>,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for ScanNode<PS> {
+    fn output_types(&self) -> Vec<Type> {
+        self.outputs.clone()
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        self.initial_states
+            .iter()
+            .cloned()
+            .chain(self.scan_inputs.iter().cloned().map(Type::Tensor))
+            .chain(self.body.captured())
+            .collect()
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let num_states = self.initial_states.len();
+        let states = &self.body.inputs[..num_states];
+        let slices = &self.body.inputs[num_states..];
+        let states_out = &self.body.outputs[..num_states];
+
+        // The states that are passed through are never updated
+        let kept = states
+            .iter()
+            .zip(states_out)
+            .filter(|(state, output)| state.name() == output.name())
+            .map(|(state, _)| state.name().clone())
+            .collect::<Vec<_>>();
+
+        let mut init = quote! {};
+        for (initial_state, state) in self.initial_states.iter().zip(states) {
+            let name = state.name();
+            let value = use_value(initial_state, scope, node_position);
+            match kept.contains(name) {
+                true => init.extend(quote! { let #name = #value; }),
+                false => init.extend(quote! { let mut #name = #value; }),
+            }
+        }
+
+        // The scan inputs are bound to the names of the slices, which shadow them in the body
+        let mut slice_inputs = quote! {};
+        for (scan_input, slice) in self.scan_inputs.iter().zip(slices) {
+            let name = slice.name();
+            let value = scope.tensor_use_owned(scan_input, node_position);
+            let dim = match scan_input.dim {
+                0 | 1 => panic!("Scan: the scan inputs must have at least 2 dimensions"),
+                dim => (dim - 1).to_tokens(),
+            };
+            init.extend(quote! { let #name = #value; });
+            if self.body.uses(name) {
+                slice_inputs.extend(quote! {
+                    let #name = #name.clone().narrow(0, index, 1).squeeze::<#dim>(0);
+                });
+            }
+        }
+        let first_input = slices[0].name();
+        init.extend(quote! { let sequence_length = #first_input.dims()[0]; });
+
+        // The captured tensors are cloned on every iteration
+        use_captured(&[&self.body], scope, node_position);
+        let captured = clone_captured(&self.body, |_| true);
+        let (body, outputs) = self.body.codegen(&kept);
+
+        let carried_values = states
+            .iter()
+            .zip(&outputs)
+            .map(|(state, value)| (state.name().clone(), value.clone()))
+            .collect::<Vec<_>>();
+
+        let mut results = states
+            .iter()
+            .map(|state| {
+                let name = state.name();
+                quote! { #name }
+            })
+            .collect::<Vec<_>>();
+        let mut scan_values = Vec::new();
+        for (output, value) in self.outputs[num_states..]
+            .iter()
+            .zip(&outputs[num_states..])
+        {
+            let name = output.name();
+            let dim = match output {
+                Type::Tensor(tensor) => tensor.dim.to_tokens(),
+                _ => panic!("Scan: only tensor scan outputs are supported"),
+            };
+            init.extend(quote! { let mut #name = [].to_vec(); });
+            scan_values.push((name.clone(), value.clone().unwrap()));
+            results.push(quote! { burn::tensor::Tensor::stack::<#dim>(#name, 0) });
+        }
+        let update = update_loop_outputs(&carried_values, &scan_values);
+        let results = tuple(&results);
+        let outputs = tuple(&output_names(&self.outputs));
+
+        quote! {
+            let #outputs = {
+                #init
+                for index in 0..sequence_length {
+                    #slice_inputs
+                    #captured
+                    #body
+                    #update
+                }
+
+                #results
+            };
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Scan(self)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        self.body.register_imports(imports);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{
+        graph::BurnGraph,
+        node::{binary::BinaryNode, test::assert_tokens},
+    };
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_scan() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        let tensor = |name: &str| TensorType::new_float(name, 1);
+
+        // Running sum over the rows of the scan input, collecting every partial sum
+        let body = SubGraph::new(
+            vec![BinaryNode::add(
+                Type::Tensor(tensor("sum_in")),
+                Type::Tensor(tensor("row")),
+                Type::Tensor(tensor("sum_out")),
+            )
+            .into_node()],
+            vec![Type::Tensor(tensor("sum_in")), Type::Tensor(tensor("row"))],
+            vec![
+                Type::Tensor(tensor("sum_out")),
+                Type::Tensor(tensor("sum_out")),
+            ],
+        );
+
+        graph.register(ScanNode::new(
+            vec![Type::Tensor(tensor("initial"))],
+            vec![TensorType::new_float("xs", 2)],
+            vec![
+                Type::Tensor(tensor("sum")),
+                Type::Tensor(TensorType::new_float("ys", 2)),
+            ],
+            body,
+        ));
+        graph.register_input_output(
+            vec!["initial".to_string(), "xs".to_string()],
+            vec!["sum".to_string(), "ys".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    initial: Tensor<B, 1>,
+                    xs: Tensor<B, 2>
+                ) -> (Tensor<B, 1>, Tensor<B, 2>) {
+                    let (sum, ys) = {
+                        let mut sum_in = initial;
+                        let row = xs;
+                        let sequence_length = row.dims()[0];
+                        let mut ys = [].to_vec();
+                        for index in 0..sequence_length {
+                            let row = row.clone().narrow(0, index, 1).squeeze::<1>(0);
+                            let sum_out = sum_in.add(row);
+                            let (sum_in_next, ys_item) = (sum_out.clone(), sum_out);
+                            sum_in = sum_in_next;
+                            ys.push(ys_item);
+                        }
+                        (sum_in, burn::tensor::Tensor::stack::<2>(ys, 0))
+                    };
+
+                    (sum, ys)
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,147 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// How the updates are combined with the existing values.
+#[derive(Config, Debug, PartialEq, Eq)]
+pub enum ScatterReduction {
+    /// The updates replace the existing values.
+    None,
+    /// The updates are added to the existing values.
+    Add,
+}
+
+#[derive(Config, Debug)]
+pub struct ScatterElementsConfig {
+    pub axis: usize,
+    pub reduction: ScatterReduction,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct ScatterElementsNode {
+    pub data: TensorType,
+    pub indices: TensorType,
+    pub updates: TensorType,
+    pub output: TensorType,
+    pub config: ScatterElementsConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for ScatterElementsNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.data.clone()),
+            Type::Tensor(self.indices.clone()),
+            Type::Tensor(self.updates.clone()),
+        ]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let data = scope.tensor_use_owned(&self.data, node_position);
+        let indices = scope.tensor_use_owned(&self.indices, node_position);
+        let updates = scope.tensor_use_owned(&self.updates, node_position);
+        let output = &self.output.name;
+        let axis = self.config.axis.to_tokens();
+
+        match self.config.reduction {
+            ScatterReduction::Add => quote! {
+                let #output = #data.scatter(#axis, #indices, #updates);
+            },
+            // Scatter sums the values, so the updates are scattered into zeros and replace the
+            // existing values where an index points to
+            ScatterReduction::None => quote! {
+                let #output = {
+                    let (data, indices, updates) = (#data, #indices, #updates);
+                    let mask = data
+                        .zeros_like()
+                        .scatter(#axis, indices.clone(), updates.ones_like())
+                        .bool();
+                    let updates = data.zeros_like().scatter(#axis, indices, updates);
+
+                    data.mask_where(mask, updates)
+                };
+            },
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::ScatterElements(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_scatter_elements() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(ScatterElementsNode::new(
+            TensorType::new_float("data", 2),
+            TensorType::new_int("indices", 2),
+            TensorType::new_float("updates", 2),
+            TensorType::new_float("output", 2),
+            ScatterElementsConfig::new(1, ScatterReduction::None),
+        ));
+        graph.register_input_output(
+            vec![
+                "data".to_string(),
+                "indices".to_string(),
+                "updates".to_string(),
+            ],
+            vec!["output".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    data: Tensor<B, 2>,
+                    indices: Tensor<B, 2, Int>,
+                    updates: Tensor<B, 2>
+                ) -> Tensor<B, 2> {
+                    let output = {
+                        let (data, indices, updates) = (data, indices, updates);
+                        let mask = data
+                            .zeros_like()
+                            .scatter(1, indices.clone(), updates.ones_like())
+                            .bool();
+                        let updates = data.zeros_like().scatter(1, indices, updates);
+
+                        data.mask_where(mask, updates)
+                    };
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
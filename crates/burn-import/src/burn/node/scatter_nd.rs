@@ -0,0 +1,130 @@
+use super::{scatter_elements::ScatterReduction, Node, NodeCodegen};
+use crate::burn::{Scope, TensorType, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Debug, Clone, new)]
+pub struct ScatterNdNode {
+    pub data: TensorType,
+    pub indices: TensorType,
+    pub updates: TensorType,
+    pub output: TensorType,
+    pub reduction: ScatterReduction,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for ScatterNdNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.data.clone()),
+            Type::Tensor(self.indices.clone()),
+            Type::Tensor(self.updates.clone()),
+        ]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let data = scope.tensor_use_owned(&self.data, node_position);
+        let indices = scope.tensor_use_owned(&self.indices, node_position);
+        let updates = scope.tensor_use_owned(&self.updates, node_position);
+        let output = &self.output.name;
+
+        match self.reduction {
+            ScatterReduction::Add => quote! {
+                let #output = #data.scatter_nd(#indices, #updates);
+            },
+            // Scatter sums the values, so the updates are scattered into zeros and replace the
+            // existing values where an index points to
+            ScatterReduction::None => quote! {
+                let #output = {
+                    let (data, indices, updates) = (#data, #indices, #updates);
+                    let mask = data
+                        .zeros_like()
+                        .scatter_nd(indices.clone(), updates.ones_like())
+                        .bool();
+                    let updates = data.zeros_like().scatter_nd(indices, updates);
+
+                    data.mask_where(mask, updates)
+                };
+            },
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::ScatterNd(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_scatter_nd() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(ScatterNdNode::new(
+            TensorType::new_float("data", 3),
+            TensorType::new_int("indices", 2),
+            TensorType::new_float("updates", 2),
+            TensorType::new_float("output", 3),
+            ScatterReduction::None,
+        ));
+        graph.register_input_output(
+            vec![
+                "data".to_string(),
+                "indices".to_string(),
+                "updates".to_string(),
+            ],
+            vec!["output".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    data: Tensor<B, 3>,
+                    indices: Tensor<B, 2, Int>,
+                    updates: Tensor<B, 2>
+                ) -> Tensor<B, 3> {
+                    let output = {
+                        let (data, indices, updates) = (data, indices, updates);
+                        let mask = data
+                            .zeros_like()
+                            .scatter_nd(indices.clone(), updates.ones_like())
+                            .bool();
+                        let updates = data.zeros_like().scatter_nd(indices, updates);
+
+                        data.mask_where(mask, updates)
+                    };
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,154 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct SplitConfig {
+    pub axis: usize,
+    /// The size of each output along the axis, the input being split in equal chunks otherwise.
+    pub split_sizes: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct SplitNode {
+    pub input: TensorType,
+    pub outputs: Vec<TensorType>,
+    pub config: SplitConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for SplitNode {
+    fn output_types(&self) -> Vec<Type> {
+        self.outputs
+            .iter()
+            .map(|output| Type::Tensor(output.clone()))
+            .collect()
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.input.clone())]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let axis = self.config.axis.to_tokens();
+        let outputs = self.outputs.iter().map(|output| &output.name);
+
+        match &self.config.split_sizes {
+            Some(sizes) => {
+                let num_outputs = sizes.len();
+                let mut start = 0;
+                let narrows = outputs.zip(sizes).enumerate().map(|(i, (output, size))| {
+                    let offset = start.to_tokens();
+                    let length = size.to_tokens();
+                    start += size;
+
+                    // The last output takes ownership of the input
+                    if i + 1 == num_outputs {
+                        quote! { let #output = split_input.narrow(#axis, #offset, #length); }
+                    } else {
+                        quote! { let #output = split_input.clone().narrow(#axis, #offset, #length); }
+                    }
+                });
+
+                quote! {
+                    let split_input = #input;
+                    #(#narrows)*
+                }
+            }
+            None => {
+                let num_outputs = self.outputs.len().to_tokens();
+
+                quote! {
+                    let mut split_tensors = #input.chunk(#num_outputs, #axis).into_iter();
+                    #(let #outputs = split_tensors.next().unwrap();)*
+                }
+            }
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::Split(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    fn codegen(split_sizes: Option<Vec<usize>>) -> TokenStream {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(SplitNode::new(
+            TensorType::new_float("input", 2),
+            vec![
+                TensorType::new_float("output1", 2),
+                TensorType::new_float("output2", 2),
+            ],
+            SplitConfig::new(1).with_split_sizes(split_sizes),
+        ));
+        graph.register_input_output(
+            vec!["input".to_string()],
+            vec!["output1".to_string(), "output2".to_string()],
+        );
+
+        graph.codegen()
+    }
+
+    fn expected(forward: TokenStream) -> TokenStream {
+        quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    input: Tensor<B, 2>
+                ) -> (Tensor<B, 2>, Tensor<B, 2>) {
+                    #forward
+
+                    (output1, output2)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_codegen_split_sizes() {
+        let forward = quote! {
+            let split_input = input;
+            let output1 = split_input.clone().narrow(1, 0, 2);
+            let output2 = split_input.narrow(1, 2, 3);
+        };
+
+        assert_tokens(codegen(Some(vec![2, 3])), expected(forward));
+    }
+
+    #[test]
+    fn test_codegen_split_chunks() {
+        let forward = quote! {
+            let mut split_tensors = input.chunk(2, 1).into_iter();
+            let output1 = split_tensors.next().unwrap();
+            let output2 = split_tensors.next().unwrap();
+        };
+
+        assert_tokens(codegen(None), expected(forward));
+    }
+}
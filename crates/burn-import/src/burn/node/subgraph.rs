@@ -0,0 +1,253 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+
+/// The subgraph of a control flow node (If branch, Loop or Scan body), generated as a nested block
+/// of the forward pass.
+///
+/// The subgraph nodes can use the variables of the enclosing scope, which are captured by the
+/// control flow node.
+#[derive(Debug, Clone)]
+pub struct SubGraph<PS: PrecisionSettings> {
+    /// The nodes in execution order.
+    pub nodes: Vec<Node<PS>>,
+    /// The inputs, bound by the control flow node before the nodes are executed.
+    pub inputs: Vec<Type>,
+    /// The outputs.
+    pub outputs: Vec<Type>,
+}
+
+impl<PS: PrecisionSettings> SubGraph<PS> {
+    /// Create a subgraph.
+    ///
+    /// # Panics
+    ///
+    /// If a node has parameters, since they can't be declared in the model struct.
+    pub fn new(nodes: Vec<Node<PS>>, inputs: Vec<Type>, outputs: Vec<Type>) -> Self {
+        if let Some(node) = nodes.iter().find(|node| node.field_type().is_some()) {
+            panic!(
+                "Nodes with parameters are not supported in subgraphs, found {}",
+                node.name()
+            );
+        }
+
+        Self {
+            nodes,
+            inputs,
+            outputs,
+        }
+    }
+
+    /// The variables of the enclosing scope used by the subgraph, in order of first use.
+    pub fn captured(&self) -> Vec<Type> {
+        let mut defined = self
+            .inputs
+            .iter()
+            .map(|ty| ty.name().clone())
+            .collect::<HashSet<_>>();
+        let mut captured = Vec::new();
+
+        for node in self.nodes.iter() {
+            for ty in node.input_types() {
+                if defined.insert(ty.name().clone()) {
+                    captured.push(ty);
+                }
+            }
+            for ty in node.output_types() {
+                defined.insert(ty.name().clone());
+            }
+        }
+        for ty in self.outputs.iter() {
+            if defined.insert(ty.name().clone()) {
+                captured.push(ty.clone());
+            }
+        }
+
+        captured
+    }
+
+    /// Whether a variable is used by the nodes of the subgraph.
+    pub fn uses(&self, name: &Ident) -> bool {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.input_types())
+            .any(|ty| ty.name() == name)
+    }
+
+    /// Generate the statements of the subgraph and the expressions of its outputs.
+    ///
+    /// The subgraph has its own scope, where the inputs and the captured variables are declared
+    /// before the first node and the outputs are used after the last one. The `kept` variables
+    /// are still needed after the subgraph, such as the loop-carried values that are not updated,
+    /// so they are never moved and their outputs are left out.
+    pub fn codegen(&self, kept: &[Ident]) -> (TokenStream, Vec<Option<TokenStream>>) {
+        let mut scope = Scope::default();
+
+        for tensor in tensors(&self.inputs).chain(tensors(&self.captured())) {
+            scope.tensor_register_variable(&tensor, 0);
+        }
+        for (node_position, node) in self.nodes.iter().enumerate() {
+            for tensor in tensors(&node.output_types()) {
+                scope.tensor_register_variable(&tensor, node_position + 1);
+            }
+            for tensor in tensors(&node.input_types()) {
+                scope.tensor_register_future_use(&tensor, node_position);
+            }
+        }
+        let end_position = self.nodes.len();
+        for tensor in tensors(&self.outputs) {
+            scope.tensor_register_future_use(&tensor, end_position);
+        }
+        for tensor in tensors(&self.inputs).filter(|tensor| kept.contains(&tensor.name)) {
+            scope.tensor_register_future_use(&tensor, end_position);
+        }
+
+        let mut body = quote! {};
+        for (node_position, node) in self.nodes.iter().enumerate() {
+            body.extend(node.forward(&mut scope, node_position));
+        }
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|ty| match ty {
+                _ if kept.contains(ty.name()) => None,
+                Type::Tensor(tensor) => Some(scope.tensor_use_owned(tensor, end_position)),
+                _ => {
+                    let name = ty.name();
+                    Some(quote! { #name })
+                }
+            })
+            .collect();
+
+        (body, outputs)
+    }
+
+    pub fn register_imports(&self, imports: &mut BurnImports) {
+        self.nodes
+            .iter()
+            .for_each(|node| node.register_imports(imports));
+    }
+}
+
+/// Use the tensors captured by the subgraphs of a control flow node, returning the names of the
+/// ones that must be cloned in the nested blocks.
+pub(crate) fn use_captured<PS: PrecisionSettings>(
+    subgraphs: &[&SubGraph<PS>],
+    scope: &mut Scope,
+    node_position: usize,
+) -> HashSet<Ident> {
+    let mut cloned = HashSet::new();
+    for tensor in tensors(&captured_union(subgraphs)) {
+        if scope.tensor_use_captured(&tensor, node_position) {
+            cloned.insert(tensor.name);
+        }
+    }
+
+    cloned
+}
+
+/// The variables captured by any of the subgraphs, without duplicates.
+pub(crate) fn captured_union<PS: PrecisionSettings>(subgraphs: &[&SubGraph<PS>]) -> Vec<Type> {
+    let mut names = HashSet::new();
+    subgraphs
+        .iter()
+        .flat_map(|subgraph| subgraph.captured())
+        .filter(|ty| names.insert(ty.name().clone()))
+        .collect()
+}
+
+/// Clone the captured tensors of the subgraph for which `is_cloned` is true.
+pub(crate) fn clone_captured<PS: PrecisionSettings>(
+    subgraph: &SubGraph<PS>,
+    is_cloned: impl Fn(&Ident) -> bool,
+) -> TokenStream {
+    let names = tensors(&subgraph.captured())
+        .map(|tensor| tensor.name)
+        .filter(|name| is_cloned(name))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(let #names = #names.clone();)*
+    }
+}
+
+/// Generate the update of the loop-carried values and the scan outputs at the end of an iteration
+/// of a Loop or Scan body.
+///
+/// The carried values that are not updated are `None`. All the new values are computed before any
+/// assignment, since they can depend on the current values.
+pub(crate) fn update_loop_outputs(
+    carried: &[(Ident, Option<TokenStream>)],
+    scan_outputs: &[(Ident, TokenStream)],
+) -> TokenStream {
+    let mut next_names = Vec::new();
+    let mut next_values = Vec::new();
+    let mut update = quote! {};
+
+    for (name, value) in carried {
+        if let Some(value) = value {
+            let next = format_ident!("{}_next", name);
+            next_names.push(quote! { #next });
+            next_values.push(value.clone());
+            update.extend(quote! { #name = #next; });
+        }
+    }
+    for (name, value) in scan_outputs {
+        let item = format_ident!("{}_item", name);
+        next_names.push(quote! { #item });
+        next_values.push(value.clone());
+        update.extend(quote! { #name.push(#item); });
+    }
+
+    if next_names.is_empty() {
+        return quote! {};
+    }
+    let next_names = tuple(&next_names);
+    let next_values = tuple(&next_values);
+
+    quote! {
+        let #next_names = #next_values;
+        #update
+    }
+}
+
+/// Use a value of the enclosing scope as an input of a control flow node.
+pub(crate) fn use_value(ty: &Type, scope: &mut Scope, node_position: usize) -> TokenStream {
+    match ty {
+        Type::Tensor(tensor) => scope.tensor_use_owned(tensor, node_position),
+        _ => {
+            let name = ty.name();
+            quote! { #name }
+        }
+    }
+}
+
+/// The names of the outputs of a control flow node.
+pub(crate) fn output_names(outputs: &[Type]) -> Vec<TokenStream> {
+    outputs
+        .iter()
+        .map(|output| {
+            let name = output.name();
+            quote! { #name }
+        })
+        .collect()
+}
+
+/// A single value or a tuple of values.
+pub(crate) fn tuple(values: &[TokenStream]) -> TokenStream {
+    match values {
+        [value] => value.clone(),
+        values => quote! { (#(#values),*) },
+    }
+}
+
+fn tensors(types: &[Type]) -> impl Iterator<Item = TensorType> + '_ {
+    types.iter().filter_map(|ty| match ty {
+        Type::Tensor(tensor) => Some(tensor.clone()),
+        _ => None,
+    })
+}
@@ -0,0 +1,130 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct TopKConfig {
+    pub axis: usize,
+    pub k: usize,
+    /// If the largest elements are returned, the smallest ones being returned otherwise.
+    pub largest: bool,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct TopKNode {
+    pub input: TensorType,
+    pub values: TensorType,
+    pub indices: TensorType,
+    pub config: TopKConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for TopKNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.values.clone()),
+            Type::Tensor(self.indices.clone()),
+        ]
+    }
+    fn input_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.input.clone())]
+    }
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let values = &self.values.name;
+        let indices = &self.indices.name;
+        let k = self.config.k.to_tokens();
+        let axis = self.config.axis.to_tokens();
+
+        // The smallest elements are the largest elements of the negated input
+        if self.config.largest {
+            quote! {
+                let (#values, #indices) = #input.topk_with_indices(#k, #axis);
+            }
+        } else {
+            quote! {
+                let (#values, #indices) = #input.neg().topk_with_indices(#k, #axis);
+                let #values = #values.neg();
+            }
+        }
+    }
+    fn into_node(self) -> super::Node<PS> {
+        Node::TopK(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    fn codegen(largest: bool) -> TokenStream {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+        graph.register(TopKNode::new(
+            TensorType::new_float("input", 2),
+            TensorType::new_float("values", 2),
+            TensorType::new_int("indices", 2),
+            TopKConfig::new(1, 3, largest),
+        ));
+        graph.register_input_output(
+            vec!["input".to_string()],
+            vec!["values".to_string(), "indices".to_string()],
+        );
+
+        graph.codegen()
+    }
+
+    fn expected(forward: TokenStream) -> TokenStream {
+        quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 2>) -> (Tensor<B, 2>, Tensor<B, 2, Int>) {
+                    #forward
+
+                    (values, indices)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_codegen_largest() {
+        let forward = quote! {
+            let (values, indices) = input.topk_with_indices(3, 1);
+        };
+
+        assert_tokens(codegen(true), expected(forward));
+    }
+
+    #[test]
+    fn test_codegen_smallest() {
+        let forward = quote! {
+            let (values, indices) = input.neg().topk_with_indices(3, 1);
+            let values = values.neg();
+        };
+
+        assert_tokens(codegen(false), expected(forward));
+    }
+}
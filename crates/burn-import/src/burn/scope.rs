@@ -44,6 +44,22 @@ impl Scope {
         }
     }
 
+    /// Use a tensor variable captured by a nested block, returning true if the block must clone it
+    /// because the tensor will still be used afterward.
+    pub fn tensor_use_captured(&mut self, tensor: &TensorType, node_position: usize) -> bool {
+        let variable = self
+            .variables
+            .get_mut(&tensor.name)
+            .unwrap_or_else(|| panic!("No variable with name {}", &tensor.name));
+
+        if node_position >= variable.node_position {
+            variable.references -= 1;
+            variable.references > 0
+        } else {
+            false
+        }
+    }
+
     /// Use a tensor variable, cloning it if it was registered multiple times and the tensor will still be used afterward.
     pub fn tensor_use_owned(&mut self, tensor: &TensorType, node_position: usize) -> TokenStream {
         if let Some(variable) = self.variables.get_mut(&tensor.name) {
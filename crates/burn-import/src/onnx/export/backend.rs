@@ -0,0 +1,113 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use burn::tensor::{
+    backend::{Backend, BackendBridge},
+    repr::{OperationDescription, ReprBackend, TensorDescription, TensorId, TensorStatus},
+    try_read_sync, TensorData,
+};
+use burn_router::{BackendRouter, ObservedChannel, ObservedClient, RunnerClient, RunnerObserver};
+
+/// A backend executing the operations on the backend `B` while recording them, so the traced
+/// forward pass of a module can be exported to ONNX.
+pub type OnnxTrace<B> = BackendRouter<TraceChannel<B>>;
+
+/// The channel of the [trace backend](OnnxTrace), with a single runner for the backend `B`.
+pub type TraceChannel<B> = ObservedChannel<B, TraceObserver>;
+
+/// The operations recorded during a trace.
+#[derive(Default, Debug)]
+pub(crate) struct Recording {
+    pub operations: Vec<OperationDescription>,
+    /// The tensors provided to the graph, the module params and the graph inputs.
+    pub provided: HashSet<TensorId>,
+    /// The values of the tensors used by the operations without being created by one of them.
+    pub values: HashMap<TensorId, TensorData>,
+    produced: HashSet<TensorId>,
+}
+
+impl Recording {
+    fn record(
+        &mut self,
+        operation: &OperationDescription,
+        read: impl Fn(&TensorDescription) -> TensorData,
+    ) {
+        for tensor in operation.nodes() {
+            match tensor.status {
+                TensorStatus::NotInit => {
+                    self.produced.insert(tensor.id);
+                }
+                TensorStatus::ReadOnly | TensorStatus::ReadWrite => {
+                    // The value is read before the operation is executed, since a `ReadWrite`
+                    // tensor can be reused by the backend
+                    if !self.provided.contains(&tensor.id)
+                        && !self.produced.contains(&tensor.id)
+                        && !self.values.contains_key(&tensor.id)
+                    {
+                        self.values.insert(tensor.id, read(tensor));
+                    }
+                }
+            }
+        }
+
+        self.operations.push(operation.clone());
+    }
+}
+
+/// Records the operations executed on a device of the [trace backend](OnnxTrace) while an
+/// exporter is alive.
+#[derive(Clone, Default)]
+pub struct TraceObserver {
+    // The active recording, and the condition notified when it ends
+    state: Arc<(Mutex<Option<Recording>>, Condvar)>,
+}
+
+impl TraceObserver {
+    /// Start a recording, blocking until the active one on the device ends.
+    pub(crate) fn start(&self) {
+        let (recording, ended) = &*self.state;
+        let mut recording = ended
+            .wait_while(
+                recording.lock().unwrap_or_else(PoisonError::into_inner),
+                |recording| recording.is_some(),
+            )
+            .unwrap_or_else(PoisonError::into_inner);
+        *recording = Some(Recording::default());
+    }
+
+    /// End the active recording.
+    pub(crate) fn end(&self) {
+        *self.recording() = None;
+        self.state.1.notify_one();
+    }
+
+    pub(crate) fn recording(&self) -> MutexGuard<'_, Option<Recording>> {
+        self.state.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<B: ReprBackend> RunnerObserver<B> for TraceObserver
+where
+    // Restrict full precision backend handle to be the same
+    <<B as Backend>::FullPrecisionBridge as BackendBridge<B>>::Target:
+        ReprBackend<Handle = B::Handle>,
+{
+    fn init(_device: &B::Device) -> Self {
+        Self::default()
+    }
+
+    fn observe(&self, operation: &OperationDescription, client: &ObservedClient<B, Self>) {
+        if let Some(recording) = self.recording().as_mut() {
+            recording.record(operation, |tensor| {
+                let tensor = TensorDescription {
+                    status: TensorStatus::ReadOnly,
+                    ..tensor.clone()
+                };
+                try_read_sync(client.read_tensor(tensor))
+                    .expect("Failed to read the tensor value synchronously")
+            });
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use burn::tensor::DType;
+
+/// Error when exporting a traced module to ONNX.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The model file can't be written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The model can't be serialized.
+    #[error("Protobuf error: {0}")]
+    Protobuf(#[from] protobuf::Error),
+
+    /// A traced operation has no ONNX equivalent.
+    #[error("Unsupported operation {0}")]
+    UnsupportedOperation(String),
+
+    /// A tensor has a data type that can't be represented in ONNX.
+    #[error("Unsupported data type {0:?}")]
+    UnsupportedType(DType),
+
+    /// An output isn't computed by the traced operations.
+    #[error("Output {0} wasn't computed during the trace")]
+    UnknownOutput(String),
+}
@@ -0,0 +1,273 @@
+use core::marker::PhantomData;
+use std::path::Path;
+
+use burn::{
+    module::{Module, ModuleVisitor, ParamId},
+    tensor::{
+        backend::{Backend, BackendBridge},
+        repr::{ReprBackend, TensorId},
+        BasicOps, Bool, DType, Element, Float, Int, Tensor, TensorData, TensorPrimitive,
+    },
+};
+use burn_router::{BackendRouter, RunnerChannel};
+use onnx_ir::protos::{ModelProto, OperatorSetIdProto};
+use protobuf::{Message, MessageField};
+
+use super::{
+    backend::{OnnxTrace, TraceChannel, TraceObserver},
+    graph::{GraphBuilder, NamedTensor},
+    Error,
+};
+
+/// The opset of the exported models.
+const OPSET_VERSION: i64 = 16;
+/// The IR version released with opset 16.
+const IR_VERSION: i64 = 8;
+
+/// Exports the forward pass of a module to ONNX.
+///
+/// The operations executed on the device of the [trace backend](OnnxTrace) are recorded from the
+/// creation of the exporter. The graph keeps the operations computing the outputs from the inputs,
+/// the params of the module becoming initializers. Only one exporter can be alive at a time for a
+/// device, creating another one blocks until the first one is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use burn::{backend::NdArray, nn::LinearConfig, tensor::Tensor};
+/// use burn_import::onnx::export::{OnnxExporter, OnnxTrace};
+///
+/// type Backend = OnnxTrace<NdArray>;
+///
+/// let device = Default::default();
+/// let model = LinearConfig::new(4, 2).init::<Backend>(&device);
+/// let input = Tensor::<Backend, 2>::zeros([1, 4], &device);
+///
+/// let mut exporter = OnnxExporter::<NdArray>::new(&device);
+/// exporter.module(&model).input("input", &input);
+/// let output = model.forward(input);
+/// exporter.output("output", &output).save("linear.onnx").unwrap();
+/// ```
+pub struct OnnxExporter<B: ReprBackend> {
+    name: String,
+    inputs: Vec<NamedTensor>,
+    params: Vec<NamedTensor>,
+    outputs: Vec<NamedTensor>,
+    // Records the operations of the device until the exporter is dropped
+    observer: TraceObserver,
+    backend: PhantomData<B>,
+}
+
+impl<B: ReprBackend> OnnxExporter<B>
+where
+    // Restrict full precision backend handle to be the same
+    <<B as Backend>::FullPrecisionBridge as BackendBridge<B>>::Target:
+        ReprBackend<Handle = B::Handle>,
+{
+    /// Create a new exporter, starting the recording of the operations executed on the device.
+    pub fn new(device: &B::Device) -> Self {
+        let observer = TraceChannel::<B>::observer(device);
+        observer.start();
+
+        Self {
+            name: "main_graph".to_string(),
+            inputs: Vec::new(),
+            params: Vec::new(),
+            outputs: Vec::new(),
+            observer,
+            backend: PhantomData,
+        }
+    }
+
+    /// Set the name of the graph.
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Register the params of the module, exported as initializers named after their id.
+    pub fn module<M: Module<OnnxTrace<B>>>(&mut self, module: &M) -> &mut Self {
+        let mut collector = ParamCollector::default();
+        module.visit(&mut collector);
+
+        if let Some(recording) = self.observer.recording().as_mut() {
+            for (param, value) in collector.params {
+                recording.provided.insert(param.id);
+                recording.values.insert(param.id, value);
+                self.params.push(param);
+            }
+        }
+        self
+    }
+
+    /// Register a graph input, which must be registered before being used.
+    pub fn input<const D: usize, K>(
+        &mut self,
+        name: &str,
+        tensor: &Tensor<OnnxTrace<B>, D, K>,
+    ) -> &mut Self
+    where
+        K: TraceKind<TraceChannel<B>>,
+    {
+        let input = named_tensor(name, tensor);
+        self.observer
+            .recording()
+            .as_mut()
+            .unwrap()
+            .provided
+            .insert(input.id);
+        self.inputs.push(input);
+        self
+    }
+
+    /// Register a graph output, computed by the operations recorded since the exporter creation.
+    pub fn output<const D: usize, K>(
+        &mut self,
+        name: &str,
+        tensor: &Tensor<OnnxTrace<B>, D, K>,
+    ) -> &mut Self
+    where
+        K: TraceKind<TraceChannel<B>>,
+    {
+        self.outputs.push(named_tensor(name, tensor));
+        self
+    }
+
+    /// Serialize the ONNX model.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.model()?.write_to_bytes()?)
+    }
+
+    /// Save the ONNX model to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    fn model(&self) -> Result<ModelProto, Error> {
+        let recording = self.observer.recording();
+        let graph = GraphBuilder::build(
+            &self.name,
+            recording.as_ref().unwrap(),
+            &self.inputs,
+            &self.params,
+            &self.outputs,
+        )?;
+
+        Ok(ModelProto {
+            ir_version: IR_VERSION,
+            producer_name: "burn".to_string(),
+            opset_import: vec![OperatorSetIdProto {
+                version: OPSET_VERSION,
+                ..Default::default()
+            }],
+            graph: MessageField::some(graph),
+            ..Default::default()
+        })
+    }
+}
+
+impl<B: ReprBackend> Drop for OnnxExporter<B> {
+    fn drop(&mut self) {
+        self.observer.end();
+    }
+}
+
+fn named_tensor<R, const D: usize, K>(
+    name: &str,
+    tensor: &Tensor<BackendRouter<R>, D, K>,
+) -> NamedTensor
+where
+    R: RunnerChannel,
+    K: TraceKind<R>,
+{
+    NamedTensor {
+        name: name.to_string(),
+        id: K::id(&tensor.clone().into_primitive()),
+        shape: tensor.shape().dims,
+        dtype: K::dtype(),
+    }
+}
+
+/// A tensor kind whose traced tensors can be registered in the graph.
+pub trait TraceKind<R: RunnerChannel>: BasicOps<BackendRouter<R>> {
+    /// The id of the traced tensor.
+    fn id(primitive: &Self::Primitive) -> TensorId;
+    /// The data type of the tensors of this kind.
+    fn dtype() -> DType;
+}
+
+impl<R: RunnerChannel> TraceKind<R> for Float {
+    fn id(primitive: &Self::Primitive) -> TensorId {
+        match primitive {
+            TensorPrimitive::Float(tensor) => tensor.id(),
+            TensorPrimitive::QFloat(tensor) => tensor.id(),
+        }
+    }
+
+    fn dtype() -> DType {
+        R::FloatElem::dtype()
+    }
+}
+
+impl<R: RunnerChannel> TraceKind<R> for Int {
+    fn id(primitive: &Self::Primitive) -> TensorId {
+        primitive.id()
+    }
+
+    fn dtype() -> DType {
+        R::IntElem::dtype()
+    }
+}
+
+impl<R: RunnerChannel> TraceKind<R> for Bool {
+    fn id(primitive: &Self::Primitive) -> TensorId {
+        primitive.id()
+    }
+
+    fn dtype() -> DType {
+        DType::Bool
+    }
+}
+
+/// Collects the params of a module with their value.
+#[derive(Default)]
+struct ParamCollector {
+    params: Vec<(NamedTensor, TensorData)>,
+}
+
+impl ParamCollector {
+    fn collect<R, const D: usize, K>(
+        &mut self,
+        id: ParamId,
+        tensor: &Tensor<BackendRouter<R>, D, K>,
+    ) where
+        R: RunnerChannel,
+        K: TraceKind<R>,
+    {
+        let param = named_tensor(&id.to_string(), tensor);
+        self.params.push((param, tensor.to_data()));
+    }
+}
+
+impl<R: RunnerChannel> ModuleVisitor<BackendRouter<R>> for ParamCollector {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<BackendRouter<R>, D>) {
+        self.collect(id, tensor);
+    }
+
+    fn visit_int<const D: usize>(
+        &mut self,
+        id: ParamId,
+        tensor: &Tensor<BackendRouter<R>, D, Int>,
+    ) {
+        self.collect(id, tensor);
+    }
+
+    fn visit_bool<const D: usize>(
+        &mut self,
+        id: ParamId,
+        tensor: &Tensor<BackendRouter<R>, D, Bool>,
+    ) {
+        self.collect(id, tensor);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{HashMap, HashSet};
+
+use burn::tensor::{
+    repr::{OperationDescription, TensorDescription, TensorId, TensorStatus},
+    DType, TensorData,
+};
+use onnx_ir::protos::{
+    attribute_proto::AttributeType, tensor_proto::DataType, tensor_shape_proto, type_proto,
+    AttributeProto, GraphProto, NodeProto, TensorProto, TensorShapeProto, TypeProto,
+    ValueInfoProto,
+};
+use protobuf::{EnumOrUnknown, MessageField};
+
+use super::{backend::Recording, Error};
+
+/// A tensor given to the exporter, with the name it has in the graph.
+#[derive(Debug, Clone)]
+pub(crate) struct NamedTensor {
+    pub name: String,
+    pub id: TensorId,
+    pub shape: Vec<usize>,
+    pub dtype: DType,
+}
+
+/// Converts the traced operations to the nodes of an ONNX graph.
+#[derive(Default)]
+pub(crate) struct GraphBuilder {
+    names: HashMap<TensorId, String>,
+    nodes: Vec<NodeProto>,
+    initializers: Vec<TensorProto>,
+    count: usize,
+}
+
+impl GraphBuilder {
+    /// Builds the graph computing the outputs from the inputs, the other tensors used by the
+    /// operations becoming initializers.
+    pub(crate) fn build(
+        name: &str,
+        recording: &Recording,
+        inputs: &[NamedTensor],
+        params: &[NamedTensor],
+        outputs: &[NamedTensor],
+    ) -> Result<GraphProto, Error> {
+        let input_ids = inputs.iter().map(|input| input.id).collect::<HashSet<_>>();
+        let operations = prune(&recording.operations, &recording.provided, outputs);
+
+        let produced = operations
+            .iter()
+            .flat_map(|operation| operation.nodes())
+            .filter(|tensor| tensor.status == TensorStatus::NotInit)
+            .map(|tensor| tensor.id)
+            .collect::<HashSet<_>>();
+        if let Some(output) = outputs
+            .iter()
+            .find(|output| !produced.contains(&output.id) && !input_ids.contains(&output.id))
+        {
+            return Err(Error::UnknownOutput(output.name.clone()));
+        }
+
+        let mut builder = Self::default();
+        for tensor in inputs.iter().chain(params).chain(outputs) {
+            builder.names.insert(tensor.id, tensor.name.clone());
+        }
+
+        let mut initialized = HashSet::new();
+        for tensor in operations.iter().flat_map(|operation| operation.nodes()) {
+            if produced.contains(&tensor.id)
+                || input_ids.contains(&tensor.id)
+                || !initialized.insert(tensor.id)
+            {
+                continue;
+            }
+
+            let value = recording
+                .values
+                .get(&tensor.id)
+                .expect("The values used by the trace are recorded");
+            let name = builder.name(tensor);
+            builder.initializers.push(tensor_proto(&name, value)?);
+        }
+
+        for operation in operations {
+            builder.operation(operation)?;
+        }
+
+        Ok(GraphProto {
+            name: name.to_string(),
+            node: builder.nodes,
+            initializer: builder.initializers,
+            input: inputs.iter().map(value_info).collect::<Result<_, _>>()?,
+            output: outputs.iter().map(value_info).collect::<Result<_, _>>()?,
+            ..Default::default()
+        })
+    }
+
+    /// The name of the tensor in the graph.
+    pub(crate) fn name(&mut self, tensor: &TensorDescription) -> String {
+        if let Some(name) = self.names.get(&tensor.id) {
+            return name.clone();
+        }
+
+        self.count += 1;
+        let name = format!("tensor{}", self.count);
+        self.names.insert(tensor.id, name.clone());
+        name
+    }
+
+    /// Adds a node computing the outputs from the inputs.
+    pub(crate) fn node(
+        &mut self,
+        op_type: &str,
+        inputs: Vec<String>,
+        outputs: Vec<String>,
+        attributes: Vec<AttributeProto>,
+    ) {
+        self.nodes.push(NodeProto {
+            name: format!("/{op_type}_{}", self.nodes.len()),
+            op_type: op_type.to_string(),
+            input: inputs,
+            output: outputs,
+            attribute: attributes,
+            ..Default::default()
+        });
+    }
+
+    /// A fresh name for an intermediate tensor that isn't part of the trace.
+    pub(crate) fn intermediate(&mut self) -> String {
+        self.count += 1;
+        format!("tensor{}", self.count)
+    }
+
+    /// Adds an initializer with the given value.
+    pub(crate) fn constant(&mut self, value: TensorData) -> Result<String, Error> {
+        let name = self.intermediate();
+        self.initializers.push(tensor_proto(&name, &value)?);
+        Ok(name)
+    }
+
+    /// Adds a 1D `int64` initializer, used for the shapes and axes.
+    pub(crate) fn constant_ints(&mut self, values: Vec<i64>) -> Result<String, Error> {
+        let len = values.len();
+        self.constant(TensorData::new(values, [len]))
+    }
+
+    /// Adds a scalar initializer of the given type.
+    pub(crate) fn constant_scalar(&mut self, value: f64, dtype: DType) -> Result<String, Error> {
+        let data = scalar_data(value, dtype, Vec::new())?;
+        self.constant(data)
+    }
+}
+
+/// Keeps the operations needed to compute the outputs, stopping at the provided tensors since the
+/// operations creating the inputs and initializing the params aren't part of the graph.
+fn prune<'a>(
+    operations: &'a [OperationDescription],
+    provided: &HashSet<TensorId>,
+    outputs: &[NamedTensor],
+) -> Vec<&'a OperationDescription> {
+    let mut needed = outputs
+        .iter()
+        .map(|output| output.id)
+        .collect::<HashSet<_>>();
+    let mut kept = Vec::new();
+
+    for operation in operations.iter().rev() {
+        let nodes = operation.nodes();
+        let is_needed = nodes.iter().any(|tensor| {
+            tensor.status == TensorStatus::NotInit
+                && needed.contains(&tensor.id)
+                && !provided.contains(&tensor.id)
+        });
+
+        if is_needed {
+            for tensor in nodes {
+                if tensor.status != TensorStatus::NotInit {
+                    needed.insert(tensor.id);
+                }
+            }
+            kept.push(operation);
+        }
+    }
+
+    kept.reverse();
+    kept
+}
+
+/// A tensor filled with the value converted to the given type.
+pub(crate) fn scalar_data(
+    value: f64,
+    dtype: DType,
+    shape: Vec<usize>,
+) -> Result<TensorData, Error> {
+    let num_elements = shape.iter().product();
+    Ok(match dtype {
+        DType::F64 => TensorData::new(vec![value; num_elements], shape),
+        DType::F32 => TensorData::new(vec![value as f32; num_elements], shape),
+        DType::F16 => TensorData::new(vec![half::f16::from_f64(value); num_elements], shape),
+        DType::BF16 => TensorData::new(vec![half::bf16::from_f64(value); num_elements], shape),
+        DType::I64 => TensorData::new(vec![value as i64; num_elements], shape),
+        DType::I32 => TensorData::new(vec![value as i32; num_elements], shape),
+        DType::I16 => TensorData::new(vec![value as i16; num_elements], shape),
+        DType::I8 => TensorData::new(vec![value as i8; num_elements], shape),
+        DType::U64 => TensorData::new(vec![value as u64; num_elements], shape),
+        DType::U32 => TensorData::new(vec![value as u32; num_elements], shape),
+        DType::U8 => TensorData::new(vec![value as u8; num_elements], shape),
+        DType::Bool => TensorData::new(vec![value != 0.; num_elements], shape),
+        dtype @ DType::QFloat(_) => return Err(Error::UnsupportedType(dtype)),
+    })
+}
+
+pub(crate) fn data_type(dtype: DType) -> Result<DataType, Error> {
+    Ok(match dtype {
+        DType::F64 => DataType::DOUBLE,
+        DType::F32 => DataType::FLOAT,
+        DType::F16 => DataType::FLOAT16,
+        DType::BF16 => DataType::BFLOAT16,
+        DType::I64 => DataType::INT64,
+        DType::I32 => DataType::INT32,
+        DType::I16 => DataType::INT16,
+        DType::I8 => DataType::INT8,
+        DType::U64 => DataType::UINT64,
+        DType::U32 => DataType::UINT32,
+        DType::U8 => DataType::UINT8,
+        DType::Bool => DataType::BOOL,
+        dtype @ DType::QFloat(_) => return Err(Error::UnsupportedType(dtype)),
+    })
+}
+
+/// Stores the value in the raw data of the tensor, the bytes are little-endian.
+fn tensor_proto(name: &str, value: &TensorData) -> Result<TensorProto, Error> {
+    Ok(TensorProto {
+        name: name.to_string(),
+        dims: value.shape.iter().map(|dim| *dim as i64).collect(),
+        data_type: data_type(value.dtype)? as i32,
+        raw_data: value.as_bytes().to_vec(),
+        ..Default::default()
+    })
+}
+
+fn value_info(tensor: &NamedTensor) -> Result<ValueInfoProto, Error> {
+    let dims = tensor
+        .shape
+        .iter()
+        .map(|dim| tensor_shape_proto::Dimension {
+            value: Some(tensor_shape_proto::dimension::Value::DimValue(*dim as i64)),
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(ValueInfoProto {
+        name: tensor.name.clone(),
+        type_: MessageField::some(TypeProto {
+            value: Some(type_proto::Value::TensorType(type_proto::Tensor {
+                elem_type: data_type(tensor.dtype)? as i32,
+                shape: MessageField::some(TensorShapeProto {
+                    dim: dims,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+pub(crate) fn attr_int(name: &str, value: i64) -> AttributeProto {
+    AttributeProto {
+        name: name.to_string(),
+        type_: EnumOrUnknown::new(AttributeType::INT),
+        i: value,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn attr_ints(name: &str, values: Vec<i64>) -> AttributeProto {
+    AttributeProto {
+        name: name.to_string(),
+        type_: EnumOrUnknown::new(AttributeType::INTS),
+        ints: values,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn attr_string(name: &str, value: &str) -> AttributeProto {
+    AttributeProto {
+        name: name.to_string(),
+        type_: EnumOrUnknown::new(AttributeType::STRING),
+        s: value.as_bytes().to_vec(),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn attr_tensor(name: &str, value: TensorData) -> Result<AttributeProto, Error> {
+    Ok(AttributeProto {
+        name: name.to_string(),
+        type_: EnumOrUnknown::new(AttributeType::TENSOR),
+        t: MessageField::some(tensor_proto("", &value)?),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,17 @@
+//! Export of Burn modules to ONNX.
+//!
+//! The forward pass of a module is traced on the [trace backend](OnnxTrace), a router backend
+//! executing the operations on an inner backend while recording their
+//! [descriptions](burn::tensor::repr::OperationDescription) per device. The recorded operations are then
+//! mapped to ONNX nodes and written as a `ModelProto`, with the params of the module as
+//! initializers.
+
+mod backend;
+mod error;
+mod exporter;
+mod graph;
+mod node;
+
+pub use backend::{OnnxTrace, TraceChannel, TraceObserver};
+pub use error::*;
+pub use exporter::{OnnxExporter, TraceKind};
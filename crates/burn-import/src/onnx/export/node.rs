@@ -0,0 +1,571 @@
+use core::fmt::Debug;
+
+use burn::tensor::{
+    repr::{
+        BaseOperationDescription, BinaryOperationDescription, BoolOperationDescription,
+        FloatOperationDescription, IntOperationDescription, ModuleOperationDescription,
+        NumericOperationDescription, OperationDescription, ScalarOperationDescription,
+        TensorDescription, UnaryOperationDescription,
+    },
+    DType,
+};
+use onnx_ir::protos::AttributeProto;
+
+use super::{
+    graph::{attr_int, attr_ints, attr_string, attr_tensor, data_type, scalar_data, GraphBuilder},
+    Error,
+};
+
+impl GraphBuilder {
+    /// Adds the nodes computing a traced operation.
+    pub(crate) fn operation(&mut self, operation: &OperationDescription) -> Result<(), Error> {
+        match operation {
+            OperationDescription::BaseFloat(op)
+            | OperationDescription::BaseInt(op)
+            | OperationDescription::BaseBool(op) => self.base(op),
+            OperationDescription::NumericFloat(dtype, op) => self.numeric(op, *dtype),
+            OperationDescription::NumericInt(dtype, op) => self.numeric(op, *dtype),
+            OperationDescription::Bool(op) => match op {
+                BoolOperationDescription::IntoFloat(desc)
+                | BoolOperationDescription::IntoInt(desc) => self.cast(desc),
+                BoolOperationDescription::Not(desc) => self.unary("Not", desc),
+            },
+            OperationDescription::Int(op) => match op {
+                IntOperationDescription::IntoFloat(desc) => self.cast(desc),
+            },
+            OperationDescription::Float(dtype, op) => self.float(op, *dtype),
+            OperationDescription::Module(op) => self.module(op),
+        }
+    }
+
+    fn base(&mut self, op: &BaseOperationDescription) -> Result<(), Error> {
+        match op {
+            BaseOperationDescription::Reshape(desc) => {
+                let input = self.name(&desc.input);
+                let shape = self.constant_ints(ints(&desc.out.shape))?;
+                self.emit("Reshape", vec![input, shape], &desc.out, vec![]);
+            }
+            BaseOperationDescription::SwapDims(desc) => {
+                let mut perm = (0..desc.input.shape.len() as i64).collect::<Vec<_>>();
+                perm.swap(desc.dim1, desc.dim2);
+                let input = self.name(&desc.input);
+                self.emit(
+                    "Transpose",
+                    vec![input],
+                    &desc.out,
+                    vec![attr_ints("perm", perm)],
+                );
+            }
+            BaseOperationDescription::Permute(desc) => {
+                let input = self.name(&desc.input);
+                let perm = attr_ints("perm", ints(&desc.axes));
+                self.emit("Transpose", vec![input], &desc.out, vec![perm]);
+            }
+            BaseOperationDescription::Flip(desc) => {
+                // A slice with a negative step going through the whole dimension
+                let num_axes = desc.axes.len();
+                let input = self.name(&desc.input);
+                let starts = self.constant_ints(vec![-1; num_axes])?;
+                let ends = self.constant_ints(vec![i64::MIN; num_axes])?;
+                let axes = self.constant_ints(ints(&desc.axes))?;
+                let steps = self.constant_ints(vec![-1; num_axes])?;
+                let inputs = vec![input, starts, ends, axes, steps];
+                self.emit("Slice", inputs, &desc.out, vec![]);
+            }
+            BaseOperationDescription::Expand(desc) => {
+                let input = self.name(&desc.input);
+                let shape = self.constant_ints(ints(&desc.shape))?;
+                self.emit("Expand", vec![input, shape], &desc.out, vec![]);
+            }
+            BaseOperationDescription::Slice(desc) => {
+                let input = self.name(&desc.tensor);
+                let starts = desc.ranges.iter().map(|range| range.start as i64).collect();
+                let starts = self.constant_ints(starts)?;
+                let ends = desc.ranges.iter().map(|range| range.end as i64).collect();
+                let ends = self.constant_ints(ends)?;
+                let axes = self.constant_ints((0..desc.ranges.len() as i64).collect())?;
+                self.emit("Slice", vec![input, starts, ends, axes], &desc.out, vec![]);
+            }
+            BaseOperationDescription::Equal(desc) => self.binary("Equal", desc),
+            BaseOperationDescription::RepeatDim(desc) => {
+                let mut repeats = vec![1; desc.tensor.shape.len()];
+                repeats[desc.dim] = desc.times as i64;
+                let input = self.name(&desc.tensor);
+                let repeats = self.constant_ints(repeats)?;
+                self.emit("Tile", vec![input, repeats], &desc.out, vec![]);
+            }
+            BaseOperationDescription::Cat(desc) => {
+                let inputs = desc
+                    .tensors
+                    .iter()
+                    .map(|tensor| self.name(tensor))
+                    .collect();
+                let axis = attr_int("axis", desc.dim as i64);
+                self.emit("Concat", inputs, &desc.out, vec![axis]);
+            }
+            BaseOperationDescription::Cast(desc) => return self.cast(desc),
+            op => return Err(unsupported(op)),
+        }
+
+        Ok(())
+    }
+
+    fn numeric<E>(&mut self, op: &NumericOperationDescription<E>, dtype: DType) -> Result<(), Error>
+    where
+        E: Copy + Into<f64> + Debug,
+    {
+        match op {
+            NumericOperationDescription::Add(desc) => self.binary("Add", desc),
+            NumericOperationDescription::AddScalar(desc) => self.scalar("Add", desc)?,
+            NumericOperationDescription::Sub(desc) => self.binary("Sub", desc),
+            NumericOperationDescription::SubScalar(desc) => self.scalar("Sub", desc)?,
+            NumericOperationDescription::Mul(desc) => self.binary("Mul", desc),
+            NumericOperationDescription::MulScalar(desc) => self.scalar("Mul", desc)?,
+            NumericOperationDescription::Div(desc) => self.binary("Div", desc),
+            NumericOperationDescription::DivScalar(desc) => self.scalar("Div", desc)?,
+            NumericOperationDescription::Powf(desc) => self.binary("Pow", desc),
+            NumericOperationDescription::Abs(desc) => self.unary("Abs", desc)?,
+            NumericOperationDescription::Zeros(out) => self.constant_of_shape(out, 0.)?,
+            NumericOperationDescription::Ones(out) => self.constant_of_shape(out, 1.)?,
+            NumericOperationDescription::Full((out, value)) => {
+                self.constant_of_shape(out, (*value).into())?
+            }
+            NumericOperationDescription::Gather(desc) => {
+                let inputs = vec![self.name(&desc.tensor), self.name(&desc.indices)];
+                let axis = attr_int("axis", desc.dim as i64);
+                self.emit("GatherElements", inputs, &desc.out, vec![axis]);
+            }
+            NumericOperationDescription::Scatter(desc) => {
+                let inputs = vec![
+                    self.name(&desc.tensor),
+                    self.name(&desc.indices),
+                    self.name(&desc.value),
+                ];
+                let attributes = vec![
+                    attr_int("axis", desc.dim as i64),
+                    attr_string("reduction", "add"),
+                ];
+                self.emit("ScatterElements", inputs, &desc.out, attributes);
+            }
+            NumericOperationDescription::Select(desc) => {
+                let inputs = vec![self.name(&desc.tensor), self.name(&desc.indices)];
+                let axis = attr_int("axis", desc.dim as i64);
+                self.emit("Gather", inputs, &desc.out, vec![axis]);
+            }
+            NumericOperationDescription::MaskWhere(desc) => {
+                let inputs = vec![
+                    self.name(&desc.mask),
+                    self.name(&desc.value),
+                    self.name(&desc.tensor),
+                ];
+                self.emit("Where", inputs, &desc.out, vec![]);
+            }
+            NumericOperationDescription::MaskFill(desc) => {
+                let mask = self.name(&desc.mask);
+                let value = self.constant_scalar(desc.value.into(), dtype)?;
+                let tensor = self.name(&desc.tensor);
+                self.emit("Where", vec![mask, value, tensor], &desc.out, vec![]);
+            }
+            NumericOperationDescription::MeanDim(desc) => self.reduce("ReduceMean", desc)?,
+            NumericOperationDescription::SumDim(desc) => self.reduce("ReduceSum", desc)?,
+            NumericOperationDescription::ProdDim(desc) => self.reduce("ReduceProd", desc)?,
+            NumericOperationDescription::MaxDim(desc) => self.reduce("ReduceMax", desc)?,
+            NumericOperationDescription::MinDim(desc) => self.reduce("ReduceMin", desc)?,
+            NumericOperationDescription::Mean(desc) => self.reduce_all("ReduceMean", desc)?,
+            NumericOperationDescription::Sum(desc) => self.reduce_all("ReduceSum", desc)?,
+            NumericOperationDescription::Prod(desc) => self.reduce_all("ReduceProd", desc)?,
+            NumericOperationDescription::Max(desc) => self.reduce_all("ReduceMax", desc)?,
+            NumericOperationDescription::Min(desc) => self.reduce_all("ReduceMin", desc)?,
+            NumericOperationDescription::EqualElem(desc) => self.scalar("Equal", desc)?,
+            NumericOperationDescription::Greater(desc) => self.binary("Greater", desc),
+            NumericOperationDescription::GreaterElem(desc) => self.scalar("Greater", desc)?,
+            NumericOperationDescription::GreaterEqual(desc) => self.binary("GreaterOrEqual", desc),
+            NumericOperationDescription::GreaterEqualElem(desc) => {
+                self.scalar("GreaterOrEqual", desc)?
+            }
+            NumericOperationDescription::Lower(desc) => self.binary("Less", desc),
+            NumericOperationDescription::LowerElem(desc) => self.scalar("Less", desc)?,
+            NumericOperationDescription::LowerEqual(desc) => self.binary("LessOrEqual", desc),
+            NumericOperationDescription::LowerEqualElem(desc) => {
+                self.scalar("LessOrEqual", desc)?
+            }
+            NumericOperationDescription::ArgMax(desc) => {
+                let input = self.name(&desc.lhs);
+                self.arg("ArgMax", input, desc.rhs, &desc.out)?;
+            }
+            NumericOperationDescription::ArgMin(desc) => {
+                let input = self.name(&desc.lhs);
+                self.arg("ArgMin", input, desc.rhs, &desc.out)?;
+            }
+            NumericOperationDescription::MaxDimWithIndices(desc) => {
+                let input = self.name(&desc.tensor);
+                let attributes = vec![
+                    attr_ints("axes", vec![desc.dim as i64]),
+                    attr_int("keepdims", 1),
+                ];
+                self.emit("ReduceMax", vec![input.clone()], &desc.out, attributes);
+                self.arg("ArgMax", input, desc.dim, &desc.out_indices)?;
+            }
+            NumericOperationDescription::MinDimWithIndices(desc) => {
+                let input = self.name(&desc.tensor);
+                let attributes = vec![
+                    attr_ints("axes", vec![desc.dim as i64]),
+                    attr_int("keepdims", 1),
+                ];
+                self.emit("ReduceMin", vec![input.clone()], &desc.out, attributes);
+                self.arg("ArgMin", input, desc.dim, &desc.out_indices)?;
+            }
+            NumericOperationDescription::Clamp(desc) => {
+                let input = self.name(&desc.tensor);
+                let min = self.constant_scalar(desc.min.into(), dtype)?;
+                let max = self.constant_scalar(desc.max.into(), dtype)?;
+                self.emit("Clip", vec![input, min, max], &desc.out, vec![]);
+            }
+            op => return Err(unsupported(op)),
+        }
+
+        Ok(())
+    }
+
+    fn float(&mut self, op: &FloatOperationDescription, dtype: DType) -> Result<(), Error> {
+        match op {
+            FloatOperationDescription::Exp(desc) => self.unary("Exp", desc),
+            FloatOperationDescription::Log(desc) => self.unary("Log", desc),
+            FloatOperationDescription::Log1p(desc) => {
+                let input = self.name(&desc.input);
+                let one = self.constant_scalar(1., dtype)?;
+                let sum = self.intermediate();
+                self.node("Add", vec![input, one], vec![sum.clone()], vec![]);
+                self.emit("Log", vec![sum], &desc.out, vec![]);
+                Ok(())
+            }
+            FloatOperationDescription::Erf(desc) => self.unary("Erf", desc),
+            FloatOperationDescription::PowfScalar(desc) => self.scalar("Pow", desc),
+            FloatOperationDescription::Sqrt(desc) => self.unary("Sqrt", desc),
+            FloatOperationDescription::Cos(desc) => self.unary("Cos", desc),
+            FloatOperationDescription::Sin(desc) => self.unary("Sin", desc),
+            FloatOperationDescription::Tanh(desc) => self.unary("Tanh", desc),
+            FloatOperationDescription::Round(desc) => self.unary("Round", desc),
+            FloatOperationDescription::Floor(desc) => self.unary("Floor", desc),
+            FloatOperationDescription::Ceil(desc) => self.unary("Ceil", desc),
+            FloatOperationDescription::Recip(desc) => self.unary("Reciprocal", desc),
+            FloatOperationDescription::IntoInt(desc) => self.cast(desc),
+            FloatOperationDescription::Matmul(desc) => {
+                self.binary("MatMul", desc);
+                Ok(())
+            }
+            op => Err(unsupported(op)),
+        }
+    }
+
+    fn module(&mut self, op: &ModuleOperationDescription) -> Result<(), Error> {
+        match op {
+            ModuleOperationDescription::Embedding(desc) => {
+                let inputs = vec![self.name(&desc.weights), self.name(&desc.indices)];
+                self.emit("Gather", inputs, &desc.out, vec![attr_int("axis", 0)]);
+            }
+            ModuleOperationDescription::Conv1d(desc) => {
+                let options = &desc.options;
+                let attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                self.conv(
+                    "Conv",
+                    &desc.x,
+                    &desc.weight,
+                    &desc.bias,
+                    &desc.out,
+                    attributes,
+                );
+            }
+            ModuleOperationDescription::Conv2d(desc) => {
+                let options = &desc.options;
+                let attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                self.conv(
+                    "Conv",
+                    &desc.x,
+                    &desc.weight,
+                    &desc.bias,
+                    &desc.out,
+                    attributes,
+                );
+            }
+            ModuleOperationDescription::Conv3d(desc) => {
+                let options = &desc.options;
+                let attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                self.conv(
+                    "Conv",
+                    &desc.x,
+                    &desc.weight,
+                    &desc.bias,
+                    &desc.out,
+                    attributes,
+                );
+            }
+            ModuleOperationDescription::ConvTranspose1d(desc) => {
+                let options = &desc.options;
+                let mut attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                attributes.push(attr_ints("output_padding", ints(&options.padding_out)));
+                let (x, weight, bias) = (&desc.x, &desc.weight, &desc.bias);
+                self.conv("ConvTranspose", x, weight, bias, &desc.out, attributes);
+            }
+            ModuleOperationDescription::ConvTranspose2d(desc) => {
+                let options = &desc.options;
+                let mut attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                attributes.push(attr_ints("output_padding", ints(&options.padding_out)));
+                let (x, weight, bias) = (&desc.x, &desc.weight, &desc.bias);
+                self.conv("ConvTranspose", x, weight, bias, &desc.out, attributes);
+            }
+            ModuleOperationDescription::ConvTranspose3d(desc) => {
+                let options = &desc.options;
+                let mut attributes = conv_attributes(
+                    &desc.weight.shape[2..],
+                    &options.stride,
+                    &options.padding,
+                    &options.dilation,
+                    options.groups,
+                );
+                attributes.push(attr_ints("output_padding", ints(&options.padding_out)));
+                let (x, weight, bias) = (&desc.x, &desc.weight, &desc.bias);
+                self.conv("ConvTranspose", x, weight, bias, &desc.out, attributes);
+            }
+            ModuleOperationDescription::AvgPool1d(desc) => {
+                let input = self.name(&desc.x);
+                let attributes = vec![
+                    attr_ints("kernel_shape", vec![desc.kernel_size as i64]),
+                    attr_ints("strides", vec![desc.stride as i64]),
+                    attr_ints("pads", vec![desc.padding as i64; 2]),
+                    attr_int("count_include_pad", desc.count_include_pad as i64),
+                ];
+                self.emit("AveragePool", vec![input], &desc.out, attributes);
+            }
+            ModuleOperationDescription::AvgPool2d(desc) => {
+                let input = self.name(&desc.x);
+                let attributes = vec![
+                    attr_ints("kernel_shape", ints(&desc.kernel_size)),
+                    attr_ints("strides", ints(&desc.stride)),
+                    attr_ints("pads", pads(&desc.padding)),
+                    attr_int("count_include_pad", desc.count_include_pad as i64),
+                ];
+                self.emit("AveragePool", vec![input], &desc.out, attributes);
+            }
+            ModuleOperationDescription::AdaptiveAvgPool1d(desc) if desc.output_size == 1 => {
+                let input = self.name(&desc.x);
+                self.emit("GlobalAveragePool", vec![input], &desc.out, vec![]);
+            }
+            ModuleOperationDescription::AdaptiveAvgPool2d(desc) if desc.output_size == [1, 1] => {
+                let input = self.name(&desc.x);
+                self.emit("GlobalAveragePool", vec![input], &desc.out, vec![]);
+            }
+            ModuleOperationDescription::MaxPool1d(desc) => {
+                let input = self.name(&desc.x);
+                let attributes = vec![
+                    attr_ints("kernel_shape", vec![desc.kernel_size as i64]),
+                    attr_ints("strides", vec![desc.stride as i64]),
+                    attr_ints("pads", vec![desc.padding as i64; 2]),
+                    attr_ints("dilations", vec![desc.dilation as i64]),
+                ];
+                self.emit("MaxPool", vec![input], &desc.out, attributes);
+            }
+            ModuleOperationDescription::MaxPool2d(desc) => {
+                let input = self.name(&desc.x);
+                let attributes = vec![
+                    attr_ints("kernel_shape", ints(&desc.kernel_size)),
+                    attr_ints("strides", ints(&desc.stride)),
+                    attr_ints("pads", pads(&desc.padding)),
+                    attr_ints("dilations", ints(&desc.dilation)),
+                ];
+                self.emit("MaxPool", vec![input], &desc.out, attributes);
+            }
+            op => return Err(unsupported(op)),
+        }
+
+        Ok(())
+    }
+
+    /// Adds a node with a single traced output.
+    fn emit(
+        &mut self,
+        op_type: &str,
+        inputs: Vec<String>,
+        out: &TensorDescription,
+        attributes: Vec<AttributeProto>,
+    ) {
+        let output = self.name(out);
+        self.node(op_type, inputs, vec![output], attributes);
+    }
+
+    fn unary(&mut self, op_type: &str, desc: &UnaryOperationDescription) -> Result<(), Error> {
+        let input = self.name(&desc.input);
+        self.emit(op_type, vec![input], &desc.out, vec![]);
+        Ok(())
+    }
+
+    fn binary(&mut self, op_type: &str, desc: &BinaryOperationDescription) {
+        let inputs = vec![self.name(&desc.lhs), self.name(&desc.rhs)];
+        self.emit(op_type, inputs, &desc.out, vec![]);
+    }
+
+    /// A binary node whose right-hand side is a scalar of the same type as the left-hand side.
+    fn scalar<E>(
+        &mut self,
+        op_type: &str,
+        desc: &ScalarOperationDescription<E>,
+    ) -> Result<(), Error>
+    where
+        E: Copy + Into<f64>,
+    {
+        let lhs = self.name(&desc.lhs);
+        let rhs = self.constant_scalar(desc.rhs.into(), desc.lhs.dtype)?;
+        self.emit(op_type, vec![lhs, rhs], &desc.out, vec![]);
+        Ok(())
+    }
+
+    fn cast(&mut self, desc: &UnaryOperationDescription) -> Result<(), Error> {
+        let input = self.name(&desc.input);
+        let to = attr_int("to", data_type(desc.out.dtype)? as i64);
+        self.emit("Cast", vec![input], &desc.out, vec![to]);
+        Ok(())
+    }
+
+    /// Reduces a single dimension, which is kept.
+    fn reduce(
+        &mut self,
+        op_type: &str,
+        desc: &ScalarOperationDescription<usize>,
+    ) -> Result<(), Error> {
+        let input = self.name(&desc.lhs);
+        let axes = vec![desc.rhs as i64];
+        let keepdims = attr_int("keepdims", 1);
+
+        // Only `ReduceSum` takes the axes as an input in opset 16
+        if op_type == "ReduceSum" {
+            let axes = self.constant_ints(axes)?;
+            self.emit(op_type, vec![input, axes], &desc.out, vec![keepdims]);
+        } else {
+            let axes = attr_ints("axes", axes);
+            self.emit(op_type, vec![input], &desc.out, vec![axes, keepdims]);
+        }
+        Ok(())
+    }
+
+    /// Reduces all the dimensions, the output has a single element like in Burn.
+    fn reduce_all(&mut self, op_type: &str, desc: &UnaryOperationDescription) -> Result<(), Error> {
+        let input = self.name(&desc.input);
+        let reduced = self.intermediate();
+        let keepdims = attr_int("keepdims", 0);
+        self.node(op_type, vec![input], vec![reduced.clone()], vec![keepdims]);
+
+        let shape = self.constant_ints(vec![1])?;
+        self.emit("Reshape", vec![reduced, shape], &desc.out, vec![]);
+        Ok(())
+    }
+
+    /// The indices of the extremum along a dimension, which is kept.
+    fn arg(
+        &mut self,
+        op_type: &str,
+        input: String,
+        dim: usize,
+        out: &TensorDescription,
+    ) -> Result<(), Error> {
+        let attributes = vec![attr_int("axis", dim as i64), attr_int("keepdims", 1)];
+
+        // The indices are always `int64` in ONNX
+        if out.dtype == DType::I64 {
+            self.emit(op_type, vec![input], out, attributes);
+        } else {
+            let indices = self.intermediate();
+            self.node(op_type, vec![input], vec![indices.clone()], attributes);
+            let to = attr_int("to", data_type(out.dtype)? as i64);
+            self.emit("Cast", vec![indices], out, vec![to]);
+        }
+        Ok(())
+    }
+
+    fn constant_of_shape(&mut self, out: &TensorDescription, value: f64) -> Result<(), Error> {
+        let shape = self.constant_ints(ints(&out.shape))?;
+        let value = attr_tensor("value", scalar_data(value, out.dtype, vec![1])?)?;
+        self.emit("ConstantOfShape", vec![shape], out, vec![value]);
+        Ok(())
+    }
+
+    fn conv(
+        &mut self,
+        op_type: &str,
+        x: &TensorDescription,
+        weight: &TensorDescription,
+        bias: &Option<TensorDescription>,
+        out: &TensorDescription,
+        attributes: Vec<AttributeProto>,
+    ) {
+        let mut inputs = vec![self.name(x), self.name(weight)];
+        if let Some(bias) = bias {
+            inputs.push(self.name(bias));
+        }
+        self.emit(op_type, inputs, out, attributes);
+    }
+}
+
+fn ints(values: &[usize]) -> Vec<i64> {
+    values.iter().map(|value| *value as i64).collect()
+}
+
+/// The padding at the beginning and at the end of each dimension.
+fn pads(padding: &[usize]) -> Vec<i64> {
+    ints(padding).repeat(2)
+}
+
+fn conv_attributes(
+    kernel_size: &[usize],
+    stride: &[usize],
+    padding: &[usize],
+    dilation: &[usize],
+    groups: usize,
+) -> Vec<AttributeProto> {
+    vec![
+        attr_ints("kernel_shape", ints(kernel_size)),
+        attr_ints("strides", ints(stride)),
+        attr_ints("pads", pads(padding)),
+        attr_ints("dilations", ints(dilation)),
+        attr_int("group", groups as i64),
+    ]
+}
+
+/// The error of an operation without ONNX equivalent, named after its variant.
+fn unsupported<T: Debug>(op: &T) -> Error {
+    let description = format!("{op:?}");
+    let name = description
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    Error::UnsupportedOperation(name.to_string())
+}
@@ -1,3 +1,10 @@
 mod op_configuration;
 mod to_burn;
+
 pub use to_burn::*;
+
+/// Export of Burn modules to ONNX.
+#[cfg(feature = "onnx-export")]
+pub mod export;
+/// Runtime evaluation of ONNX models.
+pub mod runtime;
@@ -4,12 +4,23 @@ use burn::nn::{
         ConvTranspose3dConfig,
     },
     pool::{AvgPool1dConfig, AvgPool2dConfig, MaxPool1dConfig, MaxPool2dConfig},
-    BatchNormConfig, DropoutConfig, LayerNormConfig, LinearConfig, PaddingConfig1d,
-    PaddingConfig2d, PaddingConfig3d,
+    BatchNormConfig, DropoutConfig, GroupNormConfig, InstanceNormConfig, LayerNormConfig,
+    LinearConfig, PaddingConfig1d, PaddingConfig2d, PaddingConfig3d,
 };
 
 use crate::burn::node::{
-    expand::ExpandShape, pad::PadConfig, tile::TileConfig, trilu::TriluConfig,
+    einsum::EinsumConfig,
+    expand::ExpandShape,
+    non_max_suppression::NonMaxSuppressionConfig,
+    one_hot::OneHotConfig,
+    pad::PadConfig,
+    recurrent::{RecurrentConfig, RecurrentDirection},
+    roi_align::{RoiAlignConfig, RoiAlignPooling},
+    scatter_elements::{ScatterElementsConfig, ScatterReduction},
+    split::SplitConfig,
+    tile::TileConfig,
+    top_k::TopKConfig,
+    trilu::TriluConfig,
 };
 use onnx_ir::ir::{ArgType, AttributeValue, Data, ElementType, Node};
 
@@ -778,6 +789,38 @@ pub fn argmax_config(node: &Node) -> usize {
     axis as usize
 }
 
+/// Create argmin config from the attributes of the node, returning the axis and keepdims
+pub fn argmin_config(node: &Node) -> (usize, bool) {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("ArgMin: only tensor input is valid"),
+    };
+
+    let mut axis: i64 = 0;
+    let mut keepdims = true;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "axis" => axis = value.clone().into_i64(),
+            "keepdims" => keepdims = value.clone().into_i64() != 0,
+            "select_last_index" if value.clone().into_i64() != 0 => {
+                log::warn!(
+                    "only select_last_index=0 is supported for argmin in burn. Ignoring supplied value (got {:?})",
+                    value
+                );
+            }
+            _ => {}
+        }
+    }
+
+    // if axis is negative, it is counted from the end
+    if axis < 0 {
+        axis += dim;
+    }
+
+    (axis as usize, keepdims)
+}
+
 /// Create concat config from the attributes of the node
 pub fn concat_config(node: &Node) -> usize {
     // the axis is the last dimension (Default: 1 per ONNX spec)
@@ -868,6 +911,176 @@ pub fn layer_norm_config(node: &Node) -> (LayerNormConfig, bool) {
     )
 }
 
+/// Create an InstanceNormConfig from the attributes of the node
+pub fn instance_norm_config(node: &Node) -> InstanceNormConfig {
+    // Extract the shape of the weight tensor
+    let tensor_type = if let ArgType::Tensor(ref tensor_type) = node.inputs[1].ty {
+        tensor_type
+    } else {
+        panic!("InstanceNorm: weight tensor must be present");
+    };
+
+    let num_channels: usize = tensor_type.shape.clone().unwrap()[0];
+    let mut epsilon = 1e-5;
+
+    for (key, value) in node.attrs.iter() {
+        if key.as_str() == "epsilon" {
+            epsilon = value.clone().into_f32()
+        }
+    }
+
+    InstanceNormConfig::new(num_channels).with_epsilon(epsilon as f64)
+}
+
+/// Create a GroupNormConfig from the attributes of the node
+///
+/// The scale and bias are given per group before opset 21 and per channel since then, so the
+/// number of channels is taken from the shape of the input when it is known.
+pub fn group_norm_config(node: &Node) -> GroupNormConfig {
+    // Extract the shape of the weight tensor
+    let tensor_type = if let ArgType::Tensor(ref tensor_type) = node.inputs[1].ty {
+        tensor_type
+    } else {
+        panic!("GroupNorm: weight tensor must be present");
+    };
+    let num_weights: usize = tensor_type.shape.clone().unwrap()[0];
+
+    let mut num_groups = None;
+    let mut epsilon = 1e-5;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "num_groups" => num_groups = Some(value.clone().into_i64() as usize),
+            "epsilon" => epsilon = value.clone().into_f32(),
+            _ => {}
+        }
+    }
+
+    let num_groups = num_groups.expect("GroupNorm: num_groups attribute must be present");
+    let num_channels = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor
+            .shape
+            .as_ref()
+            .map(|shape| shape[1])
+            .unwrap_or(num_weights),
+        _ => panic!("GroupNorm: only tensor input is valid"),
+    };
+
+    if num_weights != num_channels && num_weights != num_groups {
+        panic!(
+            "GroupNorm: the scale should have {num_groups} (groups) or {num_channels} (channels) elements, got {num_weights}"
+        );
+    }
+
+    GroupNormConfig::new(num_groups, num_channels).with_epsilon(epsilon as f64)
+}
+
+/// Create a RecurrentConfig from the attributes of a LSTM, GRU or RNN node
+///
+/// Only the default activations of each node type are supported.
+fn recurrent_config(node: &Node, activations: &[&str]) -> RecurrentConfig {
+    let op = &node.node_type;
+    let shape = |index: usize| match &node.inputs[index].ty {
+        ArgType::Tensor(tensor) => tensor
+            .shape
+            .clone()
+            .unwrap_or_else(|| panic!("{op:?}: the weights must be constant")),
+        _ => panic!("{op:?}: the weights must be tensors"),
+    };
+    let d_input = shape(1)[2];
+    let mut d_hidden = shape(2)[2];
+    let mut direction = RecurrentDirection::Forward;
+    let mut batch_first = false;
+    let mut node_activations = None;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "hidden_size" => d_hidden = value.clone().into_i64() as usize,
+            "direction" => {
+                direction = match value.clone().into_string().as_str() {
+                    "forward" => RecurrentDirection::Forward,
+                    "reverse" => RecurrentDirection::Reverse,
+                    "bidirectional" => RecurrentDirection::Bidirectional,
+                    direction => panic!("{op:?}: unknown direction {direction}"),
+                }
+            }
+            "layout" => batch_first = value.clone().into_i64() == 1,
+            "activations" => node_activations = Some(value.clone().into_strings()),
+            "clip" => panic!("{op:?}: the clip attribute is not supported"),
+            _ => {}
+        }
+    }
+
+    if let Some(node_activations) = node_activations {
+        let num_directions = match direction {
+            RecurrentDirection::Bidirectional => 2,
+            _ => 1,
+        };
+        let expected = activations.repeat(num_directions);
+        let is_default = node_activations.len() == expected.len()
+            && node_activations
+                .iter()
+                .zip(expected)
+                .all(|(activation, expected)| activation.eq_ignore_ascii_case(expected));
+
+        if !is_default {
+            panic!("{op:?}: only the {activations:?} activations are supported, got {node_activations:?}");
+        }
+    }
+
+    if node
+        .inputs
+        .get(4)
+        .is_some_and(|input| !input.name.is_empty())
+    {
+        panic!("{op:?}: the sequence_lens input is not supported");
+    }
+
+    let bias = node
+        .inputs
+        .get(3)
+        .is_some_and(|input| !input.name.is_empty());
+
+    RecurrentConfig::new(d_input, d_hidden, bias, direction, batch_first)
+}
+
+/// Create a RecurrentConfig from the attributes of the LSTM node
+pub fn lstm_config(node: &Node) -> RecurrentConfig {
+    if let Some(value) = node.attrs.get("input_forget") {
+        if value.clone().into_i64() != 0 {
+            panic!("LSTM: coupling the input and forget gates is not supported");
+        }
+    }
+    if node
+        .inputs
+        .get(7)
+        .is_some_and(|input| !input.name.is_empty())
+    {
+        panic!("LSTM: peephole connections are not supported");
+    }
+
+    recurrent_config(node, &["Sigmoid", "Tanh", "Tanh"])
+}
+
+/// Create a RecurrentConfig from the attributes of the GRU node
+///
+/// Also returns if the reset gate is applied after the hidden transformation of the new gate
+/// (`linear_before_reset`).
+pub fn gru_config(node: &Node) -> (RecurrentConfig, bool) {
+    let reset_after = node
+        .attrs
+        .get("linear_before_reset")
+        .map(|value| value.clone().into_i64() != 0)
+        .unwrap_or(false);
+
+    (recurrent_config(node, &["Sigmoid", "Tanh"]), reset_after)
+}
+
+/// Create a RecurrentConfig from the attributes of the RNN node
+pub fn rnn_config(node: &Node) -> RecurrentConfig {
+    recurrent_config(node, &["Tanh"])
+}
+
 /// Create a TileConfig from the attributes of the node
 pub fn tile_config(node: &Node) -> TileConfig {
     let repeat = node
@@ -907,6 +1120,306 @@ pub fn trilu_config(node: &Node) -> TriluConfig {
     TriluConfig::new(upper, diagonal)
 }
 
+/// Create a TopKConfig from the attributes of the node
+pub fn top_k_config(node: &Node) -> TopKConfig {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("TopK: only tensor input is valid"),
+    };
+
+    let mut axis = -1;
+    let mut largest = 1;
+    // Before opset 10, k is an attribute
+    let mut k = None;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "axis" => axis = value.clone().into_i64(),
+            "largest" => largest = value.clone().into_i64(),
+            "k" => k = Some(value.clone().into_i64()),
+            _ => {}
+        }
+    }
+
+    if let Some(input) = node.inputs.get(1) {
+        match &input.value {
+            Some(value) => k = Some(value.clone().into_scalar().into_i64()),
+            None => panic!("TopK: only a constant k is supported"),
+        }
+    }
+    let k = k.expect("TopK: k must be given as attribute or as input");
+
+    if axis < 0 {
+        axis += dim;
+    }
+
+    TopKConfig::new(axis as usize, k as usize, largest != 0)
+}
+
+/// Create a NonMaxSuppressionConfig from the attributes of the node
+///
+/// The optional `max_output_boxes_per_class`, `iou_threshold` and `score_threshold` inputs must be
+/// constants.
+pub fn non_max_suppression_config(node: &Node) -> NonMaxSuppressionConfig {
+    let mut center_point_box = false;
+
+    for (key, value) in node.attrs.iter() {
+        if key.as_str() == "center_point_box" {
+            center_point_box = value.clone().into_i64() == 1
+        }
+    }
+
+    let input_value = |index: usize| {
+        node.inputs
+            .get(index)
+            .filter(|input| !input.name.is_empty())
+            .map(|input| match &input.value {
+                Some(value) => value.clone().into_scalar(),
+                None => panic!(
+                    "NonMaxSuppression: only a constant {} is supported",
+                    [
+                        "max_output_boxes_per_class",
+                        "iou_threshold",
+                        "score_threshold"
+                    ][index - 2]
+                ),
+            })
+    };
+
+    // No box is selected when max_output_boxes_per_class isn't given
+    let max_output_boxes_per_class = input_value(2)
+        .map(|value| value.into_i64().max(0) as usize)
+        .unwrap_or(0);
+    let iou_threshold = input_value(3).map(Data::into_f32).unwrap_or(0.0);
+    let score_threshold = input_value(4).map(Data::into_f32);
+
+    NonMaxSuppressionConfig::new(center_point_box, iou_threshold, max_output_boxes_per_class)
+        .with_score_threshold(score_threshold)
+}
+
+/// Create a RoiAlignConfig from the attributes of the node
+pub fn roi_align_config(node: &Node) -> RoiAlignConfig {
+    let mut output_size = [1, 1];
+    let mut spatial_scale = 1.0;
+    let mut sampling_ratio = 0;
+    let mut aligned = true;
+    let mut pooling = RoiAlignPooling::Avg;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "output_height" => output_size[0] = value.clone().into_i64() as usize,
+            "output_width" => output_size[1] = value.clone().into_i64() as usize,
+            "spatial_scale" => spatial_scale = value.clone().into_f32(),
+            "sampling_ratio" => sampling_ratio = value.clone().into_i64().max(0) as usize,
+            "coordinate_transformation_mode" => {
+                aligned = match value.clone().into_string().as_str() {
+                    "half_pixel" => true,
+                    "output_half_pixel" => false,
+                    mode => panic!("RoiAlign: unsupported coordinate transformation mode {mode}"),
+                }
+            }
+            "mode" => {
+                pooling = match value.clone().into_string().as_str() {
+                    "avg" => RoiAlignPooling::Avg,
+                    "max" => RoiAlignPooling::Max,
+                    mode => panic!("RoiAlign: unsupported mode {mode}"),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    RoiAlignConfig::new(output_size, spatial_scale, sampling_ratio, aligned, pooling)
+}
+
+/// Create a SplitConfig from the attributes of the node
+///
+/// The split sizes come from the `split` input (opset 13+) or attribute, which must be constant.
+/// Without them, the input is split in equal chunks, the last one being smaller if needed.
+pub fn split_config(node: &Node) -> SplitConfig {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("Split: only tensor input is valid"),
+    };
+
+    let mut axis = 0;
+    let mut split_sizes = None;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "axis" => axis = value.clone().into_i64(),
+            "split" => split_sizes = Some(value.clone().into_i64s()),
+            _ => {}
+        }
+    }
+
+    if let Some(input) = node.inputs.get(1).filter(|input| !input.name.is_empty()) {
+        match &input.value {
+            Some(value) => split_sizes = Some(value.clone().into_i64s()),
+            None => panic!("Split: only constant split sizes are supported"),
+        }
+    }
+
+    if axis < 0 {
+        axis += dim;
+    }
+
+    let split_sizes =
+        split_sizes.map(|sizes| sizes.into_iter().map(|size| size as usize).collect());
+    SplitConfig::new(axis as usize).with_split_sizes(split_sizes)
+}
+
+/// Get the reduction of a ScatterElements or ScatterND node
+fn scatter_reduction(node: &Node) -> ScatterReduction {
+    match node.attrs.get("reduction") {
+        Some(value) => match value.clone().into_string().as_str() {
+            "none" => ScatterReduction::None,
+            "add" => ScatterReduction::Add,
+            reduction => panic!("{}: unsupported reduction {reduction}", node.name),
+        },
+        None => ScatterReduction::None,
+    }
+}
+
+/// Create a ScatterElementsConfig from the attributes of the node
+pub fn scatter_elements_config(node: &Node) -> ScatterElementsConfig {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("ScatterElements: only tensor input is valid"),
+    };
+
+    let mut axis = match node.attrs.get("axis") {
+        Some(value) => value.clone().into_i64(),
+        None => 0,
+    };
+    if axis < 0 {
+        axis += dim;
+    }
+
+    ScatterElementsConfig::new(axis as usize, scatter_reduction(node))
+}
+
+/// Get the reduction of a ScatterND node
+pub fn scatter_nd_config(node: &Node) -> ScatterReduction {
+    scatter_reduction(node)
+}
+
+/// Check the attributes of a GatherND node, only `batch_dims=0` being supported
+pub fn gather_nd_config(node: &Node) {
+    if let Some(value) = node.attrs.get("batch_dims") {
+        if value.clone().into_i64() != 0 {
+            panic!("GatherND: only batch_dims=0 is supported (got {:?})", value);
+        }
+    }
+}
+
+/// Create a OneHotConfig from the attributes of the node
+///
+/// The `depth` and `values` inputs must be constants.
+pub fn one_hot_config(node: &Node) -> OneHotConfig {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("OneHot: only tensor indices are valid"),
+    };
+
+    let depth = match &node.inputs[1].value {
+        Some(value) => value.clone().into_scalar().into_i64() as usize,
+        None => panic!("OneHot: only a constant depth is supported"),
+    };
+    let values = match &node.inputs[2].value {
+        Some(value) => match value.clone().into_f32s().as_slice() {
+            [off_value, on_value] => [*off_value, *on_value],
+            values => panic!("OneHot: expected two values, got {values:?}"),
+        },
+        None => panic!("OneHot: only constant values are supported"),
+    };
+
+    // The axis is counted in the output, which has one more dimension than the indices
+    let mut axis = match node.attrs.get("axis") {
+        Some(value) => value.clone().into_i64(),
+        None => -1,
+    };
+    if axis < 0 {
+        axis += dim + 1;
+    }
+
+    OneHotConfig::new(depth, values, axis as usize)
+}
+
+/// Create an EinsumConfig from the equation of the node
+///
+/// Ellipsis and repeated subscripts within an input (diagonals) are not supported.
+pub fn einsum_config(node: &Node) -> EinsumConfig {
+    let equation = match node.attrs.get("equation") {
+        Some(value) => value.clone().into_string().replace(' ', ""),
+        None => panic!("Einsum: equation attribute is required"),
+    };
+    if equation.contains("...") {
+        panic!("Einsum: ellipsis is not supported (got {equation})");
+    }
+
+    let (inputs, output) = match equation.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(output)),
+        None => (equation.as_str(), None),
+    };
+    let input_subscripts = inputs.split(',').map(String::from).collect::<Vec<_>>();
+
+    // In implicit mode, the output holds the subscripts appearing once, in alphabetical order
+    let output_subscripts = match output {
+        Some(output) => output.to_string(),
+        None => {
+            let mut subscripts = inputs.chars().filter(|c| *c != ',').collect::<Vec<_>>();
+            subscripts.sort();
+            subscripts
+                .iter()
+                .filter(|c| subscripts.iter().filter(|other| other == c).count() == 1)
+                .collect()
+        }
+    };
+
+    if input_subscripts.len() != node.inputs.len() {
+        panic!(
+            "Einsum: the equation {equation} doesn't match the {} inputs",
+            node.inputs.len()
+        );
+    }
+    for subscripts in input_subscripts.iter() {
+        let mut chars = subscripts.chars().collect::<Vec<_>>();
+        chars.sort();
+        chars.dedup();
+        if chars.len() != subscripts.len() {
+            panic!("Einsum: repeated subscripts in {subscripts} are not supported");
+        }
+    }
+
+    EinsumConfig::new(input_subscripts, output_subscripts)
+}
+
+/// Get the number of scan inputs of a Scan node.
+///
+/// Only the default layout is supported: the scan inputs and outputs are iterated forward over
+/// their first dimension.
+pub fn scan_config(node: &Node) -> usize {
+    let mut num_scan_inputs = None;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "num_scan_inputs" => num_scan_inputs = Some(value.clone().into_i64() as usize),
+            "scan_input_axes"
+            | "scan_output_axes"
+            | "scan_input_directions"
+            | "scan_output_directions"
+                if value.clone().into_i64s().iter().any(|value| *value != 0) =>
+            {
+                panic!("Scan: only the default {key} are supported")
+            }
+            _ => {}
+        }
+    }
+
+    num_scan_inputs.expect("Scan: num_scan_inputs attribute is required")
+}
+
 /// Create a PadConfig from the attributes of the node
 pub fn pad_config(node: &Node) -> PadConfig {
     fn get_pads_input(node: &Node) -> Vec<i64> {
@@ -1805,3 +2318,53 @@ pub fn squeeze_config(curr: &Node) -> Vec<i64> {
 
     axes
 }
+
+/// Create the per tensor quantization parameters, the scale and the `int8` zero point, from the
+/// scale and zero point inputs of a quantized node.
+///
+/// The `uint8` zero points are shifted by 128, Burn representing `uint8` quantized values as the
+/// equivalent `int8` values.
+pub fn quantization_config(node: &Node, scale_index: usize, zero_point_index: usize) -> (f32, i8) {
+    let scale = match node
+        .inputs
+        .get(scale_index)
+        .and_then(|arg| arg.value.clone())
+    {
+        Some(value @ (Data::Float16(_) | Data::Float32(_) | Data::Float64(_))) => value.into_f32(),
+        Some(value @ (Data::Float16s(_) | Data::Float32s(_) | Data::Float64s(_))) => {
+            match value.into_f32s().as_slice() {
+                [scale] => *scale,
+                _ => panic!(
+                    "{:?}: only per tensor quantization is supported",
+                    node.node_type
+                ),
+            }
+        }
+        _ => panic!("{:?}: the scale must be a constant", node.node_type),
+    };
+
+    let zero_point = node
+        .inputs
+        .get(zero_point_index)
+        .filter(|arg| !arg.name.is_empty());
+    let zero_point = match zero_point.map(|arg| arg.value.clone()) {
+        Some(Some(Data::Int8s(values))) if values.len() == 1 => Data::Int8(values[0]),
+        Some(Some(Data::Uint8s(values))) if values.len() == 1 => Data::Uint8(values[0]),
+        Some(Some(value)) => value,
+        Some(None) => panic!("{:?}: the zero point must be a constant", node.node_type),
+        // Without zero point, the quantized values are `uint8` values unless the output is `int8`
+        None => match &node.outputs[0].ty {
+            ArgType::Tensor(tensor) if tensor.elem_type == ElementType::Int8 => Data::Int8(0),
+            _ => Data::Uint8(0),
+        },
+    };
+
+    match zero_point {
+        Data::Int8(zero_point) => (scale, zero_point),
+        Data::Uint8(zero_point) => (scale, (zero_point as i16 - 128) as i8),
+        _ => panic!(
+            "{:?}: only int8 and uint8 per tensor quantization is supported",
+            node.node_type
+        ),
+    }
+}
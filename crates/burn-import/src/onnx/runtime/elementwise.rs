@@ -0,0 +1,512 @@
+use burn::tensor::{
+    activation, backend::Backend, BasicOps, Bool, Element, Float, Int, Numeric, Tensor,
+};
+use onnx_ir::ir::NodeType;
+
+use super::{
+    value::{with_rank, DynTensor, Scalar, Value, ValueKind},
+    Error,
+};
+
+/// Element-wise operations with a single input, see the codegen [UnaryNode](crate::burn::node::unary::UnaryNode).
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum UnaryOp {
+    Cos,
+    Erf,
+    Exp,
+    Gelu,
+    HardSigmoid(f64, f64),
+    LeakyRelu(f64),
+    Log,
+    LogSoftmax(usize),
+    Neg,
+    Not,
+    Reciprocal,
+    Relu,
+    Sigmoid,
+    Sign,
+    Sin,
+    Softmax(usize),
+    Sqrt,
+    Tanh,
+    Clip(Option<f64>, Option<f64>),
+}
+
+/// Element-wise operations with two inputs, see the codegen [BinaryNode](crate::burn::node::binary::BinaryNode).
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Max,
+    Min,
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+/// The element type a value is cast to.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum CastKind {
+    Float,
+    Int,
+    Bool,
+}
+
+pub(crate) fn unary<B: Backend>(op: UnaryOp, input: Value<B>) -> Result<Value<B>, Error> {
+    let output = match (op, input) {
+        (UnaryOp::Not, Value::Bool(tensor)) => {
+            with_rank!(tensor.rank(), D => tensor.into_tensor::<D>().bool_not().into())
+        }
+        (UnaryOp::Not, Value::Scalar(scalar)) => Value::Scalar(Scalar::Bool(!scalar.as_bool())),
+        (UnaryOp::Not, input) => return Err(Error::unsupported(op, &[input.kind()])),
+        (_, Value::Float(tensor)) => {
+            with_rank!(tensor.rank(), D => unary_float(op, tensor.into_tensor::<D>())?.into())
+        }
+        (UnaryOp::Neg, Value::Int(tensor)) => {
+            with_rank!(tensor.rank(), D => tensor.into_tensor::<D>().neg().into())
+        }
+        (UnaryOp::Sign, Value::Int(tensor)) => {
+            with_rank!(tensor.rank(), D => tensor.into_tensor::<D>().sign().into())
+        }
+        (UnaryOp::Clip(min, max), Value::Int(tensor)) => with_rank!(tensor.rank(), D => {
+            let tensor = tensor.into_tensor::<D>();
+            let tensor = match min {
+                Some(min) => tensor.clamp_min(min as i64),
+                None => tensor,
+            };
+            let tensor = match max {
+                Some(max) => tensor.clamp_max(max as i64),
+                None => tensor,
+            };
+            tensor.into()
+        }),
+        (op, Value::Scalar(scalar)) => Value::Scalar(unary_scalar(op, scalar)?),
+        (op, input) => return Err(Error::unsupported(op, &[input.kind()])),
+    };
+
+    Ok(output)
+}
+
+fn unary_float<B: Backend, const D: usize>(
+    op: UnaryOp,
+    tensor: Tensor<B, D>,
+) -> Result<Tensor<B, D>, Error> {
+    let output = match op {
+        UnaryOp::Cos => tensor.cos(),
+        UnaryOp::Erf => tensor.erf(),
+        UnaryOp::Exp => tensor.exp(),
+        UnaryOp::Gelu => activation::gelu(tensor),
+        UnaryOp::HardSigmoid(alpha, beta) => activation::hard_sigmoid(tensor, alpha, beta),
+        UnaryOp::LeakyRelu(alpha) => activation::leaky_relu(tensor, alpha),
+        UnaryOp::Log => tensor.log(),
+        UnaryOp::LogSoftmax(dim) => activation::log_softmax(tensor, dim),
+        UnaryOp::Neg => tensor.neg(),
+        UnaryOp::Reciprocal => tensor.recip(),
+        UnaryOp::Relu => activation::relu(tensor),
+        UnaryOp::Sigmoid => activation::sigmoid(tensor),
+        UnaryOp::Sign => tensor.sign(),
+        UnaryOp::Sin => tensor.sin(),
+        UnaryOp::Softmax(dim) => activation::softmax(tensor, dim),
+        UnaryOp::Sqrt => tensor.sqrt(),
+        UnaryOp::Tanh => activation::tanh(tensor),
+        UnaryOp::Clip(Some(min), Some(max)) => tensor.clamp(min, max),
+        UnaryOp::Clip(Some(min), None) => tensor.clamp_min(min),
+        UnaryOp::Clip(None, Some(max)) => tensor.clamp_max(max),
+        UnaryOp::Clip(None, None) | UnaryOp::Not => {
+            return Err(Error::unsupported(op, &["float tensor"]))
+        }
+    };
+
+    Ok(output)
+}
+
+fn unary_scalar(op: UnaryOp, scalar: Scalar) -> Result<Scalar, Error> {
+    if let (UnaryOp::Neg, Scalar::Int(value)) = (op, scalar) {
+        return Ok(Scalar::Int(value.wrapping_neg()));
+    }
+
+    let value = scalar.as_f64();
+    let value = match op {
+        UnaryOp::Cos => value.cos(),
+        UnaryOp::Exp => value.exp(),
+        UnaryOp::Log => value.ln(),
+        UnaryOp::Neg => -value,
+        UnaryOp::Reciprocal => value.recip(),
+        UnaryOp::Relu => value.max(0.0),
+        UnaryOp::Sigmoid => 1.0 / (1.0 + (-value).exp()),
+        UnaryOp::Sin => value.sin(),
+        UnaryOp::Sqrt => value.sqrt(),
+        UnaryOp::Tanh => value.tanh(),
+        op => return Err(Error::unsupported(op, &["scalar"])),
+    };
+
+    Ok(Scalar::Float(value))
+}
+
+pub(crate) fn binary<B: Backend>(
+    op: BinaryOp,
+    lhs: Value<B>,
+    rhs: Value<B>,
+) -> Result<Value<B>, Error> {
+    match (lhs, rhs) {
+        (Value::Float(lhs), Value::Float(rhs)) => binary_tensor(op, lhs, rhs),
+        (Value::Int(lhs), Value::Int(rhs)) => binary_tensor(op, lhs, rhs),
+        (Value::Float(lhs), Value::Int(rhs)) => binary_tensor(op, lhs, cast_tensor(rhs)?),
+        (Value::Bool(lhs), Value::Bool(rhs)) => match op {
+            BinaryOp::Equal => {
+                let (lhs, rhs) = broadcast_ranks(lhs, rhs);
+                Ok(
+                    with_rank!(lhs.rank(), D => lhs.into_tensor::<D>().equal(rhs.into_tensor::<D>()).into()),
+                )
+            }
+            op => Err(Error::unsupported(op, &["bool tensor", "bool tensor"])),
+        },
+        (Value::Float(lhs), Value::Scalar(rhs)) => binary_scalar(op, lhs, rhs),
+        (Value::Int(lhs), Value::Scalar(rhs)) => binary_scalar(op, lhs, rhs),
+        (Value::Scalar(lhs), Value::Float(rhs)) => scalar_binary(op, lhs, rhs),
+        (Value::Scalar(lhs), Value::Int(rhs)) => scalar_binary(op, lhs, rhs),
+        (Value::Scalar(lhs), Value::Scalar(rhs)) => {
+            Ok(Value::Scalar(binary_scalars(op, lhs, rhs)?))
+        }
+        (lhs, rhs) => Err(Error::unsupported(op, &[lhs.kind(), rhs.kind()])),
+    }
+}
+
+fn binary_tensor<B, K>(
+    op: BinaryOp,
+    lhs: DynTensor<B, K>,
+    rhs: DynTensor<B, K>,
+) -> Result<Value<B>, Error>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+{
+    let (lhs, rhs) = broadcast_ranks(lhs, rhs);
+    check_broadcast(op, &lhs.dims(), &rhs.dims())?;
+
+    Ok(with_rank!(lhs.rank(), D => {
+        let lhs = lhs.into_tensor::<D>();
+        let rhs = rhs.into_tensor::<D>();
+
+        match op {
+            BinaryOp::Add => lhs.add(rhs).into(),
+            BinaryOp::Sub => lhs.sub(rhs).into(),
+            BinaryOp::Mul => lhs.mul(rhs).into(),
+            BinaryOp::Div => lhs.div(rhs).into(),
+            BinaryOp::Pow => lhs.powi(rhs).into(),
+            BinaryOp::Max => lhs.max_pair(rhs).into(),
+            BinaryOp::Min => lhs.min_pair(rhs).into(),
+            BinaryOp::Equal => lhs.equal(rhs).into(),
+            BinaryOp::Greater => lhs.greater(rhs).into(),
+            BinaryOp::GreaterOrEqual => lhs.greater_equal(rhs).into(),
+            BinaryOp::Less => lhs.lower(rhs).into(),
+            BinaryOp::LessOrEqual => lhs.lower_equal(rhs).into(),
+        }
+    }))
+}
+
+fn binary_scalar<B, K>(op: BinaryOp, lhs: DynTensor<B, K>, rhs: Scalar) -> Result<Value<B>, Error>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+{
+    Ok(with_rank!(lhs.rank(), D => {
+        let lhs = lhs.into_tensor::<D>();
+
+        match (op, rhs) {
+            (BinaryOp::Pow, Scalar::Float(rhs)) => lhs.powf_scalar(rhs).into(),
+            (BinaryOp::Pow, rhs) => lhs.powi_scalar(rhs.as_i64()).into(),
+            (op, Scalar::Float(rhs)) => binary_elem(op, lhs, rhs),
+            (op, rhs) => binary_elem(op, lhs, rhs.as_i64()),
+        }
+    }))
+}
+
+fn binary_elem<B, const D: usize, K, E>(op: BinaryOp, lhs: Tensor<B, D, K>, rhs: E) -> Value<B>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+    E: Element,
+{
+    match op {
+        BinaryOp::Add => lhs.add_scalar(rhs).into(),
+        BinaryOp::Sub => lhs.sub_scalar(rhs).into(),
+        BinaryOp::Mul => lhs.mul_scalar(rhs).into(),
+        BinaryOp::Div => lhs.div_scalar(rhs).into(),
+        BinaryOp::Max => lhs.clamp_min(rhs).into(),
+        BinaryOp::Min => lhs.clamp_max(rhs).into(),
+        BinaryOp::Equal => lhs.equal_elem(rhs).into(),
+        BinaryOp::Greater => lhs.greater_elem(rhs).into(),
+        BinaryOp::GreaterOrEqual => lhs.greater_equal_elem(rhs).into(),
+        BinaryOp::Less => lhs.lower_elem(rhs).into(),
+        BinaryOp::LessOrEqual => lhs.lower_equal_elem(rhs).into(),
+        BinaryOp::Pow => unreachable!(),
+    }
+}
+
+/// Binary operation with a scalar on the left hand side.
+fn scalar_binary<B, K>(op: BinaryOp, lhs: Scalar, rhs: DynTensor<B, K>) -> Result<Value<B>, Error>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+{
+    let swapped = match op {
+        BinaryOp::Add | BinaryOp::Mul | BinaryOp::Max | BinaryOp::Min | BinaryOp::Equal => op,
+        BinaryOp::Greater => BinaryOp::Less,
+        BinaryOp::GreaterOrEqual => BinaryOp::LessOrEqual,
+        BinaryOp::Less => BinaryOp::Greater,
+        BinaryOp::LessOrEqual => BinaryOp::GreaterOrEqual,
+        BinaryOp::Sub => {
+            let rhs = with_rank!(rhs.rank(), D => DynTensor::new(rhs.into_tensor::<D>().neg()));
+            return binary_scalar(BinaryOp::Add, rhs, lhs);
+        }
+        op => return Err(Error::unsupported(op, &["scalar", "tensor"])),
+    };
+
+    binary_scalar(swapped, rhs, lhs)
+}
+
+fn binary_scalars(op: BinaryOp, lhs: Scalar, rhs: Scalar) -> Result<Scalar, Error> {
+    if let (Scalar::Int(lhs), Scalar::Int(rhs)) = (lhs, rhs) {
+        // Division by zero and negative exponents have no int result
+        let unsupported = || Error::unsupported(op, &["scalar", "scalar"]);
+
+        return Ok(match op {
+            BinaryOp::Add => Scalar::Int(lhs.wrapping_add(rhs)),
+            BinaryOp::Sub => Scalar::Int(lhs.wrapping_sub(rhs)),
+            BinaryOp::Mul => Scalar::Int(lhs.wrapping_mul(rhs)),
+            BinaryOp::Div => Scalar::Int(lhs.checked_div(rhs).ok_or_else(unsupported)?),
+            BinaryOp::Pow => {
+                let rhs = u32::try_from(rhs).map_err(|_| unsupported())?;
+                Scalar::Int(lhs.wrapping_pow(rhs))
+            }
+            BinaryOp::Max => Scalar::Int(lhs.max(rhs)),
+            BinaryOp::Min => Scalar::Int(lhs.min(rhs)),
+            BinaryOp::Equal => Scalar::Bool(lhs == rhs),
+            BinaryOp::Greater => Scalar::Bool(lhs > rhs),
+            BinaryOp::GreaterOrEqual => Scalar::Bool(lhs >= rhs),
+            BinaryOp::Less => Scalar::Bool(lhs < rhs),
+            BinaryOp::LessOrEqual => Scalar::Bool(lhs <= rhs),
+        });
+    }
+
+    let (lhs, rhs) = (lhs.as_f64(), rhs.as_f64());
+    let output = match op {
+        BinaryOp::Add => Scalar::Float(lhs + rhs),
+        BinaryOp::Sub => Scalar::Float(lhs - rhs),
+        BinaryOp::Mul => Scalar::Float(lhs * rhs),
+        BinaryOp::Div => Scalar::Float(lhs / rhs),
+        BinaryOp::Pow => Scalar::Float(lhs.powf(rhs)),
+        BinaryOp::Max => Scalar::Float(lhs.max(rhs)),
+        BinaryOp::Min => Scalar::Float(lhs.min(rhs)),
+        BinaryOp::Equal => Scalar::Bool(lhs == rhs),
+        BinaryOp::Greater => Scalar::Bool(lhs > rhs),
+        BinaryOp::GreaterOrEqual => Scalar::Bool(lhs >= rhs),
+        BinaryOp::Less => Scalar::Bool(lhs < rhs),
+        BinaryOp::LessOrEqual => Scalar::Bool(lhs <= rhs),
+    };
+
+    Ok(output)
+}
+
+/// Checks that the dimensions of tensors of the same rank can be broadcast together.
+fn check_broadcast(op: BinaryOp, lhs: &[usize], rhs: &[usize]) -> Result<(), Error> {
+    let compatible = lhs
+        .iter()
+        .zip(rhs)
+        .all(|(lhs, rhs)| lhs == rhs || *lhs == 1 || *rhs == 1);
+
+    match compatible {
+        true => Ok(()),
+        false => Err(Error::invalid_shape(op, rhs.to_vec())),
+    }
+}
+
+/// Selects elements from `x` where the condition is true and from `y` elsewhere.
+pub(crate) fn where_op<B: Backend>(
+    condition: Value<B>,
+    x: Value<B>,
+    y: Value<B>,
+) -> Result<Value<B>, Error> {
+    let condition = match condition {
+        Value::Bool(condition) => condition,
+        Value::Scalar(condition) => {
+            return Ok(if condition.as_bool() { x } else { y });
+        }
+        condition => {
+            return Err(Error::InvalidKind {
+                expected: "bool tensor",
+                actual: condition.kind(),
+            })
+        }
+    };
+
+    match (x, y) {
+        (Value::Float(x), Value::Float(y)) => mask_where(condition, x, y),
+        (Value::Int(x), Value::Int(y)) => mask_where(condition, x, y),
+        (Value::Float(x), Value::Scalar(y)) => mask_fill(bool_not(condition)?, x, y),
+        (Value::Int(x), Value::Scalar(y)) => mask_fill(bool_not(condition)?, x, y),
+        (Value::Scalar(x), Value::Float(y)) => mask_fill(condition, y, x),
+        (Value::Scalar(x), Value::Int(y)) => mask_fill(condition, y, x),
+        (x, y) => Err(Error::unsupported(NodeType::Where, &[x.kind(), y.kind()])),
+    }
+}
+
+fn mask_where<B, K>(
+    condition: DynTensor<B, Bool>,
+    x: DynTensor<B, K>,
+    y: DynTensor<B, K>,
+) -> Result<Value<B>, Error>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+{
+    let rank = condition.rank().max(x.rank()).max(y.rank());
+    let condition = unsqueeze_to(condition, rank);
+    let x = unsqueeze_to(x, rank);
+    let y = unsqueeze_to(y, rank);
+
+    Ok(with_rank!(rank, D => y
+        .into_tensor::<D>()
+        .mask_where(condition.into_tensor::<D>(), x.into_tensor::<D>())
+        .into()))
+}
+
+fn mask_fill<B, K>(
+    condition: DynTensor<B, Bool>,
+    tensor: DynTensor<B, K>,
+    value: Scalar,
+) -> Result<Value<B>, Error>
+where
+    B: Backend,
+    K: Numeric<B> + ValueKind<B>,
+    K::Elem: Element,
+{
+    let rank = condition.rank().max(tensor.rank());
+    let condition = unsqueeze_to(condition, rank);
+    let tensor = unsqueeze_to(tensor, rank);
+
+    Ok(with_rank!(rank, D => {
+        let condition = condition.into_tensor::<D>();
+        let tensor = tensor.into_tensor::<D>();
+        match value {
+            Scalar::Float(value) => tensor.mask_fill(condition, value).into(),
+            value => tensor.mask_fill(condition, value.as_i64()).into(),
+        }
+    }))
+}
+
+fn bool_not<B: Backend>(tensor: DynTensor<B, Bool>) -> Result<DynTensor<B, Bool>, Error> {
+    Ok(with_rank!(tensor.rank(), D => DynTensor::new(tensor.into_tensor::<D>().bool_not())))
+}
+
+/// Casts the value to the given element type, see the codegen `UnaryNode::cast`.
+pub(crate) fn cast<B: Backend>(input: Value<B>, kind: CastKind) -> Result<Value<B>, Error> {
+    let output = match (input, kind) {
+        (Value::Float(tensor), CastKind::Float) => tensor.into(),
+        (Value::Float(tensor), CastKind::Int) => cast_tensor::<B, Float, Int>(tensor)?.into(),
+        (Value::Float(tensor), CastKind::Bool) => cast_tensor::<B, Float, Bool>(tensor)?.into(),
+        (Value::Int(tensor), CastKind::Float) => cast_tensor::<B, Int, Float>(tensor)?.into(),
+        (Value::Int(tensor), CastKind::Int) => tensor.into(),
+        (Value::Int(tensor), CastKind::Bool) => cast_tensor::<B, Int, Bool>(tensor)?.into(),
+        (Value::Bool(tensor), CastKind::Float) => cast_tensor::<B, Bool, Float>(tensor)?.into(),
+        (Value::Bool(tensor), CastKind::Int) => cast_tensor::<B, Bool, Int>(tensor)?.into(),
+        (Value::Bool(tensor), CastKind::Bool) => tensor.into(),
+        (Value::Scalar(scalar), CastKind::Float) => Value::Scalar(Scalar::Float(scalar.as_f64())),
+        (Value::Scalar(scalar), CastKind::Int) => Value::Scalar(Scalar::Int(scalar.as_i64())),
+        (Value::Scalar(scalar), CastKind::Bool) => Value::Scalar(Scalar::Bool(scalar.as_bool())),
+        (input, kind) => return Err(Error::unsupported(kind, &[input.kind()])),
+    };
+
+    Ok(output)
+}
+
+/// Conversion between tensor kinds of the same rank.
+pub(crate) trait CastTensor<B: Backend, K: BasicOps<B>>: BasicOps<B> {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, K>;
+}
+
+impl<B: Backend> CastTensor<B, Int> for Float {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Int> {
+        tensor.int()
+    }
+}
+
+impl<B: Backend> CastTensor<B, Bool> for Float {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Bool> {
+        tensor.bool()
+    }
+}
+
+impl<B: Backend> CastTensor<B, Float> for Int {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Float> {
+        tensor.float()
+    }
+}
+
+impl<B: Backend> CastTensor<B, Bool> for Int {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Bool> {
+        tensor.bool()
+    }
+}
+
+impl<B: Backend> CastTensor<B, Float> for Bool {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Float> {
+        tensor.float()
+    }
+}
+
+impl<B: Backend> CastTensor<B, Int> for Bool {
+    fn cast<const D: usize>(tensor: Tensor<B, D, Self>) -> Tensor<B, D, Int> {
+        tensor.int()
+    }
+}
+
+pub(crate) fn cast_tensor<B, K1, K2>(tensor: DynTensor<B, K1>) -> Result<DynTensor<B, K2>, Error>
+where
+    B: Backend,
+    K1: CastTensor<B, K2>,
+    K2: BasicOps<B>,
+{
+    Ok(with_rank!(tensor.rank(), D => DynTensor::new(K1::cast(tensor.into_tensor::<D>()))))
+}
+
+/// Prepends unit dimensions so both tensors have the same rank, following the ONNX broadcasting
+/// rules.
+pub(crate) fn broadcast_ranks<B, K>(
+    lhs: DynTensor<B, K>,
+    rhs: DynTensor<B, K>,
+) -> (DynTensor<B, K>, DynTensor<B, K>)
+where
+    B: Backend,
+    K: BasicOps<B>,
+{
+    let rank = lhs.rank().max(rhs.rank());
+    (unsqueeze_to(lhs, rank), unsqueeze_to(rhs, rank))
+}
+
+/// Prepends unit dimensions up to the given rank.
+pub(crate) fn unsqueeze_to<B, K>(tensor: DynTensor<B, K>, rank: usize) -> DynTensor<B, K>
+where
+    B: Backend,
+    K: BasicOps<B>,
+{
+    let dims = tensor.dims();
+    if dims.len() >= rank {
+        return tensor;
+    }
+
+    let mut padded = vec![1; rank - dims.len()];
+    padded.extend(dims);
+    tensor.reshape(padded)
+}
@@ -0,0 +1,117 @@
+use core::fmt::Debug;
+
+use onnx_ir::ir::NodeType;
+
+/// Error when loading or evaluating an ONNX model with the runtime.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The model file can't be read.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The model file can't be parsed.
+    #[error("Invalid ONNX model: {0}")]
+    Parse(String),
+
+    /// The operator of a node isn't supported by the runtime.
+    #[error("Unsupported operator {op:?} for node {node}")]
+    UnsupportedOperator {
+        /// The name of the node.
+        node: String,
+        /// The operator of the node.
+        op: NodeType,
+    },
+
+    /// A node can't be prepared from its attributes and constant inputs.
+    #[error("Invalid node {node}: {message}")]
+    InvalidNode {
+        /// The name of the node.
+        node: String,
+        /// What is invalid.
+        message: String,
+    },
+
+    /// The number of inputs given to the model doesn't match the graph inputs.
+    #[error("Expected {expected} inputs, got {actual}")]
+    InputCount {
+        /// The number of graph inputs.
+        expected: usize,
+        /// The number of given inputs.
+        actual: usize,
+    },
+
+    /// An input given to the model doesn't match the type of the graph input.
+    #[error("Input {name} should be a {expected}, got a {actual}")]
+    InvalidInput {
+        /// The name of the graph input.
+        name: String,
+        /// The type of the graph input.
+        expected: String,
+        /// The type of the given value.
+        actual: String,
+    },
+
+    /// A value doesn't have the kind expected by an operation.
+    #[error("Expected a {expected}, got a {actual}")]
+    InvalidKind {
+        /// The expected kind.
+        expected: &'static str,
+        /// The kind of the value.
+        actual: &'static str,
+    },
+
+    /// An operation isn't supported for the kinds of its inputs.
+    #[error("{op} is not supported for {kinds}")]
+    UnsupportedKind {
+        /// The operation.
+        op: String,
+        /// The kinds of the inputs.
+        kinds: String,
+    },
+
+    /// A tensor doesn't have the rank expected by an operation.
+    #[error("Expected a tensor of rank {expected}, got rank {actual}")]
+    InvalidRank {
+        /// The expected rank.
+        expected: usize,
+        /// The rank of the tensor.
+        actual: usize,
+    },
+
+    /// Tensors of this rank can't be evaluated.
+    #[error("Tensors of rank {0} are not supported by the runtime")]
+    UnsupportedRank(usize),
+
+    /// An operation isn't supported for the shape of its input.
+    #[error("{op} is not supported for a tensor of shape {dims:?}")]
+    InvalidShape {
+        /// The operation.
+        op: String,
+        /// The dimensions of the tensor.
+        dims: Vec<usize>,
+    },
+
+    /// A required input of a node is omitted.
+    #[error("Input {0} is required")]
+    MissingInput(usize),
+
+    /// A value used by a node isn't produced by the graph.
+    #[error("Value {0} is not defined")]
+    UndefinedValue(String),
+}
+
+impl Error {
+    pub(crate) fn unsupported(op: impl Debug, kinds: &[&str]) -> Self {
+        Error::UnsupportedKind {
+            op: format!("{op:?}"),
+            kinds: kinds.join(" and "),
+        }
+    }
+
+    pub(crate) fn invalid_shape(op: impl Debug, dims: Vec<usize>) -> Self {
+        Error::InvalidShape {
+            op: format!("{op:?}"),
+            dims,
+        }
+    }
+}
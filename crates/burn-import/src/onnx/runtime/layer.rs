@@ -0,0 +1,278 @@
+use burn::{
+    module::Param,
+    nn::{
+        conv::{Conv1d, Conv2d, Conv3d, ConvTranspose1d, ConvTranspose2d, ConvTranspose3d},
+        pool::{
+            AdaptiveAvgPool1dConfig, AdaptiveAvgPool2dConfig, AvgPool1d, AvgPool2d, MaxPool1d,
+            MaxPool2d,
+        },
+        BatchNormConfig, GroupNorm, InstanceNorm, LayerNorm, Linear, PRelu, PReluConfig,
+    },
+    tensor::{backend::Backend, Tensor, TensorData},
+};
+use onnx_ir::ir::{Node, NodeType};
+
+use super::{
+    value::{with_rank, DynTensor},
+    Error,
+};
+use crate::onnx::{op_configuration::*, to_burn::extract_data_serialize};
+
+/// A node with learned parameters or a pooling module, instantiated once when the model is loaded.
+#[derive(Debug)]
+pub(crate) enum Layer<B: Backend> {
+    Conv1d(Conv1d<B>),
+    Conv2d(Conv2d<B>),
+    Conv3d(Conv3d<B>),
+    ConvTranspose1d(ConvTranspose1d<B>),
+    ConvTranspose2d(ConvTranspose2d<B>),
+    ConvTranspose3d(ConvTranspose3d<B>),
+    MaxPool1d(MaxPool1d),
+    MaxPool2d(MaxPool2d),
+    AvgPool1d(AvgPool1d),
+    AvgPool2d(AvgPool2d),
+    GlobalAvgPool,
+    Linear(Linear<B>),
+    BatchNorm(BatchNorm<B>),
+    LayerNorm(LayerNorm<B>),
+    InstanceNorm(InstanceNorm<B>),
+    GroupNorm(GroupNorm<B>),
+    PRelu(PRelu<B>),
+}
+
+/// The batch norm module is generic over the number of spatial dimensions, which is only known
+/// when the node is executed, so the inference pass is done here with the same parameters.
+#[derive(Debug)]
+pub(crate) struct BatchNorm<B: Backend> {
+    gamma: Tensor<B, 1>,
+    beta: Tensor<B, 1>,
+    running_mean: Tensor<B, 1>,
+    running_var: Tensor<B, 1>,
+    epsilon: f64,
+}
+
+impl<B: Backend> Layer<B> {
+    /// Instantiates the layer of the node, `None` if the node isn't a layer.
+    pub(crate) fn from_node(node: &Node, device: &B::Device) -> Result<Option<Self>, Error> {
+        let required = |name: &str| Error::InvalidNode {
+            node: node.name.clone(),
+            message: format!("{name} is required"),
+        };
+
+        let layer = match node.node_type {
+            NodeType::Conv1d => {
+                let mut conv = conv1d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::Conv1d(conv)
+            }
+            NodeType::Conv2d => {
+                let mut conv = conv2d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::Conv2d(conv)
+            }
+            NodeType::Conv3d => {
+                let mut conv = conv3d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::Conv3d(conv)
+            }
+            NodeType::ConvTranspose1d => {
+                let mut conv = conv_transpose1d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::ConvTranspose1d(conv)
+            }
+            NodeType::ConvTranspose2d => {
+                let mut conv = conv_transpose2d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::ConvTranspose2d(conv)
+            }
+            NodeType::ConvTranspose3d => {
+                let mut conv = conv_transpose3d_config(node).init(device);
+                conv.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                conv.bias = param(node, 2, device);
+                Layer::ConvTranspose3d(conv)
+            }
+            NodeType::MaxPool1d => Layer::MaxPool1d(max_pool1d_config(node).init()),
+            NodeType::MaxPool2d => Layer::MaxPool2d(max_pool2d_config(node).init()),
+            NodeType::AveragePool1d => Layer::AvgPool1d(avg_pool1d_config(node).init()),
+            NodeType::AveragePool2d => Layer::AvgPool2d(avg_pool2d_config(node).init()),
+            NodeType::GlobalAveragePool => Layer::GlobalAvgPool,
+            NodeType::Linear => {
+                let mut linear = linear_config(node).init(device);
+                linear.weight = param(node, 1, device).ok_or_else(|| required("Weight"))?;
+                linear.bias = param(node, 2, device);
+                Layer::Linear(linear)
+            }
+            NodeType::BatchNormalization => {
+                let config: BatchNormConfig = batch_norm_config(node);
+                Layer::BatchNorm(BatchNorm {
+                    gamma: tensor(node, 1, device).ok_or_else(|| required("Gamma"))?,
+                    beta: tensor(node, 2, device).ok_or_else(|| required("Beta"))?,
+                    running_mean: tensor(node, 3, device)
+                        .ok_or_else(|| required("Running mean"))?,
+                    running_var: tensor(node, 4, device).ok_or_else(|| required("Running var"))?,
+                    epsilon: config.epsilon,
+                })
+            }
+            NodeType::LayerNormalization => {
+                let (config, _full_precision) = layer_norm_config(node);
+                let mut norm = config.init(device);
+                norm.gamma = param(node, 1, device).ok_or_else(|| required("Gamma"))?;
+                if let Some(beta) = param(node, 2, device) {
+                    norm.beta = beta;
+                }
+                Layer::LayerNorm(norm)
+            }
+            NodeType::InstanceNormalization => {
+                let mut norm = instance_norm_config(node).init(device);
+                norm.gamma = Some(param(node, 1, device).ok_or_else(|| required("Gamma"))?);
+                norm.beta = Some(param(node, 2, device).ok_or_else(|| required("Beta"))?);
+                Layer::InstanceNorm(norm)
+            }
+            NodeType::GroupNormalization => {
+                let config = group_norm_config(node);
+                let mut norm = config.init(device);
+
+                // The scale and bias given per group are repeated for each channel of the group
+                let per_channel = |index| {
+                    let gamma: Tensor<B, 1> = tensor(node, index, device)?;
+                    if gamma.dims()[0] == config.num_channels {
+                        return Some(Param::from_tensor(gamma));
+                    }
+                    let group_size = config.num_channels / config.num_groups;
+                    let per_channel = gamma
+                        .unsqueeze_dim::<2>(1)
+                        .repeat_dim(1, group_size)
+                        .flatten(0, 1);
+                    Some(Param::from_tensor(per_channel))
+                };
+                norm.gamma = Some(per_channel(1).ok_or_else(|| required("Gamma"))?);
+                norm.beta = Some(per_channel(2).ok_or_else(|| required("Beta"))?);
+                Layer::GroupNorm(norm)
+            }
+            NodeType::PRelu => {
+                let mut prelu = PReluConfig::new().init(device);
+                let mut weight =
+                    extract_data_serialize::<f32>(1, node).ok_or_else(|| required("Alpha"))?;
+                if weight.shape.len() > 1 {
+                    if weight.shape[1..].iter().product::<usize>() == 1 {
+                        // Burn accepts rank 1 alpha weight
+                        weight.shape = weight.shape[..1].to_vec();
+                    } else {
+                        return Err(Error::InvalidNode {
+                            node: node.name.clone(),
+                            message: format!("Invalid PRelu weight with shape {:?}", weight.shape),
+                        });
+                    }
+                }
+                prelu.alpha = Param::from_tensor(Tensor::from_data(weight, device));
+                Layer::PRelu(prelu)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(layer))
+    }
+
+    pub(crate) fn forward(&self, input: DynTensor<B>) -> Result<DynTensor<B>, Error> {
+        let output = match self {
+            Layer::Conv1d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::Conv2d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::Conv3d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::ConvTranspose1d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::ConvTranspose2d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::ConvTranspose3d(conv) => DynTensor::new(conv.forward(input.try_into_tensor()?)),
+            Layer::MaxPool1d(pool) => DynTensor::new(pool.forward(input.try_into_tensor()?)),
+            Layer::MaxPool2d(pool) => DynTensor::new(pool.forward(input.try_into_tensor()?)),
+            Layer::AvgPool1d(pool) => DynTensor::new(pool.forward(input.try_into_tensor()?)),
+            Layer::AvgPool2d(pool) => DynTensor::new(pool.forward(input.try_into_tensor()?)),
+            Layer::GlobalAvgPool => match input.rank() {
+                3 => DynTensor::new(
+                    AdaptiveAvgPool1dConfig::new(1)
+                        .init()
+                        .forward(input.try_into_tensor()?),
+                ),
+                4 => DynTensor::new(
+                    AdaptiveAvgPool2dConfig::new([1, 1])
+                        .init()
+                        .forward(input.try_into_tensor()?),
+                ),
+                _ => {
+                    return Err(Error::invalid_shape(
+                        NodeType::GlobalAveragePool,
+                        input.dims(),
+                    ))
+                }
+            },
+            Layer::Linear(linear) => {
+                with_rank!(input.rank(), D => DynTensor::new(linear.forward(input.into_tensor::<D>())))
+            }
+            Layer::BatchNorm(_) if input.rank() < 2 => {
+                return Err(Error::invalid_shape(
+                    NodeType::BatchNormalization,
+                    input.dims(),
+                ))
+            }
+            Layer::BatchNorm(norm) => {
+                with_rank!(input.rank(), D => DynTensor::new(norm.forward(input.into_tensor::<D>())))
+            }
+            Layer::LayerNorm(norm) => {
+                with_rank!(input.rank(), D => DynTensor::new(norm.forward(input.into_tensor::<D>())))
+            }
+            Layer::InstanceNorm(norm) => {
+                with_rank!(input.rank(), D => DynTensor::new(norm.forward(input.into_tensor::<D>())))
+            }
+            Layer::GroupNorm(norm) => {
+                with_rank!(input.rank(), D => DynTensor::new(norm.forward(input.into_tensor::<D>())))
+            }
+            Layer::PRelu(prelu) => {
+                with_rank!(input.rank(), D => DynTensor::new(prelu.forward(input.into_tensor::<D>())))
+            }
+        };
+
+        Ok(output)
+    }
+}
+
+impl<B: Backend> BatchNorm<B> {
+    fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let mut shape = [1; D];
+        shape[1] = input.dims()[1];
+
+        let mean = self.running_mean.clone().reshape(shape);
+        let std = self
+            .running_var
+            .clone()
+            .reshape(shape)
+            .add_scalar(self.epsilon)
+            .sqrt();
+
+        input
+            .sub(mean)
+            .div(std)
+            .mul(self.gamma.clone().reshape(shape))
+            .add(self.beta.clone().reshape(shape))
+    }
+}
+
+/// Loads the constant input of the node at the given index.
+fn tensor<B: Backend, const D: usize>(
+    node: &Node,
+    index: usize,
+    device: &B::Device,
+) -> Option<Tensor<B, D>> {
+    let data: TensorData = extract_data_serialize::<B::FloatElem>(index, node)?;
+    Some(Tensor::from_data(data, device))
+}
+
+fn param<B: Backend, const D: usize>(
+    node: &Node,
+    index: usize,
+    device: &B::Device,
+) -> Option<Param<Tensor<B, D>>> {
+    tensor(node, index, device).map(Param::from_tensor)
+}
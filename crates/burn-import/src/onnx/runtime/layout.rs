@@ -0,0 +1,495 @@
+use core::ops::Range;
+
+use burn::tensor::{backend::Backend, BasicOps, Element, Int, Numeric, Shape, Tensor};
+use onnx_ir::ir::NodeType;
+
+use super::{
+    value::{with_rank, DynTensor},
+    Error,
+};
+use crate::burn::node::split::SplitConfig;
+
+/// Reshapes the tensor, a `0` keeps the input dimension and a `-1` is inferred from the others.
+pub(crate) fn reshape<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    shape: &[i64],
+) -> Result<DynTensor<B, K>, Error> {
+    let dims = tensor.dims();
+    let invalid = || Error::invalid_shape(NodeType::Reshape, dims.clone());
+    let mut output = shape
+        .iter()
+        .enumerate()
+        .map(|(i, dim)| match dim {
+            0 => dims.get(i).copied().ok_or_else(invalid),
+            -1 => Ok(1),
+            dim => usize::try_from(*dim).map_err(|_| invalid()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let num_elements = dims.iter().product::<usize>();
+    if let Some(inferred) = shape.iter().position(|dim| *dim == -1) {
+        let known = output.iter().product::<usize>();
+        if known == 0 || num_elements % known != 0 {
+            return Err(invalid());
+        }
+        output[inferred] = num_elements / known;
+    }
+    if output.iter().product::<usize>() != num_elements {
+        return Err(invalid());
+    }
+
+    Ok(tensor.reshape(output))
+}
+
+/// Flattens the dimensions from `start` to `end`, both included.
+pub(crate) fn flatten<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    start: usize,
+    end: usize,
+) -> Result<DynTensor<B, K>, Error> {
+    let dims = tensor.dims();
+    if start > end || end >= dims.len() {
+        return Err(Error::invalid_shape(NodeType::Flatten, dims));
+    }
+
+    let mut output = dims[..start].to_vec();
+    output.push(dims[start..=end].iter().product());
+    output.extend(&dims[end + 1..]);
+
+    Ok(tensor.reshape(output))
+}
+
+pub(crate) fn permute<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    perm: &[i64],
+) -> Result<DynTensor<B, K>, Error> {
+    let rank = tensor.rank();
+    if perm.len() != rank {
+        return Err(Error::InvalidRank {
+            expected: perm.len(),
+            actual: rank,
+        });
+    }
+
+    let axes = perm
+        .iter()
+        .map(|axis| axis.rem_euclid(rank as i64) as usize)
+        .collect::<Vec<_>>();
+
+    Ok(DynTensor::from_primitive(K::permute(
+        tensor.into_primitive(),
+        &axes,
+    )))
+}
+
+/// Removes the given unit dimensions, or all of them when no axes are given.
+pub(crate) fn squeeze<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    axes: &[i64],
+) -> Result<DynTensor<B, K>, Error> {
+    let dims = tensor.dims();
+    let rank = dims.len() as i64;
+    let axes = axes
+        .iter()
+        .map(|axis| axis.rem_euclid(rank) as usize)
+        .collect::<Vec<_>>();
+
+    let squeezed = |i: usize| match axes.is_empty() {
+        true => dims[i] == 1,
+        false => axes.contains(&i),
+    };
+    if (0..dims.len()).any(|i| squeezed(i) && dims[i] != 1) {
+        return Err(Error::invalid_shape(NodeType::Squeeze, dims));
+    }
+
+    let output = (0..dims.len())
+        .filter(|i| !squeezed(*i))
+        .map(|i| dims[i])
+        .collect();
+
+    Ok(tensor.reshape(output))
+}
+
+/// Inserts unit dimensions at the given axes of the output.
+pub(crate) fn unsqueeze<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    axes: &[i64],
+) -> Result<DynTensor<B, K>, Error> {
+    let dims = tensor.dims();
+    let rank = (dims.len() + axes.len()) as i64;
+    let axes = axes
+        .iter()
+        .map(|axis| axis.rem_euclid(rank) as usize)
+        .collect::<Vec<_>>();
+
+    let mut input_dims = dims.iter();
+    let output = (0..rank as usize)
+        .map(|i| match axes.contains(&i) {
+            true => Some(1),
+            false => input_dims.next().copied(),
+        })
+        .collect::<Option<Vec<_>>>()
+        // Repeated axes insert fewer dimensions than expected
+        .ok_or_else(|| Error::invalid_shape(NodeType::Unsqueeze, dims.clone()))?;
+
+    Ok(tensor.reshape(output))
+}
+
+pub(crate) fn slice<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    ranges: &[Option<(i64, i64)>],
+) -> Result<DynTensor<B, K>, Error> {
+    Ok(with_rank!(tensor.rank(), D => {
+        let ranges: [Option<(i64, i64)>; D] = ranges.try_into().map_err(|_| Error::InvalidRank {
+            expected: ranges.len(),
+            actual: D,
+        })?;
+        DynTensor::new(tensor.into_tensor::<D>().slice(ranges))
+    }))
+}
+
+/// Broadcasts the tensor to the given shape, following the ONNX `Expand` semantics.
+pub(crate) fn expand<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    shape: &[i64],
+) -> Result<DynTensor<B, K>, Error> {
+    let rank = tensor.rank().max(shape.len());
+    let tensor = super::elementwise::unsqueeze_to(tensor, rank);
+    let offset = rank - shape.len();
+    let dims = tensor.dims();
+
+    let output = dims
+        .iter()
+        .enumerate()
+        .map(|(i, dim)| match i.checked_sub(offset).map(|i| shape[i]) {
+            Some(1) | Some(-1) | None => Ok(*dim),
+            Some(target) if *dim == 1 || target == *dim as i64 => Ok(target as usize),
+            Some(_) => Err(Error::invalid_shape(NodeType::Expand, dims.clone())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DynTensor::from_primitive(K::expand(
+        tensor.into_primitive(),
+        Shape::from(output),
+    )))
+}
+
+pub(crate) fn tile<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    repeats: &[usize],
+) -> Result<DynTensor<B, K>, Error> {
+    if repeats.len() > tensor.rank() {
+        return Err(Error::InvalidRank {
+            expected: repeats.len(),
+            actual: tensor.rank(),
+        });
+    }
+
+    let primitive = repeats
+        .iter()
+        .enumerate()
+        .filter(|(_, times)| **times > 1)
+        .fold(tensor.into_primitive(), |primitive, (dim, times)| {
+            K::repeat_dim(primitive, dim, *times)
+        });
+
+    Ok(DynTensor::from_primitive(primitive))
+}
+
+pub(crate) fn split<B: Backend, K: BasicOps<B>>(
+    tensor: DynTensor<B, K>,
+    config: &SplitConfig,
+    num_outputs: usize,
+) -> Result<Vec<DynTensor<B, K>>, Error> {
+    let dims = tensor.dims();
+    let axis = config.axis;
+    if axis >= dims.len() {
+        return Err(Error::invalid_shape(NodeType::Split, dims));
+    }
+
+    let sizes = match &config.split_sizes {
+        Some(sizes) => sizes.clone(),
+        None => {
+            // Same as `chunk`, the last output is smaller when the size isn't divisible
+            let size = dims[axis].div_ceil(num_outputs.max(1));
+            (0..num_outputs)
+                .map(|i| size.min(dims[axis].saturating_sub(i * size)))
+                .filter(|size| *size > 0)
+                .collect()
+        }
+    };
+    if sizes.iter().sum::<usize>() > dims[axis] {
+        return Err(Error::invalid_shape(NodeType::Split, dims));
+    }
+
+    let mut start = 0;
+    let outputs = sizes
+        .into_iter()
+        .map(|size| {
+            let ranges = dims
+                .iter()
+                .enumerate()
+                .map(|(i, dim)| {
+                    if i == axis {
+                        start..start + size
+                    } else {
+                        0..*dim
+                    }
+                })
+                .collect::<Vec<Range<usize>>>();
+            start += size;
+
+            DynTensor::from_primitive(K::slice(tensor.clone().into_primitive(), &ranges))
+        })
+        .collect();
+
+    Ok(outputs)
+}
+
+pub(crate) fn concat<B: Backend, K: BasicOps<B>>(
+    tensors: Vec<DynTensor<B, K>>,
+    dim: usize,
+) -> Result<DynTensor<B, K>, Error> {
+    let dims = tensors.first().map(DynTensor::dims).unwrap_or_default();
+    let compatible = dim < dims.len()
+        && tensors.iter().all(|tensor| {
+            let other = tensor.dims();
+            other.len() == dims.len() && (0..dims.len()).all(|i| i == dim || other[i] == dims[i])
+        });
+    if !compatible {
+        return Err(Error::invalid_shape(NodeType::Concat, dims));
+    }
+
+    let primitives = tensors.into_iter().map(DynTensor::into_primitive).collect();
+    Ok(DynTensor::from_primitive(K::cat(primitives, dim)))
+}
+
+/// Selects the slices of the given indices along `dim`, the indices dimensions replacing it.
+pub(crate) fn gather<B, K>(
+    tensor: DynTensor<B, K>,
+    dim: usize,
+    indices: DynTensor<B, Int>,
+) -> Result<DynTensor<B, K>, Error>
+where
+    B: Backend,
+    K: Numeric<B>,
+    K::Elem: Element,
+{
+    let dims = tensor.dims();
+    if dim >= dims.len() {
+        return Err(Error::invalid_shape(NodeType::Gather, dims));
+    }
+
+    let index_dims = indices.dims();
+    let num_indices = index_dims.iter().product();
+    let indices = indices.reshape(vec![num_indices]).into_tensor::<1>();
+
+    let selected =
+        DynTensor::<B, K>::from_primitive(K::select(tensor.into_primitive(), dim, indices));
+
+    let mut output = dims[..dim].to_vec();
+    output.extend(index_dims);
+    output.extend(&dims[dim + 1..]);
+    Ok(selected.reshape(output))
+}
+
+/// Selects a single slice along `dim`, removing the dimension.
+pub(crate) fn gather_scalar<B, K>(
+    tensor: DynTensor<B, K>,
+    dim: usize,
+    index: i64,
+) -> Result<DynTensor<B, K>, Error>
+where
+    B: Backend,
+    K: Numeric<B>,
+    K::Elem: Element,
+{
+    let dims = tensor.dims();
+    if dims.get(dim).copied().unwrap_or(0) == 0 {
+        return Err(Error::invalid_shape(NodeType::Gather, dims));
+    }
+
+    let index = index.rem_euclid(dims[dim] as i64);
+    let indices = Tensor::<B, 1, Int>::from_data([index], &tensor.device());
+
+    let selected =
+        DynTensor::<B, K>::from_primitive(K::select(tensor.into_primitive(), dim, indices));
+
+    let mut output = dims[..dim].to_vec();
+    output.extend(&dims[dim + 1..]);
+    Ok(selected.reshape(output))
+}
+
+pub(crate) fn gather_elements<B, K>(
+    tensor: DynTensor<B, K>,
+    dim: usize,
+    indices: DynTensor<B, Int>,
+) -> Result<DynTensor<B, K>, Error>
+where
+    B: Backend,
+    K: Numeric<B>,
+    K::Elem: Element,
+{
+    if dim >= tensor.rank() {
+        return Err(Error::invalid_shape(
+            NodeType::GatherElements,
+            tensor.dims(),
+        ));
+    }
+
+    Ok(with_rank!(tensor.rank(), D => DynTensor::new(
+        tensor.into_tensor::<D>().gather(dim, indices.try_into_tensor::<D>()?)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::tensor::TensorData;
+
+    type TestBackend = burn::backend::NdArray<f32>;
+
+    fn tensor(dims: Vec<usize>) -> DynTensor<TestBackend> {
+        let num_elements = dims.iter().product::<usize>() as i64;
+        let tensor = Tensor::<TestBackend, 1, Int>::arange(0..num_elements, &Default::default());
+
+        DynTensor::new(tensor.float()).reshape(dims)
+    }
+
+    #[test]
+    fn reshape_should_keep_and_infer_dims() {
+        let output = reshape(tensor(vec![2, 3, 4]), &[0, -1]).unwrap();
+
+        assert_eq!(output.dims(), vec![2, 12]);
+    }
+
+    #[test]
+    fn reshape_should_fail_when_the_number_of_elements_changes() {
+        let result = reshape(tensor(vec![2, 3]), &[4, -1]);
+
+        assert!(matches!(result, Err(Error::InvalidShape { .. })));
+        assert!(reshape(tensor(vec![2, 3]), &[5]).is_err());
+    }
+
+    #[test]
+    fn flatten_should_merge_the_included_dims() {
+        let output = flatten(tensor(vec![2, 3, 4, 5]), 1, 2).unwrap();
+
+        assert_eq!(output.dims(), vec![2, 12, 5]);
+        assert!(flatten(tensor(vec![2, 3]), 1, 2).is_err());
+    }
+
+    #[test]
+    fn permute_should_accept_negative_axes() {
+        let output = permute(tensor(vec![2, 3, 4]), &[-1, 0, 1]).unwrap();
+
+        assert_eq!(output.dims(), vec![4, 2, 3]);
+        assert!(matches!(
+            permute(tensor(vec![2, 3]), &[0, 1, 2]),
+            Err(Error::InvalidRank {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn squeeze_should_remove_unit_dims() {
+        assert_eq!(squeeze(tensor(vec![1, 3, 1]), &[]).unwrap().dims(), vec![3]);
+        assert_eq!(
+            squeeze(tensor(vec![1, 3, 1]), &[-1]).unwrap().dims(),
+            vec![1, 3]
+        );
+        assert!(squeeze(tensor(vec![1, 3, 1]), &[1]).is_err());
+    }
+
+    #[test]
+    fn unsqueeze_should_insert_unit_dims_in_the_output() {
+        let output = unsqueeze(tensor(vec![2, 3]), &[0, -1]).unwrap();
+
+        assert_eq!(output.dims(), vec![1, 2, 3, 1]);
+        assert!(unsqueeze(tensor(vec![2, 3]), &[0, 0]).is_err());
+    }
+
+    #[test]
+    fn slice_should_fail_when_the_ranges_dont_match_the_rank() {
+        let output = slice(tensor(vec![4, 3]), &[Some((1, 3)), None]).unwrap();
+
+        assert_eq!(output.dims(), vec![2, 3]);
+        assert!(slice(tensor(vec![4, 3]), &[Some((1, 3))]).is_err());
+    }
+
+    #[test]
+    fn expand_should_broadcast_unit_dims() {
+        let output = expand(tensor(vec![3, 1]), &[2, 1, 4]).unwrap();
+
+        assert_eq!(output.dims(), vec![2, 3, 4]);
+        assert!(expand(tensor(vec![3, 2]), &[3, 4]).is_err());
+    }
+
+    #[test]
+    fn tile_should_repeat_each_dim() {
+        let output = tile(tensor(vec![2, 3]), &[2, 1]).unwrap();
+
+        assert_eq!(output.dims(), vec![4, 3]);
+        assert!(tile(tensor(vec![2]), &[2, 1]).is_err());
+    }
+
+    #[test]
+    fn split_should_chunk_like_the_codegen() {
+        let config = SplitConfig::new(1);
+        let outputs = split(tensor(vec![2, 5]), &config, 2).unwrap();
+
+        let dims = outputs.iter().map(DynTensor::dims).collect::<Vec<_>>();
+        assert_eq!(dims, vec![vec![2, 3], vec![2, 2]]);
+    }
+
+    #[test]
+    fn split_should_fail_when_the_sizes_exceed_the_dim() {
+        let config = SplitConfig::new(0).with_split_sizes(Some(vec![1, 2]));
+
+        assert!(split(tensor(vec![2, 5]), &config, 2).is_err());
+    }
+
+    #[test]
+    fn concat_should_check_the_other_dims() {
+        let output = concat(vec![tensor(vec![2, 3]), tensor(vec![1, 3])], 0).unwrap();
+
+        assert_eq!(output.dims(), vec![3, 3]);
+        assert!(concat(vec![tensor(vec![2, 3]), tensor(vec![2, 2])], 0).is_err());
+        assert!(concat(vec![tensor(vec![2, 3]), tensor(vec![2, 3, 1])], 0).is_err());
+    }
+
+    #[test]
+    fn gather_should_replace_the_dim_with_the_indices_dims() {
+        let indices = Tensor::<TestBackend, 2, Int>::from_ints([[2, 0]], &Default::default());
+        let output = gather(tensor(vec![2, 3]), 1, DynTensor::new(indices)).unwrap();
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([[[2f32, 0.]], [[5., 3.]]]), true);
+    }
+
+    #[test]
+    fn gather_scalar_should_wrap_negative_indices() {
+        let output = gather_scalar(tensor(vec![2, 3]), 0, -1).unwrap();
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([3f32, 4., 5.]), true);
+        assert!(gather_scalar(tensor(vec![2, 3]), 2, 0).is_err());
+    }
+
+    #[test]
+    fn gather_elements_should_fail_when_the_ranks_differ() {
+        let indices = Tensor::<TestBackend, 1, Int>::from_ints([0, 1], &Default::default());
+
+        assert!(matches!(
+            gather_elements(tensor(vec![2, 3]), 1, DynTensor::new(indices)),
+            Err(Error::InvalidRank {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,22 @@
+//! Runtime evaluation of ONNX models.
+//!
+//! Instead of generating the source code of the model at build time, the intermediate
+//! representation of the graph from `onnx-ir` is loaded when the program runs. The weights are
+//! instantiated as tensors on any backend and the nodes are executed in topological order, with
+//! the same op semantics as the generated code.
+//!
+//! The tensors flowing between the nodes are rank-erased [values](Value), since the ranks are
+//! only known once the graph is loaded.
+
+mod elementwise;
+mod error;
+mod layer;
+mod layout;
+mod model;
+mod op;
+mod reduce;
+mod value;
+
+pub use error::*;
+pub use model::OnnxModel;
+pub use value::{DynTensor, Scalar, Value, ValueKind};
@@ -0,0 +1,436 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use burn::tensor::{backend::Backend, BasicOps};
+use onnx_ir::{
+    ir::{ArgType, Argument, ElementType, Node},
+    parse_onnx, OnnxGraph,
+};
+
+use super::{
+    layout,
+    op::{constant_value, Op},
+    value::{DynTensor, Value},
+    Error,
+};
+
+/// An ONNX model evaluated at runtime, without generating code.
+///
+/// The weights are instantiated as tensors on the device when the model is loaded, and the nodes
+/// are executed in topological order on each call to [forward](OnnxModel::forward).
+///
+/// # Example
+///
+/// ```no_run
+/// use burn::backend::NdArray;
+/// use burn_import::onnx::runtime::{OnnxModel, Value};
+///
+/// let device = Default::default();
+/// let model = OnnxModel::<NdArray>::from_file("model.onnx", &device).unwrap();
+/// let outputs = model.forward(vec![Value::from(1.0)]).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct OnnxModel<B: Backend> {
+    graph: Graph<B>,
+    device: B::Device,
+}
+
+impl<B: Backend> OnnxModel<B> {
+    /// Loads the model from an ONNX file.
+    pub fn from_file(path: impl AsRef<Path>, device: &B::Device) -> Result<Self, Error> {
+        let path = path.as_ref();
+        // The parser panics on IO errors, so the file is checked first
+        File::open(path)?;
+
+        // The parser also panics on invalid files, the panic is returned as an error
+        let graph =
+            panic::catch_unwind(AssertUnwindSafe(|| parse_onnx(path))).map_err(|panic| {
+                let message = match panic.downcast::<String>() {
+                    Ok(message) => *message,
+                    Err(panic) => match panic.downcast::<&str>() {
+                        Ok(message) => message.to_string(),
+                        Err(_) => "The parser panicked".to_string(),
+                    },
+                };
+                Error::Parse(message)
+            })?;
+
+        Self::from_graph(graph, device)
+    }
+
+    /// Loads the model from the intermediate representation of the graph.
+    pub fn from_graph(graph: OnnxGraph, device: &B::Device) -> Result<Self, Error> {
+        Ok(Self {
+            graph: Graph::new(graph, Vec::new(), device)?,
+            device: device.clone(),
+        })
+    }
+
+    /// The number of inputs of the model.
+    pub fn num_inputs(&self) -> usize {
+        self.graph.inputs.len()
+    }
+
+    /// Evaluates the model, the inputs and outputs follow the order of the graph.
+    ///
+    /// The inputs must have the kind and rank of the graph inputs.
+    pub fn forward(&self, inputs: Vec<Value<B>>) -> Result<Vec<Value<B>>, Error> {
+        if inputs.len() != self.graph.inputs.len() {
+            return Err(Error::InputCount {
+                expected: self.graph.inputs.len(),
+                actual: inputs.len(),
+            });
+        }
+        for (argument, value) in self.graph.input_types.iter().zip(inputs.iter()) {
+            check_input(argument, value)?;
+        }
+
+        self.graph.forward(inputs, &self.device)
+    }
+}
+
+/// Checks that a value given to the model matches the type of the graph input.
+fn check_input<B: Backend>(argument: &Argument, value: &Value<B>) -> Result<(), Error> {
+    let valid = match (&argument.ty, value) {
+        (ArgType::Scalar(_), Value::Scalar(_)) | (ArgType::Shape(_), Value::Shape(_)) => true,
+        (ArgType::Tensor(tensor), Value::Float(value)) => {
+            matches!(
+                tensor.elem_type,
+                ElementType::Float16 | ElementType::Float32 | ElementType::Float64
+            ) && value.rank() == tensor.dim
+        }
+        (ArgType::Tensor(tensor), Value::Int(value)) => {
+            matches!(
+                tensor.elem_type,
+                ElementType::Int32 | ElementType::Int64 | ElementType::Int8 | ElementType::Uint8
+            ) && value.rank() == tensor.dim
+        }
+        (ArgType::Tensor(tensor), Value::Bool(value)) => {
+            matches!(tensor.elem_type, ElementType::Bool) && value.rank() == tensor.dim
+        }
+        _ => false,
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err(Error::InvalidInput {
+            name: argument.name.clone(),
+            expected: match &argument.ty {
+                ArgType::Scalar(elem_type) => format!("{elem_type:?} scalar"),
+                ArgType::Shape(_) => "shape".to_string(),
+                ArgType::Tensor(tensor) => {
+                    format!("{:?} tensor of rank {}", tensor.elem_type, tensor.dim)
+                }
+            },
+            actual: value.description(),
+        }),
+    }
+}
+
+/// A graph whose nodes are ready to be executed.
+#[derive(Debug)]
+pub(crate) struct Graph<B: Backend> {
+    nodes: Vec<GraphNode<B>>,
+    constants: HashMap<String, Value<B>>,
+    inputs: Vec<String>,
+    /// The types of the inputs, checked for the values given to the model.
+    input_types: Vec<Argument>,
+    outputs: Vec<String>,
+    /// The inputs of the control flow node in the enclosing graph, for a subgraph.
+    outer_inputs: Vec<String>,
+}
+
+#[derive(Debug)]
+struct GraphNode<B: Backend> {
+    op: Op<B>,
+    /// The runtime inputs, `None` for the omitted optional inputs.
+    inputs: Vec<Option<String>>,
+    outputs: Vec<String>,
+}
+
+impl<B: Backend> Graph<B> {
+    fn new(graph: OnnxGraph, outer_inputs: Vec<String>, device: &B::Device) -> Result<Self, Error> {
+        let mut constants = HashMap::new();
+        let mut register = |argument: &Argument| -> Result<(), Error> {
+            if argument.value.is_some() && !constants.contains_key(&argument.name) {
+                constants.insert(argument.name.clone(), constant_value(argument, device)?);
+            }
+            Ok(())
+        };
+
+        let mut nodes = Vec::with_capacity(graph.nodes.len());
+        for node in graph.nodes.iter() {
+            let op = Op::from_node(node, device)?;
+            let num_inputs = op.num_runtime_inputs().unwrap_or(node.inputs.len());
+            let inputs = node
+                .inputs
+                .iter()
+                .take(num_inputs)
+                .map(|input| match input.name.is_empty() {
+                    true => Ok(None),
+                    false => {
+                        register(input)?;
+                        Ok(Some(input.name.clone()))
+                    }
+                })
+                .collect::<Result<_, Error>>()?;
+            let outputs = node
+                .outputs
+                .iter()
+                .map(|output| output.name.clone())
+                .collect();
+
+            nodes.push(GraphNode {
+                op,
+                inputs,
+                outputs,
+            });
+        }
+        graph.outputs.iter().try_for_each(&mut register)?;
+
+        Ok(Self {
+            nodes,
+            constants,
+            inputs: graph
+                .inputs
+                .iter()
+                .map(|input| input.name.clone())
+                .collect(),
+            input_types: graph.inputs.clone(),
+            outputs: graph
+                .outputs
+                .iter()
+                .map(|output| output.name.clone())
+                .collect(),
+            outer_inputs,
+        })
+    }
+
+    /// Prepares the subgraph attribute of a control flow node.
+    pub(crate) fn from_subgraph(
+        node: &Node,
+        attribute: &str,
+        device: &B::Device,
+    ) -> Result<Self, Error> {
+        let subgraph = node
+            .attrs
+            .get(attribute)
+            .ok_or_else(|| Error::InvalidNode {
+                node: node.name.clone(),
+                message: format!("{attribute} attribute is required"),
+            })?
+            .clone()
+            .into_graph();
+        let outer_inputs = node.inputs.iter().map(|input| input.name.clone()).collect();
+
+        Self::new(subgraph, outer_inputs, device)
+    }
+
+    pub(crate) fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn forward(&self, inputs: Vec<Value<B>>, device: &B::Device) -> Result<Vec<Value<B>>, Error> {
+        self.forward_captured(inputs, Vec::new(), device)
+    }
+
+    /// Evaluates a subgraph, the values of the enclosing graph it uses are the remaining inputs
+    /// of the control flow node.
+    pub(crate) fn forward_captured(
+        &self,
+        inputs: Vec<Value<B>>,
+        outer_inputs: Vec<Option<Value<B>>>,
+        device: &B::Device,
+    ) -> Result<Vec<Value<B>>, Error> {
+        let mut values = self.constants.clone();
+        for (name, value) in self.outer_inputs.iter().zip(outer_inputs) {
+            if let Some(value) = value {
+                values.insert(name.clone(), value);
+            }
+        }
+        for (name, value) in self.inputs.iter().zip(inputs) {
+            values.insert(name.clone(), value);
+        }
+
+        for node in self.nodes.iter() {
+            let inputs = node
+                .inputs
+                .iter()
+                .map(|name| name.as_ref().map(|name| value(&values, name)).transpose())
+                .collect::<Result<_, _>>()?;
+            let outputs = node.op.forward(inputs, node.outputs.len(), device)?;
+
+            for (name, output) in node.outputs.iter().zip(outputs) {
+                values.insert(name.clone(), output);
+            }
+        }
+
+        self.outputs
+            .iter()
+            .map(|name| value(&values, name))
+            .collect()
+    }
+}
+
+/// The value of a boolean condition, given as a scalar or a single element tensor.
+pub(crate) fn is_true<B: Backend>(value: Value<B>) -> Result<bool, Error> {
+    match value {
+        Value::Scalar(condition) => Ok(condition.as_bool()),
+        Value::Bool(condition) => Ok(condition.into_data().iter::<bool>().next().unwrap_or(false)),
+        value => Err(Error::InvalidKind {
+            expected: "bool tensor",
+            actual: value.kind(),
+        }),
+    }
+}
+
+fn value<B: Backend>(values: &HashMap<String, Value<B>>, name: &str) -> Result<Value<B>, Error> {
+    values
+        .get(name)
+        .cloned()
+        .ok_or_else(|| Error::UndefinedValue(name.to_string()))
+}
+
+/// The given input of a control flow node, which must not be omitted.
+fn required<B: Backend>(inputs: &[Option<Value<B>>], index: usize) -> Result<Value<B>, Error> {
+    inputs
+        .get(index)
+        .cloned()
+        .flatten()
+        .ok_or(Error::MissingInput(index))
+}
+
+/// Executes a `Loop` node, see the codegen [LoopNode](crate::burn::node::loop_node::LoopNode).
+///
+/// The inputs are the trip count, the condition, the loop-carried values and the captured values.
+pub(crate) fn run_loop<B: Backend>(
+    body: &Graph<B>,
+    inputs: Vec<Option<Value<B>>>,
+    device: &B::Device,
+) -> Result<Vec<Value<B>>, Error> {
+    // The positional inputs are kept since the body can also capture them
+    let num_carried = body.num_inputs().saturating_sub(2);
+    let max_trip_count = match inputs.first().cloned().flatten() {
+        Some(count) => Some(count.into_scalar()?.as_i64()),
+        None => None,
+    };
+    let mut condition = match inputs.get(1).cloned().flatten() {
+        Some(condition) => is_true(condition)?,
+        None => true,
+    };
+    let mut carried = (2..num_carried + 2)
+        .map(|index| required(&inputs, index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut scanned: Vec<Vec<Value<B>>> = Vec::new();
+    let mut iteration = 0;
+    while condition && max_trip_count.map(|max| iteration < max).unwrap_or(true) {
+        let mut body_inputs = vec![Value::from(iteration), Value::from(condition)];
+        body_inputs.append(&mut carried);
+
+        let mut outputs = body
+            .forward_captured(body_inputs, inputs.clone(), device)?
+            .into_iter();
+        condition = is_true(outputs.next().ok_or(Error::MissingInput(0))?)?;
+        carried = outputs.by_ref().take(num_carried).collect();
+
+        for (i, output) in outputs.enumerate() {
+            match scanned.get_mut(i) {
+                Some(values) => values.push(output),
+                None => scanned.push(vec![output]),
+            }
+        }
+        iteration += 1;
+    }
+
+    carried
+        .into_iter()
+        .map(Ok)
+        .chain(scanned.into_iter().map(stack))
+        .collect()
+}
+
+/// Executes a `Scan` node, see the codegen [ScanNode](crate::burn::node::scan::ScanNode).
+///
+/// The inputs are the initial states, the scan inputs iterated over their first dimension and the
+/// captured values.
+pub(crate) fn run_scan<B: Backend>(
+    body: &Graph<B>,
+    num_scan_inputs: usize,
+    inputs: Vec<Option<Value<B>>>,
+    device: &B::Device,
+) -> Result<Vec<Value<B>>, Error> {
+    // The positional inputs are kept since the body can also capture them
+    let num_states = body.num_inputs().saturating_sub(num_scan_inputs);
+    let mut states = (0..num_states)
+        .map(|index| required(&inputs, index))
+        .collect::<Result<Vec<_>, _>>()?;
+    let scan_inputs = (num_states..num_states + num_scan_inputs)
+        .map(|index| required(&inputs, index)?.into_float())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let num_iterations = scan_inputs
+        .first()
+        .map(|input| input.dims()[0])
+        .ok_or(Error::MissingInput(num_states))?;
+    let mut scanned: Vec<Vec<Value<B>>> = Vec::new();
+    for iteration in 0..num_iterations {
+        let mut body_inputs = std::mem::take(&mut states);
+        for input in scan_inputs.iter() {
+            let dims = input.dims();
+            let mut ranges = vec![None; dims.len()];
+            ranges[0] = Some((iteration as i64, iteration as i64 + 1));
+            let slice = layout::slice(input.clone(), &ranges)?;
+            body_inputs.push(layout::squeeze(slice, &[0])?.into());
+        }
+
+        let mut outputs = body
+            .forward_captured(body_inputs, inputs.clone(), device)?
+            .into_iter();
+        states = outputs.by_ref().take(num_states).collect();
+
+        for (i, output) in outputs.enumerate() {
+            match scanned.get_mut(i) {
+                Some(values) => values.push(output),
+                None => scanned.push(vec![output]),
+            }
+        }
+    }
+
+    states
+        .into_iter()
+        .map(Ok)
+        .chain(scanned.into_iter().map(stack))
+        .collect()
+}
+
+/// Stacks the values of each iteration on a new first dimension.
+fn stack<B: Backend>(values: Vec<Value<B>>) -> Result<Value<B>, Error> {
+    fn stack_tensors<B: Backend, K: BasicOps<B>>(
+        tensors: Vec<Result<DynTensor<B, K>, Error>>,
+    ) -> Result<DynTensor<B, K>, Error> {
+        let tensors = tensors
+            .into_iter()
+            .map(|tensor| layout::unsqueeze(tensor?, &[0]))
+            .collect::<Result<_, _>>()?;
+        layout::concat(tensors, 0)
+    }
+
+    match values.first() {
+        Some(Value::Float(_)) => {
+            Ok(stack_tensors(values.into_iter().map(Value::into_float).collect())?.into())
+        }
+        Some(Value::Int(_)) => {
+            Ok(stack_tensors(values.into_iter().map(Value::into_int).collect())?.into())
+        }
+        Some(Value::Bool(_)) => {
+            Ok(stack_tensors(values.into_iter().map(Value::into_bool).collect())?.into())
+        }
+        Some(value) => Err(Error::unsupported("Stack", &[value.kind()])),
+        None => Err(Error::MissingInput(0)),
+    }
+}
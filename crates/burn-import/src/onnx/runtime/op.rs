@@ -0,0 +1,696 @@
+use burn::tensor::{backend::Backend, Bool, Float, Int, Shape, Tensor, TensorData};
+use onnx_ir::{
+    convert_constant_value,
+    ir::{ArgType, Argument, Data, ElementType, Node, NodeType},
+};
+
+use super::{
+    elementwise::{self, BinaryOp, CastKind, UnaryOp},
+    layer::Layer,
+    layout,
+    model::{self, Graph},
+    reduce::{self, ReduceOp},
+    value::{map_tensor, with_rank, DynTensor, Scalar, Value},
+    Error,
+};
+use crate::{
+    burn::node::{split::SplitConfig, top_k::TopKConfig, trilu::TriluConfig},
+    onnx::op_configuration::*,
+};
+
+/// The operation of a node, prepared once when the model is loaded.
+///
+/// The attributes and constant inputs are converted with the same configuration functions as the
+/// code generation, so both share the op semantics.
+#[derive(Debug)]
+pub(crate) enum Op<B: Backend> {
+    Layer(Box<Layer<B>>),
+    Unary(UnaryOp),
+    Binary(BinaryOp),
+    Cast(CastKind),
+    Where,
+    MatMul,
+    Sum,
+    Mean,
+    Identity,
+    Constant(Value<B>),
+    Reshape(Vec<i64>),
+    Flatten(usize, usize),
+    Transpose(Vec<i64>),
+    Squeeze(Vec<i64>),
+    Unsqueeze(Vec<i64>),
+    Concat(usize),
+    Gather(usize),
+    GatherElements(usize),
+    Slice(Vec<Option<(i64, i64)>>),
+    /// The shape is a runtime input when `None`.
+    Expand(Option<Vec<i64>>),
+    Tile(Vec<usize>),
+    Split(SplitConfig),
+    Shape(usize, usize),
+    ConstantOfShape(Scalar),
+    Range,
+    TopK(TopKConfig),
+    Trilu(TriluConfig),
+    Reduce(ReduceOp, Option<usize>),
+    ArgMax(usize),
+    ArgMin(usize, bool),
+    If {
+        then_branch: Box<Graph<B>>,
+        else_branch: Box<Graph<B>>,
+    },
+    Loop {
+        body: Box<Graph<B>>,
+    },
+    Scan {
+        body: Box<Graph<B>>,
+        num_scan_inputs: usize,
+    },
+}
+
+impl<B: Backend> Op<B> {
+    pub(crate) fn from_node(node: &Node, device: &B::Device) -> Result<Self, Error> {
+        if let Some(layer) = Layer::from_node(node, device)? {
+            return Ok(Op::Layer(Box::new(layer)));
+        }
+
+        let op = match node.node_type {
+            NodeType::Cos => Op::Unary(UnaryOp::Cos),
+            NodeType::Erf => Op::Unary(UnaryOp::Erf),
+            NodeType::Exp => Op::Unary(UnaryOp::Exp),
+            NodeType::Gelu => Op::Unary(UnaryOp::Gelu),
+            NodeType::HardSigmoid => {
+                let (alpha, beta) = hard_sigmoid_config(node);
+                Op::Unary(UnaryOp::HardSigmoid(alpha, beta))
+            }
+            NodeType::LeakyRelu => Op::Unary(UnaryOp::LeakyRelu(leaky_relu_config(node))),
+            NodeType::Log => Op::Unary(UnaryOp::Log),
+            NodeType::LogSoftmax => Op::Unary(UnaryOp::LogSoftmax(log_softmax_config(node))),
+            NodeType::Neg => Op::Unary(UnaryOp::Neg),
+            NodeType::Not => Op::Unary(UnaryOp::Not),
+            NodeType::Reciprocal => Op::Unary(UnaryOp::Reciprocal),
+            NodeType::Relu => Op::Unary(UnaryOp::Relu),
+            NodeType::Sigmoid => Op::Unary(UnaryOp::Sigmoid),
+            NodeType::Sign => Op::Unary(UnaryOp::Sign),
+            NodeType::Sin => Op::Unary(UnaryOp::Sin),
+            NodeType::Softmax => Op::Unary(UnaryOp::Softmax(softmax_config(node))),
+            NodeType::Sqrt => Op::Unary(UnaryOp::Sqrt),
+            NodeType::Tanh => Op::Unary(UnaryOp::Tanh),
+            NodeType::Clip => {
+                let (min, max) = clip_config(node);
+                Op::Unary(UnaryOp::Clip(min, max))
+            }
+            NodeType::Add => Op::Binary(BinaryOp::Add),
+            NodeType::Sub => Op::Binary(BinaryOp::Sub),
+            NodeType::Mul => Op::Binary(BinaryOp::Mul),
+            NodeType::Div => Op::Binary(BinaryOp::Div),
+            NodeType::Pow => Op::Binary(BinaryOp::Pow),
+            NodeType::Max => Op::Binary(BinaryOp::Max),
+            NodeType::Min => Op::Binary(BinaryOp::Min),
+            NodeType::Equal => Op::Binary(BinaryOp::Equal),
+            NodeType::Greater => Op::Binary(BinaryOp::Greater),
+            NodeType::GreaterOrEqual => Op::Binary(BinaryOp::GreaterOrEqual),
+            NodeType::Less => Op::Binary(BinaryOp::Less),
+            NodeType::LessOrEqual => Op::Binary(BinaryOp::LessOrEqual),
+            NodeType::Cast => Op::Cast(cast_kind(node)?),
+            NodeType::Where => Op::Where,
+            NodeType::MatMul => Op::MatMul,
+            NodeType::Sum => Op::Sum,
+            NodeType::Mean => Op::Mean,
+            NodeType::Dropout | NodeType::Identity => Op::Identity,
+            NodeType::Constant => {
+                Op::Constant(constant_value(&convert_constant_value(node), device)?)
+            }
+            NodeType::Reshape => Op::Reshape(reshape_config(node)),
+            NodeType::Flatten => {
+                let (start, end) = flatten_config(node);
+                Op::Flatten(start, end)
+            }
+            NodeType::Transpose => Op::Transpose(transpose_config(node)),
+            NodeType::Squeeze => Op::Squeeze(squeeze_config(node)),
+            NodeType::Unsqueeze => Op::Unsqueeze(unsqueeze_config(node)),
+            NodeType::Concat => Op::Concat(concat_config(node)),
+            NodeType::Gather => Op::Gather(gather_config(node)),
+            NodeType::GatherElements => Op::GatherElements(gather_config(node)),
+            NodeType::Slice => Op::Slice(slice_config(node)),
+            NodeType::Expand => Op::Expand(match &node.inputs[1].value {
+                Some(Data::Int64s(shape)) => Some(shape.clone()),
+                _ => None,
+            }),
+            NodeType::Tile => Op::Tile(tile_config(node).repeats),
+            NodeType::Split => Op::Split(split_config(node)),
+            NodeType::Shape => {
+                let (start, end) = shape_config(node);
+                Op::Shape(start, end)
+            }
+            NodeType::ConstantOfShape => Op::ConstantOfShape(constant_of_shape_value(node)?),
+            NodeType::Range => Op::Range,
+            NodeType::TopK => Op::TopK(top_k_config(node)),
+            NodeType::Trilu => Op::Trilu(trilu_config(node)),
+            NodeType::ReduceMax => Op::Reduce(ReduceOp::Max, reduce_max_config(node)),
+            NodeType::ReduceMin => Op::Reduce(ReduceOp::Min, reduce_min_config(node)),
+            NodeType::ReduceMean => Op::Reduce(ReduceOp::Mean, reduce_mean_config(node)),
+            NodeType::ReduceProd => Op::Reduce(ReduceOp::Prod, reduce_prod_config(node)),
+            NodeType::ReduceSum => Op::Reduce(ReduceOp::Sum, reduce_sum_config(node)),
+            NodeType::ArgMax => Op::ArgMax(argmax_config(node)),
+            NodeType::ArgMin => {
+                let (axis, keepdims) = argmin_config(node);
+                Op::ArgMin(axis, keepdims)
+            }
+            NodeType::If => Op::If {
+                then_branch: Box::new(Graph::from_subgraph(node, "then_branch", device)?),
+                else_branch: Box::new(Graph::from_subgraph(node, "else_branch", device)?),
+            },
+            NodeType::Loop => Op::Loop {
+                body: Box::new(Graph::from_subgraph(node, "body", device)?),
+            },
+            NodeType::Scan => Op::Scan {
+                num_scan_inputs: scan_config(node),
+                body: Box::new(Graph::from_subgraph(node, "body", device)?),
+            },
+            _ => {
+                return Err(Error::UnsupportedOperator {
+                    node: node.name.clone(),
+                    op: node.node_type.clone(),
+                })
+            }
+        };
+
+        Ok(op)
+    }
+
+    /// The number of leading inputs read when the node is executed, all of them when `None`.
+    ///
+    /// The other inputs are constants already consumed by the configuration of the op.
+    pub(crate) fn num_runtime_inputs(&self) -> Option<usize> {
+        match self {
+            Op::Layer(_)
+            | Op::Unary(_)
+            | Op::Cast(_)
+            | Op::Identity
+            | Op::Reshape(_)
+            | Op::Flatten(..)
+            | Op::Transpose(_)
+            | Op::Squeeze(_)
+            | Op::Unsqueeze(_)
+            | Op::Slice(_)
+            | Op::Expand(Some(_))
+            | Op::Tile(_)
+            | Op::Split(_)
+            | Op::Shape(..)
+            | Op::ConstantOfShape(_)
+            | Op::TopK(_)
+            | Op::Trilu(_)
+            | Op::Reduce(..)
+            | Op::ArgMax(_)
+            | Op::ArgMin(..) => Some(1),
+            Op::Constant(_) => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Executes the op, the inputs are `None` for the omitted optional inputs.
+    pub(crate) fn forward(
+        &self,
+        mut inputs: Vec<Option<Value<B>>>,
+        num_outputs: usize,
+        device: &B::Device,
+    ) -> Result<Vec<Value<B>>, Error> {
+        let mut input = |index: usize| -> Result<Value<B>, Error> {
+            inputs
+                .get_mut(index)
+                .and_then(Option::take)
+                .ok_or(Error::MissingInput(index))
+        };
+
+        let output = match self {
+            Op::Layer(layer) => layer.forward(input(0)?.into_float()?)?.into(),
+            Op::Unary(op) => elementwise::unary(*op, input(0)?)?,
+            Op::Binary(op) => elementwise::binary(*op, input(0)?, input(1)?)?,
+            Op::Cast(kind) => elementwise::cast(input(0)?, *kind)?,
+            Op::Where => elementwise::where_op(input(0)?, input(1)?, input(2)?)?,
+            Op::MatMul => matmul(input(0)?.into_float()?, input(1)?.into_float()?)?.into(),
+            Op::Sum | Op::Mean => {
+                let count = inputs.len();
+                let mut sum = None;
+                for (index, input) in inputs.into_iter().enumerate() {
+                    let input = input.ok_or(Error::MissingInput(index))?;
+                    sum = Some(match sum {
+                        Some(sum) => elementwise::binary(BinaryOp::Add, sum, input)?,
+                        None => input,
+                    });
+                }
+                let sum = sum.ok_or(Error::MissingInput(0))?;
+                match self {
+                    Op::Mean => elementwise::binary(BinaryOp::Div, sum, Value::from(count as i64))?,
+                    _ => sum,
+                }
+            }
+            Op::Identity => input(0)?,
+            Op::Constant(value) => value.clone(),
+            Op::Reshape(shape) => {
+                map_tensor!(NodeType::Reshape, input(0)?, tensor => layout::reshape(tensor, shape)?)
+            }
+            Op::Flatten(start, end) => {
+                map_tensor!(NodeType::Flatten, input(0)?, tensor => layout::flatten(tensor, *start, *end)?)
+            }
+            Op::Transpose(perm) => {
+                map_tensor!(NodeType::Transpose, input(0)?, tensor => layout::permute(tensor, perm)?)
+            }
+            Op::Squeeze(axes) => {
+                map_tensor!(NodeType::Squeeze, input(0)?, tensor => layout::squeeze(tensor, axes)?)
+            }
+            Op::Unsqueeze(axes) => match input(0)? {
+                // Same as the codegen, a scalar becomes a float tensor
+                Value::Scalar(scalar) => {
+                    let tensor = Tensor::<B, 1>::from_data([scalar.as_f64()], device);
+                    let axes = axes.get(1..).unwrap_or_default();
+                    layout::unsqueeze(DynTensor::new(tensor), axes)?.into()
+                }
+                value => {
+                    map_tensor!(NodeType::Unsqueeze, value, tensor => layout::unsqueeze(tensor, axes)?)
+                }
+            },
+            Op::Concat(dim) => {
+                let inputs = inputs
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, input)| input.ok_or(Error::MissingInput(index)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match inputs.first() {
+                    Some(Value::Float(_)) => layout::concat(
+                        inputs
+                            .into_iter()
+                            .map(Value::into_float)
+                            .collect::<Result<_, _>>()?,
+                        *dim,
+                    )?
+                    .into(),
+                    Some(Value::Int(_)) => layout::concat(
+                        inputs
+                            .into_iter()
+                            .map(Value::into_int)
+                            .collect::<Result<_, _>>()?,
+                        *dim,
+                    )?
+                    .into(),
+                    Some(Value::Bool(_)) => layout::concat(
+                        inputs
+                            .into_iter()
+                            .map(Value::into_bool)
+                            .collect::<Result<_, _>>()?,
+                        *dim,
+                    )?
+                    .into(),
+                    Some(value) => {
+                        return Err(Error::unsupported(NodeType::Concat, &[value.kind()]))
+                    }
+                    None => return Err(Error::MissingInput(0)),
+                }
+            }
+            Op::Gather(dim) => gather(input(0)?, *dim, input(1)?, device)?,
+            Op::GatherElements(dim) => {
+                let indices = input(1)?.into_int()?;
+                match input(0)? {
+                    Value::Float(tensor) => layout::gather_elements(tensor, *dim, indices)?.into(),
+                    Value::Int(tensor) => layout::gather_elements(tensor, *dim, indices)?.into(),
+                    value => {
+                        return Err(Error::unsupported(
+                            NodeType::GatherElements,
+                            &[value.kind()],
+                        ))
+                    }
+                }
+            }
+            Op::Slice(ranges) => {
+                map_tensor!(NodeType::Slice, input(0)?, tensor => layout::slice(tensor, ranges)?)
+            }
+            Op::Expand(shape) => {
+                let shape = match shape {
+                    Some(shape) => shape.clone(),
+                    None => int_values(input(1)?)?,
+                };
+                map_tensor!(NodeType::Expand, input(0)?, tensor => layout::expand(tensor, &shape)?)
+            }
+            Op::Tile(repeats) => {
+                map_tensor!(NodeType::Tile, input(0)?, tensor => layout::tile(tensor, repeats)?)
+            }
+            Op::Split(config) => {
+                let outputs = match input(0)? {
+                    Value::Float(tensor) => layout::split(tensor, config, num_outputs)?
+                        .into_iter()
+                        .map(Value::from)
+                        .collect(),
+                    Value::Int(tensor) => layout::split(tensor, config, num_outputs)?
+                        .into_iter()
+                        .map(Value::from)
+                        .collect(),
+                    Value::Bool(tensor) => layout::split(tensor, config, num_outputs)?
+                        .into_iter()
+                        .map(Value::from)
+                        .collect(),
+                    value => return Err(Error::unsupported(NodeType::Split, &[value.kind()])),
+                };
+                return Ok(outputs);
+            }
+            Op::Shape(start, end) => {
+                let dims = match input(0)? {
+                    Value::Float(tensor) => tensor.dims(),
+                    Value::Int(tensor) => tensor.dims(),
+                    Value::Bool(tensor) => tensor.dims(),
+                    value => return Err(Error::unsupported(NodeType::Shape, &[value.kind()])),
+                };
+                match dims.get(*start..*end) {
+                    Some(dims) => Value::Shape(dims.to_vec()),
+                    None => return Err(Error::invalid_shape(NodeType::Shape, dims)),
+                }
+            }
+            Op::ConstantOfShape(value) => {
+                let shape = int_values(input(0)?)?
+                    .into_iter()
+                    .map(|dim| usize::try_from(dim).unwrap_or_default())
+                    .collect::<Vec<_>>();
+                constant_of_shape(Shape::from(shape), *value, device)?
+            }
+            Op::Range => {
+                let start = input(0)?.into_scalar()?.as_i64();
+                let end = input(1)?.into_scalar()?.as_i64();
+                let step = input(2)?.into_scalar()?.as_i64();
+                // Burn only supports increasing ranges
+                let step = usize::try_from(step)
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| Error::unsupported(NodeType::Range, &["a non-positive step"]))?;
+                Tensor::<B, 1, Int>::arange_step(start..end, step, device).into()
+            }
+            Op::TopK(config) => return top_k(input(0)?.into_float()?, config),
+            Op::Trilu(config) => match input(0)? {
+                Value::Float(tensor) => trilu(tensor, config)?.into(),
+                Value::Int(tensor) => trilu(tensor, config)?.into(),
+                value => return Err(Error::unsupported(NodeType::Trilu, &[value.kind()])),
+            },
+            Op::Reduce(op, dim) => match input(0)? {
+                Value::Float(tensor) => reduce::reduce(*op, tensor, *dim)?.into(),
+                Value::Int(tensor) => reduce::reduce(*op, tensor, *dim)?.into(),
+                value => return Err(Error::unsupported(op, &[value.kind()])),
+            },
+            Op::ArgMax(dim) => match input(0)? {
+                Value::Float(tensor) => reduce::argmax(tensor, *dim)?.into(),
+                Value::Int(tensor) => reduce::argmax(tensor, *dim)?.into(),
+                value => return Err(Error::unsupported(NodeType::ArgMax, &[value.kind()])),
+            },
+            Op::ArgMin(dim, keepdims) => match input(0)? {
+                Value::Float(tensor) => reduce::argmin(tensor, *dim, *keepdims)?.into(),
+                Value::Int(tensor) => reduce::argmin(tensor, *dim, *keepdims)?.into(),
+                value => return Err(Error::unsupported(NodeType::ArgMin, &[value.kind()])),
+            },
+            Op::If {
+                then_branch,
+                else_branch,
+            } => {
+                let condition = inputs
+                    .first()
+                    .cloned()
+                    .flatten()
+                    .ok_or(Error::MissingInput(0))?;
+                let branch = match model::is_true(condition)? {
+                    true => then_branch,
+                    false => else_branch,
+                };
+                return branch.forward_captured(Vec::new(), inputs, device);
+            }
+            Op::Loop { body } => return model::run_loop(body, inputs, device),
+            Op::Scan {
+                body,
+                num_scan_inputs,
+            } => return model::run_scan(body, *num_scan_inputs, inputs, device),
+        };
+
+        Ok(vec![output])
+    }
+}
+
+/// Instantiates the value of a constant argument.
+pub(crate) fn constant_value<B: Backend>(
+    argument: &Argument,
+    device: &B::Device,
+) -> Result<Value<B>, Error> {
+    let invalid = |message: String| Error::InvalidNode {
+        node: argument.name.clone(),
+        message,
+    };
+    let data = argument
+        .value
+        .clone()
+        .ok_or_else(|| invalid("The constant has no value".to_string()))?;
+
+    let value = match &argument.ty {
+        ArgType::Scalar(_) => Value::Scalar(scalar(data.into_scalar()).map_err(invalid)?),
+        ArgType::Tensor(tensor) if tensor.dim == 0 => {
+            Value::Scalar(scalar(data.into_scalar()).map_err(invalid)?)
+        }
+        ArgType::Tensor(tensor) => {
+            let shape = tensor
+                .shape
+                .clone()
+                .unwrap_or_else(|| vec![data_len(&data)]);
+            match data {
+                Data::Float16s(values) => float_tensor(TensorData::new(values, shape), device),
+                Data::Float32s(values) => float_tensor(TensorData::new(values, shape), device),
+                Data::Float64s(values) => float_tensor(TensorData::new(values, shape), device),
+                Data::Int32s(values) => int_tensor(TensorData::new(values, shape), device),
+                Data::Int64s(values) => int_tensor(TensorData::new(values, shape), device),
+                Data::Bools(values) => {
+                    DynTensor::<B, Bool>::from_data(TensorData::new(values, shape), device).into()
+                }
+                data => return Err(invalid(format!("Unsupported constant data {data:?}"))),
+            }
+        }
+        ArgType::Shape(_) => Value::Shape(
+            data.into_i64s()
+                .into_iter()
+                .map(|dim| dim as usize)
+                .collect(),
+        ),
+    };
+
+    Ok(value)
+}
+
+fn float_tensor<B: Backend>(data: TensorData, device: &B::Device) -> Value<B> {
+    DynTensor::<B, Float>::from_data(data.convert::<B::FloatElem>(), device).into()
+}
+
+fn int_tensor<B: Backend>(data: TensorData, device: &B::Device) -> Value<B> {
+    DynTensor::<B, Int>::from_data(data.convert::<B::IntElem>(), device).into()
+}
+
+fn data_len(data: &Data) -> usize {
+    match data {
+        Data::Bools(values) => values.len(),
+        Data::Float16s(values) => values.len(),
+        Data::Float32s(values) => values.len(),
+        Data::Float64s(values) => values.len(),
+        Data::Int32s(values) => values.len(),
+        Data::Int64s(values) => values.len(),
+        Data::Strings(values) => values.len(),
+        _ => 1,
+    }
+}
+
+fn scalar(data: Data) -> Result<Scalar, String> {
+    let scalar = match data {
+        Data::Bool(value) => Scalar::Bool(value),
+        Data::Float16(value) => Scalar::Float(f32::from(value) as f64),
+        Data::Float32(value) => Scalar::Float(value as f64),
+        Data::Float64(value) => Scalar::Float(value),
+        Data::Int32(value) => Scalar::Int(value as i64),
+        Data::Int64(value) => Scalar::Int(value),
+        data => return Err(format!("Unsupported scalar data {data:?}")),
+    };
+
+    Ok(scalar)
+}
+
+fn cast_kind(node: &Node) -> Result<CastKind, Error> {
+    let elem_type = match &node.outputs[0].ty {
+        ArgType::Scalar(elem_type) => elem_type,
+        ArgType::Tensor(tensor) => &tensor.elem_type,
+        ArgType::Shape(_) => return Ok(CastKind::Int),
+    };
+
+    match elem_type {
+        ElementType::Float16 | ElementType::Float32 | ElementType::Float64 => Ok(CastKind::Float),
+        ElementType::Int32 | ElementType::Int64 => Ok(CastKind::Int),
+        ElementType::Bool => Ok(CastKind::Bool),
+        elem_type => Err(Error::InvalidNode {
+            node: node.name.clone(),
+            message: format!("Cast to {elem_type:?} is not supported"),
+        }),
+    }
+}
+
+/// The fill value of a `ConstantOfShape` node, a float zero by default.
+fn constant_of_shape_value(node: &Node) -> Result<Scalar, Error> {
+    match node
+        .attrs
+        .get("value")
+        .and_then(|value| value.clone().into_tensor().data)
+    {
+        Some(data) => scalar(data.into_scalar()).map_err(|message| Error::InvalidNode {
+            node: node.name.clone(),
+            message,
+        }),
+        None => Ok(Scalar::Float(0.0)),
+    }
+}
+
+fn constant_of_shape<B: Backend>(
+    shape: Shape,
+    value: Scalar,
+    device: &B::Device,
+) -> Result<Value<B>, Error> {
+    Ok(with_rank!(shape.num_dims(), D => match value {
+        Scalar::Float(value) => Tensor::<B, D>::full(shape, value, device).into(),
+        Scalar::Int(value) => Tensor::<B, D, Int>::full(shape, value, device).into(),
+        // There is no full bool tensor, the same workaround as the codegen is used
+        Scalar::Bool(true) => Tensor::<B, D, Int>::ones(shape, device).bool().into(),
+        Scalar::Bool(false) => Tensor::<B, D, Int>::zeros(shape, device).bool().into(),
+    }))
+}
+
+/// The values of a runtime shape or int tensor.
+fn int_values<B: Backend>(value: Value<B>) -> Result<Vec<i64>, Error> {
+    match value {
+        Value::Shape(shape) => Ok(shape.into_iter().map(|dim| dim as i64).collect()),
+        Value::Int(tensor) => Ok(tensor.into_data().iter::<i64>().collect()),
+        value => Err(Error::InvalidKind {
+            expected: "shape or int tensor",
+            actual: value.kind(),
+        }),
+    }
+}
+
+fn gather<B: Backend>(
+    input: Value<B>,
+    dim: usize,
+    index: Value<B>,
+    device: &B::Device,
+) -> Result<Value<B>, Error> {
+    if let (Value::Shape(shape), Value::Scalar(index)) = (&input, &index) {
+        if shape.is_empty() {
+            return Err(Error::invalid_shape(NodeType::Gather, vec![0]));
+        }
+        let index = index.as_i64().rem_euclid(shape.len() as i64) as usize;
+        return Ok(Value::Scalar(Scalar::Int(shape[index] as i64)));
+    }
+
+    let input = match input {
+        Value::Shape(shape) => {
+            let dims = shape.iter().map(|dim| *dim as i64).collect::<Vec<_>>();
+            let data = TensorData::new(dims, [shape.len()]).convert::<B::IntElem>();
+            Value::Int(DynTensor::from_data(data, device))
+        }
+        input => input,
+    };
+
+    let output = match (input, index) {
+        (Value::Float(tensor), Value::Scalar(index)) => {
+            layout::gather_scalar(tensor, dim, index.as_i64())?.into()
+        }
+        (Value::Int(tensor), Value::Scalar(index)) => {
+            layout::gather_scalar(tensor, dim, index.as_i64())?.into()
+        }
+        (Value::Float(tensor), Value::Int(indices)) => layout::gather(tensor, dim, indices)?.into(),
+        (Value::Int(tensor), Value::Int(indices)) => layout::gather(tensor, dim, indices)?.into(),
+        (input, index) => {
+            return Err(Error::unsupported(
+                NodeType::Gather,
+                &[input.kind(), index.kind()],
+            ))
+        }
+    };
+
+    Ok(output)
+}
+
+/// Matrix product with the numpy semantics, see the codegen [MatmulNode](crate::burn::node::matmul::MatmulNode).
+fn matmul<B: Backend>(lhs: DynTensor<B>, rhs: DynTensor<B>) -> Result<DynTensor<B>, Error> {
+    let lhs_vector = lhs.rank() == 1;
+    let rhs_vector = rhs.rank() == 1;
+    let lhs = match lhs_vector {
+        true => layout::unsqueeze(lhs, &[0])?,
+        false => lhs,
+    };
+    let rhs = match rhs_vector {
+        true => layout::unsqueeze(rhs, &[1])?,
+        false => rhs,
+    };
+
+    let (lhs, rhs) = elementwise::broadcast_ranks(lhs, rhs);
+    let rank = lhs.rank();
+    let (lhs_dims, rhs_dims) = (lhs.dims(), rhs.dims());
+    let batch_compatible = lhs_dims[..rank - 2]
+        .iter()
+        .zip(&rhs_dims[..rank - 2])
+        .all(|(lhs, rhs)| lhs == rhs || *lhs == 1 || *rhs == 1);
+    if lhs_dims[rank - 1] != rhs_dims[rank - 2] || !batch_compatible {
+        return Err(Error::invalid_shape(NodeType::MatMul, rhs_dims));
+    }
+
+    let output = with_rank!(rank, D => DynTensor::new(
+        lhs.into_tensor::<D>().matmul(rhs.into_tensor::<D>())
+    ));
+
+    // The dimensions added to the vectors are removed from the product
+    let mut squeezed = Vec::new();
+    if lhs_vector {
+        squeezed.push(rank as i64 - 2);
+    }
+    if rhs_vector {
+        squeezed.push(rank as i64 - 1);
+    }
+    match squeezed.is_empty() {
+        true => Ok(output),
+        false => layout::squeeze(output, &squeezed),
+    }
+}
+
+fn top_k<B: Backend>(tensor: DynTensor<B>, config: &TopKConfig) -> Result<Vec<Value<B>>, Error> {
+    let dims = tensor.dims();
+    if dims
+        .get(config.axis)
+        .map(|dim| config.k > *dim)
+        .unwrap_or(true)
+    {
+        return Err(Error::invalid_shape(NodeType::TopK, dims));
+    }
+
+    Ok(with_rank!(tensor.rank(), D => {
+        let tensor = tensor.into_tensor::<D>();
+        // The smallest elements are the largest elements of the negated input
+        let (values, indices) = match config.largest {
+            true => tensor.topk_with_indices(config.k, config.axis),
+            false => {
+                let (values, indices) = tensor.neg().topk_with_indices(config.k, config.axis);
+                (values.neg(), indices)
+            }
+        };
+        vec![values.into(), indices.into()]
+    }))
+}
+
+fn trilu<B, K>(tensor: DynTensor<B, K>, config: &TriluConfig) -> Result<DynTensor<B, K>, Error>
+where
+    B: Backend,
+    K: burn::tensor::Numeric<B>,
+    K::Elem: burn::tensor::Element,
+{
+    Ok(with_rank!(tensor.rank(), D => {
+        let tensor = tensor.into_tensor::<D>();
+        DynTensor::new(match config.upper {
+            true => tensor.triu(config.diagonal),
+            false => tensor.tril(config.diagonal),
+        })
+    }))
+}
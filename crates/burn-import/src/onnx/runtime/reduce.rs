@@ -0,0 +1,79 @@
+use burn::tensor::{backend::Backend, Int, Numeric};
+use onnx_ir::ir::NodeType;
+
+use super::{value::DynTensor, Error};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ReduceOp {
+    Max,
+    Min,
+    Mean,
+    Prod,
+    Sum,
+}
+
+/// Reduces along `dim` keeping the dimension, or over all the elements into a rank 1 tensor.
+pub(crate) fn reduce<B: Backend, K: Numeric<B>>(
+    op: ReduceOp,
+    tensor: DynTensor<B, K>,
+    dim: Option<usize>,
+) -> Result<DynTensor<B, K>, Error> {
+    if let Some(dim) = dim {
+        check_dim(op, &tensor, dim)?;
+    }
+
+    let primitive = tensor.into_primitive();
+    let primitive = match (op, dim) {
+        (ReduceOp::Max, Some(dim)) => K::max_dim(primitive, dim),
+        (ReduceOp::Min, Some(dim)) => K::min_dim(primitive, dim),
+        (ReduceOp::Mean, Some(dim)) => K::mean_dim(primitive, dim),
+        (ReduceOp::Prod, Some(dim)) => K::prod_dim(primitive, dim),
+        (ReduceOp::Sum, Some(dim)) => K::sum_dim(primitive, dim),
+        (ReduceOp::Max, None) => K::max(primitive),
+        (ReduceOp::Min, None) => K::min(primitive),
+        (ReduceOp::Mean, None) => K::mean(primitive),
+        (ReduceOp::Prod, None) => K::prod(primitive),
+        (ReduceOp::Sum, None) => K::sum(primitive),
+    };
+
+    Ok(DynTensor::from_primitive(primitive))
+}
+
+/// Index of the maximum value along `dim`, the dimension is kept.
+pub(crate) fn argmax<B: Backend, K: Numeric<B>>(
+    tensor: DynTensor<B, K>,
+    dim: usize,
+) -> Result<DynTensor<B, Int>, Error> {
+    check_dim(NodeType::ArgMax, &tensor, dim)?;
+
+    Ok(DynTensor::from_primitive(K::argmax(
+        tensor.into_primitive(),
+        dim,
+    )))
+}
+
+/// Index of the minimum value along `dim`, the dimension is removed unless `keepdims` is set.
+pub(crate) fn argmin<B: Backend, K: Numeric<B>>(
+    tensor: DynTensor<B, K>,
+    dim: usize,
+    keepdims: bool,
+) -> Result<DynTensor<B, Int>, Error> {
+    check_dim(NodeType::ArgMin, &tensor, dim)?;
+
+    let indices = DynTensor::<B, Int>::from_primitive(K::argmin(tensor.into_primitive(), dim));
+    match keepdims {
+        true => Ok(indices),
+        false => super::layout::squeeze(indices, &[dim as i64]),
+    }
+}
+
+fn check_dim<B: Backend, K: Numeric<B>>(
+    op: impl core::fmt::Debug,
+    tensor: &DynTensor<B, K>,
+    dim: usize,
+) -> Result<(), Error> {
+    match dim < tensor.rank() {
+        true => Ok(()),
+        false => Err(Error::invalid_shape(op, tensor.dims())),
+    }
+}
@@ -0,0 +1,426 @@
+use burn::tensor::{
+    backend::Backend, try_read_sync, BasicOps, Bool, Float, Int, Shape, Tensor, TensorData,
+    TensorKind,
+};
+
+use super::Error;
+
+/// Binds `$D` to the given rank as a constant so the body can use the typed tensor API.
+///
+/// The enclosing function returns an [Error](super::Error) for the unsupported ranks.
+macro_rules! with_rank {
+    ($rank:expr, $D:ident => $body:expr) => {
+        match $rank {
+            1 => {
+                const $D: usize = 1;
+                $body
+            }
+            2 => {
+                const $D: usize = 2;
+                $body
+            }
+            3 => {
+                const $D: usize = 3;
+                $body
+            }
+            4 => {
+                const $D: usize = 4;
+                $body
+            }
+            5 => {
+                const $D: usize = 5;
+                $body
+            }
+            6 => {
+                const $D: usize = 6;
+                $body
+            }
+            rank => return Err($crate::onnx::runtime::Error::UnsupportedRank(rank)),
+        }
+    };
+}
+
+/// Applies a function generic over the tensor kind to a tensor value.
+///
+/// The enclosing function returns an [Error](super::Error) when the value isn't a tensor.
+macro_rules! map_tensor {
+    ($op:expr, $value:expr, $tensor:ident => $body:expr) => {
+        match $value {
+            Value::Float($tensor) => Value::from($body),
+            Value::Int($tensor) => Value::from($body),
+            Value::Bool($tensor) => Value::from($body),
+            value => {
+                return Err($crate::onnx::runtime::Error::unsupported(
+                    $op,
+                    &[value.kind()],
+                ))
+            }
+        }
+    };
+}
+
+pub(crate) use map_tensor;
+pub(crate) use with_rank;
+
+/// A tensor whose rank is only known at runtime.
+#[derive(Debug, Clone)]
+pub struct DynTensor<B: Backend, K: TensorKind<B> = Float> {
+    primitive: K::Primitive,
+}
+
+impl<B: Backend, K: BasicOps<B>> DynTensor<B, K> {
+    /// Erases the rank of the given tensor.
+    pub fn new<const D: usize>(tensor: Tensor<B, D, K>) -> Self {
+        Self::from_primitive(tensor.into_primitive())
+    }
+
+    /// Wraps a tensor primitive.
+    pub fn from_primitive(primitive: K::Primitive) -> Self {
+        Self { primitive }
+    }
+
+    /// Creates a tensor from the given data.
+    pub fn from_data(data: TensorData, device: &B::Device) -> Self {
+        Self::from_primitive(K::from_data(data, device))
+    }
+
+    /// Returns the tensor primitive.
+    pub fn into_primitive(self) -> K::Primitive {
+        self.primitive
+    }
+
+    /// Converts back to a tensor of rank `D`.
+    ///
+    /// # Panics
+    ///
+    /// If the tensor doesn't have rank `D`, see [try_into_tensor](Self::try_into_tensor).
+    pub fn into_tensor<const D: usize>(self) -> Tensor<B, D, K> {
+        self.try_into_tensor()
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Converts back to a tensor of rank `D`, or returns an error if the tensor doesn't have
+    /// rank `D`.
+    pub fn try_into_tensor<const D: usize>(self) -> Result<Tensor<B, D, K>, Error> {
+        match self.rank() {
+            rank if rank == D => Ok(Tensor::from_primitive(self.primitive)),
+            rank => Err(Error::InvalidRank {
+                expected: D,
+                actual: rank,
+            }),
+        }
+    }
+
+    /// Returns the shape of the tensor.
+    pub fn shape(&self) -> Shape {
+        K::shape(&self.primitive)
+    }
+
+    /// Returns the dimensions of the tensor.
+    pub fn dims(&self) -> Vec<usize> {
+        self.shape().dims
+    }
+
+    /// Returns the rank of the tensor.
+    pub fn rank(&self) -> usize {
+        self.shape().num_dims()
+    }
+
+    /// Returns the device of the tensor.
+    pub fn device(&self) -> B::Device {
+        K::device(&self.primitive)
+    }
+
+    /// Reshapes the tensor, the rank may change.
+    pub fn reshape(self, dims: Vec<usize>) -> Self {
+        Self::from_primitive(K::reshape(self.primitive, Shape::from(dims)))
+    }
+
+    /// Returns the data of the tensor.
+    pub fn into_data(self) -> TensorData {
+        try_read_sync(K::into_data_async(self.primitive))
+            .expect("Failed to read tensor data synchronously.")
+    }
+}
+
+/// A scalar value, produced by nodes with a rank 0 output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    /// Floating point scalar.
+    Float(f64),
+    /// Integer scalar.
+    Int(i64),
+    /// Boolean scalar.
+    Bool(bool),
+}
+
+impl Scalar {
+    /// Returns the scalar as a float.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Scalar::Float(value) => *value,
+            Scalar::Int(value) => *value as f64,
+            Scalar::Bool(value) => *value as i64 as f64,
+        }
+    }
+
+    /// Returns the scalar as an integer.
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            Scalar::Float(value) => *value as i64,
+            Scalar::Int(value) => *value,
+            Scalar::Bool(value) => *value as i64,
+        }
+    }
+
+    /// Returns the scalar as a boolean.
+    pub fn as_bool(&self) -> bool {
+        match self {
+            Scalar::Float(value) => *value != 0.0,
+            Scalar::Int(value) => *value != 0,
+            Scalar::Bool(value) => *value,
+        }
+    }
+}
+
+/// A value flowing between the nodes of the graph.
+#[derive(Debug, Clone)]
+pub enum Value<B: Backend> {
+    /// Float tensor.
+    Float(DynTensor<B, Float>),
+    /// Int tensor.
+    Int(DynTensor<B, Int>),
+    /// Bool tensor.
+    Bool(DynTensor<B, Bool>),
+    /// Scalar.
+    Scalar(Scalar),
+    /// Static shape, as produced by the `Shape` node.
+    Shape(Vec<usize>),
+}
+
+impl<B: Backend> Value<B> {
+    /// Returns the float tensor, or an error if the value isn't a float tensor.
+    pub fn into_float(self) -> Result<DynTensor<B, Float>, Error> {
+        match self {
+            Value::Float(tensor) => Ok(tensor),
+            value => Err(value.invalid_kind("float tensor")),
+        }
+    }
+
+    /// Returns the int tensor, or an error if the value isn't an int tensor.
+    pub fn into_int(self) -> Result<DynTensor<B, Int>, Error> {
+        match self {
+            Value::Int(tensor) => Ok(tensor),
+            value => Err(value.invalid_kind("int tensor")),
+        }
+    }
+
+    /// Returns the bool tensor, or an error if the value isn't a bool tensor.
+    pub fn into_bool(self) -> Result<DynTensor<B, Bool>, Error> {
+        match self {
+            Value::Bool(tensor) => Ok(tensor),
+            value => Err(value.invalid_kind("bool tensor")),
+        }
+    }
+
+    /// Returns the scalar, or an error if the value isn't a scalar.
+    pub fn into_scalar(self) -> Result<Scalar, Error> {
+        match self {
+            Value::Scalar(scalar) => Ok(scalar),
+            value => Err(value.invalid_kind("scalar")),
+        }
+    }
+
+    /// Returns the shape, or an error if the value isn't a shape.
+    pub fn into_shape(self) -> Result<Vec<usize>, Error> {
+        match self {
+            Value::Shape(shape) => Ok(shape),
+            value => Err(value.invalid_kind("shape")),
+        }
+    }
+
+    /// Returns the data of a tensor value, scalars and shapes are returned as rank 1 data.
+    pub fn into_data(self) -> TensorData {
+        match self {
+            Value::Float(tensor) => tensor.into_data(),
+            Value::Int(tensor) => tensor.into_data(),
+            Value::Bool(tensor) => tensor.into_data(),
+            Value::Scalar(Scalar::Float(value)) => TensorData::new(vec![value], [1]),
+            Value::Scalar(Scalar::Int(value)) => TensorData::new(vec![value], [1]),
+            Value::Scalar(Scalar::Bool(value)) => TensorData::new(vec![value], [1]),
+            Value::Shape(shape) => {
+                let dims = shape.iter().map(|dim| *dim as i64).collect::<Vec<_>>();
+                TensorData::new(dims, [shape.len()])
+            }
+        }
+    }
+
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Value::Float(_) => "float tensor",
+            Value::Int(_) => "int tensor",
+            Value::Bool(_) => "bool tensor",
+            Value::Scalar(_) => "scalar",
+            Value::Shape(_) => "shape",
+        }
+    }
+
+    /// The kind of the value, with the rank of tensors.
+    pub(crate) fn description(&self) -> String {
+        match self {
+            Value::Float(tensor) => format!("float tensor of rank {}", tensor.rank()),
+            Value::Int(tensor) => format!("int tensor of rank {}", tensor.rank()),
+            Value::Bool(tensor) => format!("bool tensor of rank {}", tensor.rank()),
+            value => value.kind().to_string(),
+        }
+    }
+
+    fn invalid_kind(&self, expected: &'static str) -> Error {
+        Error::InvalidKind {
+            expected,
+            actual: self.kind(),
+        }
+    }
+}
+
+/// Tensor kinds that can be wrapped into a [`Value`].
+pub trait ValueKind<B: Backend>: BasicOps<B> {
+    /// Wraps the tensor into a value.
+    fn into_value(tensor: DynTensor<B, Self>) -> Value<B>;
+}
+
+impl<B: Backend> ValueKind<B> for Float {
+    fn into_value(tensor: DynTensor<B, Self>) -> Value<B> {
+        Value::Float(tensor)
+    }
+}
+
+impl<B: Backend> ValueKind<B> for Int {
+    fn into_value(tensor: DynTensor<B, Self>) -> Value<B> {
+        Value::Int(tensor)
+    }
+}
+
+impl<B: Backend> ValueKind<B> for Bool {
+    fn into_value(tensor: DynTensor<B, Self>) -> Value<B> {
+        Value::Bool(tensor)
+    }
+}
+
+impl<B: Backend, K: ValueKind<B>> From<DynTensor<B, K>> for Value<B> {
+    fn from(tensor: DynTensor<B, K>) -> Self {
+        K::into_value(tensor)
+    }
+}
+
+impl<B: Backend, const D: usize, K: ValueKind<B>> From<Tensor<B, D, K>> for Value<B> {
+    fn from(tensor: Tensor<B, D, K>) -> Self {
+        K::into_value(DynTensor::new(tensor))
+    }
+}
+
+impl<B: Backend> From<Scalar> for Value<B> {
+    fn from(scalar: Scalar) -> Self {
+        Value::Scalar(scalar)
+    }
+}
+
+impl<B: Backend> From<f32> for Value<B> {
+    fn from(value: f32) -> Self {
+        Value::Scalar(Scalar::Float(value as f64))
+    }
+}
+
+impl<B: Backend> From<f64> for Value<B> {
+    fn from(value: f64) -> Self {
+        Value::Scalar(Scalar::Float(value))
+    }
+}
+
+impl<B: Backend> From<i32> for Value<B> {
+    fn from(value: i32) -> Self {
+        Value::Scalar(Scalar::Int(value as i64))
+    }
+}
+
+impl<B: Backend> From<i64> for Value<B> {
+    fn from(value: i64) -> Self {
+        Value::Scalar(Scalar::Int(value))
+    }
+}
+
+impl<B: Backend> From<bool> for Value<B> {
+    fn from(value: bool) -> Self {
+        Value::Scalar(Scalar::Bool(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestBackend = burn::backend::NdArray<f32>;
+
+    fn float_value(dims: [usize; 2]) -> Value<TestBackend> {
+        Tensor::<TestBackend, 2>::zeros(dims, &Default::default()).into()
+    }
+
+    #[test]
+    fn into_tensor_should_check_the_rank() {
+        let tensor = float_value([2, 3]).into_float().unwrap();
+
+        assert_eq!(tensor.dims(), vec![2, 3]);
+        assert!(tensor.clone().try_into_tensor::<2>().is_ok());
+        assert!(matches!(
+            tensor.try_into_tensor::<3>(),
+            Err(Error::InvalidRank {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn into_kind_should_fail_for_other_kinds() {
+        assert!(matches!(
+            float_value([2, 3]).into_int(),
+            Err(Error::InvalidKind {
+                expected: "int tensor",
+                actual: "float tensor"
+            })
+        ));
+        assert!(Value::<TestBackend>::from(1.0).into_bool().is_err());
+        assert!(Value::<TestBackend>::Shape(vec![2]).into_scalar().is_err());
+        assert!(Value::<TestBackend>::from(true).into_shape().is_err());
+        assert_eq!(
+            Value::<TestBackend>::Shape(vec![2, 3])
+                .into_shape()
+                .unwrap(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn scalar_should_convert_between_types() {
+        assert_eq!(Scalar::Float(2.5).as_i64(), 2);
+        assert_eq!(Scalar::Int(3).as_f64(), 3.0);
+        assert!(Scalar::Float(0.5).as_bool());
+        assert!(!Scalar::Int(0).as_bool());
+        assert_eq!(Scalar::Bool(true).as_f64(), 1.0);
+    }
+
+    #[test]
+    fn into_data_should_return_scalars_and_shapes_as_rank_1() {
+        let scalar = Value::<TestBackend>::from(4i64).into_data();
+        let shape = Value::<TestBackend>::Shape(vec![2, 3]).into_data();
+
+        scalar.assert_eq(&TensorData::from([4i64]), true);
+        shape.assert_eq(&TensorData::from([2i64, 3]), true);
+    }
+
+    #[test]
+    fn description_should_include_the_rank_of_tensors() {
+        assert_eq!(float_value([2, 3]).description(), "float tensor of rank 2");
+        assert_eq!(Value::<TestBackend>::from(1).description(), "scalar");
+    }
+}
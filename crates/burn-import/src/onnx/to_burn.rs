@@ -7,7 +7,10 @@ use std::{
 use burn::{
     nn::PReluConfig,
     record::{FullPrecisionSettings, HalfPrecisionSettings, PrecisionSettings},
-    tensor::{Element, TensorData},
+    tensor::{
+        quantization::{AffineQuantization, QuantizationStrategy},
+        Element, TensorData,
+    },
 };
 use log::warn;
 
@@ -16,6 +19,7 @@ use crate::{
         graph::BurnGraph,
         node::{
             argmax::ArgMaxNode,
+            argmin::ArgMinNode,
             avg_pool1d::AvgPool1dNode,
             avg_pool2d::AvgPool2dNode,
             batch_norm::BatchNormNode,
@@ -30,31 +34,54 @@ use crate::{
             conv_transpose_1d::ConvTranspose1dNode,
             conv_transpose_2d::ConvTranspose2dNode,
             conv_transpose_3d::ConvTranspose3dNode,
+            dequantize_linear::DequantizeLinearNode,
             dropout::DropoutNode,
+            einsum::EinsumNode,
             expand::{ExpandNode, ExpandShape},
             gather::GatherNode,
             gather_elements::GatherElementsNode,
+            gather_nd::GatherNdNode,
             global_avg_pool::GlobalAvgPoolNode,
+            group_norm::GroupNormNode,
+            gru::GruNode,
+            if_node::IfNode,
+            instance_norm::InstanceNormNode,
             layer_norm::LayerNormNode,
             linear::LinearNode,
+            loop_node::LoopNode,
+            lstm::LstmNode,
             mask_where::WhereNode,
             matmul::MatmulNode,
             max_pool1d::MaxPool1dNode,
             max_pool2d::MaxPool2dNode,
+            non_max_suppression::NonMaxSuppressionNode,
+            non_zero::NonZeroNode,
+            one_hot::OneHotNode,
             pad::PadNode,
             prelu::PReluNode,
+            quantize_linear::QuantizeLinearNode,
             random_normal::RandomNormalNode,
             random_uniform::RandomUniformNode,
             range::RangeNode,
+            recurrent::GateWeights,
             reshape::ReshapeNode,
             resize::ResizeNode,
+            rnn::RnnNode,
+            roi_align::RoiAlignNode,
+            scan::ScanNode,
+            scatter_elements::ScatterElementsNode,
+            scatter_nd::ScatterNdNode,
             slice::SliceNode,
+            split::SplitNode,
             squeeze::SqueezeNode,
+            subgraph::SubGraph,
             sum::SumNode,
             tile::TileNode,
+            top_k::TopKNode,
             trilu::TriluNode,
             unary::UnaryNode,
             unsqueeze::UnsqueezeNode,
+            Node as BurnNode, NodeCodegen,
         },
         ScalarKind, ScalarType, ShapeType, TensorKind, TensorType, Type,
     },
@@ -63,14 +90,18 @@ use crate::{
 };
 
 use super::op_configuration::{
-    argmax_config, avg_pool1d_config, avg_pool2d_config, batch_norm_config, clip_config,
-    concat_config, conv1d_config, conv2d_config, conv3d_config, conv_transpose1d_config,
-    conv_transpose2d_config, conv_transpose3d_config, dropout_config, expand_config,
-    flatten_config, gather_config, hard_sigmoid_config, layer_norm_config, leaky_relu_config,
-    linear_config, log_softmax_config, max_pool1d_config, max_pool2d_config, pad_config,
+    argmax_config, argmin_config, avg_pool1d_config, avg_pool2d_config, batch_norm_config,
+    clip_config, concat_config, conv1d_config, conv2d_config, conv3d_config,
+    conv_transpose1d_config, conv_transpose2d_config, conv_transpose3d_config, dropout_config,
+    einsum_config, expand_config, flatten_config, gather_config, gather_nd_config,
+    group_norm_config, gru_config, hard_sigmoid_config, instance_norm_config, layer_norm_config,
+    leaky_relu_config, linear_config, log_softmax_config, lstm_config, max_pool1d_config,
+    max_pool2d_config, non_max_suppression_config, one_hot_config, pad_config, quantization_config,
     reduce_max_config, reduce_mean_config, reduce_min_config, reduce_prod_config,
-    reduce_sum_config, reshape_config, resize_config, shape_config, slice_config, softmax_config,
-    squeeze_config, tile_config, transpose_config, trilu_config, unsqueeze_config,
+    reduce_sum_config, reshape_config, resize_config, rnn_config, roi_align_config, scan_config,
+    scatter_elements_config, scatter_nd_config, shape_config, slice_config, softmax_config,
+    split_config, squeeze_config, tile_config, top_k_config, transpose_config, trilu_config,
+    unsqueeze_config,
 };
 use onnx_ir::{
     convert_constant_value,
@@ -83,6 +114,7 @@ use onnx_ir::{
 
 pub use crate::burn::graph::RecordType;
 use crate::burn::node::mean::MeanNode;
+pub use onnx_ir::GraphPasses;
 
 /// Generate code and states from `.onnx` files and save them to the `out_dir`.
 #[derive(Debug, Default)]
@@ -94,6 +126,7 @@ pub struct ModelGen {
     half_precision: bool,
     record_type: RecordType,
     embed_states: bool,
+    passes: GraphPasses,
 }
 
 impl ModelGen {
@@ -169,6 +202,40 @@ impl ModelGen {
         self
     }
 
+    /// Specify the optimization passes applied to the graph before generating the code.
+    ///
+    /// # Arguments
+    ///
+    /// * `passes` - The enabled passes, all of them are enabled by default.
+    pub fn passes(&mut self, passes: GraphPasses) -> &mut Self {
+        self.passes = passes;
+        self
+    }
+
+    /// Specify whether to fold the values computed from constants and static shapes.
+    pub fn constant_folding(&mut self, enabled: bool) -> &mut Self {
+        self.passes.constant_folding = enabled;
+        self
+    }
+
+    /// Specify whether to fold the batch normalizations into the preceding convolutions.
+    pub fn fuse_batch_norm(&mut self, enabled: bool) -> &mut Self {
+        self.passes.fuse_batch_norm = enabled;
+        self
+    }
+
+    /// Specify whether to remove the `Identity` and `Dropout` nodes.
+    pub fn remove_identity(&mut self, enabled: bool) -> &mut Self {
+        self.passes.remove_identity = enabled;
+        self
+    }
+
+    /// Specify whether to remove the nodes whose outputs aren't used.
+    pub fn dead_code_elimination(&mut self, enabled: bool) -> &mut Self {
+        self.passes.dead_code_elimination = enabled;
+        self
+    }
+
     /// Run code generation.
     fn run(&self, is_build_script: bool) {
         log::info!("Starting to convert ONNX to Burn");
@@ -209,7 +276,10 @@ impl ModelGen {
         log::debug!("Development mode: {:?}", self.development);
         log::debug!("Output file: {:?}", out_file);
 
-        let graph = parse_onnx(input.as_ref());
+        log::debug!("Graph passes: {:?}", self.passes);
+
+        let mut graph = parse_onnx(input.as_ref());
+        self.passes.run(&mut graph);
         let graph = ParsedOnnxGraph(graph);
 
         if self.development {
@@ -251,13 +321,40 @@ impl ParsedOnnxGraph {
     /// Converts ONNX graph to Burn graph.
     pub fn into_burn<PS: PrecisionSettings + 'static>(self) -> BurnGraph<PS> {
         let mut graph = BurnGraph::<PS>::default();
+        Self::register_nodes(&mut graph, self.0.nodes);
 
+        // Get input and output names
+        let input_names = self
+            .0
+            .inputs
+            .iter()
+            .map(|input| input.name.clone())
+            .collect::<Vec<_>>();
+        let output_names = self
+            .0
+            .outputs
+            .iter()
+            .map(|output| output.name.clone())
+            .collect::<Vec<_>>();
+
+        // Register inputs and outputs with the graph
+        graph.register_input_output(input_names, output_names);
+
+        graph
+    }
+
+    /// Converts the ONNX nodes and registers them into the Burn graph.
+    fn register_nodes<PS: PrecisionSettings + 'static>(
+        graph: &mut BurnGraph<PS>,
+        nodes: Vec<Node>,
+    ) {
         let mut unsupported_ops = vec![];
 
-        for node in self.0.nodes {
+        for node in nodes {
             match node.node_type {
                 NodeType::Add => graph.register(Self::add_conversion(node)),
                 NodeType::ArgMax => graph.register(Self::argmax_conversion(node)),
+                NodeType::ArgMin => graph.register(Self::argmin_conversion(node)),
                 NodeType::Sub => graph.register(Self::sub_conversion(node)),
                 NodeType::Mul => graph.register(Self::mul_conversion(node)),
                 NodeType::Div => graph.register(Self::div_conversion(node)),
@@ -288,14 +385,31 @@ impl ParsedOnnxGraph {
                     graph.register(Self::layer_norm_conversion::<PS>(node))
                 }
                 NodeType::Linear => graph.register(Self::linear_conversion::<PS>(node)),
+                NodeType::LSTM => graph.register(Self::lstm_conversion(node)),
+                NodeType::GRU => graph.register(Self::gru_conversion(node)),
+                NodeType::RNN => graph.register(Self::rnn_conversion(node)),
                 NodeType::BatchNormalization => {
                     graph.register(Self::batch_norm_conversion::<PS>(node))
                 }
+                NodeType::InstanceNormalization => {
+                    graph.register(Self::instance_norm_conversion::<PS>(node))
+                }
+                NodeType::GroupNormalization => {
+                    graph.register(Self::group_norm_conversion::<PS>(node))
+                }
                 NodeType::Relu => graph.register(Self::relu_conversion(node)),
                 NodeType::Gelu => graph.register(Self::gelu_conversion(node)),
                 NodeType::Flatten => graph.register(Self::flatten_conversion(node)),
                 NodeType::Gather => graph.register(Self::gather_conversion(node)),
                 NodeType::GatherElements => graph.register(Self::gather_elements_conversion(node)),
+                NodeType::GatherND => graph.register(Self::gather_nd_conversion(node)),
+                NodeType::ScatterElements => {
+                    graph.register(Self::scatter_elements_conversion(node))
+                }
+                NodeType::ScatterND => graph.register(Self::scatter_nd_conversion(node)),
+                NodeType::NonZero => graph.register(Self::non_zero_conversion(node)),
+                NodeType::OneHot => graph.register(Self::one_hot_conversion(node)),
+                NodeType::Einsum => graph.register(Self::einsum_conversion(node)),
                 NodeType::HardSigmoid => graph.register(Self::hard_sigmoid_conversion(node)),
                 NodeType::Log => graph.register(Self::log_conversion(node)),
                 NodeType::LeakyRelu => graph.register(Self::leaky_relu_conversion(node)),
@@ -318,6 +432,7 @@ impl ParsedOnnxGraph {
                 NodeType::Sigmoid => graph.register(Self::sigmoid_conversion(node)),
                 NodeType::Sin => graph.register(Self::sin_conversion(node)),
                 NodeType::Slice => graph.register(Self::slice_conversion(node)),
+                NodeType::Split => graph.register(Self::split_conversion(node)),
                 NodeType::Sum => graph.register(Self::sum_conversion(node)),
                 NodeType::Transpose => graph.register(Self::transpose_conversion(node)),
                 NodeType::Concat => graph.register(Self::concat_conversion(node)),
@@ -343,11 +458,33 @@ impl ParsedOnnxGraph {
                 NodeType::Squeeze => graph.register(Self::squeeze_conversion(node)),
                 NodeType::RandomUniform => graph.register(Self::random_uniform_conversion(node)),
                 NodeType::Tile => graph.register(Self::tile_conversion(node)),
+                NodeType::TopK => graph.register(Self::top_k_conversion(node)),
+                NodeType::NonMaxSuppression => {
+                    graph.register(Self::non_max_suppression_conversion(node))
+                }
+                NodeType::RoiAlign => graph.register(Self::roi_align_conversion(node)),
                 NodeType::Trilu => graph.register(Self::trilu_conversion(node)),
+                NodeType::If => graph.register(Self::if_conversion::<PS>(node)),
+                NodeType::Loop => graph.register(Self::loop_conversion::<PS>(node)),
+                NodeType::Scan => graph.register(Self::scan_conversion::<PS>(node)),
                 NodeType::RandomNormal => graph.register(Self::random_normal_conversion(node)),
                 NodeType::ConstantOfShape => {
                     graph.register(Self::constant_of_shape_conversion(node))
                 }
+                NodeType::QuantizeLinear => graph.register(Self::quantize_linear_conversion(node)),
+                NodeType::DequantizeLinear => {
+                    graph.register(Self::dequantize_linear_conversion(node))
+                }
+                NodeType::QLinearConv => {
+                    let (conv, quantize) = Self::qlinear_conv_conversion::<PS>(node);
+                    graph.register(conv);
+                    graph.register(quantize);
+                }
+                NodeType::QLinearMatMul => {
+                    let (matmul, quantize) = Self::qlinear_matmul_conversion::<PS>(node);
+                    graph.register(matmul);
+                    graph.register(quantize);
+                }
                 node_type => unsupported_ops.push(node_type),
             }
         }
@@ -355,25 +492,6 @@ impl ParsedOnnxGraph {
         if !unsupported_ops.is_empty() {
             panic!("Unsupported ops: {:?}", unsupported_ops);
         }
-
-        // Get input and output names
-        let input_names = self
-            .0
-            .inputs
-            .iter()
-            .map(|input| input.name.clone())
-            .collect::<Vec<_>>();
-        let output_names = self
-            .0
-            .outputs
-            .iter()
-            .map(|output| output.name.clone())
-            .collect::<Vec<_>>();
-
-        // Register inputs and outputs with the graph
-        graph.register_input_output(input_names, output_names);
-
-        graph
     }
 
     fn constant_conversion<PS: PrecisionSettings>(node: Node) -> ConstantNode {
@@ -630,6 +748,128 @@ impl ParsedOnnxGraph {
         GatherElementsNode::new(input, index, output, dim)
     }
 
+    fn gather_nd_conversion(node: Node) -> GatherNdNode {
+        let data = TensorType::from(&node.inputs[0]);
+        let indices = TensorType::from(&node.inputs[1]);
+        let output = TensorType::from(node.outputs.first().unwrap());
+        gather_nd_config(&node);
+
+        GatherNdNode::new(data, indices, output)
+    }
+
+    fn scatter_elements_conversion(node: Node) -> ScatterElementsNode {
+        let data = TensorType::from(&node.inputs[0]);
+        let indices = TensorType::from(&node.inputs[1]);
+        let updates = TensorType::from(&node.inputs[2]);
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = scatter_elements_config(&node);
+
+        ScatterElementsNode::new(data, indices, updates, output, config)
+    }
+
+    fn scatter_nd_conversion(node: Node) -> ScatterNdNode {
+        let data = TensorType::from(&node.inputs[0]);
+        let indices = TensorType::from(&node.inputs[1]);
+        let updates = TensorType::from(&node.inputs[2]);
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let reduction = scatter_nd_config(&node);
+
+        ScatterNdNode::new(data, indices, updates, output, reduction)
+    }
+
+    fn non_zero_conversion(node: Node) -> NonZeroNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+
+        NonZeroNode::new(input, output)
+    }
+
+    fn one_hot_conversion(node: Node) -> OneHotNode {
+        let indices = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = one_hot_config(&node);
+
+        OneHotNode::new(indices, output, config)
+    }
+
+    fn einsum_conversion(node: Node) -> EinsumNode {
+        let inputs = node.inputs.iter().map(TensorType::from).collect();
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = einsum_config(&node);
+
+        EinsumNode::new(inputs, output, config)
+    }
+
+    fn split_conversion(node: Node) -> SplitNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let outputs = node.outputs.iter().map(TensorType::from).collect();
+        let config = split_config(&node);
+
+        SplitNode::new(input, outputs, config)
+    }
+
+    fn if_conversion<PS: PrecisionSettings + 'static>(mut node: Node) -> IfNode<PS> {
+        let condition = Type::from(node.inputs.first().unwrap());
+        let outputs = node.outputs.iter().map(Type::from).collect();
+        let then_branch = Self::subgraph_conversion(&mut node, "then_branch");
+        let else_branch = Self::subgraph_conversion(&mut node, "else_branch");
+
+        IfNode::new(condition, outputs, then_branch, else_branch)
+    }
+
+    fn loop_conversion<PS: PrecisionSettings + 'static>(mut node: Node) -> LoopNode<PS> {
+        let body = Self::subgraph_conversion::<PS>(&mut node, "body");
+        // The inputs are the trip count, the condition, the initial values and the captured values
+        let optional_input = |input: &OnnxArgument| match input.name.is_empty() {
+            true => None,
+            false => Some(Type::from(input)),
+        };
+        let max_trip_count = optional_input(&node.inputs[0]);
+        let condition = optional_input(&node.inputs[1]);
+        let num_carried = body.inputs.len() - 2;
+        let initial_values = node.inputs[2..num_carried + 2]
+            .iter()
+            .map(Type::from)
+            .collect();
+        let outputs = node.outputs.iter().map(Type::from).collect();
+
+        LoopNode::new(max_trip_count, condition, initial_values, outputs, body)
+    }
+
+    fn scan_conversion<PS: PrecisionSettings + 'static>(mut node: Node) -> ScanNode<PS> {
+        let num_scan_inputs = scan_config(&node);
+        let body = Self::subgraph_conversion::<PS>(&mut node, "body");
+        // The inputs are the initial states, the scan inputs and the captured values
+        let num_states = body.inputs.len() - num_scan_inputs;
+        let initial_states = node.inputs[..num_states].iter().map(Type::from).collect();
+        let scan_inputs = node.inputs[num_states..num_states + num_scan_inputs]
+            .iter()
+            .map(TensorType::from)
+            .collect();
+        let outputs = node.outputs.iter().map(Type::from).collect();
+
+        ScanNode::new(initial_states, scan_inputs, outputs, body)
+    }
+
+    /// Converts the subgraph attribute of a control flow node.
+    fn subgraph_conversion<PS: PrecisionSettings + 'static>(
+        node: &mut Node,
+        attribute: &str,
+    ) -> SubGraph<PS> {
+        let subgraph = node
+            .attrs
+            .remove(attribute)
+            .unwrap_or_else(|| panic!("{}: {} attribute is required", node.name, attribute))
+            .into_graph();
+        let inputs = subgraph.inputs.iter().map(Type::from).collect();
+        let outputs = subgraph.outputs.iter().map(Type::from).collect();
+
+        let mut graph = BurnGraph::<PS>::default();
+        Self::register_nodes(&mut graph, subgraph.nodes);
+
+        SubGraph::new(graph.into_nodes(), inputs, outputs)
+    }
+
     fn transpose_conversion(node: Node) -> UnaryNode {
         let input = Type::from(node.inputs.first().unwrap());
         let output = Type::from(node.outputs.first().unwrap());
@@ -843,6 +1083,14 @@ impl ParsedOnnxGraph {
         ArgMaxNode::new(input, output, axis)
     }
 
+    fn argmin_conversion(node: Node) -> ArgMinNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let (axis, keepdims) = argmin_config(&node);
+
+        ArgMinNode::new(input, output, axis, keepdims)
+    }
+
     fn concat_conversion(node: Node) -> ConcatNode {
         let inputs = node.inputs.iter().map(TensorType::from).collect();
 
@@ -865,6 +1113,60 @@ impl ParsedOnnxGraph {
         LinearNode::new(name, input, output, weight, bias, config)
     }
 
+    fn lstm_conversion(node: Node) -> LstmNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let config = lstm_config(&node);
+        let weights = recurrent_weights(&node, 4);
+
+        LstmNode::new(
+            name,
+            input,
+            recurrent_state(&node, 5),
+            recurrent_state(&node, 6),
+            recurrent_output(&node, 0),
+            recurrent_output(&node, 1),
+            recurrent_output(&node, 2),
+            weights,
+            config,
+        )
+    }
+
+    fn gru_conversion(node: Node) -> GruNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let (config, reset_after) = gru_config(&node);
+        let weights = recurrent_weights(&node, 3);
+
+        GruNode::new(
+            name,
+            input,
+            recurrent_state(&node, 5),
+            recurrent_output(&node, 0),
+            recurrent_output(&node, 1),
+            weights,
+            config,
+            reset_after,
+        )
+    }
+
+    fn rnn_conversion(node: Node) -> RnnNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let config = rnn_config(&node);
+        let weights = recurrent_weights(&node, 1);
+
+        RnnNode::new(
+            name,
+            input,
+            recurrent_state(&node, 5),
+            recurrent_output(&node, 0),
+            recurrent_output(&node, 1),
+            weights,
+            config,
+        )
+    }
+
     fn dropout_conversion(node: Node) -> DropoutNode {
         let name = &node.name;
         let input = TensorType::from(node.inputs.first().unwrap());
@@ -917,6 +1219,57 @@ impl ParsedOnnxGraph {
         LayerNormNode::new(name, input, output, gamma, beta, config, full_precision)
     }
 
+    fn instance_norm_conversion<PS: PrecisionSettings>(node: Node) -> InstanceNormNode {
+        let config = instance_norm_config(&node);
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+
+        // Scale tensor (aka gamma)
+        let gamma = extract_data_serialize::<PS::FloatElem>(1, &node).expect("Gamma is required");
+        // Bias (B) tensor
+        let beta = extract_data_serialize::<PS::FloatElem>(2, &node).expect("Beta is required");
+
+        let name = &node.name;
+
+        InstanceNormNode::new(name, input, output, gamma, beta, config)
+    }
+
+    fn group_norm_conversion<PS: PrecisionSettings>(node: Node) -> GroupNormNode {
+        let config = group_norm_config(&node);
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+
+        // The scale and bias given per group are repeated for each channel of the group
+        let per_channel = |data: TensorData| {
+            if data.num_elements() == config.num_channels {
+                return data;
+            }
+
+            let group_size = config.num_channels / config.num_groups;
+            let values = data
+                .iter::<f32>()
+                .flat_map(|value| (0..group_size).map(move |_| value))
+                .collect::<Vec<_>>();
+            TensorData::new(values, [config.num_channels]).convert::<PS::FloatElem>()
+        };
+
+        // Scale tensor (aka gamma)
+        let gamma = extract_data_serialize::<PS::FloatElem>(1, &node).expect("Gamma is required");
+        // Bias tensor
+        let beta = extract_data_serialize::<PS::FloatElem>(2, &node).expect("Beta is required");
+
+        let name = &node.name;
+
+        GroupNormNode::new(
+            name,
+            input,
+            output,
+            per_channel(gamma),
+            per_channel(beta),
+            config,
+        )
+    }
+
     fn conv1d_conversion<PS: PrecisionSettings>(node: Node) -> Conv1dNode {
         let input = TensorType::from(node.inputs.first().unwrap());
         let output = TensorType::from(node.outputs.first().unwrap());
@@ -965,6 +1318,68 @@ impl ParsedOnnxGraph {
         Conv3dNode::new(name, input, output, weight, bias, config)
     }
 
+    fn quantize_linear_conversion(node: Node) -> QuantizeLinearNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(&quantized(node.outputs.first().unwrap()));
+        let (scale, zero_point) = quantization_config(&node, 1, 2);
+
+        QuantizeLinearNode::new(input, output, scale, zero_point)
+    }
+
+    fn dequantize_linear_conversion(node: Node) -> DequantizeLinearNode {
+        let input = TensorType::from(&quantized(node.inputs.first().unwrap()));
+        let output = TensorType::from(node.outputs.first().unwrap());
+
+        DequantizeLinearNode::new(input, output)
+    }
+
+    /// The quantized input is dequantized by the convolution, which computes the real output with
+    /// the quantized weights before it is quantized.
+    fn qlinear_conv_conversion<PS: PrecisionSettings>(
+        node: Node,
+    ) -> (BurnNode<PS>, QuantizeLinearNode) {
+        let dim = match &node.inputs[3].ty {
+            ArgType::Tensor(weight) => weight.dim,
+            _ => panic!("QLinearConv: weight tensor must be present"),
+        };
+        let node_type = match dim {
+            3 => NodeType::Conv1d,
+            4 => NodeType::Conv2d,
+            5 => NodeType::Conv3d,
+            _ => panic!("QLinearConv: unsupported weight rank {dim}"),
+        };
+        let (conv, quantize) = qlinear_split(node, node_type, &[0, 3, 8]);
+
+        let conv = match conv.node_type {
+            NodeType::Conv1d => NodeCodegen::<PS>::into_node(Self::conv1d_conversion::<PS>(conv)),
+            NodeType::Conv2d => NodeCodegen::<PS>::into_node(Self::conv2d_conversion::<PS>(conv)),
+            _ => NodeCodegen::<PS>::into_node(Self::conv3d_conversion::<PS>(conv)),
+        };
+
+        (conv, quantize)
+    }
+
+    /// The right hand side of the matmul is the weight of a linear layer when it is a constant
+    /// matrix, like for the `MatMul` nodes coalesced into `Linear` nodes.
+    fn qlinear_matmul_conversion<PS: PrecisionSettings>(
+        node: Node,
+    ) -> (BurnNode<PS>, QuantizeLinearNode) {
+        let linear = node.inputs[3].value.is_some()
+            && matches!(&node.inputs[3].ty, ArgType::Tensor(weight) if weight.dim == 2);
+        let node_type = match linear {
+            true => NodeType::Linear,
+            false => NodeType::MatMul,
+        };
+        let (matmul, quantize) = qlinear_split(node, node_type, &[0, 3]);
+
+        let matmul = match linear {
+            true => NodeCodegen::<PS>::into_node(Self::linear_conversion::<PS>(matmul)),
+            false => NodeCodegen::<PS>::into_node(Self::matmul_conversion(matmul)),
+        };
+
+        (matmul, quantize)
+    }
+
     fn max_pool1d_conversion(node: Node) -> MaxPool1dNode {
         let input = TensorType::from(node.inputs.first().unwrap());
         let output = TensorType::from(node.outputs.first().unwrap());
@@ -1208,6 +1623,34 @@ impl ParsedOnnxGraph {
         TileNode::new(input, output, config)
     }
 
+    fn top_k_conversion(node: Node) -> TopKNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let values = TensorType::from(&node.outputs[0]);
+        let indices = TensorType::from(&node.outputs[1]);
+        let config = top_k_config(&node);
+
+        TopKNode::new(input, values, indices, config)
+    }
+
+    fn non_max_suppression_conversion(node: Node) -> NonMaxSuppressionNode {
+        let boxes = TensorType::from(&node.inputs[0]);
+        let scores = TensorType::from(&node.inputs[1]);
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = non_max_suppression_config(&node);
+
+        NonMaxSuppressionNode::new(boxes, scores, output, config)
+    }
+
+    fn roi_align_conversion(node: Node) -> RoiAlignNode {
+        let input = TensorType::from(&node.inputs[0]);
+        let rois = TensorType::from(&node.inputs[1]);
+        let batch_indices = TensorType::from(&node.inputs[2]);
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = roi_align_config(&node);
+
+        RoiAlignNode::new(input, rois, batch_indices, output, config)
+    }
+
     fn trilu_conversion(node: Node) -> TriluNode {
         let input = TensorType::from(node.inputs.first().unwrap());
         let output = TensorType::from(node.outputs.first().unwrap());
@@ -1223,7 +1666,10 @@ impl ParsedOnnxGraph {
 /// * `input_index` - The index of the input originally from input.
 /// * `node` - The node where value are stored.
 #[track_caller]
-fn extract_data_serialize<E: Element>(input_index: usize, node: &Node) -> Option<TensorData> {
+pub(crate) fn extract_data_serialize<E: Element>(
+    input_index: usize,
+    node: &Node,
+) -> Option<TensorData> {
     if node.inputs.is_empty() {
         return None;
     }
@@ -1247,6 +1693,97 @@ fn extract_data_serialize<E: Element>(input_index: usize, node: &Node) -> Option
     }
 }
 
+/// Extract the weights of each gate of each direction of a LSTM, GRU or RNN node.
+fn recurrent_weights(node: &Node, num_gates: usize) -> Vec<Vec<GateWeights>> {
+    let weight = extract_data_serialize::<f32>(1, node).expect("Weight is required");
+    let recurrence = extract_data_serialize::<f32>(2, node).expect("Recurrence is required");
+    let bias = extract_data_serialize::<f32>(3, node);
+
+    GateWeights::from_onnx(weight, recurrence, bias, num_gates)
+}
+
+/// Get the initial state of a LSTM, GRU or RNN node, if provided as a runtime input.
+///
+/// Constant initial states are only supported when they are zeros, which is the default.
+fn recurrent_state(node: &Node, input_index: usize) -> Option<TensorType> {
+    let input = node
+        .inputs
+        .get(input_index)
+        .filter(|input| !input.name.is_empty())?;
+
+    match &input.value {
+        None => Some(TensorType::from(input)),
+        Some(data) if data.clone().into_f32s().iter().all(|value| *value == 0.0) => None,
+        Some(_) => panic!(
+            "{:?}: only runtime or zero initial states are supported",
+            node.node_type
+        ),
+    }
+}
+
+/// Get an optional output of a LSTM, GRU or RNN node.
+fn recurrent_output(node: &Node, output_index: usize) -> Option<TensorType> {
+    node.outputs
+        .get(output_index)
+        .filter(|output| !output.name.is_empty())
+        .map(TensorType::from)
+}
+
+/// The argument of a quantized `int8` or `uint8` tensor, which is a float tensor with a quantized
+/// representation in Burn. The other `int8` and `uint8` tensors are int tensors.
+fn quantized(arg: &OnnxArgument) -> OnnxArgument {
+    let mut arg = arg.clone();
+    if let ArgType::Tensor(tensor) = &mut arg.ty {
+        if matches!(tensor.elem_type, ElementType::Int8 | ElementType::Uint8) {
+            tensor.elem_type = ElementType::Float32;
+        }
+    }
+    arg
+}
+
+/// Split a `QLinearConv` or `QLinearMatMul` node into a float node, with the given inputs, and the
+/// quantization of its output with the output scale and zero point.
+fn qlinear_split(node: Node, node_type: NodeType, inputs: &[usize]) -> (Node, QuantizeLinearNode) {
+    let (scale, zero_point) = quantization_config(&node, 6, 7);
+    let output = node.outputs[0].clone();
+    let dim = match &output.ty {
+        ArgType::Tensor(tensor) => tensor.dim,
+        _ => panic!("{:?}: output must be a tensor", node.node_type),
+    };
+    let float_output = OnnxArgument {
+        name: format!("{}_float", output.name),
+        ty: ArgType::Tensor(OnnxTensorType {
+            elem_type: ElementType::Float32,
+            dim,
+            shape: None,
+        }),
+        value: None,
+        passed: true,
+    };
+
+    let inputs = inputs
+        .iter()
+        .filter_map(|index| node.inputs.get(*index))
+        .filter(|input| !input.name.is_empty())
+        .map(quantized)
+        .collect();
+    let quantize = QuantizeLinearNode::new(
+        TensorType::from(&float_output),
+        TensorType::from(&quantized(&output)),
+        scale,
+        zero_point,
+    );
+    let node = Node {
+        node_type,
+        name: node.name,
+        inputs,
+        outputs: vec![float_output],
+        attrs: node.attrs,
+    };
+
+    (node, quantize)
+}
+
 /// Convert data to `TensorData`.
 fn serialize_data<E: Element>(data: Data, shape: Vec<usize>) -> TensorData {
     match data {
@@ -1255,6 +1792,17 @@ fn serialize_data<E: Element>(data: Data, shape: Vec<usize>) -> TensorData {
         Data::Float64s(val) => TensorData::new(val, shape).convert::<E>(),
         Data::Int32s(val) => TensorData::new(val, shape).convert::<E>(),
         Data::Int64s(val) => TensorData::new(val, shape).convert::<E>(),
+        Data::Int8s(val) => TensorData::new(val, shape).convert::<E>(),
+        Data::Uint8s(val) => TensorData::new(val, shape).convert::<E>(),
+        // Quantized weights are stored quantized, the record keeps their representation
+        Data::QuantizedInt8s(val) => TensorData::quantized(
+            val.values,
+            shape,
+            QuantizationStrategy::PerTensorAffineInt8(AffineQuantization::init(
+                val.scale,
+                val.zero_point,
+            )),
+        ),
         // TODO support Bool tensor when it is supported by Burn
         _ => panic!("Unsupported tensor element type"),
     }
@@ -1270,7 +1818,8 @@ impl From<&OnnxArgument> for TensorType {
                 ..
             }) => TensorType::new_float_with_shape(arg.name.clone(), *dim, shape.clone()),
             ArgType::Tensor(OnnxTensorType {
-                elem_type: ElementType::Int32 | ElementType::Int64,
+                elem_type:
+                    ElementType::Int32 | ElementType::Int64 | ElementType::Int8 | ElementType::Uint8,
                 dim,
                 shape,
                 ..
@@ -1322,6 +1871,7 @@ impl From<&ElementType> for ScalarKind {
             ElementType::Bool => ScalarKind::Bool,
             ElementType::String => panic!("String tensor unsupported"),
             ElementType::Float16 => panic!("Float16 tensor unsupported"),
+            ElementType::Int8 | ElementType::Uint8 => ScalarKind::Int32,
         }
     }
 }
@@ -1334,6 +1884,8 @@ impl From<ElementType> for TensorKind {
             ElementType::Int32 => TensorKind::Int,
             ElementType::Int64 => TensorKind::Int,
             ElementType::Bool => TensorKind::Bool,
+            ElementType::Int8 => TensorKind::Int,
+            ElementType::Uint8 => TensorKind::Int,
             _ => panic!("Unsupported tensor type"),
         }
     }
@@ -0,0 +1,54 @@
+use core::marker::PhantomData;
+
+use burn_tensor::{
+    repr::{ReprBackend, TensorHandle},
+    Shape,
+};
+
+use super::MultiBackendBridge;
+
+/// Transfers tensors between the devices of a single backend.
+pub struct DeviceBridge<B> {
+    backend: PhantomData<B>,
+}
+
+impl<B: ReprBackend> MultiBackendBridge for DeviceBridge<B> {
+    type TensorHandle = B::Handle;
+    type Device = B::Device;
+
+    fn change_backend_float(
+        tensor: Self::TensorHandle,
+        shape: Shape,
+        target_device: &Self::Device,
+    ) -> Self::TensorHandle {
+        let tensor = B::float_tensor(TensorHandle {
+            handle: tensor,
+            shape,
+        });
+        B::float_tensor_handle(B::float_to_device(tensor, target_device))
+    }
+
+    fn change_backend_int(
+        tensor: Self::TensorHandle,
+        shape: Shape,
+        target_device: &Self::Device,
+    ) -> Self::TensorHandle {
+        let tensor = B::int_tensor(TensorHandle {
+            handle: tensor,
+            shape,
+        });
+        B::int_tensor_handle(B::int_to_device(tensor, target_device))
+    }
+
+    fn change_backend_bool(
+        tensor: Self::TensorHandle,
+        shape: Shape,
+        target_device: &Self::Device,
+    ) -> Self::TensorHandle {
+        let tensor = B::bool_tensor(TensorHandle {
+            handle: tensor,
+            shape,
+        });
+        B::bool_tensor_handle(B::bool_to_device(tensor, target_device))
+    }
+}
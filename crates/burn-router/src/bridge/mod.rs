@@ -1,5 +1,7 @@
 mod base;
 mod byte;
+mod device;
 
 pub use base::*;
 pub use byte::*;
+pub use device::*;
@@ -1,5 +1,7 @@
 mod base;
 mod direct;
+mod observed;
 
 pub use base::*;
 pub use direct::*;
+pub use observed::*;
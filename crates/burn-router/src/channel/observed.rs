@@ -0,0 +1,149 @@
+use alloc::{format, string::String, vec::Vec};
+use core::{future::Future, marker::PhantomData};
+
+use burn_tensor::{
+    backend::{Backend, BackendBridge},
+    repr::{OperationDescription, ReprBackend, TensorDescription, TensorId},
+    DType, TensorData,
+};
+
+use crate::{get_client, DeviceBridge, RouterTensor, Runner, RunnerChannel, RunnerClient};
+
+/// Observes the operations executed on a device, e.g. to record them.
+///
+/// An observer is created with the client of each device, so the state of the observer is
+/// specific to the device.
+pub trait RunnerObserver<B: ReprBackend>: Clone + Send + Sync + Sized + 'static {
+    /// Create the observer of the given device.
+    fn init(device: &B::Device) -> Self;
+
+    /// Called with each operation before it is executed, the client can be used to read the
+    /// values of the input tensors.
+    fn observe(&self, operation: &OperationDescription, client: &ObservedClient<B, Self>);
+}
+
+/// A local channel executing the operations on a single backend `B`, with an
+/// [observer](RunnerObserver) notified of each operation.
+pub struct ObservedChannel<B, O> {
+    backend: PhantomData<B>,
+    observer: PhantomData<O>,
+}
+
+impl<B, O> Clone for ObservedChannel<B, O> {
+    fn clone(&self) -> Self {
+        Self {
+            backend: PhantomData,
+            observer: PhantomData,
+        }
+    }
+}
+
+impl<B: ReprBackend, O: RunnerObserver<B>> ObservedChannel<B, O>
+where
+    // Restrict full precision backend handle to be the same
+    <<B as Backend>::FullPrecisionBridge as BackendBridge<B>>::Target:
+        ReprBackend<Handle = B::Handle>,
+{
+    /// Get the observer of the given device.
+    pub fn observer(device: &B::Device) -> O {
+        get_client::<Self>(device).observer
+    }
+}
+
+impl<B: ReprBackend, O: RunnerObserver<B>> RunnerChannel for ObservedChannel<B, O>
+where
+    // Restrict full precision backend handle to be the same
+    <<B as Backend>::FullPrecisionBridge as BackendBridge<B>>::Target:
+        ReprBackend<Handle = B::Handle>,
+{
+    type Device = B::Device;
+    type Bridge = DeviceBridge<B>;
+    type Client = ObservedClient<B, O>;
+    type FloatElem = B::FloatElem;
+    type IntElem = B::IntElem;
+
+    fn name() -> String {
+        format!("observed<{}>", B::name())
+    }
+
+    fn init_client(device: &Self::Device) -> Self::Client {
+        ObservedClient {
+            runner: Runner::new(device.clone()),
+            observer: O::init(device),
+        }
+    }
+
+    fn get_tensor_handle(tensor: &TensorDescription, client: &Self::Client) -> B::Handle {
+        client.runner.get_tensor_handle(tensor)
+    }
+
+    fn register_tensor(
+        client: &Self::Client,
+        handle: B::Handle,
+        shape: Vec<usize>,
+        dtype: DType,
+    ) -> RouterTensor<Self::Client> {
+        client
+            .runner
+            .register_tensor(handle, shape, dtype, client.clone())
+    }
+}
+
+/// The client of the [observed channel](ObservedChannel), executing the operations on a
+/// [runner](Runner) after notifying the observer.
+#[derive(Clone)]
+pub struct ObservedClient<B: ReprBackend, O> {
+    runner: Runner<B>,
+    observer: O,
+}
+
+impl<B: ReprBackend, O: RunnerObserver<B>> RunnerClient for ObservedClient<B, O>
+where
+    // Restrict full precision backend handle to be the same
+    <<B as Backend>::FullPrecisionBridge as BackendBridge<B>>::Target:
+        ReprBackend<Handle = B::Handle>,
+{
+    type Device = B::Device;
+
+    fn register(&self, op: OperationDescription) {
+        self.observer.observe(&op, self);
+        self.runner.register(op)
+    }
+
+    fn read_tensor(&self, tensor: TensorDescription) -> impl Future<Output = TensorData> + Send {
+        self.runner.read_tensor(tensor)
+    }
+
+    fn register_tensor_data(&self, data: TensorData) -> RouterTensor<Self> {
+        let desc = self.runner.register_tensor_data_desc(data);
+        RouterTensor::new(desc.id.into(), desc.shape, desc.dtype, self.clone())
+    }
+
+    fn register_empty_tensor(&self, shape: Vec<usize>, dtype: DType) -> RouterTensor<Self> {
+        let desc = self.runner.register_empty_tensor_desc(shape, dtype);
+        RouterTensor::new(desc.id.into(), desc.shape, desc.dtype, self.clone())
+    }
+
+    fn register_float_tensor(&self, shape: Vec<usize>, full_precision: bool) -> RouterTensor<Self> {
+        let desc = self
+            .runner
+            .register_float_tensor_desc(shape, full_precision);
+        RouterTensor::new(desc.id.into(), desc.shape, desc.dtype, self.clone())
+    }
+
+    fn device(&self) -> Self::Device {
+        self.runner.device()
+    }
+
+    fn register_orphan(&self, id: &TensorId) {
+        self.runner.register_orphan(id)
+    }
+
+    fn sync(&self) {
+        self.runner.sync()
+    }
+
+    fn seed(&self, seed: u64) {
+        self.runner.seed(seed)
+    }
+}
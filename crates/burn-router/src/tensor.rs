@@ -64,6 +64,11 @@ impl<C: RunnerClient> RouterTensor<C> {
         }
     }
 
+    /// Get the [tensor id](TensorId).
+    pub fn id(&self) -> TensorId {
+        *self.id.as_ref()
+    }
+
     pub(crate) fn shape(&self) -> Shape {
         Shape::from(self.shape.clone())
     }
@@ -870,6 +870,38 @@ impl TensorCheck {
         check
     }
 
+    pub(crate) fn gather_scatter_nd<const D: usize, const DI: usize, const DV: usize>(
+        ops: &str,
+        shape_indices: &Shape,
+    ) -> Self {
+        let mut check = Self::Ok;
+        let index_depth = shape_indices.dims[DI - 1];
+
+        if index_depth > D {
+            check = check.register(
+                ops,
+                TensorError::new(format!(
+                    "Can't index a tensor with ({D}) dimensions using ({index_depth}) coordinates"
+                )),
+            );
+        } else if DV != DI - 1 + D - index_depth {
+            check = check.register(
+                ops,
+                TensorError::new(
+                    "The slices should have the rank of the indices without the last dimension, \
+                    plus the number of dimensions that are not indexed."
+                        .to_string(),
+                )
+                .details(format!(
+                    "Expected ({}) dimensions, got ({DV}).",
+                    DI - 1 + D - index_depth
+                )),
+            );
+        }
+
+        check
+    }
+
     pub(crate) fn select<const D: usize>(dim: usize) -> Self {
         Self::check_select_basic::<D>(Self::Ok, "select", dim)
     }
@@ -53,9 +53,9 @@ where
     pub fn one_hot(self, num_classes: usize) -> Tensor<B, 2, Int> {
         check!(TensorCheck::one_hot_tensor(self.clone(), num_classes));
         let [num_samples] = self.dims();
-        let indices = self.unsqueeze();
+        let indices = self.unsqueeze_dim(1);
         let values = indices.ones_like();
-        Tensor::zeros([num_samples, num_samples], &indices.device()).scatter(1, indices, values)
+        Tensor::zeros([num_samples, num_classes], &indices.device()).scatter(1, indices, values)
     }
 }
 
@@ -5,7 +5,7 @@ use crate::alloc::borrow::ToOwned;
 use crate::TensorPrimitive;
 use crate::{
     backend::Backend, check, check::TensorCheck, BasicOps, Bool, Distribution, Element,
-    ElementConversion, Float, Int, Shape, Tensor, TensorKind,
+    ElementConversion, Float, Int, Shape, Tensor, TensorData, TensorKind,
 };
 
 impl<B, const D: usize, K> Tensor<B, D, K>
@@ -319,6 +319,61 @@ where
         ))
     }
 
+    /// Gather the slices of the tensor at the coordinates given by the last dimension of the
+    /// indices.
+    ///
+    /// The last dimension of the indices has a size `k <= D` and indexes the first `k` dimensions
+    /// of the tensor, so the output has `DI - 1 + D - k` dimensions.
+    ///
+    /// Example using a 3D tensor with `k = 2`:
+    ///
+    /// `output[i, j, l] = input[indices[i, j, 0], indices[i, j, 1], l]`
+    pub fn gather_nd<const DI: usize, const DO: usize>(
+        self,
+        indices: Tensor<B, DI, Int>,
+    ) -> Tensor<B, DO, K> {
+        check!(TensorCheck::gather_scatter_nd::<D, DI, DO>(
+            "Gather ND",
+            &indices.shape()
+        ));
+
+        let shape = self.dims();
+        let mut output_shape = indices.dims()[..DI - 1].to_vec();
+        let (positions, index_depth) = linear_nd_indices(&shape, indices);
+        output_shape.extend_from_slice(&shape[index_depth..]);
+
+        let slice_size = shape[index_depth..].iter().product::<usize>();
+        self.reshape([-1, slice_size as i32])
+            .select(0, positions)
+            .reshape(Shape::from(output_shape))
+    }
+
+    /// Assign the slices of the value tensor to the coordinates given by the last dimension of
+    /// the indices using sum reduction.
+    ///
+    /// This is the inverse of [gather_nd](Tensor::gather_nd). Example using a 3D tensor with
+    /// `k = 2`:
+    ///
+    /// `input[indices[i, j, 0], indices[i, j, 1], l] += values[i, j, l]`
+    pub fn scatter_nd<const DI: usize, const DV: usize>(
+        self,
+        indices: Tensor<B, DI, Int>,
+        values: Tensor<B, DV, K>,
+    ) -> Self {
+        check!(TensorCheck::gather_scatter_nd::<D, DI, DV>(
+            "Scatter ND",
+            &indices.shape()
+        ));
+
+        let shape = self.dims();
+        let (positions, index_depth) = linear_nd_indices(&shape, indices);
+
+        let slice_size = shape[index_depth..].iter().product::<usize>();
+        self.reshape([-1, slice_size as i32])
+            .select_assign(0, positions, values.reshape([-1, slice_size as i32]))
+            .reshape(shape)
+    }
+
     /// Select the tensor elements along the given dimension corresponding to the given indices.
     ///
     /// Example using a 3D tensor:
@@ -855,6 +910,32 @@ where
     }
 }
 
+/// Convert the coordinates in the last dimension of the indices into positions along the first
+/// dimension of the tensor reshaped to `[num_slices, slice_size]`.
+///
+/// Returns the positions and the number of indexed dimensions.
+fn linear_nd_indices<B: Backend, const DI: usize>(
+    shape: &[usize],
+    indices: Tensor<B, DI, Int>,
+) -> (Tensor<B, 1, Int>, usize) {
+    let index_depth = indices.dims()[DI - 1];
+    let strides = (0..index_depth)
+        .map(|i| shape[i + 1..index_depth].iter().product::<usize>() as i64)
+        .collect::<Vec<_>>();
+    let strides = Tensor::<B, 2, Int>::from_data(
+        TensorData::new(strides, [1, index_depth]).convert::<B::IntElem>(),
+        &indices.device(),
+    );
+
+    let positions = indices
+        .reshape([-1, index_depth as i32])
+        .mul(strides)
+        .sum_dim(1)
+        .flatten(0, 1);
+
+    (positions, index_depth)
+}
+
 /// Trait that list all operations that can be applied on all numerical tensors.
 ///
 /// # Warnings
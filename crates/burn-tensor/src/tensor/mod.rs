@@ -4,12 +4,14 @@ mod api;
 mod data;
 mod distribution;
 mod element;
+mod npy;
 mod shape;
 
 pub use api::*;
 pub use data::*;
 pub use distribution::*;
 pub use element::*;
+pub use npy::*;
 pub use shape::*;
 
 /// The activation module.
@@ -33,6 +35,12 @@ pub mod ops;
 /// Tensor quantization module.
 pub mod quantization;
 
+/// The signal processing module.
+pub mod signal;
+
+/// The computer vision module.
+pub mod vision;
+
 #[cfg(feature = "std")]
 pub use report::*;
 
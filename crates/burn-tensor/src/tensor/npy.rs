@@ -0,0 +1,619 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{DType, TensorData};
+
+/// The magic string at the start of the `.npy` files.
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// The things that can go wrong when reading or writing [NumPy](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html)
+/// `.npy` data.
+#[derive(Debug)]
+pub enum NpyError {
+    /// The header of the data is invalid.
+    InvalidHeader(String),
+    /// The data type isn't supported.
+    UnsupportedDType(String),
+    /// The data doesn't have the size expected from its header.
+    InvalidSize {
+        /// The expected number of bytes.
+        expected: usize,
+        /// The actual number of bytes.
+        actual: usize,
+    },
+    /// Failed to read or write a file.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for NpyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidHeader(message) => write!(f, "Invalid npy header: {message}"),
+            Self::UnsupportedDType(dtype) => write!(f, "Unsupported npy data type {dtype}"),
+            Self::InvalidSize { expected, actual } => {
+                write!(f, "Expected {expected} bytes of npy data, got {actual}")
+            }
+            #[cfg(feature = "std")]
+            Self::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+// TODO: Move from std to core after Error is core (see https://github.com/rust-lang/rust/issues/103765)
+#[cfg(feature = "std")]
+impl std::error::Error for NpyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for NpyError {
+    fn from(error: std::io::Error) -> Self {
+        NpyError::Io(error)
+    }
+}
+
+/// The header of [NumPy](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html)
+/// `.npy` data, describing how the values following it are laid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpyHeader {
+    /// The data type of the values.
+    pub dtype: DType,
+    /// The shape of the array.
+    pub shape: Vec<usize>,
+    /// Whether the values are stored in column-major (Fortran) order instead of row-major (C)
+    /// order.
+    pub fortran_order: bool,
+    /// Whether the values are stored in big-endian byte order.
+    pub big_endian: bool,
+}
+
+impl NpyHeader {
+    /// Parses the header at the start of `.npy` data.
+    ///
+    /// # Returns
+    ///
+    /// The header and the number of bytes it spans, i.e. the offset of the values.
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), NpyError> {
+        if bytes.len() < 10 || &bytes[..6] != NPY_MAGIC {
+            return Err(NpyError::InvalidHeader(
+                "Missing the NumPy magic string".to_string(),
+            ));
+        }
+
+        let (len_size, start) = match bytes[6] {
+            1 => (2, 10),
+            2 | 3 => (4, 12),
+            version => {
+                return Err(NpyError::InvalidHeader(format!(
+                    "Unsupported format version {version}"
+                )))
+            }
+        };
+
+        if bytes.len() < start {
+            return Err(NpyError::InvalidHeader("Truncated header".to_string()));
+        }
+
+        let mut len = [0u8; 4];
+        len[..len_size].copy_from_slice(&bytes[8..8 + len_size]);
+        let end = start + u32::from_le_bytes(len) as usize;
+
+        let header = bytes
+            .get(start..end)
+            .and_then(|header| core::str::from_utf8(header).ok())
+            .ok_or_else(|| NpyError::InvalidHeader("Truncated header".to_string()))?;
+
+        let descr = dict_value(header, "descr")?;
+        let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+        let (dtype, big_endian) = parse_descr(descr)?;
+
+        let fortran_order = match dict_value(header, "fortran_order")? {
+            "True" => true,
+            "False" => false,
+            value => {
+                return Err(NpyError::InvalidHeader(format!(
+                    "Invalid fortran order {value}"
+                )))
+            }
+        };
+
+        let shape = dict_value(header, "shape")?
+            .trim_matches(|c| c == '(' || c == ')')
+            .split(',')
+            .map(str::trim)
+            .filter(|dim| !dim.is_empty())
+            .map(|dim| {
+                dim.parse::<usize>()
+                    .map_err(|_| NpyError::InvalidHeader(format!("Invalid dimension {dim}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let header = Self {
+            dtype,
+            shape,
+            fortran_order,
+            big_endian,
+        };
+
+        Ok((header, end))
+    }
+
+    /// The number of bytes of each value.
+    pub fn elem_size(&self) -> usize {
+        dtype_size(self.dtype)
+    }
+
+    /// The number of bytes of the values described by the header.
+    pub fn data_size(&self) -> usize {
+        self.shape.iter().product::<usize>() * self.elem_size()
+    }
+
+    /// Decodes the values described by the header to row-major tensor data in native byte order.
+    ///
+    /// The values are copied once from `data` to the tensor data.
+    pub fn decode(&self, data: &[u8]) -> Result<TensorData, NpyError> {
+        self.check_size(data)?;
+
+        let bytes = if self.fortran_order && self.shape.len() > 1 {
+            fortran_to_c_order(data, &self.shape, self.elem_size())
+        } else {
+            data.to_vec()
+        };
+
+        self.decode_in_place(bytes)
+    }
+
+    /// Decodes the values described by the header, reusing their buffer when they are already in
+    /// row-major order.
+    fn decode_owned(&self, data: Vec<u8>) -> Result<TensorData, NpyError> {
+        self.check_size(&data)?;
+
+        let bytes = if self.fortran_order && self.shape.len() > 1 {
+            fortran_to_c_order(&data, &self.shape, self.elem_size())
+        } else {
+            data
+        };
+
+        self.decode_in_place(bytes)
+    }
+
+    fn check_size(&self, data: &[u8]) -> Result<(), NpyError> {
+        let expected = self.data_size();
+        if data.len() != expected {
+            return Err(NpyError::InvalidSize {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Converts row-major values to native byte order.
+    fn decode_in_place(&self, mut bytes: Vec<u8>) -> Result<TensorData, NpyError> {
+        let elem_size = self.elem_size();
+
+        if self.big_endian != cfg!(target_endian = "big") && elem_size > 1 {
+            for elem in bytes.chunks_exact_mut(elem_size) {
+                elem.reverse();
+            }
+        }
+
+        if self.dtype == DType::Bool && bytes.iter().any(|byte| *byte > 1) {
+            return Err(NpyError::InvalidHeader(
+                "Invalid boolean values".to_string(),
+            ));
+        }
+
+        Ok(TensorData {
+            bytes,
+            shape: self.shape.clone(),
+            dtype: self.dtype,
+        })
+    }
+
+    /// Encodes the header, padded so that the values following it are aligned on 64 bytes.
+    pub fn encode(&self) -> Result<Vec<u8>, NpyError> {
+        let descr = encode_descr(self.dtype, self.big_endian)?;
+        let shape = match self.shape.as_slice() {
+            [dim] => format!("({dim},)"),
+            shape => {
+                let dims = shape.iter().map(|dim| dim.to_string()).collect::<Vec<_>>();
+                format!("({})", dims.join(", "))
+            }
+        };
+        let fortran_order = if self.fortran_order { "True" } else { "False" };
+        let mut header =
+            format!("{{'descr': '{descr}', 'fortran_order': {fortran_order}, 'shape': {shape}, }}");
+
+        // The version 1 stores the header length on 2 bytes, the version 2 on 4 bytes.
+        let (version, prefix_len) = if header.len() + 11 <= u16::MAX as usize {
+            (1, 10)
+        } else {
+            (2, 12)
+        };
+        let padding = (64 - (prefix_len + header.len() + 1) % 64) % 64;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        let mut bytes = Vec::with_capacity(prefix_len + header.len());
+        bytes.extend_from_slice(NPY_MAGIC);
+        bytes.extend_from_slice(&[version, 0]);
+        match version {
+            1 => bytes.extend_from_slice(&(header.len() as u16).to_le_bytes()),
+            _ => bytes.extend_from_slice(&(header.len() as u32).to_le_bytes()),
+        }
+        bytes.extend_from_slice(header.as_bytes());
+
+        Ok(bytes)
+    }
+}
+
+impl TensorData {
+    /// Reads tensor data from the content of a [NumPy](https://numpy.org/) `.npy` file.
+    ///
+    /// All the non-quantized data types are supported, in both row-major (C) and column-major
+    /// (Fortran) order and in any byte order. The values are converted to row-major order in native byte order.
+    pub fn from_npy(bytes: &[u8]) -> Result<Self, NpyError> {
+        let (header, offset) = NpyHeader::parse(bytes)?;
+
+        header.decode(&bytes[offset..])
+    }
+
+    /// Writes the tensor data to the content of a [NumPy](https://numpy.org/) `.npy` file.
+    ///
+    /// NumPy has no bfloat16 type, so bf16 values are written with the `bfloat16` descriptor, which
+    /// is understood by [from_npy](TensorData::from_npy). NumPy has no quantized type either, so
+    /// quantized values aren't supported and return [UnsupportedDType](NpyError::UnsupportedDType):
+    /// they should be dequantized first.
+    pub fn to_npy(&self) -> Result<Vec<u8>, NpyError> {
+        let header = NpyHeader {
+            dtype: self.dtype,
+            shape: self.shape.clone(),
+            fortran_order: false,
+            big_endian: cfg!(target_endian = "big"),
+        };
+
+        let mut bytes = header.encode()?;
+        bytes.extend_from_slice(&self.bytes);
+
+        Ok(bytes)
+    }
+
+    /// Reads tensor data from a [NumPy](https://numpy.org/) `.npy` file.
+    ///
+    /// The data types are the ones supported by [from_npy](TensorData::from_npy), so quantized
+    /// data is never read.
+    #[cfg(feature = "std")]
+    pub fn read_npy<P: AsRef<std::path::Path>>(path: P) -> Result<Self, NpyError> {
+        let mut bytes = std::fs::read(path)?;
+        let (header, offset) = NpyHeader::parse(&bytes)?;
+        bytes.drain(..offset);
+
+        header.decode_owned(bytes)
+    }
+
+    /// Reads tensor data from a [NumPy](https://numpy.org/) `.npy` file mapped in memory, which
+    /// avoids reading the file into an intermediate buffer: the values are decoded directly from
+    /// the mapped file.
+    ///
+    /// The mapping only avoids the read buffer: the values are still copied once to the returned
+    /// tensor data, which owns its bytes and doesn't borrow the file.
+    ///
+    /// The file must not be modified while it is read.
+    #[cfg(feature = "memmap")]
+    pub fn read_npy_mmap<P: AsRef<std::path::Path>>(path: P) -> Result<Self, NpyError> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: The file is only read while mapped and the mapping is dropped before returning.
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let (header, offset) = NpyHeader::parse(&mmap)?;
+
+        header.decode(&mmap[offset..])
+    }
+
+    /// Writes the tensor data to a [NumPy](https://numpy.org/) `.npy` file.
+    ///
+    /// Quantized values aren't supported, as with [to_npy](TensorData::to_npy).
+    #[cfg(feature = "std")]
+    pub fn write_npy<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), NpyError> {
+        std::fs::write(path, self.to_npy()?)?;
+
+        Ok(())
+    }
+}
+
+/// Finds the raw value of a key of the Python dictionary literal of a header.
+fn dict_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let missing = || NpyError::InvalidHeader(format!("Missing key {key}"));
+
+    let start = header
+        .find(&format!("'{key}'"))
+        .or_else(|| header.find(&format!("\"{key}\"")))
+        .ok_or_else(missing)?
+        + key.len()
+        + 2;
+    let value = header[start..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(missing)?
+        .trim_start();
+
+    // The shape is a tuple containing commas, the other values end at the next comma.
+    let end = if value.starts_with('(') {
+        value.find(')').map(|end| end + 1)
+    } else {
+        value.find([',', '}'])
+    };
+
+    Ok(value[..end.unwrap_or(value.len())].trim())
+}
+
+/// Parses a NumPy array-protocol type string, e.g. `<f4`, returning the data type and whether
+/// it is big-endian.
+fn parse_descr(descr: &str) -> Result<(DType, bool), NpyError> {
+    if descr == "bfloat16" {
+        return Ok((DType::BF16, cfg!(target_endian = "big")));
+    }
+
+    let (big_endian, kind) = match descr.split_at(1) {
+        ("<", kind) => (false, kind),
+        (">", kind) => (true, kind),
+        ("|" | "=", kind) => (cfg!(target_endian = "big"), kind),
+        _ => (cfg!(target_endian = "big"), descr),
+    };
+
+    let dtype = match kind {
+        "f8" => DType::F64,
+        "f4" => DType::F32,
+        "f2" => DType::F16,
+        "i8" => DType::I64,
+        "i4" => DType::I32,
+        "i2" => DType::I16,
+        "i1" => DType::I8,
+        "u8" => DType::U64,
+        "u4" => DType::U32,
+        "u1" => DType::U8,
+        "b1" => DType::Bool,
+        _ => return Err(NpyError::UnsupportedDType(descr.to_string())),
+    };
+
+    Ok((dtype, big_endian))
+}
+
+fn encode_descr(dtype: DType, big_endian: bool) -> Result<String, NpyError> {
+    let kind = match dtype {
+        DType::F64 => "f8",
+        DType::F32 => "f4",
+        DType::F16 => "f2",
+        DType::BF16 => return Ok("bfloat16".to_string()),
+        DType::I64 => "i8",
+        DType::I32 => "i4",
+        DType::I16 => "i2",
+        DType::I8 => "i1",
+        DType::U64 => "u8",
+        DType::U32 => "u4",
+        DType::U8 => "u1",
+        DType::Bool => "b1",
+        DType::QFloat(_) => return Err(NpyError::UnsupportedDType(format!("{dtype:?}"))),
+    };
+
+    let byte_order = match dtype_size(dtype) {
+        1 => '|',
+        _ if big_endian => '>',
+        _ => '<',
+    };
+
+    Ok(format!("{byte_order}{kind}"))
+}
+
+/// The number of bytes of an element of the data type.
+fn dtype_size(dtype: DType) -> usize {
+    match dtype {
+        DType::F64 | DType::I64 | DType::U64 => 8,
+        DType::F32 | DType::I32 | DType::U32 => 4,
+        DType::F16 | DType::BF16 | DType::I16 => 2,
+        DType::I8 | DType::U8 | DType::Bool => 1,
+        DType::QFloat(_) => 1,
+    }
+}
+
+/// Reorders the elements of a column-major array to row-major order.
+fn fortran_to_c_order(data: &[u8], shape: &[usize], elem_size: usize) -> Vec<u8> {
+    let mut strides = vec![1; shape.len()];
+    for i in 1..shape.len() {
+        strides[i] = strides[i - 1] * shape[i - 1];
+    }
+
+    let num_elements = shape.iter().product::<usize>();
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut index = vec![0; shape.len()];
+
+    for _ in 0..num_elements {
+        let offset = index
+            .iter()
+            .zip(strides.iter())
+            .map(|(i, stride)| i * stride)
+            .sum::<usize>()
+            * elem_size;
+        bytes.extend_from_slice(&data[offset..offset + elem_size]);
+
+        // Increment the row-major index, the last dimension varying the fastest.
+        for dim in (0..shape.len()).rev() {
+            index[dim] += 1;
+            if index[dim] < shape[dim] {
+                break;
+            }
+            index[dim] = 0;
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantization::{QuantizationStrategy, SymmetricQuantization};
+    use half::{bf16, f16};
+
+    /// Builds the content of a `.npy` file the same way as `np.save`.
+    fn npy(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '{descr}', 'fortran_order': {}, 'shape': {shape}, }}",
+            if fortran_order { "True" } else { "False" }
+        );
+        while (header.len() + 11) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn should_read_c_order_npy() {
+        let data = [0i16, 1, 2, 3, 4, 5]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let tensor = TensorData::from_npy(&npy("<i2", false, "(2, 3)", &data)).unwrap();
+
+        assert_eq!(tensor, TensorData::new(vec![0i16, 1, 2, 3, 4, 5], [2, 3]));
+    }
+
+    #[test]
+    fn should_read_fortran_order_big_endian_npy() {
+        // The column-major values of [[0, 1, 2], [3, 4, 5]].
+        let data = [0f32, 3., 1., 4., 2., 5.]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect::<Vec<_>>();
+
+        let tensor = TensorData::from_npy(&npy(">f4", true, "(2, 3)", &data)).unwrap();
+
+        assert_eq!(
+            tensor,
+            TensorData::new(vec![0f32, 1., 2., 3., 4., 5.], [2, 3])
+        );
+    }
+
+    #[test]
+    fn should_round_trip_all_dtypes() {
+        let tensors = [
+            TensorData::new(vec![1.5f64, -2.0], [2]),
+            TensorData::new(vec![1.5f32, -2.0, 3.0, 4.0], [2, 1, 2]),
+            TensorData::new(vec![f16::from_f32(1.5), f16::from_f32(-2.0)], [1, 2]),
+            TensorData::new(vec![bf16::from_f32(1.5), bf16::from_f32(-2.0)], [2, 1]),
+            TensorData::new(vec![i64::MIN, i64::MAX], [2]),
+            TensorData::new(vec![i32::MIN, i32::MAX], [2]),
+            TensorData::new(vec![i16::MIN, i16::MAX], [2]),
+            TensorData::new(vec![i8::MIN, i8::MAX], [2]),
+            TensorData::new(vec![u64::MAX, 0], [2]),
+            TensorData::new(vec![u32::MAX, 0], [2]),
+            TensorData::new(vec![u8::MAX, 0], [2]),
+            TensorData::new(vec![true, false, true], [3]),
+            TensorData::new(vec![42i32], []),
+        ];
+
+        for tensor in tensors {
+            let bytes = tensor.to_npy().unwrap();
+
+            assert_eq!(bytes[..6], NPY_MAGIC[..]);
+            // The values start at a multiple of 64 bytes.
+            assert_eq!((bytes.len() - tensor.bytes.len()) % 64, 0);
+            assert_eq!(TensorData::from_npy(&bytes).unwrap(), tensor);
+        }
+    }
+
+    #[test]
+    fn should_encode_header_like_numpy() {
+        let bytes = TensorData::new(vec![0f32; 3], [3]).to_npy().unwrap();
+        let header = core::str::from_utf8(&bytes[10..bytes.len() - 12]).unwrap();
+
+        assert_eq!(
+            header.trim_end(),
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }"
+        );
+    }
+
+    #[test]
+    fn should_fail_on_invalid_data() {
+        let data = [0u8; 4];
+
+        assert!(matches!(
+            TensorData::from_npy(&data),
+            Err(NpyError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            TensorData::from_npy(&npy("<c8", false, "(1,)", &[0; 8])),
+            Err(NpyError::UnsupportedDType(_))
+        ));
+        assert!(matches!(
+            TensorData::from_npy(&npy("<i4", false, "(2,)", &[0; 4])),
+            Err(NpyError::InvalidSize {
+                expected: 8,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn should_fail_on_quantized_data() {
+        let data = TensorData::quantized(
+            vec![0i8, 127],
+            [2],
+            QuantizationStrategy::PerTensorSymmetricInt8(SymmetricQuantization::init(0.1)),
+        );
+
+        assert!(matches!(data.to_npy(), Err(NpyError::UnsupportedDType(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_read_npy_files() {
+        let data = [0f32, 3., 1., 4., 2., 5.]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect::<Vec<_>>();
+        let expected = TensorData::new(vec![0f32, 1., 2., 3., 4., 5.], [2, 3]);
+        let path = std::env::temp_dir().join(format!("burn-npy-{}.npy", std::process::id()));
+
+        for (fortran_order, data) in [(true, data), (false, expected.to_npy().unwrap())] {
+            let bytes = match fortran_order {
+                true => npy(">f4", true, "(2, 3)", &data),
+                false => data,
+            };
+            std::fs::write(&path, bytes).unwrap();
+
+            assert_eq!(TensorData::read_npy(&path).unwrap(), expected);
+            #[cfg(feature = "memmap")]
+            assert_eq!(TensorData::read_npy_mmap(&path).unwrap(), expected);
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_display_errors() {
+        let error = NpyError::InvalidSize {
+            expected: 8,
+            actual: 4,
+        };
+
+        assert_eq!(error.to_string(), "Expected 8 bytes of npy data, got 4");
+    }
+}
@@ -0,0 +1,387 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use num_traits::Float;
+
+use crate::backend::Backend;
+use crate::{Int, Tensor, TensorData};
+
+fn float_tensor<B: Backend, const D: usize>(
+    values: Vec<f32>,
+    shape: [usize; D],
+    device: &B::Device,
+) -> Tensor<B, D> {
+    Tensor::from_data(
+        TensorData::new(values, shape).convert::<B::FloatElem>(),
+        device,
+    )
+}
+
+fn int_tensor<B: Backend>(indices: Vec<i64>, device: &B::Device) -> Tensor<B, 1, Int> {
+    let len = indices.len();
+
+    Tensor::from_data(
+        TensorData::new(indices, [len]).convert::<B::IntElem>(),
+        device,
+    )
+}
+
+/// Multiplies the last dimension of a tensor of shape `[batch, n, k]` by a matrix of shape `[k, m]`.
+fn matmul_last<B: Backend>(tensor: Tensor<B, 3>, matrix: Tensor<B, 2>) -> Tensor<B, 3> {
+    let [batch, n, k] = tensor.dims();
+    let [_, m] = matrix.dims();
+
+    tensor
+        .reshape([batch * n, k])
+        .matmul(matrix)
+        .reshape([batch, n, m])
+}
+
+fn periodic_window<B: Backend>(
+    size: usize,
+    device: &B::Device,
+    coefficient: impl Fn(f64) -> f64,
+) -> Tensor<B, 1> {
+    let values = (0..size)
+        .map(|n| coefficient(2.0 * PI * n as f64 / size as f64) as f32)
+        .collect();
+
+    float_tensor(values, [size], device)
+}
+
+/// Creates a periodic Hann window, `0.5 - 0.5 * cos(2πn / size)`.
+pub fn hann_window<B: Backend>(size: usize, device: &B::Device) -> Tensor<B, 1> {
+    periodic_window(size, device, |phase| 0.5 - 0.5 * Float::cos(phase))
+}
+
+/// Creates a periodic Hamming window, `0.54 - 0.46 * cos(2πn / size)`.
+pub fn hamming_window<B: Backend>(size: usize, device: &B::Device) -> Tensor<B, 1> {
+    periodic_window(size, device, |phase| 0.54 - 0.46 * Float::cos(phase))
+}
+
+/// Index of the sample at the given position of a signal reflected at its boundaries.
+fn reflect(position: isize, len: usize) -> i64 {
+    if len == 1 {
+        return 0;
+    }
+
+    let period = 2 * (len as isize - 1);
+    let position = position.rem_euclid(period);
+
+    match position < len as isize {
+        true => position as i64,
+        false => (period - position) as i64,
+    }
+}
+
+/// Splits signals into overlapping frames.
+///
+/// # Arguments
+///
+/// * `signal` - The signals of shape `[batch_size, num_samples]`.
+/// * `frame_length` - The number of samples of each frame.
+/// * `hop_length` - The number of samples between the starts of consecutive frames.
+/// * `center` - Whether to pad the signals by reflection with `frame_length / 2` samples on both
+///   sides, so that the frame `t` is centered on the sample `t * hop_length`.
+///
+/// # Returns
+///
+/// The frames of shape `[batch_size, num_frames, frame_length]`.
+pub fn frame<B: Backend>(
+    signal: Tensor<B, 2>,
+    frame_length: usize,
+    hop_length: usize,
+    center: bool,
+) -> Tensor<B, 3> {
+    let [batch_size, num_samples] = signal.dims();
+    let pad = match center {
+        true => frame_length / 2,
+        false => 0,
+    };
+    let padded_len = num_samples + 2 * pad;
+    assert!(
+        num_samples > 0 && padded_len >= frame_length,
+        "The signal of {num_samples} samples is shorter than a frame of {frame_length} samples"
+    );
+    let num_frames = 1 + (padded_len - frame_length) / hop_length;
+
+    let indices = (0..num_frames)
+        .flat_map(|t| {
+            (0..frame_length).map(move |n| {
+                let position = (t * hop_length + n) as isize - pad as isize;
+                reflect(position, num_samples)
+            })
+        })
+        .collect();
+    let indices = int_tensor::<B>(indices, &signal.device());
+
+    signal
+        .select(1, indices)
+        .reshape([batch_size, num_frames, frame_length])
+}
+
+/// Short-time Fourier transform of signals.
+///
+/// The Fourier transform of the frames is computed as a matrix multiplication, which runs on any
+/// backend and is differentiable.
+///
+/// # Arguments
+///
+/// * `signal` - The signals of shape `[batch_size, num_samples]`.
+/// * `n_fft` - The size of the Fourier transform, which is also the length of the frames.
+/// * `hop_length` - The number of samples between the starts of consecutive frames.
+/// * `window` - The window of shape `[n_fft]` applied to the frames, e.g. [hann_window].
+/// * `center` - Whether to center the frames on the samples `t * hop_length` (see [frame]).
+///
+/// # Returns
+///
+/// The real and imaginary parts of the `n_fft / 2 + 1` frequency bins of each frame, of shape
+/// `[batch_size, num_frames, n_fft / 2 + 1]`.
+pub fn stft<B: Backend>(
+    signal: Tensor<B, 2>,
+    n_fft: usize,
+    hop_length: usize,
+    window: Tensor<B, 1>,
+    center: bool,
+) -> (Tensor<B, 3>, Tensor<B, 3>) {
+    let device = signal.device();
+    let n_freqs = n_fft / 2 + 1;
+    let frames = frame(signal, n_fft, hop_length, center) * window.reshape([1, 1, n_fft]);
+
+    let mut cos = Vec::with_capacity(n_fft * n_freqs);
+    let mut sin = Vec::with_capacity(n_fft * n_freqs);
+    for n in 0..n_fft {
+        for k in 0..n_freqs {
+            let angle = -2.0 * PI * ((n * k) % n_fft) as f64 / n_fft as f64;
+            cos.push(Float::cos(angle) as f32);
+            sin.push(Float::sin(angle) as f32);
+        }
+    }
+    let cos = float_tensor::<B, 2>(cos, [n_fft, n_freqs], &device);
+    let sin = float_tensor::<B, 2>(sin, [n_fft, n_freqs], &device);
+
+    (matmul_last(frames.clone(), cos), matmul_last(frames, sin))
+}
+
+/// Power spectrogram of signals, the squared magnitude of their [STFT](stft).
+///
+/// # Returns
+///
+/// The power of the `n_fft / 2 + 1` frequency bins of each frame, of shape
+/// `[batch_size, num_frames, n_fft / 2 + 1]`.
+pub fn spectrogram<B: Backend>(
+    signal: Tensor<B, 2>,
+    n_fft: usize,
+    hop_length: usize,
+    window: Tensor<B, 1>,
+    center: bool,
+) -> Tensor<B, 3> {
+    let (real, imaginary) = stft(signal, n_fft, hop_length, window, center);
+
+    real.powf_scalar(2.0) + imaginary.powf_scalar(2.0)
+}
+
+fn hz_to_mel(frequency: f64) -> f64 {
+    2595.0 * Float::log10(1.0 + frequency / 700.0)
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    700.0 * (Float::powf(10.0, mel / 2595.0) - 1.0)
+}
+
+/// Triangular mel filterbank, with filters equally spaced on the HTK mel scale.
+///
+/// # Arguments
+///
+/// * `n_freqs` - The number of frequency bins of the spectrogram, `n_fft / 2 + 1`.
+/// * `sample_rate` - The sample rate of the signals, in Hz.
+/// * `n_mels` - The number of mel filters.
+/// * `f_min` - The lowest frequency, in Hz.
+/// * `f_max` - The highest frequency, in Hz.
+/// * `device` - The device of the filterbank.
+///
+/// # Returns
+///
+/// The weight of each frequency bin for each filter, of shape `[n_freqs, n_mels]`.
+pub fn mel_filterbank<B: Backend>(
+    n_freqs: usize,
+    sample_rate: usize,
+    n_mels: usize,
+    f_min: f32,
+    f_max: f32,
+    device: &B::Device,
+) -> Tensor<B, 2> {
+    let nyquist = sample_rate as f64 / 2.0;
+    let (mel_min, mel_max) = (hz_to_mel(f_min as f64), hz_to_mel(f_max as f64));
+    let points = (0..n_mels + 2)
+        .map(|i| mel_to_hz(mel_min + (mel_max - mel_min) * i as f64 / (n_mels + 1) as f64))
+        .collect::<Vec<_>>();
+
+    let mut weights = Vec::with_capacity(n_freqs * n_mels);
+    for bin in 0..n_freqs {
+        let frequency = match n_freqs {
+            1 => 0.0,
+            _ => nyquist * bin as f64 / (n_freqs - 1) as f64,
+        };
+
+        for m in 0..n_mels {
+            let rising = (frequency - points[m]) / (points[m + 1] - points[m]);
+            let falling = (points[m + 2] - frequency) / (points[m + 2] - points[m + 1]);
+            weights.push(rising.min(falling).max(0.0) as f32);
+        }
+    }
+
+    float_tensor(weights, [n_freqs, n_mels], device)
+}
+
+/// Mel spectrogram, the power spectrogram filtered by a [mel filterbank](mel_filterbank).
+///
+/// # Arguments
+///
+/// * `spectrogram` - The power [spectrogram](spectrogram) of shape
+///   `[batch_size, num_frames, n_freqs]`.
+/// * `filterbank` - The mel filterbank of shape `[n_freqs, n_mels]`.
+///
+/// # Returns
+///
+/// The mel spectrogram of shape `[batch_size, num_frames, n_mels]`.
+pub fn mel_spectrogram<B: Backend>(
+    spectrogram: Tensor<B, 3>,
+    filterbank: Tensor<B, 2>,
+) -> Tensor<B, 3> {
+    matmul_last(spectrogram, filterbank)
+}
+
+/// Converts power features to decibels, `10 * log10(max(x, 1e-10))`.
+///
+/// # Arguments
+///
+/// * `features` - The power features of shape `[batch_size, num_frames, num_features]`.
+/// * `top_db` - The maximum range of the decibels of each item of the batch, the lower values
+///   being clamped to `max - top_db`.
+pub fn power_to_db<B: Backend>(features: Tensor<B, 3>, top_db: Option<f32>) -> Tensor<B, 3> {
+    let decibels = features
+        .clamp_min(1e-10)
+        .log()
+        .mul_scalar(10.0 / core::f32::consts::LN_10);
+
+    match top_db {
+        Some(top_db) => {
+            let shape = decibels.shape();
+            let min = decibels
+                .clone()
+                .max_dim(2)
+                .max_dim(1)
+                .sub_scalar(top_db)
+                .expand(shape);
+
+            decibels.max_pair(min)
+        }
+        None => decibels,
+    }
+}
+
+/// Mel-frequency cepstral coefficients of log-mel features, their orthonormal DCT-II.
+///
+/// # Arguments
+///
+/// * `log_mel` - The log-mel features of shape `[batch_size, num_frames, n_mels]`, e.g. the
+///   [mel spectrogram](mel_spectrogram) in [decibels](power_to_db).
+/// * `n_mfcc` - The number of coefficients to keep.
+///
+/// # Returns
+///
+/// The coefficients of shape `[batch_size, num_frames, n_mfcc]`.
+pub fn mfcc<B: Backend>(log_mel: Tensor<B, 3>, n_mfcc: usize) -> Tensor<B, 3> {
+    let [_, _, n_mels] = log_mel.dims();
+
+    let mut dct = Vec::with_capacity(n_mels * n_mfcc);
+    for n in 0..n_mels {
+        for k in 0..n_mfcc {
+            let scale = match k {
+                0 => Float::sqrt(1.0 / n_mels as f64),
+                _ => Float::sqrt(2.0 / n_mels as f64),
+            };
+            let angle = PI / n_mels as f64 * (n as f64 + 0.5) * k as f64;
+            dct.push((scale * Float::cos(angle)) as f32);
+        }
+    }
+    let dct = float_tensor::<B, 2>(dct, [n_mels, n_mfcc], &log_mel.device());
+
+    matmul_last(log_mel, dct)
+}
+
+/// Width of the windowed sinc filter used to resample, in zero crossings on each side.
+const RESAMPLE_ZERO_CROSSINGS: f64 = 6.0;
+
+/// Cutoff of the resampling filter relative to the Nyquist frequency, reducing aliasing.
+const RESAMPLE_ROLLOFF: f64 = 0.99;
+
+/// Resamples signals to another sample rate with band-limited (Hann windowed sinc) interpolation.
+///
+/// # Arguments
+///
+/// * `signal` - The signals of shape `[batch_size, num_samples]`.
+/// * `orig_rate` - The sample rate of the signals, in Hz.
+/// * `new_rate` - The sample rate of the resampled signals, in Hz.
+///
+/// # Returns
+///
+/// The resampled signals of shape `[batch_size, ceil(num_samples * new_rate / orig_rate)]`.
+pub fn resample<B: Backend>(
+    signal: Tensor<B, 2>,
+    orig_rate: usize,
+    new_rate: usize,
+) -> Tensor<B, 2> {
+    if orig_rate == new_rate {
+        return signal;
+    }
+
+    let device = signal.device();
+    let [batch_size, num_samples] = signal.dims();
+    let num_outputs = (num_samples * new_rate).div_ceil(orig_rate);
+    // Lowpass below the lowest of the two Nyquist frequencies, in cycles per input sample.
+    let cutoff = RESAMPLE_ROLLOFF * (new_rate as f64 / orig_rate as f64).min(1.0);
+    let half_width = Float::ceil(RESAMPLE_ZERO_CROSSINGS / cutoff) as isize;
+    let num_taps = (2 * half_width + 1) as usize;
+
+    // Each output sample is the weighted sum of its neighboring input samples, the input samples
+    // outside of the signals having a weight of zero.
+    let mut indices = Vec::with_capacity(num_outputs * num_taps);
+    let mut weights = Vec::with_capacity(num_outputs * num_taps);
+    for k in 0..num_outputs {
+        let time = (k * orig_rate) as f64 / new_rate as f64;
+        let offset = Float::floor(time) as isize - half_width;
+
+        for j in 0..num_taps as isize {
+            let position = offset + j;
+            let distance = (time - position as f64) * cutoff;
+            let inside = (0..num_samples as isize).contains(&position);
+
+            let weight = match inside && Float::abs(distance) <= RESAMPLE_ZERO_CROSSINGS {
+                true => {
+                    let window = Float::cos(PI * distance / (2.0 * RESAMPLE_ZERO_CROSSINGS));
+                    let sinc = match distance == 0.0 {
+                        true => 1.0,
+                        false => Float::sin(PI * distance) / (PI * distance),
+                    };
+                    cutoff * sinc * window * window
+                }
+                false => 0.0,
+            };
+
+            indices.push(position.clamp(0, num_samples as isize - 1) as i64);
+            weights.push(weight as f32);
+        }
+    }
+
+    let indices = int_tensor::<B>(indices, &device);
+    let weights = float_tensor::<B, 3>(weights, [1, num_outputs, num_taps], &device);
+
+    (signal
+        .select(1, indices)
+        .reshape([batch_size, num_outputs, num_taps])
+        * weights)
+        .sum_dim(2)
+        .reshape([batch_size, num_outputs])
+}
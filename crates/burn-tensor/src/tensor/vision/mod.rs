@@ -0,0 +1,360 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::backend::Backend;
+use crate::{Int, Tensor, TensorData};
+
+/// The format of the coordinates of bounding boxes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoxFormat {
+    /// The coordinates of two diagonal corners, `[y1, x1, y2, x2]`. Since the overlap of boxes
+    /// doesn't depend on the order of the axes, `[x1, y1, x2, y2]` boxes can be used as well.
+    #[default]
+    Corners,
+    /// The coordinates of the center followed by the size, `[x_center, y_center, width, height]`.
+    CenterSize,
+}
+
+/// Non-maximum suppression options.
+#[derive(new, Debug, Clone)]
+pub struct NmsOptions {
+    /// The format of the boxes.
+    pub box_format: BoxFormat,
+    /// Boxes overlapping a selected box with an intersection over union greater than this
+    /// threshold are suppressed.
+    pub iou_threshold: f32,
+    /// Only the boxes with a score greater than this threshold are selected.
+    pub score_threshold: Option<f32>,
+    /// The maximum number of boxes selected for each batch and class, all the boxes that aren't
+    /// suppressed being selected when `None`.
+    pub max_output_boxes_per_class: Option<usize>,
+}
+
+/// Pooling method of the samples of each output bin of [roi_align].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoiAlignMode {
+    /// Average of the bilinearly interpolated samples.
+    #[default]
+    Avg,
+    /// Maximum of the samples, where each sample is the maximum of its 4 weighted neighbors
+    /// (as in ONNX Runtime) rather than their interpolation.
+    Max,
+}
+
+/// RoI Align options.
+#[derive(new, Debug, Clone)]
+pub struct RoiAlignOptions {
+    /// The height and width of the pooled output of each region.
+    pub output_size: [usize; 2],
+    /// The scale from the coordinates of the regions to the coordinates of the input, e.g.
+    /// `1 / 16` for the features of a backbone with a stride of 16.
+    pub spatial_scale: f32,
+    /// The number of samples along each axis of the output bins, the number of samples being
+    /// `ceil(region_size / output_size)` when `0`.
+    pub sampling_ratio: usize,
+    /// Whether the pixels are shifted by `-0.5` to align them with the input (`half_pixel`), as
+    /// in Detectron2, instead of the legacy behavior of the original implementation.
+    pub aligned: bool,
+    /// The pooling method.
+    pub mode: RoiAlignMode,
+}
+
+fn float_values<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> Vec<f32> {
+    tensor.into_data().convert::<f32>().to_vec().unwrap()
+}
+
+fn int_values<B: Backend, const D: usize>(tensor: Tensor<B, D, Int>) -> Vec<i64> {
+    tensor.into_data().convert::<i64>().to_vec().unwrap()
+}
+
+/// Returns the `[y1, x1, y2, x2]` corners of a box, with `y1 <= y2` and `x1 <= x2`.
+fn box_corners(coordinates: &[f32], format: BoxFormat) -> [f32; 4] {
+    match format {
+        BoxFormat::Corners => [
+            coordinates[0].min(coordinates[2]),
+            coordinates[1].min(coordinates[3]),
+            coordinates[0].max(coordinates[2]),
+            coordinates[1].max(coordinates[3]),
+        ],
+        BoxFormat::CenterSize => {
+            let [x, y, width, height] = [
+                coordinates[0],
+                coordinates[1],
+                coordinates[2] / 2.0,
+                coordinates[3] / 2.0,
+            ];
+            [y - height, x - width, y + height, x + width]
+        }
+    }
+}
+
+fn intersection_over_union(lhs: &[f32; 4], rhs: &[f32; 4]) -> f32 {
+    let area = |corners: &[f32; 4]| (corners[2] - corners[0]) * (corners[3] - corners[1]);
+    let (lhs_area, rhs_area) = (area(lhs), area(rhs));
+    if lhs_area <= 0.0 || rhs_area <= 0.0 {
+        return 0.0;
+    }
+
+    let height = (lhs[2].min(rhs[2]) - lhs[0].max(rhs[0])).max(0.0);
+    let width = (lhs[3].min(rhs[3]) - lhs[1].max(rhs[1])).max(0.0);
+    let intersection = height * width;
+
+    intersection / (lhs_area + rhs_area - intersection)
+}
+
+/// Selects the boxes with the highest scores, greedily suppressing the boxes that overlap a
+/// selected box of the same class.
+///
+/// The selection depends on the values of the boxes and scores, which are read back from the
+/// device.
+///
+/// # Arguments
+///
+/// * `boxes` - The boxes of shape `[batch_size, num_boxes, 4]`.
+/// * `scores` - The scores of each class of the boxes, of shape `[batch_size, num_classes, num_boxes]`.
+/// * `options` - The non-maximum suppression options.
+///
+/// # Returns
+///
+/// The `[batch_index, class_index, box_index]` of the selected boxes, of shape
+/// `[num_selected, 3]`. The boxes of each batch and class are sorted by decreasing score.
+pub fn non_max_suppression<B: Backend>(
+    boxes: Tensor<B, 3>,
+    scores: Tensor<B, 3>,
+    options: NmsOptions,
+) -> Tensor<B, 2, Int> {
+    let device = boxes.device();
+    let [batch_size, num_boxes, _] = boxes.dims();
+    let [_, num_classes, _] = scores.dims();
+    assert_eq!(
+        scores.dims(),
+        [batch_size, num_classes, num_boxes],
+        "The scores should be of shape [batch_size, num_classes, num_boxes]"
+    );
+
+    let corners = float_values(boxes)
+        .chunks(4)
+        .map(|coordinates| box_corners(coordinates, options.box_format))
+        .collect::<Vec<_>>();
+    let scores = float_values(scores);
+    let max_selected = options.max_output_boxes_per_class.unwrap_or(num_boxes);
+
+    let mut selected = Vec::new();
+    for batch in 0..batch_size {
+        let corners = &corners[batch * num_boxes..(batch + 1) * num_boxes];
+
+        for class in 0..num_classes {
+            let offset = (batch * num_classes + class) * num_boxes;
+            let scores = &scores[offset..offset + num_boxes];
+
+            let mut candidates = (0..num_boxes)
+                .filter(|&index| match options.score_threshold {
+                    Some(threshold) => scores[index] > threshold,
+                    None => true,
+                })
+                .collect::<Vec<_>>();
+            // The sort is stable, so boxes with the same score keep their order.
+            candidates.sort_by(|&lhs, &rhs| scores[rhs].total_cmp(&scores[lhs]));
+
+            let mut kept: Vec<usize> = Vec::new();
+            for candidate in candidates {
+                if kept.len() >= max_selected {
+                    break;
+                }
+
+                let suppressed = kept.iter().any(|&index| {
+                    intersection_over_union(&corners[candidate], &corners[index])
+                        > options.iou_threshold
+                });
+                if !suppressed {
+                    kept.push(candidate);
+                }
+            }
+
+            selected.extend(
+                kept.into_iter()
+                    .flat_map(|index| [batch as i64, class as i64, index as i64]),
+            );
+        }
+    }
+
+    let num_selected = selected.len() / 3;
+    Tensor::from_data(
+        TensorData::new(selected, [num_selected, 3]).convert::<B::IntElem>(),
+        &device,
+    )
+}
+
+/// A sample point of a region, as the 4 neighbors of the point with their bilinear weights.
+type Sample = [(usize, f32); 4];
+
+/// Computes the bilinear neighbors of the point `(y, x)`, as in ONNX Runtime and Detectron2.
+fn bilinear_sample(y: f32, x: f32, height: usize, width: usize) -> Sample {
+    if y < -1.0 || y > height as f32 || x < -1.0 || x > width as f32 {
+        return [(0, 0.0); 4];
+    }
+
+    let neighbors = |position: f32, size: usize| {
+        let position = position.max(0.0);
+        let low = position as usize;
+        match low >= size - 1 {
+            true => (size - 1, size - 1, 0.0),
+            false => (low, low + 1, position - low as f32),
+        }
+    };
+    let (y_low, y_high, ly) = neighbors(y, height);
+    let (x_low, x_high, lx) = neighbors(x, width);
+    let (hy, hx) = (1.0 - ly, 1.0 - lx);
+
+    [
+        (y_low * width + x_low, hy * hx),
+        (y_low * width + x_high, hy * lx),
+        (y_high * width + x_low, ly * hx),
+        (y_high * width + x_high, ly * lx),
+    ]
+}
+
+/// Pools the features of regions of interest into fixed-size outputs, bilinearly interpolating
+/// the features at regularly spaced sample points of each output bin ([Mask R-CNN](https://arxiv.org/abs/1703.06870)).
+///
+/// The sample points depend on the values of the regions, which are read back from the device,
+/// while the output is differentiable with respect to the input.
+///
+/// # Arguments
+///
+/// * `input` - The features of shape `[batch_size, channels, height, width]`.
+/// * `rois` - The `[x1, y1, x2, y2]` regions of shape `[num_rois, 4]`, in the coordinates of the
+///   image the input was computed from (see [spatial_scale](RoiAlignOptions::spatial_scale)).
+/// * `batch_indices` - The index of the batch of each region, of shape `[num_rois]`.
+/// * `options` - The RoI Align options.
+///
+/// # Returns
+///
+/// The pooled features of shape `[num_rois, channels, output_height, output_width]`.
+pub fn roi_align<B: Backend>(
+    input: Tensor<B, 4>,
+    rois: Tensor<B, 2>,
+    batch_indices: Tensor<B, 1, Int>,
+    options: RoiAlignOptions,
+) -> Tensor<B, 4> {
+    let device = input.device();
+    let [batch_size, channels, height, width] = input.dims();
+    let [num_rois, _] = rois.dims();
+    let [output_height, output_width] = options.output_size;
+    let num_bins = output_height * output_width;
+
+    let rois = float_values(rois);
+    let batch_indices = int_values(batch_indices);
+    let offset = match options.aligned {
+        true => 0.5,
+        false => 0.0,
+    };
+
+    // The samples of each output bin, and the number of samples of each region.
+    let mut bins: Vec<Vec<Sample>> = Vec::with_capacity(num_rois * num_bins);
+    let mut counts = Vec::with_capacity(num_rois);
+    for (roi, &batch) in rois.chunks(4).zip(batch_indices.iter()) {
+        assert!(
+            (batch as usize) < batch_size,
+            "The batch index {batch} of a region is out of bounds"
+        );
+        let [start_x, start_y, end_x, end_y] =
+            [roi[0], roi[1], roi[2], roi[3]].map(|value| value * options.spatial_scale - offset);
+        let (mut roi_height, mut roi_width) = (end_y - start_y, end_x - start_x);
+        if !options.aligned {
+            roi_height = roi_height.max(1.0);
+            roi_width = roi_width.max(1.0);
+        }
+
+        let bin_height = roi_height / output_height as f32;
+        let bin_width = roi_width / output_width as f32;
+        let (grid_height, grid_width) = match options.sampling_ratio {
+            0 => (
+                Float::ceil(bin_height).max(0.0) as usize,
+                Float::ceil(bin_width).max(0.0) as usize,
+            ),
+            ratio => (ratio, ratio),
+        };
+        counts.push((grid_height * grid_width).max(1) as f32);
+
+        let batch_offset = batch as usize * height * width;
+        for bin_y in 0..output_height {
+            for bin_x in 0..output_width {
+                let mut samples = Vec::with_capacity(grid_height * grid_width);
+                for iy in 0..grid_height {
+                    let y = start_y
+                        + bin_y as f32 * bin_height
+                        + (iy as f32 + 0.5) * bin_height / grid_height as f32;
+                    for ix in 0..grid_width {
+                        let x = start_x
+                            + bin_x as f32 * bin_width
+                            + (ix as f32 + 0.5) * bin_width / grid_width as f32;
+                        let sample = bilinear_sample(y, x, height, width)
+                            .map(|(index, weight)| (batch_offset + index, weight));
+                        samples.push(sample);
+                    }
+                }
+                bins.push(samples);
+            }
+        }
+    }
+
+    // Every bin is padded to the same number of samples, with samples that don't change the
+    // pooled value: zero weights for the average, and a copy of a sample for the maximum.
+    let num_samples = bins.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let mut indices = Vec::with_capacity(bins.len() * num_samples * 4);
+    let mut weights = Vec::with_capacity(bins.len() * num_samples * 4);
+    for samples in bins.iter() {
+        let padding = match (options.mode, samples.first()) {
+            (RoiAlignMode::Max, Some(sample)) => *sample,
+            _ => [(0, 0.0); 4],
+        };
+        let padding = vec![padding; num_samples - samples.len()];
+
+        for (index, weight) in samples.iter().chain(padding.iter()).flatten() {
+            indices.push(*index as i64);
+            weights.push(*weight);
+        }
+    }
+
+    let num_values = indices.len();
+    let indices = Tensor::<B, 1, Int>::from_data(
+        TensorData::new(indices, [num_values]).convert::<B::IntElem>(),
+        &device,
+    );
+    let weights = Tensor::<B, 1>::from_data(
+        TensorData::new(weights, [num_values]).convert::<B::FloatElem>(),
+        &device,
+    );
+
+    // [channels, num_rois * num_bins * num_samples * 4]
+    let values = input
+        .swap_dims(0, 1)
+        .reshape([channels, batch_size * height * width])
+        .select(1, indices)
+        * weights.unsqueeze();
+    let values = values.reshape([
+        channels,
+        num_rois,
+        output_height,
+        output_width,
+        num_samples * 4,
+    ]);
+
+    let output = match options.mode {
+        RoiAlignMode::Avg => {
+            let counts = Tensor::<B, 1>::from_data(
+                TensorData::new(counts, [num_rois]).convert::<B::FloatElem>(),
+                &device,
+            );
+            values.sum_dim(4) / counts.reshape([1, num_rois, 1, 1, 1])
+        }
+        RoiAlignMode::Max => values.max_dim(4),
+    };
+
+    output
+        .reshape([channels, num_rois, output_height, output_width])
+        .swap_dims(0, 1)
+}
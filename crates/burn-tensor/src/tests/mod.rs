@@ -3,7 +3,9 @@ mod clone_invariance;
 mod module;
 mod ops;
 mod quantization;
+mod signal;
 mod stats;
+mod vision;
 
 #[allow(missing_docs)]
 #[macro_export]
@@ -61,6 +63,7 @@ macro_rules! testgen_all {
         burn_tensor::testgen_flatten!();
         burn_tensor::testgen_full!();
         burn_tensor::testgen_gather_scatter!();
+        burn_tensor::testgen_gather_scatter_nd!();
         burn_tensor::testgen_init!();
         burn_tensor::testgen_iter_dim!();
         burn_tensor::testgen_log!();
@@ -110,6 +113,15 @@ macro_rules! testgen_all {
         burn_tensor::testgen_floor!();
         burn_tensor::testgen_ceil!();
 
+        // test signal
+        burn_tensor::testgen_signal_stft!();
+        burn_tensor::testgen_signal_mel!();
+        burn_tensor::testgen_signal_resample!();
+
+        // test vision
+        burn_tensor::testgen_vision_nms!();
+        burn_tensor::testgen_vision_roi_align!();
+
         // test stats
         burn_tensor::testgen_var!();
         burn_tensor::testgen_cov!();
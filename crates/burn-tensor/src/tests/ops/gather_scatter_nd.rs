@@ -0,0 +1,71 @@
+#[burn_tensor_testgen::testgen(gather_scatter_nd)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Tensor, TensorData};
+
+    #[test]
+    fn should_gather_nd_elements() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[0.0, 1.0], [2.0, 3.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0, 0], [1, 1], [1, 0]], &device);
+
+        let output = tensor.gather_nd::<2, 1>(indices);
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([0.0, 3.0, 2.0]), false);
+    }
+
+    #[test]
+    fn should_gather_nd_slices() {
+        let device = Default::default();
+        let tensor = TestTensorInt::<1>::arange(0..12, &device).reshape([2, 3, 2]);
+        let indices = TestTensorInt::<3>::from_ints([[[1, 2]], [[0, 1]]], &device);
+
+        let output = tensor.gather_nd::<3, 3>(indices);
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([[[10, 11]], [[2, 3]]]), false);
+    }
+
+    #[test]
+    fn should_scatter_nd_slices() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::zeros([3, 2], &device);
+        let indices = TestTensorInt::<2>::from_ints([[2], [0], [2]], &device);
+        let values = TestTensor::<2>::from_floats([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]], &device);
+
+        let output = tensor.scatter_nd(indices, values);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[3.0, 4.0], [0.0, 0.0], [6.0, 8.0]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn should_scatter_nd_elements() {
+        let device = Default::default();
+        let tensor = TestTensorInt::<3>::zeros([2, 2, 2], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0, 1, 0], [1, 0, 1]], &device);
+        let values = TestTensorInt::<1>::from_ints([7, 9], &device);
+
+        let output = tensor.scatter_nd(indices, values);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[[0, 0], [7, 0]], [[0, 9], [0, 0]]]),
+            false,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_gather_nd_output_rank_is_wrong() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::zeros([2, 2], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0, 0]], &device);
+
+        let _output = tensor.gather_nd::<2, 2>(indices);
+    }
+}
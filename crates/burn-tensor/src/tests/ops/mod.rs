@@ -26,6 +26,7 @@ mod flip;
 mod floor;
 mod full;
 mod gather_scatter;
+mod gather_scatter_nd;
 mod init;
 mod iter_dim;
 mod log;
@@ -24,6 +24,15 @@ mod tests {
         tensor.into_data().assert_eq(&expected, false);
     }
 
+    #[test]
+    fn should_support_one_hot_int() {
+        let device = Default::default();
+
+        let tensor = TestTensorInt::<1>::from_ints([2, 0, 3], &device).one_hot(5);
+        let expected = TensorData::from([[0, 0, 1, 0, 0], [1, 0, 0, 0, 0], [0, 0, 0, 1, 0]]);
+        tensor.into_data().assert_eq(&expected, false);
+    }
+
     #[test]
     #[should_panic]
     fn should_panic_when_index_exceeds_number_of_classes() {
@@ -0,0 +1,63 @@
+#[burn_tensor_testgen::testgen(signal_mel)]
+mod tests {
+    use super::*;
+    use burn_tensor::{signal, TensorData};
+
+    #[test]
+    fn should_create_triangular_mel_filterbank() {
+        let device = Default::default();
+
+        let filterbank = signal::mel_filterbank::<TestBackend>(5, 16000, 2, 0.0, 8000.0, &device);
+
+        assert_eq!(filterbank.dims(), [5, 2]);
+        let weights = filterbank
+            .into_data()
+            .convert::<f32>()
+            .to_vec::<f32>()
+            .unwrap();
+        // Every weight is in [0, 1], and the first bin is the start of the first filter.
+        assert!(weights.iter().all(|weight| (0.0..=1.0).contains(weight)));
+        assert_eq!(weights[0..2], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn should_convert_power_to_db() {
+        let device = Default::default();
+        let tensor = TestTensor::<3>::from_data([[[1.0, 10.0], [100.0, 0.0]]], &device);
+
+        let output = signal::power_to_db(tensor.clone(), None);
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[0.0, 10.0], [20.0, -100.0]]]), 3);
+
+        let output = signal::power_to_db(tensor, Some(15.0));
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[5.0, 10.0], [20.0, 5.0]]]), 3);
+    }
+
+    #[test]
+    fn should_compute_mfcc_of_constant_log_mel() {
+        let device = Default::default();
+        let tensor = TestTensor::<3>::full([1, 2, 16], 2.0, &device);
+
+        let output = signal::mfcc(tensor, 3);
+
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[8.0, 0.0, 0.0], [8.0, 0.0, 0.0]]]), 4);
+    }
+
+    #[test]
+    fn should_compute_mel_spectrogram() {
+        let device = Default::default();
+        let spectrogram = TestTensor::<3>::from_data([[[1.0, 2.0, 3.0]]], &device);
+        let filterbank = TestTensor::<2>::from_data([[1.0, 0.0], [0.5, 0.5], [0.0, 1.0]], &device);
+
+        let output = signal::mel_spectrogram(spectrogram, filterbank);
+
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[2.0, 4.0]]]), 5);
+    }
+}
@@ -0,0 +1,3 @@
+mod mel;
+mod resample;
+mod stft;
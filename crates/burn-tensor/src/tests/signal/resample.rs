@@ -0,0 +1,42 @@
+#[burn_tensor_testgen::testgen(signal_resample)]
+mod tests {
+    use super::*;
+    use burn_tensor::{signal, TensorData};
+
+    fn sine(frequency: f32, sample_rate: usize, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| {
+                (2.0 * core::f32::consts::PI * frequency * n as f32 / sample_rate as f32).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_resample_keeping_frequency() {
+        let device = Default::default();
+        let samples = sine(100.0, 1600, 320);
+        let tensor = TestTensor::<2>::from_data(TensorData::new(samples, [1, 320]), &device);
+
+        let output = signal::resample(tensor, 1600, 800);
+
+        assert_eq!(output.dims(), [1, 160]);
+        // Away from the boundaries, the resampled signal matches the signal sampled at 800 Hz.
+        let expected = sine(100.0, 800, 160);
+        output
+            .slice([0..1, 20..140])
+            .into_data()
+            .assert_approx_eq(&TensorData::new(expected[20..140].to_vec(), [1, 120]), 2);
+    }
+
+    #[test]
+    fn should_not_resample_same_rate() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_data([[1.0, 2.0, 3.0]], &device);
+
+        let output = signal::resample(tensor, 16000, 16000);
+
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[1.0, 2.0, 3.0]]), 5);
+    }
+}
@@ -0,0 +1,61 @@
+#[burn_tensor_testgen::testgen(signal_stft)]
+mod tests {
+    use super::*;
+    use burn_tensor::{signal, Tensor, TensorData};
+
+    #[test]
+    fn should_frame_with_reflection_padding() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_data([[1.0, 2.0, 3.0, 4.0, 5.0]], &device);
+
+        let output = signal::frame(tensor.clone(), 4, 2, true);
+        let expected = TensorData::from([[
+            [3.0, 2.0, 1.0, 2.0],
+            [1.0, 2.0, 3.0, 4.0],
+            [3.0, 4.0, 5.0, 4.0],
+        ]]);
+        output.into_data().assert_approx_eq(&expected, 5);
+
+        let output = signal::frame(tensor, 4, 2, false);
+        assert_eq!(output.dims(), [1, 1, 4]);
+    }
+
+    #[test]
+    fn should_compute_stft_of_cosine() {
+        let device = Default::default();
+        // A cosine of 2 cycles per 8 samples, with a rectangular window.
+        let samples = (0..8)
+            .map(|n| (core::f32::consts::PI * n as f32 / 2.0).cos())
+            .collect::<Vec<_>>();
+        let tensor = TestTensor::<2>::from_data(TensorData::new(samples, [1, 8]), &device);
+        let window = Tensor::<TestBackend, 1>::ones([8], &device);
+
+        let (real, imaginary) = signal::stft(tensor.clone(), 8, 8, window.clone(), false);
+
+        real.into_data()
+            .assert_approx_eq(&TensorData::from([[[0.0, 0.0, 4.0, 0.0, 0.0]]]), 4);
+        imaginary
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[0.0, 0.0, 0.0, 0.0, 0.0]]]), 4);
+
+        let power = signal::spectrogram(tensor, 8, 8, window, false);
+        power
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[[0.0, 0.0, 16.0, 0.0, 0.0]]]), 3);
+    }
+
+    #[test]
+    fn should_create_windows() {
+        let device = Default::default();
+
+        let output = signal::hann_window::<TestBackend>(4, &device);
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([0.0, 0.5, 1.0, 0.5]), 5);
+
+        let output = signal::hamming_window::<TestBackend>(4, &device);
+        output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([0.08, 0.54, 1.0, 0.54]), 5);
+    }
+}
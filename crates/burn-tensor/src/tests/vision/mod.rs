@@ -0,0 +1,2 @@
+mod nms;
+mod roi_align;
@@ -0,0 +1,94 @@
+#[burn_tensor_testgen::testgen(vision_nms)]
+mod tests {
+    use super::*;
+    use burn_tensor::vision::{self, BoxFormat, NmsOptions};
+    use burn_tensor::TensorData;
+
+    fn boxes() -> TestTensor<3> {
+        TestTensor::from_data(
+            [[
+                [0.0, 0.0, 1.0, 1.0],
+                [0.0, 0.1, 1.0, 1.1],
+                [0.0, -0.1, 1.0, 0.9],
+                [0.0, 10.0, 1.0, 11.0],
+                [0.0, 10.1, 1.0, 11.1],
+                [0.0, 100.0, 1.0, 101.0],
+            ]],
+            &Default::default(),
+        )
+    }
+
+    fn scores() -> TestTensor<3> {
+        TestTensor::from_data([[[0.9, 0.75, 0.6, 0.95, 0.5, 0.3]]], &Default::default())
+    }
+
+    #[test]
+    fn should_suppress_overlapping_boxes() {
+        let options = NmsOptions::new(BoxFormat::Corners, 0.5, None, Some(3));
+
+        let selected = vision::non_max_suppression(boxes(), scores(), options);
+
+        selected
+            .into_data()
+            .assert_eq(&TensorData::from([[0, 0, 3], [0, 0, 0], [0, 0, 5]]), false);
+    }
+
+    #[test]
+    fn should_select_boxes_with_center_format() {
+        let boxes = TestTensor::from_data(
+            [[
+                [0.5, 0.5, 1.0, 1.0],
+                [0.5, 0.6, 1.0, 1.0],
+                [0.5, 0.4, 1.0, 1.0],
+                [0.5, 10.5, 1.0, 1.0],
+                [0.5, 10.6, 1.0, 1.0],
+                [0.5, 100.5, 1.0, 1.0],
+            ]],
+            &Default::default(),
+        );
+        let options = NmsOptions::new(BoxFormat::CenterSize, 0.5, None, Some(3));
+
+        let selected = vision::non_max_suppression(boxes, scores(), options);
+
+        selected
+            .into_data()
+            .assert_eq(&TensorData::from([[0, 0, 3], [0, 0, 0], [0, 0, 5]]), false);
+    }
+
+    #[test]
+    fn should_only_select_boxes_above_score_threshold() {
+        let options = NmsOptions::new(BoxFormat::Corners, 0.5, Some(0.4), None);
+
+        let selected = vision::non_max_suppression(boxes(), scores(), options);
+
+        selected
+            .into_data()
+            .assert_eq(&TensorData::from([[0, 0, 3], [0, 0, 0]]), false);
+    }
+
+    #[test]
+    fn should_select_boxes_of_each_batch_and_class() {
+        let boxes = TestTensor::cat(vec![boxes(), boxes()], 0);
+        let scores = scores();
+        // The second class prefers the boxes in reverse order.
+        let scores = TestTensor::cat(vec![scores.clone(), scores.flip([2])], 1);
+        let scores = TestTensor::cat(vec![scores.clone(), scores], 0);
+        let options = NmsOptions::new(BoxFormat::Corners, 0.5, None, Some(2));
+
+        let selected = vision::non_max_suppression(boxes, scores, options);
+
+        selected.into_data().assert_eq(
+            &TensorData::from([
+                [0, 0, 3],
+                [0, 0, 0],
+                [0, 1, 2],
+                [0, 1, 5],
+                [1, 0, 3],
+                [1, 0, 0],
+                [1, 1, 2],
+                [1, 1, 5],
+            ]),
+            false,
+        );
+    }
+}
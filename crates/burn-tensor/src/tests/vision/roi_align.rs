@@ -0,0 +1,87 @@
+#[burn_tensor_testgen::testgen(vision_roi_align)]
+mod tests {
+    use super::*;
+    use burn_tensor::vision::{self, RoiAlignMode, RoiAlignOptions};
+    use burn_tensor::TensorData;
+
+    fn input() -> TestTensor<4> {
+        TestTensorInt::<1>::arange(0..80, &Default::default())
+            .float()
+            .reshape([2, 2, 4, 5])
+            .mul_scalar(0.1)
+            .sub_scalar(1.0)
+    }
+
+    fn batch_indices(indices: [i64; 2]) -> TestTensorInt<1> {
+        TestTensorInt::from_data(indices, &Default::default())
+    }
+
+    #[test]
+    fn should_pool_regions_with_fixed_sampling_ratio() {
+        let rois = TestTensor::from_data(
+            [[0.0, 0.0, 3.0, 2.0], [1.0, 0.5, 4.0, 3.5]],
+            &Default::default(),
+        );
+        let options = RoiAlignOptions::new([2, 2], 1.0, 2, false, RoiAlignMode::Avg);
+
+        let output = vision::roi_align(input(), rois, batch_indices([0, 1]), options);
+
+        output.into_data().assert_approx_eq(
+            &TensorData::from([
+                [
+                    [[-0.675, -0.525], [-0.175, -0.025]],
+                    [[1.325, 1.475], [1.825, 1.975]],
+                ],
+                [
+                    [[3.8, 3.95], [4.51875, 4.66875]],
+                    [[5.8, 5.95], [6.51875, 6.66875]],
+                ],
+            ]),
+            4,
+        );
+    }
+
+    #[test]
+    fn should_pool_aligned_regions_with_adaptive_sampling() {
+        let rois = TestTensor::from_data(
+            [[0.0, 0.0, 3.0, 2.0], [1.0, 0.5, 4.0, 3.5]],
+            &Default::default(),
+        );
+        let options = RoiAlignOptions::new([2, 2], 1.0, 0, true, RoiAlignMode::Avg);
+
+        let output = vision::roi_align(input(), rois, batch_indices([0, 1]), options);
+
+        output.into_data().assert_approx_eq(
+            &TensorData::from([
+                [
+                    [[-0.96875, -0.825], [-0.46875, -0.325]],
+                    [[1.03125, 1.175], [1.53125, 1.675]],
+                ],
+                [[[3.5, 3.65], [4.25, 4.4]], [[5.5, 5.65], [6.25, 6.4]]],
+            ]),
+            4,
+        );
+    }
+
+    #[test]
+    fn should_max_pool_scaled_regions() {
+        let rois = TestTensor::from_data(
+            [[0.0, 0.0, 6.0, 4.0], [2.0, 1.0, 8.0, 7.0]],
+            &Default::default(),
+        );
+        let options = RoiAlignOptions::new([2, 2], 0.5, 0, true, RoiAlignMode::Max);
+
+        let output = vision::roi_align(input(), rois, batch_indices([1, 0]), options);
+
+        output.into_data().assert_approx_eq(
+            &TensorData::from([
+                [[[3.0, 2.8], [3.5, 3.2375]], [[5.0, 4.55], [5.5, 4.9875]]],
+                [
+                    [[0.015625, 0.0328125], [0.328125, 0.4375]],
+                    [[1.225, 1.378125], [1.6078125, 1.7609375]],
+                ],
+            ]),
+            4,
+        );
+    }
+}
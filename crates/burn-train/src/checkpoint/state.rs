@@ -0,0 +1,30 @@
+use burn_core::{
+    data::dataloader::DataLoaderState,
+    record::{PrecisionSettings, Record},
+    tensor::backend::Backend,
+};
+use serde::{Deserialize, Serialize};
+
+/// The position of the training loop, saved with mid-epoch checkpoints so that the training can
+/// resume at the exact iteration where it stopped.
+#[derive(new, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrainingState {
+    /// The current epoch.
+    pub epoch: usize,
+    /// The number of iterations already done during the current epoch.
+    pub iteration: usize,
+    /// The state of the training data loader iterator.
+    pub dataloader: DataLoaderState,
+}
+
+impl<B: Backend> Record<B> for TrainingState {
+    type Item<S: PrecisionSettings> = TrainingState;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, _device: &B::Device) -> Self {
+        item
+    }
+}
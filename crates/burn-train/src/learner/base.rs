@@ -1,11 +1,13 @@
-use crate::checkpoint::{Checkpointer, CheckpointingAction, CheckpointingStrategy};
+use crate::checkpoint::{
+    AsyncCheckpointer, Checkpointer, CheckpointingAction, CheckpointingStrategy, TrainingState,
+};
 use crate::components::LearnerComponents;
 use crate::learner::EarlyStoppingStrategy;
 use crate::metric::store::EventStoreClient;
 use crate::LearnerSummaryConfig;
 use burn_core::lr_scheduler::LrScheduler;
 use burn_core::module::Module;
-use burn_core::optim::Optimizer;
+use burn_core::optim::{ModuleEma, ModuleEmaConfig, ModuleEmaRecord, Optimizer};
 use burn_core::tensor::backend::Backend;
 use burn_core::tensor::Device;
 use std::rc::Rc;
@@ -21,6 +23,9 @@ pub struct Learner<LC: LearnerComponents> {
     pub(crate) lr_scheduler: LC::LrScheduler,
     pub(crate) num_epochs: usize,
     pub(crate) checkpoint: Option<usize>,
+    pub(crate) checkpoint_mid_epoch: Option<usize>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) ema: Option<ModuleEmaConfig>,
     pub(crate) grad_accumulation: Option<usize>,
     pub(crate) checkpointer: Option<LearnerCheckpointer<LC>>,
     pub(crate) devices: Vec<<LC::Backend as Backend>::Device>,
@@ -31,12 +36,33 @@ pub struct Learner<LC: LearnerComponents> {
     pub(crate) summary: Option<LearnerSummaryConfig>,
 }
 
+/// Saves and restores the training components of a [learner](Learner) at the end of each epoch,
+/// and optionally every few iterations in the middle of an epoch.
 #[derive(new)]
-pub(crate) struct LearnerCheckpointer<LC: LearnerComponents> {
+pub struct LearnerCheckpointer<LC: LearnerComponents> {
     model: LC::CheckpointerModel,
     optim: LC::CheckpointerOptimizer,
     lr_scheduler: LC::CheckpointerLrScheduler,
+    ema: AsyncCheckpointer<ModuleEmaRecord<LC::Backend, LC::Model>, LC::Backend>,
     strategy: LC::CheckpointerStrategy,
+    iteration: Option<LearnerIterationCheckpointer<LC>>,
+}
+
+/// The checkpointers used to save the training components in the middle of an epoch.
+///
+/// Only the latest mid-epoch checkpoint of each epoch is kept, and it is deleted once the
+/// checkpoint of the whole epoch is handled.
+#[derive(new)]
+pub struct LearnerIterationCheckpointer<LC: LearnerComponents> {
+    model: LC::CheckpointerModel,
+    optim: LC::CheckpointerOptimizer,
+    lr_scheduler: LC::CheckpointerLrScheduler,
+    ema: AsyncCheckpointer<ModuleEmaRecord<LC::Backend, LC::Model>, LC::Backend>,
+    state: AsyncCheckpointer<TrainingState, LC::Backend>,
+    interval: Option<usize>,
+    /// The epoch and iteration of the latest mid-epoch checkpoint.
+    #[new(default)]
+    last: Option<(usize, usize)>,
 }
 
 impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
@@ -45,11 +71,16 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
         model: &LC::Model,
         optim: &LC::Optimizer,
         scheduler: &LC::LrScheduler,
+        ema: Option<&ModuleEma<LC::Backend, LC::Model>>,
         epoch: usize,
         store: &EventStoreClient,
     ) {
         let actions = self.strategy.checkpointing(epoch, store);
 
+        if let Some(iteration) = &self.iteration {
+            iteration.delete(epoch);
+        }
+
         for action in actions {
             match action {
                 CheckpointingAction::Delete(epoch) => {
@@ -62,6 +93,7 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
                     self.lr_scheduler
                         .delete(epoch)
                         .expect("Can delete learning rate scheduler checkpoint.");
+                    self.ema.delete(epoch).expect("Can delete EMA checkpoint.");
                 }
                 CheckpointingAction::Save => {
                     self.model
@@ -73,19 +105,197 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
                     self.lr_scheduler
                         .save(epoch, scheduler.to_record())
                         .expect("Can save learning rate scheduler checkpoint.");
+                    if let Some(ema) = ema {
+                        self.ema
+                            .save(epoch, ema.to_record())
+                            .expect("Can save EMA checkpoint.");
+                    }
                 }
             }
         }
     }
 
+    #[allow(clippy::type_complexity)]
     pub(crate) fn load_checkpoint(
         &self,
         model: LC::Model,
         optim: LC::Optimizer,
         scheduler: LC::LrScheduler,
+        ema: Option<ModuleEma<LC::Backend, LC::Model>>,
+        device: &Device<LC::Backend>,
+        epoch: usize,
+    ) -> (
+        LC::Model,
+        LC::Optimizer,
+        LC::LrScheduler,
+        Option<ModuleEma<LC::Backend, LC::Model>>,
+    ) {
+        let record = self
+            .model
+            .restore(epoch, device)
+            .expect("Can load model checkpoint.");
+        let model = model.load_record(record);
+
+        let record = self
+            .optim
+            .restore(epoch, device)
+            .expect("Can load optimizer checkpoint.");
+        let optim = optim.load_record(record);
+
+        let record = self
+            .lr_scheduler
+            .restore(epoch, device)
+            .expect("Can load learning rate scheduler checkpoint.");
+        let scheduler = scheduler.load_record(record);
+
+        let ema = ema.map(|ema| {
+            let record = self
+                .ema
+                .restore(epoch, device)
+                .expect("Can load EMA checkpoint.");
+            ema.load_record(record)
+        });
+
+        (model, optim, scheduler, ema)
+    }
+
+    pub(crate) fn checkpoint_iteration(
+        &mut self,
+        model: &LC::Model,
+        optim: &LC::Optimizer,
+        scheduler: &LC::LrScheduler,
+        ema: Option<&ModuleEma<LC::Backend, LC::Model>>,
+        state: TrainingState,
+    ) {
+        if let Some(iteration) = &mut self.iteration {
+            iteration.checkpoint(model, optim, scheduler, ema, state);
+        }
+    }
+
+    /// The state of the mid-epoch checkpoint saved during the given epoch, if there is one.
+    pub(crate) fn iteration_state(
+        &self,
+        device: &Device<LC::Backend>,
+        epoch: usize,
+    ) -> Option<TrainingState> {
+        self.iteration.as_ref()?.state.restore(epoch, device).ok()
+    }
+
+    /// Load the mid-epoch checkpoint saved during the given epoch.
+    ///
+    /// Its existence should be checked with [iteration_state](Self::iteration_state) first.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn load_iteration_checkpoint(
+        &mut self,
+        model: LC::Model,
+        optim: LC::Optimizer,
+        scheduler: LC::LrScheduler,
+        ema: Option<ModuleEma<LC::Backend, LC::Model>>,
+        device: &Device<LC::Backend>,
+        epoch: usize,
+    ) -> (
+        LC::Model,
+        LC::Optimizer,
+        LC::LrScheduler,
+        Option<ModuleEma<LC::Backend, LC::Model>>,
+    ) {
+        let iteration = self
+            .iteration
+            .as_mut()
+            .expect("Mid-epoch checkpoints should be enabled to resume from an iteration.");
+
+        iteration.load_checkpoint(model, optim, scheduler, ema, device, epoch)
+    }
+}
+
+impl<LC: LearnerComponents> LearnerIterationCheckpointer<LC> {
+    /// Save a checkpoint if a multiple of the interval was reached since the latest one.
+    ///
+    /// Checkpoints are only taken after an optimizer step, and the iteration can advance by more
+    /// than one between two of them with gradient accumulation or multiple devices, so the
+    /// multiple itself can be skipped.
+    fn checkpoint(
+        &mut self,
+        model: &LC::Model,
+        optim: &LC::Optimizer,
+        scheduler: &LC::LrScheduler,
+        ema: Option<&ModuleEma<LC::Backend, LC::Model>>,
+        state: TrainingState,
+    ) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let epoch = state.epoch;
+        let last = match self.last {
+            Some((last_epoch, iteration)) if last_epoch == epoch => iteration,
+            _ => 0,
+        };
+        if state.iteration / interval <= last / interval {
+            return;
+        }
+
+        log::info!(
+            "Saving mid-epoch checkpoint at iteration {} of epoch {} (interval {})",
+            state.iteration,
+            epoch,
+            interval
+        );
+
+        self.model
+            .save(epoch, model.clone().into_record())
+            .expect("Can save model checkpoint.");
+        self.optim
+            .save(epoch, optim.to_record())
+            .expect("Can save optimizer checkpoint.");
+        self.lr_scheduler
+            .save(epoch, scheduler.to_record())
+            .expect("Can save learning rate scheduler checkpoint.");
+        if let Some(ema) = ema {
+            self.ema
+                .save(epoch, ema.to_record())
+                .expect("Can save EMA checkpoint.");
+        }
+        self.last = Some((epoch, state.iteration));
+        self.state
+            .save(epoch, state)
+            .expect("Can save training state checkpoint.");
+    }
+
+    fn delete(&self, epoch: usize) {
+        if self.interval.is_none() {
+            return;
+        }
+
+        self.model
+            .delete(epoch)
+            .expect("Can delete model checkpoint.");
+        self.optim
+            .delete(epoch)
+            .expect("Can delete optimizer checkpoint.");
+        self.lr_scheduler
+            .delete(epoch)
+            .expect("Can delete learning rate scheduler checkpoint.");
+        self.ema.delete(epoch).expect("Can delete EMA checkpoint.");
+        self.state
+            .delete(epoch)
+            .expect("Can delete training state checkpoint.");
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn load_checkpoint(
+        &mut self,
+        model: LC::Model,
+        optim: LC::Optimizer,
+        scheduler: LC::LrScheduler,
+        ema: Option<ModuleEma<LC::Backend, LC::Model>>,
         device: &Device<LC::Backend>,
         epoch: usize,
-    ) -> (LC::Model, LC::Optimizer, LC::LrScheduler) {
+    ) -> (
+        LC::Model,
+        LC::Optimizer,
+        LC::LrScheduler,
+        Option<ModuleEma<LC::Backend, LC::Model>>,
+    ) {
         let record = self
             .model
             .restore(epoch, device)
@@ -104,7 +314,21 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
             .expect("Can load learning rate scheduler checkpoint.");
         let scheduler = scheduler.load_record(record);
 
-        (model, optim, scheduler)
+        let ema = ema.map(|ema| {
+            let record = self
+                .ema
+                .restore(epoch, device)
+                .expect("Can load EMA checkpoint.");
+            ema.load_record(record)
+        });
+
+        let state = self
+            .state
+            .restore(epoch, device)
+            .expect("Can load training state checkpoint.");
+        self.last = Some((epoch, state.iteration));
+
+        (model, optim, scheduler, ema)
     }
 }
 
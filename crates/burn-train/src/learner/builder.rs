@@ -5,7 +5,7 @@ use std::rc::Rc;
 use super::Learner;
 use crate::checkpoint::{
     AsyncCheckpointer, CheckpointingStrategy, ComposedCheckpointingStrategy, FileCheckpointer,
-    KeepLastNCheckpoints, MetricCheckpointingStrategy,
+    KeepLastNCheckpoints, MetricCheckpointingStrategy, TrainingState,
 };
 use crate::components::LearnerComponentsMarker;
 use crate::learner::base::TrainingInterrupter;
@@ -17,11 +17,11 @@ use crate::metric::{Adaptor, LossMetric, Metric};
 use crate::renderer::{default_renderer, MetricsRenderer};
 use crate::{
     ApplicationLoggerInstaller, FileApplicationLoggerInstaller, LearnerCheckpointer,
-    LearnerSummaryConfig,
+    LearnerIterationCheckpointer, LearnerSummaryConfig,
 };
 use burn_core::lr_scheduler::LrScheduler;
 use burn_core::module::AutodiffModule;
-use burn_core::optim::Optimizer;
+use burn_core::optim::{ModuleEmaConfig, ModuleEmaRecord, Optimizer};
 use burn_core::record::FileRecorder;
 use burn_core::tensor::backend::AutodiffBackend;
 
@@ -43,9 +43,22 @@ where
         AsyncCheckpointer<M::Record, B>,
         AsyncCheckpointer<O::Record, B>,
         AsyncCheckpointer<S::Record<B>, B>,
+        AsyncCheckpointer<ModuleEmaRecord<B, M>, B>,
+    )>,
+    #[allow(clippy::type_complexity)]
+    checkpointers_iteration: Option<(
+        AsyncCheckpointer<M::Record, B>,
+        AsyncCheckpointer<O::Record, B>,
+        AsyncCheckpointer<S::Record<B>, B>,
+        AsyncCheckpointer<ModuleEmaRecord<B, M>, B>,
+        AsyncCheckpointer<TrainingState, B>,
     )>,
     num_epochs: usize,
     checkpoint: Option<usize>,
+    checkpoint_mid_epoch: Option<usize>,
+    checkpoint_interval: Option<usize>,
+    seed: Option<u64>,
+    ema: Option<ModuleEmaConfig>,
     directory: PathBuf,
     grad_accumulation: Option<usize>,
     devices: Vec<B::Device>,
@@ -81,7 +94,12 @@ where
         Self {
             num_epochs: 1,
             checkpoint: None,
+            checkpoint_mid_epoch: None,
+            checkpoint_interval: None,
+            seed: None,
+            ema: None,
             checkpointers: None,
+            checkpointers_iteration: None,
             directory,
             grad_accumulation: None,
             devices: vec![B::Device::default()],
@@ -223,6 +241,68 @@ where
         self
     }
 
+    /// Resume the training from the last mid-epoch checkpoint saved during the given epoch.
+    ///
+    /// The model, the optimizer, the learning rate scheduler and the position of the training
+    /// data loader are restored, so the training continues at the exact iteration where the
+    /// checkpoint was saved. Takes precedence over [checkpoint](Self::checkpoint).
+    ///
+    /// When no mid-epoch checkpoint was saved during the epoch, the epoch is restarted from the
+    /// checkpoint of the previous epoch.
+    ///
+    /// # Notes
+    ///
+    /// The resumed training is only bit-identical to an uninterrupted one when a [seed](Self::seed)
+    /// is set, otherwise the random numbers sampled by the backend (e.g. dropout masks) differ.
+    pub fn checkpoint_mid_epoch(mut self, epoch: usize) -> Self {
+        self.checkpoint_mid_epoch = Some(epoch);
+        self
+    }
+
+    /// Save a mid-epoch checkpoint every `num_iterations` training iterations.
+    ///
+    /// Only the latest mid-epoch checkpoint is kept for each epoch, and it is deleted when the
+    /// epoch ends. Requires a [file checkpointer](Self::with_file_checkpointer).
+    ///
+    /// # Notes
+    ///
+    /// A checkpoint is only saved on iterations where the optimizer updates the model, which
+    /// happens every few iterations when gradients are accumulated or when the training runs on
+    /// multiple devices. The checkpoint is then saved at the first update after each multiple of
+    /// `num_iterations`.
+    ///
+    /// # Panics
+    ///
+    /// If `num_iterations` is zero.
+    pub fn checkpoint_interval(mut self, num_iterations: usize) -> Self {
+        assert!(
+            num_iterations > 0,
+            "The checkpoint interval should be at least one iteration."
+        );
+        self.checkpoint_interval = Some(num_iterations);
+        self
+    }
+
+    /// Reseed the backend before each training iteration with a seed derived from the given
+    /// one, the epoch and the iteration.
+    ///
+    /// This makes the random numbers sampled during a training step (e.g. dropout masks)
+    /// independent of where the training started, so a resumed training is reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Keep an [exponential moving average](burn_core::optim::ModuleEma) of the model weights,
+    /// updated after each optimizer step.
+    ///
+    /// The averaged weights are used for validation in place of the raw model, are saved with the
+    /// checkpoints and are the ones returned by [fit](Learner::fit).
+    pub fn ema(mut self, config: ModuleEmaConfig) -> Self {
+        self.ema = Some(config);
+        self
+    }
+
     /// Provides a handle that can be used to interrupt training.
     pub fn interrupter(&self) -> TrainingInterrupter {
         self.interrupter.clone()
@@ -264,12 +344,32 @@ where
         let checkpointer_optimizer =
             FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "optim");
         let checkpointer_scheduler: FileCheckpointer<FR> =
-            FileCheckpointer::new(recorder, &checkpoint_dir, "scheduler");
+            FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "scheduler");
+        let checkpointer_ema = FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "ema");
 
         self.checkpointers = Some((
             AsyncCheckpointer::new(checkpointer_model),
             AsyncCheckpointer::new(checkpointer_optimizer),
             AsyncCheckpointer::new(checkpointer_scheduler),
+            AsyncCheckpointer::new(checkpointer_ema),
+        ));
+
+        let checkpoint_dir = checkpoint_dir.join("iteration");
+        let checkpointer_model = FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "model");
+        let checkpointer_optimizer =
+            FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "optim");
+        let checkpointer_scheduler: FileCheckpointer<FR> =
+            FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "scheduler");
+        let checkpointer_ema = FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "ema");
+        let checkpointer_state: FileCheckpointer<FR> =
+            FileCheckpointer::new(recorder, &checkpoint_dir, "state");
+
+        self.checkpointers_iteration = Some((
+            AsyncCheckpointer::new(checkpointer_model),
+            AsyncCheckpointer::new(checkpointer_optimizer),
+            AsyncCheckpointer::new(checkpointer_scheduler),
+            AsyncCheckpointer::new(checkpointer_ema),
+            AsyncCheckpointer::new(checkpointer_state),
         ));
 
         self
@@ -330,8 +430,28 @@ where
         let event_store = Rc::new(EventStoreClient::new(self.event_store));
         let event_processor = FullEventProcessor::new(self.metrics, renderer, event_store.clone());
 
-        let checkpointer = self.checkpointers.map(|(model, optim, scheduler)| {
-            LearnerCheckpointer::new(model, optim, scheduler, self.checkpointer_strategy)
+        let checkpoint_interval = self.checkpoint_interval;
+        let checkpointer_iteration =
+            self.checkpointers_iteration
+                .map(|(model, optim, scheduler, ema, state)| {
+                    LearnerIterationCheckpointer::new(
+                        model,
+                        optim,
+                        scheduler,
+                        ema,
+                        state,
+                        checkpoint_interval,
+                    )
+                });
+        let checkpointer = self.checkpointers.map(|(model, optim, scheduler, ema)| {
+            LearnerCheckpointer::new(
+                model,
+                optim,
+                scheduler,
+                ema,
+                self.checkpointer_strategy,
+                checkpointer_iteration,
+            )
         });
 
         let summary = if self.summary {
@@ -352,6 +472,9 @@ where
             event_processor,
             event_store,
             checkpoint: self.checkpoint,
+            checkpoint_mid_epoch: self.checkpoint_mid_epoch,
+            seed: self.seed,
+            ema: self.ema,
             grad_accumulation: self.grad_accumulation,
             devices: self.devices,
             interrupter: self.interrupter,
@@ -1,4 +1,8 @@
-use crate::metric::{AccuracyInput, Adaptor, HammingScoreInput, LossInput};
+use crate::metric::{
+    AccuracyInput, Adaptor, AurocInput, ConfusionMatrixInput, ConfusionStatsInput,
+    HammingScoreInput, LossInput,
+};
+use burn_core::tensor::activation::softmax;
 use burn_core::tensor::backend::Backend;
 use burn_core::tensor::{Int, Tensor};
 
@@ -27,6 +31,34 @@ impl<B: Backend> Adaptor<LossInput<B>> for ClassificationOutput<B> {
     }
 }
 
+impl<B: Backend> Adaptor<ConfusionStatsInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> ConfusionStatsInput<B> {
+        let [_batch_size, num_classes] = self.output.dims();
+
+        ConfusionStatsInput::new(
+            self.output.clone(),
+            self.targets.clone().one_hot(num_classes).bool(),
+        )
+    }
+}
+
+impl<B: Backend> Adaptor<ConfusionMatrixInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> ConfusionMatrixInput<B> {
+        ConfusionMatrixInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<AurocInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> AurocInput<B> {
+        let [_batch_size, num_classes] = self.output.dims();
+
+        AurocInput::new(
+            softmax(self.output.clone(), 1),
+            self.targets.clone().one_hot(num_classes).bool(),
+        )
+    }
+}
+
 /// Multi-label classification output adapted for multiple metrics.
 #[derive(new)]
 pub struct MultiLabelClassificationOutput<B: Backend> {
@@ -51,3 +83,15 @@ impl<B: Backend> Adaptor<LossInput<B>> for MultiLabelClassificationOutput<B> {
         LossInput::new(self.loss.clone())
     }
 }
+
+impl<B: Backend> Adaptor<ConfusionStatsInput<B>> for MultiLabelClassificationOutput<B> {
+    fn adapt(&self) -> ConfusionStatsInput<B> {
+        ConfusionStatsInput::new(self.output.clone(), self.targets.clone().bool())
+    }
+}
+
+impl<B: Backend> Adaptor<AurocInput<B>> for MultiLabelClassificationOutput<B> {
+    fn adapt(&self) -> AurocInput<B> {
+        AurocInput::new(self.output.clone(), self.targets.clone().bool())
+    }
+}
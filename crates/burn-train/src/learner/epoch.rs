@@ -1,31 +1,41 @@
 use burn_core::{
-    data::dataloader::DataLoader, lr_scheduler::LrScheduler, module::AutodiffModule,
-    optim::GradientsAccumulator, tensor::backend::Backend,
+    data::dataloader::{DataLoader, DataLoaderIterator, DataLoaderState, Progress},
+    lr_scheduler::LrScheduler,
+    module::AutodiffModule,
+    optim::{GradientsAccumulator, ModuleEma},
+    tensor::backend::{AutodiffBackend, Backend},
 };
 use std::sync::Arc;
 
+use crate::checkpoint::TrainingState;
 use crate::metric::processor::{Event, EventProcessor, LearnerItem};
 use crate::{components::LearnerComponents, learner::base::TrainingInterrupter};
-use crate::{MultiDevicesTrainStep, TrainStep, ValidStep};
+use crate::{LearnerCheckpointer, MultiDevicesTrainStep, TrainStep, ValidStep};
 
 /// A validation epoch.
 #[derive(new)]
-pub struct ValidEpoch<VI> {
-    dataloader: Arc<dyn DataLoader<VI>>,
+pub struct ValidEpoch<B: Backend, VI> {
+    dataloader: Arc<dyn DataLoader<B, VI>>,
     epoch: usize,
     epoch_total: usize,
 }
 
 /// A training epoch.
 #[derive(new)]
-pub struct TrainEpoch<TI> {
-    dataloader: Arc<dyn DataLoader<TI>>,
+pub struct TrainEpoch<B: Backend, TI> {
+    dataloader: Arc<dyn DataLoader<B, TI>>,
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    /// The state of a mid-epoch checkpoint to resume from.
+    #[new(default)]
+    resume: Option<TrainingState>,
+    /// The seed used to reseed the backend before each iteration.
+    #[new(default)]
+    seed: Option<u64>,
 }
 
-impl<VI> ValidEpoch<VI> {
+impl<B: Backend, VI> ValidEpoch<B, VI> {
     /// Runs the validation epoch.
     ///
     /// # Arguments
@@ -72,7 +82,41 @@ impl<VI> ValidEpoch<VI> {
     }
 }
 
-impl<TI> TrainEpoch<TI> {
+impl<B: Backend, TI> TrainEpoch<B, TI> {
+    /// Resume the epoch from the given [mid-epoch state](TrainingState).
+    pub fn with_resume(mut self, state: TrainingState) -> Self {
+        self.resume = Some(state);
+        self
+    }
+
+    /// Reseed the backend before each iteration with a seed derived from the given one, the epoch
+    /// and the iteration, so that a resumed training samples the same random numbers.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The state of the data loader where the epoch should start, with the number of iterations
+    /// already done.
+    ///
+    /// Each epoch uses its own iteration of the data loader, so that the shuffling order of a
+    /// given epoch doesn't depend on the epoch the training started from.
+    fn start_state(&self) -> (DataLoaderState, usize) {
+        match &self.resume {
+            Some(state) => (state.dataloader.clone(), state.iteration),
+            None => (DataLoaderState::new(self.epoch - 1, Vec::new()), 0),
+        }
+    }
+
+    fn seed_backend<BS: Backend>(&self, iteration: usize) {
+        if let Some(seed) = self.seed {
+            let seed = seed
+                .wrapping_add((self.epoch as u64) << 32)
+                .wrapping_add(iteration as u64);
+            BS::seed(seed);
+        }
+    }
+
     /// Runs the training epoch.
     ///
     /// # Arguments
@@ -81,10 +125,14 @@ impl<TI> TrainEpoch<TI> {
     /// * `optim` - The optimizer to use.
     /// * `scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
+    /// * `interrupter` - The handle used to stop the training.
+    /// * `checkpointer` - The checkpointer used to save mid-epoch checkpoints.
+    /// * `ema` - The moving average of the model weights, updated after each optimizer step.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn run<LC: LearnerComponents, TO>(
         &self,
         mut model: LC::Model,
@@ -92,6 +140,8 @@ impl<TI> TrainEpoch<TI> {
         scheduler: &mut LC::LrScheduler,
         processor: &mut LC::EventProcessor,
         interrupter: &TrainingInterrupter,
+        mut checkpointer: Option<&mut LearnerCheckpointer<LC>>,
+        mut ema: Option<&mut ModuleEma<LC::Backend, LC::Model>>,
     ) -> (LC::Model, LC::Optimizer)
     where
         LC::EventProcessor: EventProcessor<ItemTrain = TO>,
@@ -99,8 +149,8 @@ impl<TI> TrainEpoch<TI> {
     {
         log::info!("Executing training step for epoch {}", self.epoch,);
 
-        let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
+        let (state, mut iteration) = self.start_state();
+        let mut iterator = self.dataloader.iter_from(&state);
         let mut accumulator = GradientsAccumulator::new();
         let mut accumulation_current = 0;
 
@@ -110,6 +160,7 @@ impl<TI> TrainEpoch<TI> {
             log::info!("Iteration {}", iteration);
 
             let progress = iterator.progress();
+            self.seed_backend::<LC::Backend>(iteration);
             let item = model.step(item);
 
             match self.grad_accumulation {
@@ -121,9 +172,19 @@ impl<TI> TrainEpoch<TI> {
                         let grads = accumulator.grads();
                         model = model.optimize(&mut optim, lr, grads);
                         accumulation_current = 0;
+
+                        if let Some(ema) = ema.as_deref_mut() {
+                            ema.update(&model);
+                        }
+                    }
+                }
+                None => {
+                    model = model.optimize(&mut optim, lr, item.grads);
+
+                    if let Some(ema) = ema.as_deref_mut() {
+                        ema.update(&model);
                     }
                 }
-                None => model = model.optimize(&mut optim, lr, item.grads),
             }
 
             let item = LearnerItem::new(
@@ -137,6 +198,21 @@ impl<TI> TrainEpoch<TI> {
 
             processor.process_train(Event::ProcessedItem(item));
 
+            // Accumulated gradients aren't part of the checkpoint, so it can only be taken after
+            // an optimizer step.
+            if let Some(checkpointer) = checkpointer.as_deref_mut() {
+                if accumulation_current == 0 {
+                    let state = TrainingState::new(self.epoch, iteration, iterator.state());
+                    checkpointer.checkpoint_iteration(
+                        &model,
+                        &optim,
+                        scheduler,
+                        ema.as_deref(),
+                        state,
+                    );
+                }
+            }
+
             if interrupter.should_stop() {
                 log::info!("Training interrupted.");
                 break;
@@ -148,9 +224,12 @@ impl<TI> TrainEpoch<TI> {
     }
 }
 
-impl<TI> TrainEpoch<TI> {
+impl<B: AutodiffBackend, TI> TrainEpoch<B, TI> {
     /// Runs the training epoch on multiple devices.
     ///
+    /// The data loader is split into one data loader per device, each creating its batches
+    /// directly on its device.
+    ///
     /// # Arguments
     ///
     /// * `model` - The model to train.
@@ -158,11 +237,15 @@ impl<TI> TrainEpoch<TI> {
     /// * `lr_scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
     /// * `devices` - The devices to use.
+    /// * `interrupter` - The handle used to stop the training.
+    /// * `checkpointer` - The checkpointer used to save mid-epoch checkpoints.
+    /// * `ema` - The moving average of the model weights, updated after each optimizer step.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
-    pub fn run_multi_device<LC: LearnerComponents, TO>(
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_multi_device<LC: LearnerComponents<Backend = B>, TO>(
         &self,
         mut model: LC::Model,
         mut optim: LC::Optimizer,
@@ -170,6 +253,8 @@ impl<TI> TrainEpoch<TI> {
         processor: &mut LC::EventProcessor,
         devices: Vec<<LC::Backend as Backend>::Device>,
         interrupter: &TrainingInterrupter,
+        mut checkpointer: Option<&mut LearnerCheckpointer<LC>>,
+        mut ema: Option<&mut ModuleEma<LC::Backend, LC::Model>>,
     ) -> (LC::Model, LC::Optimizer)
     where
         LC::EventProcessor: EventProcessor<ItemTrain = TO>,
@@ -183,8 +268,32 @@ impl<TI> TrainEpoch<TI> {
             devices
         );
 
-        let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
+        let (state, mut iteration) = self.start_state();
+        let dataloaders = self
+            .dataloader
+            .split(devices.len())
+            .into_iter()
+            .zip(devices.iter())
+            .map(|(dataloader, device)| dataloader.to_device(device))
+            .collect::<Vec<_>>();
+
+        // The items processed by the workers of each device are saved one device after the other.
+        let num_workers = state.items_processed.len() / devices.len();
+        let mut iterators = dataloaders
+            .iter()
+            .enumerate()
+            .map(|(index, dataloader)| {
+                let items_processed = state
+                    .items_processed
+                    .iter()
+                    .skip(index * num_workers)
+                    .take(num_workers)
+                    .copied()
+                    .collect();
+                dataloader.iter_from(&DataLoaderState::new(state.iteration, items_processed))
+            })
+            .collect::<Vec<_>>();
+
         let mut accumulator = GradientsAccumulator::new();
         let mut accumulation_current = 0;
 
@@ -196,7 +305,8 @@ impl<TI> TrainEpoch<TI> {
         let mut interrupted = false;
 
         loop {
-            let items = step.step(&mut iterator, &model);
+            self.seed_backend::<LC::Backend>(iteration + 1);
+            let items = step.step(&mut iterators, &model);
             if items.is_empty() {
                 break;
             }
@@ -204,7 +314,7 @@ impl<TI> TrainEpoch<TI> {
             for item in items {
                 iteration += 1;
                 let lr = lr_scheduler.step();
-                let progress = iterator.progress();
+                let progress = Self::progress(&iterators);
 
                 let grads = item.grads.to_device(&device_main, &model);
 
@@ -215,6 +325,10 @@ impl<TI> TrainEpoch<TI> {
                     let grads = accumulator.grads();
                     model = model.optimize(&mut optim, lr, grads);
                     accumulation_current = 0;
+
+                    if let Some(ema) = ema.as_deref_mut() {
+                        ema.update(&model);
+                    }
                 }
 
                 let item = LearnerItem::new(
@@ -235,6 +349,26 @@ impl<TI> TrainEpoch<TI> {
                 }
             }
 
+            // The whole group of items is processed before checkpointing, since the iterator is
+            // already positioned after the last item sent to the devices.
+            if let Some(checkpointer) = checkpointer.as_deref_mut() {
+                if accumulation_current == 0 {
+                    let items_processed = iterators
+                        .iter()
+                        .flat_map(|iterator| iterator.state().items_processed)
+                        .collect();
+                    let dataloader = DataLoaderState::new(state.iteration, items_processed);
+                    let state = TrainingState::new(self.epoch, iteration, dataloader);
+                    checkpointer.checkpoint_iteration(
+                        &model,
+                        &optim,
+                        lr_scheduler,
+                        ema.as_deref(),
+                        state,
+                    );
+                }
+            }
+
             if interrupted {
                 break;
             }
@@ -244,4 +378,16 @@ impl<TI> TrainEpoch<TI> {
 
         (model, optim)
     }
+
+    fn progress(iterators: &[Box<dyn DataLoaderIterator<TI> + '_>]) -> Progress {
+        let mut progress = Progress::new(0, 0);
+
+        for iterator in iterators.iter() {
+            let current = iterator.progress();
+            progress.items_processed += current.items_processed;
+            progress.items_total += current.items_total;
+        }
+
+        progress
+    }
 }
@@ -5,6 +5,7 @@ mod classification;
 mod early_stopping;
 mod epoch;
 mod regression;
+mod sequence;
 mod step;
 mod summary;
 mod train_val;
@@ -16,6 +17,7 @@ pub use classification::*;
 pub use early_stopping::*;
 pub use epoch::*;
 pub use regression::*;
+pub use sequence::*;
 pub use step::*;
 pub use summary::*;
 pub use train::*;
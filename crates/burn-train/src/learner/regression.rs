@@ -1,4 +1,4 @@
-use crate::metric::{Adaptor, LossInput};
+use crate::metric::{Adaptor, LossInput, RegressionInput};
 use burn_core::tensor::backend::Backend;
 use burn_core::tensor::Tensor;
 
@@ -20,3 +20,9 @@ impl<B: Backend> Adaptor<LossInput<B>> for RegressionOutput<B> {
         LossInput::new(self.loss.clone())
     }
 }
+
+impl<B: Backend> Adaptor<RegressionInput<B>> for RegressionOutput<B> {
+    fn adapt(&self) -> RegressionInput<B> {
+        RegressionInput::new(self.output.clone(), self.targets.clone())
+    }
+}
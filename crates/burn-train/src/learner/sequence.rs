@@ -0,0 +1,54 @@
+use crate::metric::{Adaptor, LossInput, PerplexityInput, TextInput};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Int, Tensor};
+
+/// Sequence output, such as the output of a language model, adapted for multiple metrics.
+///
+/// Text metrics need the decoded texts, which can be added with
+/// [with_texts](SequenceOutput::with_texts).
+#[derive(new)]
+pub struct SequenceOutput<B: Backend> {
+    /// The loss.
+    pub loss: Tensor<B, 1>,
+
+    /// The output logits (batch_size, seq_length, vocab_size).
+    pub output: Tensor<B, 3>,
+
+    /// The target tokens (batch_size, seq_length).
+    pub targets: Tensor<B, 2, Int>,
+
+    /// The decoded predictions.
+    #[new(default)]
+    pub predictions: Vec<String>,
+
+    /// The decoded targets.
+    #[new(default)]
+    pub references: Vec<String>,
+}
+
+impl<B: Backend> SequenceOutput<B> {
+    /// Sets the decoded predictions and targets used by the text metrics.
+    pub fn with_texts(mut self, predictions: Vec<String>, references: Vec<String>) -> Self {
+        self.predictions = predictions;
+        self.references = references;
+        self
+    }
+}
+
+impl<B: Backend> Adaptor<LossInput<B>> for SequenceOutput<B> {
+    fn adapt(&self) -> LossInput<B> {
+        LossInput::new(self.loss.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<PerplexityInput<B>> for SequenceOutput<B> {
+    fn adapt(&self) -> PerplexityInput<B> {
+        PerplexityInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<TextInput> for SequenceOutput<B> {
+    fn adapt(&self) -> TextInput {
+        TextInput::new(self.predictions.clone(), self.references.clone())
+    }
+}
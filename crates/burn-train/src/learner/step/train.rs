@@ -107,7 +107,7 @@ where
     ///
     /// # Arguments
     ///
-    /// * `dataloader` - Dataloader.
+    /// * `dataloaders` - One dataloader iterator per device, in the same order as the devices.
     /// * `model` - Model.
     ///
     /// # Returns
@@ -115,12 +115,12 @@ where
     /// Outputs.
     pub fn step<'a>(
         &self,
-        dataloader: &mut Box<dyn DataLoaderIterator<TI> + 'a>,
+        dataloaders: &mut [Box<dyn DataLoaderIterator<TI> + 'a>],
         model: &M,
     ) -> Vec<TrainOutput<TO>> {
         let mut num_send = 0;
 
-        for worker in self.workers.iter() {
+        for (worker, dataloader) in self.workers.iter().zip(dataloaders.iter_mut()) {
             if let Some(item) = dataloader.next() {
                 worker.register(item, model);
                 num_send += 1;
@@ -105,13 +105,19 @@ impl<LC: LearnerComponents> Learner<LC> {
     /// * `dataloader_train` - The training dataloader.
     /// * `dataloader_valid` - The validation dataloader.
     ///
+    /// The batches of both data loaders are created on the [devices](crate::LearnerBuilder::devices)
+    /// of the learner, whatever the device they were built with.
+    ///
     /// # Returns
     ///
-    /// The fitted model.
+    /// The fitted model, or the moving average of its weights when
+    /// [ema](crate::LearnerBuilder::ema) is enabled.
     pub fn fit<InputTrain, InputValid, OutputTrain, OutputValid>(
         mut self,
-        dataloader_train: Arc<dyn DataLoader<InputTrain>>,
-        dataloader_valid: Arc<dyn DataLoader<InputValid>>,
+        dataloader_train: Arc<dyn DataLoader<LC::Backend, InputTrain>>,
+        dataloader_valid: Arc<
+            dyn DataLoader<<LC::Backend as AutodiffBackend>::InnerBackend, InputValid>,
+        >,
     ) -> LC::Model
     where
         InputTrain: Send + 'static,
@@ -123,34 +129,92 @@ impl<LC: LearnerComponents> Learner<LC> {
         LC::EventProcessor: EventProcessor<ItemTrain = OutputTrain, ItemValid = OutputValid>,
     {
         log::info!("Fitting the model:\n {}", self.model.to_string());
-        // The reference model is always on the first device provided.
+        // The reference model is always on the first device provided, where the batches are also
+        // created, the training data loader being split across the devices when there are more.
+        let mut dataloader_train = dataloader_train;
+        let mut dataloader_valid = dataloader_valid;
         if let Some(device) = self.devices.first() {
             self.model = self.model.fork(device);
+            dataloader_train = dataloader_train.to_device(device);
+            dataloader_valid = dataloader_valid.to_device(device);
         }
 
-        let starting_epoch = match self.checkpoint {
-            Some(checkpoint) => {
+        let mut ema = self.ema.as_ref().map(|config| config.init(&self.model));
+        let mut resume = None;
+        let starting_epoch = match (self.checkpoint_mid_epoch, self.checkpoint) {
+            (Some(epoch), _) => {
+                // Load the checkpoint on the default device.
+                let device = Default::default();
+                let state = self
+                    .checkpointer
+                    .as_ref()
+                    .and_then(|checkpointer| checkpointer.iteration_state(&device, epoch));
+
+                match (&mut self.checkpointer, state) {
+                    (Some(checkpointer), Some(state)) => {
+                        (self.model, self.optim, self.lr_scheduler, ema) = checkpointer
+                            .load_iteration_checkpoint(
+                                self.model,
+                                self.optim,
+                                self.lr_scheduler,
+                                ema,
+                                &device,
+                                epoch,
+                            );
+                        resume = Some(state);
+                    }
+                    // Without mid-epoch checkpoint, the epoch is restarted from the checkpoint of
+                    // the previous epoch.
+                    (Some(checkpointer), None) => {
+                        log::warn!(
+                            "No mid-epoch checkpoint found for epoch {epoch}, restarting the epoch"
+                        );
+                        if epoch > 1 {
+                            (self.model, self.optim, self.lr_scheduler, ema) = checkpointer
+                                .load_checkpoint(
+                                    self.model,
+                                    self.optim,
+                                    self.lr_scheduler,
+                                    ema,
+                                    &device,
+                                    epoch - 1,
+                                );
+                        }
+                    }
+                    (None, _) => {}
+                }
+                epoch
+            }
+            (None, Some(checkpoint)) => {
                 if let Some(checkpointer) = &mut self.checkpointer {
-                    (self.model, self.optim, self.lr_scheduler) = checkpointer.load_checkpoint(
-                        self.model,
-                        self.optim,
-                        self.lr_scheduler,
-                        &Default::default(), // Load the checkpoint on the default device.
-                        checkpoint,
-                    );
+                    (self.model, self.optim, self.lr_scheduler, ema) = checkpointer
+                        .load_checkpoint(
+                            self.model,
+                            self.optim,
+                            self.lr_scheduler,
+                            ema,
+                            &Default::default(), // Load the checkpoint on the default device.
+                            checkpoint,
+                        );
                 }
                 checkpoint + 1
             }
-            None => 1,
+            (None, None) => 1,
         };
 
         for epoch in starting_epoch..self.num_epochs + 1 {
-            let epoch_train = TrainEpoch::new(
+            let mut epoch_train = TrainEpoch::new(
                 dataloader_train.clone(),
                 epoch,
                 self.num_epochs,
                 self.grad_accumulation,
             );
+            if let Some(state) = resume.take() {
+                epoch_train = epoch_train.with_resume(state);
+            }
+            if let Some(seed) = self.seed {
+                epoch_train = epoch_train.with_seed(seed);
+            }
 
             if self.devices.len() > 1 {
                 (self.model, self.optim) = epoch_train.run_multi_device::<LC, OutputTrain>(
@@ -160,6 +224,8 @@ impl<LC: LearnerComponents> Learner<LC> {
                     &mut self.event_processor,
                     self.devices.clone(),
                     &self.interrupter,
+                    self.checkpointer.as_mut(),
+                    ema.as_mut(),
                 )
             } else {
                 (self.model, self.optim) = epoch_train.run::<LC, OutputTrain>(
@@ -168,6 +234,8 @@ impl<LC: LearnerComponents> Learner<LC> {
                     &mut self.lr_scheduler,
                     &mut self.event_processor,
                     &self.interrupter,
+                    self.checkpointer.as_mut(),
+                    ema.as_mut(),
                 );
             }
 
@@ -176,8 +244,13 @@ impl<LC: LearnerComponents> Learner<LC> {
             }
 
             let epoch_valid = ValidEpoch::new(dataloader_valid.clone(), epoch, self.num_epochs);
+            // The moving average of the weights is validated in place of the raw model.
+            let model_valid = match &ema {
+                Some(ema) => ema.module(),
+                None => &self.model,
+            };
             epoch_valid.run::<LC, OutputValid>(
-                &self.model,
+                model_valid,
                 &mut self.event_processor,
                 &self.interrupter,
             );
@@ -187,6 +260,7 @@ impl<LC: LearnerComponents> Learner<LC> {
                     &self.model,
                     &self.optim,
                     &self.lr_scheduler,
+                    ema.as_ref(),
                     epoch,
                     &self.event_store,
                 );
@@ -199,6 +273,10 @@ impl<LC: LearnerComponents> Learner<LC> {
             }
         }
 
+        if let Some(ema) = ema {
+            self.model = ema.into_module();
+        }
+
         // Display learner summary
         if let Some(summary) = self.summary {
             match summary.init() {
@@ -215,3 +293,223 @@ impl<LC: LearnerComponents> Learner<LC> {
         self.model
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{MetricState, MetricsRenderer, TrainingProgress};
+    use crate::{LearnerBuilder, RegressionOutput, TrainingInterrupter};
+    use burn_core as burn;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::nn::loss::{MseLoss, Reduction};
+    use burn_core::nn::{Dropout, DropoutConfig, Linear, LinearConfig};
+    use burn_core::optim::AdamConfig;
+    use burn_core::record::{FullPrecisionSettings, NamedMpkFileRecorder};
+    use burn_core::tensor::backend::Backend;
+    use burn_core::tensor::{Tensor, TensorData};
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type TestAutodiffBackend = burn_autodiff::Autodiff<crate::TestBackend>;
+
+    #[derive(Module, Debug)]
+    struct TestModel<B: Backend> {
+        linear: Linear<B>,
+        dropout: Dropout,
+    }
+
+    #[derive(Clone, Debug)]
+    struct TestBatch<B: Backend> {
+        inputs: Tensor<B, 2>,
+        targets: Tensor<B, 2>,
+    }
+
+    impl<B: Backend> TestModel<B> {
+        fn forward(&self, batch: TestBatch<B>) -> RegressionOutput<B> {
+            let output = self.linear.forward(self.dropout.forward(batch.inputs));
+            let loss =
+                MseLoss::new().forward(output.clone(), batch.targets.clone(), Reduction::Mean);
+
+            RegressionOutput::new(loss, output, batch.targets)
+        }
+    }
+
+    impl<B: AutodiffBackend> TrainStep<TestBatch<B>, RegressionOutput<B>> for TestModel<B> {
+        fn step(&self, batch: TestBatch<B>) -> TrainOutput<RegressionOutput<B>> {
+            let item = self.forward(batch);
+            TrainOutput::new(self, item.loss.backward(), item)
+        }
+    }
+
+    impl<B: Backend> ValidStep<TestBatch<B>, RegressionOutput<B>> for TestModel<B> {
+        fn step(&self, batch: TestBatch<B>) -> RegressionOutput<B> {
+            self.forward(batch)
+        }
+    }
+
+    /// Stops the training once the given number of batches is created.
+    #[derive(Clone)]
+    struct TestBatcher {
+        interrupter: Option<(TrainingInterrupter, usize)>,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<B: Backend> Batcher<B, [f32; 3], TestBatch<B>> for TestBatcher {
+        fn batch(&self, items: Vec<[f32; 3]>, device: &B::Device) -> TestBatch<B> {
+            let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Some((interrupter, stop)) = &self.interrupter {
+                if count == *stop {
+                    interrupter.stop();
+                }
+            }
+
+            let num_items = items.len();
+            let inputs = items.iter().flat_map(|item| [item[0], item[1]]).collect();
+            let targets = items.iter().map(|item| item[2]).collect();
+
+            TestBatch {
+                inputs: Tensor::from_data(TensorData::new(inputs, [num_items, 2]), device),
+                targets: Tensor::from_data(TensorData::new(targets, [num_items, 1]), device),
+            }
+        }
+    }
+
+    struct NoopRenderer;
+
+    impl MetricsRenderer for NoopRenderer {
+        fn update_train(&mut self, _state: MetricState) {}
+        fn update_valid(&mut self, _state: MetricState) {}
+        fn render_train(&mut self, _item: TrainingProgress) {}
+        fn render_valid(&mut self, _item: TrainingProgress) {}
+    }
+
+    fn dataset() -> InMemDataset<[f32; 3]> {
+        let items = (0..12)
+            .map(|i| {
+                let x = i as f32 / 12.0;
+                [x, 1.0 - x, 2.0 * x - 0.5]
+            })
+            .collect();
+        InMemDataset::new(items)
+    }
+
+    /// Train two epochs, stopping after the given number of batches, optionally resuming from a
+    /// mid-epoch checkpoint of the given epoch and accumulating the gradients of a few batches.
+    fn train(
+        directory: &Path,
+        stop: Option<usize>,
+        resume: Option<usize>,
+        accumulation: Option<usize>,
+    ) -> TestModel<TestAutodiffBackend> {
+        let device = Default::default();
+        TestAutodiffBackend::seed(0);
+        let model = TestModel {
+            linear: LinearConfig::new(2, 1).init(&device),
+            dropout: DropoutConfig::new(0.5).init(),
+        };
+
+        let mut builder = LearnerBuilder::new(directory)
+            .with_file_checkpointer(NamedMpkFileRecorder::<FullPrecisionSettings>::new())
+            .checkpoint_interval(2)
+            .seed(42)
+            .num_epochs(2)
+            .renderer(NoopRenderer)
+            .with_application_logger(None);
+        if let Some(epoch) = resume {
+            builder = builder.checkpoint_mid_epoch(epoch);
+        }
+        if let Some(accumulation) = accumulation {
+            builder = builder.grads_accumulation(accumulation);
+        }
+
+        let batcher = TestBatcher {
+            interrupter: stop.map(|stop| (builder.interrupter(), stop)),
+            count: Default::default(),
+        };
+        let dataloader_train = DataLoaderBuilder::new(batcher.clone())
+            .batch_size(2)
+            .shuffle(7)
+            .build(dataset());
+        let batcher_valid = TestBatcher {
+            interrupter: None,
+            count: Default::default(),
+        };
+        let dataloader_valid = DataLoaderBuilder::new(batcher_valid)
+            .batch_size(4)
+            .build(dataset());
+
+        let learner = builder.build(model, AdamConfig::new().init(), 1e-2);
+        learner.fit(dataloader_train, dataloader_valid)
+    }
+
+    #[test]
+    fn resumed_training_should_be_identical_to_uninterrupted_training() {
+        let directory = tempfile::tempdir().unwrap();
+        let expected = train(&directory.path().join("full"), None, None, None);
+
+        // Stopped at the fifth batch of the second epoch, after its mid-epoch checkpoint at the
+        // fourth iteration
+        let directory = directory.path().join("resumed");
+        train(&directory, Some(6 + 5), None, None);
+        let state = directory.join("checkpoint/iteration/state-2.mpk");
+        assert!(state.exists(), "The mid-epoch checkpoint should be saved");
+        let resumed = train(&directory, None, Some(2), None);
+
+        resumed
+            .linear
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&expected.linear.weight.val().into_data(), true);
+        resumed
+            .linear
+            .bias
+            .unwrap()
+            .val()
+            .into_data()
+            .assert_eq(&expected.linear.bias.unwrap().val().into_data(), true);
+    }
+
+    #[test]
+    fn missing_mid_epoch_checkpoint_should_restart_the_epoch() {
+        let directory = tempfile::tempdir().unwrap();
+        let expected = train(&directory.path().join("full"), None, None, None);
+
+        // Stopped at the first batch of the second epoch, before any mid-epoch checkpoint
+        let directory = directory.path().join("resumed");
+        train(&directory, Some(6 + 1), None, None);
+        let state = directory.join("checkpoint/iteration/state-2.mpk");
+        assert!(!state.exists(), "No mid-epoch checkpoint should be saved");
+        let resumed = train(&directory, None, Some(2), None);
+
+        resumed
+            .linear
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&expected.linear.weight.val().into_data(), true);
+    }
+
+    #[test]
+    fn mid_epoch_checkpoint_should_be_saved_after_the_interval_with_accumulation() {
+        let directory = tempfile::tempdir().unwrap();
+        let expected = train(&directory.path().join("full"), None, None, Some(3));
+
+        // With 3 accumulated batches, the optimizer steps happen at the third and sixth
+        // iterations, so the checkpoint of the second interval is saved at the third iteration.
+        let directory = directory.path().join("resumed");
+        train(&directory, Some(6 + 4), None, Some(3));
+        let state = directory.join("checkpoint/iteration/state-2.mpk");
+        assert!(state.exists(), "The mid-epoch checkpoint should be saved");
+        let resumed = train(&directory, None, Some(2), Some(3));
+
+        resumed
+            .linear
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&expected.linear.weight.val().into_data(), true);
+    }
+}
@@ -0,0 +1,454 @@
+use super::{InMemoryMetricLogger, MetricLogger};
+use crate::metric::{MetricEntry, NumericEntry};
+use burn_core::module::{Module, ModuleVisitor, ParamId};
+use burn_core::tensor::{backend::Backend, Tensor};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The number of buckets of the histograms.
+const HISTOGRAM_BUCKETS: usize = 30;
+
+/// Writes summaries to a TensorBoard event file (`events.out.tfevents.*`).
+///
+/// The writer can be cloned to add histograms and texts to the same event file used by a
+/// [TensorBoard metric logger](TensorBoardMetricLogger), e.g. from a training step.
+#[derive(Clone)]
+pub struct TensorBoardWriter {
+    file: Arc<Mutex<File>>,
+    path: PathBuf,
+}
+
+/// The TensorBoard metric logger.
+///
+/// Numeric metrics are written as scalars at each iteration, along with the aggregated value of
+/// each epoch under the `epoch/` prefix, while other metrics are written as text summaries.
+/// Using a different directory for each split, such as `train` and `valid` in the same parent
+/// directory, displays them as separate runs.
+pub struct TensorBoardMetricLogger {
+    writer: TensorBoardWriter,
+    memory: InMemoryMetricLogger,
+    steps: HashMap<String, usize>,
+    numeric: HashSet<String>,
+}
+
+impl TensorBoardWriter {
+    /// Create a new event file in the given directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory.
+    ///
+    /// # Returns
+    ///
+    /// The TensorBoard writer.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory).ok();
+
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        let name = format!(
+            "events.out.tfevents.{}.{host}.{}",
+            wall_time() as u64,
+            std::process::id()
+        );
+        let path = directory.join(name);
+        let file = File::create(&path).expect("Can create the event file.");
+
+        let writer = Self {
+            file: Arc::new(Mutex::new(file)),
+            path,
+        };
+
+        let mut event = ProtoBuf::default();
+        event.double(1, wall_time());
+        event.string(3, "brain.Event:2");
+        writer.write_record(&event.0);
+
+        writer
+    }
+
+    /// The path of the event file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Add a scalar summary.
+    pub fn add_scalar(&self, tag: &str, value: f64, step: usize) {
+        let mut summary = ProtoBuf::default();
+        summary.string(1, tag);
+        summary.float(2, value as f32);
+
+        self.write_summary(&summary, step);
+    }
+
+    /// Add a histogram summary of the given values.
+    pub fn add_histogram(&self, tag: &str, values: &[f64], step: usize) {
+        let mut summary = ProtoBuf::default();
+        summary.string(1, tag);
+        summary.message(5, &histogram(values));
+
+        self.write_summary(&summary, step);
+    }
+
+    /// Add a text summary, rendered as markdown by TensorBoard.
+    pub fn add_text(&self, tag: &str, text: &str, step: usize) {
+        // DT_STRING scalar tensor.
+        let mut tensor = ProtoBuf::default();
+        tensor.int64(1, 7);
+        tensor.message(2, &[]);
+        tensor.bytes(8, text.as_bytes());
+
+        let mut plugin = ProtoBuf::default();
+        plugin.string(1, "text");
+
+        let mut metadata = ProtoBuf::default();
+        metadata.message(1, &plugin.0);
+
+        let mut summary = ProtoBuf::default();
+        summary.string(1, tag);
+        summary.message(8, &tensor.0);
+        summary.message(9, &metadata.0);
+
+        self.write_summary(&summary, step);
+    }
+
+    /// Add a histogram summary for each float parameter of the module.
+    ///
+    /// The parameters are tagged with their [id](ParamId), which is kept during training.
+    pub fn add_module_histograms<B: Backend, M: Module<B>>(&self, module: &M, step: usize) {
+        let mut visitor = HistogramVisitor { writer: self, step };
+        module.visit(&mut visitor);
+    }
+
+    fn write_summary(&self, value: &ProtoBuf, step: usize) {
+        let mut summary = ProtoBuf::default();
+        summary.message(1, &value.0);
+
+        let mut event = ProtoBuf::default();
+        event.double(1, wall_time());
+        event.int64(2, step as i64);
+        event.message(5, &summary.0);
+
+        self.write_record(&event.0);
+    }
+
+    /// Write a record with the TFRecord framing: the length, its masked CRC, the data and its
+    /// masked CRC.
+    fn write_record(&self, data: &[u8]) {
+        let length = (data.len() as u64).to_le_bytes();
+
+        let mut record = Vec::with_capacity(data.len() + 16);
+        record.extend_from_slice(&length);
+        record.extend_from_slice(&masked_crc32c(&length).to_le_bytes());
+        record.extend_from_slice(data);
+        record.extend_from_slice(&masked_crc32c(data).to_le_bytes());
+
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&record)
+            .expect("Can write to the event file.");
+    }
+}
+
+impl TensorBoardMetricLogger {
+    /// Create a new TensorBoard metric logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory where the event file is created.
+    ///
+    /// # Returns
+    ///
+    /// The TensorBoard metric logger.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            writer: TensorBoardWriter::new(directory),
+            memory: InMemoryMetricLogger::new(),
+            steps: HashMap::new(),
+            numeric: HashSet::new(),
+        }
+    }
+
+    /// A writer to the event file of the logger.
+    pub fn writer(&self) -> TensorBoardWriter {
+        self.writer.clone()
+    }
+}
+
+impl MetricLogger for TensorBoardMetricLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        let step = self.steps.entry(item.name.clone()).or_insert(0);
+        *step += 1;
+
+        match NumericEntry::deserialize(&item.serialize) {
+            Ok(NumericEntry::Value(value))
+            | Ok(NumericEntry::Aggregated(value, _))
+            | Ok(NumericEntry::Accumulated(value)) => {
+                self.writer.add_scalar(&item.name, value, *step);
+                self.numeric.insert(item.name.clone());
+            }
+            // Indented to be rendered as preformatted markdown.
+            Err(_) => self.writer.add_text(
+                &item.name,
+                &item
+                    .formatted
+                    .lines()
+                    .map(|line| format!("    {line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                *step,
+            ),
+        }
+
+        self.memory.log(item);
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        for name in self.numeric.iter() {
+            let entries = self.memory.read_numeric(name, epoch).unwrap_or_default();
+
+            if let Some(value) = NumericEntry::epoch_value(entries) {
+                self.writer
+                    .add_scalar(&format!("epoch/{name}"), value, epoch);
+            }
+        }
+
+        self.memory.end_epoch(epoch);
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.memory.read_numeric(name, epoch)
+    }
+}
+
+struct HistogramVisitor<'a> {
+    writer: &'a TensorBoardWriter,
+    step: usize,
+}
+
+impl<B: Backend> ModuleVisitor<B> for HistogramVisitor<'_> {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        let values = tensor.to_data().iter::<f64>().collect::<Vec<_>>();
+
+        self.writer
+            .add_histogram(&format!("parameters/{id}"), &values, self.step);
+    }
+}
+
+fn wall_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/// Encode a `HistogramProto` with evenly spaced buckets between the minimum and the maximum.
+fn histogram(values: &[f64]) -> Vec<u8> {
+    let values = values
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite())
+        .collect::<Vec<_>>();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut limits = Vec::new();
+    let mut counts = Vec::new();
+
+    if !values.is_empty() {
+        let num_buckets = if max > min { HISTOGRAM_BUCKETS } else { 1 };
+        let width = (max - min) / num_buckets as f64;
+
+        limits = (1..=num_buckets)
+            .map(|i| min + width * i as f64)
+            .collect::<Vec<_>>();
+        counts = vec![0.0; num_buckets];
+
+        for value in values.iter() {
+            let index = if width > 0.0 {
+                ((value - min) / width) as usize
+            } else {
+                0
+            };
+            counts[index.min(num_buckets - 1)] += 1.0;
+        }
+    }
+
+    let mut histogram = ProtoBuf::default();
+    histogram.double(1, if values.is_empty() { 0.0 } else { min });
+    histogram.double(2, if values.is_empty() { 0.0 } else { max });
+    histogram.double(3, values.len() as f64);
+    histogram.double(4, values.iter().sum());
+    histogram.double(5, values.iter().map(|v| v * v).sum());
+    histogram.packed_doubles(6, &limits);
+    histogram.packed_doubles(7, &counts);
+
+    histogram.0
+}
+
+/// Minimal protocol buffer encoder for the messages of the event files.
+#[derive(Default)]
+struct ProtoBuf(Vec<u8>);
+
+impl ProtoBuf {
+    const VARINT: u64 = 0;
+    const FIXED64: u64 = 1;
+    const LENGTH_DELIMITED: u64 = 2;
+    const FIXED32: u64 = 5;
+
+    fn key(&mut self, field: u64, wire_type: u64) {
+        self.varint((field << 3) | wire_type);
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8 & 0x7F) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn double(&mut self, field: u64, value: f64) {
+        self.key(field, Self::FIXED64);
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn float(&mut self, field: u64, value: f32) {
+        self.key(field, Self::FIXED32);
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn int64(&mut self, field: u64, value: i64) {
+        self.key(field, Self::VARINT);
+        self.varint(value as u64);
+    }
+
+    fn bytes(&mut self, field: u64, value: &[u8]) {
+        self.key(field, Self::LENGTH_DELIMITED);
+        self.varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    fn string(&mut self, field: u64, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    fn message(&mut self, field: u64, value: &[u8]) {
+        self.bytes(field, value);
+    }
+
+    fn packed_doubles(&mut self, field: u64, values: &[f64]) {
+        let bytes = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.bytes(field, &bytes);
+    }
+}
+
+/// The CRC32C (Castagnoli) checksum, masked as required by the TFRecord format.
+fn masked_crc32c(data: &[u8]) -> u32 {
+    let crc = crc32c(data);
+    crc.rotate_right(15).wrapping_add(0xa282_ead8)
+}
+
+fn crc32c(data: &[u8]) -> u32 {
+    const POLYNOMIAL: u32 = 0x82f6_3b78;
+
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (POLYNOMIAL & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32c() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+        assert_eq!(crc32c(b""), 0);
+    }
+
+    #[test]
+    fn test_varint() {
+        let mut buffer = ProtoBuf::default();
+        buffer.varint(300);
+        assert_eq!(buffer.0, vec![0xac, 0x02]);
+    }
+
+    #[test]
+    fn test_logger_writes_valid_records() {
+        let directory = std::env::temp_dir().join(format!(
+            "burn-train-tensorboard-{}-{}",
+            std::process::id(),
+            wall_time()
+        ));
+        let mut logger = TensorBoardMetricLogger::new(&directory);
+
+        logger.log(&MetricEntry::new(
+            "Loss".to_string(),
+            "1.0".to_string(),
+            NumericEntry::Aggregated(1.0, 2).serialize(),
+        ));
+        logger.log(&MetricEntry::new(
+            "Matrix".to_string(),
+            "1 0\n0 1".to_string(),
+            "[[1,0],[0,1]]".to_string(),
+        ));
+        logger.writer().add_histogram("values", &[1.0, 2.0, 3.0], 1);
+        logger.end_epoch(1);
+
+        let data = std::fs::read(logger.writer().path()).unwrap();
+        let mut records = Vec::new();
+        let mut position = 0;
+
+        while position < data.len() {
+            let length = &data[position..position + 8];
+            let length_crc =
+                u32::from_le_bytes(data[position + 8..position + 12].try_into().unwrap());
+            assert_eq!(masked_crc32c(length), length_crc);
+
+            let length = u64::from_le_bytes(length.try_into().unwrap()) as usize;
+            let record = &data[position + 12..position + 12 + length];
+            let record_crc = u32::from_le_bytes(
+                data[position + 12 + length..position + 16 + length]
+                    .try_into()
+                    .unwrap(),
+            );
+            assert_eq!(masked_crc32c(record), record_crc);
+
+            records.push(record.to_vec());
+            position += 16 + length;
+        }
+
+        let contains =
+            |record: &[u8], value: &[u8]| record.windows(value.len()).any(|window| window == value);
+
+        // File version, loss, matrix, histogram and epoch loss.
+        assert_eq!(records.len(), 5);
+        assert!(contains(&records[0], b"brain.Event:2"));
+        assert!(contains(&records[1], b"Loss"));
+        assert!(contains(&records[2], b"text"));
+        assert!(contains(&records[3], b"values"));
+        assert!(contains(&records[4], b"epoch/Loss"));
+        assert_eq!(
+            logger.read_numeric("Loss", 1).unwrap().len(),
+            1,
+            "Should keep the numeric entries of the epoch."
+        );
+
+        std::fs::remove_dir_all(directory).ok();
+    }
+}
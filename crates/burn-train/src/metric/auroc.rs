@@ -0,0 +1,389 @@
+use core::marker::PhantomData;
+
+use super::{format_float, ClassReduction, MetricEntry, MetricMetadata, NumericEntry};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::{backend::Backend, Bool, Tensor};
+
+/// The area under the receiver operating characteristic curve (AUROC), computed for each class
+/// against all the others.
+///
+/// Contrary to most metrics, the AUROC can't be averaged over batches: the scores and targets are
+/// accumulated during the whole epoch and the value is always computed over all the items seen
+/// since the last clear.
+///
+/// The accumulated items are kept in a few runs sorted by score, so each batch is only sorted on
+/// its own and its items are ranked against the accumulated ones with binary searches, the value
+/// being updated without going over all the items of the epoch.
+pub struct AurocMetric<B: Backend> {
+    /// The ranking of the items of each class, or of all classes pooled together with
+    /// [micro](ClassReduction::Micro) reduction.
+    rankings: Vec<Ranking>,
+    class_reduction: ClassReduction,
+    current: f64,
+    _b: PhantomData<B>,
+}
+
+/// The [AUROC metric](AurocMetric) input type.
+#[derive(new)]
+pub struct AurocInput<B: Backend> {
+    /// The scores (batch_size, num_classes), e.g. the probabilities of each class.
+    scores: Tensor<B, 2>,
+    /// The one-hot or multi-hot encoded targets (batch_size, num_classes).
+    targets: Tensor<B, 2, Bool>,
+}
+
+impl<B: Backend> AurocMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how the AUROC of each class is reduced.
+    ///
+    /// With [micro](ClassReduction::Micro) reduction, the scores of all classes are pooled
+    /// together before computing a single AUROC.
+    pub fn with_class_reduction(mut self, class_reduction: ClassReduction) -> Self {
+        self.class_reduction = class_reduction;
+        self
+    }
+
+    fn compute(&self) -> f64 {
+        if let ClassReduction::Micro = self.class_reduction {
+            return self.rankings[0].auroc().unwrap_or(f64::NAN);
+        }
+
+        let mut values = Vec::with_capacity(self.rankings.len());
+        let mut support = Vec::with_capacity(self.rankings.len());
+
+        // Classes without positive or negative targets have an undefined AUROC and are skipped.
+        for ranking in self.rankings.iter() {
+            if let Some(value) = ranking.auroc() {
+                values.push(value);
+                support.push(ranking.num_positive as f64);
+            }
+        }
+
+        if values.is_empty() {
+            return f64::NAN;
+        }
+
+        let ones = vec![1.0; values.len()];
+        self.class_reduction.reduce(&values, &ones, &support)
+    }
+}
+
+impl<B: Backend> Default for AurocMetric<B> {
+    /// Creates a new metric instance with default values.
+    fn default() -> Self {
+        Self {
+            rankings: Vec::new(),
+            class_reduction: ClassReduction::default(),
+            current: f64::NAN,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for AurocMetric<B> {
+    const NAME: &'static str = "AUROC";
+
+    type Input = AurocInput<B>;
+
+    fn update(&mut self, input: &AurocInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [_batch_size, num_classes] = input.scores.dims();
+        let num_groups = match self.class_reduction {
+            ClassReduction::Micro => 1,
+            _ => num_classes,
+        };
+
+        if self.rankings.len() != num_groups {
+            self.rankings = vec![Ranking::default(); num_groups];
+        }
+
+        let scores = input.scores.clone().into_data();
+        let targets = input.targets.clone().into_data();
+        let mut batch = vec![Vec::new(); num_groups];
+
+        for (i, item) in scores.iter::<f64>().zip(targets.iter::<bool>()).enumerate() {
+            batch[i % num_classes % num_groups].push(item);
+        }
+
+        for (ranking, batch) in self.rankings.iter_mut().zip(batch) {
+            ranking.add(batch);
+        }
+
+        self.current = self.compute();
+
+        // The value is computed over all the items of the epoch so far, so the last entry is the
+        // value of the epoch.
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {}", format_float(self.current, 4)),
+            NumericEntry::Accumulated(self.current).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.rankings.clear();
+        self.current = f64::NAN;
+    }
+}
+
+impl<B: Backend> Numeric for AurocMetric<B> {
+    fn value(&self) -> f64 {
+        self.current
+    }
+}
+
+/// The `(score, target)` items of a class, kept in runs sorted by score whose sizes decrease
+/// geometrically, along with the number of pairs of a positive and a negative item ranked in the
+/// right order.
+#[derive(Clone, Default)]
+struct Ranking {
+    runs: Vec<Run>,
+    num_positive: usize,
+    num_negative: usize,
+    /// The number of pairs of a positive and a negative item where the positive item has the
+    /// higher score, ties counting as one half.
+    concordant: f64,
+}
+
+impl Ranking {
+    /// Add the items of a batch, counting their pairs with the items already added.
+    fn add(&mut self, mut batch: Vec<(f64, bool)>) {
+        batch.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        for item in batch.iter() {
+            for run in self.runs.iter() {
+                self.concordant += run.concordant(*item);
+            }
+        }
+        self.concordant += concordant(&batch);
+
+        let num_positive = batch.iter().filter(|(_, target)| *target).count();
+        self.num_positive += num_positive;
+        self.num_negative += batch.len() - num_positive;
+
+        // Runs of similar sizes are merged, so there are only a logarithmic number of runs and
+        // each item is merged a logarithmic number of times.
+        let mut items = batch;
+        while self
+            .runs
+            .last()
+            .is_some_and(|run| run.items.len() <= items.len())
+        {
+            let run = self.runs.pop().unwrap();
+            items = merge(run.items, items);
+        }
+        self.runs.push(Run::new(items));
+    }
+
+    /// The probability that a positive item is ranked above a negative one, counting ties as one
+    /// half.
+    ///
+    /// Returns `None` when there are no positive or no negative targets.
+    fn auroc(&self) -> Option<f64> {
+        if self.num_positive == 0 || self.num_negative == 0 {
+            return None;
+        }
+
+        Some(self.concordant / (self.num_positive as f64 * self.num_negative as f64))
+    }
+}
+
+/// Items sorted by score, with the number of positive items before each index.
+#[derive(Clone)]
+struct Run {
+    items: Vec<(f64, bool)>,
+    positives: Vec<usize>,
+}
+
+impl Run {
+    fn new(items: Vec<(f64, bool)>) -> Self {
+        let mut positives = Vec::with_capacity(items.len() + 1);
+        positives.push(0);
+        for (_, target) in items.iter() {
+            positives.push(positives[positives.len() - 1] + *target as usize);
+        }
+
+        Self { items, positives }
+    }
+
+    /// The number of pairs of the item with the items of the opposite target ranked in the right
+    /// order, ties counting as one half.
+    fn concordant(&self, (score, target): (f64, bool)) -> f64 {
+        let start = self
+            .items
+            .partition_point(|(other, _)| other.total_cmp(&score).is_lt());
+        let end = self
+            .items
+            .partition_point(|(other, _)| other.total_cmp(&score).is_le());
+
+        // The number of items of the opposite target between the two indices.
+        let opposite = |from: usize, to: usize| {
+            let positives = self.positives[to] - self.positives[from];
+            match target {
+                true => to - from - positives,
+                false => positives,
+            }
+        };
+
+        // A positive item is ranked above the negative items with a lower score, and a negative
+        // one below the positive items with a higher score.
+        let ranked = match target {
+            true => opposite(0, start),
+            false => opposite(end, self.items.len()),
+        };
+
+        ranked as f64 + opposite(start, end) as f64 / 2.0
+    }
+}
+
+/// Merge two lists of items sorted by score.
+fn merge(lhs: Vec<(f64, bool)>, rhs: Vec<(f64, bool)>) -> Vec<(f64, bool)> {
+    let mut merged = Vec::with_capacity(lhs.len() + rhs.len());
+    let mut lhs = lhs.into_iter().peekable();
+    let mut rhs = rhs.into_iter().peekable();
+
+    while let (Some(a), Some(b)) = (lhs.peek(), rhs.peek()) {
+        if b.0.total_cmp(&a.0).is_lt() {
+            merged.extend(rhs.next());
+        } else {
+            merged.extend(lhs.next());
+        }
+    }
+
+    merged.extend(lhs);
+    merged.extend(rhs);
+    merged
+}
+
+/// The number of pairs of a positive and a negative item ranked in the right order among items
+/// sorted by score, ties counting as one half.
+fn concordant(items: &[(f64, bool)]) -> f64 {
+    let num_positive = items.iter().filter(|(_, target)| *target).count() as f64;
+
+    // Sum of the (1-based, tie averaged) ranks of the positive items.
+    let mut rank_sum = 0.0;
+    let mut start = 0;
+
+    while start < items.len() {
+        let mut end = start + 1;
+        while end < items.len() && items[end].0.total_cmp(&items[start].0).is_eq() {
+            end += 1;
+        }
+
+        let rank = (start + 1 + end) as f64 / 2.0;
+        let positives = items[start..end].iter().filter(|(_, t)| *t).count();
+        rank_sum += rank * positives as f64;
+
+        start = end;
+    }
+
+    rank_sum - num_positive * (num_positive + 1.0) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn auroc(items: &[(f64, bool)]) -> Option<f64> {
+        let mut ranking = Ranking::default();
+        ranking.add(items.to_vec());
+        ranking.auroc()
+    }
+
+    #[test]
+    fn test_auroc_binary() {
+        assert_eq!(
+            auroc(&[(0.1, false), (0.35, true), (0.4, false), (0.8, true)]),
+            Some(0.75)
+        );
+        assert_eq!(auroc(&[(0.5, true), (0.5, false)]), Some(0.5));
+        assert_eq!(auroc(&[(0.1, true), (0.2, true)]), None);
+    }
+
+    #[test]
+    fn test_auroc_ranks_batches_against_accumulated_items() {
+        // Scores with many ties, added in batches of various sizes.
+        let items = (0..500)
+            .map(|i: usize| ((i * 37 % 11) as f64 / 10.0, i * 13 % 3 == 1))
+            .collect::<Vec<_>>();
+        let mut ranking = Ranking::default();
+        let mut start = 0;
+        for size in (1..).cycle() {
+            let end = usize::min(start + size % 17, items.len());
+            ranking.add(items[start..end].to_vec());
+            start = end;
+            if start == items.len() {
+                break;
+            }
+        }
+
+        let mut concordant = 0.0;
+        let mut pairs = 0.0;
+        for (positive, _) in items.iter().filter(|(_, target)| *target) {
+            for (negative, _) in items.iter().filter(|(_, target)| !*target) {
+                concordant += match positive.total_cmp(negative) {
+                    core::cmp::Ordering::Greater => 1.0,
+                    core::cmp::Ordering::Equal => 0.5,
+                    core::cmp::Ordering::Less => 0.0,
+                };
+                pairs += 1.0;
+            }
+        }
+
+        assert!(ranking.runs.len() <= 10, "{}", ranking.runs.len());
+        assert!((ranking.auroc().unwrap() - concordant / pairs).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge_sorted_items() {
+        let merged = merge(
+            vec![(0.1, false), (0.5, true), (0.9, true)],
+            vec![(0.2, true), (0.5, false), (1.0, false)],
+        );
+
+        assert_eq!(
+            merged,
+            vec![
+                (0.1, false),
+                (0.2, true),
+                (0.5, true),
+                (0.5, false),
+                (0.9, true),
+                (1.0, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auroc_accumulates_over_batches() {
+        let device = Default::default();
+        let mut metric = AurocMetric::<TestBackend>::new();
+
+        let _entry = metric.update(
+            &AurocInput::new(
+                Tensor::from_data([[0.9, 0.1], [0.6, 0.4]], &device),
+                Tensor::from_data([[true, false], [false, true]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+        assert_eq!(metric.value(), 1.0);
+
+        let _entry = metric.update(
+            &AurocInput::new(
+                Tensor::from_data([[0.35, 0.65], [0.2, 0.8]], &device),
+                Tensor::from_data([[false, true], [true, false]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+        // Class 0 scores [0.9, 0.6, 0.35, 0.2] with targets [1, 0, 0, 1] -> 0.5
+        // Class 1 scores [0.1, 0.4, 0.65, 0.8] with targets [0, 1, 1, 0] -> 0.5
+        assert_eq!(metric.value(), 0.5);
+
+        metric.clear();
+        assert!(metric.value().is_nan());
+    }
+}
@@ -90,6 +90,9 @@ pub enum NumericEntry {
     Value(f64),
     /// Aggregated numeric (value, number of elements).
     Aggregated(f64, usize),
+    /// Numeric value accumulated since the start of the epoch, the last one being the value of
+    /// the whole epoch.
+    Accumulated(f64),
 }
 
 impl NumericEntry {
@@ -97,6 +100,7 @@ impl NumericEntry {
         match self {
             Self::Value(v) => v.to_string(),
             Self::Aggregated(v, n) => format!("{v},{n}"),
+            Self::Accumulated(v) => format!("{v},running"),
         }
     }
 
@@ -111,6 +115,12 @@ impl NumericEntry {
                 Ok(value) => Ok(NumericEntry::Value(value)),
                 Err(err) => Err(err.to_string()),
             }
+        } else if num_values == 2 && values[1] == "running" {
+            // Accumulated numeric (value)
+            match values[0].parse::<f64>() {
+                Ok(value) => Ok(NumericEntry::Accumulated(value)),
+                Err(err) => Err(err.to_string()),
+            }
         } else if num_values == 2 {
             // Aggregated numeric (value, number of elements)
             let (value, numel) = (values[0], values[1]);
@@ -125,6 +135,38 @@ impl NumericEntry {
             Err("Invalid number of values for numeric entry".to_string())
         }
     }
+
+    /// The value of an epoch from its entries.
+    ///
+    /// The value of accumulated entries is the last one, otherwise it is the mean of the entries
+    /// weighted by their number of elements.
+    pub(crate) fn epoch_value(entries: impl IntoIterator<Item = Self>) -> Option<f64> {
+        let mut accumulated = None;
+        let mut sum = 0.0;
+        let mut count = 0;
+
+        for entry in entries {
+            match entry {
+                Self::Value(v) => {
+                    sum += v;
+                    count += 1;
+                }
+                // Right now the mean is the only aggregate available, so we can assume that the
+                // sum of an entry corresponds to (value * number of elements)
+                Self::Aggregated(v, n) => {
+                    sum += v * n as f64;
+                    count += n;
+                }
+                Self::Accumulated(v) => accumulated = Some(v),
+            }
+        }
+
+        match accumulated {
+            Some(value) => Some(value),
+            None if count > 0 => Some(sum / count as f64),
+            None => None,
+        }
+    }
 }
 
 /// Format a float with the given precision. Will use scientific notation if necessary.
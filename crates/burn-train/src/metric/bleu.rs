@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::{MetricEntry, MetricMetadata, TextInput};
+use crate::metric::{Metric, Numeric};
+
+/// The corpus BLEU score metric, with a single reference for each prediction.
+///
+/// Texts are tokenized on whitespace. The clipped n-gram matches as well as the prediction and
+/// reference lengths are accumulated over the epoch, so the score is computed over the whole
+/// corpus instead of averaging the score of each sentence or batch.
+pub struct BleuMetric {
+    state: AccumulatedMetricState,
+    max_order: usize,
+    counts: NgramCounts,
+}
+
+#[derive(Default, Clone)]
+struct NgramCounts {
+    /// The number of clipped matching n-grams for each order.
+    matches: Vec<usize>,
+    /// The number of predicted n-grams for each order.
+    totals: Vec<usize>,
+    prediction_length: usize,
+    reference_length: usize,
+}
+
+impl NgramCounts {
+    fn new(input: &TextInput, max_order: usize) -> Self {
+        let mut counts = Self {
+            matches: vec![0; max_order],
+            totals: vec![0; max_order],
+            prediction_length: 0,
+            reference_length: 0,
+        };
+
+        for (prediction, reference) in input.pairs() {
+            let prediction = prediction.split_whitespace().collect::<Vec<_>>();
+            let reference = reference.split_whitespace().collect::<Vec<_>>();
+
+            counts.prediction_length += prediction.len();
+            counts.reference_length += reference.len();
+
+            for order in 1..=max_order {
+                let reference_ngrams = ngrams(&reference, order);
+
+                for (ngram, count) in ngrams(&prediction, order) {
+                    let reference_count = reference_ngrams.get(&ngram).copied().unwrap_or(0);
+
+                    counts.matches[order - 1] += count.min(reference_count);
+                    counts.totals[order - 1] += count;
+                }
+            }
+        }
+
+        counts
+    }
+
+    fn add(&mut self, other: &Self) {
+        let add = |a: &mut [usize], b: &[usize]| a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+
+        add(&mut self.matches, &other.matches);
+        add(&mut self.totals, &other.totals);
+        self.prediction_length += other.prediction_length;
+        self.reference_length += other.reference_length;
+    }
+
+    fn bleu(&self) -> f64 {
+        if self.prediction_length == 0 {
+            return 0.0;
+        }
+
+        let mut log_precision = 0.0;
+
+        for (matches, total) in self.matches.iter().zip(self.totals.iter()) {
+            if *matches == 0 {
+                return 0.0;
+            }
+
+            log_precision += (*matches as f64 / *total as f64).ln();
+        }
+        log_precision /= self.matches.len() as f64;
+
+        let brevity_penalty = if self.prediction_length >= self.reference_length {
+            1.0
+        } else {
+            (1.0 - self.reference_length as f64 / self.prediction_length as f64).exp()
+        };
+
+        100.0 * brevity_penalty * log_precision.exp()
+    }
+}
+
+fn ngrams<'a>(tokens: &[&'a str], order: usize) -> HashMap<Vec<&'a str>, usize> {
+    let mut counts = HashMap::new();
+
+    for ngram in tokens.windows(order) {
+        *counts.entry(ngram.to_vec()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+impl BleuMetric {
+    /// Creates the metric, using n-grams up to 4 tokens.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum n-gram order.
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        self.max_order = max_order;
+        self.counts = NgramCounts::default();
+        self
+    }
+}
+
+impl Default for BleuMetric {
+    /// Creates a new metric instance with default values.
+    fn default() -> Self {
+        Self {
+            state: AccumulatedMetricState::default(),
+            max_order: 4,
+            counts: NgramCounts::default(),
+        }
+    }
+}
+
+impl Metric for BleuMetric {
+    const NAME: &'static str = "BLEU";
+
+    type Input = TextInput;
+
+    fn update(&mut self, input: &TextInput, _metadata: &MetricMetadata) -> MetricEntry {
+        let counts = NgramCounts::new(input, self.max_order);
+
+        if self.counts.matches.is_empty() {
+            self.counts = counts.clone();
+        } else {
+            self.counts.add(&counts);
+        }
+
+        self.state.update(
+            self.counts.bleu(),
+            counts.bleu(),
+            FormatOptions::new(Self::NAME).precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.counts = NgramCounts::default();
+    }
+}
+
+impl Numeric for BleuMetric {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(predictions: &[&str], references: &[&str]) -> TextInput {
+        TextInput::new(
+            predictions.iter().map(|s| s.to_string()).collect(),
+            references.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_bleu_perfect_match() {
+        let mut metric = BleuMetric::new();
+
+        let _entry = metric.update(
+            &input(&["the cat sat on the mat"], &["the cat sat on the mat"]),
+            &MetricMetadata::fake(),
+        );
+
+        assert!((metric.value() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bleu_accumulates_over_batches() {
+        let mut metric = BleuMetric::new().with_max_order(2);
+
+        // No bigram matches: the BLEU of this batch alone is zero.
+        let _entry = metric.update(&input(&["a b"], &["b a"]), &MetricMetadata::fake());
+        assert_eq!(0.0, metric.value());
+
+        let _entry = metric.update(&input(&["c d e"], &["c d e"]), &MetricMetadata::fake());
+
+        // Unigrams: 5 / 5, bigrams: 2 / 3, same lengths.
+        let expected = 100.0 * (2.0f64 / 3.0).sqrt();
+        assert!((metric.value() - expected).abs() < 1e-9);
+    }
+}
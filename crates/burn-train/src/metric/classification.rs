@@ -0,0 +1,80 @@
+/// How the per-class values of a classification metric are combined into a single value.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassReduction {
+    /// Compute the metric from the counts summed over all classes.
+    Micro,
+    /// Compute the metric for each class, then take the unweighted mean.
+    #[default]
+    Macro,
+    /// Compute the metric for each class, then take the mean weighted by the number of targets
+    /// of each class.
+    Weighted,
+}
+
+/// How the model outputs are turned into predicted classes.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum DecisionRule {
+    /// The class with the highest output is predicted, used for single-label classification.
+    #[default]
+    Argmax,
+    /// Every class with an output greater than the threshold is predicted, used for multi-label
+    /// classification.
+    Threshold(f64),
+    /// The `k` classes with the highest outputs are predicted.
+    TopK(usize),
+}
+
+impl ClassReduction {
+    /// Reduce the per-class ratios `numerators / denominators`.
+    ///
+    /// A ratio with a null denominator is considered to be zero.
+    pub(crate) fn reduce(&self, numerators: &[f64], denominators: &[f64], support: &[f64]) -> f64 {
+        let ratio = |num: f64, den: f64| if den == 0.0 { 0.0 } else { num / den };
+
+        match self {
+            Self::Micro => ratio(numerators.iter().sum(), denominators.iter().sum()),
+            Self::Macro => {
+                let sum: f64 = numerators
+                    .iter()
+                    .zip(denominators)
+                    .map(|(num, den)| ratio(*num, *den))
+                    .sum();
+                ratio(sum, numerators.len() as f64)
+            }
+            Self::Weighted => {
+                let sum: f64 = numerators
+                    .iter()
+                    .zip(denominators)
+                    .zip(support)
+                    .map(|((num, den), support)| ratio(*num, *den) * support)
+                    .sum();
+                ratio(sum, support.iter().sum())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_reduction() {
+        let numerators = [1.0, 0.0, 3.0];
+        let denominators = [2.0, 0.0, 4.0];
+        let support = [1.0, 0.0, 3.0];
+
+        assert_eq!(
+            ClassReduction::Micro.reduce(&numerators, &denominators, &support),
+            4.0 / 6.0
+        );
+        assert_eq!(
+            ClassReduction::Macro.reduce(&numerators, &denominators, &support),
+            (0.5 + 0.75) / 3.0
+        );
+        assert_eq!(
+            ClassReduction::Weighted.reduce(&numerators, &denominators, &support),
+            (0.5 + 0.75 * 3.0) / 4.0
+        );
+    }
+}
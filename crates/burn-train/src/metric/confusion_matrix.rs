@@ -0,0 +1,200 @@
+use core::marker::PhantomData;
+
+use super::{MetricEntry, MetricMetadata};
+use crate::metric::Metric;
+use burn_core::tensor::{backend::Backend, Int, Tensor};
+
+/// The confusion matrix metric, counting for each target class how many times each class was
+/// predicted over the epoch.
+///
+/// The matrix is displayed as text, with one row per target class and one column per predicted
+/// class.
+///
+/// # Panics
+///
+/// The update panics when a target isn't in the range of the classes of the outputs.
+pub struct ConfusionMatrixMetric<B: Backend> {
+    matrix: Vec<Vec<usize>>,
+    class_names: Option<Vec<String>>,
+    _b: PhantomData<B>,
+}
+
+/// The [confusion matrix metric](ConfusionMatrixMetric) input type.
+#[derive(new)]
+pub struct ConfusionMatrixInput<B: Backend> {
+    /// The outputs (batch_size, num_classes).
+    outputs: Tensor<B, 2>,
+    /// The labels (batch_size).
+    targets: Tensor<B, 1, Int>,
+}
+
+impl<B: Backend> ConfusionMatrixMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the names of the classes displayed instead of their index.
+    pub fn with_class_names(mut self, class_names: Vec<String>) -> Self {
+        self.class_names = Some(class_names);
+        self
+    }
+
+    /// The confusion matrix accumulated since the last clear, indexed by `[target][prediction]`.
+    pub fn matrix(&self) -> &[Vec<usize>] {
+        &self.matrix
+    }
+
+    fn format(&self) -> String {
+        let labels = (0..self.matrix.len())
+            .map(|class| match &self.class_names {
+                Some(names) if class < names.len() => names[class].clone(),
+                _ => class.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let header = "target \\ pred";
+        let width_labels = labels
+            .iter()
+            .map(|label| label.len())
+            .chain([header.len()])
+            .max()
+            .unwrap_or_default();
+        let width_counts = self
+            .matrix
+            .iter()
+            .flatten()
+            .map(|count| count.to_string().len())
+            .chain(labels.iter().map(|label| label.len()))
+            .max()
+            .unwrap_or_default();
+
+        let mut formatted = format!("{header:>width_labels$} |");
+        for label in labels.iter() {
+            formatted += &format!(" {label:>width_counts$}");
+        }
+
+        for (label, row) in labels.iter().zip(self.matrix.iter()) {
+            formatted += &format!("\n{label:>width_labels$} |");
+            for count in row {
+                formatted += &format!(" {count:>width_counts$}");
+            }
+        }
+
+        formatted
+    }
+
+    fn serialize(&self) -> String {
+        let rows = self
+            .matrix
+            .iter()
+            .map(|row| {
+                let counts = row.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+                format!("[{}]", counts.join(","))
+            })
+            .collect::<Vec<_>>();
+
+        format!("[{}]", rows.join(","))
+    }
+}
+
+impl<B: Backend> Default for ConfusionMatrixMetric<B> {
+    /// Creates a new metric instance with default values.
+    fn default() -> Self {
+        Self {
+            matrix: Vec::new(),
+            class_names: None,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for ConfusionMatrixMetric<B> {
+    const NAME: &'static str = "Confusion Matrix";
+
+    type Input = ConfusionMatrixInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ConfusionMatrixInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        let [batch_size, num_classes] = input.outputs.dims();
+
+        if self.matrix.len() != num_classes {
+            self.matrix = vec![vec![0; num_classes]; num_classes];
+        }
+
+        let predictions = input.outputs.clone().argmax(1).reshape([batch_size]);
+        let predictions = predictions.into_data();
+        let targets = input.targets.clone().into_data();
+
+        for (target, prediction) in targets.iter::<i64>().zip(predictions.iter::<i64>()) {
+            assert!(
+                (0..num_classes as i64).contains(&target),
+                "The target {target} is not a valid class, the outputs have {num_classes} classes"
+            );
+
+            self.matrix[target as usize][prediction as usize] += 1;
+        }
+
+        MetricEntry::new(Self::NAME.to_string(), self.format(), self.serialize())
+    }
+
+    fn clear(&mut self) {
+        self.matrix.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_confusion_matrix() {
+        let device = Default::default();
+        let mut metric = ConfusionMatrixMetric::<TestBackend>::new();
+        let outputs = Tensor::from_data(
+            [
+                [0.7, 0.2, 0.1],
+                [0.1, 0.8, 0.1],
+                [0.2, 0.2, 0.6],
+                [0.5, 0.4, 0.1],
+            ],
+            &device,
+        );
+        let targets = Tensor::from_data([0, 1, 1, 2], &device);
+        let input = ConfusionMatrixInput::new(outputs, targets);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        let entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(
+            metric.matrix(),
+            &[vec![2, 0, 0], vec![0, 2, 2], vec![2, 0, 0]]
+        );
+        assert_eq!(entry.serialize, "[[2,0,0],[0,2,2],[2,0,0]]");
+        assert_eq!(
+            entry.formatted,
+            "target \\ pred | 0 1 2\n            0 | 2 0 0\n            1 | 0 2 2\n            2 | 2 0 0"
+        );
+
+        metric.clear();
+        assert!(metric.matrix().is_empty());
+    }
+
+    #[test]
+    #[should_panic = "The target 3 is not a valid class"]
+    fn test_confusion_matrix_invalid_target() {
+        let device = Default::default();
+        let mut metric = ConfusionMatrixMetric::<TestBackend>::new();
+        let outputs = Tensor::from_data([[0.7, 0.2, 0.1], [0.1, 0.8, 0.1]], &device);
+        let targets = Tensor::from_data([0, 3], &device);
+
+        let _entry = metric.update(
+            &ConfusionMatrixInput::new(outputs, targets),
+            &MetricMetadata::fake(),
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use super::{ClassReduction, DecisionRule};
+use burn_core::tensor::{backend::Backend, Bool, Tensor};
+
+/// The input type of the [precision](super::PrecisionMetric), [recall](super::RecallMetric)
+/// and [F1 score](super::F1ScoreMetric) metrics.
+#[derive(new)]
+pub struct ConfusionStatsInput<B: Backend> {
+    /// The outputs (batch_size, num_classes).
+    outputs: Tensor<B, 2>,
+    /// The one-hot or multi-hot encoded targets (batch_size, num_classes).
+    targets: Tensor<B, 2, Bool>,
+}
+
+/// The number of true positives, false positives, false negatives and targets of each class.
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct ConfusionStats {
+    pub batch_size: usize,
+    pub true_positive: Vec<f64>,
+    pub false_positive: Vec<f64>,
+    pub false_negative: Vec<f64>,
+    pub support: Vec<f64>,
+}
+
+impl ConfusionStats {
+    /// Count the confusion statistics of each class for the given input.
+    pub fn new<B: Backend>(input: &ConfusionStatsInput<B>, rule: DecisionRule) -> Self {
+        let [batch_size, num_classes] = input.outputs.dims();
+        let predictions = predictions(input.outputs.clone(), rule)
+            .into_data()
+            .iter::<bool>()
+            .collect::<Vec<_>>();
+        let targets = input
+            .targets
+            .clone()
+            .into_data()
+            .iter::<bool>()
+            .collect::<Vec<_>>();
+
+        let mut stats = Self {
+            batch_size,
+            true_positive: vec![0.0; num_classes],
+            false_positive: vec![0.0; num_classes],
+            false_negative: vec![0.0; num_classes],
+            support: vec![0.0; num_classes],
+        };
+
+        for i in 0..batch_size * num_classes {
+            let class = i % num_classes;
+
+            match (predictions[i], targets[i]) {
+                (true, true) => stats.true_positive[class] += 1.0,
+                (true, false) => stats.false_positive[class] += 1.0,
+                (false, true) => stats.false_negative[class] += 1.0,
+                (false, false) => {}
+            }
+
+            if targets[i] {
+                stats.support[class] += 1.0;
+            }
+        }
+
+        stats
+    }
+
+    /// Add the statistics of another batch, to accumulate them over an epoch.
+    pub fn add(&mut self, other: &Self) {
+        fn add(values: &mut Vec<f64>, other: &[f64]) {
+            if values.len() < other.len() {
+                values.resize(other.len(), 0.0);
+            }
+            values
+                .iter_mut()
+                .zip(other)
+                .for_each(|(value, other)| *value += other);
+        }
+
+        self.batch_size += other.batch_size;
+        add(&mut self.true_positive, &other.true_positive);
+        add(&mut self.false_positive, &other.false_positive);
+        add(&mut self.false_negative, &other.false_negative);
+        add(&mut self.support, &other.support);
+    }
+
+    /// The precision, reduced over the classes.
+    pub fn precision(&self, reduction: ClassReduction) -> f64 {
+        reduction.reduce(
+            &self.true_positive,
+            &self.predicted_positive(),
+            &self.support,
+        )
+    }
+
+    /// The recall, reduced over the classes.
+    pub fn recall(&self, reduction: ClassReduction) -> f64 {
+        reduction.reduce(&self.true_positive, &self.support, &self.support)
+    }
+
+    /// The F1 score, reduced over the classes.
+    pub fn f1_score(&self, reduction: ClassReduction) -> f64 {
+        // F1 = 2 * tp / (2 * tp + fp + fn)
+        let numerators = self
+            .true_positive
+            .iter()
+            .map(|tp| 2.0 * tp)
+            .collect::<Vec<_>>();
+        let denominators = self
+            .predicted_positive()
+            .iter()
+            .zip(&self.support)
+            .map(|(predicted, support)| predicted + support)
+            .collect::<Vec<_>>();
+
+        reduction.reduce(&numerators, &denominators, &self.support)
+    }
+
+    /// The sum of true positives and false positives of each class.
+    pub fn predicted_positive(&self) -> Vec<f64> {
+        self.true_positive
+            .iter()
+            .zip(&self.false_positive)
+            .map(|(tp, fp)| tp + fp)
+            .collect()
+    }
+}
+
+/// Turn the outputs into a mask of the predicted classes (batch_size, num_classes).
+fn predictions<B: Backend>(outputs: Tensor<B, 2>, rule: DecisionRule) -> Tensor<B, 2, Bool> {
+    match rule {
+        DecisionRule::Argmax => top_k(outputs, 1),
+        DecisionRule::Threshold(threshold) => outputs.greater_elem(threshold),
+        DecisionRule::TopK(k) => top_k(outputs, k),
+    }
+}
+
+fn top_k<B: Backend>(outputs: Tensor<B, 2>, k: usize) -> Tensor<B, 2, Bool> {
+    let [batch_size, num_classes] = outputs.dims();
+    let device = outputs.device();
+    let (_, indices) = outputs.topk_with_indices(k, 1);
+
+    Tensor::<B, 2>::zeros([batch_size, num_classes], &device)
+        .scatter(1, indices, Tensor::ones([batch_size, k], &device))
+        .bool()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_confusion_stats_argmax() {
+        let device = Default::default();
+        let outputs = Tensor::<TestBackend, 2>::from_data(
+            [
+                [0.7, 0.2, 0.1],
+                [0.1, 0.8, 0.1],
+                [0.2, 0.2, 0.6],
+                [0.5, 0.4, 0.1],
+            ],
+            &device,
+        );
+        let targets = Tensor::from_data(
+            [
+                [true, false, false],
+                [false, true, false],
+                [false, true, false],
+                [false, false, true],
+            ],
+            &device,
+        );
+
+        let stats = ConfusionStats::new(
+            &ConfusionStatsInput::new(outputs, targets),
+            DecisionRule::Argmax,
+        );
+
+        assert_eq!(stats.true_positive, vec![1.0, 1.0, 0.0]);
+        assert_eq!(stats.false_positive, vec![1.0, 0.0, 1.0]);
+        assert_eq!(stats.false_negative, vec![0.0, 1.0, 1.0]);
+        assert_eq!(stats.support, vec![1.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_confusion_stats_threshold() {
+        let device = Default::default();
+        let outputs =
+            Tensor::<TestBackend, 2>::from_data([[0.7, 0.6, 0.1], [0.1, 0.8, 0.9]], &device);
+        let targets = Tensor::from_data([[true, false, false], [false, true, false]], &device);
+
+        let stats = ConfusionStats::new(
+            &ConfusionStatsInput::new(outputs, targets),
+            DecisionRule::Threshold(0.5),
+        );
+
+        assert_eq!(stats.true_positive, vec![1.0, 1.0, 0.0]);
+        assert_eq!(stats.false_positive, vec![0.0, 1.0, 1.0]);
+        assert_eq!(stats.false_negative, vec![0.0, 0.0, 0.0]);
+        assert_eq!(stats.support, vec![1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_confusion_stats_top_k() {
+        let device = Default::default();
+        let outputs =
+            Tensor::<TestBackend, 2>::from_data([[0.7, 0.2, 0.1], [0.1, 0.3, 0.6]], &device);
+        let targets = Tensor::from_data([[false, true, false], [true, false, false]], &device);
+
+        let stats = ConfusionStats::new(
+            &ConfusionStatsInput::new(outputs, targets),
+            DecisionRule::TopK(2),
+        );
+
+        assert_eq!(stats.true_positive, vec![0.0, 1.0, 0.0]);
+        assert_eq!(stats.false_positive, vec![1.0, 1.0, 1.0]);
+        assert_eq!(stats.false_negative, vec![1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_confusion_stats_add() {
+        let device = Default::default();
+        let input = |outputs: [[f64; 2]; 2], targets: [[bool; 2]; 2]| {
+            ConfusionStatsInput::<TestBackend>::new(
+                Tensor::from_data(outputs, &device),
+                Tensor::from_data(targets, &device),
+            )
+        };
+
+        let mut stats = ConfusionStats::default();
+        stats.add(&ConfusionStats::new(
+            &input([[0.7, 0.3], [0.6, 0.4]], [[true, false], [false, true]]),
+            DecisionRule::Argmax,
+        ));
+        stats.add(&ConfusionStats::new(
+            &input([[0.2, 0.8], [0.9, 0.1]], [[false, true], [true, false]]),
+            DecisionRule::Argmax,
+        ));
+
+        assert_eq!(stats.batch_size, 4);
+        assert_eq!(stats.true_positive, vec![2.0, 1.0]);
+        assert_eq!(stats.false_positive, vec![1.0, 0.0]);
+        assert_eq!(stats.false_negative, vec![0.0, 1.0]);
+        assert_eq!(stats.support, vec![2.0, 2.0]);
+    }
+}
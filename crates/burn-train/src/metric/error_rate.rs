@@ -0,0 +1,169 @@
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::text::edit_distance;
+use super::{MetricEntry, MetricMetadata, TextInput};
+use crate::metric::{Metric, Numeric};
+
+/// The character error rate metric, the edit distance between the predicted and reference
+/// characters divided by the number of reference characters.
+///
+/// The edit distances and reference lengths are accumulated over the epoch.
+#[derive(Default)]
+pub struct CerMetric {
+    state: AccumulatedMetricState,
+    counts: ErrorCounts,
+}
+
+/// The word error rate metric, the edit distance between the predicted and reference words
+/// divided by the number of reference words.
+///
+/// Words are separated by whitespace. The edit distances and reference lengths are accumulated
+/// over the epoch.
+#[derive(Default)]
+pub struct WerMetric {
+    state: AccumulatedMetricState,
+    counts: ErrorCounts,
+}
+
+#[derive(Default, Clone, Copy)]
+struct ErrorCounts {
+    errors: usize,
+    total: usize,
+}
+
+impl ErrorCounts {
+    fn new<T: PartialEq>(pairs: impl Iterator<Item = (Vec<T>, Vec<T>)>) -> Self {
+        pairs.fold(Self::default(), |counts, (prediction, reference)| Self {
+            errors: counts.errors + edit_distance(&prediction, &reference),
+            total: counts.total + reference.len(),
+        })
+    }
+
+    fn add(&mut self, other: Self) {
+        self.errors += other.errors;
+        self.total += other.total;
+    }
+
+    fn rate(&self) -> f64 {
+        100.0 * self.errors as f64 / self.total as f64
+    }
+}
+
+impl CerMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WerMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for CerMetric {
+    const NAME: &'static str = "CER";
+
+    type Input = TextInput;
+
+    fn update(&mut self, input: &TextInput, _metadata: &MetricMetadata) -> MetricEntry {
+        let counts = ErrorCounts::new(input.pairs().map(|(prediction, reference)| {
+            (
+                prediction.chars().collect::<Vec<_>>(),
+                reference.chars().collect::<Vec<_>>(),
+            )
+        }));
+        self.counts.add(counts);
+
+        self.state.update(
+            self.counts.rate(),
+            counts.rate(),
+            FormatOptions::new(Self::NAME).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.counts = ErrorCounts::default();
+    }
+}
+
+impl Numeric for CerMetric {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+impl Metric for WerMetric {
+    const NAME: &'static str = "WER";
+
+    type Input = TextInput;
+
+    fn update(&mut self, input: &TextInput, _metadata: &MetricMetadata) -> MetricEntry {
+        let counts = ErrorCounts::new(input.pairs().map(|(prediction, reference)| {
+            (
+                prediction.split_whitespace().collect::<Vec<_>>(),
+                reference.split_whitespace().collect::<Vec<_>>(),
+            )
+        }));
+        self.counts.add(counts);
+
+        self.state.update(
+            self.counts.rate(),
+            counts.rate(),
+            FormatOptions::new(Self::NAME).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.counts = ErrorCounts::default();
+    }
+}
+
+impl Numeric for WerMetric {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(predictions: &[&str], references: &[&str]) -> TextInput {
+        TextInput::new(
+            predictions.iter().map(|s| s.to_string()).collect(),
+            references.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_cer_accumulates_over_batches() {
+        let mut metric = CerMetric::new();
+
+        let _entry = metric.update(&input(&["abd"], &["abc"]), &MetricMetadata::fake());
+        assert_eq!(100.0 / 3.0, metric.value());
+
+        // A mean of the batches would give (1/3 + 0/1) / 2.
+        let _entry = metric.update(&input(&["a"], &["a"]), &MetricMetadata::fake());
+        assert_eq!(25.0, metric.value());
+    }
+
+    #[test]
+    fn test_wer() {
+        let mut metric = WerMetric::new();
+
+        let _entry = metric.update(
+            &input(
+                &["the cat sat on mat", "hello world"],
+                &["the cat sat on the mat", "hello there world"],
+            ),
+            &MetricMetadata::fake(),
+        );
+
+        // Two missing words over nine reference words.
+        assert_eq!(200.0 / 9.0, metric.value());
+    }
+}
@@ -2,35 +2,65 @@
 pub mod state;
 
 mod acc;
+mod auroc;
 mod base;
+mod bleu;
+mod classification;
+mod confusion_matrix;
+mod confusion_stats;
 #[cfg(feature = "metrics")]
 mod cpu_temp;
 #[cfg(feature = "metrics")]
 mod cpu_use;
 #[cfg(feature = "metrics")]
 mod cuda;
+mod error_rate;
+mod f1_score;
 mod hamming;
 mod learning_rate;
 mod loss;
+mod mae;
 #[cfg(feature = "metrics")]
 mod memory_use;
+mod perplexity;
+mod precision;
+mod r2;
+mod recall;
+mod regression;
+mod rmse;
+mod text;
 
 #[cfg(feature = "metrics")]
 mod top_k_acc;
 
 pub use acc::*;
+pub use auroc::*;
 pub use base::*;
+pub use bleu::*;
+pub use classification::*;
+pub use confusion_matrix::*;
+pub use confusion_stats::ConfusionStatsInput;
 #[cfg(feature = "metrics")]
 pub use cpu_temp::*;
 #[cfg(feature = "metrics")]
 pub use cpu_use::*;
 #[cfg(feature = "metrics")]
 pub use cuda::*;
+pub use error_rate::*;
+pub use f1_score::*;
 pub use hamming::*;
 pub use learning_rate::*;
 pub use loss::*;
+pub use mae::*;
 #[cfg(feature = "metrics")]
 pub use memory_use::*;
+pub use perplexity::*;
+pub use precision::*;
+pub use r2::*;
+pub use recall::*;
+pub use regression::*;
+pub use rmse::*;
+pub use text::TextInput;
 #[cfg(feature = "metrics")]
 pub use top_k_acc::*;
 
@@ -0,0 +1,154 @@
+use core::marker::PhantomData;
+
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::{MetricEntry, MetricMetadata};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::activation::log_softmax;
+use burn_core::tensor::{backend::Backend, ElementConversion, Int, Tensor};
+
+/// The perplexity metric, the exponential of the mean negative log-likelihood of the targets.
+///
+/// The negative log-likelihoods and the number of tokens are accumulated over the epoch.
+#[derive(Default)]
+pub struct PerplexityMetric<B: Backend> {
+    state: AccumulatedMetricState,
+    sum: f64,
+    count: usize,
+    /// If specified, targets equal to this value will be considered padding and will not count
+    /// towards the metric
+    pad_token: Option<usize>,
+    _b: PhantomData<B>,
+}
+
+/// The [perplexity metric](PerplexityMetric) input type.
+#[derive(new)]
+pub struct PerplexityInput<B: Backend> {
+    /// The logits (batch_size, seq_length, vocab_size).
+    outputs: Tensor<B, 3>,
+    /// The target tokens (batch_size, seq_length).
+    targets: Tensor<B, 2, Int>,
+}
+
+impl<B: Backend> PerplexityMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the pad token.
+    pub fn with_pad_token(mut self, index: usize) -> Self {
+        self.pad_token = Some(index);
+        self
+    }
+}
+
+impl<B: Backend> Metric for PerplexityMetric<B> {
+    const NAME: &'static str = "Perplexity";
+
+    type Input = PerplexityInput<B>;
+
+    fn update(&mut self, input: &PerplexityInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, seq_length] = input.targets.dims();
+        let targets = input.targets.clone();
+
+        let nll = log_softmax(input.outputs.clone(), 2)
+            .gather(2, targets.clone().unsqueeze_dim(2))
+            .reshape([batch_size, seq_length])
+            .neg();
+
+        let (nll, count) = match self.pad_token {
+            Some(pad_token) => {
+                // we ignore the tokens where the target is equal to the pad token
+                let mask = targets.equal_elem(pad_token as i64);
+                let num_pad = mask.clone().int().sum().into_scalar().elem::<i64>() as usize;
+                (nll.mask_fill(mask, 0.0), batch_size * seq_length - num_pad)
+            }
+            None => (nll, batch_size * seq_length),
+        };
+        let sum = nll.sum().into_scalar().elem::<f64>();
+
+        self.sum += sum;
+        self.count += count;
+
+        self.state.update(
+            perplexity(self.sum, self.count),
+            perplexity(sum, count),
+            FormatOptions::new(Self::NAME).precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.sum = 0.0;
+        self.count = 0;
+    }
+}
+
+impl<B: Backend> Numeric for PerplexityMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+/// The exponential of the mean negative log-likelihood, which is one when there are no tokens
+/// since every token may be padding.
+fn perplexity(sum: f64, count: usize) -> f64 {
+    if count == 0 {
+        return 1.0;
+    }
+
+    (sum / count as f64).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_perplexity_uniform() {
+        let device = Default::default();
+        let mut metric = PerplexityMetric::<TestBackend>::new();
+        let input = PerplexityInput::new(
+            Tensor::zeros([2, 3, 4], &device),
+            Tensor::from_data([[0, 1, 2], [3, 2, 1]], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // A uniform distribution over 4 tokens has a perplexity of 4.
+        assert!((metric.value() - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_perplexity_with_pad_token() {
+        let device = Default::default();
+        let mut metric = PerplexityMetric::<TestBackend>::new().with_pad_token(0);
+        let input = PerplexityInput::new(
+            Tensor::from_data(
+                [[[0.0, 0.0], [0.0, 100.0]], [[0.0, 100.0], [0.0, 0.0]]],
+                &device,
+            ),
+            Tensor::from_data([[0, 1], [1, 0]], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // Only the confident predictions aren't padding.
+        assert!((metric.value() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_perplexity_only_padding() {
+        let device = Default::default();
+        let mut metric = PerplexityMetric::<TestBackend>::new().with_pad_token(0);
+        let input = PerplexityInput::new(
+            Tensor::zeros([1, 2, 2], &device),
+            Tensor::from_data([[0, 0]], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(metric.value(), 1.0);
+    }
+}
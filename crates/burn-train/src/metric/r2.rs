@@ -0,0 +1,213 @@
+use core::marker::PhantomData;
+
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::{MetricEntry, MetricMetadata, RegressionInput};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::{backend::Backend, Tensor};
+
+/// The coefficient of determination (R²) metric, averaged uniformly over the targets.
+///
+/// The sums of squares are accumulated over the epoch, so the value is computed against the
+/// mean of all the targets seen since the last clear.
+#[derive(Default)]
+pub struct R2ScoreMetric<B: Backend> {
+    state: AccumulatedMetricState,
+    sums: SumsOfSquares,
+    _b: PhantomData<B>,
+}
+
+/// The statistics of each target required to compute the R².
+///
+/// The targets are summarized by their mean and the sum of their squared deviations from it,
+/// merged between batches with the parallel algorithm of Chan et al., so the total sum of squares
+/// isn't the difference of two large sums.
+#[derive(Default, Clone)]
+struct SumsOfSquares {
+    count: usize,
+    means: Vec<f64>,
+    deviations_squared: Vec<f64>,
+    errors_squared: Vec<f64>,
+}
+
+impl SumsOfSquares {
+    fn new<B: Backend>(input: &RegressionInput<B>) -> Self {
+        let [batch_size, _num_targets] = input.targets.dims();
+        let values = |tensor: Tensor<B, 2>| tensor.into_data().iter::<f64>().collect::<Vec<_>>();
+        let means = input.targets.clone().mean_dim(0);
+        let deviations = input.targets.clone() - means.clone();
+        let errors = input.outputs.clone() - input.targets.clone();
+
+        Self {
+            count: batch_size,
+            means: values(means),
+            deviations_squared: values(deviations.powf_scalar(2.0).sum_dim(0)),
+            errors_squared: values(errors.powf_scalar(2.0).sum_dim(0)),
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+
+        let count = self.count + other.count;
+        let weight = other.count as f64 / count as f64;
+        let pairs = self.count as f64 * weight;
+
+        for ((mean, deviations_squared), (other_mean, other_deviations_squared)) in self
+            .means
+            .iter_mut()
+            .zip(self.deviations_squared.iter_mut())
+            .zip(other.means.iter().zip(&other.deviations_squared))
+        {
+            let delta = other_mean - *mean;
+            *mean += delta * weight;
+            *deviations_squared += other_deviations_squared + delta * delta * pairs;
+        }
+        self.errors_squared
+            .iter_mut()
+            .zip(&other.errors_squared)
+            .for_each(|(a, b)| *a += b);
+        self.count = count;
+    }
+
+    fn r2(&self) -> f64 {
+        let count = self.count as f64;
+        let scores = self
+            .means
+            .iter()
+            .zip(&self.deviations_squared)
+            .zip(&self.errors_squared)
+            .map(|((mean, total), errors_squared)| {
+                // The statistics are computed with the precision of the backend, so the total sum
+                // of squares of constant targets is only zero up to the rounding errors.
+                let sum_squared = total + count * mean * mean;
+                let tolerance = f32::EPSILON as f64 * sum_squared;
+
+                // With constant targets, the score is perfect when the predictions are exact
+                // and null otherwise.
+                match *total > tolerance {
+                    true => 1.0 - errors_squared / total,
+                    false if *errors_squared == 0.0 => 1.0,
+                    false => 0.0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        scores.iter().sum::<f64>() / scores.len() as f64
+    }
+}
+
+impl<B: Backend> R2ScoreMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for R2ScoreMetric<B> {
+    const NAME: &'static str = "R²";
+
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let sums = SumsOfSquares::new(input);
+        self.sums.add(&sums);
+
+        self.state.update(
+            self.sums.r2(),
+            sums.r2(),
+            FormatOptions::new(Self::NAME).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.sums = SumsOfSquares::default();
+    }
+}
+
+impl<B: Backend> Numeric for R2ScoreMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::TensorData;
+
+    #[test]
+    fn test_r2_accumulates_over_batches() {
+        let device = Default::default();
+        let mut metric = R2ScoreMetric::<TestBackend>::new();
+
+        let _entry = metric.update(
+            &RegressionInput::new(
+                Tensor::from_data([[1.0, 2.0], [2.0, 4.0]], &device),
+                Tensor::from_data([[1.0, 2.0], [3.0, 4.0]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+        let _entry = metric.update(
+            &RegressionInput::new(
+                Tensor::from_data([[3.0, 7.0], [4.0, 8.0]], &device),
+                Tensor::from_data([[3.0, 6.0], [5.0, 8.0]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+
+        // First target: [1, 3, 3, 5] with mean 3, SST = 8, SSE = 2
+        // Second target: [2, 4, 6, 8] with mean 5, SST = 20, SSE = 1
+        let expected = ((1.0 - 2.0 / 8.0) + (1.0 - 1.0 / 20.0)) / 2.0;
+        assert!((metric.value() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_r2_constant_targets() {
+        let device = Default::default();
+        let mut metric = R2ScoreMetric::<TestBackend>::new();
+
+        let _entry = metric.update(
+            &RegressionInput::new(
+                Tensor::from_data([[0.1, 0.2], [0.1, 0.4], [0.1, 0.3]], &device),
+                Tensor::from_data([[0.1, 0.3], [0.1, 0.3], [0.1, 0.3]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+
+        // The first target is predicted exactly, the second one isn't.
+        assert_eq!(metric.value(), 0.5);
+    }
+
+    #[test]
+    fn test_r2_nonzero_mean_targets_over_many_batches() {
+        let device = Default::default();
+        let mut metric = R2ScoreMetric::<TestBackend>::new();
+
+        // Targets 10 ± 1 over 100k items, predicted with half of their deviation from the mean.
+        let targets = [[11.0], [9.0]].repeat(50);
+        let outputs = [[10.5], [9.5]].repeat(50);
+        for _ in 0..1000 {
+            let _entry = metric.update(
+                &RegressionInput::new(
+                    Tensor::<TestBackend, 2>::from_data(
+                        TensorData::new(outputs.concat(), [100, 1]),
+                        &device,
+                    ),
+                    Tensor::<TestBackend, 2>::from_data(
+                        TensorData::new(targets.concat(), [100, 1]),
+                        &device,
+                    ),
+                ),
+                &MetricMetadata::fake(),
+            );
+        }
+
+        // SSE = 0.25 * SST
+        assert!((metric.value() - 0.75).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,130 @@
+use core::marker::PhantomData;
+
+use super::confusion_stats::{ConfusionStats, ConfusionStatsInput};
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::{ClassReduction, DecisionRule, MetricEntry, MetricMetadata};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The recall metric, the proportion of target classes that are predicted.
+#[derive(Default)]
+pub struct RecallMetric<B: Backend> {
+    state: AccumulatedMetricState,
+    stats: ConfusionStats,
+    decision_rule: DecisionRule,
+    class_reduction: ClassReduction,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> RecallMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the rule used to predict classes from the outputs.
+    pub fn with_decision_rule(mut self, decision_rule: DecisionRule) -> Self {
+        self.decision_rule = decision_rule;
+        self
+    }
+
+    /// Sets how the recall of each class is reduced.
+    pub fn with_class_reduction(mut self, class_reduction: ClassReduction) -> Self {
+        self.class_reduction = class_reduction;
+        self
+    }
+}
+
+impl<B: Backend> Metric for RecallMetric<B> {
+    const NAME: &'static str = "Recall";
+
+    type Input = ConfusionStatsInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ConfusionStatsInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        let stats = ConfusionStats::new(input, self.decision_rule);
+        self.stats.add(&stats);
+
+        self.state.update(
+            100.0 * self.stats.recall(self.class_reduction),
+            100.0 * stats.recall(self.class_reduction),
+            FormatOptions::new(Self::NAME).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.stats = ConfusionStats::default();
+    }
+}
+
+impl<B: Backend> Numeric for RecallMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    fn input() -> ConfusionStatsInput<TestBackend> {
+        let device = Default::default();
+        let outputs = Tensor::from_data(
+            [
+                [0.7, 0.2, 0.1],
+                [0.1, 0.8, 0.1],
+                [0.2, 0.2, 0.6],
+                [0.5, 0.4, 0.1],
+            ],
+            &device,
+        );
+        let targets = Tensor::from_data(
+            [
+                [true, false, false],
+                [false, true, false],
+                [false, true, false],
+                [false, false, true],
+            ],
+            &device,
+        );
+
+        ConfusionStatsInput::new(outputs, targets)
+    }
+
+    #[test]
+    fn test_recall_micro() {
+        let mut metric =
+            RecallMetric::<TestBackend>::new().with_class_reduction(ClassReduction::Micro);
+
+        let _entry = metric.update(&input(), &MetricMetadata::fake());
+
+        assert_eq!(50.0, metric.value());
+    }
+
+    #[test]
+    fn test_recall_macro() {
+        let mut metric = RecallMetric::<TestBackend>::new();
+
+        let _entry = metric.update(&input(), &MetricMetadata::fake());
+
+        // Per class: [1/1, 1/2, 0/1]
+        assert_eq!(50.0, metric.value());
+    }
+
+    #[test]
+    fn test_recall_weighted() {
+        let mut metric =
+            RecallMetric::<TestBackend>::new().with_class_reduction(ClassReduction::Weighted);
+
+        let _entry = metric.update(&input(), &MetricMetadata::fake());
+
+        // Per class: [1/1, 1/2, 0/1] with support [1, 2, 1]
+        assert_eq!(50.0, metric.value());
+    }
+}
@@ -0,0 +1,25 @@
+use burn_core::tensor::{backend::Backend, ElementConversion, Tensor};
+
+/// The input type of the [MAE](super::MaeMetric), [RMSE](super::RmseMetric) and
+/// [R²](super::R2ScoreMetric) metrics.
+#[derive(new)]
+pub struct RegressionInput<B: Backend> {
+    /// The outputs (batch_size, num_targets).
+    pub(crate) outputs: Tensor<B, 2>,
+    /// The targets (batch_size, num_targets).
+    pub(crate) targets: Tensor<B, 2>,
+}
+
+impl<B: Backend> RegressionInput<B> {
+    /// The sum of the given function applied to the errors, with the number of errors.
+    pub(crate) fn sum_errors<F>(&self, func: F) -> (f64, usize)
+    where
+        F: FnOnce(Tensor<B, 2>) -> Tensor<B, 2>,
+    {
+        let errors = self.outputs.clone() - self.targets.clone();
+        let numel = errors.shape().num_elements();
+        let sum = func(errors).sum().into_scalar().elem::<f64>();
+
+        (sum, numel)
+    }
+}
@@ -0,0 +1,87 @@
+use core::marker::PhantomData;
+
+use super::state::{AccumulatedMetricState, FormatOptions};
+use super::{MetricEntry, MetricMetadata, RegressionInput};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The root mean squared error metric.
+///
+/// The squared errors are accumulated over the epoch, since the root of the mean isn't the mean
+/// of the roots of each batch.
+#[derive(Default)]
+pub struct RmseMetric<B: Backend> {
+    state: AccumulatedMetricState,
+    sum: f64,
+    count: usize,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> RmseMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for RmseMetric<B> {
+    const NAME: &'static str = "RMSE";
+
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let (sum, numel) = input.sum_errors(|errors| errors.powf_scalar(2.0));
+
+        self.sum += sum;
+        self.count += numel;
+
+        self.state.update(
+            (self.sum / self.count as f64).sqrt(),
+            (sum / numel as f64).sqrt(),
+            FormatOptions::new(Self::NAME).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.sum = 0.0;
+        self.count = 0;
+    }
+}
+
+impl<B: Backend> Numeric for RmseMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    #[test]
+    fn test_rmse_accumulates_over_batches() {
+        let device = Default::default();
+        let mut metric = RmseMetric::<TestBackend>::new();
+
+        let _entry = metric.update(
+            &RegressionInput::new(
+                Tensor::from_data([[1.0, 2.0]], &device),
+                Tensor::from_data([[1.0, 5.0]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+        assert_eq!((9.0f64 / 2.0).sqrt(), metric.value());
+
+        let _entry = metric.update(
+            &RegressionInput::new(
+                Tensor::from_data([[3.0, 4.0]], &device),
+                Tensor::from_data([[2.0, 4.0]], &device),
+            ),
+            &MetricMetadata::fake(),
+        );
+        assert_eq!((10.0f64 / 4.0).sqrt(), metric.value());
+    }
+}
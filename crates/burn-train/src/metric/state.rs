@@ -67,10 +67,24 @@ impl NumericMetricState {
 
         let value_current = value;
         let value_running = self.sum / self.count as f64;
+
         // Numeric metric state is an aggregated value
-        let serialized = NumericEntry::Aggregated(value_current, batch_size).serialize();
+        let serialized = NumericEntry::Aggregated(value_current, batch_size);
 
-        let (formatted_current, formatted_running) = match format.precision {
+        format.entry(value_running, value_current, serialized)
+    }
+}
+
+impl FormatOptions {
+    fn entry(
+        self,
+        value_running: f64,
+        value_current: f64,
+        serialized: NumericEntry,
+    ) -> MetricEntry {
+        let serialized = serialized.serialize();
+
+        let (formatted_current, formatted_running) = match self.precision {
             Some(precision) => (
                 format_float(value_current, precision),
                 format_float(value_running, precision),
@@ -78,14 +92,72 @@ impl NumericMetricState {
             None => (format!("{value_current}"), format!("{value_running}")),
         };
 
-        let formatted = match format.unit {
+        let formatted = match self.unit {
             Some(unit) => {
                 format!("epoch {formatted_running} {unit} - batch {formatted_current} {unit}")
             }
             None => format!("epoch {formatted_running} - batch {formatted_current}"),
         };
 
-        MetricEntry::new(format.name, formatted, serialized)
+        MetricEntry::new(self.name, formatted, serialized)
+    }
+}
+
+/// Useful utility to implement numeric metrics that can't be computed as the mean of the values
+/// of each batch, such as a ratio of counts accumulated over the epoch.
+///
+/// # Notes
+///
+/// The epoch value is computed by the metric itself from its accumulated statistics. It is what
+/// gets logged, so the last entry of an epoch is the value of the whole epoch, while the value of
+/// each batch is only displayed.
+pub struct AccumulatedMetricState {
+    current: f64,
+}
+
+impl AccumulatedMetricState {
+    /// Create a new [accumulated metric state](AccumulatedMetricState).
+    pub fn new() -> Self {
+        Self { current: f64::NAN }
+    }
+
+    /// Reset the state.
+    pub fn reset(&mut self) {
+        self.current = f64::NAN;
+    }
+
+    /// Update the state.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_epoch` - The value computed over all the items of the epoch so far.
+    /// * `value_batch` - The value computed over the items of the current batch.
+    /// * `format` - The formatting options.
+    pub fn update(
+        &mut self,
+        value_epoch: f64,
+        value_batch: f64,
+        format: FormatOptions,
+    ) -> MetricEntry {
+        self.current = value_epoch;
+
+        format.entry(
+            value_epoch,
+            value_batch,
+            NumericEntry::Accumulated(value_epoch),
+        )
+    }
+}
+
+impl Numeric for AccumulatedMetricState {
+    fn value(&self) -> f64 {
+        self.current
+    }
+}
+
+impl Default for AccumulatedMetricState {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -44,24 +44,8 @@ impl NumericMetricsAggregate {
 
         let points = points().expect("Can read values");
 
-        if points.is_empty() {
-            return None;
-        }
-
-        // Accurately compute the aggregated value based on the *actual* number of points
-        // since not all mini-batches are guaranteed to have the specified batch size
-        let (sum, num_points) = points
-            .into_iter()
-            .map(|entry| match entry {
-                NumericEntry::Value(v) => (v, 1),
-                // Right now the mean is the only aggregate available, so we can assume that the sum
-                // of an entry corresponds to (value * number of elements)
-                NumericEntry::Aggregated(v, n) => (v * n as f64, n),
-            })
-            .reduce(|(acc_v, acc_n), (v, n)| (acc_v + v, acc_n + n))
-            .unwrap();
         let value = match aggregate {
-            Aggregate::Mean => sum / num_points as f64,
+            Aggregate::Mean => NumericEntry::epoch_value(points)?,
         };
 
         self.value_for_each_epoch.insert(key, value);
@@ -202,4 +186,26 @@ mod tests {
         // Average should be (0.5 + 1.25 * 2) / 3 = 1.0, not (0.5 + 1.25) / 2 = 0.875
         assert_eq!(value, 1.0);
     }
+
+    #[test]
+    fn should_aggregate_accumulated_entry_to_the_last_value() {
+        let mut logger = InMemoryMetricLogger::default();
+        let mut aggregate = NumericMetricsAggregate::default();
+        let metric_name = "Precision";
+
+        for value in [50.0, 75.0, 60.0] {
+            logger.log(&MetricEntry::new(
+                metric_name.to_string(),
+                value.to_string(),
+                NumericEntry::Accumulated(value).serialize(),
+            ));
+        }
+
+        let value = aggregate
+            .aggregate(metric_name, 1, Aggregate::Mean, &mut [Box::new(logger)])
+            .unwrap();
+
+        // The last entry is computed over all the items of the epoch
+        assert_eq!(value, 60.0);
+    }
 }
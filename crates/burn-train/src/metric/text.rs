@@ -0,0 +1,64 @@
+/// The input type of the [CER](super::CerMetric), [WER](super::WerMetric) and
+/// [BLEU](super::BleuMetric) metrics.
+#[derive(new)]
+pub struct TextInput {
+    /// The decoded predictions of the batch.
+    pub(crate) predictions: Vec<String>,
+    /// The reference texts of the batch, one for each prediction.
+    pub(crate) references: Vec<String>,
+}
+
+impl TextInput {
+    /// Iterate over the pairs of (prediction, reference).
+    pub(crate) fn pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        assert_eq!(
+            self.predictions.len(),
+            self.references.len(),
+            "Each prediction should have exactly one reference."
+        );
+
+        self.predictions
+            .iter()
+            .zip(self.references.iter())
+            .map(|(prediction, reference)| (prediction.as_str(), reference.as_str()))
+    }
+}
+
+/// The minimum number of insertions, deletions and substitutions required to transform the
+/// prediction into the reference (Levenshtein distance).
+pub(crate) fn edit_distance<T: PartialEq>(prediction: &[T], reference: &[T]) -> usize {
+    // Only the previous row of the dynamic programming table is kept.
+    let mut previous = (0..=reference.len()).collect::<Vec<_>>();
+    let mut current = vec![0; reference.len() + 1];
+
+    for (i, p) in prediction.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, r) in reference.iter().enumerate() {
+            let substitution = previous[j] + usize::from(p != r);
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+
+            current[j + 1] = substitution.min(deletion).min(insertion);
+        }
+
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[reference.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars(""), &chars("abc")), 3);
+        assert_eq!(edit_distance(&chars("abc"), &chars("")), 3);
+        assert_eq!(edit_distance(&chars("abc"), &chars("abc")), 0);
+    }
+}
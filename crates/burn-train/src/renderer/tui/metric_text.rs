@@ -57,17 +57,19 @@ impl TextMetricView {
         let mut lines = Vec::with_capacity(names.len() * 4);
 
         let start_line = |title: &str| vec![Span::from(format!(" {title} ")).bold().yellow()];
-        let train_line = |formatted: &str| {
-            vec![
-                Span::from("   Train ").bold(),
-                Span::from(formatted.to_string()).italic(),
-            ]
-        };
-        let valid_line = |formatted: &str| {
-            vec![
-                Span::from("   Valid ").bold(),
-                Span::from(formatted.to_string()).italic(),
-            ]
+        // Multi-line entries, such as a confusion matrix, are aligned after the split name.
+        let entry_lines = |split: &'static str, formatted: &str| {
+            formatted
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    let prefix = if i == 0 { split } else { "         " };
+                    vec![
+                        Span::from(prefix).bold(),
+                        Span::from(line.to_string()).italic(),
+                    ]
+                })
+                .collect::<Vec<_>>()
         };
 
         for name in names {
@@ -76,11 +78,11 @@ impl TextMetricView {
             let entry = data.get(name).unwrap();
 
             if let Some(entry) = &entry.train {
-                lines.push(train_line(&entry.formatted));
+                lines.extend(entry_lines("   Train ", &entry.formatted));
             }
 
             if let Some(entry) = &entry.valid {
-                lines.push(valid_line(&entry.formatted));
+                lines.extend(entry_lines("   Valid ", &entry.formatted));
             }
 
             lines.push(vec![Span::from("")]);
@@ -87,6 +87,10 @@ fn transpose_linear_node_weights(node: &mut Node) {
             let data_t = transpose_flattened(data, shape[0], shape[1]);
             node.inputs[1].value = Some(Data::Float16s(data_t));
         }
+        Data::QuantizedInt8s(mut data) => {
+            data.values = transpose_flattened(data.values, shape[0], shape[1]);
+            node.inputs[1].value = Some(Data::QuantizedInt8s(data));
+        }
         _ => panic!("Only float types are supported for Linear node"),
     }
     let shape = Some(vec![shape[1], shape[0]]); // Transpose the shape
@@ -5,7 +5,7 @@ use log::debug;
 use protobuf::Enum;
 
 use crate::{
-    ir::{ArgType, AttributeValue, Data, ElementType, Node, NodeType, TensorType},
+    ir::{ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, TensorType},
     protos::tensor_proto::DataType,
     util::{flatten_config, shape_config},
 };
@@ -15,6 +15,7 @@ pub fn dim_inference(node: &mut Node) {
     match node.node_type {
         NodeType::Add => same_as_input_broadcast(node),
         NodeType::ArgMax => argmax_update_outputs(node),
+        NodeType::ArgMin => argmin_update_outputs(node),
         NodeType::AveragePool1d => same_as_input(node),
         NodeType::AveragePool2d => same_as_input(node),
         NodeType::BatchNormalization => same_as_input(node),
@@ -26,8 +27,10 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Conv1d => conv1d_update_outputs(node),
         NodeType::Conv2d => conv2d_update_outputs(node),
         NodeType::Cos => same_as_input(node),
+        NodeType::DequantizeLinear => dequantize_linear_update_outputs(node),
         NodeType::Div => same_as_input_broadcast(node),
         NodeType::Dropout => same_as_input(node),
+        NodeType::Einsum => einsum_update_outputs(node),
         NodeType::Equal => elementwise_comparison_outputs(node),
         NodeType::Erf => same_as_input(node),
         NodeType::Exp => same_as_input(node),
@@ -36,10 +39,15 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Gelu => same_as_input(node),
         NodeType::Gather => gather_update_outputs(node),
         NodeType::GatherElements => same_as_input(node),
+        NodeType::GatherND => gather_nd_update_outputs(node),
+        NodeType::GroupNormalization => same_as_input(node),
+        NodeType::GRU => recurrent_update_outputs(node),
         NodeType::Greater => elementwise_comparison_outputs(node),
         NodeType::GreaterOrEqual => elementwise_comparison_outputs(node),
         NodeType::HardSigmoid => same_as_input(node),
+        NodeType::If => if_update_outputs(node),
         NodeType::GlobalAveragePool => same_as_input(node),
+        NodeType::InstanceNormalization => same_as_input(node),
         NodeType::ConvTranspose1d => conv_transpose1d_update_outputs(node),
         NodeType::ConvTranspose2d => conv_transpose2d_update_outputs(node),
         NodeType::LayerNormalization => same_as_input(node),
@@ -47,7 +55,9 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Less => elementwise_comparison_outputs(node),
         NodeType::LessOrEqual => elementwise_comparison_outputs(node),
         NodeType::Linear => linear_update_outputs(node),
+        NodeType::LSTM => recurrent_update_outputs(node),
         NodeType::Log => same_as_input(node),
+        NodeType::Loop => loop_update_outputs(node),
         NodeType::LogSoftmax => same_as_input(node),
         NodeType::MatMul => matmul_update_outputs(node),
         NodeType::Max => same_as_input_broadcast(node),
@@ -56,10 +66,16 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Min => same_as_input_broadcast(node),
         NodeType::Mul => same_as_input(node),
         NodeType::Neg => same_as_input(node),
+        NodeType::NonMaxSuppression => non_max_suppression_update_outputs(node),
+        NodeType::NonZero => non_zero_update_outputs(node),
         NodeType::Not => same_as_input(node),
+        NodeType::OneHot => one_hot_update_outputs(node),
         NodeType::Pad => same_as_input(node),
         NodeType::PRelu => same_as_input_broadcast(node),
         NodeType::Pow => same_as_input_broadcast(node),
+        NodeType::QLinearConv => qlinear_conv_update_outputs(node),
+        NodeType::QLinearMatMul => qlinear_matmul_update_outputs(node),
+        NodeType::QuantizeLinear => quantize_linear_update_outputs(node),
         NodeType::RandomNormal => random_update_output(node),
         NodeType::RandomUniform => random_update_output(node),
         NodeType::Range => range_update_outputs(node),
@@ -72,6 +88,11 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Relu => same_as_input(node),
         NodeType::Reshape => reshape_update_outputs(node),
         NodeType::Resize => same_as_input(node),
+        NodeType::RNN => recurrent_update_outputs(node),
+        NodeType::RoiAlign => roi_align_update_outputs(node),
+        NodeType::Scan => scan_update_outputs(node),
+        NodeType::ScatterElements => same_as_input(node),
+        NodeType::ScatterND => same_as_input(node),
         NodeType::Shape => shape_update_outputs(node),
         NodeType::Sigmoid => same_as_input(node),
         NodeType::Sign => same_as_input(node),
@@ -79,10 +100,12 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Slice => same_as_input(node),
         NodeType::Softmax => same_as_input(node),
         NodeType::Squeeze => squeeze_update_output(node),
+        NodeType::Split => split_update_outputs(node),
         NodeType::Sqrt => same_as_input(node),
         NodeType::Sub => same_as_input_broadcast(node),
         NodeType::Sum => same_as_input_broadcast(node),
         NodeType::Tanh => same_as_input(node),
+        NodeType::TopK => top_k_update_outputs(node),
         NodeType::Transpose => same_as_input(node),
         NodeType::Trilu => same_as_input(node),
         NodeType::Unsqueeze => unsqueeze_update_output(node),
@@ -233,6 +256,66 @@ fn linear_update_outputs(node: &mut Node) {
     }
 }
 
+/// Infer the output types of a recurrent (LSTM, GRU or RNN) node.
+///
+/// The output Y is of rank 4 (`[seq_length, num_directions, batch_size, hidden_size]`) while the
+/// outputs Y_h and Y_c are of rank 3 (`[num_directions, batch_size, hidden_size]`).
+fn recurrent_update_outputs(node: &mut Node) {
+    let elem_type = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("{}: only tensor input is valid", node.name),
+    };
+
+    for (i, output) in node.outputs.iter_mut().enumerate() {
+        output.ty = ArgType::Tensor(TensorType {
+            elem_type: elem_type.clone(),
+            dim: if i == 0 { 4 } else { 3 },
+            shape: None,
+        });
+    }
+}
+
+/// Update the values and indices outputs of a TopK node, which have the rank of the input
+fn top_k_update_outputs(node: &mut Node) {
+    let tensor = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("TopK: only tensor input is valid"),
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        shape: None,
+        ..tensor.clone()
+    });
+    node.outputs[1].ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Int64,
+        dim: tensor.dim,
+        shape: None,
+    });
+}
+
+/// The selected indices of a NonMaxSuppression node are a `[num_selected, 3]` int64 tensor
+fn non_max_suppression_update_outputs(node: &mut Node) {
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Int64,
+        dim: 2,
+        shape: None,
+    });
+}
+
+/// The output of a RoiAlign node is a `[num_rois, channels, output_height, output_width]` tensor
+fn roi_align_update_outputs(node: &mut Node) {
+    let elem_type = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("RoiAlign: only tensor input is valid"),
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type,
+        dim: 4,
+        shape: None,
+    });
+}
+
 /// Update the output type using "to" attribute
 fn cast_update_outputs(node: &mut Node) {
     if node.inputs.len() != 1 {
@@ -250,6 +333,8 @@ fn cast_update_outputs(node: &mut Node) {
                 DataType::INT64 => ElementType::Int64,
                 DataType::DOUBLE => ElementType::Float64,
                 DataType::BOOL => ElementType::Bool,
+                DataType::INT8 => ElementType::Int8,
+                DataType::UINT8 => ElementType::Uint8,
                 _ => panic!("Cast: unsupported type"),
             },
             _ => panic!("'to' attribute must be an Int64"),
@@ -376,6 +461,213 @@ fn argmax_update_outputs(node: &mut Node) {
     });
 }
 
+fn argmin_update_outputs(node: &mut Node) {
+    let tensor = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("ArgMin: only tensor input is valid"),
+    };
+
+    let keepdims = match node.attrs.get("keepdims") {
+        Some(value) => value.clone().into_i64() != 0,
+        None => true,
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Int64,
+        dim: if keepdims { tensor.dim } else { tensor.dim - 1 },
+        shape: None,
+    });
+}
+
+/// Every output of a Split node has the rank of the input
+fn split_update_outputs(node: &mut Node) {
+    let tensor = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("Split: only tensor input is valid"),
+    };
+
+    for output in node.outputs.iter_mut() {
+        output.ty = ArgType::Tensor(TensorType {
+            shape: None,
+            ..tensor.clone()
+        });
+    }
+}
+
+/// The outputs of an If node have the types of the `then_branch` outputs
+fn if_update_outputs(node: &mut Node) {
+    let then_branch = node
+        .attrs
+        .get("then_branch")
+        .expect("If: then_branch attribute is required")
+        .clone()
+        .into_graph();
+
+    for (output, branch_output) in node.outputs.iter_mut().zip(then_branch.outputs) {
+        output.ty = branch_output.ty;
+    }
+}
+
+/// The outputs of a Loop node are the final loop-carried values, followed by the scan outputs,
+/// which are the per-iteration values stacked on a new first dimension.
+fn loop_update_outputs(node: &mut Node) {
+    let body = node
+        .attrs
+        .get("body")
+        .expect("Loop: body attribute is required")
+        .clone()
+        .into_graph();
+
+    // The body inputs are the iteration number, the condition and the loop-carried values
+    let num_carried = body.inputs.len() - 2;
+    // The body outputs are the condition, the loop-carried values and the scan outputs
+    let body_outputs = &body.outputs[1..];
+
+    update_stacked_outputs(&mut node.outputs, body_outputs, num_carried);
+}
+
+/// The outputs of a Scan node are the final state values, followed by the scan outputs, which are
+/// the per-iteration values stacked on a new first dimension.
+fn scan_update_outputs(node: &mut Node) {
+    let body = node
+        .attrs
+        .get("body")
+        .expect("Scan: body attribute is required")
+        .clone()
+        .into_graph();
+    let num_scan_inputs = node
+        .attrs
+        .get("num_scan_inputs")
+        .expect("Scan: num_scan_inputs attribute is required")
+        .clone()
+        .into_i64() as usize;
+
+    // The body inputs are the state values and a slice of each scan input
+    let num_state = body.inputs.len() - num_scan_inputs;
+
+    update_stacked_outputs(&mut node.outputs, &body.outputs, num_state);
+}
+
+/// The first `num_carried` outputs have the type of the body outputs, the other ones are stacked.
+fn update_stacked_outputs(outputs: &mut [Argument], body_outputs: &[Argument], num_carried: usize) {
+    for (i, (output, body_output)) in outputs.iter_mut().zip(body_outputs).enumerate() {
+        if i < num_carried {
+            output.ty = body_output.ty.clone();
+            continue;
+        }
+
+        output.ty = match &body_output.ty {
+            ArgType::Tensor(tensor) => ArgType::Tensor(TensorType {
+                dim: tensor.dim + 1,
+                shape: None,
+                ..tensor.clone()
+            }),
+            ArgType::Scalar(elem_type) => ArgType::Tensor(TensorType {
+                elem_type: elem_type.clone(),
+                dim: 1,
+                shape: None,
+            }),
+            ArgType::Shape(_) => panic!("Scan outputs of type Shape are not supported"),
+        };
+    }
+}
+
+/// The output of a GatherND node has rank `q - 1 + r - k - batch_dims`, where `k` is the size of
+/// the last dimension of the indices, which must be known statically.
+fn gather_nd_update_outputs(node: &mut Node) {
+    let data = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("GatherND: only tensor data is valid"),
+    };
+    let indices = match &node.inputs[1].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("GatherND: only tensor indices are valid"),
+    };
+
+    let index_depth = indices
+        .shape
+        .as_ref()
+        .and_then(|shape| shape.last().copied())
+        .expect("GatherND: the last dimension of the indices must be known statically");
+    let batch_dims = match node.attrs.get("batch_dims") {
+        Some(value) => value.clone().into_i64() as usize,
+        None => 0,
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: data.elem_type,
+        dim: indices.dim - 1 + data.dim - index_depth - batch_dims,
+        shape: None,
+    });
+}
+
+/// The output of a NonZero node is a `[rank, num_non_zero]` int64 tensor, whose second
+/// dimension is only known at runtime.
+fn non_zero_update_outputs(node: &mut Node) {
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Int64,
+        dim: 2,
+        shape: None,
+    });
+}
+
+/// The output of a OneHot node has one more dimension than the indices and the element type of
+/// the values.
+fn one_hot_update_outputs(node: &mut Node) {
+    let dim = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim,
+        ArgType::Scalar(_) => 0,
+        _ => panic!("OneHot: only tensor indices are valid"),
+    };
+    let elem_type = match &node.inputs[2].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("OneHot: only tensor values are valid"),
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type,
+        dim: dim + 1,
+        shape: None,
+    });
+}
+
+/// The rank of the output of an Einsum node is the number of output subscripts.
+fn einsum_update_outputs(node: &mut Node) {
+    let elem_type = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("Einsum: only tensor inputs are valid"),
+    };
+    let equation = match node.attrs.get("equation") {
+        Some(value) => value.clone().into_string(),
+        None => panic!("Einsum: equation attribute is required"),
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type,
+        dim: einsum_output_subscripts(&equation).len(),
+        shape: None,
+    });
+}
+
+/// Returns the output subscripts of an Einsum equation.
+///
+/// In implicit mode (no `->`), the output holds the subscripts that appear exactly once, in
+/// alphabetical order.
+fn einsum_output_subscripts(equation: &str) -> Vec<char> {
+    let equation = equation.replace(' ', "");
+    if let Some((_, output)) = equation.split_once("->") {
+        return output.chars().collect();
+    }
+
+    let mut subscripts = equation.chars().filter(|c| *c != ',').collect::<Vec<_>>();
+    subscripts.sort();
+    subscripts
+        .iter()
+        .filter(|c| subscripts.iter().filter(|other| other == c).count() == 1)
+        .copied()
+        .collect()
+}
+
 /// Update the output tensor dimension
 fn squeeze_update_output(node: &mut Node) {
     let axes = if node.inputs.len() == 2 {
@@ -643,6 +935,78 @@ fn matmul_update_outputs(node: &mut Node) {
     }
 }
 
+/// The element type of a quantized output, given by the type of the zero point which is `uint8`
+/// by default.
+fn quantized_elem_type(node: &Node, zero_point_index: usize) -> ElementType {
+    match node.inputs.get(zero_point_index).map(|arg| &arg.ty) {
+        Some(ArgType::Scalar(elem_type)) => elem_type.clone(),
+        Some(ArgType::Tensor(tensor)) => tensor.elem_type.clone(),
+        _ => ElementType::Uint8,
+    }
+}
+
+/// Infers the type of a QuantizeLinear node, the input quantized with the type of the zero point.
+fn quantize_linear_update_outputs(node: &mut Node) {
+    let elem_type = quantized_elem_type(node, 2);
+    match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => {
+            node.outputs[0].ty = ArgType::Tensor(TensorType {
+                elem_type,
+                ..tensor.clone()
+            });
+        }
+        _ => panic!("QuantizeLinear: only tensor input is valid"),
+    }
+}
+
+/// Infers the type of a DequantizeLinear node, the real values of the quantized input.
+fn dequantize_linear_update_outputs(node: &mut Node) {
+    match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => {
+            node.outputs[0].ty = ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                ..tensor.clone()
+            });
+        }
+        _ => panic!("DequantizeLinear: only tensor input is valid"),
+    }
+}
+
+/// Infers the type of a QLinearConv node, which has the rank of its input.
+fn qlinear_conv_update_outputs(node: &mut Node) {
+    let elem_type = quantized_elem_type(node, 7);
+    match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => {
+            node.outputs[0].ty = ArgType::Tensor(TensorType {
+                elem_type,
+                dim: tensor.dim,
+                shape: None,
+            });
+        }
+        _ => panic!("QLinearConv: only tensor input is valid"),
+    }
+}
+
+/// Infers the type of a QLinearMatMul node, whose operands are the first and fourth inputs.
+fn qlinear_matmul_update_outputs(node: &mut Node) {
+    let elem_type = quantized_elem_type(node, 7);
+    match (&node.inputs[0].ty, &node.inputs[3].ty) {
+        (ArgType::Tensor(a), ArgType::Tensor(b)) => {
+            let mut dim = max(a.dim, b.dim);
+            if (a.dim >= 2 && b.dim == 1) || (a.dim == 1 && b.dim >= 2) {
+                dim -= 1;
+            }
+
+            node.outputs[0].ty = ArgType::Tensor(TensorType {
+                elem_type,
+                dim,
+                shape: None,
+            });
+        }
+        _ => panic!("QLinearMatMul: only tensor inputs are valid"),
+    }
+}
+
 fn range_update_outputs(node: &mut Node) {
     if node.inputs.len() != 3 {
         panic!("Range: expected 3 inputs, found {}", node.inputs.len());
@@ -8,9 +8,13 @@ use crate::node_remap::remap_node_type;
 
 use super::{
     coalesce::coalesce,
-    ir::{Data, OnnxGraph, TensorType},
+    ir::{AttributeValue, Data, OnnxGraph, TensorType},
     proto_conversion::convert_node_proto,
-    protos::{ModelProto, NodeProto, TensorProto, ValueInfoProto},
+    protos::{
+        attribute_proto::AttributeType, GraphProto, ModelProto, NodeProto, TensorProto,
+        ValueInfoProto,
+    },
+    quantization::{dequantize_constant, quantize_weights},
 };
 
 use super::dim_inference::dim_inference;
@@ -18,19 +22,32 @@ use super::ir::{ArgType, Argument, Node, NodeType};
 
 use protobuf::Message;
 
-const LIFT_CONSTANTS_FOR_NODE_TYPES: [NodeType; 12] = [
+pub(crate) const LIFT_CONSTANTS_FOR_NODE_TYPES: [NodeType; 25] = [
     NodeType::BatchNormalization,
     NodeType::Clip,
     NodeType::Conv1d,
     NodeType::Conv2d,
+    NodeType::DequantizeLinear,
     NodeType::Dropout,
     NodeType::Expand,
+    NodeType::GroupNormalization,
+    NodeType::GRU,
+    NodeType::InstanceNormalization,
+    NodeType::LSTM,
+    NodeType::NonMaxSuppression,
+    NodeType::OneHot,
+    NodeType::QLinearConv,
+    NodeType::QLinearMatMul,
+    NodeType::QuantizeLinear,
     NodeType::Reshape,
     NodeType::Resize,
     NodeType::Unsqueeze,
     NodeType::ReduceSum,
+    NodeType::RNN,
     NodeType::Slice,
+    NodeType::Split,
     NodeType::Squeeze,
+    NodeType::TopK,
 ];
 
 #[derive(Debug, Clone)]
@@ -52,6 +69,10 @@ pub struct GraphData {
     input_name_map: HashMap<String, IOEntry>,
     /// Maps the updated input name to the original input name. Required to check if the input is an initializer
     input_key_map: HashMap<String, String>,
+    /// The values of the enclosing graphs visible from a subgraph, by original name
+    outer_scope: HashMap<String, Argument>,
+    /// True for the subgraphs of control flow nodes, whose inputs are all kept
+    is_subgraph: bool,
 }
 
 impl GraphData {
@@ -59,6 +80,33 @@ impl GraphData {
         inputs: &[ValueInfoProto],
         outputs: &[ValueInfoProto],
         initializers: &[TensorProto],
+    ) -> Self {
+        Self::with_scope(inputs, outputs, initializers, "input", HashMap::new())
+    }
+
+    /// Create the graph data of a subgraph, which can use the values of the enclosing graphs.
+    pub(crate) fn new_subgraph(
+        graph: &GraphProto,
+        input_prefix: &str,
+        outer_scope: HashMap<String, Argument>,
+    ) -> Self {
+        let mut graph_data = Self::with_scope(
+            &graph.input,
+            &graph.output,
+            &graph.initializer,
+            input_prefix,
+            outer_scope,
+        );
+        graph_data.is_subgraph = true;
+        graph_data
+    }
+
+    fn with_scope(
+        inputs: &[ValueInfoProto],
+        outputs: &[ValueInfoProto],
+        initializers: &[TensorProto],
+        input_prefix: &str,
+        outer_scope: HashMap<String, Argument>,
     ) -> Self {
         let mut input_name_map = HashMap::new();
         let mut input_key_map = HashMap::new();
@@ -75,7 +123,7 @@ impl GraphData {
             .iter()
             .enumerate()
             .map(|(i, x)| {
-                let in_name = format!("input{}", i + 1);
+                let in_name = format!("{}{}", input_prefix, i + 1);
 
                 input_name_map.insert(x.name.clone(), IOEntry::In(i));
                 input_key_map.insert(in_name.clone(), x.name.clone());
@@ -99,6 +147,8 @@ impl GraphData {
             processed_nodes: Vec::new(),
             input_name_map,
             input_key_map,
+            outer_scope,
+            is_subgraph: false,
         }
     }
 
@@ -110,6 +160,8 @@ impl GraphData {
                 //need to confirm) then we could pop the initializer from the map
                 if let Some(init_arg) = self.initializers.get(proto_str) {
                     init_arg.clone()
+                } else if let Some(outer_arg) = self.outer_scope.get(proto_str) {
+                    outer_arg.clone()
                 } else {
                     log::warn!(
                         "Input {} not found, should only happen when peeking",
@@ -123,6 +175,16 @@ impl GraphData {
         }
     }
 
+    /// The values visible from a subgraph of the current node, by original name
+    fn visible_scope(&self) -> HashMap<String, Argument> {
+        let mut scope = self.outer_scope.clone();
+        scope.extend(self.initializers.clone());
+        for name in self.input_name_map.keys() {
+            scope.insert(name.clone(), self.init_in(name));
+        }
+        scope
+    }
+
     /// Mark the graph_inputs to a node as passed, unless they are also initializers
     fn mark_input_passed(&mut self, node: &Node) {
         // we have to double map the inputs because the input might be replaced by an initializer
@@ -147,20 +209,28 @@ impl GraphData {
     fn add_node(&mut self, mut node: Node) {
         log::debug!("adding node {:?}", &node.name);
         self.mark_input_passed(&node);
-        let mut out_count = 1;
-        for output in node.outputs.iter_mut() {
+        for (i, output) in node.outputs.iter_mut().enumerate() {
+            // Optional outputs that are not used keep their empty name
+            if output.name.is_empty() {
+                continue;
+            }
             self.input_name_map.insert(
                 output.name.clone(),
-                IOEntry::Node(self.processed_nodes.len(), 0),
+                IOEntry::Node(self.processed_nodes.len(), i),
             );
-            output.name = format!("{}_out{}", node.name, out_count);
-            out_count += 1;
+            output.name = format!("{}_out{}", node.name, i + 1);
         }
         self.processed_nodes.push(node);
     }
 
     /// Consumes the graph data and returns the processed nodes, filtered inputs and outputs
     fn consume(mut self) -> (Vec<Node>, Vec<Argument>, Vec<Argument>) {
+        if self.is_subgraph {
+            // The subgraph inputs and outputs are positional, and an output can be any visible value
+            let outputs = self.outputs.iter().map(|x| self.init_in(&x.name)).collect();
+            return (self.processed_nodes, self.inputs, outputs);
+        }
+
         self.inputs.retain(|x| x.passed);
         let outputs = self
             .outputs
@@ -202,22 +272,35 @@ impl OnnxGraphBuilder {
     pub(crate) fn build(mut self, model_proto: &ModelProto) -> OnnxGraph {
         self.constants_types = LIFT_CONSTANTS_FOR_NODE_TYPES.into_iter().collect();
 
-        let mut graph_data = GraphData::new(
+        let graph_data = GraphData::new(
             &model_proto.graph.input,
             &model_proto.graph.output,
             &model_proto.graph.initializer,
         );
 
-        let mut node_iter = model_proto.graph.node.iter().peekable();
+        self.build_graph(&model_proto.graph.node, graph_data)
+    }
+
+    fn build_graph(&mut self, nodes: &[NodeProto], mut graph_data: GraphData) -> OnnxGraph {
+        let mut node_iter = nodes.iter().peekable();
 
         while let Some(node_proto) = node_iter.next() {
             let mut node = convert_node_proto(node_proto, &graph_data);
 
             remap_node_type(&mut node);
             self.handle_node_renaming(&mut node);
+            self.handle_subgraphs(&mut node, node_proto, &graph_data);
             coalesce(&mut node, &mut node_iter, &graph_data);
             self.handle_identity(&mut node, &graph_data);
             self.check_constants(&mut node, &graph_data);
+            quantize_weights(&mut node);
+            if let Some(value) = dequantize_constant(&node) {
+                // The dequantized constant is used as an initializer, like its quantized input
+                graph_data
+                    .initializers
+                    .insert(node_proto.output[0].clone(), value);
+                continue;
+            }
             // NOTE: potential start of custom functions
             // can filter, coalesce, or modify the nodes here
             // args : node, peek_iter, graph_data
@@ -227,7 +310,13 @@ impl OnnxGraphBuilder {
             graph_data.add_node(node);
         }
 
-        let (mut processed_nodes, inputs, outputs) = graph_data.consume();
+        let (mut processed_nodes, inputs, mut outputs) = graph_data.consume();
+        // Outputs produced by removed identity nodes are their inputs
+        for output in outputs.iter_mut() {
+            if let Some(identity_idx) = self.identity_idx.get(&output.name) {
+                *output = processed_nodes[*identity_idx].inputs[0].clone();
+            }
+        }
         // Remove the graph inputs/output that are not used by any node
         let mut i = 0;
         processed_nodes.retain(|_| {
@@ -261,6 +350,57 @@ impl OnnxGraphBuilder {
         node.name.clone_from(&new_name);
     }
 
+    /// Convert the graph attributes of control flow nodes (If branches, Loop and Scan bodies).
+    ///
+    /// The subgraph nodes share the naming counter of the enclosing graph so that every variable
+    /// of the generated code is unique. The values of the enclosing graph used by a subgraph are
+    /// appended to the node inputs, which marks them as passed.
+    fn handle_subgraphs(
+        &mut self,
+        node: &mut Node,
+        node_proto: &NodeProto,
+        graph_data: &GraphData,
+    ) {
+        for attr in node_proto.attribute.iter() {
+            if attr.type_.unwrap() != AttributeType::GRAPH {
+                continue;
+            }
+            log::debug!("converting subgraph {} of node {}", &attr.name, &node.name);
+
+            let outer_scope = graph_data.visible_scope();
+            let outer_names = outer_scope
+                .values()
+                .filter(|arg| arg.value.is_none())
+                .map(|arg| arg.name.clone())
+                .collect::<HashSet<_>>();
+
+            let input_prefix = format!("{}_{}_in", node.name, attr.name);
+            let subgraph_data = GraphData::new_subgraph(&attr.g, &input_prefix, outer_scope);
+            let mut builder = OnnxGraphBuilder {
+                constants_types: self.constants_types.clone(),
+                node_name_counter: std::mem::take(&mut self.node_name_counter),
+                ..Default::default()
+            };
+            let subgraph = builder.build_graph(&attr.g.node, subgraph_data);
+            self.node_name_counter = builder.node_name_counter;
+
+            let captured = subgraph
+                .nodes
+                .iter()
+                .flat_map(|node| node.inputs.iter())
+                .chain(subgraph.outputs.iter())
+                .filter(|arg| outer_names.contains(&arg.name));
+            for arg in captured {
+                if !node.inputs.contains(arg) {
+                    node.inputs.push(arg.clone());
+                }
+            }
+
+            node.attrs
+                .insert(attr.name.clone(), AttributeValue::Graph(subgraph));
+        }
+    }
+
     fn check_constants(&mut self, node: &mut Node, graph_data: &GraphData) {
         if node.node_type == NodeType::Constant
             || (node.node_type == NodeType::Identity && node.inputs[0].value.is_some())
@@ -379,15 +519,13 @@ pub fn parse_onnx(onnx_path: &Path) -> OnnxGraph {
 /// node renaming has been done. avoids marking rhs as passed so that it can be
 /// properly deleted if nothing else uses it
 /// Remap the unsqueeze node to a reshape node
+/// The output shape must be static, the node is kept otherwise
 pub(crate) fn remap_unsqueeze_to_reshape(node: &mut Node, out_arg: &Argument) {
-    if let ArgType::Tensor(output_tensor) = &out_arg.ty {
-        let inner = output_tensor
-            .shape
-            .clone()
-            .unwrap()
-            .into_iter()
-            .map(|x| x as i64)
-            .collect::<Vec<i64>>();
+    if let ArgType::Tensor(TensorType {
+        shape: Some(shape), ..
+    }) = &out_arg.ty
+    {
+        let inner = shape.iter().map(|x| *x as i64).collect::<Vec<i64>>();
         let shape_len = inner.len();
         let new_rhs_value = Some(Data::Int64s(inner));
         //moving the remap to here
@@ -425,7 +563,7 @@ impl TopologicalSortable for Vec<NodeProto> {
         // Iterate over each node in the vector
         for node in self {
             // Iterate over each output of the node
-            for output in &node.output {
+            for output in node.output.iter().filter(|output| !output.is_empty()) {
                 // Iterate over each other node in the vector
                 for other_node in self {
                     // If the other node has an input that matches the current output
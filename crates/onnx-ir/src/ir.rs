@@ -87,6 +87,7 @@ pub enum AttributeValue {
     Strings(Vec<String>),
     Tensor(Tensor),
     Tensors(Vec<Tensor>),
+    Graph(OnnxGraph),
 }
 
 pub type Attributes = HashMap<String, AttributeValue>;
@@ -101,6 +102,8 @@ pub enum ElementType {
     String,
     Float16,
     Bool,
+    Int8,
+    Uint8,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -198,6 +201,33 @@ pub enum Data {
     Int64s(Vec<i64>),
     String(String),
     Strings(Vec<String>),
+    Int8(i8),
+    Int8s(Vec<i8>),
+    Uint8(u8),
+    Uint8s(Vec<u8>),
+    QuantizedInt8s(QuantizedInt8s),
+}
+
+/// Per-tensor affine quantized `int8` values, the real values being
+/// `(values - zero_point) * scale`.
+#[derive(Debug, Clone)]
+pub struct QuantizedInt8s {
+    /// The quantized values.
+    pub values: Vec<i8>,
+    /// The scaling factor.
+    pub scale: f32,
+    /// The zero-point offset.
+    pub zero_point: i8,
+}
+
+impl QuantizedInt8s {
+    /// The real values represented by the quantized values.
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.values
+            .iter()
+            .map(|value| (*value as i32 - self.zero_point as i32) as f32 * self.scale)
+            .collect()
+    }
 }
 
 /// ONNX graph representation
@@ -505,6 +535,15 @@ impl fmt::Debug for Data {
             Data::Int64s(v) => write!(f, "Int64s({})", trunc(v)),
             Data::Strings(v) => write!(f, "Strings({})", trunc(v)),
             Data::Bools(v) => write!(f, "Bools({})", trunc(v)),
+            Data::Int8s(v) => write!(f, "Int8s({})", trunc(v)),
+            Data::Uint8s(v) => write!(f, "Uint8s({})", trunc(v)),
+            Data::QuantizedInt8s(v) => write!(
+                f,
+                "QuantizedInt8s({}, scale: {}, zero_point: {})",
+                trunc(&v.values),
+                v.scale,
+                v.zero_point
+            ),
             Data::Float16(v) => write!(f, "Float16({})", v),
             Data::Float32(v) => write!(f, "Float32({})", v),
             Data::Float64(v) => write!(f, "Float64({})", v),
@@ -512,6 +551,8 @@ impl fmt::Debug for Data {
             Data::Int64(v) => write!(f, "Int64({})", v),
             Data::String(v) => write!(f, "String({})", v),
             Data::Bool(v) => write!(f, "Bool({})", v),
+            Data::Int8(v) => write!(f, "Int8({})", v),
+            Data::Uint8(v) => write!(f, "Uint8({})", v),
         }
     }
 }
@@ -547,6 +588,14 @@ impl Data {
                 assert_eq!(data.len(), 1);
                 Data::String(data[0].clone())
             }
+            Data::Int8s(data) => {
+                assert_eq!(data.len(), 1);
+                Data::Int8(data[0])
+            }
+            Data::Uint8s(data) => {
+                assert_eq!(data.len(), 1);
+                Data::Uint8(data[0])
+            }
             _ => self,
         }
     }
@@ -566,6 +615,8 @@ impl Data {
             Data::Float64(elem) => elem as f32,
             Data::Int32(elem) => elem as f32,
             Data::Int64(elem) => elem as f32,
+            Data::Int8(elem) => elem as f32,
+            Data::Uint8(elem) => elem as f32,
             _ => panic!("Cannot convert {:?} to f32", self),
         }
     }
@@ -577,6 +628,8 @@ impl Data {
             Data::Float64(elem) => elem,
             Data::Int32(elem) => elem as f64,
             Data::Int64(elem) => elem as f64,
+            Data::Int8(elem) => elem as f64,
+            Data::Uint8(elem) => elem as f64,
             _ => panic!("Cannot convert {:?} to f64", self),
         }
     }
@@ -587,6 +640,8 @@ impl Data {
             Data::Int64(elem) => elem as i32,
             Data::Float32(elem) => elem as i32,
             Data::Float64(elem) => elem as i32,
+            Data::Int8(elem) => elem as i32,
+            Data::Uint8(elem) => elem as i32,
             _ => panic!("Cannot convert {:?} to i32", self),
         }
     }
@@ -597,6 +652,8 @@ impl Data {
             Data::Int64(elem) => elem,
             Data::Float32(elem) => elem as i64,
             Data::Float64(elem) => elem as i64,
+            Data::Int8(elem) => elem as i64,
+            Data::Uint8(elem) => elem as i64,
             _ => panic!("Cannot convert {:?} to i64", self),
         }
     }
@@ -633,6 +690,9 @@ impl Data {
             Data::Float64s(elem) => elem.into_iter().map(|x| x as f32).collect(),
             Data::Int32s(elem) => elem.into_iter().map(|x| x as f32).collect(),
             Data::Int64s(elem) => elem.into_iter().map(|x| x as f32).collect(),
+            Data::Int8s(elem) => elem.into_iter().map(|x| x as f32).collect(),
+            Data::Uint8s(elem) => elem.into_iter().map(|x| x as f32).collect(),
+            Data::QuantizedInt8s(elem) => elem.dequantize(),
             _ => panic!("Cannot convert {:?} to Vec<f32>", self),
         }
     }
@@ -644,6 +704,9 @@ impl Data {
             Data::Float64s(elem) => elem,
             Data::Int32s(elem) => elem.into_iter().map(|x| x as f64).collect(),
             Data::Int64s(elem) => elem.into_iter().map(|x| x as f64).collect(),
+            Data::Int8s(elem) => elem.into_iter().map(|x| x as f64).collect(),
+            Data::Uint8s(elem) => elem.into_iter().map(|x| x as f64).collect(),
+            Data::QuantizedInt8s(elem) => elem.dequantize().into_iter().map(|x| x as f64).collect(),
             _ => panic!("Cannot convert {:?} to Vec<f64>", self),
         }
     }
@@ -654,6 +717,8 @@ impl Data {
             Data::Int64s(elem) => elem.into_iter().map(|x| x as i32).collect(),
             Data::Float32s(elem) => elem.into_iter().map(|x| x as i32).collect(),
             Data::Float64s(elem) => elem.into_iter().map(|x| x as i32).collect(),
+            Data::Int8s(elem) => elem.into_iter().map(|x| x as i32).collect(),
+            Data::Uint8s(elem) => elem.into_iter().map(|x| x as i32).collect(),
             _ => panic!("Cannot convert {:?} to Vec<i32>", self),
         }
     }
@@ -664,6 +729,8 @@ impl Data {
             Data::Int64s(elem) => elem,
             Data::Float32s(elem) => elem.into_iter().map(|x| x as i64).collect(),
             Data::Float64s(elem) => elem.into_iter().map(|x| x as i64).collect(),
+            Data::Int8s(elem) => elem.into_iter().map(|x| x as i64).collect(),
+            Data::Uint8s(elem) => elem.into_iter().map(|x| x as i64).collect(),
             _ => panic!("Cannot convert {:?} to Vec<i64>", self),
         }
     }
@@ -757,6 +824,14 @@ impl AttributeValue {
             panic!("Expected Tensors, got {:?}", self);
         }
     }
+
+    pub fn into_graph(self) -> OnnxGraph {
+        if let AttributeValue::Graph(elem) = self {
+            elem
+        } else {
+            panic!("Expected Graph, got {:?}", self);
+        }
+    }
 }
 
 /// Convert AttributeValue to an Argument
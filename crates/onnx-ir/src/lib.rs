@@ -3,10 +3,16 @@ mod dim_inference;
 mod from_onnx;
 pub mod ir;
 mod node_remap;
+mod passes;
 mod proto_conversion;
+#[cfg(feature = "protos")]
+pub mod protos;
+#[cfg(not(feature = "protos"))]
 mod protos;
+mod quantization;
 mod util;
 
 pub use from_onnx::convert_constant_value;
 pub use from_onnx::parse_onnx;
 pub use ir::OnnxGraph;
+pub use passes::GraphPasses;
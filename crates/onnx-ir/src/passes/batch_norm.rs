@@ -0,0 +1,254 @@
+use crate::ir::{
+    ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, OnnxGraph, TensorType,
+};
+
+use super::variable_uses;
+
+/// The default epsilon of the `BatchNormalization` operator.
+const DEFAULT_EPSILON: f32 = 1e-5;
+
+/// Fold the `BatchNormalization` nodes into the weights and bias of the `Conv` or `Linear` node
+/// computing their input.
+///
+/// The node is only folded when its input isn't used elsewhere and the weights are `f32`
+/// constants. For a `Linear` node, the normalized features must be the last axis of a 2D output.
+pub(crate) fn fuse_batch_norm(graph: &mut OnnxGraph) {
+    let uses = variable_uses(graph);
+    let mut removed = Vec::new();
+
+    for index in 0..graph.nodes.len() {
+        let batch_norm = &graph.nodes[index];
+        if batch_norm.node_type != NodeType::BatchNormalization {
+            continue;
+        }
+
+        let input = &batch_norm.inputs[0].name;
+        if uses.get(input) != Some(&1) {
+            continue;
+        }
+        let Some(producer) = graph.nodes[..index]
+            .iter()
+            .position(|node| node.outputs.iter().any(|output| &output.name == input))
+        else {
+            continue;
+        };
+
+        let Some(scale) = batch_norm_scale(batch_norm) else {
+            continue;
+        };
+        let output = batch_norm.outputs[0].clone();
+
+        if fuse(&mut graph.nodes[producer], &scale) {
+            log::debug!(
+                "fusing {} into {}",
+                graph.nodes[index].name,
+                graph.nodes[producer].name
+            );
+            graph.nodes[producer].outputs[0] = output;
+            removed.push(index);
+        }
+    }
+
+    let mut index = 0;
+    graph.nodes.retain(|_| {
+        let keep = !removed.contains(&index);
+        index += 1;
+        keep
+    });
+}
+
+/// The normalization as an affine transform, `y = x * scale + shift` for each channel.
+struct ChannelScale {
+    scale: Vec<f32>,
+    shift: Vec<f32>,
+}
+
+fn batch_norm_scale(node: &Node) -> Option<ChannelScale> {
+    let training = matches!(
+        node.attrs.get("training_mode"),
+        Some(AttributeValue::Int64(1))
+    );
+    let has_stats_outputs = node
+        .outputs
+        .iter()
+        .skip(1)
+        .any(|output| !output.name.is_empty());
+    if training || has_stats_outputs || node.inputs.len() != 5 {
+        return None;
+    }
+
+    let epsilon = match node.attrs.get("epsilon") {
+        Some(AttributeValue::Float32(epsilon)) => *epsilon,
+        _ => DEFAULT_EPSILON,
+    };
+    let [gamma, beta, mean, var] = [1, 2, 3, 4].map(|i| node.inputs[i].value.clone());
+    let (gamma, beta, mean, var) = (
+        gamma?.into_f32s(),
+        beta?.into_f32s(),
+        mean?.into_f32s(),
+        var?.into_f32s(),
+    );
+
+    let scale = gamma
+        .iter()
+        .zip(var.iter())
+        .map(|(gamma, var)| gamma / (var + epsilon).sqrt())
+        .collect::<Vec<_>>();
+    let shift = beta
+        .iter()
+        .zip(mean.iter())
+        .zip(scale.iter())
+        .map(|((beta, mean), scale)| beta - mean * scale)
+        .collect();
+
+    Some(ChannelScale { scale, shift })
+}
+
+/// Scale the weights and bias of the node, returns false if the node can't be fused.
+fn fuse(node: &mut Node, channel: &ChannelScale) -> bool {
+    let channels = channel.scale.len();
+    let Some(Data::Float32s(weight)) = &node.inputs[1].value else {
+        return false;
+    };
+    let ArgType::Tensor(TensorType {
+        shape: Some(weight_shape),
+        ..
+    }) = &node.inputs[1].ty
+    else {
+        return false;
+    };
+
+    let weight = match node.node_type {
+        NodeType::Conv1d | NodeType::Conv2d | NodeType::Conv3d if weight_shape[0] == channels => {
+            // The weights are [out_channels, in_channels / groups, kernel...]
+            let kernel_size = weight.len() / channels;
+            weight
+                .iter()
+                .enumerate()
+                .map(|(i, w)| w * channel.scale[i / kernel_size])
+                .collect::<Vec<_>>()
+        }
+        NodeType::Linear if weight_shape[1] == channels && node.outputs[0].ty.rank() == 2 => {
+            // The weights are [in_features, out_features]
+            weight
+                .iter()
+                .enumerate()
+                .map(|(i, w)| w * channel.scale[i % channels])
+                .collect::<Vec<_>>()
+        }
+        _ => return false,
+    };
+
+    let bias = match node.inputs.get(2) {
+        Some(bias) => match &bias.value {
+            Some(value) => value.clone().into_f32s(),
+            None => return false,
+        },
+        None => vec![0.0; channels],
+    };
+    let bias = bias
+        .iter()
+        .zip(channel.scale.iter().zip(channel.shift.iter()))
+        .map(|(bias, (scale, shift))| bias * scale + shift)
+        .collect::<Vec<_>>();
+
+    node.inputs[1].value = Some(Data::Float32s(weight));
+    let bias = Argument {
+        name: format!("{}_bias", node.name),
+        ty: ArgType::Tensor(TensorType {
+            elem_type: ElementType::Float32,
+            dim: 1,
+            shape: Some(vec![channels]),
+        }),
+        value: Some(Data::Float32s(bias)),
+        passed: false,
+    };
+    node.inputs.truncate(2);
+    node.inputs.push(bias);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::tests::{constant, node, variable};
+
+    fn f32s(name: &str, values: Vec<f32>, shape: Vec<usize>) -> Argument {
+        constant(name, ElementType::Float32, Data::Float32s(values), shape)
+    }
+
+    /// `x -> Conv2d -> BatchNormalization -> y`, with 2 channels and a 1x1 kernel.
+    fn conv_batch_norm() -> OnnxGraph {
+        let mut batch_norm = node(
+            NodeType::BatchNormalization,
+            "batchnormalization1",
+            vec![
+                variable("a", 4, None),
+                f32s("gamma", vec![2.0, 4.0], vec![2]),
+                f32s("beta", vec![1.0, 1.0], vec![2]),
+                f32s("mean", vec![0.0, 1.0], vec![2]),
+                f32s("var", vec![4.0, 4.0], vec![2]),
+            ],
+            vec![variable("y", 4, None)],
+        );
+        batch_norm
+            .attrs
+            .insert("epsilon".to_string(), AttributeValue::Float32(0.0));
+
+        OnnxGraph {
+            nodes: vec![
+                node(
+                    NodeType::Conv2d,
+                    "conv2d1",
+                    vec![
+                        variable("x", 4, None),
+                        f32s("weight", vec![1.0, 2.0], vec![2, 1, 1, 1]),
+                    ],
+                    vec![variable("a", 4, None)],
+                ),
+                batch_norm,
+            ],
+            inputs: vec![variable("x", 4, None)],
+            outputs: vec![variable("y", 4, None)],
+        }
+    }
+
+    #[test]
+    fn fuse_batch_norm_should_scale_the_conv_weights_and_bias() {
+        let mut graph = conv_batch_norm();
+
+        fuse_batch_norm(&mut graph);
+
+        // The scale is gamma / sqrt(var) and the shift beta - mean * scale
+        assert_eq!(graph.nodes.len(), 1);
+        let conv = &graph.nodes[0];
+        assert_eq!(conv.outputs[0].name, "y");
+        let values = |arg: &Argument| arg.value.clone().unwrap().into_f32s();
+        assert_eq!(values(&conv.inputs[1]), vec![1.0, 4.0]);
+        assert_eq!(values(&conv.inputs[2]), vec![1.0, -1.0]);
+    }
+
+    #[test]
+    fn fuse_batch_norm_should_keep_the_node_when_the_input_is_used_elsewhere() {
+        let mut graph = conv_batch_norm();
+        graph.outputs.push(variable("a", 4, None));
+
+        fuse_batch_norm(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.nodes[0].inputs.len(), 2);
+    }
+
+    #[test]
+    fn fuse_batch_norm_should_keep_the_node_in_training_mode() {
+        let mut graph = conv_batch_norm();
+        graph.nodes[1]
+            .attrs
+            .insert("training_mode".to_string(), AttributeValue::Int64(1));
+
+        fuse_batch_norm(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 2);
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    dim_inference::dim_inference,
+    from_onnx::convert_constant_value,
+    ir::{ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, OnnxGraph, Tensor},
+    util::shape_config,
+};
+
+use super::{is_lifted, reads_value};
+
+/// An integer value known before the inference.
+#[derive(Debug, Clone)]
+struct Constant {
+    values: Vec<i64>,
+    /// The shape of the value, empty for a scalar.
+    shape: Vec<usize>,
+}
+
+/// Evaluate the nodes computing integer values from constants and statically known shapes.
+///
+/// The folded nodes become `Constant` nodes, and their values are lifted into the inputs read as
+/// values, like the constants lifted while parsing. Since a shape can't be a constant, a `Shape`
+/// node is only folded when all the nodes using its output are folded or read it as a value.
+///
+/// The shape of a graph input only used by `Shape` nodes isn't folded, the input would otherwise
+/// be removed from the model.
+pub(crate) fn fold_constants(graph: &mut OnnxGraph) {
+    let mut constants = HashMap::new();
+    let mut folded = Vec::new();
+
+    let graph_inputs = graph
+        .inputs
+        .iter()
+        .map(|input| input.name.as_str())
+        .collect::<HashSet<_>>();
+    let used_inputs = graph
+        .nodes
+        .iter()
+        .filter(|node| node.node_type != NodeType::Shape)
+        .flat_map(|node| node.inputs.iter())
+        .map(|input| input.name.as_str())
+        .filter(|name| graph_inputs.contains(name))
+        .collect::<HashSet<_>>();
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        let value = match node.node_type {
+            NodeType::Constant => known_value(&convert_constant_value(node)),
+            NodeType::Shape
+                if graph_inputs.contains(node.inputs[0].name.as_str())
+                    && !used_inputs.contains(node.inputs[0].name.as_str()) =>
+            {
+                None
+            }
+            _ => evaluate(node, &constants).inspect(|_| folded.push(index)),
+        };
+
+        if let Some(value) = value {
+            log::debug!("node {} has the constant value {:?}", node.name, value);
+            constants.insert(node.outputs[0].name.clone(), value);
+        }
+    }
+
+    // Lift the folded values into the inputs read as values
+    let mut lifted = HashSet::new();
+    for (node_index, node) in graph.nodes.iter_mut().enumerate() {
+        for index in 1..node.inputs.len() {
+            let input = &mut node.inputs[index];
+            if input.value.is_none() && reads_value(&node.node_type, index) {
+                if let Some(value) = constants.get(&input.name) {
+                    let arg = constant_argument(value, elem_type(&input.ty));
+                    input.ty = arg.ty;
+                    input.value = arg.value;
+                    lifted.insert(node_index);
+                }
+            }
+        }
+    }
+
+    // The folded shapes still used as variables are computed at inference
+    let mut folded = folded.into_iter().collect::<HashSet<_>>();
+    for index in (0..graph.nodes.len()).rev() {
+        let node = &graph.nodes[index];
+        if !folded.contains(&index) || !matches!(node.outputs[0].ty, ArgType::Shape(_)) {
+            continue;
+        }
+
+        let name = &node.outputs[0].name;
+        let used_by_node = graph.nodes[index + 1..]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !folded.contains(&(index + 1 + i)))
+            .any(|(_, consumer)| {
+                consumer
+                    .inputs
+                    .iter()
+                    .enumerate()
+                    .any(|(i, input)| &input.name == name && !is_lifted(consumer, i))
+            });
+        let is_output = graph.outputs.iter().any(|output| &output.name == name);
+
+        if used_by_node || is_output {
+            folded.remove(&index);
+        }
+    }
+
+    let mut types = HashMap::new();
+    for index in folded {
+        let node = &mut graph.nodes[index];
+        let value = &constants[&node.outputs[0].name];
+        let tensor = constant_tensor(value, elem_type(&node.outputs[0].ty));
+        let ty = Argument::from(AttributeValue::Tensor(tensor.clone())).ty;
+
+        node.node_type = NodeType::Constant;
+        node.inputs.clear();
+        node.attrs.clear();
+        node.attrs
+            .insert("value".to_string(), AttributeValue::Tensor(tensor));
+        node.outputs[0].ty = ty.clone();
+        types.insert(node.outputs[0].name.clone(), ty);
+    }
+
+    // The variables computed by the constants have the type of their value, and the outputs of
+    // the nodes reading them are inferred again, the lifted values giving the shapes of the
+    // outputs that were unknown while parsing
+    for (index, node) in graph.nodes.iter_mut().enumerate() {
+        let mut changed = lifted.contains(&index);
+        for input in node.inputs.iter_mut() {
+            if let Some(ty) = types.get(&input.name) {
+                input.ty = ty.clone();
+                changed = true;
+            }
+        }
+        if !changed || node.node_type == NodeType::Constant {
+            continue;
+        }
+
+        let previous = node
+            .outputs
+            .iter()
+            .map(|output| output.ty.clone())
+            .collect::<Vec<_>>();
+        dim_inference(node);
+        for (output, previous) in node.outputs.iter().zip(previous) {
+            if !same_type(&output.ty, &previous) {
+                types.insert(output.name.clone(), output.ty.clone());
+            }
+        }
+    }
+    for output in graph.outputs.iter_mut() {
+        if let Some(ty) = types.get(&output.name) {
+            output.ty = ty.clone();
+        }
+    }
+}
+
+/// Evaluate the node if its output is an integer value computed from known values.
+fn evaluate(node: &Node, constants: &HashMap<String, Constant>) -> Option<Constant> {
+    if node.outputs.len() != 1 {
+        return None;
+    }
+    // The known values are integers, so only a cast can change the type of the output. The output
+    // type of the other nodes isn't checked, it isn't always inferred for the shape computations.
+    let is_integer_output = match &node.outputs[0].ty {
+        ArgType::Shape(_) => true,
+        ty => is_integer(ty.elem_type()),
+    };
+    if node.node_type == NodeType::Cast && !is_integer_output {
+        return None;
+    }
+
+    if node.node_type == NodeType::Shape {
+        return shape(node);
+    }
+
+    let inputs = node
+        .inputs
+        .iter()
+        .map(|input| {
+            constants
+                .get(&input.name)
+                .cloned()
+                .or_else(|| known_value(input))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match node.node_type {
+        NodeType::Identity | NodeType::Cast => inputs.into_iter().next(),
+        NodeType::Gather => gather(node, &inputs),
+        NodeType::Unsqueeze => unsqueeze(node, &inputs),
+        NodeType::Squeeze => squeeze(node, &inputs),
+        NodeType::Concat => concat(node, &inputs),
+        NodeType::Add => binary(&inputs, i64::checked_add),
+        NodeType::Sub => binary(&inputs, i64::checked_sub),
+        NodeType::Mul => binary(&inputs, i64::checked_mul),
+        // The integer division rounds toward zero or toward negative infinity depending on the
+        // runtime, only the non-negative values are folded
+        NodeType::Div => binary(&inputs, |lhs, rhs| (lhs >= 0 && rhs > 0).then(|| lhs / rhs)),
+        _ => None,
+    }
+}
+
+/// The shape of the input, only known when every dim is static.
+fn shape(node: &Node) -> Option<Constant> {
+    let ArgType::Tensor(tensor) = &node.inputs[0].ty else {
+        return None;
+    };
+    // A symbolic dim leaves the whole shape unknown
+    let shape = tensor
+        .shape
+        .as_ref()
+        .filter(|shape| shape.len() == tensor.dim)?;
+    let (start, end) = shape_config(node);
+
+    Some(Constant {
+        values: shape[start..end].iter().map(|dim| *dim as i64).collect(),
+        shape: vec![end - start],
+    })
+}
+
+fn gather(node: &Node, inputs: &[Constant]) -> Option<Constant> {
+    let (data, indices) = (&inputs[0], &inputs[1]);
+    let axis = match node.attrs.get("axis") {
+        Some(AttributeValue::Int64(axis)) => *axis,
+        _ => 0,
+    };
+    if data.shape.len() != 1 || !(axis == 0 || axis == -1) || indices.shape.len() > 1 {
+        return None;
+    }
+
+    let len = data.values.len() as i64;
+    let values = indices
+        .values
+        .iter()
+        .map(|index| {
+            let index = if *index < 0 { index + len } else { *index };
+            data.values.get(usize::try_from(index).ok()?).copied()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Constant {
+        values,
+        shape: indices.shape.clone(),
+    })
+}
+
+fn unsqueeze(node: &Node, inputs: &[Constant]) -> Option<Constant> {
+    let data = &inputs[0];
+    let axes = axes(node, inputs)?;
+    let rank = data.shape.len() + axes.len();
+
+    let mut axes = normalize_axes(&axes, rank)?;
+    axes.sort_unstable();
+    let mut shape = data.shape.clone();
+    for axis in axes {
+        if axis > shape.len() {
+            return None;
+        }
+        shape.insert(axis, 1);
+    }
+
+    Some(Constant {
+        values: data.values.clone(),
+        shape,
+    })
+}
+
+fn squeeze(node: &Node, inputs: &[Constant]) -> Option<Constant> {
+    let data = &inputs[0];
+    let axes = match axes(node, inputs) {
+        Some(axes) => normalize_axes(&axes, data.shape.len())?,
+        None => (0..data.shape.len())
+            .filter(|axis| data.shape[*axis] == 1)
+            .collect(),
+    };
+    if axes.iter().any(|axis| data.shape[*axis] != 1) {
+        return None;
+    }
+
+    let shape = data
+        .shape
+        .iter()
+        .enumerate()
+        .filter(|(axis, _)| !axes.contains(axis))
+        .map(|(_, dim)| *dim)
+        .collect();
+
+    Some(Constant {
+        values: data.values.clone(),
+        shape,
+    })
+}
+
+fn concat(node: &Node, inputs: &[Constant]) -> Option<Constant> {
+    let axis = match node.attrs.get("axis") {
+        Some(AttributeValue::Int64(axis)) => *axis,
+        _ => return None,
+    };
+    if !(axis == 0 || axis == -1) || inputs.iter().any(|input| input.shape.len() != 1) {
+        return None;
+    }
+
+    let values = inputs
+        .iter()
+        .flat_map(|input| input.values.iter().copied())
+        .collect::<Vec<_>>();
+
+    Some(Constant {
+        shape: vec![values.len()],
+        values,
+    })
+}
+
+/// An elementwise operation, where one of the values can be broadcast if it has a single element.
+fn binary(inputs: &[Constant], op: impl Fn(i64, i64) -> Option<i64>) -> Option<Constant> {
+    let (lhs, rhs) = (&inputs[0], &inputs[1]);
+    let shape = match (lhs.values.len(), rhs.values.len()) {
+        _ if lhs.shape == rhs.shape => lhs.shape.clone(),
+        (1, 1) if lhs.shape.len() >= rhs.shape.len() => lhs.shape.clone(),
+        (1, _) => rhs.shape.clone(),
+        (_, 1) => lhs.shape.clone(),
+        _ => return None,
+    };
+
+    let len = lhs.values.len().max(rhs.values.len());
+    let values = (0..len)
+        .map(|i| {
+            let lhs = lhs.values[i % lhs.values.len()];
+            let rhs = rhs.values[i % rhs.values.len()];
+            op(lhs, rhs)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Constant { values, shape })
+}
+
+/// The axes of an `Unsqueeze` or `Squeeze` node, given as an input or as an attribute.
+fn axes(node: &Node, inputs: &[Constant]) -> Option<Vec<i64>> {
+    match inputs.get(1) {
+        Some(axes) => Some(axes.values.clone()),
+        None => match node.attrs.get("axes") {
+            Some(AttributeValue::Int64s(axes)) => Some(axes.clone()),
+            _ => None,
+        },
+    }
+}
+
+fn normalize_axes(axes: &[i64], rank: usize) -> Option<Vec<usize>> {
+    axes.iter()
+        .map(|axis| {
+            let axis = if *axis < 0 { axis + rank as i64 } else { *axis };
+            usize::try_from(axis).ok().filter(|axis| *axis < rank)
+        })
+        .collect()
+}
+
+/// The value of an argument holding integers with a known shape.
+fn known_value(arg: &Argument) -> Option<Constant> {
+    let shape = match &arg.ty {
+        ArgType::Scalar(_) => Vec::new(),
+        ArgType::Tensor(tensor) => tensor.shape.clone()?,
+        ArgType::Shape(_) => return None,
+    };
+    let values = match arg.value.as_ref()? {
+        Data::Int32(value) => vec![*value as i64],
+        Data::Int64(value) => vec![*value],
+        Data::Int32s(values) => values.iter().map(|value| *value as i64).collect(),
+        Data::Int64s(values) => values.clone(),
+        _ => return None,
+    };
+
+    Some(Constant { values, shape })
+}
+
+fn constant_tensor(value: &Constant, elem_type: ElementType) -> Tensor {
+    let data = match elem_type {
+        ElementType::Int32 => {
+            Data::Int32s(value.values.iter().map(|value| *value as i32).collect())
+        }
+        _ => Data::Int64s(value.values.clone()),
+    };
+
+    Tensor {
+        elem_type,
+        dim: value.shape.len(),
+        data: Some(data),
+        shape: Some(value.shape.clone()),
+    }
+}
+
+/// The argument of a constant input, a scalar if the value has no dimension.
+fn constant_argument(value: &Constant, elem_type: ElementType) -> Argument {
+    Argument::from(AttributeValue::Tensor(constant_tensor(value, elem_type)))
+}
+
+/// The element type of an integer argument, the shapes being `int64`.
+fn elem_type(ty: &ArgType) -> ElementType {
+    match ty {
+        ArgType::Shape(_) => ElementType::Int64,
+        ty => ty.elem_type().clone(),
+    }
+}
+
+/// True if the types have the same rank and element type.
+fn same_type(lhs: &ArgType, rhs: &ArgType) -> bool {
+    match (lhs, rhs) {
+        (ArgType::Scalar(lhs), ArgType::Scalar(rhs)) => lhs == rhs,
+        (ArgType::Shape(_), ArgType::Shape(_)) => true,
+        (ArgType::Tensor(lhs), ArgType::Tensor(rhs)) => {
+            lhs.dim == rhs.dim && lhs.elem_type == rhs.elem_type
+        }
+        _ => false,
+    }
+}
+
+fn is_integer(elem_type: &ElementType) -> bool {
+    matches!(elem_type, ElementType::Int32 | ElementType::Int64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::TensorType,
+        passes::tests::{constant, node, variable},
+    };
+
+    /// `Reshape(x, Concat(Unsqueeze(Gather(Shape(x), 0)), [-1]))`, flattening all but the first dim.
+    fn reshape_graph(input: Argument) -> OnnxGraph {
+        let int64 = |name: &str, dim: usize| Argument {
+            ty: ArgType::Tensor(TensorType {
+                elem_type: ElementType::Int64,
+                dim,
+                shape: None,
+            }),
+            ..variable(name, dim, None)
+        };
+        let mut concat = node(
+            NodeType::Concat,
+            "concat1",
+            vec![
+                int64("unsqueezed", 1),
+                constant("last", ElementType::Int64, Data::Int64s(vec![-1]), vec![1]),
+            ],
+            vec![int64("target", 1)],
+        );
+        concat
+            .attrs
+            .insert("axis".to_string(), AttributeValue::Int64(0));
+
+        OnnxGraph {
+            nodes: vec![
+                node(
+                    NodeType::Shape,
+                    "shape1",
+                    vec![input.clone()],
+                    vec![Argument {
+                        ty: ArgType::Shape(3),
+                        ..variable("shape", 1, None)
+                    }],
+                ),
+                node(
+                    NodeType::Gather,
+                    "gather1",
+                    vec![
+                        Argument {
+                            ty: ArgType::Shape(3),
+                            ..variable("shape", 1, None)
+                        },
+                        constant("index", ElementType::Int64, Data::Int64(0), vec![]),
+                    ],
+                    vec![Argument {
+                        ty: ArgType::Scalar(ElementType::Int64),
+                        ..variable("batch", 0, None)
+                    }],
+                ),
+                node(
+                    NodeType::Unsqueeze,
+                    "unsqueeze1",
+                    vec![
+                        Argument {
+                            ty: ArgType::Scalar(ElementType::Int64),
+                            ..variable("batch", 0, None)
+                        },
+                        constant("axes", ElementType::Int64, Data::Int64s(vec![0]), vec![1]),
+                    ],
+                    vec![int64("unsqueezed", 1)],
+                ),
+                concat,
+                node(
+                    NodeType::Reshape,
+                    "reshape1",
+                    vec![input.clone(), int64("target", 1)],
+                    vec![variable("y", 2, None)],
+                ),
+            ],
+            inputs: vec![input],
+            outputs: vec![variable("y", 2, None)],
+        }
+    }
+
+    fn node_types(graph: &OnnxGraph) -> Vec<NodeType> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| node.node_type.clone())
+            .collect()
+    }
+
+    #[test]
+    fn fold_constants_should_compute_the_target_shape() {
+        let mut graph = reshape_graph(variable("x", 3, Some(vec![2, 3, 4])));
+
+        fold_constants(&mut graph);
+
+        let reshape = &graph.nodes[4];
+        assert_eq!(
+            reshape.inputs[1].value.clone().map(Data::into_i64s),
+            Some(vec![2, -1])
+        );
+        assert_eq!(reshape.outputs[0].ty.rank(), 2);
+        assert!(node_types(&graph)[..4]
+            .iter()
+            .all(|node_type| *node_type == NodeType::Constant));
+    }
+
+    #[test]
+    fn fold_constants_should_keep_the_shape_of_symbolic_dims() {
+        // The shape is unknown when a dim is symbolic
+        let mut graph = reshape_graph(variable("x", 3, None));
+
+        fold_constants(&mut graph);
+
+        assert!(graph.nodes[4].inputs[1].value.is_none());
+        assert_eq!(
+            node_types(&graph),
+            vec![
+                NodeType::Shape,
+                NodeType::Gather,
+                NodeType::Unsqueeze,
+                NodeType::Concat,
+                NodeType::Reshape
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_should_broadcast_a_single_value() {
+        let lhs = Constant {
+            values: vec![2, 3, 4],
+            shape: vec![3],
+        };
+        let rhs = Constant {
+            values: vec![2],
+            shape: vec![],
+        };
+
+        let output = binary(&[lhs.clone(), rhs.clone()], i64::checked_mul).unwrap();
+        assert_eq!(output.values, vec![4, 6, 8]);
+        assert_eq!(output.shape, vec![3]);
+
+        let output = binary(&[rhs, lhs], i64::checked_sub).unwrap();
+        assert_eq!(output.values, vec![0, -1, -2]);
+    }
+
+    #[test]
+    fn binary_should_not_fold_an_overflow() {
+        let values = Constant {
+            values: vec![i64::MAX],
+            shape: vec![1],
+        };
+
+        assert!(binary(&[values.clone(), values], i64::checked_add).is_none());
+    }
+
+    #[test]
+    fn gather_should_wrap_negative_indices() {
+        let data = Constant {
+            values: vec![2, 3, 4],
+            shape: vec![3],
+        };
+        let indices = Constant {
+            values: vec![-1, 0],
+            shape: vec![2],
+        };
+        let node = node(NodeType::Gather, "gather1", vec![], vec![]);
+
+        let output = gather(&node, &[data, indices]).unwrap();
+
+        assert_eq!(output.values, vec![4, 2]);
+        assert_eq!(output.shape, vec![2]);
+    }
+}
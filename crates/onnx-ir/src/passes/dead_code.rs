@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use crate::ir::OnnxGraph;
+
+use super::is_lifted;
+
+/// Remove the nodes whose outputs aren't used by the other nodes nor returned by the graph.
+///
+/// The inputs lifted as values don't use the node computing them, so the constants only read
+/// through their value are removed as well.
+pub(crate) fn eliminate_dead_code(graph: &mut OnnxGraph) {
+    let mut used = graph
+        .outputs
+        .iter()
+        .map(|output| output.name.clone())
+        .collect::<HashSet<_>>();
+
+    let nodes = std::mem::take(&mut graph.nodes);
+    let mut kept = Vec::with_capacity(nodes.len());
+    for node in nodes.into_iter().rev() {
+        if !node
+            .outputs
+            .iter()
+            .any(|output| used.contains(&output.name))
+        {
+            log::debug!("removing unused {:?} node {}", node.node_type, node.name);
+            continue;
+        }
+
+        for (index, input) in node.inputs.iter().enumerate() {
+            if !is_lifted(&node, index) {
+                used.insert(input.name.clone());
+            }
+        }
+        kept.push(node);
+    }
+
+    kept.reverse();
+    graph.nodes = kept;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{Data, ElementType, NodeType},
+        passes::tests::{constant, node, variable},
+    };
+
+    #[test]
+    fn eliminate_dead_code_should_remove_the_unused_nodes() {
+        let shape = constant("shape", ElementType::Int64, Data::Int64s(vec![-1]), vec![1]);
+        let mut graph = OnnxGraph {
+            nodes: vec![
+                // Only read through the value lifted into the reshape
+                node(NodeType::Constant, "constant1", vec![], vec![shape.clone()]),
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![variable("x", 2, None)],
+                    vec![variable("unused", 2, None)],
+                ),
+                node(
+                    NodeType::Reshape,
+                    "reshape1",
+                    vec![variable("x", 2, None), shape],
+                    vec![variable("y", 1, None)],
+                ),
+            ],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 1, None)],
+        };
+
+        eliminate_dead_code(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 1);
+        assert_eq!(graph.nodes[0].name, "reshape1");
+    }
+
+    #[test]
+    fn eliminate_dead_code_should_keep_the_variables_used_by_the_outputs() {
+        let mut graph = OnnxGraph {
+            nodes: vec![
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![variable("x", 2, None)],
+                    vec![variable("a", 2, None)],
+                ),
+                node(
+                    NodeType::Add,
+                    "add1",
+                    vec![variable("a", 2, None), variable("x", 2, None)],
+                    vec![variable("y", 2, None)],
+                ),
+            ],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 2, None)],
+        };
+
+        eliminate_dead_code(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 2);
+    }
+}
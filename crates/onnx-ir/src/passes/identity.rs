@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::{Argument, AttributeValue, Node, NodeType, OnnxGraph};
+
+use super::referenced_names;
+
+/// Remove the `Identity` and `Dropout` nodes, their outputs being replaced by their input.
+///
+/// The `Identity` nodes of variables are already removed while parsing, but the ones created by
+/// other rewrites and the `Dropout` nodes are kept. A `Dropout` node whose mask is used is kept,
+/// as well as a node returning a graph input, since the graph would have no node computing it.
+pub(crate) fn remove_identity(graph: &mut OnnxGraph) {
+    let mut referenced = HashSet::new();
+    referenced_names(&graph.nodes, &mut referenced);
+    referenced.extend(graph.outputs.iter().map(|output| output.name.clone()));
+
+    let graph_inputs = graph
+        .inputs
+        .iter()
+        .map(|input| input.name.as_str())
+        .collect::<HashSet<_>>();
+    let graph_outputs = graph
+        .outputs
+        .iter()
+        .map(|output| output.name.clone())
+        .collect::<HashSet<_>>();
+
+    let mut replaced = HashMap::new();
+    let nodes = std::mem::take(&mut graph.nodes);
+    for mut node in nodes {
+        replace_inputs(&mut node, &replaced);
+
+        if is_removable(&node, &referenced)
+            && !(graph_outputs.contains(&node.outputs[0].name)
+                && graph_inputs.contains(node.inputs[0].name.as_str()))
+        {
+            log::debug!("removing {:?} node {}", node.node_type, node.name);
+            replaced.insert(node.outputs[0].name.clone(), node.inputs[0].clone());
+        } else {
+            graph.nodes.push(node);
+        }
+    }
+
+    for output in graph.outputs.iter_mut() {
+        if let Some(input) = replaced.get(&output.name) {
+            *output = input.clone();
+        }
+    }
+}
+
+fn is_removable(node: &Node, referenced: &HashSet<String>) -> bool {
+    match node.node_type {
+        NodeType::Identity => node.inputs[0].value.is_none(),
+        NodeType::Dropout => !matches!(
+            node.outputs.get(1),
+            Some(mask) if !mask.name.is_empty() && referenced.contains(&mask.name)
+        ),
+        _ => false,
+    }
+}
+
+/// Replace the inputs of the node and of its subgraphs by the inputs of the removed nodes.
+fn replace_inputs(node: &mut Node, replaced: &HashMap<String, Argument>) {
+    for input in node.inputs.iter_mut() {
+        if let Some(replacement) = replaced.get(&input.name) {
+            input.name.clone_from(&replacement.name);
+            input.ty = replacement.ty.clone();
+        }
+    }
+
+    for attr in node.attrs.values_mut() {
+        if let AttributeValue::Graph(subgraph) = attr {
+            for node in subgraph.nodes.iter_mut() {
+                replace_inputs(node, replaced);
+            }
+            for output in subgraph.outputs.iter_mut() {
+                if let Some(replacement) = replaced.get(&output.name) {
+                    output.name.clone_from(&replacement.name);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::tests::{node, variable};
+
+    fn relu_identity(identity: Node) -> OnnxGraph {
+        OnnxGraph {
+            nodes: vec![
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![variable("x", 2, None)],
+                    vec![variable("a", 2, None)],
+                ),
+                identity,
+                node(
+                    NodeType::Relu,
+                    "relu2",
+                    vec![variable("b", 2, None)],
+                    vec![variable("y", 2, None)],
+                ),
+            ],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 2, None), variable("b", 2, None)],
+        }
+    }
+
+    #[test]
+    fn remove_identity_should_replace_the_output_by_the_input() {
+        let identity = node(
+            NodeType::Identity,
+            "identity1",
+            vec![variable("a", 2, None)],
+            vec![variable("b", 2, None)],
+        );
+        let mut graph = relu_identity(identity);
+
+        remove_identity(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.nodes[1].inputs[0].name, "a");
+        assert_eq!(graph.outputs[1].name, "a");
+    }
+
+    #[test]
+    fn remove_identity_should_keep_the_dropout_with_a_used_mask() {
+        let dropout = node(
+            NodeType::Dropout,
+            "dropout1",
+            vec![variable("a", 2, None)],
+            vec![variable("b", 2, None), variable("mask", 2, None)],
+        );
+        let mut graph = relu_identity(dropout);
+        graph.outputs.push(variable("mask", 2, None));
+
+        remove_identity(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.nodes[1].node_type, NodeType::Dropout);
+    }
+
+    #[test]
+    fn remove_identity_should_keep_the_graph_input_returned_as_output() {
+        let mut graph = OnnxGraph {
+            nodes: vec![node(
+                NodeType::Identity,
+                "identity1",
+                vec![variable("x", 2, None)],
+                vec![variable("y", 2, None)],
+            )],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 2, None)],
+        };
+
+        remove_identity(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 1);
+        assert_eq!(graph.outputs[0].name, "y");
+    }
+}
@@ -0,0 +1,253 @@
+mod batch_norm;
+mod constant_folding;
+mod dead_code;
+mod identity;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    from_onnx::LIFT_CONSTANTS_FOR_NODE_TYPES,
+    ir::{AttributeValue, Node, NodeType, OnnxGraph},
+};
+
+/// The optimization passes applied to a parsed graph before code generation.
+///
+/// All the passes are enabled by default. They are applied to the main graph, the subgraphs of
+/// the control flow nodes are left untouched.
+#[derive(Debug, Clone)]
+pub struct GraphPasses {
+    /// Evaluate the nodes computing integer values from constants and statically known shapes,
+    /// such as the `Shape -> Gather -> Unsqueeze -> Concat` chains computing a target shape.
+    ///
+    /// The static shapes of the graph inputs declared in the model are assumed to be the ones used
+    /// at inference, the shapes with symbolic dims are left to the inference.
+    pub constant_folding: bool,
+    /// Fold the `BatchNormalization` nodes into the weights of the preceding `Conv` or `Linear`
+    /// node.
+    pub fuse_batch_norm: bool,
+    /// Remove the `Identity` and `Dropout` nodes, which don't change their input at inference.
+    pub remove_identity: bool,
+    /// Remove the nodes whose outputs aren't used, such as unused constants.
+    pub dead_code_elimination: bool,
+}
+
+impl Default for GraphPasses {
+    fn default() -> Self {
+        Self {
+            constant_folding: true,
+            fuse_batch_norm: true,
+            remove_identity: true,
+            dead_code_elimination: true,
+        }
+    }
+}
+
+impl GraphPasses {
+    /// No optimization pass, the graph is kept as parsed.
+    pub fn none() -> Self {
+        Self {
+            constant_folding: false,
+            fuse_batch_norm: false,
+            remove_identity: false,
+            dead_code_elimination: false,
+        }
+    }
+
+    /// Apply the enabled passes to the graph.
+    pub fn run(&self, graph: &mut OnnxGraph) {
+        if self.remove_identity {
+            identity::remove_identity(graph);
+        }
+        if self.constant_folding {
+            constant_folding::fold_constants(graph);
+        }
+        if self.fuse_batch_norm {
+            batch_norm::fuse_batch_norm(graph);
+        }
+        if self.dead_code_elimination {
+            dead_code::eliminate_dead_code(graph);
+        }
+    }
+}
+
+/// True if the input of the node is read from its value rather than from the variable computing
+/// it, like the constants lifted while parsing.
+fn is_lifted(node: &Node, index: usize) -> bool {
+    node.inputs[index].value.is_some() && reads_value(&node.node_type, index)
+}
+
+/// True if the input of a node of this type is read from its value when it is a constant.
+fn reads_value(node_type: &NodeType, index: usize) -> bool {
+    index > 0 && LIFT_CONSTANTS_FOR_NODE_TYPES.contains(node_type)
+}
+
+/// Count the uses of each variable by the nodes and the graph outputs.
+fn variable_uses(graph: &OnnxGraph) -> HashMap<String, usize> {
+    let mut uses = HashMap::new();
+    let inputs = graph.nodes.iter().flat_map(|node| {
+        node.inputs
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !is_lifted(node, *index))
+            .map(|(_, input)| input)
+    });
+
+    for arg in inputs.chain(graph.outputs.iter()) {
+        if !arg.name.is_empty() {
+            *uses.entry(arg.name.clone()).or_insert(0) += 1;
+        }
+    }
+    uses
+}
+
+/// The names referenced by the nodes, including the nodes of the subgraphs.
+fn referenced_names(nodes: &[Node], names: &mut HashSet<String>) {
+    for node in nodes {
+        names.extend(node.inputs.iter().map(|input| input.name.clone()));
+        for attr in node.attrs.values() {
+            if let AttributeValue::Graph(subgraph) = attr {
+                names.extend(subgraph.outputs.iter().map(|output| output.name.clone()));
+                referenced_names(&subgraph.nodes, names);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::ir::{ArgType, Argument, Attributes, Data, ElementType, TensorType};
+
+    /// A tensor computed at inference, with its shape if it is known.
+    pub(crate) fn variable(name: &str, dim: usize, shape: Option<Vec<usize>>) -> Argument {
+        Argument {
+            name: name.to_string(),
+            ty: ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                dim,
+                shape,
+            }),
+            value: None,
+            passed: true,
+        }
+    }
+
+    /// A constant tensor, or a scalar if the shape is empty.
+    pub(crate) fn constant(
+        name: &str,
+        elem_type: ElementType,
+        value: Data,
+        shape: Vec<usize>,
+    ) -> Argument {
+        let ty = match shape.is_empty() {
+            true => ArgType::Scalar(elem_type),
+            false => ArgType::Tensor(TensorType {
+                elem_type,
+                dim: shape.len(),
+                shape: Some(shape),
+            }),
+        };
+
+        Argument {
+            name: name.to_string(),
+            ty,
+            value: Some(value),
+            passed: false,
+        }
+    }
+
+    pub(crate) fn node(
+        node_type: NodeType,
+        name: &str,
+        inputs: Vec<Argument>,
+        outputs: Vec<Argument>,
+    ) -> Node {
+        Node {
+            node_type,
+            name: name.to_string(),
+            inputs,
+            outputs,
+            attrs: Attributes::new(),
+        }
+    }
+
+    /// `x -> Relu -> Identity -> Dropout -> y`, with an unused constant.
+    fn graph() -> OnnxGraph {
+        let unused = constant("unused", ElementType::Int64, Data::Int64s(vec![1]), vec![1]);
+        let mut constant1 = node(
+            NodeType::Constant,
+            "constant1",
+            vec![],
+            vec![unused.clone()],
+        );
+        let value = unused.into_tensor().unwrap();
+        constant1
+            .attrs
+            .insert("value".to_string(), AttributeValue::Tensor(value));
+
+        OnnxGraph {
+            nodes: vec![
+                constant1,
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![variable("x", 2, None)],
+                    vec![variable("a", 2, None)],
+                ),
+                node(
+                    NodeType::Identity,
+                    "identity1",
+                    vec![variable("a", 2, None)],
+                    vec![variable("b", 2, None)],
+                ),
+                node(
+                    NodeType::Dropout,
+                    "dropout1",
+                    vec![variable("b", 2, None)],
+                    vec![variable("y", 2, None)],
+                ),
+            ],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 2, None)],
+        }
+    }
+
+    #[test]
+    fn run_should_apply_the_enabled_passes() {
+        let mut graph = graph();
+        GraphPasses::default().run(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 1);
+        assert_eq!(graph.nodes[0].node_type, NodeType::Relu);
+        assert_eq!(graph.outputs[0].name, "a");
+    }
+
+    #[test]
+    fn run_should_keep_the_graph_without_passes() {
+        let mut graph = graph();
+        GraphPasses::none().run(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.outputs[0].name, "y");
+    }
+
+    #[test]
+    fn variable_uses_should_skip_the_lifted_inputs() {
+        let shape = constant("shape", ElementType::Int64, Data::Int64s(vec![-1]), vec![1]);
+        let graph = OnnxGraph {
+            nodes: vec![node(
+                NodeType::Reshape,
+                "reshape1",
+                vec![variable("x", 2, None), shape],
+                vec![variable("y", 1, None)],
+            )],
+            inputs: vec![variable("x", 2, None)],
+            outputs: vec![variable("y", 1, None)],
+        };
+        let uses = variable_uses(&graph);
+
+        assert_eq!(uses.get("x"), Some(&1));
+        assert_eq!(uses.get("y"), Some(&1));
+        assert_eq!(uses.get("shape"), None);
+    }
+}
@@ -3,13 +3,13 @@ use std::str::{from_utf8, FromStr};
 use crate::ir::TensorType;
 
 use super::from_onnx::GraphData;
-use super::ir::Dim;
 use super::ir::{
-    ArgType, Argument, AttributeValue, Attributes, Data, ElementType, Node, NodeType, Tensor,
+    ArgType, Argument, AttributeValue, Attributes, Data, Dim, ElementType, Node, NodeType, Shape,
+    Tensor,
 };
 use super::protos::{
-    attribute_proto::AttributeType, tensor_proto::DataType, tensor_shape_proto::dimension::Value,
-    type_proto, AttributeProto, NodeProto, TensorProto, TensorShapeProto, ValueInfoProto,
+    attribute_proto::AttributeType, tensor_proto::DataType, type_proto, AttributeProto, NodeProto,
+    TensorProto, TensorShapeProto, ValueInfoProto,
 };
 
 use bytemuck::cast_slice;
@@ -70,6 +70,23 @@ impl TryFrom<TensorProto> for Tensor {
                 assert!(!tensor.raw_data.is_empty());
                 Data::Bools(tensor.raw_data.iter().map(|x| *x != 0).collect())
             }),
+            DataType::INT8 => (
+                ElementType::Int8,
+                // The values are stored as int32 when there is no raw data
+                if !tensor.raw_data.is_empty() {
+                    Data::Int8s(cast_slice(&tensor.raw_data[..]).to_vec())
+                } else {
+                    Data::Int8s(tensor.int32_data.iter().map(|x| *x as i8).collect())
+                },
+            ),
+            DataType::UINT8 => (
+                ElementType::Uint8,
+                if !tensor.raw_data.is_empty() {
+                    Data::Uint8s(tensor.raw_data.to_vec())
+                } else {
+                    Data::Uint8s(tensor.int32_data.iter().map(|x| *x as u8).collect())
+                },
+            ),
             // TODO : Add more types
             _ => {
                 return Err(ParseError::VariantNotFound);
@@ -86,19 +103,13 @@ impl TryFrom<TensorProto> for Tensor {
     }
 }
 
-impl TryFrom<TensorShapeProto> for Vec<usize> {
-    type Error = ParseError;
-    fn try_from(shape: TensorShapeProto) -> Result<Vec<usize>, Self::Error> {
-        let mut result = Vec::new();
-
-        for dim in shape.dim {
-            if let Value::DimValue(value) = dim.value.unwrap() {
-                result.push(value as usize);
-            }
-        }
-
-        Ok(result)
-    }
+/// The shape if every dim has a value, or `None` when a dim is symbolic or unknown.
+fn static_shape(shape: &TensorShapeProto) -> Option<Shape> {
+    shape
+        .dim
+        .iter()
+        .map(|dim| dim.has_dim_value().then(|| dim.dim_value() as Dim))
+        .collect()
 }
 
 /// Convert a vector of AttributeProto to a HashMap of AttributeValue
@@ -111,6 +122,8 @@ impl TryFrom<&type_proto::Tensor> for Tensor {
             DataType::INT64 => ElementType::Int64,
             DataType::DOUBLE => ElementType::Float64,
             DataType::BOOL => ElementType::Bool,
+            DataType::INT8 => ElementType::Int8,
+            DataType::UINT8 => ElementType::Uint8,
 
             // TODO : Add more types
             _ => {
@@ -119,12 +132,11 @@ impl TryFrom<&type_proto::Tensor> for Tensor {
         };
 
         let shape_proto = tensor.shape.clone().unwrap();
-        let shape: Vec<usize> = shape_proto.try_into().unwrap();
 
         Ok(Tensor {
             elem_type,
-            dim: shape.len(),
-            shape: Some(shape),
+            dim: shape_proto.dim.len(),
+            shape: static_shape(&shape_proto),
             data: None,
         })
     }
@@ -151,8 +163,7 @@ impl TryFrom<AttributeProto> for AttributeValue {
             // warning: tensor can be empty TODO: check if it is empty
             AttributeType::TENSOR => AttributeValue::Tensor(Tensor::try_from(attr.t.unwrap())?),
 
-            // Graph is not supported for now
-            // AttributeType::GRAPH => AttributeValue::Graph(attr.g),
+            // Graphs are converted by the graph builder, see `OnnxGraphBuilder::handle_subgraphs`
             AttributeType::FLOATS => AttributeValue::Float32s(attr.floats),
             AttributeType::INTS => AttributeValue::Int64s(attr.ints),
             AttributeType::STRINGS => AttributeValue::Strings(to_string_vec(attr.strings)),
@@ -175,6 +186,10 @@ impl TryFrom<AttributeProto> for AttributeValue {
 pub fn convert_vec_attrs_proto(attrs: Vec<AttributeProto>) -> Attributes {
     let mut result = Attributes::new();
     for attr in attrs {
+        // Subgraphs need the enclosing graph to resolve the captured values
+        if attr.type_.unwrap() == AttributeType::GRAPH {
+            continue;
+        }
         result.insert(attr.name.clone(), AttributeValue::try_from(attr).unwrap());
     }
     result
@@ -237,6 +252,8 @@ impl TryFrom<ValueInfoProto> for Argument {
             DataType::INT64 => ElementType::Int64,
             DataType::DOUBLE => ElementType::Float64,
             DataType::BOOL => ElementType::Bool,
+            DataType::INT8 => ElementType::Int8,
+            DataType::UINT8 => ElementType::Uint8,
             _ => {
                 return Err(ParseError::VariantNotFound);
             }
@@ -250,14 +267,8 @@ impl TryFrom<ValueInfoProto> for Argument {
             let tensor_type = TensorType {
                 dim: tensor_proto.shape.dim.len(),
                 elem_type,
-                shape: Some(
-                    tensor_proto
-                        .shape
-                        .dim
-                        .iter()
-                        .map(|x| x.dim_value() as Dim)
-                        .collect(),
-                ),
+                // The symbolic dims aren't known until the model is run
+                shape: static_shape(&tensor_proto.shape),
             };
 
             ArgType::Tensor(tensor_type)
@@ -271,3 +282,64 @@ impl TryFrom<ValueInfoProto> for Argument {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos::{tensor_shape_proto::Dimension, TypeProto};
+    use protobuf::MessageField;
+
+    fn value_info(dims: Vec<Dimension>) -> ValueInfoProto {
+        let tensor = type_proto::Tensor {
+            elem_type: DataType::FLOAT as i32,
+            shape: MessageField::some(TensorShapeProto {
+                dim: dims,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        ValueInfoProto {
+            name: "input".to_string(),
+            type_: MessageField::some(TypeProto {
+                value: Some(type_proto::Value::TensorType(tensor)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn dim_value(value: i64) -> Dimension {
+        let mut dim = Dimension::new();
+        dim.set_dim_value(value);
+        dim
+    }
+
+    fn dim_param(param: &str) -> Dimension {
+        let mut dim = Dimension::new();
+        dim.set_dim_param(param.to_string());
+        dim
+    }
+
+    #[test]
+    fn argument_should_keep_the_static_shape() {
+        let arg = Argument::try_from(value_info(vec![dim_value(2), dim_value(0)])).unwrap();
+
+        let ArgType::Tensor(tensor) = arg.ty else {
+            panic!("Expected a tensor");
+        };
+        assert_eq!(tensor.dim, 2);
+        assert_eq!(tensor.shape, Some(vec![2, 0]));
+    }
+
+    #[test]
+    fn argument_should_not_have_a_shape_with_symbolic_dims() {
+        let arg = Argument::try_from(value_info(vec![dim_param("batch"), dim_value(3)])).unwrap();
+
+        let ArgType::Tensor(tensor) = arg.ty else {
+            panic!("Expected a tensor");
+        };
+        assert_eq!(tensor.dim, 2);
+        assert_eq!(tensor.shape, None);
+    }
+}
@@ -0,0 +1,415 @@
+use crate::ir::{
+    ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, QuantizedInt8s,
+    TensorType,
+};
+
+/// The value of a `DequantizeLinear` node whose inputs are all constants, such as the quantized
+/// weights of a QDQ model.
+///
+/// The `int8` and `uint8` values with a single scale stay quantized, the other values, like the
+/// `int32` biases or the weights quantized per axis, are dequantized.
+pub(crate) fn dequantize_constant(node: &Node) -> Option<Argument> {
+    if node.node_type != NodeType::DequantizeLinear {
+        return None;
+    }
+
+    let input = &node.inputs[0];
+    let zero_point = node.inputs.get(2).filter(|arg| !arg.name.is_empty());
+    if input.value.is_none()
+        || node.inputs[1].value.is_none()
+        || zero_point.is_some_and(|arg| arg.value.is_none())
+    {
+        return None;
+    }
+    let ArgType::Tensor(tensor) = &input.ty else {
+        return None;
+    };
+    let shape = tensor.shape.clone()?;
+    let axis = match node.attrs.get("axis") {
+        Some(AttributeValue::Int64(axis)) => *axis,
+        _ => 1,
+    };
+
+    log::debug!("folding constant {} into a quantized value", node.name);
+    let value = quantize_or_dequantize(input, &node.inputs[1], zero_point, axis, &shape);
+
+    Some(Argument {
+        name: node.outputs[0].name.clone(),
+        ty: ArgType::Tensor(TensorType {
+            elem_type: ElementType::Float32,
+            dim: shape.len(),
+            shape: Some(shape),
+        }),
+        value: Some(value),
+        passed: false,
+    })
+}
+
+/// Replace the constant quantized weights of a `QLinearConv` or `QLinearMatMul` node by their
+/// quantized values, and the `int32` bias of a `QLinearConv` node by its real values.
+///
+/// The inputs are `[x, x_scale, x_zero_point, w, w_scale, w_zero_point, y_scale, y_zero_point]`,
+/// followed by the optional bias of a `QLinearConv` node.
+pub(crate) fn quantize_weights(node: &mut Node) {
+    // The weights of a convolution are quantized per output channel, the first axis, and the
+    // right hand side of a matmul per column, the last axis
+    let axis = match node.node_type {
+        NodeType::QLinearConv => 0,
+        NodeType::QLinearMatMul => -1,
+        _ => return,
+    };
+
+    let weight = &node.inputs[3];
+    let ArgType::Tensor(TensorType {
+        shape: Some(shape), ..
+    }) = &weight.ty
+    else {
+        return;
+    };
+    if weight.value.is_none() || node.inputs[4].value.is_none() || node.inputs[5].value.is_none() {
+        return;
+    }
+    let shape = shape.clone();
+
+    let value =
+        quantize_or_dequantize(weight, &node.inputs[4], Some(&node.inputs[5]), axis, &shape);
+    node.inputs[3].value = Some(value);
+    node.inputs[3].ty = float_tensor(&shape);
+
+    // The bias is quantized with the product of the input and weight scales and no zero point
+    if let Some(bias) = node.inputs.get(8).filter(|bias| bias.value.is_some()) {
+        let input_scale = node.inputs[1].value.clone().map(scalar_f32);
+        let weight_scales = node.inputs[4].value.clone().map(f32_values);
+        let (Some(Some(input_scale)), Some(weight_scales)) = (input_scale, weight_scales) else {
+            panic!("QLinearConv: the scales of a constant bias must be constants");
+        };
+
+        let values = bias.value.clone().unwrap().into_f32s();
+        let bias_len = values.len();
+        let values = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| value * input_scale * weight_scales[i % weight_scales.len()])
+            .collect();
+        node.inputs[8].value = Some(Data::Float32s(values));
+        node.inputs[8].ty = float_tensor(&[bias_len]);
+    }
+}
+
+/// The quantized values if they are `int8` or `uint8` values with a single scale, otherwise the
+/// real values.
+fn quantize_or_dequantize(
+    input: &Argument,
+    scale: &Argument,
+    zero_point: Option<&Argument>,
+    axis: i64,
+    shape: &[usize],
+) -> Data {
+    let values = input.value.as_ref().unwrap();
+    let scales = f32_values(scale.value.clone().unwrap());
+    let zero_points = zero_point
+        .and_then(|arg| arg.value.clone())
+        .map(i64_values)
+        .unwrap_or_else(|| vec![0]);
+
+    if let ([scale], [zero_point]) = (scales.as_slice(), zero_points.as_slice()) {
+        if let Some(quantized) = quantized_int8s(values, *scale, *zero_point) {
+            return Data::QuantizedInt8s(quantized);
+        }
+    }
+
+    if scales.len() > 1 {
+        log::warn!(
+            "{} is quantized per axis, which Burn doesn't support, its values are dequantized",
+            input.name
+        );
+    }
+    Data::Float32s(dequantize(
+        values.clone().into_f32s(),
+        &scales,
+        &zero_points,
+        axis,
+        shape,
+    ))
+}
+
+/// The `int8` or `uint8` values as `int8` values, the `uint8` values and zero point being shifted
+/// by 128 which represents the same real values.
+fn quantized_int8s(values: &Data, scale: f32, zero_point: i64) -> Option<QuantizedInt8s> {
+    let (values, zero_point) = match values {
+        Data::Int8s(values) => (values.clone(), zero_point as i8),
+        Data::Uint8s(values) => (
+            values
+                .iter()
+                .map(|value| (*value as i16 - 128) as i8)
+                .collect(),
+            (zero_point - 128) as i8,
+        ),
+        _ => return None,
+    };
+
+    Some(QuantizedInt8s {
+        values,
+        scale,
+        zero_point,
+    })
+}
+
+/// Dequantize the values with a scale and zero point per tensor or per index of the axis.
+fn dequantize(
+    values: Vec<f32>,
+    scales: &[f32],
+    zero_points: &[i64],
+    axis: i64,
+    shape: &[usize],
+) -> Vec<f32> {
+    let axis = if axis < 0 {
+        (axis + shape.len() as i64) as usize
+    } else {
+        axis as usize
+    };
+    let inner = shape.iter().skip(axis + 1).product::<usize>();
+    let channel = |index: usize, len: usize| match len {
+        1 => 0,
+        _ => (index / inner) % shape[axis],
+    };
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let scale = scales[channel(i, scales.len())];
+            let zero_point = zero_points[channel(i, zero_points.len())];
+            (value - zero_point as f32) * scale
+        })
+        .collect()
+}
+
+fn float_tensor(shape: &[usize]) -> ArgType {
+    ArgType::Tensor(TensorType {
+        elem_type: ElementType::Float32,
+        dim: shape.len(),
+        shape: Some(shape.to_vec()),
+    })
+}
+
+/// The value of a scalar or of a tensor with a single element.
+fn scalar_f32(value: Data) -> Option<f32> {
+    match f32_values(value).as_slice() {
+        [value] => Some(*value),
+        _ => None,
+    }
+}
+
+/// The values of a scale, given as a scalar or a tensor.
+fn f32_values(value: Data) -> Vec<f32> {
+    match value {
+        Data::Float16(_) | Data::Float32(_) | Data::Float64(_) => vec![value.into_f32()],
+        _ => value.into_f32s(),
+    }
+}
+
+/// The values of a zero point, given as a scalar or a tensor.
+fn i64_values(value: Data) -> Vec<i64> {
+    match value {
+        Data::Int8(_) | Data::Uint8(_) | Data::Int32(_) | Data::Int64(_) => {
+            vec![value.into_i64()]
+        }
+        _ => value.into_i64s(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::tests::{constant, node, variable};
+
+    #[test]
+    fn dequantize_should_use_the_scale_of_the_first_axis() {
+        // [[1, 2, 3], [4, 5, 6]] with a scale and zero point per row
+        let values = dequantize(vec![1., 2., 3., 4., 5., 6.], &[1., 2.], &[0, 4], 0, &[2, 3]);
+
+        assert_eq!(values, vec![1., 2., 3., 0., 2., 4.]);
+    }
+
+    #[test]
+    fn dequantize_should_use_the_scale_of_the_last_axis() {
+        // [[1, 2, 3], [4, 5, 6]] with a scale and zero point per column
+        let values = dequantize(
+            vec![1., 2., 3., 4., 5., 6.],
+            &[1., 2., 3.],
+            &[0, 1, 2],
+            -1,
+            &[2, 3],
+        );
+
+        assert_eq!(values, vec![1., 2., 3., 4., 8., 12.]);
+    }
+
+    #[test]
+    fn dequantize_should_use_a_single_scale_for_the_tensor() {
+        let values = dequantize(vec![1., 2., 3., 4.], &[0.5], &[2], 1, &[2, 2]);
+
+        assert_eq!(values, vec![-0.5, 0., 0.5, 1.]);
+    }
+
+    #[test]
+    fn quantized_int8s_should_shift_uint8_values() {
+        let quantized = quantized_int8s(&Data::Uint8s(vec![0, 128, 255]), 0.5, 128).unwrap();
+
+        assert_eq!(quantized.values, vec![-128, 0, 127]);
+        assert_eq!(quantized.zero_point, 0);
+        assert_eq!(quantized.dequantize(), vec![-64., 0., 63.5]);
+    }
+
+    #[test]
+    fn quantized_int8s_should_not_quantize_int32_values() {
+        assert!(quantized_int8s(&Data::Int32s(vec![1, 2]), 0.5, 0).is_none());
+    }
+
+    #[test]
+    fn dequantize_constant_should_keep_per_tensor_values_quantized() {
+        let dequantize_linear = node(
+            NodeType::DequantizeLinear,
+            "dequantizelinear1",
+            vec![
+                constant("w", ElementType::Int8, Data::Int8s(vec![-2, 0, 2]), vec![3]),
+                constant("w_scale", ElementType::Float32, Data::Float32(0.5), vec![]),
+                constant("w_zero_point", ElementType::Int8, Data::Int8(0), vec![]),
+            ],
+            vec![variable("y", 1, None)],
+        );
+
+        let output = dequantize_constant(&dequantize_linear).unwrap();
+
+        assert_eq!(output.name, "y");
+        assert!(matches!(output.value, Some(Data::QuantizedInt8s(_))));
+        assert_eq!(output.value.unwrap().into_f32s(), vec![-1., 0., 1.]);
+    }
+
+    #[test]
+    fn dequantize_constant_should_dequantize_per_axis_values() {
+        let mut dequantize_linear = node(
+            NodeType::DequantizeLinear,
+            "dequantizelinear1",
+            vec![
+                constant(
+                    "w",
+                    ElementType::Uint8,
+                    Data::Uint8s(vec![1, 2, 3, 4]),
+                    vec![2, 2],
+                ),
+                constant(
+                    "w_scale",
+                    ElementType::Float32,
+                    Data::Float32s(vec![1., 10.]),
+                    vec![2],
+                ),
+                constant(
+                    "w_zero_point",
+                    ElementType::Uint8,
+                    Data::Uint8s(vec![1, 2]),
+                    vec![2],
+                ),
+            ],
+            vec![variable("y", 2, None)],
+        );
+        dequantize_linear
+            .attrs
+            .insert("axis".to_string(), AttributeValue::Int64(0));
+
+        let output = dequantize_constant(&dequantize_linear).unwrap();
+
+        assert!(matches!(output.value, Some(Data::Float32s(_))));
+        assert_eq!(output.value.unwrap().into_f32s(), vec![0., 1., 10., 20.]);
+    }
+
+    #[test]
+    fn quantize_weights_should_dequantize_the_bias_with_the_input_and_weight_scales() {
+        let mut qlinear_conv = node(
+            NodeType::QLinearConv,
+            "qlinearconv1",
+            vec![
+                variable("x", 4, None),
+                constant("x_scale", ElementType::Float32, Data::Float32(0.5), vec![]),
+                constant("x_zero_point", ElementType::Uint8, Data::Uint8(128), vec![]),
+                constant(
+                    "w",
+                    ElementType::Int8,
+                    Data::Int8s(vec![1, 2, 3, 4]),
+                    vec![2, 2, 1, 1],
+                ),
+                constant(
+                    "w_scale",
+                    ElementType::Float32,
+                    Data::Float32s(vec![2., 4.]),
+                    vec![2],
+                ),
+                constant(
+                    "w_zero_point",
+                    ElementType::Int8,
+                    Data::Int8s(vec![0, 1]),
+                    vec![2],
+                ),
+                constant("y_scale", ElementType::Float32, Data::Float32(1.), vec![]),
+                constant("y_zero_point", ElementType::Uint8, Data::Uint8(128), vec![]),
+                constant(
+                    "b",
+                    ElementType::Int32,
+                    Data::Int32s(vec![10, -10]),
+                    vec![2],
+                ),
+            ],
+            vec![variable("y", 4, None)],
+        );
+
+        quantize_weights(&mut qlinear_conv);
+
+        // The weights are quantized per output channel, so they are dequantized
+        let weight = qlinear_conv.inputs[3].value.clone().unwrap();
+        assert_eq!(weight.into_f32s(), vec![2., 4., 8., 12.]);
+        // The bias scale of each output channel is `x_scale * w_scale`
+        let bias = qlinear_conv.inputs[8].value.clone().unwrap();
+        assert_eq!(bias.into_f32s(), vec![10., -20.]);
+        assert!(matches!(
+            qlinear_conv.inputs[8].ty,
+            ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn quantize_weights_should_keep_per_tensor_matmul_weights_quantized() {
+        let mut qlinear_matmul = node(
+            NodeType::QLinearMatMul,
+            "qlinearmatmul1",
+            vec![
+                variable("a", 2, None),
+                constant("a_scale", ElementType::Float32, Data::Float32(0.5), vec![]),
+                constant("a_zero_point", ElementType::Int8, Data::Int8(0), vec![]),
+                constant(
+                    "b",
+                    ElementType::Uint8,
+                    Data::Uint8s(vec![130, 126]),
+                    vec![2, 1],
+                ),
+                constant("b_scale", ElementType::Float32, Data::Float32(0.25), vec![]),
+                constant("b_zero_point", ElementType::Uint8, Data::Uint8(128), vec![]),
+                constant("y_scale", ElementType::Float32, Data::Float32(1.), vec![]),
+                constant("y_zero_point", ElementType::Int8, Data::Int8(0), vec![]),
+            ],
+            vec![variable("y", 2, None)],
+        );
+
+        quantize_weights(&mut qlinear_matmul);
+
+        let Some(Data::QuantizedInt8s(weight)) = qlinear_matmul.inputs[3].value.clone() else {
+            panic!("the weights should be quantized");
+        };
+        assert_eq!(weight.values, vec![2, -2]);
+        assert_eq!(weight.zero_point, 0);
+        assert_eq!(weight.dequantize(), vec![0.5, -0.5]);
+    }
+}
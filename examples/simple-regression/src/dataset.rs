@@ -111,11 +111,8 @@ impl<B: Backend> Normalizer<B> {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct HousingBatcher<B: Backend> {
-    device: B::Device,
-    normalizer: Normalizer<B>,
-}
+#[derive(Clone, Debug, Default)]
+pub struct HousingBatcher {}
 
 #[derive(Clone, Debug)]
 pub struct HousingBatch<B: Backend> {
@@ -123,17 +120,8 @@ pub struct HousingBatch<B: Backend> {
     pub targets: Tensor<B, 1>,
 }
 
-impl<B: Backend> HousingBatcher<B> {
-    pub fn new(device: B::Device) -> Self {
-        Self {
-            device: device.clone(),
-            normalizer: Normalizer::new(&device, &FEATURES_MIN, &FEATURES_MAX),
-        }
-    }
-}
-
-impl<B: Backend> Batcher<HousingDistrictItem, HousingBatch<B>> for HousingBatcher<B> {
-    fn batch(&self, items: Vec<HousingDistrictItem>) -> HousingBatch<B> {
+impl<B: Backend> Batcher<B, HousingDistrictItem, HousingBatch<B>> for HousingBatcher {
+    fn batch(&self, items: Vec<HousingDistrictItem>, device: &B::Device) -> HousingBatch<B> {
         let mut inputs: Vec<Tensor<B, 2>> = Vec::new();
 
         for item in items.iter() {
@@ -148,18 +136,19 @@ impl<B: Backend> Batcher<HousingDistrictItem, HousingBatch<B>> for HousingBatche
                     item.latitude,
                     item.longitude,
                 ],
-                &self.device,
+                device,
             );
 
             inputs.push(input_tensor.unsqueeze());
         }
 
         let inputs = Tensor::cat(inputs, 0);
-        let inputs = self.normalizer.normalize(inputs);
+        let normalizer = Normalizer::new(device, &FEATURES_MIN, &FEATURES_MAX);
+        let inputs = normalizer.normalize(inputs);
 
         let targets = items
             .iter()
-            .map(|item| Tensor::<B, 1>::from_floats([item.median_house_value], &self.device))
+            .map(|item| Tensor::<B, 1>::from_floats([item.median_house_value], device))
             .collect();
 
         let targets = Tensor::cat(targets, 0);